@@ -0,0 +1,91 @@
+//! スクリーンリーダー向けの読み上げ (aria-live)。
+//!
+//! 画面は DOM のグリッドなので支援技術からは中身が読めない。設定で
+//! 読み上げを ON にすると、[`announce`] に渡した文言 (トースト・ログの
+//! 要所) を `index.html` の `window.__announce(text)` 経由で画面外の
+//! `aria-live="polite"` ノードへ書き、読み上げさせる。
+//!
+//! [`crate::sound::play`] と同じく、呼び出し側は ON/OFF を気にせず
+//! logic のどこからでも呼んでよい。OFF の間と native (cargo test) では no-op。
+//!
+//! ON/OFF は [`crate::profile::scoped_key`] でプロファイルごとに保存する。
+
+use std::cell::Cell;
+
+#[cfg(target_arch = "wasm32")]
+use crate::profile;
+
+/// localStorage のキー。値は `"1"` / `"0"`。
+#[cfg(target_arch = "wasm32")]
+const SCREEN_READER_KEY: &str = "app_screen_reader";
+
+#[cfg(target_arch = "wasm32")]
+mod imp {
+    use wasm_bindgen::prelude::*;
+
+    #[wasm_bindgen]
+    extern "C" {
+        // index.html の `<script>` 内で `window.__announce` として定義。
+        #[wasm_bindgen(js_namespace = window, js_name = __announce, catch)]
+        fn js_announce(text: &str) -> Result<(), JsValue>;
+    }
+
+    pub fn announce(text: &str) {
+        // 未定義でも読み上げが無いだけなので無視する。
+        let _ = js_announce(text);
+    }
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+mod imp {
+    pub fn announce(_text: &str) {}
+}
+
+thread_local! {
+    static ENABLED: Cell<bool> = const { Cell::new(false) };
+}
+
+/// 読み上げが ON か。
+pub fn enabled() -> bool {
+    ENABLED.with(|e| e.get())
+}
+
+/// 読み上げが ON なら `text` を aria-live ノードへ流す。
+pub fn announce(text: &str) {
+    if enabled() {
+        imp::announce(text);
+    }
+}
+
+#[cfg(target_arch = "wasm32")]
+fn get_storage() -> Option<crate::storage::Store> {
+    crate::storage::get()
+}
+
+/// アクティブなプロファイルの設定を読み込んで有効にする。未保存なら OFF。
+/// プロファイルを切り替えたら呼び直すこと。
+pub fn load() -> bool {
+    #[cfg(target_arch = "wasm32")]
+    let on = get_storage()
+        .and_then(|s| s.get_item(&profile::scoped_key(SCREEN_READER_KEY)).ok().flatten())
+        .is_some_and(|v| v == "1");
+    #[cfg(not(target_arch = "wasm32"))]
+    let on = false;
+    ENABLED.with(|e| e.set(on));
+    on
+}
+
+/// 設定を保存して有効にする。保存に失敗しても今のセッションには効く。
+pub fn store(on: bool) {
+    ENABLED.with(|e| e.set(on));
+    #[cfg(target_arch = "wasm32")]
+    if let Some(storage) = get_storage() {
+        let value = if on { "1" } else { "0" };
+        if let Err(e) = storage.set_item(&profile::scoped_key(SCREEN_READER_KEY), value) {
+            web_sys::console::warn_1(&format!("a11y: localStorage への保存に失敗: {e:?}").into());
+        }
+    }
+    if on {
+        imp::announce("読み上げを有効にしました");
+    }
+}
@@ -0,0 +1,177 @@
+//! クリック action ID の区間と、その重なり検査。
+//!
+//! 一覧の行など「base + index」で発行する ID は [`ActionRange`] で
+//! 定義し、render 側は [`ActionRange::id`]、入力側は
+//! [`ActionRange::index`] で変換する。手書きの
+//! `(BASE..BASE + 26).contains(&id)` と `id - BASE` を書かない。
+//!
+//! 各ゲームの `actions.rs` は、単発の ID と区間をまとめて
+//! [`assert_disjoint`] に渡す:
+//!
+//! ```ignore
+//! const _: () = assert_disjoint(&[
+//!     ActionRange::single(CLICK_COOKIE),
+//!     BUY_PRODUCER,
+//!     // ...
+//! ]);
+//! ```
+//!
+//! 重なりはコンパイル時 (const 評価) のエラーになる。一覧を `pub const`
+//! にしておけば、[`end_of`] で「そのゲームが使う ID の上限」も引ける
+//! (fuzz harness がクリック ID の範囲に使う)。
+
+/// 「メニューに戻る」共通アクション ID。
+/// 各ゲームの `Clickable::new(back, BACK_TO_MENU)` から参照される。ルートの
+/// crate が再エクスポートしているので `crate::BACK_TO_MENU` でも引ける。
+pub const BACK_TO_MENU: u16 = 65535;
+
+/// 「スコアトークンを表示」共通アクション ID。プレイ中オーバーレイの
+/// ★ボタンから発行される (ルート crate の `PlayingScreen` がゲームより先に拾う)。
+pub const SHOW_SCORE: u16 = 65534;
+
+/// `base` から `len` 個の連続した action ID。
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct ActionRange {
+    base: u16,
+    len: u16,
+}
+
+impl ActionRange {
+    /// 空の区間や、共通 ID ([`BACK_TO_MENU`] / [`SHOW_SCORE`]) に
+    /// かかる区間は const 評価で弾く。
+    pub const fn new(base: u16, len: u16) -> Self {
+        assert!(len > 0, "empty action range");
+        assert!(
+            base as u32 + len as u32 <= RESERVED_FLOOR as u32,
+            "action range runs into the shared IDs"
+        );
+        Self { base, len }
+    }
+
+    /// 単発の ID を区間として扱う ([`assert_disjoint`] 用)。
+    pub const fn single(id: u16) -> Self {
+        Self::new(id, 1)
+    }
+
+    /// 先頭の ID。[`crate::widgets::ClickableGrid`] のように自前で
+    /// encode する builder に渡す。
+    pub const fn base(self) -> u16 {
+        self.base
+    }
+
+    /// 区間に含まれる ID の数。
+    pub const fn count(self) -> usize {
+        self.len as usize
+    }
+
+    /// `index` 番目の ID。区間外は隣の区間を指してしまうので debug で止める。
+    pub fn id(self, index: usize) -> u16 {
+        debug_assert!(
+            index < self.count(),
+            "action index {} out of range {:?}",
+            index,
+            self,
+        );
+        self.base + index as u16
+    }
+
+    /// `id` がこの区間に入っていれば、その index。
+    pub fn index(self, id: u16) -> Option<usize> {
+        if self.contains(id) {
+            Some((id - self.base) as usize)
+        } else {
+            None
+        }
+    }
+
+    pub const fn contains(self, id: u16) -> bool {
+        id >= self.base && id < self.end()
+    }
+
+    /// 区間の直後の ID (排他的な終端)。
+    pub const fn end(self) -> u16 {
+        self.base + self.len
+    }
+
+    pub const fn overlaps(self, other: ActionRange) -> bool {
+        self.base < other.end() && other.base < self.end()
+    }
+}
+
+/// 共通 ID のうち小さい方。ゲームの区間はこれより下に収める。
+/// 共通 ID は [`assert_disjoint`] の一覧に入れない (ここで別に弾いている)。
+const RESERVED_FLOOR: u16 = SHOW_SCORE;
+const _: () = assert!(BACK_TO_MENU > RESERVED_FLOOR, "BACK_TO_MENU must sit above the floor");
+
+/// 一覧のうち最も後ろの区間の直後の ID (= 使っている ID はすべてこれ未満)。
+/// 空なら 0。
+pub const fn end_of(ranges: &[ActionRange]) -> u16 {
+    let mut end = 0;
+    let mut i = 0;
+    while i < ranges.len() {
+        if ranges[i].end() > end {
+            end = ranges[i].end();
+        }
+        i += 1;
+    }
+    end
+}
+
+/// 1 画面 (1 スコープ) の ID がどれも重ならないことを確かめる。
+/// `const _: () = assert_disjoint(&[...]);` の形で呼ぶとコンパイル時に検査される。
+pub const fn assert_disjoint(ranges: &[ActionRange]) {
+    let mut i = 0;
+    while i < ranges.len() {
+        let mut j = i + 1;
+        while j < ranges.len() {
+            assert!(!ranges[i].overlaps(ranges[j]), "overlapping action IDs");
+            j += 1;
+        }
+        i += 1;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const LIST: ActionRange = ActionRange::new(100, 5);
+
+    #[test]
+    fn id_and_index_round_trip() {
+        for i in 0..LIST.count() {
+            assert_eq!(LIST.index(LIST.id(i)), Some(i));
+        }
+        assert_eq!(LIST.index(99), None);
+        assert_eq!(LIST.index(105), None);
+    }
+
+    #[test]
+    fn overlap_is_symmetric_and_exclusive_at_the_end() {
+        let next = ActionRange::new(105, 3);
+        assert!(!LIST.overlaps(next));
+        assert!(!next.overlaps(LIST));
+        let inner = ActionRange::single(104);
+        assert!(LIST.overlaps(inner));
+        assert!(inner.overlaps(LIST));
+    }
+
+    #[test]
+    fn end_of_is_the_largest_exclusive_end() {
+        assert_eq!(end_of(&[]), 0);
+        assert_eq!(end_of(&[ActionRange::single(200), LIST]), 201);
+        assert_eq!(end_of(&[LIST, ActionRange::single(3)]), 105);
+    }
+
+    #[test]
+    #[should_panic(expected = "overlapping action IDs")]
+    fn disjoint_check_rejects_overlap() {
+        assert_disjoint(&[LIST, ActionRange::single(102)]);
+    }
+
+    #[test]
+    #[should_panic(expected = "out of range")]
+    fn id_past_the_end_is_caught_in_debug() {
+        LIST.id(5);
+    }
+}
@@ -0,0 +1,44 @@
+//! デバッグコンソールのコマンド引数を読む小さな補助。
+//!
+//! コンソール本体はルート crate の `debug_console` にある。各ゲームの
+//! [`crate::game::Game::debug_command`] はここの関数で数量を読み、知らない
+//! コマンドには [`unknown`] を返す。
+
+/// ゲームが知らないコマンドを受け取ったときのエラー文。
+pub fn unknown(args: &[&str]) -> String {
+    format!("不明なコマンド: {} (help で一覧)", args.join(" "))
+}
+
+/// 数量を読む。`1e9` のような指数表記と `_` 区切りを受け付ける。
+pub fn parse_amount(s: &str) -> Result<f64, String> {
+    let v: f64 = s
+        .replace('_', "")
+        .parse()
+        .map_err(|_| format!("数値ではない: {}", s))?;
+    if !v.is_finite() || v < 0.0 {
+        return Err(format!("0 以上の有限値を指定: {}", s));
+    }
+    Ok(v)
+}
+
+/// 整数の数量を読む ([`parse_amount`] の結果を切り捨て、`u64` に飽和)。
+pub fn parse_count(s: &str) -> Result<u64, String> {
+    // `as` は範囲外を u64::MAX に飽和させる
+    parse_amount(s).map(|v| v.floor() as u64)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn amounts_accept_exponents_and_separators() {
+        assert_eq!(parse_amount("1e9"), Ok(1e9));
+        assert_eq!(parse_amount("1_000"), Ok(1000.0));
+        assert_eq!(parse_count("12.7"), Ok(12));
+        assert_eq!(parse_count("1e30"), Ok(u64::MAX));
+        assert!(parse_amount("-1").is_err());
+        assert!(parse_amount("inf").is_err());
+        assert!(parse_amount("abc").is_err());
+    }
+}
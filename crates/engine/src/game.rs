@@ -0,0 +1,127 @@
+//! Game trait and the list of games.
+//!
+//! 各ゲームの実装と `create_game` はルート crate の `games` に残る。
+//! ここには画面・入力・スコアから参照される型だけを置く。
+
+use std::cell::RefCell;
+use std::rc::Rc;
+
+use ratzilla::ratatui::layout::Rect;
+use ratzilla::ratatui::Frame;
+
+use crate::input::{ClickState, InputEvent, KeyRepeat};
+use crate::scores::Score;
+
+/// Trait that all games implement.
+pub trait Game {
+    /// Identify which game this is.  Used by the dispatcher to validate
+    /// `ClickScope::Game(_)` events match the currently active game.
+    fn choice(&self) -> GameChoice;
+
+    /// Handle an input event. Returns true if the event was consumed.
+    fn handle_input(&mut self, event: &InputEvent) -> bool;
+
+    /// Advance game logic by `delta_ticks` discrete ticks.
+    fn tick(&mut self, delta_ticks: u32);
+
+    /// 1 秒あたりの logic tick 数。`tick` に渡る tick はこの速さで進む。
+    /// 既定は [`crate::time::TICKS_PER_SEC`]。変えるゲームは tick 数で
+    /// 数えている時間 (持続・窓幅など) もこの速さに合わせること。
+    fn tick_rate(&self) -> u32 {
+        crate::time::TICKS_PER_SEC
+    }
+
+    /// Render the game into the given area.
+    fn render(&self, f: &mut Frame, area: Rect, click_state: &Rc<RefCell<ClickState>>);
+
+    /// 共有用の代表スコア ([`crate::scores`])。`None` のゲームはスコア
+    /// トークン画面を出さない (対戦型など、比べる数値が無いもの)。
+    fn score(&self) -> Option<Score> {
+        None
+    }
+
+    /// 今すぐ保存する。ページを閉じる・タブが隠れるときに呼ばれるので、
+    /// オートセーブ間隔を待たずに書き出すこと。保存しないゲームは何もしない。
+    fn save(&mut self) {}
+
+    /// 押しっぱなしのキーを自動で繰り返すか ([`crate::input::KeyRepeater`])。
+    /// 移動を連打させたいゲームだけ `Some` を返す。クリッカーは連打そのものが
+    /// 遊びなので既定の `None` のまま。
+    fn key_repeat(&self) -> Option<KeyRepeat> {
+        None
+    }
+
+    /// デバッグコンソール (ルート crate の `debug_console`) のゲーム固有コマンド。
+    /// `args` は空白区切りのトークン (`["give", "cookies", "1e9"]`)。
+    /// `["help"]` にはコマンド一覧を返すこと。成功時の戻り値は結果表示用。
+    #[cfg(any(feature = "debug-console", debug_assertions))]
+    fn debug_command(&mut self, args: &[&str]) -> Result<String, String> {
+        Err(crate::debug::unknown(args))
+    }
+}
+
+/// Which game the player has selected (or is choosing).
+#[derive(Clone, Debug, PartialEq)]
+pub enum GameChoice {
+    Cookie,
+    Factory,
+    Rpg,
+    Abyss,
+    Godfield,
+    Metropolis,
+    Drill,
+    Port,
+    Defense,
+}
+
+impl GameChoice {
+    /// 全ゲーム (メニュー表示順)。
+    pub fn all() -> [GameChoice; 9] {
+        [
+            GameChoice::Cookie,
+            GameChoice::Factory,
+            GameChoice::Rpg,
+            GameChoice::Abyss,
+            GameChoice::Godfield,
+            GameChoice::Metropolis,
+            GameChoice::Drill,
+            GameChoice::Port,
+            GameChoice::Defense,
+        ]
+    }
+
+    /// 永続化用の安定 ID。localStorage に書くので既存値は変更しないこと。
+    pub fn id(&self) -> &'static str {
+        match self {
+            GameChoice::Cookie => "cookie",
+            GameChoice::Factory => "factory",
+            GameChoice::Rpg => "rpg",
+            GameChoice::Abyss => "abyss",
+            GameChoice::Godfield => "godfield",
+            GameChoice::Metropolis => "metropolis",
+            GameChoice::Drill => "drill",
+            GameChoice::Port => "port",
+            GameChoice::Defense => "defense",
+        }
+    }
+
+    /// [`GameChoice::id`] の逆変換。不明な ID は `None`。
+    pub fn from_id(id: &str) -> Option<GameChoice> {
+        GameChoice::all().into_iter().find(|c| c.id() == id)
+    }
+
+    /// メニュー等に出す表示名。
+    pub fn title(&self) -> &'static str {
+        match self {
+            GameChoice::Cookie => "Cookie Factory",
+            GameChoice::Factory => "Tiny Factory",
+            GameChoice::Rpg => "Dungeon Dive",
+            GameChoice::Abyss => "深淵潜行 (Abyss Idle)",
+            GameChoice::Godfield => "神の戦場 (God Field)",
+            GameChoice::Metropolis => "Idle Metropolis",
+            GameChoice::Drill => "Deep Drill",
+            GameChoice::Port => "Port Trader",
+            GameChoice::Defense => "Grid Defense",
+        }
+    }
+}
@@ -4,7 +4,7 @@
 
 use ratzilla::ratatui::layout::Rect;
 
-use crate::games::GameChoice;
+use crate::game::GameChoice;
 
 /// Identifies which screen / game owns a click event.
 ///
@@ -17,6 +17,9 @@ use crate::games::GameChoice;
 pub enum ClickScope {
     Menu,
     Settings,
+    Profiles,
+    /// Score token screen (shown over a paused game).
+    Scores,
     Game(GameChoice),
 }
 
@@ -29,6 +32,57 @@ pub enum InputEvent {
     /// the screen/game that registered the target; the second is a semantic
     /// action ID defined within that scope.
     Click(ClickScope, u16),
+    /// A press held on a target for [`LONG_PRESS_MS`].  Fired once while the
+    /// pointer is still down; the release that follows produces no `Click`.
+    LongPress(ClickScope, u16),
+    /// A second tap on the same target within [`DOUBLE_TAP_MS`].  Replaces
+    /// the second `Click`; screens that ignore it get that `Click` instead
+    /// (see [`InputEvent::to_click`]).
+    DoubleTap(ClickScope, u16),
+    /// One step of a drag stroke: the pointer, held down, moved to another
+    /// cell.  Only presses that start inside a drag area (see
+    /// [`ClickState::add_drag_area`]) drag; a drag never produces a `Click`.
+    Drag(ClickScope, DragMotion),
+}
+
+/// One step of a drag stroke (see [`InputEvent::Drag`]).
+#[derive(Debug, Clone, PartialEq)]
+pub struct DragMotion {
+    /// Target the press started on.
+    pub origin: u16,
+    /// Target under the pointer now, hit-tested against the latest frame's
+    /// targets.  `None` over empty space.
+    pub target: Option<u16>,
+    /// Movement in terminal cells `(cols, rows)` since the previous step
+    /// (since the press for the first step).
+    pub delta: (i16, i16),
+    /// True for the first step of a stroke.
+    pub first: bool,
+}
+
+impl InputEvent {
+    /// Scope of a pointer event (`None` for keys).
+    pub fn scope(&self) -> Option<&ClickScope> {
+        match self {
+            InputEvent::Key(_) => None,
+            InputEvent::Click(scope, _)
+            | InputEvent::LongPress(scope, _)
+            | InputEvent::DoubleTap(scope, _)
+            | InputEvent::Drag(scope, _) => Some(scope),
+        }
+    }
+
+    /// The plain `Click` a gesture degrades to when the receiving screen
+    /// doesn't handle it.  `None` for keys, clicks and drags (an unhandled
+    /// drag is dropped rather than activating whatever it started on).
+    pub fn to_click(&self) -> Option<InputEvent> {
+        match self {
+            InputEvent::LongPress(scope, id) | InputEvent::DoubleTap(scope, id) => {
+                Some(InputEvent::Click(scope.clone(), *id))
+            }
+            InputEvent::Key(_) | InputEvent::Click(..) | InputEvent::Drag(..) => None,
+        }
+    }
 }
 
 /// A region on screen that can be tapped/clicked to trigger an action.
@@ -47,6 +101,29 @@ pub struct ClickTarget {
 ///   spamming is well over 50ms even at full speed)
 pub const TAP_DEDUP_MS: f64 = 30.0;
 
+/// How long (ms) a press must be held before it becomes a long-press.
+pub const LONG_PRESS_MS: f64 = 500.0;
+
+/// Maximum gap (ms) between two taps on the same target for a double-tap.
+/// Measured release-to-release.
+pub const DOUBLE_TAP_MS: f64 = 300.0;
+
+/// A press that has gone down on a target but not yet been released.
+#[derive(Debug, Clone)]
+struct PendingPress {
+    col: u16,
+    row: u16,
+    action_id: u16,
+    scope: ClickScope,
+    at: f64,
+    /// Set once [`ClickState::poll_long_press`] has reported this press.
+    long_fired: bool,
+    /// The press went down inside a drag area.
+    draggable: bool,
+    /// The press has left its cell and is now a drag stroke.
+    dragging: bool,
+}
+
 /// Shared state between the render loop and click handler.
 pub struct ClickState {
     pub targets: Vec<ClickTarget>,
@@ -54,7 +131,7 @@ pub struct ClickState {
     pub terminal_rows: u16,
     /// Timestamp of the most recently accepted tap (ms, from `performance.now`).
     /// Used together with [`last_tap_pos`] to drop the browser's compatibility
-    /// mouse event that follows our synthetic mousedown for the same touch.
+    /// mouse event that follows our synthetic mouseup for the same touch.
     last_tap_at: Option<f64>,
     last_tap_pos: Option<(u16, u16)>,
     /// Scope of click targets registered during the current frame.  Set once
@@ -63,6 +140,14 @@ pub struct ClickState {
     /// [`InputEvent::Click`] so the dispatcher can verify the click is bound
     /// for the currently active screen.
     current_scope: Option<ClickScope>,
+    /// Regions where a held press drags instead of cancelling when it moves.
+    /// Registered per frame by render code, like click targets.
+    drag_areas: Vec<Rect>,
+    /// Press currently held down (see [`press`](Self::press)).
+    pending_press: Option<PendingPress>,
+    /// Action and time of the last completed single tap, for double-tap
+    /// detection.
+    last_tap_action: Option<(u16, f64)>,
 }
 
 impl ClickState {
@@ -74,10 +159,14 @@ impl ClickState {
             last_tap_at: None,
             last_tap_pos: None,
             current_scope: None,
+            drag_areas: Vec::new(),
+            pending_press: None,
+            last_tap_action: None,
         }
     }
 
-    /// Clear all click targets.  Called once at the start of each render frame.
+    /// Clear all click targets and drag areas.  Called once at the start of
+    /// each render frame.
     /// The tap-dedup state intentionally outlives a single frame because frame
     /// stutter can push the browser's compatibility mouse event past the next
     /// frame boundary.  The scope is also cleared so a missing
@@ -85,6 +174,7 @@ impl ClickState {
     /// next frame.
     pub fn clear_targets(&mut self) {
         self.targets.clear();
+        self.drag_areas.clear();
         self.current_scope = None;
     }
 
@@ -121,6 +211,113 @@ impl ClickState {
         true
     }
 
+    /// Record a press going down at `(col, row)`.  Nothing is dispatched yet:
+    /// the press becomes a `Click` / `DoubleTap` on [`release`](Self::release)
+    /// or a `LongPress` via [`poll_long_press`](Self::poll_long_press).
+    /// A press that misses every target is ignored.
+    pub fn press(&mut self, col: u16, row: u16, now_ms: f64) {
+        let draggable = self.in_drag_area(col, row);
+        self.pending_press = self.hit_test(col, row).map(|action_id| PendingPress {
+            col,
+            row,
+            action_id,
+            scope: self.current_scope.clone().unwrap_or(ClickScope::Menu),
+            at: now_ms,
+            long_fired: false,
+            draggable,
+            dragging: false,
+        });
+    }
+
+    /// Mark `rect` as a drag area for this frame: a press that starts inside
+    /// it turns into [`InputEvent::Drag`] steps when moved, instead of being
+    /// cancelled.
+    pub fn add_drag_area(&mut self, rect: Rect) {
+        self.drag_areas.push(rect);
+    }
+
+    /// Drag areas registered this frame (exported to the touch handler so it
+    /// can tell a drag from a scroll swipe).
+    pub fn drag_areas(&self) -> &[Rect] {
+        &self.drag_areas
+    }
+
+    fn in_drag_area(&self, col: u16, row: u16) -> bool {
+        self.drag_areas
+            .iter()
+            .any(|r| col >= r.x && col < r.x + r.width && row >= r.y && row < r.y + r.height)
+    }
+
+    /// Whether a press is being held (so pointer movement matters).
+    pub fn has_pending_press(&self) -> bool {
+        self.pending_press.is_some()
+    }
+
+    /// The pointer moved while held.
+    ///
+    /// Outside drag areas, leaving the pressed cell — or the grid (`None`) —
+    /// cancels the press, so swipes never activate whatever they started on.
+    /// A press that started in a drag area instead reports each new cell as
+    /// an [`InputEvent::Drag`] step; leaving the grid ends the stroke.
+    pub fn pointer_moved(&mut self, cell: Option<(u16, u16)>) -> Option<InputEvent> {
+        let p = self.pending_press.as_ref()?;
+        let Some((col, row)) = cell else {
+            self.pending_press = None;
+            return None;
+        };
+        if (p.col, p.row) == (col, row) {
+            return None;
+        }
+        if !p.draggable || p.long_fired {
+            self.pending_press = None;
+            return None;
+        }
+        let target = self.hit_test(col, row);
+        let p = self.pending_press.as_mut()?;
+        let motion = DragMotion {
+            origin: p.action_id,
+            target,
+            delta: (col as i16 - p.col as i16, row as i16 - p.row as i16),
+            first: !p.dragging,
+        };
+        p.dragging = true;
+        p.col = col;
+        p.row = row;
+        Some(InputEvent::Drag(p.scope.clone(), motion))
+    }
+
+    /// Report a press that has been held for [`LONG_PRESS_MS`].  Returns the
+    /// `LongPress` event once per press; call every frame.
+    pub fn poll_long_press(&mut self, now_ms: f64) -> Option<InputEvent> {
+        let p = self.pending_press.as_mut()?;
+        if p.long_fired || p.dragging || now_ms - p.at < LONG_PRESS_MS {
+            return None;
+        }
+        p.long_fired = true;
+        Some(InputEvent::LongPress(p.scope.clone(), p.action_id))
+    }
+
+    /// Finish the held press at `(col, row)` and classify it.
+    ///
+    /// Returns `None` if there was no press, it already fired as a long-press
+    /// or a drag, or the release landed on a different target.  A tap on the same
+    /// target as the previous tap within [`DOUBLE_TAP_MS`] is a `DoubleTap`
+    /// (a third tap then starts a new pair).
+    pub fn release(&mut self, col: u16, row: u16, now_ms: f64) -> Option<InputEvent> {
+        let p = self.pending_press.take()?;
+        if p.long_fired || p.dragging || self.hit_test(col, row) != Some(p.action_id) {
+            return None;
+        }
+        if let Some((id, at)) = self.last_tap_action {
+            if id == p.action_id && now_ms - at < DOUBLE_TAP_MS {
+                self.last_tap_action = None;
+                return Some(InputEvent::DoubleTap(p.scope, p.action_id));
+            }
+        }
+        self.last_tap_action = Some((p.action_id, now_ms));
+        Some(InputEvent::Click(p.scope, p.action_id))
+    }
+
     /// Register a click target with a rectangular hit region and a semantic action ID.
     pub fn add_click_target(&mut self, rect: Rect, action_id: u16) {
         self.targets.push(ClickTarget { rect, action_id });
@@ -258,6 +455,132 @@ pub fn pixel_x_to_col(click_x: f64, grid_width: f64, terminal_cols: u16) -> Opti
     if col >= terminal_cols { None } else { Some(col) }
 }
 
+// ── Paging keys ────────────────────────────────────────────────────
+
+/// Char dispatched for PgUp.  Games only see `char` keys, so the paging
+/// keys get chars no keyboard layout types directly — they can't collide
+/// with a game's letter/digit/symbol bindings.
+pub const PAGE_UP_KEY: char = '⇞';
+
+/// Char dispatched for PgDn (see [`PAGE_UP_KEY`]).
+pub const PAGE_DOWN_KEY: char = '⇟';
+
+// ── Held-key repeat ────────────────────────────────────────────────
+
+/// Movement keys after arrow-key normalization (see `on_key_event` in main).
+pub const MOVEMENT_KEYS: &[char] = &['h', 'j', 'k', 'l'];
+
+/// Held-key auto-repeat a game opts into via
+/// [`Game::key_repeat`](crate::game::Game::key_repeat).
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct KeyRepeat {
+    /// Hold time (ms) before the first repeat.
+    pub delay_ms: f64,
+    /// Time (ms) between repeats after that.
+    pub interval_ms: f64,
+    /// Keys that repeat while held.  Anything else fires once per keydown.
+    pub keys: &'static [char],
+}
+
+impl KeyRepeat {
+    /// Repeat only the [`MOVEMENT_KEYS`].
+    pub const fn movement(delay_ms: f64, interval_ms: f64) -> Self {
+        Self { delay_ms, interval_ms, keys: MOVEMENT_KEYS }
+    }
+}
+
+/// Held-key state machine.
+///
+/// Browsers disagree on auto-repeat: some send a keydown stream while a key
+/// is held, some send one.  The browser's own repeats are dropped here and
+/// `Key` events are synthesized at the game's [`KeyRepeat`] pace instead, so
+/// holding an arrow moves at the same speed everywhere.
+#[derive(Debug, Default)]
+pub struct KeyRepeater {
+    held: Option<HeldKey>,
+}
+
+#[derive(Debug, Clone, Copy)]
+struct HeldKey {
+    key: char,
+    next_at: f64,
+    interval_ms: f64,
+}
+
+impl KeyRepeater {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// A keydown for `key`.  Returns `false` if it is the browser's own
+    /// repeat of the key already being held (drop it), `true` otherwise.
+    /// Pressing any other key stops the current repeat, like a keyboard does.
+    pub fn key_down(&mut self, key: char, now_ms: f64, repeat: Option<KeyRepeat>) -> bool {
+        if self.held.is_some_and(|h| h.key == key) {
+            return false;
+        }
+        self.held = repeat.filter(|r| r.keys.contains(&key)).map(|r| HeldKey {
+            key,
+            next_at: now_ms + r.delay_ms,
+            interval_ms: r.interval_ms,
+        });
+        true
+    }
+
+    /// A keyup for `key`.  Releasing some other key leaves the repeat running.
+    pub fn key_up(&mut self, key: char) {
+        if self.held.is_some_and(|h| h.key == key) {
+            self.held = None;
+        }
+    }
+
+    /// Stop repeating (window lost focus, screen changed).  The matching
+    /// keyup may never arrive in those cases.
+    pub fn release(&mut self) {
+        self.held = None;
+    }
+
+    /// Whether a key is currently repeating.
+    pub fn is_holding(&self) -> bool {
+        self.held.is_some()
+    }
+
+    /// The repeated `Key` event due at `now_ms`, if any.  Call every frame.
+    /// At most one event per call: after a long stall the schedule restarts
+    /// from now instead of bursting the missed repeats.
+    pub fn poll(&mut self, now_ms: f64) -> Option<InputEvent> {
+        let h = self.held.as_mut()?;
+        if now_ms < h.next_at {
+            return None;
+        }
+        h.next_at = if now_ms - h.next_at > h.interval_ms {
+            now_ms + h.interval_ms
+        } else {
+            h.next_at + h.interval_ms
+        };
+        Some(InputEvent::Key(h.key))
+    }
+}
+
+/// Map a DOM `KeyboardEvent.key` to the char the keydown path dispatches
+/// (arrows become h/j/k/l, PgUp/PgDn become [`PAGE_UP_KEY`] /
+/// [`PAGE_DOWN_KEY`]).  `None` for keys the games never see.
+pub fn key_from_dom(key: &str) -> Option<char> {
+    match key {
+        "ArrowLeft" => Some('h'),
+        "ArrowRight" => Some('l'),
+        "ArrowUp" => Some('k'),
+        "ArrowDown" => Some('j'),
+        "PageUp" => Some(PAGE_UP_KEY),
+        "PageDown" => Some(PAGE_DOWN_KEY),
+        _ => {
+            let mut chars = key.chars();
+            let c = chars.next()?;
+            chars.next().is_none().then_some(c)
+        }
+    }
+}
+
 #[cfg(test)]
 #[allow(clippy::disallowed_methods)]
 mod tests {
@@ -664,4 +987,211 @@ mod tests {
         assert_tap_hits(&cs, grid_height, 11, 3);
         assert_tap_hits(&cs, grid_height, 48, 99);
     }
+
+    // ── Gesture recognition (press / release / long-press) ────────
+
+    fn gesture_state() -> ClickState {
+        let mut cs = ClickState::new();
+        cs.set_scope(ClickScope::Menu);
+        cs.add_click_target(Rect::new(0, 0, 10, 1), 1);
+        cs.add_click_target(Rect::new(0, 1, 10, 1), 2);
+        cs
+    }
+
+    #[test]
+    fn press_release_is_click() {
+        let mut cs = gesture_state();
+        cs.press(3, 0, 1000.0);
+        assert_eq!(cs.poll_long_press(1100.0), None);
+        assert_eq!(
+            cs.release(3, 0, 1100.0),
+            Some(InputEvent::Click(ClickScope::Menu, 1))
+        );
+        assert!(!cs.has_pending_press());
+    }
+
+    #[test]
+    fn release_without_press_or_off_target_is_dropped() {
+        let mut cs = gesture_state();
+        assert_eq!(cs.release(3, 0, 1000.0), None);
+        cs.press(3, 0, 1000.0);
+        assert_eq!(cs.release(3, 1, 1050.0), None, "released on another target");
+        cs.press(20, 5, 1000.0);
+        assert!(!cs.has_pending_press(), "press on empty space");
+    }
+
+    #[test]
+    fn held_press_fires_long_press_once_and_swallows_release() {
+        let mut cs = gesture_state();
+        cs.press(3, 1, 1000.0);
+        assert_eq!(cs.poll_long_press(1000.0 + LONG_PRESS_MS - 1.0), None);
+        assert_eq!(
+            cs.poll_long_press(1000.0 + LONG_PRESS_MS),
+            Some(InputEvent::LongPress(ClickScope::Menu, 2))
+        );
+        assert_eq!(cs.poll_long_press(2000.0), None);
+        assert_eq!(cs.release(3, 1, 2000.0), None);
+    }
+
+    #[test]
+    fn leaving_the_cell_cancels_press() {
+        let mut cs = gesture_state();
+        cs.press(3, 0, 1000.0);
+        cs.pointer_moved(Some((3, 0)));
+        assert!(cs.has_pending_press());
+        cs.pointer_moved(Some((4, 0)));
+        assert_eq!(cs.poll_long_press(2000.0), None);
+        assert_eq!(cs.release(4, 0, 2000.0), None);
+
+        cs.press(3, 0, 3000.0);
+        cs.pointer_moved(None);
+        assert!(!cs.has_pending_press());
+    }
+
+    #[test]
+    fn second_quick_tap_on_same_target_is_double_tap() {
+        let mut cs = gesture_state();
+        let tap = |cs: &mut ClickState, row: u16, t: f64| {
+            cs.press(3, row, t);
+            cs.release(3, row, t + 20.0)
+        };
+        assert_eq!(tap(&mut cs, 0, 1000.0), Some(InputEvent::Click(ClickScope::Menu, 1)));
+        assert_eq!(tap(&mut cs, 0, 1200.0), Some(InputEvent::DoubleTap(ClickScope::Menu, 1)));
+        // Third tap starts a new pair
+        assert_eq!(tap(&mut cs, 0, 1400.0), Some(InputEvent::Click(ClickScope::Menu, 1)));
+        // Too slow
+        assert_eq!(tap(&mut cs, 0, 1400.0 + DOUBLE_TAP_MS), Some(InputEvent::Click(ClickScope::Menu, 1)));
+        // Different target in between breaks the pair
+        assert_eq!(tap(&mut cs, 1, 2000.0), Some(InputEvent::Click(ClickScope::Menu, 2)));
+        assert_eq!(tap(&mut cs, 0, 2100.0), Some(InputEvent::Click(ClickScope::Menu, 1)));
+    }
+
+    #[test]
+    fn press_in_drag_area_streams_drag_steps() {
+        let mut cs = gesture_state();
+        cs.add_drag_area(Rect::new(0, 0, 10, 2));
+        cs.press(3, 0, 1000.0);
+
+        let step = |ev: Option<InputEvent>| match ev {
+            Some(InputEvent::Drag(ClickScope::Menu, m)) => m,
+            other => panic!("expected drag, got {:?}", other),
+        };
+        assert_eq!(cs.pointer_moved(Some((3, 0))), None, "same cell");
+        let m = step(cs.pointer_moved(Some((3, 1))));
+        assert_eq!(m, DragMotion { origin: 1, target: Some(2), delta: (0, 1), first: true });
+        let m = step(cs.pointer_moved(Some((5, 1))));
+        assert_eq!(m, DragMotion { origin: 1, target: Some(2), delta: (2, 0), first: false });
+        let m = step(cs.pointer_moved(Some((12, 1))));
+        assert_eq!(m.target, None, "empty space");
+
+        // A drag never long-presses or clicks
+        assert_eq!(cs.poll_long_press(5000.0), None);
+        assert_eq!(cs.release(3, 0, 5000.0), None);
+    }
+
+    #[test]
+    fn leaving_grid_ends_drag_and_areas_reset_per_frame() {
+        let mut cs = gesture_state();
+        cs.add_drag_area(Rect::new(0, 0, 10, 2));
+        cs.press(3, 0, 1000.0);
+        assert!(cs.pointer_moved(Some((4, 0))).is_some());
+        assert_eq!(cs.pointer_moved(None), None);
+        assert!(!cs.has_pending_press());
+
+        cs.clear_targets();
+        assert!(cs.drag_areas().is_empty());
+    }
+
+    #[test]
+    fn gestures_degrade_to_click() {
+        let lp = InputEvent::LongPress(ClickScope::Menu, 7);
+        let dt = InputEvent::DoubleTap(ClickScope::Settings, 8);
+        assert_eq!(lp.to_click(), Some(InputEvent::Click(ClickScope::Menu, 7)));
+        assert_eq!(dt.to_click(), Some(InputEvent::Click(ClickScope::Settings, 8)));
+        assert_eq!(InputEvent::Click(ClickScope::Menu, 7).to_click(), None);
+        assert_eq!(InputEvent::Key('a').to_click(), None);
+        let drag = InputEvent::Drag(
+            ClickScope::Menu,
+            DragMotion { origin: 1, target: None, delta: (1, 0), first: true },
+        );
+        assert_eq!(drag.to_click(), None);
+        assert_eq!(drag.scope(), Some(&ClickScope::Menu));
+        assert_eq!(dt.scope(), Some(&ClickScope::Settings));
+        assert_eq!(InputEvent::Key('a').scope(), None);
+    }
+
+    // ── KeyRepeater tests ───────────────────────────────────────────
+
+    const REPEAT: KeyRepeat = KeyRepeat::movement(250.0, 100.0);
+
+    #[test]
+    fn key_repeat_waits_for_delay_then_fires_at_interval() {
+        let mut kr = KeyRepeater::new();
+        assert!(kr.key_down('l', 0.0, Some(REPEAT)));
+        assert_eq!(kr.poll(249.0), None);
+        assert_eq!(kr.poll(250.0), Some(InputEvent::Key('l')));
+        assert_eq!(kr.poll(300.0), None);
+        assert_eq!(kr.poll(350.0), Some(InputEvent::Key('l')));
+    }
+
+    #[test]
+    fn key_repeat_drops_browser_repeats_of_held_key() {
+        let mut kr = KeyRepeater::new();
+        assert!(kr.key_down('j', 0.0, Some(REPEAT)));
+        assert!(!kr.key_down('j', 30.0, Some(REPEAT)));
+        // The browser repeat did not reset the schedule.
+        assert_eq!(kr.poll(250.0), Some(InputEvent::Key('j')));
+    }
+
+    #[test]
+    fn key_repeat_stops_on_key_up_of_the_held_key_only() {
+        let mut kr = KeyRepeater::new();
+        kr.key_down('k', 0.0, Some(REPEAT));
+        kr.key_up('x');
+        assert!(kr.is_holding());
+        kr.key_up('k');
+        assert!(!kr.is_holding());
+        assert_eq!(kr.poll(1000.0), None);
+        // A fresh press after release is dispatched again.
+        assert!(kr.key_down('k', 1000.0, Some(REPEAT)));
+    }
+
+    #[test]
+    fn key_repeat_ignores_keys_outside_the_opt_in() {
+        let mut kr = KeyRepeater::new();
+        // Non-movement key, and a game without repeat: both fire once.
+        assert!(kr.key_down(' ', 0.0, Some(REPEAT)));
+        assert!(kr.key_down(' ', 10.0, Some(REPEAT)));
+        assert!(kr.key_down('h', 20.0, None));
+        assert!(kr.key_down('h', 30.0, None));
+        assert!(!kr.is_holding());
+    }
+
+    #[test]
+    fn key_repeat_other_key_takes_over() {
+        let mut kr = KeyRepeater::new();
+        kr.key_down('h', 0.0, Some(REPEAT));
+        kr.key_down('l', 100.0, Some(REPEAT));
+        kr.key_up('h');
+        assert_eq!(kr.poll(350.0), Some(InputEvent::Key('l')));
+    }
+
+    #[test]
+    fn key_repeat_does_not_burst_after_stall() {
+        let mut kr = KeyRepeater::new();
+        kr.key_down('h', 0.0, Some(REPEAT));
+        assert!(kr.poll(1000.0).is_some());
+        assert_eq!(kr.poll(1001.0), None);
+        assert!(kr.poll(1100.0).is_some());
+    }
+
+    #[test]
+    fn key_from_dom_maps_arrows_and_single_chars() {
+        assert_eq!(key_from_dom("ArrowLeft"), Some('h'));
+        assert_eq!(key_from_dom("ArrowDown"), Some('j'));
+        assert_eq!(key_from_dom("PageDown"), Some(PAGE_DOWN_KEY));
+        assert_eq!(key_from_dom("w"), Some('w'));
+        assert_eq!(key_from_dom("Shift"), None);
+        assert_eq!(key_from_dom(""), None);
+    }
 }
@@ -0,0 +1,234 @@
+//! キー割り当て (アクション名 → キー) の登録表。
+//!
+//! ゲームは移動・決定・戻るを「正規キー」(h/j/k/l/' '/q) で受け取る。
+//! 押されたキーは main.rs の keydown 入口で [`resolve`] を通して正規キーに
+//! 読み替えるので、割り当てを変えてもゲーム側のコードは変わらない。
+//! 矢印キー / Enter / Esc は読み替えを通さず常に正規キーとして届く
+//! (割り当てを壊しても操作不能にならないための逃げ道)。
+//!
+//! 割り当ては [`crate::profile::scoped_key`] でプロファイルごとに保存する。
+//! localStorage は WASM ビルドでのみ触る。native (cargo test) では読み込みは
+//! 常に既定値、書き込みは no-op。
+
+use std::cell::RefCell;
+
+#[cfg(target_arch = "wasm32")]
+use crate::profile;
+
+/// localStorage のキー。値は [`KeyAction::ALL`] 順にキーを並べた文字列。
+#[cfg(target_arch = "wasm32")]
+const KEYBINDINGS_KEY: &str = "app_keybindings";
+
+/// 割り当てを変えられる操作。
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum KeyAction {
+    Up,
+    Down,
+    Left,
+    Right,
+    Confirm,
+    Back,
+}
+
+impl KeyAction {
+    pub const ALL: [KeyAction; 6] = [
+        KeyAction::Up,
+        KeyAction::Down,
+        KeyAction::Left,
+        KeyAction::Right,
+        KeyAction::Confirm,
+        KeyAction::Back,
+    ];
+
+    /// 設定画面の表示名。
+    pub fn label(self) -> &'static str {
+        match self {
+            KeyAction::Up => "上へ移動",
+            KeyAction::Down => "下へ移動",
+            KeyAction::Left => "左へ移動",
+            KeyAction::Right => "右へ移動",
+            KeyAction::Confirm => "決定 (A)",
+            KeyAction::Back => "戻る",
+        }
+    }
+
+    /// ゲームが受け取る正規キー。既定の割り当てもこれ。
+    pub fn canonical(self) -> char {
+        match self {
+            KeyAction::Up => 'k',
+            KeyAction::Down => 'j',
+            KeyAction::Left => 'h',
+            KeyAction::Right => 'l',
+            KeyAction::Confirm => ' ',
+            KeyAction::Back => 'q',
+        }
+    }
+
+    fn index(self) -> usize {
+        KeyAction::ALL.iter().position(|&a| a == self).unwrap_or(0)
+    }
+}
+
+/// 表示用のキー名 (空白は見えないので名前にする)。
+pub fn key_label(c: char) -> String {
+    match c {
+        ' ' => "Space".to_string(),
+        c => c.to_string(),
+    }
+}
+
+/// 各アクションに割り当てたキー。キーは常にアクション間で重複しない。
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct KeyBindings {
+    keys: [char; 6],
+}
+
+impl Default for KeyBindings {
+    fn default() -> Self {
+        Self {
+            keys: KeyAction::ALL.map(KeyAction::canonical),
+        }
+    }
+}
+
+impl KeyBindings {
+    pub fn key(&self, action: KeyAction) -> char {
+        self.keys[action.index()]
+    }
+
+    /// `action` に `key` を割り当てる。他のアクションが使っていたキーなら、
+    /// そちらには `action` の元のキーを渡す (入れ替え)。
+    pub fn set(&mut self, action: KeyAction, key: char) {
+        let i = action.index();
+        if let Some(j) = self.keys.iter().position(|&k| k == key) {
+            self.keys[j] = self.keys[i];
+        }
+        self.keys[i] = key;
+    }
+
+    /// 押されたキーを、ゲームに渡すキーに読み替える。
+    /// - 割り当て済みのキー → そのアクションの正規キー
+    /// - 割り当てから外れた正規キー (例: 上を w にした後の k) → `None` (捨てる)
+    /// - それ以外 → そのまま
+    pub fn resolve(&self, c: char) -> Option<char> {
+        if let Some(a) = KeyAction::ALL.into_iter().find(|&a| self.key(a) == c) {
+            return Some(a.canonical());
+        }
+        if KeyAction::ALL.into_iter().any(|a| a.canonical() == c) {
+            return None;
+        }
+        Some(c)
+    }
+
+    /// 保存形式 ([`KeyAction::ALL`] 順に 1 文字ずつ)。
+    pub fn encode(&self) -> String {
+        self.keys.iter().collect()
+    }
+
+    /// [`encode`](Self::encode) の逆。数が合わない・重複がある値は `None`。
+    pub fn decode(s: &str) -> Option<Self> {
+        let chars: Vec<char> = s.chars().collect();
+        let keys: [char; 6] = chars.try_into().ok()?;
+        let distinct = keys
+            .iter()
+            .enumerate()
+            .all(|(i, k)| !keys[..i].contains(k));
+        distinct.then_some(Self { keys })
+    }
+}
+
+thread_local! {
+    /// 現在の割り当て。keydown / keyup のハンドラから毎回引くため、
+    /// localStorage を読まずに済むようここに置く。
+    static ACTIVE: RefCell<KeyBindings> = RefCell::new(KeyBindings::default());
+}
+
+/// 現在の割り当て。
+pub fn active() -> KeyBindings {
+    ACTIVE.with(|b| b.borrow().clone())
+}
+
+/// 現在の割り当てで押されたキーを読み替える ([`KeyBindings::resolve`])。
+pub fn resolve(c: char) -> Option<char> {
+    ACTIVE.with(|b| b.borrow().resolve(c))
+}
+
+#[cfg(target_arch = "wasm32")]
+fn get_storage() -> Option<crate::storage::Store> {
+    crate::storage::get()
+}
+
+/// アクティブなプロファイルの割り当てを読み込んで有効にする。
+/// 未保存・破損時は既定値。プロファイルを切り替えたら呼び直すこと。
+pub fn load() -> KeyBindings {
+    #[cfg(target_arch = "wasm32")]
+    let bindings = get_storage()
+        .and_then(|s| s.get_item(&profile::scoped_key(KEYBINDINGS_KEY)).ok().flatten())
+        .and_then(|s| KeyBindings::decode(&s))
+        .unwrap_or_default();
+    #[cfg(not(target_arch = "wasm32"))]
+    let bindings = KeyBindings::default();
+    ACTIVE.with(|b| *b.borrow_mut() = bindings.clone());
+    bindings
+}
+
+/// 割り当てを保存して有効にする。保存に失敗しても今のセッションには効く。
+pub fn store(bindings: &KeyBindings) {
+    ACTIVE.with(|b| *b.borrow_mut() = bindings.clone());
+    #[cfg(target_arch = "wasm32")]
+    if let Some(storage) = get_storage() {
+        if let Err(e) = storage.set_item(&profile::scoped_key(KEYBINDINGS_KEY), &bindings.encode()) {
+            web_sys::console::warn_1(
+                &format!("keybind: localStorage への保存に失敗: {e:?}").into(),
+            );
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn default_bindings_pass_keys_through() {
+        let b = KeyBindings::default();
+        for c in ['h', 'j', 'k', 'l', ' ', 'q', 'w', '1'] {
+            assert_eq!(b.resolve(c), Some(c));
+        }
+    }
+
+    #[test]
+    fn remapped_key_resolves_to_canonical_and_old_key_is_dropped() {
+        let mut b = KeyBindings::default();
+        b.set(KeyAction::Up, 'w');
+        assert_eq!(b.resolve('w'), Some('k'));
+        assert_eq!(b.resolve('k'), None);
+        assert_eq!(b.resolve('j'), Some('j'));
+    }
+
+    #[test]
+    fn set_swaps_when_key_is_taken() {
+        let mut b = KeyBindings::default();
+        b.set(KeyAction::Up, 'j');
+        assert_eq!(b.key(KeyAction::Up), 'j');
+        assert_eq!(b.key(KeyAction::Down), 'k');
+        // 入れ替えたので上下が逆になるだけで、キーは失われない
+        assert_eq!(b.resolve('j'), Some('k'));
+        assert_eq!(b.resolve('k'), Some('j'));
+    }
+
+    #[test]
+    fn encode_decode_roundtrip() {
+        let mut b = KeyBindings::default();
+        b.set(KeyAction::Confirm, 'z');
+        b.set(KeyAction::Left, 'a');
+        assert_eq!(KeyBindings::decode(&b.encode()), Some(b));
+    }
+
+    #[test]
+    fn decode_rejects_wrong_length_and_duplicates() {
+        assert_eq!(KeyBindings::decode("kjhl q"), Some(KeyBindings::default()));
+        assert_eq!(KeyBindings::decode("kjhl"), None);
+        assert_eq!(KeyBindings::decode("kkhl q"), None);
+    }
+}
@@ -0,0 +1,30 @@
+//! ゲームに依存しない共通モジュール。
+//!
+//! [`Game`](game::Game) trait と [`GameChoice`](game::GameChoice)・入力
+//! ([`input`])・クリック付き widget ([`widgets`])・action ID の区間
+//! ([`action`])・スコアトークン ([`scores`]) に加えて、保存先 ([`storage`])・
+//! プロファイル ([`profile`])・各種設定 ([`theme`] / [`numfmt`] / [`keybind`] /
+//! [`zoom`] / [`a11y`])・通知 ([`toast`] / [`sound`])・tick 時間 ([`time`]) を
+//! 置く。ルートの crate はこれらを再エクスポートしているので、ゲーム側は
+//! 今まで通り `crate::input` や `crate::games::Game` などで引ける。
+//!
+//! ここからルートの crate (`games` / `screens` など) は参照できない。
+//! 移す順番は docs/adr/0002-workspace-split.md を参照。
+
+pub mod a11y;
+pub mod action;
+#[cfg(any(feature = "debug-console", debug_assertions))]
+pub mod debug;
+pub mod game;
+pub mod input;
+pub mod keybind;
+pub mod numfmt;
+pub mod profile;
+pub mod scores;
+pub mod sound;
+pub mod storage;
+pub mod theme;
+pub mod time;
+pub mod toast;
+pub mod widgets;
+pub mod zoom;
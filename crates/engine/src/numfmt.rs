@@ -0,0 +1,290 @@
+//! 大きな数の表記 (桁区切り / 万・億・兆 / K・M・B / 指数)。
+//!
+//! もとは Cookie Factory の `format_number` だったものを、Tiny Factory など
+//! 他のゲームからも同じ見た目で使えるよう共通化した。表記は設定画面で
+//! 切り替え、[`format`] / [`push`] は常に現在の表記で書く。特定の表記で
+//! 書きたいとき (テストなど) は [`format_with`] を使う。
+//!
+//! 閾値 ([`Notation`] の各変種を参照) より小さい数は、どの表記でも
+//! 桁区切りのまま書く。
+//!
+//! 設定は [`crate::profile::scoped_key`] でプロファイルごとに保存する。
+//! localStorage は WASM ビルドでのみ触る。native (cargo test) では読み込みは
+//! 常に既定値、書き込みは no-op。
+
+use std::cell::Cell;
+use std::fmt::Write;
+
+#[cfg(target_arch = "wasm32")]
+use crate::profile;
+
+/// localStorage のキー。値は [`Notation::index`]。
+#[cfg(target_arch = "wasm32")]
+const NOTATION_KEY: &str = "app_number_notation";
+
+/// 選べる数の表記。
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Default)]
+pub enum Notation {
+    /// 1,234,567
+    #[default]
+    Comma,
+    /// 123万4567 / 1億2345万 (1 万以上)
+    Japanese,
+    /// 1.23K / 45.6M / 789B (1,000 以上)
+    Short,
+    /// 1.23e6 (100 万以上)
+    Scientific,
+}
+
+/// [`Notation::Japanese`] の単位 (4 桁ごと)。これより大きい数は指数表記。
+const JAPANESE_UNITS: [&str; 5] = ["万", "億", "兆", "京", "垓"];
+
+/// [`Notation::Short`] の接尾辞 (3 桁ごと)。これより大きい数は指数表記。
+const SHORT_SUFFIXES: [&str; 6] = ["K", "M", "B", "T", "Qa", "Qi"];
+
+impl Notation {
+    /// 保存値は [`index`](Self::index) なので、新しい表記は末尾に足すこと。
+    pub const ALL: [Notation; 4] = [
+        Notation::Comma,
+        Notation::Japanese,
+        Notation::Short,
+        Notation::Scientific,
+    ];
+
+    /// 設定画面の表示名。
+    pub fn label(self) -> &'static str {
+        match self {
+            Notation::Comma => "桁区切り",
+            Notation::Japanese => "万・億・兆",
+            Notation::Short => "K・M・B",
+            Notation::Scientific => "指数",
+        }
+    }
+
+    /// 次の表記 (設定画面で押すたびに巡回)。
+    pub fn next(self) -> Self {
+        let i = self.index() as usize;
+        Notation::ALL[(i + 1) % Notation::ALL.len()]
+    }
+
+    pub fn index(self) -> u8 {
+        Notation::ALL.iter().position(|&n| n == self).unwrap_or(0) as u8
+    }
+
+    /// [`index`](Self::index) の逆。範囲外は既定値。
+    pub fn from_index(i: u8) -> Self {
+        Notation::ALL.get(i as usize).copied().unwrap_or_default()
+    }
+}
+
+thread_local! {
+    /// 現在の表記。render のたびに引くため localStorage は読まない。
+    static ACTIVE: Cell<Notation> = const { Cell::new(Notation::Comma) };
+}
+
+/// 現在の表記。
+pub fn active() -> Notation {
+    ACTIVE.with(|n| n.get())
+}
+
+/// 現在の表記で書く (例: 桁区切りなら 1234567 → "1,234,567")。
+pub fn format(n: f64) -> String {
+    format_with(n, active())
+}
+
+/// 表記を指定して書く。
+pub fn format_with(n: f64, notation: Notation) -> String {
+    let mut out = String::new();
+    push_with(&mut out, n, notation);
+    out
+}
+
+/// [`format`] を既存のバッファへ書き足す。`out` 自身の伸長以外は
+/// 割り当てない (パーティクルの文字列はバッファを使い回す)。
+pub fn push(out: &mut String, n: f64) {
+    push_with(out, n, active());
+}
+
+/// [`format_with`] を既存のバッファへ書き足す。
+pub fn push_with(out: &mut String, n: f64, notation: Notation) {
+    if n < 0.0 {
+        out.push('-');
+        return push_with(out, -n, notation);
+    }
+    match notation {
+        _ if !n.is_finite() => push_comma(out, n),
+        Notation::Comma => push_comma(out, n),
+        Notation::Japanese if n >= 1e4 => push_japanese(out, n),
+        Notation::Short if n >= 1e3 => push_short(out, n),
+        Notation::Scientific if n >= 1e6 => push_scientific(out, n),
+        _ => push_comma(out, n),
+    }
+}
+
+/// 3 桁ごとにカンマ。小数は 1 桁まで (0.05 以下は切り捨て)。
+fn push_comma(out: &mut String, n: f64) {
+    let int_part = n.floor() as u64;
+    let frac = n - int_part as f64;
+
+    // Digits least-significant first; u64 has at most 20.
+    let mut digits = [0u8; 20];
+    let mut len = 0;
+    let mut v = int_part;
+    loop {
+        digits[len] = (v % 10) as u8;
+        len += 1;
+        v /= 10;
+        if v == 0 {
+            break;
+        }
+    }
+    for i in (0..len).rev() {
+        out.push((b'0' + digits[i]) as char);
+        if i > 0 && i % 3 == 0 {
+            out.push(',');
+        }
+    }
+
+    if frac > 0.05 {
+        let _ = write!(out, ".{}", (frac * 10.0).round() as u8);
+    }
+}
+
+/// 上から 2 単位まで (「1億2345万」)。下の単位が 0 なら省く。
+fn push_japanese(out: &mut String, n: f64) {
+    let n = n.floor();
+    let exp = (n.log10() / 4.0).floor() as usize;
+    if exp > JAPANESE_UNITS.len() {
+        return push_scientific(out, n);
+    }
+    let unit = 1e4f64.powi(exp as i32);
+    let top = (n / unit).floor();
+    let rest = ((n - top * unit) / (unit / 1e4)).floor();
+    let _ = write!(out, "{}{}", top as u64, JAPANESE_UNITS[exp - 1]);
+    if rest >= 1.0 {
+        let _ = write!(out, "{}", rest as u64);
+        if exp >= 2 {
+            out.push_str(JAPANESE_UNITS[exp - 2]);
+        }
+    }
+}
+
+/// 有効数字 3 桁 + 接尾辞 (「1.23K」「45.6M」「789B」)。
+fn push_short(out: &mut String, n: f64) {
+    let mut exp = (n.log10() / 3.0).floor() as i32;
+    let mut v = n / 1e3f64.powi(exp);
+    // 999.5K のように丸めで 1000 に届いたら次の接尾辞へ
+    if v.round() >= 1000.0 {
+        exp += 1;
+        v /= 1000.0;
+    }
+    let Some(suffix) = (exp as usize).checked_sub(1).and_then(|i| SHORT_SUFFIXES.get(i)) else {
+        return push_scientific(out, n);
+    };
+    let _ = if v >= 99.95 {
+        write!(out, "{:.0}{}", v, suffix)
+    } else if v >= 9.995 {
+        write!(out, "{:.1}{}", v, suffix)
+    } else {
+        write!(out, "{:.2}{}", v, suffix)
+    };
+}
+
+/// 仮数は小数 2 桁 (「1.23e6」)。
+fn push_scientific(out: &mut String, n: f64) {
+    let mut exp = n.log10().floor() as i32;
+    let mut mantissa = n / 10f64.powi(exp);
+    if (mantissa * 100.0).round() >= 1000.0 {
+        exp += 1;
+        mantissa /= 10.0;
+    }
+    let _ = write!(out, "{:.2}e{}", mantissa, exp);
+}
+
+#[cfg(target_arch = "wasm32")]
+fn get_storage() -> Option<crate::storage::Store> {
+    crate::storage::get()
+}
+
+/// アクティブなプロファイルの表記を読み込んで有効にする。
+/// 未保存・破損時は既定値。プロファイルを切り替えたら呼び直すこと。
+pub fn load() -> Notation {
+    #[cfg(target_arch = "wasm32")]
+    let notation = get_storage()
+        .and_then(|s| s.get_item(&profile::scoped_key(NOTATION_KEY)).ok().flatten())
+        .and_then(|s| s.parse::<u8>().ok())
+        .map(Notation::from_index)
+        .unwrap_or_default();
+    #[cfg(not(target_arch = "wasm32"))]
+    let notation = Notation::default();
+    ACTIVE.with(|n| n.set(notation));
+    notation
+}
+
+/// 表記を保存して有効にする。保存に失敗しても今のセッションには効く。
+pub fn store(notation: Notation) {
+    ACTIVE.with(|n| n.set(notation));
+    #[cfg(target_arch = "wasm32")]
+    if let Some(storage) = get_storage() {
+        if let Err(e) = storage.set_item(&profile::scoped_key(NOTATION_KEY), &notation.index().to_string()) {
+            web_sys::console::warn_1(&format!("numfmt: localStorage への保存に失敗: {e:?}").into());
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn notation_index_roundtrips_and_cycles() {
+        for n in Notation::ALL {
+            assert_eq!(Notation::from_index(n.index()), n);
+        }
+        assert_eq!(Notation::from_index(99), Notation::Comma);
+        assert_eq!(Notation::Scientific.next(), Notation::Comma);
+    }
+
+    #[test]
+    fn comma_groups_thousands() {
+        assert_eq!(format_with(0.0, Notation::Comma), "0");
+        assert_eq!(format_with(1234567.0, Notation::Comma), "1,234,567");
+        assert_eq!(format_with(12.5, Notation::Comma), "12.5");
+        assert_eq!(format_with(-1234.0, Notation::Comma), "-1,234");
+    }
+
+    #[test]
+    fn japanese_units() {
+        assert_eq!(format_with(9999.0, Notation::Japanese), "9,999");
+        assert_eq!(format_with(12345.0, Notation::Japanese), "1万2345");
+        assert_eq!(format_with(1_0000_0000.0, Notation::Japanese), "1億");
+        assert_eq!(format_with(1_2345_6789.0, Notation::Japanese), "1億2345万");
+        assert_eq!(format_with(3_0000_0000_0000.0, Notation::Japanese), "3兆");
+        assert_eq!(format_with(1e25, Notation::Japanese), "1.00e25");
+    }
+
+    #[test]
+    fn short_suffixes() {
+        assert_eq!(format_with(999.0, Notation::Short), "999");
+        assert_eq!(format_with(1234.0, Notation::Short), "1.23K");
+        assert_eq!(format_with(45_600_000.0, Notation::Short), "45.6M");
+        assert_eq!(format_with(789e9, Notation::Short), "789B");
+        assert_eq!(format_with(999_999.0, Notation::Short), "1.00M");
+        assert_eq!(format_with(1e21, Notation::Short), "1.00e21");
+    }
+
+    #[test]
+    fn scientific_above_a_million() {
+        assert_eq!(format_with(999_999.0, Notation::Scientific), "999,999");
+        assert_eq!(format_with(1_234_567.0, Notation::Scientific), "1.23e6");
+        assert_eq!(format_with(9.999e8, Notation::Scientific), "1.00e9");
+    }
+
+    #[test]
+    fn format_follows_active_notation() {
+        store(Notation::Short);
+        assert_eq!(format(2500.0), "2.50K");
+        store(Notation::Comma);
+        assert_eq!(format(2500.0), "2,500");
+    }
+}
@@ -0,0 +1,317 @@
+//! プレイヤープロファイル (1 台の端末を家族で共有する用途)。
+//!
+//! 各ゲームのセーブキーは [`scoped_key`] を通してプロファイルごとに
+//! 名前空間化する。既定プロファイル (id 0) は素のキーをそのまま使うので、
+//! プロファイル機能導入前のセーブデータはそのまま既定プロファイルに載る。
+//!
+//! id は作成順に振る単調増加の値で、削除しても詰めない。並び順 (index) を
+//! キーに使うと、途中のプロファイルを消した時に後続のセーブが別人に
+//! すり替わってしまうため。
+//!
+//! プロファイル一覧の永続化は WASM ビルドのみ。native (cargo test) では
+//! 既定プロファイル 1 件だけの状態で動く。
+
+use std::cell::Cell;
+
+use serde::{Deserialize, Serialize};
+
+/// 作成できるプロファイルの上限。設定画面の 1 画面に収まる数に抑える。
+pub const MAX_PROFILES: usize = 4;
+
+/// プロファイル一覧を保存する localStorage キー (名前空間化しない)。
+#[cfg(target_arch = "wasm32")]
+const PROFILES_KEY: &str = "app_profiles";
+
+/// 名前空間化の対象になる全キー。プロファイル削除時にまとめて消す。
+/// [`scoped_key`] に渡すキーは必ずここに足すこと (足し忘れは debug ビルドで
+/// [`scoped_key`] が panic して知らせる)。
+const SCOPED_BASE_KEYS: &[&str] = &[
+    "cookie_factory_save",
+    "abyss_idle_save",
+    "metropolis_save",
+    "deep_drill_save",
+    "port_trader_save",
+    "app_last_played",
+    "app_keybindings",
+    "app_theme",
+    "app_screen_reader",
+    "app_play_stats",
+    "app_favorites",
+    "app_arcade_wallet",
+    "app_number_notation",
+    "dungeon_dive_meta",
+    "dungeon_dive_abyss",
+    "dungeon_dive_difficulty",
+    "dungeon_dive_bestiary",
+];
+
+thread_local! {
+    /// 現在アクティブなプロファイル id。セーブ処理はゲーム内部の深い所から
+    /// 呼ばれるため、引数で引き回さずここから引く。
+    static ACTIVE_ID: Cell<u32> = const { Cell::new(0) };
+}
+
+/// 1 件のプロファイル。
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct Profile {
+    pub id: u32,
+    pub name: String,
+}
+
+/// プロファイル一覧と、どれがアクティブか。
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct Profiles {
+    pub list: Vec<Profile>,
+    /// `list` 内の index。
+    pub active: usize,
+    /// 次に作成するプロファイルの id。
+    pub next_id: u32,
+}
+
+impl Profiles {
+    /// 既定プロファイル 1 件だけの初期状態。
+    pub fn new() -> Self {
+        Self {
+            list: vec![Profile {
+                id: 0,
+                name: default_name(0),
+            }],
+            active: 0,
+            next_id: 1,
+        }
+    }
+
+    pub fn active_profile(&self) -> &Profile {
+        &self.list[self.active]
+    }
+
+    /// 新規プロファイルを追加する。上限に達していれば false。
+    pub fn create(&mut self, name: Option<&str>) -> bool {
+        if self.list.len() >= MAX_PROFILES {
+            return false;
+        }
+        let id = self.next_id;
+        self.next_id += 1;
+        let name = match name.map(str::trim) {
+            Some(n) if !n.is_empty() => n.to_string(),
+            _ => default_name(id),
+        };
+        self.list.push(Profile { id, name });
+        true
+    }
+
+    /// 名前を変更する。空文字や範囲外は false。
+    pub fn rename(&mut self, index: usize, name: &str) -> bool {
+        let name = name.trim();
+        if name.is_empty() {
+            return false;
+        }
+        match self.list.get_mut(index) {
+            Some(p) => {
+                p.name = name.to_string();
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// プロファイルを削除し、消した id を返す。最後の 1 件は消せない。
+    /// アクティブなものを消した場合は先頭に切り替える。
+    pub fn delete(&mut self, index: usize) -> Option<u32> {
+        if self.list.len() <= 1 || index >= self.list.len() {
+            return None;
+        }
+        let removed = self.list.remove(index);
+        if self.active == index {
+            self.active = 0;
+        } else if self.active > index {
+            self.active -= 1;
+        }
+        Some(removed.id)
+    }
+
+    /// アクティブなプロファイルを切り替える。範囲外は false。
+    pub fn select(&mut self, index: usize) -> bool {
+        if index >= self.list.len() {
+            return false;
+        }
+        self.active = index;
+        true
+    }
+}
+
+impl Default for Profiles {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+fn default_name(id: u32) -> String {
+    format!("プレイヤー{}", id + 1)
+}
+
+/// `base` キーを指定プロファイル用に名前空間化する。
+/// 既定プロファイル (id 0) は後方互換のため素のキーを返す。
+pub fn key_for(base: &str, profile_id: u32) -> String {
+    if profile_id == 0 {
+        base.to_string()
+    } else {
+        format!("{base}@p{profile_id}")
+    }
+}
+
+/// 現在アクティブなプロファイル用のキー。`base` は [`SCOPED_BASE_KEYS`] に
+/// 登録済みであること。
+pub fn scoped_key(base: &str) -> String {
+    debug_assert!(
+        SCOPED_BASE_KEYS.contains(&base),
+        "{base} が SCOPED_BASE_KEYS に無い (プロファイル削除で消えなくなる)"
+    );
+    key_for(base, active_id())
+}
+
+pub fn active_id() -> u32 {
+    ACTIVE_ID.with(|c| c.get())
+}
+
+/// アクティブなプロファイル id を切り替える。以降のセーブ/ロードに効く。
+pub fn set_active_id(id: u32) {
+    ACTIVE_ID.with(|c| c.set(id));
+}
+
+#[cfg(target_arch = "wasm32")]
+fn get_storage() -> Option<crate::storage::Store> {
+    crate::storage::get()
+}
+
+/// 保存済みのプロファイル一覧を読み込み、アクティブ id を反映する。
+/// 未保存・破損時は既定の 1 件。
+pub fn load() -> Profiles {
+    #[cfg(target_arch = "wasm32")]
+    let profiles = get_storage()
+        .and_then(|s| s.get_item(PROFILES_KEY).ok().flatten())
+        .and_then(|json| serde_json::from_str::<Profiles>(&json).ok())
+        .filter(|p| !p.list.is_empty() && p.active < p.list.len())
+        .unwrap_or_default();
+    #[cfg(not(target_arch = "wasm32"))]
+    let profiles = Profiles::new();
+    set_active_id(profiles.active_profile().id);
+    profiles
+}
+
+/// プロファイル一覧を保存し、アクティブ id を反映する。
+pub fn store(profiles: &Profiles) {
+    set_active_id(profiles.active_profile().id);
+    #[cfg(target_arch = "wasm32")]
+    if let (Some(storage), Ok(json)) = (get_storage(), serde_json::to_string(profiles)) {
+        if let Err(e) = storage.set_item(PROFILES_KEY, &json) {
+            web_sys::console::warn_1(
+                &format!("profiles: localStorage への保存に失敗: {e:?}").into(),
+            );
+        }
+    }
+}
+
+/// 削除したプロファイルのセーブデータを全て消す。
+pub fn delete_data(profile_id: u32) {
+    if let Some(storage) = crate::storage::get() {
+        for base in SCOPED_BASE_KEYS {
+            let key = key_for(base, profile_id);
+            let _ = storage.remove_item(&key);
+            let _ = storage.remove_item(&crate::storage::trash_key(&key));
+        }
+    }
+}
+
+/// 名前入力を求める。ターミナル描画ではソフトキーボードを出せないため
+/// ブラウザ標準の `prompt()` を使う。キャンセル時・native では `None`。
+pub fn prompt_name(message: &str, default: &str) -> Option<String> {
+    #[cfg(target_arch = "wasm32")]
+    {
+        web_sys::window()?
+            .prompt_with_message_and_default(message, default)
+            .ok()
+            .flatten()
+    }
+    #[cfg(not(target_arch = "wasm32"))]
+    {
+        let _ = (message, default);
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn default_profile_uses_bare_keys() {
+        assert_eq!(key_for("cookie_factory_save", 0), "cookie_factory_save");
+        assert_eq!(key_for("cookie_factory_save", 3), "cookie_factory_save@p3");
+    }
+
+    #[test]
+    fn create_respects_limit_and_default_names() {
+        let mut p = Profiles::new();
+        assert!(p.create(None));
+        assert!(p.create(Some("  ")));
+        assert!(p.create(Some("たろう")));
+        assert!(!p.create(None));
+        assert_eq!(p.list.len(), MAX_PROFILES);
+        assert_eq!(p.list[1].name, "プレイヤー2");
+        assert_eq!(p.list[3].name, "たろう");
+    }
+
+    #[test]
+    fn ids_are_not_reused_after_delete() {
+        let mut p = Profiles::new();
+        p.create(None); // id 1
+        p.create(None); // id 2
+        assert_eq!(p.delete(1), Some(1));
+        p.create(None);
+        let ids: Vec<u32> = p.list.iter().map(|x| x.id).collect();
+        assert_eq!(ids, vec![0, 2, 3]);
+    }
+
+    #[test]
+    fn delete_keeps_active_pointing_at_same_profile() {
+        let mut p = Profiles::new();
+        p.create(None);
+        p.create(None);
+        p.select(2);
+        p.delete(0);
+        assert_eq!(p.active_profile().id, 2);
+        // Deleting the active profile falls back to the first one.
+        p.delete(p.active);
+        assert_eq!(p.active, 0);
+        // The last profile can never be deleted.
+        assert_eq!(p.delete(0), None);
+    }
+
+    #[test]
+    fn delete_data_removes_every_scoped_key_of_that_profile_only() {
+        let store = crate::storage::get().unwrap();
+        for base in SCOPED_BASE_KEYS {
+            store.set_item(&key_for(base, 0), "keep").unwrap();
+            store.set_item(&key_for(base, 2), "gone").unwrap();
+        }
+        assert!(SCOPED_BASE_KEYS.contains(&"dungeon_dive_abyss"));
+        assert!(SCOPED_BASE_KEYS.contains(&"app_number_notation"));
+
+        delete_data(2);
+        for base in SCOPED_BASE_KEYS {
+            assert_eq!(store.get_item(&key_for(base, 2)).unwrap(), None, "{base}");
+            assert_eq!(store.get_item(&key_for(base, 0)).unwrap().as_deref(), Some("keep"));
+            store.remove_item(&key_for(base, 0)).unwrap();
+        }
+    }
+
+    #[test]
+    fn rename_rejects_blank() {
+        let mut p = Profiles::new();
+        assert!(!p.rename(0, "   "));
+        assert!(p.rename(0, " はなこ "));
+        assert_eq!(p.list[0].name, "はなこ");
+        assert!(!p.rename(5, "x"));
+    }
+}
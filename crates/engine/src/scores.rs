@@ -0,0 +1,171 @@
+//! 共有用スコア (リーダーボード向けエクスポート)。
+//!
+//! 各ゲームは [`crate::game::Game::score`] で「そのゲームを代表する 1 つの数値」
+//! を返す。ここではそれを外部に貼り付けられる短い文字列 (スコアトークン) に
+//! 変換 / 復元する。
+//!
+//! トークン形式: `CSGE1-<game id>-<値 (16 hex, 難読化)>-<署名 (8 hex)>`
+//!
+//! - 値はゲーム ID 由来の鍵と XOR して、数字の書き換えが一目で分からないようにする。
+//! - 署名は ID・値・ソルトの FNV-1a ハッシュ。1 桁でも書き換えると検証に失敗する。
+//!
+//! 鍵とソルトはクライアントに埋め込まれているので暗号学的な保証はない。
+//! 「手で数字を盛る」程度の改ざんを弾くのが目的。
+
+use crate::game::GameChoice;
+
+/// トークンの先頭に付ける形式タグ。形式を変えるときは数字を上げる。
+const TOKEN_PREFIX: &str = "CSGE1";
+
+/// 署名に混ぜるソルト。変えると既存トークンがすべて無効になる。
+const SIGN_SALT: &str = "cli-sim-game-escape/score";
+
+/// ゲームを代表するスコア。
+#[derive(Clone, Debug, PartialEq)]
+pub struct Score {
+    /// 数値 (大きいほど良い)。
+    pub value: u64,
+    /// 何を測った値か (「天国チップ」など)。
+    pub label: &'static str,
+    /// 値の後ろに付ける単位 (`"F"`, `"m"` など。無ければ空)。
+    pub unit: &'static str,
+}
+
+impl Score {
+    pub fn new(value: u64, label: &'static str, unit: &'static str) -> Self {
+        Self { value, label, unit }
+    }
+}
+
+fn fnv1a(bytes: &[u8]) -> u64 {
+    let mut h: u64 = 0xcbf2_9ce4_8422_2325;
+    for b in bytes {
+        h ^= *b as u64;
+        h = h.wrapping_mul(0x0000_0100_0000_01b3);
+    }
+    h
+}
+
+fn value_key(choice: &GameChoice) -> u64 {
+    fnv1a(choice.id().as_bytes()).rotate_left(17) ^ 0x5a5a_a5a5_3c3c_c3c3
+}
+
+fn signature(choice: &GameChoice, value: u64) -> u32 {
+    let h = fnv1a(format!("{}:{}:{}", choice.id(), value, SIGN_SALT).as_bytes());
+    (h ^ (h >> 32)) as u32
+}
+
+/// スコアトークンを作る。
+pub fn encode_token(choice: &GameChoice, value: u64) -> String {
+    format!(
+        "{}-{}-{:016X}-{:08X}",
+        TOKEN_PREFIX,
+        choice.id(),
+        value ^ value_key(choice),
+        signature(choice, value)
+    )
+}
+
+/// [`encode_token`] の逆変換。形式不正・未知のゲーム・署名不一致は `None`。
+pub fn decode_token(token: &str) -> Option<(GameChoice, u64)> {
+    let mut parts = token.trim().split('-');
+    if parts.next()? != TOKEN_PREFIX {
+        return None;
+    }
+    let choice = GameChoice::from_id(parts.next()?)?;
+    let obf = parts.next()?;
+    let sig = parts.next()?;
+    if parts.next().is_some() || obf.len() != 16 || sig.len() != 8 {
+        return None;
+    }
+    let value = u64::from_str_radix(obf, 16).ok()? ^ value_key(&choice);
+    let sig = u32::from_str_radix(sig, 16).ok()?;
+    (sig == signature(&choice, value)).then_some((choice, value))
+}
+
+#[cfg(target_arch = "wasm32")]
+mod clipboard {
+    use wasm_bindgen::prelude::*;
+
+    #[wasm_bindgen]
+    extern "C" {
+        // index.html の `<script>` 内で `window.__copyText` として定義。
+        #[wasm_bindgen(js_namespace = window, js_name = __copyText, catch)]
+        fn js_copy_text(text: &str) -> Result<bool, JsValue>;
+    }
+
+    pub fn copy(text: &str) -> bool {
+        js_copy_text(text).unwrap_or(false)
+    }
+}
+
+/// クリップボードへコピーする。実体は `index.html` の `window.__copyText`。
+/// 非対応ブラウザや非セキュアコンテキスト、native (cargo test) では `false`。
+/// 書き込み自体は非同期なので、`true` は「依頼できた」ことしか意味しない。
+pub fn copy_to_clipboard(text: &str) -> bool {
+    #[cfg(target_arch = "wasm32")]
+    {
+        clipboard::copy(text)
+    }
+    #[cfg(not(target_arch = "wasm32"))]
+    {
+        let _ = text;
+        false
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn token_roundtrip_for_every_game() {
+        for c in GameChoice::all() {
+            for v in [0, 1, 42, 1_000_000_007, u64::MAX] {
+                let t = encode_token(&c, v);
+                assert_eq!(decode_token(&t), Some((c.clone(), v)), "{}", t);
+            }
+        }
+    }
+
+    #[test]
+    fn token_hides_plain_value() {
+        let t = encode_token(&GameChoice::Cookie, 12345);
+        assert!(!t.contains("12345"), "{}", t);
+        assert!(!t.contains(&format!("{:X}", 12345)), "{}", t);
+    }
+
+    #[test]
+    fn tampered_token_is_rejected() {
+        let t = encode_token(&GameChoice::Drill, 500);
+        // 値部分の 1 文字を書き換える
+        let mut bytes = t.clone().into_bytes();
+        let i = TOKEN_PREFIX.len() + "-drill-".len() + 15;
+        bytes[i] = if bytes[i] == b'0' { b'1' } else { b'0' };
+        let forged = String::from_utf8(bytes).unwrap();
+        assert_ne!(forged, t);
+        assert_eq!(decode_token(&forged), None);
+
+        // 別ゲームの ID に付け替えても通らない
+        let moved = t.replace("-drill-", "-port-");
+        assert_eq!(decode_token(&moved), None);
+    }
+
+    #[test]
+    fn malformed_tokens_are_rejected() {
+        for t in [
+            "",
+            "CSGE1",
+            "CSGE0-cookie-0000000000000000-00000000",
+            "CSGE1-career-0-0",
+        ] {
+            assert_eq!(decode_token(t), None, "{}", t);
+        }
+        let ok = encode_token(&GameChoice::Rpg, 7);
+        assert_eq!(
+            decode_token(&format!("  {}\n", ok)),
+            Some((GameChoice::Rpg, 7))
+        );
+        assert_eq!(decode_token(&format!("{}-extra", ok)), None);
+    }
+}
@@ -1,9 +1,10 @@
 //! 操作フィードバック (効果音 + ハプティクス) の薄いラッパー。
 //!
-//! 実体は `index.html` の `window.__playSound(name)`。同じ name で
+//! 実体は `index.html` の `window.__playSound(name)`。同じ name で次の 2 つを駆動する:
 //! - 効果音: Web Audio API で oscillator + envelope を合成 (`SOUNDS` テーブル)
 //! - 振動:   Vibration API (`navigator.vibrate`) で触覚 (`VIBRATION` テーブル)
-//! を駆動する。振動は Android Chrome 等のみで、iOS Safari は Vibration API
+//!
+//! 振動は Android Chrome 等のみで、iOS Safari は Vibration API
 //! 非対応のため別途 index.html 側の touch ハンドラが `<input switch>` トリック
 //! でタップ触覚を出す (こちらは `play()` 経由ではなく実ジェスチャ駆動)。
 //!
@@ -0,0 +1,227 @@
+//! セーブの置き場 (localStorage) への共通の窓口。
+//!
+//! プライベートブラウズや埋め込み先の制限で localStorage が無い・書けない
+//! 環境では、起動時の [`init`] で検出してメモリ上の置き場に切り替える。
+//! 各セーブ処理は [`get`] の戻り値を localStorage と同じ感覚で使えばよく、
+//! 保存は成功扱いになる (タブを閉じれば消える)。プレイヤーには
+//! [`is_persistent`] を見たアプリシェルが常時警告チップを出す。
+//!
+//! native (cargo test) では localStorage に触らず、常にメモリ置き場になる。
+//!
+//! 消したセーブを取り消せるよう、キーごとに「ゴミ箱」の副スロットを 1 つ
+//! 持てる ([`move_to_trash`] / [`restore_from_trash`])。[`TRASH_TTL_MS`] を
+//! 過ぎた退避は読むときに捨てる。
+
+use std::cell::{Cell, RefCell};
+use std::collections::HashMap;
+
+use web_sys::wasm_bindgen::JsValue;
+
+thread_local! {
+    /// 起動時の検出結果。`false` ならメモリ置き場を使っている。
+    static PERSISTENT: Cell<bool> = const { Cell::new(true) };
+    /// localStorage の代わりのメモリ置き場。
+    static MEMORY: RefCell<HashMap<String, String>> = RefCell::new(HashMap::new());
+}
+
+/// [`get`] が返す置き場。`web_sys::Storage` と同じ名前・形のメソッドを持つ。
+pub enum Store {
+    #[cfg(target_arch = "wasm32")]
+    Local(web_sys::Storage),
+    Memory,
+}
+
+impl Store {
+    pub fn get_item(&self, key: &str) -> Result<Option<String>, JsValue> {
+        match self {
+            #[cfg(target_arch = "wasm32")]
+            Store::Local(s) => s.get_item(key),
+            Store::Memory => Ok(MEMORY.with(|m| m.borrow().get(key).cloned())),
+        }
+    }
+
+    pub fn set_item(&self, key: &str, value: &str) -> Result<(), JsValue> {
+        match self {
+            #[cfg(target_arch = "wasm32")]
+            Store::Local(s) => s.set_item(key, value),
+            Store::Memory => {
+                MEMORY.with(|m| m.borrow_mut().insert(key.to_string(), value.to_string()));
+                Ok(())
+            }
+        }
+    }
+
+    pub fn remove_item(&self, key: &str) -> Result<(), JsValue> {
+        match self {
+            #[cfg(target_arch = "wasm32")]
+            Store::Local(s) => s.remove_item(key),
+            Store::Memory => {
+                MEMORY.with(|m| m.borrow_mut().remove(key));
+                Ok(())
+            }
+        }
+    }
+}
+
+/// 書き込みまで試して使えるか確かめる。Safari のプライベートブラウズは
+/// `localStorage` 自体はあっても `setItem` で例外を投げるため。
+#[cfg(target_arch = "wasm32")]
+fn probe_local_storage() -> Option<web_sys::Storage> {
+    const PROBE_KEY: &str = "__storage_probe";
+    let storage = web_sys::window()?.local_storage().ok()??;
+    storage.set_item(PROBE_KEY, "1").ok()?;
+    storage.remove_item(PROBE_KEY).ok()?;
+    Some(storage)
+}
+
+/// 起動時に 1 回呼ぶ。localStorage が使えなければメモリ置き場に切り替える。
+pub fn init() {
+    #[cfg(target_arch = "wasm32")]
+    let ok = probe_local_storage().is_some();
+    #[cfg(not(target_arch = "wasm32"))]
+    let ok = false;
+    PERSISTENT.with(|p| p.set(ok));
+    #[cfg(target_arch = "wasm32")]
+    if !ok {
+        web_sys::console::warn_1(
+            &"storage: localStorage が使えないため、セーブはこのタブの間だけ保持します".into(),
+        );
+    }
+}
+
+/// セーブがタブを閉じても残るか。`false` なら警告を出す。
+pub fn is_persistent() -> bool {
+    PERSISTENT.with(|p| p.get())
+}
+
+/// 今使うべき置き場。
+pub fn get() -> Option<Store> {
+    #[cfg(target_arch = "wasm32")]
+    if is_persistent() {
+        return web_sys::window()?.local_storage().ok()?.map(Store::Local);
+    }
+    Some(Store::Memory)
+}
+
+/// ゴミ箱に退避したセーブを取り消せる期間 (24 時間)。
+pub const TRASH_TTL_MS: u64 = 24 * 60 * 60 * 1000;
+
+/// `key` のゴミ箱スロットのキー。値は `"<退避時刻 ms>:<元の中身>"`。
+pub fn trash_key(key: &str) -> String {
+    format!("{key}#trash")
+}
+
+/// [`move_to_trash`] が退避しなかった理由。どちらの場合も元の中身と
+/// 前の退避には触らない。
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum TrashError {
+    /// 退避する中身が無い。
+    Empty,
+    /// ゴミ箱に書けなかった (容量不足など)。
+    WriteFailed,
+}
+
+/// `key` の中身をゴミ箱へ移す (前の退避は上書き)。
+pub fn move_to_trash(key: &str, now_ms: u64) -> Result<(), TrashError> {
+    let store = get().ok_or(TrashError::WriteFailed)?;
+    let data = store
+        .get_item(key)
+        .ok()
+        .flatten()
+        .ok_or(TrashError::Empty)?;
+    store
+        .set_item(&trash_key(key), &format!("{now_ms}:{data}"))
+        .map_err(|_| TrashError::WriteFailed)?;
+    let _ = store.remove_item(key);
+    Ok(())
+}
+
+/// ゴミ箱の中身 (退避時刻と元の中身)。期限切れ・壊れた値はここで捨てる。
+/// 時計が戻って退避時刻が未来になっていても期限内として扱う。
+fn read_trash(store: &Store, key: &str, now_ms: u64) -> Option<(u64, String)> {
+    let raw = store.get_item(&trash_key(key)).ok().flatten()?;
+    let entry = raw
+        .split_once(':')
+        .and_then(|(at, data)| Some((at.parse::<u64>().ok()?, data.to_string())));
+    match entry {
+        Some((at, data)) if now_ms.saturating_sub(at) < TRASH_TTL_MS => Some((at, data)),
+        _ => {
+            let _ = store.remove_item(&trash_key(key));
+            None
+        }
+    }
+}
+
+/// `key` のゴミ箱に期限内の退避があれば、その退避時刻 (ms)。
+pub fn trashed_at(key: &str, now_ms: u64) -> Option<u64> {
+    read_trash(&get()?, key, now_ms).map(|(at, _)| at)
+}
+
+/// 期限内の退避を `key` に戻してゴミ箱を空にする。今 `key` にある中身は
+/// 上書きされる。戻せたら `true`。
+pub fn restore_from_trash(key: &str, now_ms: u64) -> bool {
+    let Some(store) = get() else { return false };
+    let Some((_, data)) = read_trash(&store, key, now_ms) else {
+        return false;
+    };
+    if store.set_item(key, &data).is_err() {
+        return false;
+    }
+    let _ = store.remove_item(&trash_key(key));
+    true
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn trash_moves_and_restores_within_ttl() {
+        let key = "storage_trash_test";
+        // 中身が無ければ何もしない
+        assert_eq!(move_to_trash(key, 0), Err(TrashError::Empty));
+
+        let store = get().unwrap();
+        store.set_item(key, "{\"v\":1}").unwrap();
+        assert_eq!(move_to_trash(key, 1_000), Ok(()));
+        assert_eq!(store.get_item(key).unwrap(), None);
+        assert_eq!(trashed_at(key, 1_000 + TRASH_TTL_MS - 1), Some(1_000));
+
+        // 新しく始めたセーブは取り消しで上書きされる
+        store.set_item(key, "new").unwrap();
+        assert!(restore_from_trash(key, 2_000));
+        assert_eq!(store.get_item(key).unwrap().as_deref(), Some("{\"v\":1}"));
+        assert_eq!(trashed_at(key, 2_000), None);
+        store.remove_item(key).unwrap();
+    }
+
+    #[test]
+    fn expired_trash_is_discarded() {
+        let key = "storage_trash_expiry_test";
+        let store = get().unwrap();
+        store.set_item(key, "old").unwrap();
+        assert_eq!(move_to_trash(key, 0), Ok(()));
+        assert!(!restore_from_trash(key, TRASH_TTL_MS));
+        assert_eq!(store.get_item(&trash_key(key)).unwrap(), None);
+        assert_eq!(store.get_item(key).unwrap(), None);
+    }
+
+    #[test]
+    fn memory_store_roundtrips_and_removes() {
+        let store = Store::Memory;
+        assert_eq!(store.get_item("storage_test_key").unwrap(), None);
+        store.set_item("storage_test_key", "v1").unwrap();
+        assert_eq!(store.get_item("storage_test_key").unwrap().as_deref(), Some("v1"));
+        store.set_item("storage_test_key", "v2").unwrap();
+        assert_eq!(store.get_item("storage_test_key").unwrap().as_deref(), Some("v2"));
+        store.remove_item("storage_test_key").unwrap();
+        assert_eq!(store.get_item("storage_test_key").unwrap(), None);
+    }
+
+    #[test]
+    fn native_falls_back_to_memory() {
+        init();
+        assert!(!is_persistent());
+        assert!(matches!(get(), Some(Store::Memory)));
+    }
+}
@@ -0,0 +1,314 @@
+//! 配色 (パレット) の切り替え。
+//!
+//! 赤/緑の見分けに頼る表示 (HP バー・敵の危険度など) は、色を直接
+//! 書かずに [`active`] の [`Theme`] から引く。色覚サポート配色では
+//! 色を青/橙の組 (Okabe–Ito) に替え、さらに `shapes` を立てて
+//! 形 (♥ の数・▲ の数) でも読めるようにする。
+//!
+//! 共通画面 (メニュー・設定など) は [`Theme`] の基調色 (primary / accent /
+//! dim) を直接引く。各ゲームが直に書いた色は、描画し終えた画面全体を
+//! [`apply`] で配色に寄せる (高コントラストは明るい側へ、ダークブルーは紺地、
+//! モノクロは灰色の濃淡へ)。
+//!
+//! 設定は [`crate::profile::scoped_key`] でプロファイルごとに保存する。
+//! localStorage は WASM ビルドでのみ触る。native (cargo test) では読み込みは
+//! 常に既定値、書き込みは no-op。
+
+use std::cell::Cell;
+
+use ratzilla::ratatui::buffer::Buffer;
+use ratzilla::ratatui::style::{Color, Modifier};
+
+#[cfg(target_arch = "wasm32")]
+use crate::profile;
+
+/// localStorage のキー。値は [`Palette::index`]。
+#[cfg(target_arch = "wasm32")]
+const THEME_KEY: &str = "app_theme";
+
+/// 選べる配色。
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Default)]
+pub enum Palette {
+    #[default]
+    Standard,
+    /// 赤緑の区別が難しくても読める配色 + 形による表示。
+    ColorblindSafe,
+    /// 暗い色を明るい側へ寄せ、DIM をやめる。
+    HighContrast,
+    /// 紺地に青系の基調色。
+    DarkBlue,
+    /// 灰色の濃淡だけ。色の区別は形で補う。
+    Monochrome,
+}
+
+impl Palette {
+    /// 保存値は [`index`](Self::index) なので、新しい配色は末尾に足すこと。
+    pub const ALL: [Palette; 5] = [
+        Palette::Standard,
+        Palette::ColorblindSafe,
+        Palette::HighContrast,
+        Palette::DarkBlue,
+        Palette::Monochrome,
+    ];
+
+    /// 設定画面の表示名。
+    pub fn label(self) -> &'static str {
+        match self {
+            Palette::Standard => "標準",
+            Palette::ColorblindSafe => "色覚サポート",
+            Palette::HighContrast => "高コントラスト",
+            Palette::DarkBlue => "ダークブルー",
+            Palette::Monochrome => "モノクロ",
+        }
+    }
+
+    /// 次の配色 (設定画面で押すたびに巡回)。
+    pub fn next(self) -> Self {
+        let i = Palette::ALL.iter().position(|&p| p == self).unwrap_or(0);
+        Palette::ALL[(i + 1) % Palette::ALL.len()]
+    }
+
+    pub fn index(self) -> u8 {
+        Palette::ALL.iter().position(|&p| p == self).unwrap_or(0) as u8
+    }
+
+    /// [`index`](Self::index) の逆。範囲外は既定値。
+    pub fn from_index(i: u8) -> Self {
+        Palette::ALL.get(i as usize).copied().unwrap_or_default()
+    }
+}
+
+/// render 時に引く、意味づけされた色。
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Theme {
+    pub palette: Palette,
+    /// 見出し・枠・選択肢など画面の基調色。
+    pub primary: Color,
+    /// 値や選択中の項目など、目を引かせたい所。
+    pub accent: Color,
+    /// 補足文・非活性の項目。
+    pub dim: Color,
+    /// 画面の地の色。`Reset` なら端末 (ページ) の背景のまま。
+    pub bg: Color,
+    /// 安全・十分 (HP 多め、弱い敵)。
+    pub good: Color,
+    /// 注意 (HP 半分以下、手強い敵)。
+    pub warn: Color,
+    /// 危険 (HP 瀕死、致命的な敵、取り消せない操作)。
+    pub danger: Color,
+    /// 色だけでなく形でも状態を示す (♥ の HP、▲ の危険度)。
+    pub shapes: bool,
+}
+
+impl Theme {
+    pub fn for_palette(palette: Palette) -> Self {
+        let standard = Theme {
+            palette,
+            primary: Color::Cyan,
+            accent: Color::Yellow,
+            dim: Color::DarkGray,
+            bg: Color::Reset,
+            good: Color::Green,
+            warn: Color::Yellow,
+            danger: Color::Red,
+            shapes: false,
+        };
+        match palette {
+            Palette::Standard => standard,
+            Palette::ColorblindSafe => Theme {
+                good: Color::Rgb(86, 180, 233), // sky blue
+                warn: Color::Rgb(230, 159, 0),  // orange
+                danger: Color::Rgb(213, 94, 0), // vermillion
+                shapes: true,
+                ..standard
+            },
+            Palette::HighContrast => Theme {
+                primary: Color::LightCyan,
+                accent: Color::LightYellow,
+                dim: Color::Gray,
+                bg: Color::Black,
+                good: Color::LightGreen,
+                warn: Color::LightYellow,
+                danger: Color::LightRed,
+                shapes: true,
+                ..standard
+            },
+            Palette::DarkBlue => Theme {
+                primary: Color::LightBlue,
+                dim: Color::Rgb(100, 120, 160),
+                bg: Color::Rgb(10, 18, 40),
+                danger: Color::LightRed,
+                ..standard
+            },
+            Palette::Monochrome => Theme {
+                primary: Color::White,
+                accent: Color::White,
+                good: Color::White,
+                warn: Color::Gray,
+                danger: Color::White,
+                shapes: true,
+                ..standard
+            },
+        }
+    }
+
+    /// 描画済みの色をこの配色へ寄せる。個々の render が直接書いた色にも
+    /// 効かせるための後処理で、[`apply`] から呼ばれる。
+    pub fn remap(&self, color: Color) -> Color {
+        match self.palette {
+            Palette::Standard | Palette::ColorblindSafe => color,
+            // 暗い基本色を明るい版へ
+            Palette::HighContrast => match color {
+                Color::DarkGray => Color::Gray,
+                Color::Gray => Color::White,
+                Color::Red => Color::LightRed,
+                Color::Green => Color::LightGreen,
+                Color::Yellow => Color::LightYellow,
+                Color::Blue => Color::LightBlue,
+                Color::Magenta => Color::LightMagenta,
+                Color::Cyan => Color::LightCyan,
+                other => other,
+            },
+            // 補足文の灰色だけ青みに
+            Palette::DarkBlue => match color {
+                Color::DarkGray => self.dim,
+                other => other,
+            },
+            // 明るさだけ残して灰色 3 段に
+            Palette::Monochrome => match color {
+                Color::Reset | Color::Black | Color::DarkGray | Color::Gray | Color::White => color,
+                Color::Rgb(r, g, b) => match (r as u32 * 3 + g as u32 * 6 + b as u32) / 10 {
+                    0..=40 => Color::Black,
+                    41..=110 => Color::DarkGray,
+                    111..=190 => Color::Gray,
+                    _ => Color::White,
+                },
+                Color::Red | Color::Green | Color::Blue | Color::Magenta => Color::Gray,
+                _ => Color::White,
+            },
+        }
+    }
+}
+
+/// 描画済みの `buf` 全体に現在のテーマを当てる。main.rs が毎フレーム
+/// 最後に呼ぶ。標準・色覚サポート配色では何もしない。
+pub fn apply(buf: &mut Buffer) {
+    let theme = active();
+    if matches!(theme.palette, Palette::Standard | Palette::ColorblindSafe) {
+        return;
+    }
+    for cell in buf.content.iter_mut() {
+        cell.fg = theme.remap(cell.fg);
+        cell.bg = if cell.bg == Color::Reset { theme.bg } else { theme.remap(cell.bg) };
+        if theme.palette == Palette::HighContrast {
+            cell.modifier.remove(Modifier::DIM);
+        }
+    }
+}
+
+thread_local! {
+    /// 現在の配色。render のたびに引くため localStorage は読まない。
+    static ACTIVE: Cell<Palette> = const { Cell::new(Palette::Standard) };
+}
+
+/// 現在の配色のテーマ。
+pub fn active() -> Theme {
+    Theme::for_palette(ACTIVE.with(|p| p.get()))
+}
+
+#[cfg(target_arch = "wasm32")]
+fn get_storage() -> Option<crate::storage::Store> {
+    crate::storage::get()
+}
+
+/// アクティブなプロファイルの配色を読み込んで有効にする。
+/// 未保存・破損時は既定値。プロファイルを切り替えたら呼び直すこと。
+pub fn load() -> Palette {
+    #[cfg(target_arch = "wasm32")]
+    let palette = get_storage()
+        .and_then(|s| s.get_item(&profile::scoped_key(THEME_KEY)).ok().flatten())
+        .and_then(|s| s.parse::<u8>().ok())
+        .map(Palette::from_index)
+        .unwrap_or_default();
+    #[cfg(not(target_arch = "wasm32"))]
+    let palette = Palette::default();
+    ACTIVE.with(|p| p.set(palette));
+    palette
+}
+
+/// 配色を保存して有効にする。保存に失敗しても今のセッションには効く。
+pub fn store(palette: Palette) {
+    ACTIVE.with(|p| p.set(palette));
+    #[cfg(target_arch = "wasm32")]
+    if let Some(storage) = get_storage() {
+        if let Err(e) = storage.set_item(&profile::scoped_key(THEME_KEY), &palette.index().to_string()) {
+            web_sys::console::warn_1(&format!("theme: localStorage への保存に失敗: {e:?}").into());
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn palette_index_roundtrips_and_cycles() {
+        for p in Palette::ALL {
+            assert_eq!(Palette::from_index(p.index()), p);
+        }
+        assert_eq!(Palette::from_index(99), Palette::Standard);
+        assert_eq!(Palette::Standard.next(), Palette::ColorblindSafe);
+        assert_eq!(Palette::Monochrome.next(), Palette::Standard);
+    }
+
+    #[test]
+    fn colorblind_palette_avoids_red_green_and_uses_shapes() {
+        let t = Theme::for_palette(Palette::ColorblindSafe);
+        assert!(t.shapes);
+        for c in [t.good, t.warn, t.danger] {
+            assert!(!matches!(c, Color::Red | Color::Green | Color::LightRed | Color::LightGreen));
+        }
+        store(Palette::ColorblindSafe);
+        assert_eq!(active(), t);
+        store(Palette::Standard);
+        assert!(!active().shapes);
+    }
+
+    #[test]
+    fn high_contrast_brightens_buffer_and_drops_dim() {
+        use ratzilla::ratatui::layout::Rect;
+        use ratzilla::ratatui::style::Style;
+
+        let mut buf = Buffer::empty(Rect::new(0, 0, 2, 1));
+        buf.set_style(Rect::new(0, 0, 2, 1), Style::default().fg(Color::DarkGray).add_modifier(Modifier::DIM));
+        apply(&mut buf);
+        assert_eq!(buf.content[0].fg, Color::DarkGray, "standard palette leaves the frame alone");
+
+        store(Palette::HighContrast);
+        apply(&mut buf);
+        store(Palette::Standard);
+        assert_eq!(buf.content[0].fg, Color::Gray);
+        assert!(!buf.content[1].modifier.contains(Modifier::DIM));
+        assert_eq!(buf.content[0].bg, Color::Black);
+    }
+
+    #[test]
+    fn dark_blue_fills_background_and_monochrome_drops_hue() {
+        let dark = Theme::for_palette(Palette::DarkBlue);
+        assert_eq!(dark.remap(Color::DarkGray), dark.dim);
+        assert_eq!(dark.remap(Color::Green), Color::Green);
+
+        let mono = Theme::for_palette(Palette::Monochrome);
+        assert!(mono.shapes);
+        for c in [Color::Red, Color::LightGreen, Color::Cyan, Color::Rgb(230, 159, 0)] {
+            assert!(matches!(mono.remap(c), Color::Black | Color::DarkGray | Color::Gray | Color::White), "{c:?}");
+        }
+        assert_eq!(mono.remap(Color::Rgb(10, 10, 10)), Color::Black);
+
+        let mut buf = Buffer::empty(ratzilla::ratatui::layout::Rect::new(0, 0, 1, 1));
+        store(Palette::DarkBlue);
+        apply(&mut buf);
+        store(Palette::Standard);
+        assert_eq!(buf.content[0].bg, dark.bg);
+    }
+}
@@ -3,6 +3,20 @@
 //! `draw_web()` calls at ~60fps with variable delta. GameTime converts
 //! this into a fixed number of discrete ticks per second, making game
 //! logic deterministic and fully testable.
+//!
+//! Logic ticks are counted independently of frames: a slow frame yields
+//! several ticks (up to [`MAX_CATCH_UP_MS`] worth), a fast one none, and
+//! the remainder carries over.  The rate defaults to [`TICKS_PER_SEC`];
+//! a game may ask for another via `Game::tick_rate`.
+
+/// Default logic rate.  Game logic and UI timers (toasts, countdowns)
+/// that count in ticks assume this rate.
+pub const TICKS_PER_SEC: u32 = 10;
+
+/// Longest wall-clock gap one update catches up on.  A backgrounded tab
+/// resumes with at most this much simulated time instead of a burst of
+/// thousands of ticks (spiral of death).
+pub const MAX_CATCH_UP_MS: f64 = 500.0;
 
 pub struct GameTime {
     /// Milliseconds per tick (e.g. 100ms = 10 ticks/sec)
@@ -20,13 +34,24 @@ impl GameTime {
     /// `ticks_per_sec`: how many game ticks per real-time second (e.g. 10).
     pub fn new(ticks_per_sec: u32) -> Self {
         Self {
-            ms_per_tick: 1000.0 / ticks_per_sec as f64,
+            ms_per_tick: 1000.0 / ticks_per_sec.max(1) as f64,
             accumulator: 0.0,
             total_ticks: 0,
             last_timestamp: None,
         }
     }
 
+    /// Current rate in ticks per second.
+    pub fn ticks_per_sec(&self) -> u32 {
+        (1000.0 / self.ms_per_tick).round() as u32
+    }
+
+    /// Switch the tick rate (e.g. when a game with its own rate starts).
+    /// The time already accumulated is kept, so no partial tick is lost.
+    pub fn set_rate(&mut self, ticks_per_sec: u32) {
+        self.ms_per_tick = 1000.0 / ticks_per_sec.max(1) as f64;
+    }
+
     /// Feed wall-clock timestamp (from `performance.now()` or similar).
     /// Returns the number of discrete ticks to process this frame.
     ///
@@ -37,7 +62,7 @@ impl GameTime {
             Some(prev) => {
                 let d = now_ms - prev;
                 // Clamp to avoid spiral-of-death if tab was backgrounded
-                d.clamp(0.0, 500.0)
+                d.clamp(0.0, MAX_CATCH_UP_MS)
             }
             None => 0.0, // First frame: no delta
         };
@@ -129,6 +154,19 @@ mod tests {
         assert!((9..=11).contains(&total), "expected ~10 ticks, got {}", total);
     }
 
+    #[test]
+    fn faster_rate_yields_more_ticks_and_keeps_remainder() {
+        let mut gt = GameTime::new(TICKS_PER_SEC);
+        gt.update(0.0);
+        assert_eq!(gt.update(75.0), 0); // 75ms banked at 10Hz
+        gt.set_rate(20);
+        assert_eq!(gt.ticks_per_sec(), 20);
+        // 75 + 75 = 150ms at 50ms/tick → 3 ticks
+        assert_eq!(gt.update(150.0), 3);
+        // Catch-up cap scales with the rate: 500ms at 20Hz = 10 ticks
+        assert_eq!(gt.update(60_000.0), 10);
+    }
+
     #[test]
     fn add_ticks_directly() {
         let mut gt = GameTime::new(10);
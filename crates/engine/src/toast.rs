@@ -0,0 +1,182 @@
+//! 画面隅に一定時間だけ出す通知 (トースト) のキュー。
+//!
+//! ログに流れると見逃しやすい出来事 (マイルストーン達成・ゴールデン
+//! クッキー出現・レベルアップなど) を、ゲームのロジックから
+//! [`push`] で積む。[`crate::sound::play`] と同じく呼び出し側は状態を
+//! 持ち回らなくてよく、`tick` / logic のどこからでも呼べる。
+//!
+//! 積んだ文言は [`crate::a11y::announce`] で読み上げにも流す。
+//!
+//! キューは main.rs が毎フレーム [`tick`] で寿命を減らし、[`render`] で
+//! 全画面の上に右上へ重ねて描く。クリック判定は登録しない。
+
+use std::cell::RefCell;
+use std::collections::VecDeque;
+
+use ratzilla::ratatui::layout::Rect;
+use ratzilla::ratatui::style::{Color, Modifier, Style};
+use ratzilla::ratatui::text::Span;
+use ratzilla::ratatui::widgets::{Clear, Paragraph};
+use ratzilla::ratatui::Frame;
+
+use crate::{a11y, theme};
+
+/// 既定の表示時間 (tick, 10 tick = 1 秒)。
+pub const DEFAULT_TTL: u32 = 40;
+
+/// 同時に保持する最大数。あふれたら古いものから捨てる
+/// (オフライン進行でまとめて積まれても画面を埋めない)。
+pub const MAX_TOASTS: usize = 3;
+
+/// 通知の重要度。色と先頭の記号が変わる。
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Severity {
+    Info,
+    Success,
+    Warning,
+    Danger,
+}
+
+impl Severity {
+    /// 色だけに頼らないよう、重要度ごとに記号も変える。
+    fn icon(self) -> &'static str {
+        match self {
+            Severity::Info => "ℹ",
+            Severity::Success => "✔",
+            Severity::Warning => "⚠",
+            Severity::Danger => "✖",
+        }
+    }
+
+    /// 背景色。警告系は [`theme::active`] に従う。
+    fn color(self) -> Color {
+        let theme = theme::active();
+        match self {
+            Severity::Info => Color::Cyan,
+            Severity::Success => theme.good,
+            Severity::Warning => theme.warn,
+            Severity::Danger => theme.danger,
+        }
+    }
+}
+
+/// キュー内の通知 1 件。
+#[derive(Clone, Debug, PartialEq)]
+pub struct Toast {
+    pub text: String,
+    pub severity: Severity,
+    /// 残り表示時間 (tick)。0 で消える。
+    pub ttl: u32,
+}
+
+thread_local! {
+    static QUEUE: RefCell<VecDeque<Toast>> = const { RefCell::new(VecDeque::new()) };
+}
+
+/// [`DEFAULT_TTL`] の通知を積む。
+pub fn push(severity: Severity, text: impl Into<String>) {
+    push_with_ttl(severity, text, DEFAULT_TTL);
+}
+
+/// 表示時間を指定して通知を積む。直前と同じ文面なら積み増さずに寿命だけ
+/// 延ばす (同じ tick 内の連続イベントで同じ行が並ばないように)。
+pub fn push_with_ttl(severity: Severity, text: impl Into<String>, ttl: u32) {
+    let text = text.into();
+    QUEUE.with(|q| {
+        let mut q = q.borrow_mut();
+        if let Some(last) = q.back_mut() {
+            if last.text == text && last.severity == severity {
+                last.ttl = last.ttl.max(ttl);
+                return;
+            }
+        }
+        a11y::announce(&text);
+        if q.len() >= MAX_TOASTS {
+            q.pop_front();
+        }
+        q.push_back(Toast { text, severity, ttl });
+    });
+}
+
+/// 寿命を `delta_ticks` 減らし、切れたものを捨てる。
+pub fn tick(delta_ticks: u32) {
+    QUEUE.with(|q| {
+        let mut q = q.borrow_mut();
+        for t in q.iter_mut() {
+            t.ttl = t.ttl.saturating_sub(delta_ticks);
+        }
+        q.retain(|t| t.ttl > 0);
+    });
+}
+
+/// 現在のキューの写し (古い順)。
+pub fn snapshot() -> Vec<Toast> {
+    QUEUE.with(|q| q.borrow().iter().cloned().collect())
+}
+
+/// すべて消す (プロファイル切り替え時など)。
+pub fn clear() {
+    QUEUE.with(|q| q.borrow_mut().clear());
+}
+
+/// 右上 (1 行目の ★スコア ボタンの下) に新しい順で重ねて描く。
+pub fn render(f: &mut Frame, area: Rect) {
+    let toasts = snapshot();
+    if toasts.is_empty() || area.width == 0 {
+        return;
+    }
+    let max_w = area.width.min(40);
+    for (row, t) in toasts.iter().rev().enumerate() {
+        let y = area.y + 1 + row as u16;
+        if y >= area.y + area.height {
+            break;
+        }
+        let chip = Span::styled(
+            format!(" {} {} ", t.severity.icon(), t.text),
+            Style::default()
+                .fg(Color::Black)
+                .bg(t.severity.color())
+                .add_modifier(Modifier::BOLD),
+        );
+        let width = (chip.width() as u16).min(max_w);
+        let rect = Rect::new(area.x + area.width - width, y, width, 1);
+        f.render_widget(Clear, rect);
+        f.render_widget(Paragraph::new(chip), rect);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn toasts_expire_after_ttl_and_queue_is_capped() {
+        clear();
+        push_with_ttl(Severity::Info, "a", 5);
+        push_with_ttl(Severity::Success, "b", 10);
+        tick(5);
+        let left = snapshot();
+        assert_eq!(left.len(), 1);
+        assert_eq!(left[0].text, "b");
+        assert_eq!(left[0].ttl, 5);
+
+        for i in 0..(MAX_TOASTS + 2) {
+            push(Severity::Warning, format!("w{i}"));
+        }
+        let q = snapshot();
+        assert_eq!(q.len(), MAX_TOASTS);
+        assert_eq!(q.last().unwrap().text, format!("w{}", MAX_TOASTS + 1));
+        clear();
+    }
+
+    #[test]
+    fn repeated_toast_extends_instead_of_stacking() {
+        clear();
+        push_with_ttl(Severity::Success, "Lv.2", 3);
+        push_with_ttl(Severity::Success, "Lv.2", 8);
+        let q = snapshot();
+        assert_eq!(q.len(), 1);
+        assert_eq!(q[0].ttl, 8);
+        clear();
+    }
+}
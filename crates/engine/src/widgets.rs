@@ -8,10 +8,19 @@
 //!
 //! - [`TabBar`] — Horizontal tab navigation (rendering + click targets).
 //! - [`ClickableList`] — Vertical list with per-row click targets.
-//! - [`ClickableGrid`] — 2D grid with per-cell click targets.
+//! - [`ClickableGrid`] — 2D grid with per-cell click targets, optionally
+//!   drawing labelled button cells (D-pads, toolbars).
 //! - [`Clickable`] — Wrap any [`Widget`] with a single full-area click target.
-//! - [`ScrollableTab`] — `ClickableList` + bordered block + auto ▲▼ tap column,
-//!   with the scroll position auto-clamped against per-frame content height.
+//! - [`ScrollableTab`] — `ClickableList` + bordered block + auto ▲▼ tap column
+//!   with a scrollbar thumb, the scroll position auto-clamped against
+//!   per-frame content height.  [`scroll_by`] / [`page_scroll`] move it from
+//!   key handlers (PgUp / PgDn).
+//! - [`Modal`] — Centered dialog (title, body, buttons) over a dimmed,
+//!   click-blocked background.
+//! - [`CountdownChip`] — Compact m:ss countdown span for timed effects
+//!   (display only, no click target).
+//! - [`Gauge`] — Text progress bar (filled/empty glyphs, label, color
+//!   thresholds) as spans (display only, no click target).
 //!
 //! These builders are the **only** sanctioned way to register click targets.
 //! Direct calls to `ClickState::add_click_target` / `add_row_target` are
@@ -20,13 +29,14 @@
 
 use std::cell::Cell;
 
-use ratzilla::ratatui::layout::{Constraint, Direction, Layout, Rect};
+use ratzilla::ratatui::layout::{Alignment, Constraint, Direction, Layout, Rect};
 use ratzilla::ratatui::style::{Color, Modifier, Style};
 use ratzilla::ratatui::text::{Line, Span};
-use ratzilla::ratatui::widgets::{Block, Paragraph, Widget, Wrap};
+use ratzilla::ratatui::widgets::{Block, Borders, Clear, Paragraph, Widget, Wrap};
 use ratzilla::ratatui::Frame;
 
 use crate::input::ClickState;
+use crate::time::TICKS_PER_SEC;
 
 // ── TabBar ─────────────────────────────────────────────────────
 
@@ -36,17 +46,41 @@ use crate::input::ClickState;
 /// separator string, and registers click targets that match the actual
 /// rendered positions (accounting for CJK character widths and dynamic labels).
 ///
+/// Tabs added with [`colored`](Self::colored) derive their style from
+/// [`active`](Self::active): the active tab is drawn reversed on its color,
+/// the others in plain color.  [`badge`](Self::badge) attaches a count chip
+/// to the last added tab (hidden at 0), and the click target widens to
+/// include it.
+///
 /// # Example
 /// ```ignore
 /// TabBar::new(" │ ")
-///     .tab("生産", tab_style(0), TAB_PRODUCERS)
-///     .tab("強化", tab_style(1), TAB_UPGRADES)
+///     .colored("生産", Color::Green, TAB_PRODUCERS)
+///     .colored("目標", Color::Cyan, TAB_MILESTONES)
+///     .badge(ready_count)
+///     .active(TAB_PRODUCERS)
 ///     .render(f, area, &mut cs);
 /// ```
 pub struct TabBar<'a> {
-    tabs: Vec<(String, Style, u16)>,
+    tabs: Vec<Tab>,
     separator: &'a str,
     block: Option<Block<'a>>,
+    active: Option<u16>,
+}
+
+/// One tab of a [`TabBar`].
+struct Tab {
+    label: String,
+    look: TabLook,
+    action_id: u16,
+    badge: u64,
+}
+
+enum TabLook {
+    /// Fixed style from [`TabBar::tab`].
+    Style(Style),
+    /// Style derived from [`TabBar::active`].
+    Color(Color),
 }
 
 impl<'a> TabBar<'a> {
@@ -55,12 +89,33 @@ impl<'a> TabBar<'a> {
             tabs: Vec::new(),
             separator,
             block: None,
+            active: None,
         }
     }
 
     /// Add a tab with its label, style, and action ID.
     pub fn tab(mut self, label: impl Into<String>, style: Style, action_id: u16) -> Self {
-        self.tabs.push((label.into(), style, action_id));
+        self.tabs.push(Tab { label: label.into(), look: TabLook::Style(style), action_id, badge: 0 });
+        self
+    }
+
+    /// Add a tab whose style follows [`active`](Self::active).
+    pub fn colored(mut self, label: impl Into<String>, color: Color, action_id: u16) -> Self {
+        self.tabs.push(Tab { label: label.into(), look: TabLook::Color(color), action_id, badge: 0 });
+        self
+    }
+
+    /// Show `count` as a badge on the last added tab (nothing when 0).
+    pub fn badge(mut self, count: u64) -> Self {
+        if let Some(tab) = self.tabs.last_mut() {
+            tab.badge = count;
+        }
+        self
+    }
+
+    /// Highlight the [`colored`](Self::colored) tab with this action ID.
+    pub fn active(mut self, action_id: u16) -> Self {
+        self.active = Some(action_id);
         self
     }
 
@@ -73,22 +128,50 @@ impl<'a> TabBar<'a> {
         self
     }
 
+    /// Spans of each tab (padding and badge included) with its display
+    /// width and action ID.
+    fn tab_spans(&self) -> Vec<(Vec<Span<'static>>, u16, u16)> {
+        self.tabs
+            .iter()
+            .map(|tab| {
+                let style = match tab.look {
+                    TabLook::Style(style) => style,
+                    TabLook::Color(color) if self.active == Some(tab.action_id) => Style::default()
+                        .fg(Color::Black)
+                        .bg(color)
+                        .add_modifier(Modifier::BOLD),
+                    TabLook::Color(color) => Style::default().fg(color),
+                };
+                let mut spans = vec![Span::styled(format!(" {}", tab.label), style)];
+                if tab.badge > 0 {
+                    spans.push(Span::styled(" ", style));
+                    spans.push(Span::styled(
+                        tab.badge.to_string(),
+                        Style::default().fg(Color::White).bg(Color::Red).add_modifier(Modifier::BOLD),
+                    ));
+                }
+                spans.push(Span::styled(" ", style));
+                let width = Line::from(spans.clone()).width() as u16;
+                (spans, width, tab.action_id)
+            })
+            .collect()
+    }
+
     /// Render the tab bar and register click targets.
     pub fn render(self, f: &mut Frame, area: Rect, cs: &mut ClickState) {
         let mut spans: Vec<Span> = Vec::new();
         let sep_width = Line::from(self.separator).width() as u16;
         let mut tab_widths: Vec<(u16, u16)> = Vec::new();
 
-        for (i, (label, style, action_id)) in self.tabs.iter().enumerate() {
+        for (i, (tab, width, action_id)) in self.tab_spans().into_iter().enumerate() {
             if i > 0 {
                 spans.push(Span::styled(
                     self.separator,
                     Style::default().fg(Color::DarkGray),
                 ));
             }
-            let padded = format!(" {} ", label);
-            tab_widths.push((Line::from(padded.as_str()).width() as u16, *action_id));
-            spans.push(Span::styled(padded, *style));
+            tab_widths.push((width, action_id));
+            spans.extend(tab);
         }
 
         // Compute inner content area (accounting for borders) before consuming block
@@ -184,6 +267,16 @@ impl<'a> ClickableList<'a> {
         self.lines
     }
 
+    /// Wrap the list in a [`ScrollableTab`] (▲▼ + scrollbar, clamped scroll).
+    pub fn scrollable(
+        self,
+        scroll_state: &'a Cell<u16>,
+        scroll_up_id: u16,
+        scroll_down_id: u16,
+    ) -> ScrollableTab<'a> {
+        ScrollableTab::new(self, scroll_state, scroll_up_id, scroll_down_id)
+    }
+
     /// 指定 `inner_width` で wrap (`Wrap { trim: false }`) した時の visual 行数。
     ///
     /// スクロール上限 (`max_scroll = visual_height - area_inner_h`) の事前計算に
@@ -350,11 +443,26 @@ impl<'a> ClickableList<'a> {
 /// use the same formula.  The action ID for cell `(col, row)` in the
 /// viewport is `base + row * view_w + col`.
 ///
+/// Two ways to use it:
+///
+/// - Game boards draw the cells themselves and call
+///   [`register_targets`](Self::register_targets), which makes **every**
+///   cell a target.
+/// - Button grids (D-pads, toolbars) add labelled cells with
+///   [`button`](Self::button) / [`button_with_id`](Self::button_with_id) /
+///   [`label`](Self::label) and call [`render`](Self::render), which draws
+///   each label in its cell and registers only the buttons.  Cells left
+///   empty are blank space.
+///
 /// # Example
 /// ```ignore
-/// let mut grid = ClickableGrid::new(VIEW_W, VIEW_H, GRID_CLICK_BASE, 2);
+/// let mut grid = ClickableGrid::new(VIEW_W, VIEW_H, GRID_CLICK.base(), 2);
 /// // ... render cells ...
 /// grid.register_targets(area, &block, &mut cs);
+///
+/// ClickableGrid::fill(3, 3, DPAD.base(), area)
+///     .button(1, 0, Line::styled("[ ▲ ]", style))
+///     .render(f, area, Block::default(), &mut cs);
 /// ```
 pub struct ClickableGrid {
     view_w: usize,
@@ -364,6 +472,18 @@ pub struct ClickableGrid {
     cell_display_width: u16,
     /// Display height of each cell in terminal rows.
     cell_display_height: u16,
+    /// Labelled cells drawn by [`render`](Self::render).
+    cells: Vec<GridCell>,
+    /// Horizontal alignment of labels within their cell.
+    alignment: Alignment,
+}
+
+/// One labelled cell.  `action_id` is `None` for display-only labels.
+struct GridCell {
+    col: usize,
+    row: usize,
+    label: Line<'static>,
+    action_id: Option<u16>,
 }
 
 impl ClickableGrid {
@@ -374,15 +494,58 @@ impl ClickableGrid {
             action_base,
             cell_display_width,
             cell_display_height: 1,
+            cells: Vec::new(),
+            alignment: Alignment::Center,
         }
     }
 
+    /// A grid whose cells split `inner` evenly (remainder columns / rows
+    /// are left over at the right / bottom).  `inner` is the area inside
+    /// the block passed to [`render`](Self::render).
+    pub fn fill(view_w: usize, view_h: usize, action_base: u16, inner: Rect) -> Self {
+        let cell_w = (inner.width / view_w.max(1) as u16).max(1);
+        let cell_h = (inner.height / view_h.max(1) as u16).max(1);
+        Self::new(view_w, view_h, action_base, cell_w).with_cell_height(cell_h)
+    }
+
     /// Create a grid with multi-row cells.
     pub fn with_cell_height(mut self, h: u16) -> Self {
         self.cell_display_height = h;
         self
     }
 
+    /// Horizontal alignment of labels (default: centred).
+    pub fn align(mut self, alignment: Alignment) -> Self {
+        self.alignment = alignment;
+        self
+    }
+
+    /// Label cell `(col, row)` as a button with the encoded grid action ID
+    /// (decode it with [`decode`](Self::decode)).
+    pub fn button(self, col: usize, row: usize, label: impl Into<Line<'static>>) -> Self {
+        let id = self.action_base + (row * self.view_w + col) as u16;
+        self.button_with_id(col, row, label, id)
+    }
+
+    /// Label cell `(col, row)` as a button with its own action ID, for
+    /// toolbars whose buttons already have named action constants.
+    pub fn button_with_id(
+        mut self,
+        col: usize,
+        row: usize,
+        label: impl Into<Line<'static>>,
+        action_id: u16,
+    ) -> Self {
+        self.cells.push(GridCell { col, row, label: label.into(), action_id: Some(action_id) });
+        self
+    }
+
+    /// Label cell `(col, row)` without a click target (locked / disabled).
+    pub fn label(mut self, col: usize, row: usize, label: impl Into<Line<'static>>) -> Self {
+        self.cells.push(GridCell { col, row, label: label.into(), action_id: None });
+        self
+    }
+
     /// Decode an action_id back into viewport-relative `(col, row)`.
     ///
     /// Returns `None` if the action_id is not in the grid range.
@@ -396,6 +559,15 @@ impl ClickableGrid {
         Some((col, row))
     }
 
+    /// Screen rect of cell `(col, row)` inside `inner`, clipped to it.
+    /// `None` when the cell falls entirely outside.
+    fn cell_rect(&self, inner: Rect, padding_left: u16, col: usize, row: usize) -> Option<Rect> {
+        let x = inner.x + padding_left + col as u16 * self.cell_display_width;
+        let y = inner.y + row as u16 * self.cell_display_height;
+        let rect = Rect::new(x, y, self.cell_display_width, self.cell_display_height).intersection(inner);
+        (rect.width > 0 && rect.height > 0).then_some(rect)
+    }
+
     /// Register click targets for all cells in the viewport.
     ///
     /// * `area` — the widget area (including borders).
@@ -427,6 +599,26 @@ impl ClickableGrid {
             }
         }
     }
+
+    /// Draw `block` and every labelled cell (label on the cell's middle
+    /// row), and register the buttons' whole cells as click targets.
+    pub fn render(self, f: &mut Frame, area: Rect, block: Block, cs: &mut ClickState) {
+        let inner = block.inner(area);
+        f.render_widget(block, area);
+        for cell in &self.cells {
+            let Some(rect) = self.cell_rect(inner, 0, cell.col, cell.row) else {
+                continue;
+            };
+            let label_row = Rect::new(rect.x, rect.y + (rect.height - 1) / 2, rect.width, 1);
+            f.render_widget(
+                Paragraph::new(cell.label.clone()).alignment(self.alignment),
+                label_row,
+            );
+            if let Some(id) = cell.action_id {
+                cs.add_click_target(rect, id);
+            }
+        }
+    }
 }
 
 // ── Clickable ─────────────────────────────────────────────────
@@ -502,6 +694,8 @@ impl<W: Widget> Clickable<W> {
 /// - ▲ / ▼ tap targets are drawn into the scroll column. Each tap target
 ///   spans HALF the column height (not just the single ▲/▼ glyph) so it's
 ///   reachable with a fingertip.
+/// - Between the arrows the column shows a scrollbar: a dim `│` track with a
+///   `█` thumb sized to the visible fraction (see [`scrollbar_thumb`]).
 ///
 /// # Example
 /// ```ignore
@@ -551,8 +745,6 @@ impl<'a> ScrollableTab<'a> {
     /// Enable line wrapping. When false (default) one logical line maps to
     /// one visual row; with wrap, long lines may consume multiple rows and
     /// the scroll/clamp math accounts for wrap-expanded heights.
-    /// Currently kept ready for the abyss migration that uses `wrap=narrow`.
-    #[allow(dead_code)]
     pub fn wrap(mut self, w: bool) -> Self {
         self.wrap = w;
         self
@@ -619,7 +811,11 @@ impl<'a> ScrollableTab<'a> {
             render_scroll_indicators(
                 f,
                 sc,
-                ScrollIndicatorState { scroll, max_scroll },
+                ScrollIndicatorState {
+                    scroll,
+                    max_scroll,
+                    view: content_area.height,
+                },
                 cs,
                 ScrollIndicatorIds {
                     up: scroll_up_id,
@@ -635,6 +831,8 @@ impl<'a> ScrollableTab<'a> {
 struct ScrollIndicatorState {
     scroll: u16,
     max_scroll: u16,
+    /// Visible content rows (content height = `max_scroll + view`).
+    view: u16,
 }
 
 #[derive(Copy, Clone)]
@@ -661,7 +859,9 @@ fn split_for_scroll(inner: Rect) -> (Rect, Option<Rect>) {
 
 /// Draw ▲ on the upper half and ▼ on the lower half of `area`. Each glyph
 /// is drawn at the visual edge but the **tap target spans the full half** —
-/// 1-cell-wide tap targets are unreachable on touch devices.
+/// 1-cell-wide tap targets are unreachable on touch devices.  The rows
+/// between the glyphs get the scrollbar track + thumb (display only; taps
+/// there still hit the ▲ / ▼ halves).
 fn render_scroll_indicators(
     f: &mut Frame,
     area: Rect,
@@ -690,6 +890,419 @@ fn render_scroll_indicators(
         let para = Paragraph::new(lines);
         Clickable::new(para, ids.down).render(f, down_rect, cs);
     }
+
+    let track_len = area.height.saturating_sub(2);
+    let (thumb_start, thumb_len) = scrollbar_thumb(
+        track_len,
+        state.view,
+        state.max_scroll.saturating_add(state.view),
+        state.scroll,
+    );
+    let buf = f.buffer_mut();
+    for i in 0..track_len {
+        let on_thumb = (thumb_start..thumb_start + thumb_len).contains(&i);
+        let (glyph, cell_style) = if on_thumb {
+            ("█", Style::default().fg(ids.color))
+        } else {
+            ("│", Style::default().fg(Color::DarkGray))
+        };
+        buf.set_string(area.x, area.y + 1 + i, glyph, cell_style);
+    }
+}
+
+/// Scrollbar thumb `(start, len)` within a track of `track_len` rows, for
+/// `view` visible rows out of `content` rows scrolled by `scroll`.
+///
+/// The thumb is at least 1 row, fills the whole track when everything fits,
+/// and touches the track's bottom exactly when scrolled to the end.
+pub fn scrollbar_thumb(track_len: u16, view: u16, content: u16, scroll: u16) -> (u16, u16) {
+    if track_len == 0 || content <= view {
+        return (0, track_len);
+    }
+    let len = ((track_len as u32 * view as u32) / content as u32).clamp(1, track_len as u32) as u16;
+    let max_scroll = (content - view) as u32;
+    let free = (track_len - len) as u32;
+    let start = (free * scroll.min(content - view) as u32 + max_scroll / 2) / max_scroll;
+    (start as u16, len)
+}
+
+/// Rows one PgUp / PgDn moves a [`ScrollableTab`].  The key handler doesn't
+/// know the viewport height, so a page is a fixed stride that keeps a few
+/// rows of context on a phone-sized panel.
+pub const SCROLL_PAGE_ROWS: u16 = 8;
+
+/// Move a [`ScrollableTab`] scroll position by `delta` rows.  Only the lower
+/// bound is enforced here; the next render clamps against content height.
+pub fn scroll_by(scroll_state: &Cell<u16>, delta: i32) {
+    let next = (scroll_state.get() as i32 + delta).clamp(0, u16::MAX as i32) as u16;
+    scroll_state.set(next);
+}
+
+/// Handle a paging key ([`PAGE_UP_KEY`](crate::input::PAGE_UP_KEY) /
+/// [`PAGE_DOWN_KEY`](crate::input::PAGE_DOWN_KEY)) for `scroll_state`.
+/// Returns `false` for any other key so callers can fall through.
+pub fn page_scroll(scroll_state: &Cell<u16>, key: char) -> bool {
+    let rows = SCROLL_PAGE_ROWS as i32;
+    match key {
+        crate::input::PAGE_UP_KEY => scroll_by(scroll_state, -rows),
+        crate::input::PAGE_DOWN_KEY => scroll_by(scroll_state, rows),
+        _ => return false,
+    }
+    true
+}
+
+// ── Modal ─────────────────────────────────────────────────────
+
+/// A centered dialog: title, body lines and a column of buttons.
+///
+/// `render` dims everything already drawn in `area`, **drops the click
+/// targets registered so far** (the scope is kept), then draws the dialog
+/// box and registers one target per button.  So the screen underneath can
+/// be rendered as usual and the modal simply drawn last — taps can't fall
+/// through to it.  [`backdrop`](Self::backdrop) makes a tap outside the box
+/// fire an action (typically "cancel").
+///
+/// Buttons are separated by a blank row (fingertip-sized).  Each gets a `▶`
+/// marker; with [`selected`](Self::selected) only the cursor row does, for
+/// screens navigated with j/k + A.
+///
+/// # Example
+/// ```ignore
+/// Modal::new(" 確認 ")
+///     .border_color(Color::Red)
+///     .body(vec![Line::from(" 本当にリセットしますか？")])
+///     .button(Line::from("はい"), CONFIRM_YES)
+///     .button(Line::from("キャンセル"), CONFIRM_NO)
+///     .backdrop(CONFIRM_NO)
+///     .render(f, area, &mut cs);
+/// ```
+pub struct Modal<'a> {
+    title: Line<'a>,
+    body: Vec<Line<'a>>,
+    buttons: Vec<(Line<'a>, u16)>,
+    selected: Option<usize>,
+    border_color: Color,
+    backdrop_id: Option<u16>,
+}
+
+impl<'a> Modal<'a> {
+    pub fn new(title: impl Into<Line<'a>>) -> Self {
+        Self {
+            title: title.into(),
+            body: Vec::new(),
+            buttons: Vec::new(),
+            selected: None,
+            border_color: Color::Yellow,
+            backdrop_id: None,
+        }
+    }
+
+    pub fn body(mut self, lines: Vec<Line<'a>>) -> Self {
+        self.body = lines;
+        self
+    }
+
+    pub fn button(mut self, label: impl Into<Line<'a>>, action_id: u16) -> Self {
+        self.buttons.push((label.into(), action_id));
+        self
+    }
+
+    /// Highlight the button at `index` as the cursor row.
+    pub fn selected(mut self, index: usize) -> Self {
+        self.selected = Some(index);
+        self
+    }
+
+    pub fn border_color(mut self, c: Color) -> Self {
+        self.border_color = c;
+        self
+    }
+
+    /// Action fired by a tap on the dimmed area outside the dialog.
+    pub fn backdrop(mut self, action_id: u16) -> Self {
+        self.backdrop_id = Some(action_id);
+        self
+    }
+
+    /// The dialog box for `area`: sized to its content (plus borders and a
+    /// 1-cell margin each side), clamped to `area`, and centered.
+    pub fn dialog_rect(&self, area: Rect) -> Rect {
+        let content_w = self
+            .body
+            .iter()
+            .map(Line::width)
+            .chain(self.buttons.iter().map(|(l, _)| l.width() + 3))
+            .chain(std::iter::once(self.title.width()))
+            .max()
+            .unwrap_or(0);
+        let w = (content_w as u16).saturating_add(4).max(24).min(area.width);
+        let button_rows = (self.buttons.len() as u16 * 2).saturating_sub(1);
+        let gap = u16::from(!self.body.is_empty() && !self.buttons.is_empty());
+        let h = (self.body.len() as u16 + gap + button_rows)
+            .saturating_add(2)
+            .min(area.height);
+        Rect::new(
+            area.x + (area.width - w) / 2,
+            area.y + (area.height - h) / 2,
+            w,
+            h,
+        )
+    }
+
+    pub fn render(self, f: &mut Frame, area: Rect, cs: &mut ClickState) {
+        let dialog = self.dialog_rect(area);
+        let Modal { title, body, buttons, selected, border_color, backdrop_id } = self;
+
+        f.buffer_mut().set_style(
+            area,
+            Style::default().fg(Color::DarkGray).add_modifier(Modifier::DIM),
+        );
+        let scope = cs.current_scope().cloned();
+        cs.clear_targets();
+        if let Some(scope) = scope {
+            cs.set_scope(scope);
+        }
+        if let Some(id) = backdrop_id {
+            cs.add_click_target(area, id);
+        }
+
+        let mut cl = ClickableList::new();
+        let has_body = !body.is_empty();
+        for line in body {
+            cl.push(line);
+        }
+        if has_body && !buttons.is_empty() {
+            cl.push(Line::from(""));
+        }
+        let button_style = Style::default().fg(border_color).add_modifier(Modifier::BOLD);
+        for (i, (label, id)) in buttons.into_iter().enumerate() {
+            if i > 0 {
+                cl.push(Line::from(""));
+            }
+            let marker = if selected.is_none_or(|s| s == i) { " ▶ " } else { "   " };
+            let mut spans = vec![Span::styled(marker, button_style)];
+            spans.extend(label.spans);
+            cl.push_clickable(Line::from(spans).style(label.style), id);
+        }
+
+        let block = Block::default()
+            .borders(Borders::ALL)
+            .border_style(Style::default().fg(border_color))
+            .title(title);
+        f.render_widget(Clear, dialog);
+        cl.render(f, dialog, block, cs, true, 0);
+    }
+}
+
+// ── CountdownChip ─────────────────────────────────────────────
+
+/// At or below this many seconds left the chip turns yellow.
+pub const COUNTDOWN_LOW_SECS: u32 = 15;
+
+/// At or below this many seconds left the chip turns red and blinks.
+pub const COUNTDOWN_BLINK_SECS: u32 = 5;
+
+/// A compact countdown for timed effects (market phases, buffs, contracts…).
+///
+/// Formats the remaining ticks as `m:ss`, keeps the caller's color while
+/// there is plenty of time, turns yellow at [`COUNTDOWN_LOW_SECS`] and red
+/// at [`COUNTDOWN_BLINK_SECS`], where it also blinks (reverse video toggled
+/// every half second of game time, so it pauses with the game).
+///
+/// Display only — it yields a [`Span`] to embed in a line and registers no
+/// click target.
+///
+/// # Example
+/// ```ignore
+/// Line::from(vec![
+///     Span::styled(" 🌟 生産フレンジー ", style),
+///     CountdownChip::new(buff.ticks_left).color(Color::Magenta).span(),
+/// ])
+/// ```
+#[derive(Debug, Clone, Copy)]
+pub struct CountdownChip {
+    ticks_left: u32,
+    color: Color,
+}
+
+impl CountdownChip {
+    pub fn new(ticks_left: u32) -> Self {
+        Self { ticks_left, color: Color::White }
+    }
+
+    /// Color used while more than [`COUNTDOWN_LOW_SECS`] remain.
+    pub fn color(mut self, color: Color) -> Self {
+        self.color = color;
+        self
+    }
+
+    /// Whole seconds left, rounded up (so `0:00` only once time is up).
+    pub fn seconds_left(&self) -> u32 {
+        self.ticks_left.div_ceil(TICKS_PER_SEC)
+    }
+
+    /// `m:ss` text.
+    pub fn text(&self) -> String {
+        let secs = self.seconds_left();
+        format!("{}:{:02}", secs / 60, secs % 60)
+    }
+
+    pub fn style(&self) -> Style {
+        let secs = self.seconds_left();
+        if secs <= COUNTDOWN_BLINK_SECS {
+            let style = Style::default().fg(Color::LightRed).add_modifier(Modifier::BOLD);
+            let blink_on = (self.ticks_left / (TICKS_PER_SEC / 2)).is_multiple_of(2);
+            if blink_on { style.add_modifier(Modifier::REVERSED) } else { style }
+        } else if secs <= COUNTDOWN_LOW_SECS {
+            Style::default().fg(Color::Yellow)
+        } else {
+            Style::default().fg(self.color)
+        }
+    }
+
+    pub fn span(&self) -> Span<'static> {
+        Span::styled(self.text(), self.style())
+    }
+}
+
+// ── Gauge ──────────────────────────────────────────────────────
+
+/// A text progress bar such as `█████░░░ 62%`.
+///
+/// Fills `width` cells in proportion to the ratio (rounded, or rounded up
+/// with [`ceil`](Self::ceil) so a nearly-spent timer still shows a sliver).
+/// The bar takes the base [`color`](Self::color) unless the ratio is above
+/// one of the [`threshold`](Self::threshold)s, in which case the highest
+/// such threshold's color wins.  The empty part uses the same color unless
+/// [`empty_color`](Self::empty_color) is set.
+///
+/// Display only — it yields spans to embed in a line and registers no click
+/// target.
+///
+/// # Example
+/// ```ignore
+/// let hp = Gauge::new(hp, max_hp, 10)
+///     .color(Color::Red)
+///     .threshold(0.25, Color::Yellow)
+///     .threshold(0.5, Color::Green);
+/// line_spans.push(hp.span());
+/// ```
+#[derive(Debug, Clone)]
+pub struct Gauge {
+    ratio: f64,
+    width: usize,
+    filled: &'static str,
+    empty: &'static str,
+    ceil: bool,
+    color: Color,
+    empty_color: Option<Color>,
+    thresholds: Vec<(f64, Color)>,
+    label: Option<String>,
+}
+
+impl Gauge {
+    /// `current / max`, clamped to 0..=1 (0 when `max` is 0).
+    pub fn new(current: u64, max: u64, width: usize) -> Self {
+        let ratio = if max > 0 { current as f64 / max as f64 } else { 0.0 };
+        Self::ratio(ratio, width)
+    }
+
+    pub fn ratio(ratio: f64, width: usize) -> Self {
+        Self {
+            ratio: if ratio.is_nan() { 0.0 } else { ratio.clamp(0.0, 1.0) },
+            width,
+            filled: "█",
+            empty: "░",
+            ceil: false,
+            color: Color::White,
+            empty_color: None,
+            thresholds: Vec::new(),
+            label: None,
+        }
+    }
+
+    /// Glyphs for filled and empty cells (each one cell wide).
+    pub fn glyphs(mut self, filled: &'static str, empty: &'static str) -> Self {
+        self.filled = filled;
+        self.empty = empty;
+        self
+    }
+
+    /// Round the filled cell count up instead of to nearest.
+    pub fn ceil(mut self) -> Self {
+        self.ceil = true;
+        self
+    }
+
+    pub fn color(mut self, color: Color) -> Self {
+        self.color = color;
+        self
+    }
+
+    pub fn empty_color(mut self, color: Color) -> Self {
+        self.empty_color = Some(color);
+        self
+    }
+
+    /// Use `color` while the ratio is above `above`.
+    pub fn threshold(mut self, above: f64, color: Color) -> Self {
+        self.thresholds.push((above, color));
+        self
+    }
+
+    /// Text after the bar, in the bar's color (e.g. `" 62%"`).
+    pub fn label(mut self, label: impl Into<String>) -> Self {
+        self.label = Some(label.into());
+        self
+    }
+
+    pub fn filled_cells(&self) -> usize {
+        let cells = self.ratio * self.width as f64;
+        let cells = if self.ceil { cells.ceil() } else { cells.round() };
+        (cells as usize).min(self.width)
+    }
+
+    /// Color for the current ratio.
+    pub fn current_color(&self) -> Color {
+        self.thresholds
+            .iter()
+            .filter(|(above, _)| self.ratio > *above)
+            .max_by(|a, b| a.0.total_cmp(&b.0))
+            .map_or(self.color, |&(_, c)| c)
+    }
+
+    /// The bar text alone, without label.
+    pub fn bar(&self) -> String {
+        let filled = self.filled_cells();
+        self.filled.repeat(filled) + &self.empty.repeat(self.width - filled)
+    }
+
+    /// The whole bar as one span (ignores `empty_color` and the label).
+    pub fn span(&self) -> Span<'static> {
+        Span::styled(self.bar(), Style::default().fg(self.current_color()))
+    }
+
+    /// Bar (split in two when `empty_color` is set) followed by the label.
+    pub fn spans(&self) -> Vec<Span<'static>> {
+        let color = self.current_color();
+        let mut spans = match self.empty_color {
+            Some(empty_color) => {
+                let filled = self.filled_cells();
+                vec![
+                    Span::styled(self.filled.repeat(filled), Style::default().fg(color)),
+                    Span::styled(
+                        self.empty.repeat(self.width - filled),
+                        Style::default().fg(empty_color),
+                    ),
+                ]
+            }
+            None => vec![self.span()],
+        };
+        if let Some(label) = &self.label {
+            spans.push(Span::styled(label.clone(), Style::default().fg(color)));
+        }
+        spans
+    }
 }
 
 #[cfg(test)]
@@ -713,6 +1326,24 @@ mod tests {
         assert_eq!(cs.targets.len(), 3);
     }
 
+    #[test]
+    fn tab_bar_highlights_active_colored_tab_and_widens_for_badge() {
+        let tabs = TabBar::new("|")
+            .colored("生産", Color::Green, 1)
+            .colored("目標", Color::Cyan, 2)
+            .badge(12)
+            .colored("転生", Color::Blue, 3)
+            .badge(0)
+            .active(2)
+            .tab_spans();
+        // " 生産 " = 1 + 4 + 1、" 目標 12 " = 1 + 4 + 1 + 2 + 1
+        assert_eq!(tabs.iter().map(|t| t.1).collect::<Vec<_>>(), [6, 9, 6]);
+        assert_eq!(tabs[0].0[0].style.bg, None);
+        assert_eq!(tabs[1].0[0].style.bg, Some(Color::Cyan));
+        assert_eq!(tabs[1].0[2].content, "12");
+        assert_eq!(tabs[2].0.len(), 2, "a zero badge is hidden");
+    }
+
     // ── ClickableList tests ────────────────────────────────────
 
     #[test]
@@ -968,6 +1599,59 @@ mod tests {
         assert_eq!(ClickableGrid::decode(base, view_w, 99), None);
     }
 
+    #[test]
+    fn clickable_grid_render_registers_only_buttons() {
+        use ratzilla::ratatui::backend::TestBackend;
+        use ratzilla::ratatui::Terminal;
+
+        let mut cs = ClickState::new();
+        let area = Rect::new(0, 0, 9, 6);
+        let mut terminal = Terminal::new(TestBackend::new(20, 10)).unwrap();
+        terminal
+            .draw(|f| {
+                ClickableGrid::fill(3, 3, 100, area)
+                    .button(1, 0, "▲")
+                    .button_with_id(2, 1, "▶", 7)
+                    .label(0, 1, "x")
+                    .render(f, area, Block::default(), &mut cs);
+            })
+            .unwrap();
+
+        // 3×2 cells: (1,0) covers cols 3..6, rows 0..2
+        assert_eq!(cs.hit_test(3, 0), Some(101));
+        assert_eq!(cs.hit_test(5, 1), Some(101));
+        assert_eq!(cs.hit_test(6, 2), Some(7));
+        // Labels and empty cells are not targets
+        assert_eq!(cs.hit_test(0, 2), None);
+        assert_eq!(cs.hit_test(0, 0), None);
+        assert_eq!(cs.targets.len(), 2);
+
+        // The label sits centred on the cell's middle row
+        let buffer = terminal.backend().buffer();
+        assert_eq!(buffer[(4, 0)].symbol(), "▲");
+        assert_eq!(buffer[(7, 2)].symbol(), "▶");
+    }
+
+    #[test]
+    fn clickable_grid_clips_cells_outside_the_area() {
+        use ratzilla::ratatui::backend::TestBackend;
+        use ratzilla::ratatui::Terminal;
+
+        let mut cs = ClickState::new();
+        let area = Rect::new(0, 0, 10, 2);
+        let mut terminal = Terminal::new(TestBackend::new(20, 10)).unwrap();
+        terminal
+            .draw(|f| {
+                ClickableGrid::new(2, 4, 0, 5)
+                    .button(0, 1, "a")
+                    .button(1, 3, "b")
+                    .render(f, area, Block::default(), &mut cs);
+            })
+            .unwrap();
+        assert_eq!(cs.targets.len(), 1);
+        assert_eq!(cs.hit_test(0, 1), Some(2));
+    }
+
     // ── Clickable tests ───────────────────────────────────────
 
     /// Verify that `Clickable::render` registers exactly the `area` it is
@@ -1108,4 +1792,197 @@ mod tests {
             "overflow case must register ▼ scroll target on the right-edge column"
         );
     }
+
+    #[test]
+    fn scrollbar_thumb_tracks_scroll_position() {
+        // Everything fits → thumb fills the track.
+        assert_eq!(scrollbar_thumb(10, 20, 15, 0), (0, 10));
+        // Half visible → half-length thumb, top / bottom at the ends.
+        assert_eq!(scrollbar_thumb(10, 20, 40, 0), (0, 5));
+        assert_eq!(scrollbar_thumb(10, 20, 40, 20), (5, 5));
+        assert_eq!(scrollbar_thumb(10, 20, 40, 10), (3, 5));
+        // Tiny view into a huge list still shows a 1-row thumb.
+        assert_eq!(scrollbar_thumb(4, 2, 1000, 998), (3, 1));
+        assert_eq!(scrollbar_thumb(0, 2, 1000, 0), (0, 0));
+    }
+
+    #[test]
+    fn page_scroll_moves_by_a_page_and_stops_at_top() {
+        use crate::input::{PAGE_DOWN_KEY, PAGE_UP_KEY};
+        let scroll = Cell::new(3u16);
+        assert!(page_scroll(&scroll, PAGE_DOWN_KEY));
+        assert_eq!(scroll.get(), 3 + SCROLL_PAGE_ROWS);
+        assert!(page_scroll(&scroll, PAGE_UP_KEY));
+        assert!(page_scroll(&scroll, PAGE_UP_KEY));
+        assert_eq!(scroll.get(), 0);
+        assert!(!page_scroll(&scroll, 'j'));
+    }
+
+    #[test]
+    fn scrollable_tab_draws_thumb_between_arrows() {
+        use ratzilla::ratatui::backend::TestBackend;
+        use ratzilla::ratatui::widgets::Borders;
+        use ratzilla::ratatui::Terminal;
+
+        let scroll = Cell::new(u16::MAX);
+        let mut terminal = Terminal::new(TestBackend::new(20, 12)).unwrap();
+        let mut cs = ClickState::new();
+        cs.terminal_cols = 20;
+        cs.terminal_rows = 12;
+
+        terminal
+            .draw(|f| {
+                let mut cl = ClickableList::new();
+                for i in 0..40 {
+                    cl.push(Line::from(format!("row {i}")));
+                }
+                cl.scrollable(&scroll, 200, 201)
+                    .block(Block::default().borders(Borders::ALL))
+                    .render(f, f.area(), &mut cs);
+            })
+            .unwrap();
+
+        // Clamped to the end: inner 10 rows of 40 → max_scroll 30.
+        assert_eq!(scroll.get(), 30);
+        let buf = terminal.backend().buffer();
+        let col: String = (1..11).map(|y| buf[(18, y)].symbol().to_string()).collect();
+        // ▲ on top, track, thumb (2 rows) at the bottom of the track, no ▼.
+        assert_eq!(col, "▲││││││██ ");
+    }
+
+    // ── Modal ──────────────────────────────────────────────────────
+
+    #[test]
+    fn modal_blocks_background_taps_and_registers_buttons() {
+        use ratzilla::ratatui::backend::TestBackend;
+        use ratzilla::ratatui::Terminal;
+
+        let mut terminal = Terminal::new(TestBackend::new(40, 20)).unwrap();
+        let mut cs = ClickState::new();
+        cs.terminal_cols = 40;
+        cs.terminal_rows = 20;
+        cs.set_scope(crate::input::ClickScope::Settings);
+
+        let mut dialog = Rect::default();
+        terminal
+            .draw(|f| {
+                // Background screen with its own full-area target.
+                Clickable::new(Paragraph::new("background"), 1).render(f, f.area(), &mut cs);
+                let modal = Modal::new(" 確認 ")
+                    .body(vec![Line::from(" 本当に？")])
+                    .button("はい", 10)
+                    .button("いいえ", 11);
+                dialog = modal.dialog_rect(f.area());
+                modal.render(f, f.area(), &mut cs);
+            })
+            .unwrap();
+
+        assert_eq!(cs.current_scope(), Some(&crate::input::ClickScope::Settings));
+        // Background no longer clickable.
+        assert_eq!(cs.hit_test(0, 0), None);
+        // border, body, gap, button 0, gap, button 1, border
+        assert_eq!(dialog.height, 7);
+        assert_eq!(cs.hit_test(dialog.x + 2, dialog.y + 3), Some(10));
+        assert_eq!(cs.hit_test(dialog.x + 2, dialog.y + 4), None);
+        assert_eq!(cs.hit_test(dialog.x + 2, dialog.y + 5), Some(11));
+        // Background is dimmed.
+        let buf = terminal.backend().buffer();
+        assert!(buf[(0, 0)].modifier.contains(Modifier::DIM));
+    }
+
+    #[test]
+    fn modal_backdrop_and_cursor_marker() {
+        use ratzilla::ratatui::backend::TestBackend;
+        use ratzilla::ratatui::Terminal;
+
+        let mut terminal = Terminal::new(TestBackend::new(40, 20)).unwrap();
+        let mut cs = ClickState::new();
+        let mut dialog = Rect::default();
+        terminal
+            .draw(|f| {
+                let modal = Modal::new(" x ")
+                    .button("a", 10)
+                    .button("b", 11)
+                    .selected(1)
+                    .backdrop(99);
+                dialog = modal.dialog_rect(f.area());
+                modal.render(f, f.area(), &mut cs);
+            })
+            .unwrap();
+
+        assert_eq!(cs.hit_test(0, 0), Some(99));
+        let buf = terminal.backend().buffer();
+        let row = |y: u16| -> String {
+            (dialog.x + 1..dialog.x + 4).map(|x| buf[(x, y)].symbol().to_string()).collect()
+        };
+        assert_eq!(row(dialog.y + 1), "   ");
+        assert_eq!(row(dialog.y + 3), " ▶ ");
+    }
+
+    // ── Gauge ──────────────────────────────────────────────────────
+
+    #[test]
+    fn gauge_fills_proportionally_with_custom_glyphs() {
+        assert_eq!(Gauge::new(5, 10, 4).bar(), "██░░");
+        assert_eq!(Gauge::new(1, 10, 4).bar(), "░░░░");
+        assert_eq!(Gauge::new(1, 10, 4).ceil().bar(), "█░░░");
+        assert_eq!(Gauge::new(20, 10, 3).glyphs("♥", "♡").bar(), "♥♥♥");
+        assert_eq!(Gauge::new(3, 0, 2).bar(), "░░");
+    }
+
+    #[test]
+    fn gauge_picks_highest_threshold_below_ratio() {
+        let g = |cur| {
+            Gauge::new(cur, 100, 10)
+                .color(Color::Red)
+                .threshold(0.5, Color::Green)
+                .threshold(0.25, Color::Yellow)
+                .current_color()
+        };
+        assert_eq!(g(80), Color::Green);
+        assert_eq!(g(50), Color::Yellow);
+        assert_eq!(g(25), Color::Red);
+    }
+
+    #[test]
+    fn gauge_spans_split_empty_part_and_append_label() {
+        let spans = Gauge::new(1, 2, 2)
+            .color(Color::Cyan)
+            .empty_color(Color::DarkGray)
+            .label(" 50%")
+            .spans();
+        let texts: Vec<_> = spans.iter().map(|s| s.content.to_string()).collect();
+        assert_eq!(texts, ["█", "░", " 50%"]);
+        assert_eq!(spans[1].style.fg, Some(Color::DarkGray));
+        assert_eq!(spans[2].style.fg, Some(Color::Cyan));
+    }
+
+    // ── CountdownChip ──────────────────────────────────────────────
+
+    #[test]
+    fn countdown_chip_formats_minutes_and_rounds_up() {
+        assert_eq!(CountdownChip::new(0).text(), "0:00");
+        assert_eq!(CountdownChip::new(1).text(), "0:01");
+        assert_eq!(CountdownChip::new(230).text(), "0:23");
+        assert_eq!(CountdownChip::new(600).text(), "1:00");
+        assert_eq!(CountdownChip::new(1234).text(), "2:04");
+    }
+
+    #[test]
+    fn countdown_chip_shifts_color_as_time_runs_low() {
+        let chip = |t| CountdownChip::new(t).color(Color::Magenta).style();
+        assert_eq!(chip(300).fg, Some(Color::Magenta));
+        assert_eq!(chip(150).fg, Some(Color::Yellow));
+        assert_eq!(chip(50).fg, Some(Color::LightRed));
+    }
+
+    #[test]
+    fn countdown_chip_blinks_only_under_five_seconds() {
+        let reversed = |t| CountdownChip::new(t).style().add_modifier.contains(Modifier::REVERSED);
+        assert!(!reversed(100) && !reversed(95));
+        // Half-second phases alternate.
+        assert!(reversed(40));
+        assert!(!reversed(35));
+        assert!(reversed(20));
+    }
 }
@@ -0,0 +1,140 @@
+//! 文字の大きさ (ズーム)。
+//!
+//! 画面は DOM の `<pre>` グリッドで、`index.html` が視覚幅から列数を決め、
+//! その列数がちょうど収まるようにフォントを合わせる。ズームはこの列数を
+//! 倍率ぶん減らす (= 文字が大きくなる) もので、`window.__setZoom(scale)`
+//! で JS に伝えると列数・行数を測り直してグリッドを作り直させる。
+//!
+//! 入口は設定画面の ＋/− と、タッチ端末のピンチ。ピンチは `index.html`
+//! の touch handler が検出して `window.__pinchZoom(steps)` を呼ぶ
+//! (`main.rs` が登録)。
+//!
+//! 画面の大きさは端末で決まるので、プロファイルごとではなく端末全体で
+//! 1 つだけ保存する。native (cargo test) では読み込みは常に既定値、
+//! 書き込みと JS への通知は no-op。
+
+use std::cell::Cell;
+
+/// localStorage のキー (端末全体の設定なので名前空間化しない)。値は倍率 (%)。
+#[cfg(target_arch = "wasm32")]
+const ZOOM_KEY: &str = "app_zoom";
+
+/// 選べる倍率 (%)。小さい順。
+pub const LEVELS: [u16; 7] = [80, 90, 100, 115, 130, 150, 175];
+
+/// 既定の倍率 (%)。
+pub const DEFAULT: u16 = 100;
+
+#[cfg(target_arch = "wasm32")]
+mod imp {
+    use wasm_bindgen::prelude::*;
+
+    #[wasm_bindgen]
+    extern "C" {
+        // index.html の viewport-fit `<script>` 内で `window.__setZoom` として定義。
+        #[wasm_bindgen(js_namespace = window, js_name = __setZoom, catch)]
+        fn js_set_zoom(scale: f64) -> Result<(), JsValue>;
+    }
+
+    pub fn apply(percent: u16) {
+        // 未定義なら文字の大きさが変わらないだけなので無視する。
+        let _ = js_set_zoom(percent as f64 / 100.0);
+    }
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+mod imp {
+    pub fn apply(_percent: u16) {}
+}
+
+thread_local! {
+    static ACTIVE: Cell<u16> = const { Cell::new(DEFAULT) };
+}
+
+/// 現在の倍率 (%)。
+pub fn active() -> u16 {
+    ACTIVE.with(|z| z.get())
+}
+
+/// `percent` にいちばん近い [`LEVELS`] の位置。保存値が壊れていても丸める。
+fn nearest_index(percent: u16) -> usize {
+    LEVELS
+        .iter()
+        .enumerate()
+        .min_by_key(|(_, &l)| l.abs_diff(percent))
+        .map_or(0, |(i, _)| i)
+}
+
+/// `percent` から `steps` 段だけ大きく (負なら小さく) した倍率。両端で止まる。
+pub fn step(percent: u16, steps: i32) -> u16 {
+    let i = nearest_index(percent) as i32 + steps;
+    LEVELS[i.clamp(0, LEVELS.len() as i32 - 1) as usize]
+}
+
+/// 現在の倍率を `steps` 段動かして保存する。端で動かなければ `false`。
+pub fn zoom_by(steps: i32) -> bool {
+    let next = step(active(), steps);
+    if next == active() {
+        return false;
+    }
+    store(next);
+    true
+}
+
+#[cfg(target_arch = "wasm32")]
+fn get_storage() -> Option<crate::storage::Store> {
+    crate::storage::get()
+}
+
+/// 保存済みの倍率を読み込んで JS に伝える。未保存・破損時は既定値。
+/// ratzilla が列数を読む前 (`DomBackend::new` より前) に呼ぶこと。
+pub fn load() -> u16 {
+    #[cfg(target_arch = "wasm32")]
+    let percent = get_storage()
+        .and_then(|s| s.get_item(ZOOM_KEY).ok().flatten())
+        .and_then(|s| s.parse::<u16>().ok())
+        .map_or(DEFAULT, |p| LEVELS[nearest_index(p)]);
+    #[cfg(not(target_arch = "wasm32"))]
+    let percent = DEFAULT;
+    ACTIVE.with(|z| z.set(percent));
+    imp::apply(percent);
+    percent
+}
+
+/// 倍率を保存して画面に反映する。保存に失敗しても今のセッションには効く。
+pub fn store(percent: u16) {
+    ACTIVE.with(|z| z.set(percent));
+    imp::apply(percent);
+    #[cfg(target_arch = "wasm32")]
+    if let Some(storage) = get_storage() {
+        if let Err(e) = storage.set_item(ZOOM_KEY, &percent.to_string()) {
+            web_sys::console::warn_1(&format!("zoom: localStorage への保存に失敗: {e:?}").into());
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn step_moves_between_levels_and_stops_at_the_ends() {
+        assert_eq!(step(100, 1), 115);
+        assert_eq!(step(100, -2), 80);
+        assert_eq!(step(175, 1), 175);
+        assert_eq!(step(80, -3), 80);
+        // 一覧に無い値は近い段に寄せてから動かす
+        assert_eq!(step(120, 0), 115);
+        assert_eq!(step(999, -1), 150);
+    }
+
+    #[test]
+    fn zoom_by_reports_whether_it_changed() {
+        store(DEFAULT);
+        assert!(zoom_by(1));
+        assert_eq!(active(), 115);
+        store(*LEVELS.last().unwrap());
+        assert!(!zoom_by(1));
+        store(DEFAULT);
+    }
+}
@@ -0,0 +1,276 @@
+//! アーケードトークン — ゲームをまたいで貯まる共通通貨。
+//!
+//! どのゲームでも節目 (Cookie Factory の転生、Dungeon Dive のボス撃破、
+//! Tiny Factory の目標達成) に [`earn`] で貯まり、設定から開く交換所
+//! ([`crate::screens::ArcadeScreen`]) で [`Reward`] と交換する。交換品は
+//! メニューの見た目と、各ゲームを新しく始めたときの小さな開始ボーナス。
+//!
+//! [`crate::toast`] と同じく財布は thread_local に持つので、ゲームの
+//! logic の奥からでも引数を持ち回らずに呼べる。保存は
+//! [`crate::profile::scoped_key`] でプロファイルごと。localStorage は
+//! WASM ビルドでのみ触る。native (cargo test) では読み込みは常に空、
+//! 書き込みは no-op (メモリ上の財布だけが動く)。
+
+use std::cell::RefCell;
+
+#[cfg(target_arch = "wasm32")]
+use crate::profile;
+use crate::toast::{self, Severity};
+
+/// localStorage のキー。値は [`format_wallet`] の文字列。
+#[cfg(target_arch = "wasm32")]
+const WALLET_KEY: &str = "app_arcade_wallet";
+
+// ── 貯まる量 ────────────────────────────────────────────────────
+/// Cookie Factory で転生したとき。
+pub const PRESTIGE_TOKENS: u64 = 5;
+/// Dungeon Dive で各地の主を倒したとき。
+pub const BOSS_TOKENS: u64 = 3;
+/// Dungeon Dive で魔王を倒したとき。
+pub const FINAL_BOSS_TOKENS: u64 = 10;
+/// Tiny Factory のキャンペーン目標を 1 つ達成したとき。
+pub const MILESTONE_TOKENS: u64 = 2;
+
+// ── 開始ボーナスの中身 ──────────────────────────────────────────
+/// [`Reward::CookieHeadStart`]: 新しい工場の最初のクッキー。
+pub const COOKIE_HEAD_START: f64 = 500.0;
+/// [`Reward::RpgStarterKit`]: 最初から持っている薬草。
+pub const RPG_STARTER_HERBS: u32 = 3;
+/// [`Reward::FactorySeedMoney`]: 開始資金への上乗せ。
+pub const FACTORY_SEED_MONEY: u64 = 50;
+
+/// 交換品。保存値は [`Reward::id`] なので既存の ID は変えないこと。
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Reward {
+    /// メニューの枠を金色にする。
+    GoldFrame,
+    /// メニューの見出しを虹色に流す。
+    RainbowTitle,
+    CookieHeadStart,
+    RpgStarterKit,
+    FactorySeedMoney,
+}
+
+impl Reward {
+    /// 交換所の並び順。
+    pub const ALL: [Reward; 5] = [
+        Reward::GoldFrame,
+        Reward::RainbowTitle,
+        Reward::CookieHeadStart,
+        Reward::RpgStarterKit,
+        Reward::FactorySeedMoney,
+    ];
+
+    pub fn id(self) -> &'static str {
+        match self {
+            Reward::GoldFrame => "gold_frame",
+            Reward::RainbowTitle => "rainbow_title",
+            Reward::CookieHeadStart => "cookie_head_start",
+            Reward::RpgStarterKit => "rpg_starter_kit",
+            Reward::FactorySeedMoney => "factory_seed_money",
+        }
+    }
+
+    pub fn from_id(id: &str) -> Option<Reward> {
+        Reward::ALL.into_iter().find(|r| r.id() == id)
+    }
+
+    pub fn name(self) -> &'static str {
+        match self {
+            Reward::GoldFrame => "金の額縁",
+            Reward::RainbowTitle => "虹色の看板",
+            Reward::CookieHeadStart => "焼きたてスタート",
+            Reward::RpgStarterKit => "冒険者の支度",
+            Reward::FactorySeedMoney => "創業資金",
+        }
+    }
+
+    pub fn description(self) -> String {
+        match self {
+            Reward::GoldFrame => "メニューのゲーム一覧の枠が金色になる".into(),
+            Reward::RainbowTitle => "メニューの見出しが虹色に流れる".into(),
+            Reward::CookieHeadStart => {
+                format!("Cookie Factory を新しく始めるとクッキー {} 枚から", COOKIE_HEAD_START)
+            }
+            Reward::RpgStarterKit => {
+                format!("Dungeon Dive を始めるとき薬草を {} 個持っている", RPG_STARTER_HERBS)
+            }
+            Reward::FactorySeedMoney => {
+                format!("Tiny Factory の開始資金 +${}", FACTORY_SEED_MONEY)
+            }
+        }
+    }
+
+    pub fn cost(self) -> u64 {
+        match self {
+            Reward::GoldFrame => 10,
+            Reward::RainbowTitle => 20,
+            Reward::CookieHeadStart | Reward::RpgStarterKit | Reward::FactorySeedMoney => 15,
+        }
+    }
+}
+
+/// トークンの残高と交換済みの品。
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Wallet {
+    pub tokens: u64,
+    /// これまでに貯めた総数 (使っても減らない)。
+    pub earned_total: u64,
+    pub unlocked: Vec<Reward>,
+}
+
+impl Wallet {
+    pub fn earn(&mut self, tokens: u64) {
+        self.tokens = self.tokens.saturating_add(tokens);
+        self.earned_total = self.earned_total.saturating_add(tokens);
+    }
+
+    pub fn owns(&self, reward: Reward) -> bool {
+        self.unlocked.contains(&reward)
+    }
+
+    /// 交換する。交換済み・残高不足なら false。
+    pub fn redeem(&mut self, reward: Reward) -> bool {
+        if self.owns(reward) || self.tokens < reward.cost() {
+            return false;
+        }
+        self.tokens -= reward.cost();
+        self.unlocked.push(reward);
+        true
+    }
+}
+
+/// 保存用の文字列にする (`"tokens;earned_total;id,id"`)。
+pub fn format_wallet(wallet: &Wallet) -> String {
+    let ids: Vec<&str> = wallet.unlocked.iter().map(|r| r.id()).collect();
+    format!("{};{};{}", wallet.tokens, wallet.earned_total, ids.join(","))
+}
+
+/// [`format_wallet`] の逆。数値が壊れていれば `None`、不明な品は読み飛ばす。
+pub fn parse_wallet(s: &str) -> Option<Wallet> {
+    let mut parts = s.split(';');
+    let tokens = parts.next()?.parse().ok()?;
+    let earned_total = parts.next()?.parse().ok()?;
+    let mut unlocked = Vec::new();
+    for reward in parts.next().unwrap_or("").split(',').filter_map(Reward::from_id) {
+        if !unlocked.contains(&reward) {
+            unlocked.push(reward);
+        }
+    }
+    Some(Wallet { tokens, earned_total, unlocked })
+}
+
+thread_local! {
+    /// 現在のプロファイルの財布。render のたびに引くため localStorage は読まない。
+    static WALLET: RefCell<Wallet> = RefCell::new(Wallet::default());
+}
+
+/// 現在の財布の写し。
+pub fn wallet() -> Wallet {
+    WALLET.with(|w| w.borrow().clone())
+}
+
+/// `reward` を交換済みか。
+pub fn owns(reward: Reward) -> bool {
+    WALLET.with(|w| w.borrow().owns(reward))
+}
+
+/// トークンを `tokens` 枚貯めて保存し、`reason` 付きでトーストを出す。
+pub fn earn(reason: &str, tokens: u64) {
+    if tokens == 0 {
+        return;
+    }
+    WALLET.with(|w| w.borrow_mut().earn(tokens));
+    store();
+    toast::push(Severity::Success, format!("🪙 アーケードトークン +{tokens} ({reason})"));
+}
+
+/// 交換して保存する。交換できなければ false。
+pub fn redeem(reward: Reward) -> bool {
+    let ok = WALLET.with(|w| w.borrow_mut().redeem(reward));
+    if ok {
+        store();
+    }
+    ok
+}
+
+#[cfg(target_arch = "wasm32")]
+fn get_storage() -> Option<crate::storage::Store> {
+    crate::storage::get()
+}
+
+/// アクティブなプロファイルの財布を読み込む。未保存・破損時は空。
+/// プロファイルを切り替えたら呼び直すこと。
+pub fn load() {
+    #[cfg(target_arch = "wasm32")]
+    let wallet = get_storage()
+        .and_then(|s| s.get_item(&profile::scoped_key(WALLET_KEY)).ok().flatten())
+        .and_then(|s| parse_wallet(&s))
+        .unwrap_or_default();
+    #[cfg(not(target_arch = "wasm32"))]
+    let wallet = Wallet::default();
+    WALLET.with(|w| *w.borrow_mut() = wallet);
+}
+
+fn store() {
+    #[cfg(target_arch = "wasm32")]
+    if let Some(storage) = get_storage() {
+        let value = WALLET.with(|w| format_wallet(&w.borrow()));
+        if let Err(e) = storage.set_item(&profile::scoped_key(WALLET_KEY), &value) {
+            web_sys::console::warn_1(&format!("arcade: localStorage への保存に失敗: {e:?}").into());
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn redeem_spends_tokens_once() {
+        let mut w = Wallet::default();
+        w.earn(12);
+        assert!(!w.redeem(Reward::RainbowTitle), "残高不足");
+        assert!(w.redeem(Reward::GoldFrame));
+        assert_eq!(w.tokens, 2);
+        assert_eq!(w.earned_total, 12);
+        assert!(w.owns(Reward::GoldFrame));
+        w.earn(100);
+        assert!(!w.redeem(Reward::GoldFrame), "交換済み");
+    }
+
+    #[test]
+    fn wallet_roundtrips_and_skips_unknown_rewards() {
+        let w = Wallet {
+            tokens: 7,
+            earned_total: 40,
+            unlocked: vec![Reward::RpgStarterKit, Reward::GoldFrame],
+        };
+        assert_eq!(parse_wallet(&format_wallet(&w)), Some(w.clone()));
+        let old = parse_wallet("7;40;rpg_starter_kit,career_badge,gold_frame,gold_frame").unwrap();
+        assert_eq!(old, w);
+        assert_eq!(parse_wallet("3;3;"), Some(Wallet { tokens: 3, earned_total: 3, unlocked: vec![] }));
+        assert_eq!(parse_wallet("x;1;"), None);
+        assert_eq!(parse_wallet(""), None);
+    }
+
+    #[test]
+    fn reward_ids_roundtrip() {
+        for r in Reward::ALL {
+            assert_eq!(Reward::from_id(r.id()), Some(r));
+        }
+    }
+
+    #[test]
+    fn earning_goes_through_the_shared_wallet() {
+        load();
+        earn("テスト", 4);
+        assert!(!redeem(Reward::FactorySeedMoney));
+        earn("テスト", 20);
+        assert!(redeem(Reward::FactorySeedMoney));
+        assert!(owns(Reward::FactorySeedMoney));
+        assert_eq!(wallet().tokens, 24 - Reward::FactorySeedMoney.cost());
+        assert!(toast::snapshot().iter().any(|t| t.text.contains("+20")));
+        toast::clear();
+    }
+}
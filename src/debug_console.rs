@@ -0,0 +1,300 @@
+//! 隠しデバッグコンソール (cargo feature `debug-console`)。
+//!
+//! プレイ中に `` ` `` を 3 回続けて押すと開き、1 行コマンドでゲーム状態を
+//! 直接いじれる。深い階層・終盤の機能を手で検証するためのもので、
+//! リリースビルドには含めない:
+//!
+//! ```text
+//! trunk serve --features debug-console
+//! ```
+//!
+//! feature なしのデバッグビルド (`trunk serve`) にも組み込まれるが、
+//! URL に `?debug` を付けたときだけ開ける ([`DebugConsole::from_query`])。
+//!
+//! - 共通コマンド (`help` / `tick <n>`) はここで処理する。
+//! - それ以外は [`crate::games::Game::debug_command`] に空白区切りの
+//!   トークン列で丸ごと渡す。各ゲームは `match args { ["give", "gold", n] => … }`
+//!   の形で自分のコマンドを実装する。
+//! - 開いている間ゲームは一時停止し、キー入力はすべてコンソールが受け取る。
+
+use ratzilla::ratatui::layout::Rect;
+use ratzilla::ratatui::style::{Color, Style};
+use ratzilla::ratatui::text::{Line, Span};
+use ratzilla::ratatui::widgets::{Block, Borders, Clear, Paragraph};
+use ratzilla::ratatui::Frame;
+
+use crate::games::Game;
+
+// 数量の読み取りとエラー文は各ゲームからも使うので engine 側に置いている。
+pub use engine::debug::{parse_amount, parse_count, unknown};
+
+/// コンソールを開くキー。
+pub const OPEN_KEY: char = '`';
+
+/// [`OPEN_KEY`] を何回連続で押すと開くか。
+const OPEN_PRESSES: u8 = 3;
+
+/// 表示・保持する履歴の行数。
+const HISTORY_LINES: usize = 50;
+
+/// 一度に進められる tick の上限 (`tick` コマンド)。1 日分。
+const MAX_DEBUG_TICKS: u32 = 10 * 60 * 60 * 24;
+
+/// feature なしのビルドでコンソールを有効にするクエリパラメータ。
+pub const URL_PARAM: &str = "debug";
+
+pub struct DebugConsole {
+    /// `false` の間は開くシーケンスを無視する。
+    enabled: bool,
+    pub open: bool,
+    pub input: String,
+    /// 入力コマンド (`> ...`) と結果を交互に積む。
+    pub history: Vec<String>,
+    /// 直近で [`OPEN_KEY`] が連続して押された回数。
+    presses: u8,
+}
+
+impl DebugConsole {
+    pub fn new() -> Self {
+        Self {
+            enabled: true,
+            open: false,
+            input: String::new(),
+            history: Vec::new(),
+            presses: 0,
+        }
+    }
+
+    /// `location.search` を見て作る。feature `debug-console` 付きなら
+    /// 常に有効、そうでなければ [`URL_PARAM`] があるときだけ有効。
+    pub fn from_query(search: &str) -> Self {
+        Self {
+            enabled: cfg!(feature = "debug-console") || query_has_flag(search, URL_PARAM),
+            ..Self::new()
+        }
+    }
+
+    /// 閉じている間のキー入力を観察し、開くシーケンスが完成したら開いて
+    /// `true` を返す。途中のキーはゲームにもそのまま流れる。
+    pub fn note_key(&mut self, key: char) -> bool {
+        if !self.enabled || key != OPEN_KEY {
+            self.presses = 0;
+            return false;
+        }
+        self.presses += 1;
+        if self.presses < OPEN_PRESSES {
+            return false;
+        }
+        self.presses = 0;
+        self.open = true;
+        self.input.clear();
+        true
+    }
+
+    pub fn close(&mut self) {
+        self.open = false;
+        self.presses = 0;
+    }
+
+    /// 入力中の行を実行して履歴に積む。
+    pub fn submit(&mut self, game: &mut dyn Game) {
+        let line = std::mem::take(&mut self.input);
+        if line.trim().is_empty() {
+            return;
+        }
+        self.push_history(format!("> {}", line.trim()));
+        let out = match execute(game, &line) {
+            Ok(msg) => msg,
+            Err(msg) => format!("error: {}", msg),
+        };
+        for l in out.lines() {
+            self.push_history(l.to_string());
+        }
+    }
+
+    fn push_history(&mut self, line: String) {
+        self.history.push(line);
+        if self.history.len() > HISTORY_LINES {
+            self.history.remove(0);
+        }
+    }
+
+    /// 画面下部に重ねて描く。
+    pub fn render(&self, f: &mut Frame, area: Rect) {
+        let height = area.height.min(12);
+        if height < 3 {
+            return;
+        }
+        let rect = Rect::new(area.x, area.y + area.height - height, area.width, height);
+        let visible = (height - 3) as usize;
+        let mut lines: Vec<Line> = self
+            .history
+            .iter()
+            .skip(self.history.len().saturating_sub(visible))
+            .map(|l| {
+                let color = if l.starts_with("> ") {
+                    Color::Gray
+                } else if l.starts_with("error:") {
+                    Color::Red
+                } else {
+                    Color::Green
+                };
+                Line::from(Span::styled(l.clone(), Style::default().fg(color)))
+            })
+            .collect();
+        lines.push(Line::from(Span::styled(
+            format!("> {}_", self.input),
+            Style::default().fg(Color::Yellow),
+        )));
+        let block = Block::default()
+            .borders(Borders::ALL)
+            .border_style(Style::default().fg(Color::Magenta))
+            .title(" debug (Esc で閉じる / help) ");
+        f.render_widget(Clear, rect);
+        f.render_widget(Paragraph::new(lines).block(block), rect);
+    }
+}
+
+impl Default for DebugConsole {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// 1 行を実行する。共通コマンド以外はゲームに委ねる。
+pub fn execute(game: &mut dyn Game, line: &str) -> Result<String, String> {
+    let args: Vec<&str> = line.split_whitespace().collect();
+    match args.as_slice() {
+        [] => Ok(String::new()),
+        ["help"] => {
+            let mut out = String::from("tick <n> — n tick 進める");
+            if let Ok(game_help) = game.debug_command(&["help"]) {
+                out.push('\n');
+                out.push_str(&game_help);
+            }
+            Ok(out)
+        }
+        ["tick", n] => {
+            let n = parse_count(n)?.min(MAX_DEBUG_TICKS as u64) as u32;
+            game.tick(n);
+            Ok(format!("{} tick 進めた", n))
+        }
+        _ => game.debug_command(&args),
+    }
+}
+
+/// `?a=1&debug` 形式のクエリに `name` (値の有無は問わない) があるか。
+fn query_has_flag(search: &str, name: &str) -> bool {
+    search
+        .trim_start_matches('?')
+        .split('&')
+        .any(|pair| pair.split('=').next() == Some(name))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::games::cookie::CookieGame;
+    use crate::games::godfield::GodFieldGame;
+
+    #[test]
+    fn open_sequence_needs_consecutive_presses() {
+        let mut c = DebugConsole::new();
+        assert!(!c.note_key('`'));
+        assert!(!c.note_key('`'));
+        assert!(!c.note_key('x'), "other keys reset the streak");
+        assert!(!c.note_key('`'));
+        assert!(!c.note_key('`'));
+        assert!(c.note_key('`'));
+        assert!(c.open);
+    }
+
+    #[test]
+    fn url_flag_gates_the_console_without_the_feature() {
+        assert!(query_has_flag("?debug", URL_PARAM));
+        assert!(query_has_flag("?lang=ja&debug=1", URL_PARAM));
+        assert!(!query_has_flag("?debugger", URL_PARAM));
+        assert!(!query_has_flag("", URL_PARAM));
+
+        if !cfg!(feature = "debug-console") {
+            let mut c = DebugConsole::from_query("");
+            for _ in 0..OPEN_PRESSES {
+                assert!(!c.note_key(OPEN_KEY));
+            }
+            assert!(!c.open);
+        }
+        let mut c = DebugConsole::from_query("?debug");
+        for _ in 0..OPEN_PRESSES - 1 {
+            c.note_key(OPEN_KEY);
+        }
+        assert!(c.note_key(OPEN_KEY));
+    }
+
+    #[test]
+    fn cookie_can_spawn_a_golden_cookie() {
+        let mut g = CookieGame::new();
+        assert!(g.state.golden_event.is_none());
+        execute(&mut g, "spawn golden").unwrap();
+        assert!(g.state.golden_event.is_some());
+    }
+
+    #[test]
+    fn builtin_and_game_commands() {
+        let mut g = CookieGame::new();
+        let help = execute(&mut g, "help").unwrap();
+        assert!(help.contains("tick"));
+        assert!(help.contains("give cookies"), "{}", help);
+
+        execute(&mut g, "give cookies 1e9").unwrap();
+        assert!(g.state.cookies >= 1e9);
+
+        assert!(execute(&mut g, "tick 10").is_ok());
+        assert!(execute(&mut g, "fly away").is_err());
+        assert_eq!(execute(&mut g, "   "), Ok(String::new()));
+    }
+
+    #[test]
+    fn warp_commands_reach_deep_content() {
+        use crate::games::abyss::AbyssGame;
+        use crate::games::drill::DrillGame;
+        use crate::games::rpg::RpgGame;
+
+        let mut rpg = RpgGame::new();
+        execute(&mut rpg, "goto floor 5").unwrap();
+        assert_eq!(rpg.state.max_floor_reached, 5);
+        execute(&mut rpg, "set floor 7").unwrap();
+        assert_eq!(rpg.state.max_floor_reached, 7);
+        assert!(execute(&mut rpg, "goto floor 0").is_err());
+        assert!(execute(&mut rpg, "goto floor 99").is_err());
+
+        let mut abyss = AbyssGame::new();
+        execute(&mut abyss, "goto floor 40").unwrap();
+        assert_eq!(abyss.state.floor, 40);
+        assert_eq!(abyss.state.deepest_floor_ever, 40);
+        assert!(abyss.state.current_enemy.max_hp > 0);
+
+        let mut drill = DrillGame::new();
+        execute(&mut drill, "set depth 1e3").unwrap();
+        assert_eq!(drill.state.depth, 1000.0);
+        assert_eq!(drill.state.best_depth, 1000.0);
+    }
+
+    #[test]
+    fn games_without_commands_still_get_builtins() {
+        let mut g = GodFieldGame::new();
+        assert!(execute(&mut g, "give gold 5").is_err());
+        let help = execute(&mut g, "help").unwrap();
+        assert!(help.contains("tick"));
+    }
+
+    #[test]
+    fn submit_records_history() {
+        let mut c = DebugConsole::new();
+        let mut g = CookieGame::new();
+        c.input = "nope".into();
+        c.submit(&mut g);
+        assert_eq!(c.history[0], "> nope");
+        assert!(c.history[1].starts_with("error:"));
+        assert!(c.input.is_empty());
+    }
+}
@@ -707,6 +707,20 @@ pub fn retreat_to(state: &mut AbyssState, target: u32) {
     spawn_next_enemy(state);
 }
 
+/// デバッグコンソール用: 戦闘を経ずに指定フロアへ直接移動する。
+#[cfg(any(feature = "debug-console", debug_assertions, test))]
+pub fn debug_warp(state: &mut AbyssState, floor: u32) {
+    state.floor = floor.max(1);
+    state.max_floor = state.max_floor.max(state.floor);
+    state.deepest_floor_ever = state.deepest_floor_ever.max(state.floor);
+    state.floor_kind = FloorKind::Normal;
+    state.kills_on_floor = 0;
+    state.hero_hp = state.hero_max_hp();
+    state.combat_focus = 0;
+    state.hero_atk_cooldown = state.hero_atk_period();
+    spawn_next_enemy(state);
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -30,6 +30,7 @@ use tachyonfx::Duration;
 
 use crate::games::{Game, GameChoice};
 use crate::input::{ClickState, InputEvent};
+use crate::scores::Score;
 use crate::sound;
 
 fn now_ms() -> Option<f64> {
@@ -317,6 +318,7 @@ impl Game for AbyssGame {
         let action = match event {
             InputEvent::Key(c) => self.key_to_action(*c),
             InputEvent::Click(_, id) => self.click_to_action(*id),
+            InputEvent::LongPress(..) | InputEvent::DoubleTap(..) | InputEvent::Drag(..) => None,
         };
         if let Some(a) = action {
             let save_after = is_save_worthy(a);
@@ -369,6 +371,10 @@ impl Game for AbyssGame {
         }
     }
 
+    fn save(&mut self) {
+        self.flush_save();
+    }
+
     fn render(&self, f: &mut Frame, area: Rect, click_state: &Rc<RefCell<ClickState>>) {
         self.detect_transitions(area);
         render::render(&self.state, f, area, click_state);
@@ -377,6 +383,34 @@ impl Game for AbyssGame {
             .borrow_mut()
             .process(elapsed, f.buffer_mut(), area);
     }
+
+    fn score(&self) -> Option<Score> {
+        Some(Score::new(self.state.deepest_floor_ever as u64, "最深到達層", "F"))
+    }
+
+    #[cfg(any(feature = "debug-console", debug_assertions, test))]
+    fn debug_command(&mut self, args: &[&str]) -> Result<String, String> {
+        use crate::debug_console::{parse_count, unknown};
+        match args {
+            ["help"] => Ok("give gold <n> / give souls <n> / goto floor <n>".into()),
+            ["give", "gold", n] => {
+                let n = parse_count(n)?;
+                self.state.gold = self.state.gold.saturating_add(n);
+                Ok(format!("ゴールド +{}", n))
+            }
+            ["give", "souls", n] => {
+                let n = parse_count(n)?;
+                self.state.souls = self.state.souls.saturating_add(n);
+                Ok(format!("魂 +{}", n))
+            }
+            ["goto", "floor", n] => {
+                let floor = parse_count(n)?.clamp(1, u32::MAX as u64) as u32;
+                logic::debug_warp(&mut self.state, floor);
+                Ok(format!("B{}F へ移動", floor))
+            }
+            _ => Err(unknown(args)),
+        }
+    }
 }
 
 #[cfg(test)]
@@ -22,9 +22,14 @@ const SAVE_VERSION: u32 = 4;
 #[cfg(target_arch = "wasm32")]
 const MIN_COMPATIBLE_VERSION: u32 = 4;
 
-#[cfg(target_arch = "wasm32")]
 const STORAGE_KEY: &str = "abyss_idle_save";
 
+/// アクティブなプロファイル用に名前空間化した保存キー。
+/// 設定画面のリセットはこのキーの中身をゴミ箱 ([`crate::storage::move_to_trash`]) へ移す。
+pub fn storage_key() -> String {
+    crate::profile::scoped_key(STORAGE_KEY)
+}
+
 /// イベントベース保存の保険として走らせる定期セーブ間隔 (tick 数)。
 pub const AUTOSAVE_INTERVAL: u32 = 300;
 
@@ -195,8 +200,8 @@ fn apply_save(state: &mut AbyssState, save: &GameSave) {
 }
 
 #[cfg(target_arch = "wasm32")]
-fn get_storage() -> Option<web_sys::Storage> {
-    web_sys::window()?.local_storage().ok()?
+fn get_storage() -> Option<crate::storage::Store> {
+    crate::storage::get()
 }
 
 #[cfg(target_arch = "wasm32")]
@@ -212,7 +217,7 @@ pub fn save_game(state: &AbyssState) {
         }
     };
     if let Some(storage) = get_storage() {
-        if let Err(e) = storage.set_item(STORAGE_KEY, &json) {
+        if let Err(e) = storage.set_item(&storage_key(), &json) {
             web_sys::console::warn_1(
                 &format!("Abyss Idle: localStorage への保存に失敗: {e:?}").into(),
             );
@@ -226,7 +231,7 @@ pub fn load_game(state: &mut AbyssState) -> bool {
         Some(s) => s,
         None => return false,
     };
-    let json = match storage.get_item(STORAGE_KEY) {
+    let json = match storage.get_item(&storage_key()) {
         Ok(Some(j)) => j,
         _ => return false,
     };
@@ -236,25 +241,46 @@ pub fn load_game(state: &mut AbyssState) -> bool {
             web_sys::console::warn_1(
                 &format!("Abyss Idle: セーブデータのパースに失敗 (破棄します): {e}").into(),
             );
-            let _ = storage.remove_item(STORAGE_KEY);
+            let _ = storage.remove_item(&storage_key());
             return false;
         }
     };
     if save_data.version < MIN_COMPATIBLE_VERSION {
         // v3 以前 (旧 UpgradeKind 体系) は破棄: 進行軸が根本的に変わったため、
         // 機械的なマイグレーションでは整合が取れない。完全新規スタートさせる。
-        let _ = storage.remove_item(STORAGE_KEY);
+        let _ = storage.remove_item(&storage_key());
         return false;
     }
     apply_save(state, &save_data.game);
     true
 }
 
-#[cfg(target_arch = "wasm32")]
-pub fn delete_save() {
-    if let Some(storage) = get_storage() {
-        let _ = storage.remove_item(STORAGE_KEY);
+/// メインメニュー用の 1 行の進捗要約。セーブが無ければ `None`。
+/// セーブ全体を読み込むので、メニューに入るときに 1 回だけ呼ぶこと。
+pub fn progress_summary() -> Option<String> {
+    #[cfg(target_arch = "wasm32")]
+    {
+        let mut state = AbyssState::new();
+        load_game(&mut state).then(|| summarize(&state))
     }
+    #[cfg(not(target_arch = "wasm32"))]
+    None
+}
+
+#[cfg(any(target_arch = "wasm32", test))]
+fn summarize(state: &AbyssState) -> String {
+    format!("B{}F / 最深 B{}F", state.floor, state.deepest_floor_ever)
+}
+
+/// extract → JSON → apply → extract の前後の JSON を返す (fuzz ハーネスの往復検査用)。
+#[cfg(test)]
+pub(crate) fn roundtrip_json(state: &AbyssState) -> (String, String) {
+    let before = serde_json::to_string(&extract_save(state)).unwrap();
+    let loaded: SaveData = serde_json::from_str(&before).unwrap();
+    let mut restored = AbyssState::new();
+    apply_save(&mut restored, &loaded.game);
+    let after = serde_json::to_string(&extract_save(&restored)).unwrap();
+    (before, after)
 }
 
 #[cfg(test)]
@@ -262,6 +288,14 @@ mod tests {
     use super::*;
     use crate::games::abyss::state::{EquipmentId, EquipmentLane, SoulPerk};
 
+    #[test]
+    fn menu_summary_shows_floors() {
+        let mut s = AbyssState::new();
+        s.floor = 7;
+        s.deepest_floor_ever = 31;
+        assert_eq!(summarize(&s), "B7F / 最深 B31F");
+    }
+
     #[test]
     fn extract_and_apply_roundtrip() {
         let mut original = AbyssState::new();
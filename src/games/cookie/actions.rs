@@ -2,6 +2,13 @@
 //!
 //! Each constant represents a distinct clickable action in the UI.
 //! These IDs are registered during render and dispatched via `InputEvent::Click`.
+//! Per-row IDs are [`ActionRange`]s; the whole table is checked for
+//! overlaps at compile time at the bottom of this file.
+
+use super::state::{
+    FUTURES_BUY_FRACTIONS, GARDEN_H, GARDEN_W, MAX_PINNED_UPGRADES, MAX_PURCHASE_QUEUE,
+};
+use crate::action::{assert_disjoint, ActionRange};
 
 // ── Core actions ────────────────────────────────────────────────
 pub const CLICK_COOKIE: u16 = 0;
@@ -13,38 +20,148 @@ pub const TAB_UPGRADES: u16 = 11;
 pub const TAB_RESEARCH: u16 = 12;
 pub const TAB_MILESTONES: u16 = 13;
 pub const TAB_PRESTIGE: u16 = 14;
+pub const TAB_GARDEN: u16 = 15;
+pub const TAB_FUTURES: u16 = 16;
 
-// ── Producer purchase (base + producer index 0..11) ─────────────
-pub const BUY_PRODUCER_BASE: u16 = 100;
+// ── Producer purchase (producer index 0..11) ────────────────────
+pub const BUY_PRODUCER: ActionRange = ActionRange::new(100, 12);
 
-// ── Upgrade purchase (base + display index) ─────────────────────
-pub const BUY_UPGRADE_BASE: u16 = 200;
+// ── Upgrade purchase (display index) ────────────────────────────
+pub const BUY_UPGRADE: ActionRange = ActionRange::new(200, 100);
 
-// ── Research purchase (base + display index) ────────────────────
-pub const BUY_RESEARCH_BASE: u16 = 300;
+// ── Research purchase (display index) ───────────────────────────
+pub const BUY_RESEARCH: ActionRange = ActionRange::new(300, 100);
+// ── Research path-choice dialog (first purchase) ────────────────
+pub const CONFIRM_RESEARCH_PATH: u16 = 546;
+pub const CANCEL_RESEARCH_PATH: u16 = 547;
 
-// ── Milestone actions ───────────────────────────────────────────
-pub const CLAIM_MILESTONE_BASE: u16 = 400;
+// ── Milestone actions (claim: display index) ────────────────────
+pub const CLAIM_MILESTONE: ActionRange = ActionRange::new(400, 99);
 pub const CLAIM_ALL_MILESTONES: u16 = 499;
+pub const CYCLE_COOKIE_ART: u16 = 545;
 
 // ── Prestige actions ────────────────────────────────────────────
 pub const PRESTIGE_RESET: u16 = 500;
-pub const BUY_PRESTIGE_UPGRADE_BASE: u16 = 600;
+pub const BUY_PRESTIGE_UPGRADE: ActionRange = ActionRange::new(600, 100);
 
-// ── Dragon actions (feed producer to dragon, base + producer index) ──
-pub const DRAGON_FEED_BASE: u16 = 700;
+// ── Dragon actions (feed producer to dragon, producer index) ────
+pub const DRAGON_FEED: ActionRange = ActionRange::new(700, 12);
 pub const DRAGON_CYCLE_AURA: u16 = 799;
+pub const DRAGON_CYCLE_AURA2: u16 = 798;
+// ── Dragon hunt (answer: HuntChoice index) ──────────────────────
+pub const DRAGON_HUNT: ActionRange = ActionRange::new(780, 3);
 
 // ── Prestige sub-section navigation ─────────────────────────────
 pub const PRESTIGE_SEC_UPGRADES: u16 = 520;
 pub const PRESTIGE_SEC_BOOSTS: u16 = 521;
 pub const PRESTIGE_SEC_DRAGON: u16 = 522;
 pub const PRESTIGE_SEC_STATS: u16 = 523;
+pub const PRESTIGE_SEC_CHALLENGES: u16 = 524;
 pub const PRESTIGE_SCROLL_UP: u16 = 530;
 pub const PRESTIGE_SCROLL_DOWN: u16 = 531;
 
+// ── List scrolling (upgrades / milestones ▲▼) ──────────────────
+pub const UPGRADES_SCROLL_UP: u16 = 540;
+pub const UPGRADES_SCROLL_DOWN: u16 = 541;
+pub const MILESTONES_SCROLL_UP: u16 = 542;
+pub const MILESTONES_SCROLL_DOWN: u16 = 543;
+
 // ── Sugar boost / auto-clicker ──────────────────────────────────
 pub const SUGAR_RUSH: u16 = 800;
 pub const SUGAR_FEVER: u16 = 801;
 pub const SUGAR_FRENZY: u16 = 802;
 pub const TOGGLE_AUTO_CLICKER: u16 = 810;
+
+// ── Purchase queue (cancel: queue index) ────────────────────────
+pub const TOGGLE_QUEUE_MODE: u16 = 820;
+pub const CANCEL_QUEUE: ActionRange = ActionRange::new(830, MAX_PURCHASE_QUEUE as u16);
+
+// ── Upgrade pins (open pinned upgrade: pin index) ───────────────
+pub const TOGGLE_PIN_MODE: u16 = 840;
+pub const PINNED_UPGRADE: ActionRange = ActionRange::new(850, MAX_PINNED_UPGRADES as u16);
+
+// ── Producer detail card (opened by long-press on a producer) ───
+pub const CLOSE_PRODUCER_DETAIL: u16 = 860;
+
+// ── CPS breakdown card (opened from the stats row) ──────────────
+pub const TOGGLE_CPS_BREAKDOWN: u16 = 865;
+
+// ── Settings (Prestige → stats section) ─────────────────────────
+pub const CYCLE_ANIMATION_INTENSITY: u16 = 870;
+
+// ── Challenge runs (start: ChallengeKind index) ─────────────────
+pub const START_CHALLENGE: ActionRange = ActionRange::new(880, 8);
+pub const ABANDON_CHALLENGE: u16 = 890;
+
+// ── Garden (seed: SeedKind index, plot: row * GARDEN_W + col) ───
+pub const GARDEN_SEED: ActionRange = ActionRange::new(900, 4);
+pub const GARDEN_PLOT: ActionRange = ActionRange::new(910, (GARDEN_W * GARDEN_H) as u16);
+
+// ── Futures (buy: FUTURES_BUY_FRACTIONS index) ──────────────────
+pub const FUTURES_BUY: ActionRange = ActionRange::new(930, FUTURES_BUY_FRACTIONS.len() as u16);
+pub const FUTURES_SELL: u16 = 939;
+
+// ── Producer level-up (producer index 0..11) ────────────────────
+pub const LEVEL_UP_PRODUCER: ActionRange = ActionRange::new(960, 12);
+
+// ── Combo skills (learn: ComboSkill index) ──────────────────────
+pub const BUY_COMBO_SKILL: ActionRange = ActionRange::new(950, 6);
+
+/// この画面の全 ID。重なりはコンパイル時に検査する。
+pub const ALL_ACTIONS: &[ActionRange] = &[
+    ActionRange::single(CLICK_COOKIE),
+    ActionRange::single(CLAIM_GOLDEN),
+    ActionRange::single(TAB_PRODUCERS),
+    ActionRange::single(TAB_UPGRADES),
+    ActionRange::single(TAB_RESEARCH),
+    ActionRange::single(TAB_MILESTONES),
+    ActionRange::single(TAB_PRESTIGE),
+    ActionRange::single(TAB_GARDEN),
+    ActionRange::single(TAB_FUTURES),
+    ActionRange::single(CLAIM_ALL_MILESTONES),
+    ActionRange::single(CYCLE_COOKIE_ART),
+    ActionRange::single(CONFIRM_RESEARCH_PATH),
+    ActionRange::single(CANCEL_RESEARCH_PATH),
+    ActionRange::single(PRESTIGE_RESET),
+    ActionRange::single(DRAGON_CYCLE_AURA),
+    ActionRange::single(DRAGON_CYCLE_AURA2),
+    ActionRange::single(PRESTIGE_SEC_UPGRADES),
+    ActionRange::single(PRESTIGE_SEC_BOOSTS),
+    ActionRange::single(PRESTIGE_SEC_DRAGON),
+    ActionRange::single(PRESTIGE_SEC_STATS),
+    ActionRange::single(PRESTIGE_SEC_CHALLENGES),
+    ActionRange::single(PRESTIGE_SCROLL_UP),
+    ActionRange::single(PRESTIGE_SCROLL_DOWN),
+    ActionRange::single(UPGRADES_SCROLL_UP),
+    ActionRange::single(UPGRADES_SCROLL_DOWN),
+    ActionRange::single(MILESTONES_SCROLL_UP),
+    ActionRange::single(MILESTONES_SCROLL_DOWN),
+    ActionRange::single(SUGAR_RUSH),
+    ActionRange::single(SUGAR_FEVER),
+    ActionRange::single(SUGAR_FRENZY),
+    ActionRange::single(TOGGLE_AUTO_CLICKER),
+    ActionRange::single(TOGGLE_QUEUE_MODE),
+    ActionRange::single(TOGGLE_PIN_MODE),
+    ActionRange::single(CLOSE_PRODUCER_DETAIL),
+    ActionRange::single(TOGGLE_CPS_BREAKDOWN),
+    ActionRange::single(CYCLE_ANIMATION_INTENSITY),
+    ActionRange::single(ABANDON_CHALLENGE),
+    ActionRange::single(FUTURES_SELL),
+    BUY_PRODUCER,
+    BUY_UPGRADE,
+    BUY_RESEARCH,
+    CLAIM_MILESTONE,
+    BUY_PRESTIGE_UPGRADE,
+    DRAGON_FEED,
+    DRAGON_HUNT,
+    CANCEL_QUEUE,
+    PINNED_UPGRADE,
+    START_CHALLENGE,
+    GARDEN_SEED,
+    GARDEN_PLOT,
+    FUTURES_BUY,
+    BUY_COMBO_SKILL,
+    LEVEL_UP_PRODUCER,
+];
+
+const _: () = assert_disjoint(ALL_ACTIONS);
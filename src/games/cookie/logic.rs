@@ -1,8 +1,18 @@
 //! Cookie Factory game logic — pure functions, fully testable.
 
+use std::borrow::Cow;
+use std::fmt::Write;
+
+use crate::{arcade, numfmt};
+use crate::toast::{self, Severity};
+
+use super::news;
 use super::state::{
-    ActiveBuff, CookieState, DragonAura, GoldenCookieEvent, GoldenEffect, MarketPhase,
-    MilestoneCondition, MilestoneStatus, MiniEventKind, Particle, ParticleStyle, ProducerKind,
+    ActiveBuff, BuffKind, BuffStacking, ChallengeKind, ComboSkill, CookieState, DragonAbility, DragonAura, GoldenCookieEvent,
+    GoldenEffect, HuntChoice, MarketPhase, DRAGON_DUAL_AURA_LEVEL, DRAGON_HUNT_WINDOW, MAX_DRAGON_LEVEL,
+    MilestoneCondition, MilestoneReward, MilestoneStatus, MiniEventKind, Particle, ParticlePanel, ParticleStyle,
+    Plant, ProducerKind, QueuedPurchase, SeedKind, CRIT_CLICK_COOLDOWN, MAX_PINNED_UPGRADES, MAX_PRODUCER_LEVEL,
+    MAX_PURCHASE_QUEUE, MAX_QUEUED_BUFFS,
     ResearchPath, RoiInfo, UpgradeEffect,
 };
 
@@ -51,17 +61,16 @@ pub fn tick(state: &mut CookieState, delta_ticks: u32) {
         state.purchase_flash = state.purchase_flash.saturating_sub(delta_ticks);
     }
 
-    // Combo cooldown: if no click within 15 ticks (1.5s), reset combo
+    state.crit_click_cooldown = state.crit_click_cooldown.saturating_sub(delta_ticks);
+
+    // Combo cooldown: if no click within the combo window (1.5s + skills), reset combo
     state.click_cooldown += delta_ticks;
-    if state.click_cooldown > 15 {
+    if state.click_cooldown > state.combo_window() {
         state.combo_count = 0;
     }
 
     // Update particles
-    for p in &mut state.particles {
-        p.life = p.life.saturating_sub(delta_ticks);
-    }
-    state.particles.retain(|p| p.life > 0);
+    state.particles.tick(delta_ticks);
 
     // Ambient sparkle particles when producing
     if current_cps > 1.0 {
@@ -77,6 +86,9 @@ pub fn tick(state: &mut CookieState, delta_ticks: u32) {
     // Tick auto-clicker
     tick_auto_clicker(state, delta_ticks);
 
+    // Buy reserved producers that became affordable
+    process_purchase_queue(state);
+
     // Tick golden cookie spawning
     tick_golden(state, delta_ticks);
 
@@ -86,9 +98,27 @@ pub fn tick(state: &mut CookieState, delta_ticks: u32) {
     // Tick market phase
     tick_market(state, delta_ticks);
 
+    // Offer / expire dragon hunts
+    tick_dragon_hunt(state, delta_ticks);
+
+    // Grow garden plants
+    tick_garden(state, delta_ticks);
+
+    // Walk the futures ticker (keeps moving while the panel is closed)
+    state.futures.advance(delta_ticks);
+
+    // Rotate the news headline
+    news::tick_news(state, delta_ticks);
+
     // Check milestones
     check_milestones(state);
 
+    // Complete the challenge run once its goal is baked
+    check_challenge(state);
+
+    // Ease the displayed milk level toward the real one
+    tick_milk_display(state, delta_ticks);
+
     // Tick milestone flash
     if state.milestone_flash > 0 {
         state.milestone_flash = state.milestone_flash.saturating_sub(delta_ticks);
@@ -116,6 +146,63 @@ fn tick_buffs(state: &mut CookieState, delta_ticks: u32) {
     for name in expired {
         state.add_log(&format!("  {} 終了", name), false);
     }
+    start_queued_buffs(state);
+}
+
+/// 種類 `kind` のバフが今いくつ掛かっているか。
+fn active_stacks(state: &CookieState, kind: BuffKind) -> usize {
+    state
+        .active_buffs
+        .iter()
+        .filter(|b| b.effect.buff_kind() == Some(kind))
+        .count()
+}
+
+/// バフを [`BuffKind`] の積み方のルールに従って足す。実際に取った積み方を返す。
+pub fn add_buff(state: &mut CookieState, effect: GoldenEffect, ticks: u32) -> BuffStacking {
+    let Some(kind) = effect.buff_kind() else { return BuffStacking::Dropped };
+    if let Some(buff) = state.active_buffs.iter_mut().find(|b| b.effect == effect) {
+        buff.extend(ticks.saturating_sub(buff.ticks_left));
+        return BuffStacking::Refresh;
+    }
+    if active_stacks(state, kind) < kind.stack_limit() {
+        state.active_buffs.push(ActiveBuff::new(effect, ticks));
+        return BuffStacking::Stack;
+    }
+    if state.queued_buffs.len() >= MAX_QUEUED_BUFFS {
+        return BuffStacking::Dropped;
+    }
+    state.queued_buffs.push(ActiveBuff::new(effect, ticks));
+    BuffStacking::Queue
+}
+
+/// 枠の空いた種類の待機バフを古い順に始める。
+fn start_queued_buffs(state: &mut CookieState) {
+    let mut i = 0;
+    while i < state.queued_buffs.len() {
+        let Some(kind) = state.queued_buffs[i].effect.buff_kind() else {
+            state.queued_buffs.remove(i);
+            continue;
+        };
+        if active_stacks(state, kind) < kind.stack_limit() {
+            let buff = state.queued_buffs.remove(i);
+            let detail = buff.effect.detail();
+            state.active_buffs.push(buff);
+            state.add_log(&format!("  ▶ 待機中の{}", detail), false);
+        } else {
+            i += 1;
+        }
+    }
+}
+
+/// [`add_buff`] の結果をログの末尾に添える言葉。
+fn stacking_label(stacking: BuffStacking) -> &'static str {
+    match stacking {
+        BuffStacking::Refresh => " (延長)",
+        BuffStacking::Stack => "",
+        BuffStacking::Queue => " (待機)",
+        BuffStacking::Dropped => " (待機列が満杯)",
+    }
 }
 
 /// Tick golden cookie spawning and expiration.
@@ -141,15 +228,20 @@ fn tick_golden(state: &mut CookieState, delta_ticks: u32) {
     // Count down to next spawn
     state.golden_next_spawn = state.golden_next_spawn.saturating_sub(delta_ticks);
     if state.golden_next_spawn == 0 {
-        // Spawn a golden cookie! Visible for 10 seconds (100 ticks).
-        state.golden_event = Some(GoldenCookieEvent {
-            appear_ticks_left: 100,
-            claimed: false,
-        });
-        state.add_log("✦ ゴールデンクッキー出現！クリックで取得！", true);
+        spawn_golden(state);
     }
 }
 
+/// Spawn a golden cookie now. Visible for 10 seconds (100 ticks).
+pub fn spawn_golden(state: &mut CookieState) {
+    state.golden_event = Some(GoldenCookieEvent {
+        appear_ticks_left: 100,
+        claimed: false,
+    });
+    state.add_log("✦ ゴールデンクッキー出現！クリックで取得！", true);
+    toast::push(Severity::Info, "✦ ゴールデンクッキー出現！");
+}
+
 /// Generate a random spawn delay between 30-90 seconds (300-900 ticks).
 /// Prestige upgrades (GoldenCookieSpeed) can reduce this.
 fn random_spawn_delay(state: &mut CookieState) -> u32 {
@@ -194,18 +286,20 @@ pub fn claim_golden(state: &mut CookieState) -> bool {
         GoldenEffect::ProductionFrenzy { multiplier } => {
             let effective_mult = 1.0 + (multiplier - 1.0) * chain_bonus;
             let ticks = (70.0 * buff_dur_mult * chain_bonus) as u32;
-            state.active_buffs.push(ActiveBuff {
-                effect: GoldenEffect::ProductionFrenzy {
+            let stacking = add_buff(
+                state,
+                GoldenEffect::ProductionFrenzy {
                     multiplier: effective_mult,
                 },
-                ticks_left: ticks,
-            });
+                ticks,
+            );
             state.add_log(
                 &format!(
-                    "🍪 生産×{:.0} 発動中！({:.0}秒){}",
+                    "🍪 生産×{:.0} 発動中！({:.0}秒){}{}",
                     effective_mult,
                     ticks as f64 / 10.0,
                     chain_label,
+                    stacking_label(stacking),
                 ),
                 true,
             );
@@ -213,18 +307,20 @@ pub fn claim_golden(state: &mut CookieState) -> bool {
         GoldenEffect::ClickFrenzy { multiplier } => {
             let effective_mult = 1.0 + (multiplier - 1.0) * chain_bonus;
             let ticks = (100.0 * buff_dur_mult * chain_bonus) as u32;
-            state.active_buffs.push(ActiveBuff {
-                effect: GoldenEffect::ClickFrenzy {
+            let stacking = add_buff(
+                state,
+                GoldenEffect::ClickFrenzy {
                     multiplier: effective_mult,
                 },
-                ticks_left: ticks,
-            });
+                ticks,
+            );
             state.add_log(
                 &format!(
-                    "🍪 クリック×{:.0} 発動中！({:.0}秒){}",
+                    "🍪 クリック×{:.0} 発動中！({:.0}秒){}{}",
                     effective_mult,
                     ticks as f64 / 10.0,
                     chain_label,
+                    stacking_label(stacking),
                 ),
                 true,
             );
@@ -254,6 +350,8 @@ pub fn claim_golden(state: &mut CookieState) -> bool {
     let delay = random_spawn_delay(state);
     state.golden_next_spawn = delay;
 
+    trigger_dragon_abilities(state);
+
     true
 }
 
@@ -337,22 +435,18 @@ fn apply_mini_event(state: &mut CookieState, event: &MiniEventKind) {
             );
         }
         MiniEventKind::SugarRush { multiplier } => {
-            state.active_buffs.push(ActiveBuff {
-                effect: GoldenEffect::ClickFrenzy {
-                    multiplier: *multiplier,
-                },
-                ticks_left: 50, // 5 seconds
-            });
-            state.add_log(&desc, true);
+            let effect = GoldenEffect::ClickFrenzy {
+                multiplier: *multiplier,
+            };
+            let stacking = add_buff(state, effect, 50); // 5 seconds
+            state.add_log(&format!("{}{}", desc, stacking_label(stacking)), true);
         }
         MiniEventKind::ProductionSurge { multiplier, .. } => {
-            state.active_buffs.push(ActiveBuff {
-                effect: GoldenEffect::ProductionFrenzy {
-                    multiplier: *multiplier,
-                },
-                ticks_left: 100, // 10 seconds
-            });
-            state.add_log(&desc, true);
+            let effect = GoldenEffect::ProductionFrenzy {
+                multiplier: *multiplier,
+            };
+            let stacking = add_buff(state, effect, 100); // 10 seconds
+            state.add_log(&format!("{}{}", desc, stacking_label(stacking)), true);
         }
         MiniEventKind::DiscountWave { discount } => {
             state.active_discount = *discount;
@@ -372,6 +466,25 @@ const SPARKLE_CHARS: &[&str] = &["·", "✧", "˚", "°"];
 
 /// Manual click: add cookies_per_click to cookies (with buffs) + spawn particles.
 pub fn click(state: &mut CookieState) {
+    click_with(state, false);
+}
+
+/// Double-tap on the cookie: a guaranteed critical click, then a cooldown of
+/// `CRIT_CLICK_COOLDOWN` ticks during which it is an ordinary click.
+/// Returns true if the click was forced critical.
+pub fn critical_click(state: &mut CookieState) -> bool {
+    let ready = state.crit_click_cooldown == 0;
+    if ready {
+        state.crit_click_cooldown = CRIT_CLICK_COOLDOWN;
+    }
+    click_with(state, ready);
+    ready
+}
+
+fn click_with(state: &mut CookieState, force_critical: bool) {
+    if state.clicks_disabled() {
+        return;
+    }
     let mut power = state.effective_click_power();
 
     // Update combo first (affects critical chance)
@@ -384,9 +497,9 @@ pub fn click(state: &mut CookieState) {
     // Critical hit check
     let crit_chance = state.critical_chance();
     let crit_roll = (state.next_random() % 1000) as f64 / 1000.0;
-    let is_critical = crit_roll < crit_chance;
+    let is_critical = force_critical || crit_roll < crit_chance;
     if is_critical {
-        power *= 7.0;
+        power *= state.critical_multiplier();
     }
 
     state.cookies += power;
@@ -399,21 +512,39 @@ pub fn click(state: &mut CookieState) {
     // Main "+N" particle (critical = special text)
     let col_offset = (state.next_random() % 13) as i16 - 6;
     let life = if is_critical { 12 } else { 8 + (state.next_random() % 5) };
-    let text = if is_critical {
-        format!("⚡+{}", format_number(power))
-    } else if power >= 10.0 {
-        format!("+{}", format_number(power))
+    let merged = if is_critical {
+        false
+    } else if let Some(p) = state.particles.mergeable_click() {
+        // Rapid clicks fold into the "+N" still rising from the last one.
+        p.amount += power;
+        let text = p.text.to_mut();
+        text.clear();
+        write_click_text(text, p.amount);
+        true
     } else {
-        format!("+{}", power as u32)
+        false
     };
-    state.particles.push(Particle {
-        text,
-        col_offset,
-        life,
-        max_life: life,
-        style: ParticleStyle::Click,
-        row_offset: 0,
-    });
+    if !merged {
+        let mut text = state.particles.text_buf();
+        let amount = if is_critical {
+            text.push_str("⚡+");
+            push_number(&mut text, power);
+            0.0
+        } else {
+            write_click_text(&mut text, power);
+            power
+        };
+        state.particles.spawn(Particle {
+            text: Cow::Owned(text),
+            col_offset,
+            life,
+            max_life: life,
+            style: ParticleStyle::Click,
+            row_offset: 0,
+            panel: ParticlePanel::Cookie,
+            amount,
+        });
+    }
 
     // Critical hit burst particles
     if is_critical {
@@ -425,35 +556,51 @@ pub fn click(state: &mut CookieState) {
         let idx = state.next_random() as usize % CLICK_ACCENTS.len();
         let col = (state.next_random() % 11) as i16 - 5;
         let life = 6 + (state.next_random() % 4);
-        state.particles.push(Particle {
-            text: CLICK_ACCENTS[idx].to_string(),
+        state.particles.spawn(Particle {
+            text: Cow::Borrowed(CLICK_ACCENTS[idx]),
             col_offset: col,
             life,
             max_life: life,
             style: ParticleStyle::Emoji,
             row_offset: 0,
+            panel: ParticlePanel::Cookie,
+            amount: 0.0,
         });
     }
 
     // Combo milestone indicator — concise, elegant
     if combo >= 10 && combo.is_multiple_of(10) {
-        let combo_text = format!("── {}連打 ──", combo);
-        state.particles.push(Particle {
-            text: combo_text,
+        let mut combo_text = state.particles.text_buf();
+        let _ = write!(combo_text, "── {}連打 ──", combo);
+        state.particles.spawn(Particle {
+            text: Cow::Owned(combo_text),
             col_offset: 0,
             life: 12,
             max_life: 12,
             style: ParticleStyle::Combo,
             row_offset: -2,
+            panel: ParticlePanel::Cookie,
+            amount: 0.0,
         });
     }
+}
 
-    // Cap particles to avoid memory issues
-    while state.particles.len() > 30 {
-        state.particles.remove(0);
+/// Write the "+N" text for a click particle carrying `amount` cookies.
+fn write_click_text(out: &mut String, amount: f64) {
+    out.push('+');
+    if amount >= 10.0 {
+        push_number(out, amount);
+    } else {
+        let _ = write!(out, "{}", amount as u32);
     }
 }
 
+/// Cycle the animation intensity and apply its particle cap.
+pub fn cycle_animation_intensity(state: &mut CookieState) {
+    state.animation_intensity = state.animation_intensity.next();
+    state.particles.set_cap(state.animation_intensity.particle_cap());
+}
+
 /// Spawn ambient sparkle particles during production.
 fn spawn_ambient_particles(state: &mut CookieState, _delta_ticks: u32) {
     // Spawn rate: subtle, scales with CPS but never overwhelming
@@ -473,19 +620,51 @@ fn spawn_ambient_particles(state: &mut CookieState, _delta_ticks: u32) {
         let col = (state.next_random() % 25) as i16 - 12;
         let row = (state.next_random() % 7) as i16 - 3;
         let life = 3 + (state.next_random() % 4);
-        state.particles.push(Particle {
-            text: SPARKLE_CHARS[idx].to_string(),
+        state.particles.spawn(Particle {
+            text: Cow::Borrowed(SPARKLE_CHARS[idx]),
             col_offset: col,
             life,
             max_life: life,
             style: ParticleStyle::Sparkle,
             row_offset: row,
+            panel: ParticlePanel::Cookie,
+            amount: 0.0,
+        });
+    }
+}
+
+/// Milk droplet glyphs for the milestone splash.
+const MILK_SPLASH_CHARS: &[&str] = &["•", "∘", "°", "·"];
+
+/// Spawn a milk-splash burst over the milestones panel.
+/// Droplets start on the milk bar row and drip down into the list.
+pub fn spawn_milk_splash(state: &mut CookieState, count: u32) {
+    for _ in 0..count {
+        let idx = state.next_random() as usize % MILK_SPLASH_CHARS.len();
+        // Spread across the 20-cell milk bar (bar starts ~16 cols in)
+        let col = 16 + (state.next_random() % 20) as i16;
+        let life = 6 + (state.next_random() % 5);
+        state.particles.spawn(Particle {
+            text: Cow::Borrowed(MILK_SPLASH_CHARS[idx]),
+            col_offset: col,
+            life,
+            max_life: life,
+            style: ParticleStyle::MilkSplash,
+            row_offset: 0,
+            panel: ParticlePanel::Milestones,
+            amount: 0.0,
         });
     }
+}
 
-    // Cap particles
-    while state.particles.len() > 30 {
-        state.particles.remove(0);
+/// Move `milk_display` toward `milk`: +1 percentage point per tick while filling,
+/// snapping down immediately (prestige reset) so it never lags backwards.
+fn tick_milk_display(state: &mut CookieState, delta_ticks: u32) {
+    if state.milk_display > state.milk {
+        state.milk_display = state.milk;
+    } else if state.milk_display < state.milk {
+        let step = 0.01 * delta_ticks as f64;
+        state.milk_display = (state.milk_display + step).min(state.milk);
     }
 }
 
@@ -496,18 +675,17 @@ pub fn spawn_celebration(state: &mut CookieState, count: u32) {
         let col = (state.next_random() % 15) as i16 - 7;
         let row = (state.next_random() % 5) as i16 - 2;
         let life = 8 + (state.next_random() % 6);
-        state.particles.push(Particle {
-            text: CELEBRATION_ACCENTS[idx].to_string(),
+        state.particles.spawn(Particle {
+            text: Cow::Borrowed(CELEBRATION_ACCENTS[idx]),
             col_offset: col,
             life,
             max_life: life,
             style: ParticleStyle::Celebration,
             row_offset: row,
+            panel: ParticlePanel::Cookie,
+            amount: 0.0,
         });
     }
-    while state.particles.len() > 30 {
-        state.particles.remove(0);
-    }
 }
 
 /// Try to buy a producer by kind. Returns true if successful.
@@ -555,13 +733,15 @@ pub fn buy_producer(state: &mut CookieState, kind: &ProducerKind) -> bool {
 
         // Spawn quality feedback particle for good purchases
         if is_best_roi {
-            state.particles.push(Particle {
-                text: if is_crash { "GREAT DEAL!".into() } else { "Good!".into() },
+            state.particles.spawn(Particle {
+                text: Cow::Borrowed(if is_crash { "GREAT DEAL!" } else { "Good!" }),
                 col_offset: 0,
                 life: 15,
                 max_life: 15,
                 style: ParticleStyle::Celebration,
                 row_offset: 0,
+                panel: ParticlePanel::Cookie,
+                amount: 0.0,
             });
         }
 
@@ -575,6 +755,80 @@ pub fn buy_producer(state: &mut CookieState, kind: &ProducerKind) -> bool {
     }
 }
 
+/// Level up a producer type (+10% to that type per level). Requires at
+/// least one unit. Returns true if successful.
+pub fn level_up_producer(state: &mut CookieState, kind: &ProducerKind) -> bool {
+    let idx = kind.index();
+    let p = &state.producers[idx];
+    if p.count == 0 || p.level >= MAX_PRODUCER_LEVEL {
+        return false;
+    }
+    let cost = p.level_up_cost() * state.total_cost_modifier();
+    if state.cookies < cost {
+        return false;
+    }
+    state.cookies -= cost;
+    let p = &mut state.producers[idx];
+    p.level += 1;
+    let msg = format!(
+        "⬆ {} が Lv{} に！ (生産 +{:.0}%)",
+        kind.name(),
+        p.level,
+        (p.level_multiplier() - 1.0) * 100.0
+    );
+    state.purchase_flash = 8;
+    state.add_log(&msg, false);
+    true
+}
+
+/// Reserve `amount` units of a producer, bought in `tick` once affordable.
+/// Queuing a kind that's already queued adds to that entry. Returns false
+/// when the queue is full.
+pub fn queue_producer(state: &mut CookieState, kind: &ProducerKind, amount: u32) -> bool {
+    if amount == 0 {
+        return false;
+    }
+    if let Some(entry) = state.purchase_queue.iter_mut().find(|q| q.kind == *kind) {
+        entry.remaining += amount;
+    } else if state.purchase_queue.len() < MAX_PURCHASE_QUEUE {
+        state.purchase_queue.push(QueuedPurchase {
+            kind: kind.clone(),
+            remaining: amount,
+        });
+    } else {
+        state.add_log("⚠ 予約がいっぱいです", false);
+        return false;
+    }
+    state.add_log(&format!("📋 {} ×{} を予約", kind.name(), amount), false);
+    true
+}
+
+/// Cancel a queued purchase by queue index. Returns true if removed.
+pub fn cancel_queued(state: &mut CookieState, index: usize) -> bool {
+    if index >= state.purchase_queue.len() {
+        return false;
+    }
+    let entry = state.purchase_queue.remove(index);
+    state.add_log(&format!("📋 {} の予約を取消", entry.kind.name()), false);
+    true
+}
+
+/// Buy from the front of the queue while affordable. Strict FIFO: a
+/// pricey head entry blocks later ones, so the player's order is respected.
+fn process_purchase_queue(state: &mut CookieState) {
+    while let Some(kind) = state.purchase_queue.first().map(|q| q.kind.clone()) {
+        if !buy_producer(state, &kind) {
+            break;
+        }
+        let head = &mut state.purchase_queue[0];
+        head.remaining -= 1;
+        if head.remaining == 0 {
+            state.purchase_queue.remove(0);
+            state.add_log(&format!("📋 予約完了: {}", kind.name()), true);
+        }
+    }
+}
+
 /// Check if a producer is the best ROI option among affordable producers.
 fn is_best_roi_producer(state: &CookieState, kind: &ProducerKind) -> bool {
     let target_idx = kind.index();
@@ -642,6 +896,7 @@ pub fn buy_upgrade(state: &mut CookieState, upgrade_idx: usize) -> bool {
         state.active_discount = 0.0;
     }
     state.upgrades[upgrade_idx].purchased = true;
+    state.pinned_upgrades.retain(|&i| i != upgrade_idx);
     state.purchase_flash = 8; // longer flash for upgrades (0.8s)
     spawn_celebration(state, 3); // subtle celebration
 
@@ -653,6 +908,43 @@ pub fn buy_upgrade(state: &mut CookieState, upgrade_idx: usize) -> bool {
     true
 }
 
+/// Pin or unpin an upgrade (by real index). Returns true if the pin state changed.
+/// Purchased upgrades and ones locked out by an exclusive group can't be pinned.
+pub fn toggle_pin_upgrade(state: &mut CookieState, upgrade_idx: usize) -> bool {
+    if let Some(pos) = state.pinned_upgrades.iter().position(|&i| i == upgrade_idx) {
+        state.pinned_upgrades.remove(pos);
+        let name = state.upgrades[upgrade_idx].name.clone();
+        state.add_log(&format!("📌 {} のピンを外した", name), false);
+        return true;
+    }
+    let Some(upgrade) = state.upgrades.get(upgrade_idx) else {
+        return false;
+    };
+    if upgrade.purchased {
+        return false;
+    }
+    if let Some(group) = upgrade.exclusive_group {
+        let blocked = state
+            .upgrades
+            .iter()
+            .any(|u| u.purchased && u.exclusive_group == Some(group));
+        if blocked {
+            return false;
+        }
+    }
+    if state.pinned_upgrades.len() >= MAX_PINNED_UPGRADES {
+        state.add_log(
+            &format!("⚠ ピン留めは{}個までです", MAX_PINNED_UPGRADES),
+            false,
+        );
+        return false;
+    }
+    let name = upgrade.name.clone();
+    state.pinned_upgrades.push(upgrade_idx);
+    state.add_log(&format!("📌 {} をピン留め", name), false);
+    true
+}
+
 /// Apply an upgrade's effect to the game state.
 fn apply_upgrade_effect(state: &mut CookieState, effect: &UpgradeEffect, name: &str) {
     match effect {
@@ -745,6 +1037,7 @@ pub fn check_milestones(state: &mut CookieState) {
 
     if newly_ready {
         state.milestone_flash = 15; // 1.5 seconds flash to draw attention
+        toast::push(Severity::Success, "🏆 マイルストーン達成！受け取れます");
     }
 }
 
@@ -757,6 +1050,8 @@ pub fn claim_milestone(state: &mut CookieState, index: usize) -> bool {
         return false;
     }
 
+    // Cookie lumps pay out at the CPS the player had when they claimed.
+    let cps = state.total_cps();
     state.milestones[index].status = MilestoneStatus::Claimed;
 
     // Recalculate milk
@@ -767,12 +1062,17 @@ pub fn claim_milestone(state: &mut CookieState, index: usize) -> bool {
     recalculate_kitten_multiplier(state);
 
     let name = state.milestones[index].name.clone();
+    let reward = state.milestones[index].reward;
+    let reward = grant_milestone_reward(state, reward, cps)
+        .map(|r| format!(" {}", r))
+        .unwrap_or_default();
     state.add_log(
-        &format!("🏆 解放！「{}」 (ミルク: {:.0}%)", name, state.milk * 100.0),
+        &format!("🏆 解放！「{}」 (ミルク: {:.0}%){}", name, state.milk * 100.0, reward),
         true,
     );
     state.milestone_flash = 15;
     spawn_celebration(state, 4);
+    spawn_milk_splash(state, 6);
     true
 }
 
@@ -786,6 +1086,8 @@ pub fn claim_all_milestones(state: &mut CookieState) -> usize {
     if count == 0 {
         return 0;
     }
+    // Cookie lumps pay out at the CPS the player had when they claimed.
+    let cps = state.total_cps();
     for idx in &ready_indices {
         state.milestones[*idx].status = MilestoneStatus::Claimed;
     }
@@ -794,17 +1096,72 @@ pub fn claim_all_milestones(state: &mut CookieState) -> usize {
     state.milk = achieved * 0.04;
     recalculate_kitten_multiplier(state);
 
+    let mut rewards: Vec<String> = Vec::new();
+    for idx in &ready_indices {
+        let reward = state.milestones[*idx].reward;
+        rewards.extend(grant_milestone_reward(state, reward, cps));
+    }
     let names: Vec<String> = ready_indices.iter()
         .map(|i| state.milestones[*i].name.clone())
         .collect();
+    let reward_text = if rewards.is_empty() {
+        String::new()
+    } else {
+        format!(" {}", rewards.join(" "))
+    };
     state.add_log(
-        &format!("🏆 {}個解放！「{}」 (ミルク: {:.0}%)", count, names.join("」「"), state.milk * 100.0),
+        &format!(
+            "🏆 {}個解放！「{}」 (ミルク: {:.0}%){}",
+            count,
+            names.join("」「"),
+            state.milk * 100.0,
+            reward_text
+        ),
         true,
     );
     state.milestone_flash = 15;
+    spawn_milk_splash(state, (4 + count as u32 * 2).min(12));
     count
 }
 
+/// Apply a milestone's extra reward. Returns the log text, or `None` for
+/// milk-only milestones (milk is handled by the caller). Cookie lumps are
+/// `cps` × seconds, with `cps` taken before the claim.
+fn grant_milestone_reward(
+    state: &mut CookieState,
+    reward: MilestoneReward,
+    cps: f64,
+) -> Option<String> {
+    match reward {
+        MilestoneReward::Milk => None,
+        MilestoneReward::Sugar(n) => {
+            state.sugar += n;
+            state.sugar_all_time += n;
+            Some(format!("🍬+{}", n))
+        }
+        MilestoneReward::CookieLump(secs) => {
+            let bonus = cps * secs;
+            state.cookies += bonus;
+            state.cookies_all_time += bonus;
+            Some(format!("🍪+{}", format_number(bonus)))
+        }
+        MilestoneReward::Art(art) => {
+            if !state.cookie_arts.contains(&art) {
+                state.cookie_arts.push(art);
+            }
+            state.cookie_art = art;
+            Some(format!("🎨「{}」", art.name()))
+        }
+    }
+}
+
+/// Switch the main cookie to the next unlocked art.
+pub fn cycle_cookie_art(state: &mut CookieState) {
+    let arts = state.unlocked_cookie_arts();
+    let pos = arts.iter().position(|a| *a == state.cookie_art).unwrap_or(0);
+    state.cookie_art = arts[(pos + 1) % arts.len()];
+}
+
 /// Recalculate kitten_multiplier from milk and purchased kitten upgrades.
 pub fn recalculate_kitten_multiplier(state: &mut CookieState) {
     let mut multiplier = 1.0;
@@ -831,6 +1188,7 @@ pub fn perform_prestige(state: &mut CookieState) -> u64 {
     state.cookies_all_runs += state.cookies_all_time;
     state.heavenly_chips += new_chips;
     state.prestige_count += 1;
+    arcade::earn("転生", arcade::PRESTIGE_TOKENS);
 
     // Award sugar (20% of new chips — increased from 10% for stronger prestige reward)
     let new_sugar = new_chips / 5;
@@ -942,20 +1300,28 @@ pub fn perform_prestige(state: &mut CookieState) -> u64 {
     }
 
     state.upgrades = CookieState::create_upgrades();
+    state.combo_skills.clear();
     state.log.clear();
     state.show_upgrades = false;
     state.show_research = false;
     state.show_milestones = false;
     state.show_prestige = false;
+    state.show_garden = false;
+    state.show_futures = false;
+    // The ticker keeps its price; open positions are lost with the cookies.
+    state.futures.units = 0.0;
+    state.futures.cost_basis = 0.0;
     state.anim_frame = 0;
     state.click_flash = 0;
     state.purchase_flash = 0;
     state.particles.clear();
+    state.purchase_queue.clear();
     state.synergy_multiplier = 1.0;
     state.cross_synergies.clear();
     state.golden_next_spawn = 200;
     state.golden_event = None;
     state.active_buffs.clear();
+    state.queued_buffs.clear();
     state.golden_cookies_claimed = 0;
     state.count_scalings.clear();
     state.cps_percent_bonuses.clear();
@@ -974,9 +1340,12 @@ pub fn perform_prestige(state: &mut CookieState) -> u64 {
     state.prev_cps = 0.0;
     state.cookies_earned_window = 0.0;
     state.peak_cookies_per_sec = 0.0;
+    // A reset ends any challenge in progress (start_challenge sets it after)
+    state.active_challenge = None;
 
     // Reset research (player can choose a different path next run)
     state.research_path = ResearchPath::None;
+    state.research_confirm = None;
     state.research_nodes = CookieState::create_research_nodes();
 
     // Market continues (not reset)
@@ -1031,6 +1400,192 @@ pub fn buy_prestige_upgrade(state: &mut CookieState, index: usize) -> bool {
     true
 }
 
+// ═══════════════════════════════════════════════════════
+// Garden
+// ═══════════════════════════════════════════════════════
+
+/// Grow every planted seed at the current garden rate (milk × market).
+fn tick_garden(state: &mut CookieState, delta_ticks: u32) {
+    let growth = delta_ticks as f64 * state.garden_growth_rate();
+    for plant in state.garden.iter_mut().flatten() {
+        plant.growth += growth;
+    }
+}
+
+/// Choose the seed planted by tapping an empty plot.
+pub fn select_seed(state: &mut CookieState, seed: SeedKind) {
+    state.selected_seed = seed;
+}
+
+/// Tap a plot: plant the selected seed if empty, harvest if mature.
+/// Returns true if something happened.
+pub fn tap_plot(state: &mut CookieState, plot: usize) -> bool {
+    match state.garden.get(plot) {
+        Some(None) => plant_seed(state, plot),
+        Some(Some(plant)) if plant.is_mature() => harvest_plant(state, plot),
+        _ => false,
+    }
+}
+
+/// Buy the selected seed and plant it in an empty plot.
+pub fn plant_seed(state: &mut CookieState, plot: usize) -> bool {
+    if !matches!(state.garden.get(plot), Some(None)) {
+        return false;
+    }
+    let seed = state.selected_seed;
+    let cost = seed.cost(state.total_cps());
+    if state.cookies < cost {
+        return false;
+    }
+    state.cookies -= cost;
+    state.garden[plot] = Some(Plant { seed, growth: 0.0 });
+    true
+}
+
+/// Harvest a mature plant and apply its effect.
+pub fn harvest_plant(state: &mut CookieState, plot: usize) -> bool {
+    let seed = match state.garden.get(plot) {
+        Some(Some(plant)) if plant.is_mature() => plant.seed,
+        _ => return false,
+    };
+    state.garden[plot] = None;
+    match seed {
+        SeedKind::Bakerwheat => {
+            let bonus = state.total_cps() * 60.0;
+            state.cookies += bonus;
+            state.cookies_all_time += bonus;
+        }
+        SeedKind::Thumbcorn => {
+            add_buff(state, GoldenEffect::ClickFrenzy { multiplier: 3.0 }, 200);
+        }
+        SeedKind::Cronerice => {
+            add_buff(state, GoldenEffect::ProductionFrenzy { multiplier: 1.5 }, 300);
+        }
+        SeedKind::Sugarcane => {
+            state.sugar += 1;
+            state.sugar_all_time += 1;
+        }
+    }
+    state.add_log(&format!("🌱 {} を収穫！ {}", seed.name(), seed.description()), false);
+    state.purchase_flash = 10;
+    true
+}
+
+// ═══════════════════════════════════════════════════════
+// Futures
+// ═══════════════════════════════════════════════════════
+
+/// Invest `fraction` of the current cookies at the current price.
+/// Returns true if anything was bought.
+pub fn invest_futures(state: &mut CookieState, fraction: f64) -> bool {
+    let amount = (state.cookies * fraction).floor();
+    if amount < 1.0 {
+        return false;
+    }
+    state.cookies -= amount;
+    state.futures.units += amount / state.futures.price;
+    state.futures.cost_basis += amount;
+    state.add_log(
+        &format!("📈 先物を {} 枚分購入 (×{:.2})", format_number(amount), state.futures.price),
+        false,
+    );
+    state.purchase_flash = 10;
+    true
+}
+
+/// Sell the whole position at the current price. Gains count toward
+/// `cookies_all_time` like any other income; losses don't reduce it.
+pub fn sell_futures(state: &mut CookieState) -> bool {
+    if !state.futures.has_position() {
+        return false;
+    }
+    let value = state.futures.position_value();
+    let profit = state.futures.profit();
+    state.cookies += value;
+    if profit > 0.0 {
+        state.cookies_all_time += profit;
+    }
+    state.futures.units = 0.0;
+    state.futures.cost_basis = 0.0;
+    let sign = if profit >= 0.0 { "+" } else { "-" };
+    state.add_log(
+        &format!(
+            "💹 先物を決済: {} 枚 ({}{})",
+            format_number(value),
+            sign,
+            format_number(profit.abs())
+        ),
+        profit > 0.0,
+    );
+    true
+}
+
+// ═══════════════════════════════════════════════════════
+// Challenge runs
+// ═══════════════════════════════════════════════════════
+
+/// Prestige and start `kind` as the new run. Needs pending heavenly chips
+/// like a normal prestige. Returns true if the challenge started.
+pub fn start_challenge(state: &mut CookieState, kind: ChallengeKind) -> bool {
+    if state.active_challenge.is_some() {
+        state.add_log("⚠ 挑戦中です (中断してから選び直してください)", true);
+        return false;
+    }
+    if perform_prestige(state) == 0 {
+        return false;
+    }
+    state.active_challenge = Some(kind);
+    state.add_log(
+        &format!(
+            "⚔ 挑戦開始: {} ({}) — {}枚で達成",
+            kind.name(),
+            kind.description(),
+            format_number(kind.goal())
+        ),
+        true,
+    );
+    true
+}
+
+/// Give up the active challenge. The run continues without the modifier
+/// and without the reward. Returns true if a challenge was active.
+pub fn abandon_challenge(state: &mut CookieState) -> bool {
+    let Some(kind) = state.active_challenge.take() else {
+        return false;
+    };
+    state.add_log(&format!("⚔ 挑戦を中断: {}", kind.name()), true);
+    true
+}
+
+/// Complete the active challenge when this run has baked its goal.
+fn check_challenge(state: &mut CookieState) {
+    let Some(kind) = state.active_challenge else {
+        return;
+    };
+    if state.cookies_all_time < kind.goal() {
+        return;
+    }
+    state.active_challenge = None;
+    let reward = kind.reward_chips();
+    state.heavenly_chips += reward;
+    state.bonus_chips += reward;
+    let first = !state.challenges_completed.contains(&kind);
+    if first {
+        state.challenges_completed.push(kind);
+    }
+    state.add_log(
+        &format!(
+            "🏆 挑戦達成: {}！ 天国チップ+{}{}",
+            kind.name(),
+            reward,
+            if first { " (バッジ獲得)" } else { "" }
+        ),
+        true,
+    );
+    toast::push(Severity::Success, format!("🏆 挑戦達成: {}", kind.name()));
+    spawn_celebration(state, 6);
+}
+
 // ═══════════════════════════════════════════════════════
 // Sugar Boost System
 // ═══════════════════════════════════════════════════════
@@ -1104,7 +1659,7 @@ fn tick_sugar_boost(state: &mut CookieState, delta_ticks: u32) {
 /// Tick the auto-clicker (unlocked at prestige 1).
 fn tick_auto_clicker(state: &mut CookieState, delta_ticks: u32) {
     // Check if unlocked and enabled
-    if !state.is_auto_clicker_unlocked() || !state.auto_clicker_enabled {
+    if !state.is_auto_clicker_unlocked() || !state.auto_clicker_enabled || state.clicks_disabled() {
         return;
     }
 
@@ -1140,6 +1695,21 @@ pub fn toggle_auto_clicker(state: &mut CookieState) -> bool {
     state.auto_clicker_enabled
 }
 
+/// Open or close the CPS breakdown card. It takes the producers list's
+/// place, so opening it also returns to the producers tab.
+pub fn toggle_cps_breakdown(state: &mut CookieState) {
+    state.show_cps_breakdown = !state.show_cps_breakdown;
+    if state.show_cps_breakdown {
+        state.producer_detail = None;
+        state.show_upgrades = false;
+        state.show_research = false;
+        state.show_milestones = false;
+        state.show_prestige = false;
+        state.show_garden = false;
+        state.show_futures = false;
+    }
+}
+
 // ═══════════════════════════════════════════════════════
 // Market phase cycling
 // ═══════════════════════════════════════════════════════
@@ -1221,11 +1791,6 @@ fn tick_market(state: &mut CookieState, delta_ticks: u32) {
 // Market display helpers — pure functions for render
 // ═══════════════════════════════════════════════════════
 
-/// Remaining market phase time in whole seconds (10 ticks = 1 second).
-pub fn market_seconds_left(ticks_left: u32) -> u32 {
-    ticks_left / 10
-}
-
 /// Buy-time phases: producer costs are below normal, so buying is favored.
 pub fn is_market_buy_time(phase: &MarketPhase) -> bool {
     phase.cost_multiplier() < 1.0
@@ -1259,46 +1824,36 @@ fn market_effect_text(phase: &MarketPhase) -> String {
 }
 
 /// Market banner line for wide layouts (>= 60 cols).
-/// e.g. 「💥 市場: 暴落！ 生産者75%OFF (残り23秒)」
-pub fn market_banner_wide(phase: &MarketPhase, ticks_left: u32) -> String {
-    let secs = market_seconds_left(ticks_left);
+/// e.g. 「💥 市場: 暴落！ 生産者75%OFF」 (render appends the countdown chip)
+pub fn market_banner_wide(phase: &MarketPhase) -> String {
     let effect = market_effect_text(phase);
     if effect.is_empty() {
-        format!("{} 市場: {} (残り{}秒)", phase.symbol(), phase.name(), secs)
+        format!("{} 市場: {}", phase.symbol(), phase.name())
     } else {
-        format!(
-            "{} 市場: {} {} (残り{}秒)",
-            phase.symbol(),
-            phase.name(),
-            effect,
-            secs
-        )
+        format!("{} 市場: {} {}", phase.symbol(), phase.name(), effect)
     }
 }
 
 /// Compact market banner for narrow layouts (< 60 cols).
-/// e.g. 「💥暴落！75%OFF(23s)」
-pub fn market_banner_narrow(phase: &MarketPhase, ticks_left: u32) -> String {
-    let secs = market_seconds_left(ticks_left);
+/// e.g. 「💥暴落！75%OFF」 (render appends the countdown chip)
+pub fn market_banner_narrow(phase: &MarketPhase) -> String {
     let cost = phase.cost_multiplier();
     if is_market_buy_time(phase) {
         format!(
-            "{}{}{:.0}%OFF({}s)",
+            "{}{}{:.0}%OFF",
             phase.symbol(),
             phase.name(),
-            (1.0 - cost) * 100.0,
-            secs
+            (1.0 - cost) * 100.0
         )
     } else if cost > 1.0 {
         format!(
-            "{}{} コスト×{}({}s)",
+            "{}{} コスト×{}",
             phase.symbol(),
             phase.name(),
-            format_market_multiplier(cost),
-            secs
+            format_market_multiplier(cost)
         )
     } else {
-        format!("{}{}({}s)", phase.symbol(), phase.name(), secs)
+        format!("{}{}", phase.symbol(), phase.name())
     }
 }
 
@@ -1306,53 +1861,70 @@ pub fn market_banner_narrow(phase: &MarketPhase, ticks_left: u32) -> String {
 // Research Tree
 // ═══════════════════════════════════════════════════════
 
-/// Buy a research node by index. Returns true if successful.
-/// The first purchase locks the player into that research path.
-pub fn buy_research(state: &mut CookieState, index: usize) -> bool {
-    if index >= state.research_nodes.len() {
+/// Whether research node `index` can be bought right now (path, prerequisite
+/// tier and cookies).
+fn can_buy_research(state: &CookieState, index: usize) -> bool {
+    let Some(node) = state.research_nodes.get(index) else {
         return false;
-    }
-    if state.research_nodes[index].purchased {
+    };
+    if node.purchased {
         return false;
     }
 
-    let node_path = state.research_nodes[index].path.clone();
-    let node_tier = state.research_nodes[index].tier;
-
     // Check if path is compatible
-    if state.research_path != ResearchPath::None && state.research_path != node_path {
+    if state.research_path != ResearchPath::None && state.research_path != node.path {
         return false; // locked into a different path
     }
 
     // Check tier prerequisite: must have purchased previous tier
-    if node_tier > 1 {
+    if node.tier > 1 {
         let has_prev = state
             .research_nodes
             .iter()
-            .any(|n| n.path == node_path && n.tier == node_tier - 1 && n.purchased);
+            .any(|n| n.path == node.path && n.tier == node.tier - 1 && n.purchased);
         if !has_prev {
             return false;
         }
     }
 
-    let cost = state.research_nodes[index].cost;
-    if state.cookies < cost {
+    state.cookies >= node.cost
+}
+
+/// Buy from the research tab. The first purchase picks the path for the
+/// whole run, so it opens the confirm dialog instead of buying at once.
+pub fn request_research(state: &mut CookieState, index: usize) -> bool {
+    if state.research_path == ResearchPath::None && can_buy_research(state, index) {
+        state.research_confirm = Some(index);
+        return false;
+    }
+    buy_research(state, index)
+}
+
+/// "Yes" on the path-choice dialog.
+pub fn confirm_research(state: &mut CookieState) -> bool {
+    match state.research_confirm.take() {
+        Some(index) => buy_research(state, index),
+        None => false,
+    }
+}
+
+/// Buy a research node by index. Returns true if successful.
+/// The first purchase locks the player into that research path.
+pub fn buy_research(state: &mut CookieState, index: usize) -> bool {
+    if !can_buy_research(state, index) {
         return false;
     }
 
+    let node_path = state.research_nodes[index].path.clone();
+    let cost = state.research_nodes[index].cost;
     state.cookies -= cost;
     state.research_nodes[index].purchased = true;
 
     // Lock into this path on first purchase
     if state.research_path == ResearchPath::None {
         state.research_path = node_path.clone();
-        let path_name = match &node_path {
-            ResearchPath::MassProduction => "量産路線",
-            ResearchPath::Quality => "品質路線",
-            ResearchPath::None => "なし",
-        };
         state.add_log(
-            &format!("🔬 研究パス選択: {}！", path_name),
+            &format!("🔬 研究パス選択: {}！", node_path.name()),
             true,
         );
     }
@@ -1368,6 +1940,25 @@ pub fn buy_research(state: &mut CookieState, index: usize) -> bool {
     true
 }
 
+// ═══════════════════════════════════════════════════════
+// Combo Skills
+// ═══════════════════════════════════════════════════════
+
+/// Learn a combo skill. Returns true if successful.
+pub fn buy_combo_skill(state: &mut CookieState, skill: ComboSkill) -> bool {
+    if !state.combo_skill_unlocked(skill) || state.cookies < skill.cost() {
+        return false;
+    }
+    state.cookies -= skill.cost();
+    state.combo_skills.push(skill);
+    state.add_log(
+        &format!("⚡ 連撃スキル習得！「{}」{}", skill.name(), skill.description()),
+        true,
+    );
+    state.purchase_flash = 8;
+    true
+}
+
 // ═══════════════════════════════════════════════════════
 // Dragon
 // ═══════════════════════════════════════════════════════
@@ -1375,7 +1966,7 @@ pub fn buy_research(state: &mut CookieState, index: usize) -> bool {
 /// Feed producers to the dragon. Sacrifices `count` units of the given producer kind.
 /// Returns true if the dragon leveled up.
 pub fn feed_dragon(state: &mut CookieState, kind: &ProducerKind, count: u32) -> bool {
-    if state.dragon_level >= 7 {
+    if state.dragon_level >= MAX_DRAGON_LEVEL {
         return false; // max level
     }
 
@@ -1403,18 +1994,30 @@ pub fn feed_dragon(state: &mut CookieState, kind: &ProducerKind, count: u32) ->
         true,
     );
 
-    // Check if dragon leveled up
-    if state.dragon_fed_toward_next() >= feed_cost {
-        state.dragon_level += 1;
-        state.add_log(
-            &format!("🐉 ドラゴンLv.{}に成長！", state.dragon_level),
-            true,
-        );
-        spawn_celebration(state, 6);
-        true
-    } else {
-        false
+    check_dragon_level_up(state)
+}
+
+/// Level the dragon up if enough has been fed toward the next level.
+fn check_dragon_level_up(state: &mut CookieState) -> bool {
+    let feed_cost = state.dragon_feed_cost();
+    if feed_cost == 0 || state.dragon_fed_toward_next() < feed_cost {
+        return false;
+    }
+    state.dragon_level += 1;
+    state.add_log(
+        &format!("🐉 ドラゴンLv.{}に成長！", state.dragon_level),
+        true,
+    );
+    if state.dragon_level == DRAGON_DUAL_AURA_LEVEL {
+        state.add_log("🔮 第2オーラが装備できるようになった！", true);
     }
+    for ability in state.dragon_abilities() {
+        if ability.unlock_level() == state.dragon_level {
+            state.add_log(&format!("⚔ 能力「{}」を覚えた！", ability.name()), true);
+        }
+    }
+    spawn_celebration(state, 6);
+    true
 }
 
 /// Set the dragon's active aura. Requires dragon_level >= 1.
@@ -1430,6 +2033,134 @@ pub fn set_dragon_aura(state: &mut CookieState, aura: DragonAura) -> bool {
     true
 }
 
+/// Set the second aura slot. Requires dragon_level >= [`DRAGON_DUAL_AURA_LEVEL`].
+pub fn set_dragon_aura2(state: &mut CookieState, aura: DragonAura) -> bool {
+    if state.dragon_level < DRAGON_DUAL_AURA_LEVEL {
+        return false;
+    }
+    state.dragon_aura2 = aura.clone();
+    state.add_log(
+        &format!("🐉 第2オーラ変更: {}", aura.name()),
+        true,
+    );
+    true
+}
+
+/// Switch a slot to the next aura, skipping the one already in the other
+/// slot (the same aura twice doesn't stack).
+pub fn cycle_dragon_aura(state: &mut CookieState, second: bool) -> bool {
+    let auras = DragonAura::all();
+    let (current, other) = if second {
+        (&state.dragon_aura2, &state.dragon_aura)
+    } else {
+        (&state.dragon_aura, &state.dragon_aura2)
+    };
+    let dual = state.dragon_level >= DRAGON_DUAL_AURA_LEVEL;
+    let start = auras.iter().position(|a| a == current).map_or(0, |i| i + 1);
+    let next = (0..auras.len())
+        .map(|k| &auras[(start + k) % auras.len()])
+        .find(|a| !(dual && *a == other))
+        .cloned();
+    match next {
+        Some(aura) if second => set_dragon_aura2(state, aura),
+        Some(aura) => set_dragon_aura(state, aura),
+        None => false,
+    }
+}
+
+/// Run every unlocked dragon ability after a golden cookie claim.
+fn trigger_dragon_abilities(state: &mut CookieState) {
+    for ability in state.dragon_abilities() {
+        match ability {
+            DragonAbility::Hoard => {
+                let bonus = state.total_cps() * 2.0 * state.dragon_level as f64;
+                state.cookies += bonus;
+                state.cookies_all_time += bonus;
+                state.add_log(&format!("🐉 財宝の番人: +{}", format_number(bonus)), false);
+            }
+            DragonAbility::Roar => {
+                for buff in &mut state.active_buffs {
+                    buff.extend(50);
+                }
+                state.add_log("🐉 咆哮: バフ+5秒", false);
+            }
+            DragonAbility::Flame => {
+                if state.next_random().is_multiple_of(4) {
+                    state.golden_next_spawn = state.golden_next_spawn.min(50);
+                    state.add_log("🐉 竜炎: 次のゴールデンが近い！", true);
+                }
+            }
+        }
+    }
+}
+
+/// Offer a hunt every 3–5 minutes once the dragon has hatched; an
+/// unanswered hunt lapses after [`DRAGON_HUNT_WINDOW`].
+fn tick_dragon_hunt(state: &mut CookieState, delta_ticks: u32) {
+    if state.dragon_level == 0 {
+        return;
+    }
+    if let Some(left) = state.dragon_hunt {
+        let left = left.saturating_sub(delta_ticks);
+        if left == 0 {
+            state.dragon_hunt = None;
+            state.add_log("🐉 狩りの誘いを見送った…", false);
+            schedule_dragon_hunt(state);
+        } else {
+            state.dragon_hunt = Some(left);
+        }
+        return;
+    }
+    state.dragon_hunt_next = state.dragon_hunt_next.saturating_sub(delta_ticks);
+    if state.dragon_hunt_next == 0 {
+        state.dragon_hunt = Some(DRAGON_HUNT_WINDOW);
+        state.add_log("🏹 ドラゴンが狩りに行きたがっている！ (転生→ドラゴン)", true);
+        toast::push(Severity::Info, "🏹 ドラゴン狩り！");
+    }
+}
+
+fn schedule_dragon_hunt(state: &mut CookieState) {
+    state.dragon_hunt_next = 1800 + state.next_random() % 1200;
+}
+
+/// Answer the current dragon hunt. Returns true if the choice was taken.
+pub fn answer_dragon_hunt(state: &mut CookieState, choice: HuntChoice) -> bool {
+    if state.dragon_hunt.is_none() {
+        return false;
+    }
+    match choice {
+        HuntChoice::Hunt => {
+            let chance = (40 + 5 * state.dragon_level).min(75);
+            if state.next_random() % 100 < chance {
+                let bonus = state.total_cps() * 120.0;
+                state.cookies += bonus;
+                state.cookies_all_time += bonus;
+                state.add_log(&format!("🏹 狩り成功！ +{}", format_number(bonus)), true);
+                spawn_celebration(state, 5);
+            } else {
+                state.add_log("🏹 獲物に逃げられた…", false);
+            }
+        }
+        HuntChoice::Forage => {
+            let bonus = state.total_cps() * 30.0;
+            state.cookies += bonus;
+            state.cookies_all_time += bonus;
+            state.add_log(&format!("🧺 採集: +{}", format_number(bonus)), false);
+        }
+        HuntChoice::Rest => {
+            if state.dragon_level >= MAX_DRAGON_LEVEL {
+                return false;
+            }
+            state.dragon_fed_total += 5;
+            state.add_log("💤 ドラゴンはぐっすり休んだ (育成+5)", false);
+            check_dragon_level_up(state);
+        }
+    }
+    state.dragon_hunt = None;
+    schedule_dragon_hunt(state);
+    true
+}
+
 /// Calculate ROI info for a specific producer.
 #[allow(dead_code)]
 pub fn calculate_producer_roi(state: &CookieState, kind: &ProducerKind) -> RoiInfo {
@@ -1514,34 +2245,24 @@ pub fn format_payback_time(seconds: f64) -> String {
     }
 }
 
-/// Format a number with commas (e.g. 1234567 → "1,234,567").
+/// Format a number in the player's chosen notation
+/// (default: commas, e.g. 1234567 → "1,234,567"). See [`crate::numfmt`].
 pub fn format_number(n: f64) -> String {
-    if n < 0.0 {
-        return format!("-{}", format_number(-n));
-    }
-    let int_part = n.floor() as u64;
-    let frac = n - int_part as f64;
-
-    let s = int_part.to_string();
-    let mut result = String::new();
-    for (i, c) in s.chars().rev().enumerate() {
-        if i > 0 && i % 3 == 0 {
-            result.push(',');
-        }
-        result.push(c);
-    }
-    let result: String = result.chars().rev().collect();
+    numfmt::format(n)
+}
 
-    if frac > 0.05 {
-        format!("{}.{}", result, ((frac * 10.0).round() as u8))
-    } else {
-        result
-    }
+/// [`format_number`] into an existing buffer, without allocating
+/// beyond `out`'s own growth (particle text reuses its buffer).
+fn push_number(out: &mut String, n: f64) {
+    numfmt::push(out, n);
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::games::cookie::state::{
+        AnimationIntensity, Futures, CLICK_MERGE_TICKS, FUTURES_STEP_TICKS, MAX_PARTICLES,
+    };
 
     #[test]
     fn tick_produces_cookies() {
@@ -1600,14 +2321,33 @@ mod tests {
     fn click_with_buff() {
         let mut state = CookieState::new();
         state.cookies_per_click = 2.0;
-        state.active_buffs.push(ActiveBuff {
-            effect: GoldenEffect::ClickFrenzy { multiplier: 10.0 },
-            ticks_left: 100,
-        });
+        state.active_buffs.push(ActiveBuff::new(GoldenEffect::ClickFrenzy { multiplier: 10.0 }, 100));
         click(&mut state);
         assert!((state.cookies - 20.0).abs() < 0.001);
     }
 
+    #[test]
+    fn level_up_producer_boosts_that_type_only() {
+        let mut state = CookieState::new();
+        state.producers[1].count = 10; // 10 grandmas = 10.0 cps
+        state.cookies = 1e6;
+        assert!(!level_up_producer(&mut state, &ProducerKind::Cursor), "未所有");
+
+        let before = state.total_cps();
+        let cost = state.producers[1].level_up_cost() * state.total_cost_modifier();
+        assert!(level_up_producer(&mut state, &ProducerKind::Grandma));
+        assert_eq!(state.producers[1].level, 1);
+        assert!((state.cookies - (1e6 - cost)).abs() < 0.01);
+        assert!((state.total_cps() - before * 1.1).abs() < 0.01);
+        assert!(state.producers[1].level_up_cost() > cost);
+
+        state.producers[1].level = MAX_PRODUCER_LEVEL;
+        assert!(!level_up_producer(&mut state, &ProducerKind::Grandma), "上限");
+        state.producers[1].level = 1;
+        state.cookies = 0.0;
+        assert!(!level_up_producer(&mut state, &ProducerKind::Grandma), "クッキー不足");
+    }
+
     #[test]
     fn buy_producer_success() {
         let mut state = CookieState::new();
@@ -1637,6 +2377,88 @@ mod tests {
         assert!((cost_after_1 - expected).abs() < 0.01);
     }
 
+    #[test]
+    fn purchase_queue_buys_when_affordable() {
+        let mut state = CookieState::new();
+        assert!(queue_producer(&mut state, &ProducerKind::Cursor, 2));
+        tick(&mut state, 1);
+        assert_eq!(state.producers[0].count, 0); // can't afford yet
+        state.cookies = 1000.0;
+        tick(&mut state, 1);
+        assert_eq!(state.producers[0].count, 2);
+        assert!(state.purchase_queue.is_empty());
+    }
+
+    #[test]
+    fn purchase_queue_is_fifo_and_merges_same_kind() {
+        let mut state = CookieState::new();
+        queue_producer(&mut state, &ProducerKind::Grandma, 1);
+        queue_producer(&mut state, &ProducerKind::Cursor, 1);
+        queue_producer(&mut state, &ProducerKind::Grandma, 1);
+        assert_eq!(state.purchase_queue.len(), 2);
+        assert_eq!(state.purchase_queue[0].remaining, 2);
+        // Enough for cursors but not a grandma: head blocks the queue
+        state.cookies = 50.0;
+        tick(&mut state, 1);
+        assert_eq!(state.producers[0].count, 0);
+        assert!(cancel_queued(&mut state, 0));
+        tick(&mut state, 1);
+        assert_eq!(state.producers[0].count, 1);
+    }
+
+    #[test]
+    fn purchase_queue_respects_capacity() {
+        let mut state = CookieState::new();
+        for kind in ProducerKind::all().iter().take(MAX_PURCHASE_QUEUE) {
+            assert!(queue_producer(&mut state, kind, 1));
+        }
+        let extra = &ProducerKind::all()[MAX_PURCHASE_QUEUE];
+        assert!(!queue_producer(&mut state, extra, 1));
+        assert!(!cancel_queued(&mut state, MAX_PURCHASE_QUEUE));
+    }
+
+    #[test]
+    fn pin_upgrade_toggles_and_respects_limit() {
+        let mut state = CookieState::new();
+        for i in 0..MAX_PINNED_UPGRADES {
+            assert!(toggle_pin_upgrade(&mut state, i));
+        }
+        assert!(!toggle_pin_upgrade(&mut state, MAX_PINNED_UPGRADES));
+        assert_eq!(state.pinned_upgrades.len(), MAX_PINNED_UPGRADES);
+        // Toggling again unpins
+        assert!(toggle_pin_upgrade(&mut state, 1));
+        assert_eq!(state.pinned_upgrades, vec![0, 2]);
+        assert!(!toggle_pin_upgrade(&mut state, 9999));
+    }
+
+    #[test]
+    fn pin_rejects_purchased_and_group_blocked_upgrades() {
+        let mut state = CookieState::new();
+        state.upgrades[0].purchased = true;
+        assert!(!toggle_pin_upgrade(&mut state, 0));
+
+        let group: Vec<usize> = state
+            .upgrades
+            .iter()
+            .enumerate()
+            .filter(|(_, u)| u.exclusive_group == Some(1))
+            .map(|(i, _)| i)
+            .collect();
+        state.upgrades[group[0]].purchased = true;
+        assert!(!toggle_pin_upgrade(&mut state, group[1]));
+        assert!(state.pinned_upgrades.is_empty());
+    }
+
+    #[test]
+    fn buying_pinned_upgrade_unpins_it() {
+        let mut state = CookieState::new();
+        toggle_pin_upgrade(&mut state, 0);
+        toggle_pin_upgrade(&mut state, 1);
+        state.cookies = 200.0;
+        assert!(buy_upgrade(&mut state, 0));
+        assert_eq!(state.pinned_upgrades, vec![1]);
+    }
+
     #[test]
     fn buy_upgrade_success() {
         let mut state = CookieState::new();
@@ -1765,14 +2587,92 @@ mod tests {
     #[test]
     fn buff_expires() {
         let mut state = CookieState::new();
-        state.active_buffs.push(ActiveBuff {
-            effect: GoldenEffect::ProductionFrenzy { multiplier: 7.0 },
-            ticks_left: 10,
-        });
+        state.active_buffs.push(ActiveBuff::new(GoldenEffect::ProductionFrenzy { multiplier: 7.0 }, 10));
         tick(&mut state, 10);
         assert!(state.active_buffs.is_empty());
     }
 
+    #[test]
+    fn same_buff_refreshes_instead_of_stacking() {
+        let mut state = CookieState::new();
+        let frenzy = GoldenEffect::ProductionFrenzy { multiplier: 7.0 };
+        assert_eq!(add_buff(&mut state, frenzy.clone(), 30), BuffStacking::Stack);
+        assert_eq!(add_buff(&mut state, frenzy.clone(), 70), BuffStacking::Refresh);
+        assert_eq!(state.active_buffs.len(), 1);
+        assert_eq!(state.active_buffs[0].ticks_left, 70);
+        assert_eq!(state.active_buffs[0].total_ticks, 70);
+        // 短い方では縮まない
+        assert_eq!(add_buff(&mut state, frenzy, 10), BuffStacking::Refresh);
+        assert_eq!(state.active_buffs[0].ticks_left, 70);
+    }
+
+    #[test]
+    fn production_frenzies_stack_up_to_limit_then_queue() {
+        let mut state = CookieState::new();
+        state.producers[1].count = 5;
+        let base = state.total_cps();
+        add_buff(&mut state, GoldenEffect::ProductionFrenzy { multiplier: 7.0 }, 10);
+        add_buff(&mut state, GoldenEffect::ProductionFrenzy { multiplier: 2.0 }, 50);
+        assert_eq!(
+            add_buff(&mut state, GoldenEffect::ProductionFrenzy { multiplier: 3.0 }, 50),
+            BuffStacking::Queue
+        );
+        // 2 つまでしか掛からない (7 × 2、2 層で buff combo は付かない)
+        assert!((state.total_cps() - base * 14.0).abs() < 0.01);
+
+        // ×7 が切れたら待機中の ×3 が始まる
+        tick(&mut state, 10);
+        assert!(state.queued_buffs.is_empty());
+        let active: Vec<f64> = state.active_buffs.iter().map(|b| b.effect.multiplier()).collect();
+        assert_eq!(active, vec![2.0, 3.0]);
+    }
+
+    #[test]
+    fn click_frenzies_queue_one_at_a_time() {
+        let mut state = CookieState::new();
+        state.cookies_per_click = 1.0;
+        add_buff(&mut state, GoldenEffect::ClickFrenzy { multiplier: 10.0 }, 10);
+        assert_eq!(
+            add_buff(&mut state, GoldenEffect::ClickFrenzy { multiplier: 3.0 }, 20),
+            BuffStacking::Queue
+        );
+        assert!((state.effective_click_power() - 10.0).abs() < 0.001);
+
+        tick(&mut state, 10);
+        assert_eq!(state.active_buffs.len(), 1);
+        assert_eq!(state.active_buffs[0].ticks_left, 20);
+        assert!((state.effective_click_power() - 3.0).abs() < 0.001);
+    }
+
+    #[test]
+    fn full_buff_queue_drops_new_buffs() {
+        let mut state = CookieState::new();
+        add_buff(&mut state, GoldenEffect::ClickFrenzy { multiplier: 2.0 }, 100);
+        for i in 0..MAX_QUEUED_BUFFS {
+            let effect = GoldenEffect::ClickFrenzy { multiplier: 3.0 + i as f64 };
+            assert_eq!(add_buff(&mut state, effect, 100), BuffStacking::Queue);
+        }
+        assert_eq!(
+            add_buff(&mut state, GoldenEffect::ClickFrenzy { multiplier: 9.0 }, 100),
+            BuffStacking::Dropped
+        );
+        assert_eq!(state.queued_buffs.len(), MAX_QUEUED_BUFFS);
+    }
+
+    #[test]
+    fn buff_summary_groups_by_kind() {
+        let mut state = CookieState::new();
+        add_buff(&mut state, GoldenEffect::ProductionFrenzy { multiplier: 7.0 }, 70);
+        add_buff(&mut state, GoldenEffect::ProductionFrenzy { multiplier: 2.0 }, 40);
+        add_buff(&mut state, GoldenEffect::ProductionFrenzy { multiplier: 3.0 }, 40);
+        let summary = state.buff_summary();
+        assert_eq!(summary.len(), 1);
+        assert_eq!(summary[0].kind, BuffKind::Production);
+        assert!((summary[0].multiplier - 14.0).abs() < 1e-9);
+        assert_eq!((summary[0].stacks, summary[0].queued), (2, 1));
+        assert_eq!((summary[0].ticks_left, summary[0].total_ticks), (40, 40));
+    }
+
     #[test]
     fn format_number_basic() {
         assert_eq!(format_number(0.0), "0");
@@ -1916,6 +2816,90 @@ mod tests {
         assert!((state.milk - count as f64 * 0.04).abs() < 0.001);
     }
 
+    #[test]
+    fn milestone_rewards_grant_sugar_and_cookie_lumps() {
+        let mut state = CookieState::new();
+        let sugar_idx = state.milestones.iter().position(|m| m.name == "パン屋の朝").unwrap();
+        assert_eq!(state.milestones[sugar_idx].reward, MilestoneReward::Sugar(1));
+        state.milestones[sugar_idx].status = MilestoneStatus::Ready;
+        assert!(claim_milestone(&mut state, sugar_idx));
+        assert_eq!(state.sugar, 1);
+        assert_eq!(state.sugar_all_time, 1);
+
+        let lump_idx = state.milestones.iter().position(|m| m.name == "自動化の兆し").unwrap();
+        state.producers[1].count = 10;
+        state.milestones[lump_idx].status = MilestoneStatus::Ready;
+        let before = state.cookies;
+        let expected = state.total_cps() * 600.0;
+        claim_all_milestones(&mut state);
+        assert!(expected > 0.0);
+        assert!((state.cookies - before - expected).abs() < 0.01);
+    }
+
+    #[test]
+    fn first_research_asks_before_locking_the_path() {
+        let mut state = CookieState::new();
+        state.cookies = 1_000_000.0;
+        let mass = state.research_nodes.iter().position(|n| n.name == "効率生産").unwrap();
+        let quality = state.research_nodes.iter().position(|n| n.name == "熟練の技").unwrap();
+
+        // 最初の購入は確認待ちになるだけ
+        assert!(!request_research(&mut state, mass));
+        assert_eq!(state.research_confirm, Some(mass));
+        assert_eq!(state.research_path, ResearchPath::None);
+        assert!(!state.research_nodes[mass].purchased);
+
+        assert!(confirm_research(&mut state));
+        assert_eq!(state.research_confirm, None);
+        assert_eq!(state.research_path, ResearchPath::MassProduction);
+        assert!(state.research_nodes[mass].purchased);
+
+        // 路線が決まった後は確認なしで買える / もう一方は買えない
+        let tier2 = state.research_nodes.iter().position(|n| n.name == "大量発注").unwrap();
+        assert!(request_research(&mut state, tier2));
+        assert!(!request_research(&mut state, quality));
+        assert_eq!(state.research_confirm, None);
+    }
+
+    #[test]
+    fn research_confirm_is_skipped_when_the_node_cannot_be_bought() {
+        let mut state = CookieState::new();
+        let tier2 = state.research_nodes.iter().position(|n| n.tier == 2).unwrap();
+        state.cookies = 1e9;
+        assert!(!request_research(&mut state, tier2), "前段階が未研究");
+        state.cookies = 0.0;
+        assert!(!request_research(&mut state, 0), "クッキー不足");
+        assert_eq!(state.research_confirm, None);
+        assert!(!confirm_research(&mut state));
+    }
+
+    #[test]
+    fn art_rewards_unlock_and_survive_prestige() {
+        use crate::games::cookie::state::CookieArt;
+
+        let mut state = CookieState::new();
+        assert_eq!(state.unlocked_cookie_arts(), vec![CookieArt::Classic]);
+        let idx = state.milestones.iter().position(|m| m.name == "連打の達人").unwrap();
+        state.milestones[idx].status = MilestoneStatus::Ready;
+        assert!(claim_milestone(&mut state, idx));
+        assert_eq!(state.cookie_art, CookieArt::Chocolate);
+        assert_eq!(
+            state.unlocked_cookie_arts(),
+            vec![CookieArt::Classic, CookieArt::Chocolate]
+        );
+
+        cycle_cookie_art(&mut state);
+        assert_eq!(state.cookie_art, CookieArt::Classic);
+        cycle_cookie_art(&mut state);
+        assert_eq!(state.cookie_art, CookieArt::Chocolate);
+
+        state.cookies_all_time = 1e15;
+        perform_prestige(&mut state);
+        assert_eq!(state.milestones[idx].status, MilestoneStatus::Locked);
+        assert_eq!(state.cookie_art, CookieArt::Chocolate);
+        assert!(state.cookie_arts.contains(&CookieArt::Chocolate));
+    }
+
     #[test]
     fn kitten_upgrade_multiplies_cps() {
         let mut state = CookieState::new();
@@ -1948,6 +2932,144 @@ mod tests {
         assert!((state.kitten_multiplier - 1.155).abs() < 0.01);
     }
 
+    #[test]
+    fn rapid_clicks_merge_into_one_rising_number() {
+        let mut state = CookieState::new();
+        for _ in 0..20 {
+            click(&mut state);
+        }
+        let plain: Vec<&Particle> = state
+            .particles
+            .iter()
+            .filter(|p| p.style == ParticleStyle::Click && p.amount > 0.0)
+            .collect();
+        assert_eq!(plain.len(), 1);
+        assert!(plain[0].amount > 1.0);
+        let mut expected = String::new();
+        write_click_text(&mut expected, plain[0].amount);
+        assert_eq!(plain[0].text, expected);
+
+        // Once it has risen a few ticks, the next click starts a new number.
+        state.particles.tick(CLICK_MERGE_TICKS);
+        click(&mut state);
+        let plain = state
+            .particles
+            .iter()
+            .filter(|p| p.style == ParticleStyle::Click && p.amount > 0.0)
+            .count();
+        assert!(plain >= 1 && state.particles.len() <= MAX_PARTICLES);
+    }
+
+    #[test]
+    fn animation_intensity_caps_particles() {
+        let mut state = CookieState::new();
+        cycle_animation_intensity(&mut state);
+        assert_eq!(state.animation_intensity, AnimationIntensity::Reduced);
+        spawn_celebration(&mut state, 25);
+        assert_eq!(state.particles.len(), 10);
+
+        cycle_animation_intensity(&mut state);
+        assert_eq!(state.animation_intensity, AnimationIntensity::Off);
+        assert!(state.particles.is_empty());
+        click(&mut state);
+        spawn_celebration(&mut state, 5);
+        assert!(state.particles.is_empty());
+
+        cycle_animation_intensity(&mut state);
+        spawn_celebration(&mut state, 50);
+        assert_eq!(state.particles.len(), MAX_PARTICLES);
+    }
+
+    #[test]
+    fn combo_skills_follow_the_tree_and_boost_crits() {
+        let mut state = CookieState::new();
+        state.cookies = 10_000_000.0;
+        assert_eq!(state.critical_multiplier(), 10.0);
+        assert!(!buy_combo_skill(&mut state, ComboSkill::KeenEye), "連打の呼吸が先");
+
+        assert!(buy_combo_skill(&mut state, ComboSkill::Tempo));
+        assert!(!buy_combo_skill(&mut state, ComboSkill::Tempo), "習得済み");
+        assert!(buy_combo_skill(&mut state, ComboSkill::KeenEye));
+        assert!(buy_combo_skill(&mut state, ComboSkill::Hawkeye));
+        assert!(buy_combo_skill(&mut state, ComboSkill::HeavyHand));
+        assert!((state.critical_chance() - 0.08).abs() < 1e-9);
+        assert_eq!(state.critical_multiplier(), 15.0);
+
+        state.cookies = 0.0;
+        assert!(!buy_combo_skill(&mut state, ComboSkill::Shatter), "クッキー不足");
+
+        state.cookies_all_time = 1e9;
+        perform_prestige(&mut state);
+        assert!(state.combo_skills.is_empty());
+    }
+
+    #[test]
+    fn tempo_keeps_the_combo_alive_longer() {
+        let mut state = CookieState::new();
+        click(&mut state);
+        tick(&mut state, 18);
+        assert_eq!(state.combo_count, 0);
+
+        state.combo_skills.push(ComboSkill::Tempo);
+        click(&mut state);
+        tick(&mut state, 18);
+        assert_eq!(state.combo_count, 1);
+        tick(&mut state, 5);
+        assert_eq!(state.combo_count, 0);
+    }
+
+    #[test]
+    fn expired_particle_text_buffers_are_reused() {
+        let mut state = CookieState::new();
+        state.combo_count = 9; // next click shows the owned "10連打" text
+        click(&mut state);
+        let owned: Vec<*const u8> = state
+            .particles
+            .iter()
+            .filter(|p| matches!(p.text, Cow::Owned(_)))
+            .map(|p| p.text.as_ptr())
+            .collect();
+        assert!(!owned.is_empty());
+
+        state.particles.tick(u32::MAX);
+        assert!(state.particles.is_empty());
+        let reused = state.particles.text_buf();
+        assert!(reused.is_empty());
+        assert!(owned.contains(&reused.as_ptr()));
+    }
+
+    #[test]
+    fn milestone_claim_spawns_milk_splash() {
+        let mut state = CookieState::new();
+        state.cookies_all_time = 100.0;
+        check_milestones(&mut state);
+        claim_milestone(&mut state, 0);
+        let splash = state
+            .particles
+            .iter()
+            .filter(|p| p.panel == ParticlePanel::Milestones)
+            .count();
+        assert!(splash > 0);
+        assert!(state
+            .particles
+            .iter()
+            .filter(|p| p.panel == ParticlePanel::Milestones)
+            .all(|p| p.style == ParticleStyle::MilkSplash));
+    }
+
+    #[test]
+    fn milk_display_fills_gradually_after_claim() {
+        let mut state = CookieState::new();
+        state.cookies_all_time = 100.0;
+        check_milestones(&mut state);
+        claim_milestone(&mut state, 0);
+        assert!(state.milk_display < state.milk);
+        tick(&mut state, 1);
+        assert!(state.milk_display > 0.0 && state.milk_display < state.milk);
+        tick(&mut state, 10);
+        assert!((state.milk_display - state.milk).abs() < 1e-9);
+    }
+
     #[test]
     fn milestone_flash_decreases_over_ticks() {
         let mut state = CookieState::new();
@@ -2020,6 +3142,274 @@ mod tests {
         assert_eq!(state.prestige_count, 2);
     }
 
+    #[test]
+    fn challenge_needs_a_prestige_and_resets_the_run() {
+        let mut state = CookieState::new();
+        assert!(!start_challenge(&mut state, ChallengeKind::NoClick));
+        assert_eq!(state.active_challenge, None);
+
+        state.cookies_all_time = 1e9;
+        assert!(start_challenge(&mut state, ChallengeKind::NoClick));
+        assert_eq!(state.prestige_count, 1);
+        assert_eq!(state.active_challenge, Some(ChallengeKind::NoClick));
+        // One at a time
+        state.cookies_all_time = 1e10;
+        assert!(!start_challenge(&mut state, ChallengeKind::Expensive));
+        assert_eq!(state.active_challenge, Some(ChallengeKind::NoClick));
+    }
+
+    #[test]
+    fn challenge_modifiers_hit_click_cps_and_cost() {
+        let mut state = CookieState::new();
+        state.producers[0].count = 10;
+        let cursor_cps = state.total_cps();
+        let cost = state.total_cost_modifier();
+
+        state.active_challenge = Some(ChallengeKind::NoClick);
+        click(&mut state);
+        assert_eq!(state.cookies, 0.0);
+        assert_eq!(state.total_clicks, 0);
+
+        state.active_challenge = Some(ChallengeKind::NoCursors);
+        assert!(cursor_cps > 0.0);
+        assert_eq!(state.total_cps(), 0.0);
+
+        state.active_challenge = Some(ChallengeKind::Expensive);
+        assert!((state.total_cost_modifier() - cost * 1.5).abs() < 1e-9);
+    }
+
+    #[test]
+    fn completing_a_challenge_awards_chips_and_a_badge_once() {
+        let mut state = CookieState::new();
+        state.active_challenge = Some(ChallengeKind::NoCursors);
+        state.cookies_all_time = ChallengeKind::NoCursors.goal();
+        tick(&mut state, 1);
+        assert_eq!(state.active_challenge, None);
+        assert_eq!(state.heavenly_chips, ChallengeKind::NoCursors.reward_chips());
+        assert_eq!(state.challenges_completed, vec![ChallengeKind::NoCursors]);
+
+        state.active_challenge = Some(ChallengeKind::NoCursors);
+        tick(&mut state, 1);
+        assert_eq!(state.heavenly_chips, 2 * ChallengeKind::NoCursors.reward_chips());
+        assert_eq!(state.challenges_completed.len(), 1);
+    }
+
+    #[test]
+    fn bonus_chips_do_not_eat_into_baked_chips() {
+        let mut state = CookieState::new();
+        state.cookies_all_time = 4e9; // sqrt(4) = 2 chips
+        assert_eq!(state.pending_heavenly_chips(), 2);
+        state.heavenly_chips = 5;
+        state.bonus_chips = 5;
+        assert_eq!(state.pending_heavenly_chips(), 2);
+    }
+
+    #[test]
+    fn abandon_keeps_the_run_without_reward() {
+        let mut state = CookieState::new();
+        assert!(!abandon_challenge(&mut state));
+        state.active_challenge = Some(ChallengeKind::Expensive);
+        state.cookies = 123.0;
+        assert!(abandon_challenge(&mut state));
+        assert_eq!(state.active_challenge, None);
+        assert_eq!(state.cookies, 123.0);
+        assert!(state.challenges_completed.is_empty());
+    }
+
+    #[test]
+    fn garden_unlocks_with_a_claimed_farm_milestone() {
+        let mut state = CookieState::new();
+        assert!(!state.is_tab_unlocked("garden"));
+        let farm = state
+            .milestones
+            .iter()
+            .position(|m| m.condition == MilestoneCondition::ProducerCount(ProducerKind::Farm, 10))
+            .unwrap();
+        state.milestones[farm].status = MilestoneStatus::Claimed;
+        assert!(state.is_tab_unlocked("garden"));
+    }
+
+    #[test]
+    fn garden_plant_grow_and_harvest() {
+        let mut state = CookieState::new();
+        state.market_phase = MarketPhase::Normal;
+        state.selected_seed = SeedKind::Sugarcane;
+        assert!(!tap_plot(&mut state, 0), "can't afford the seed");
+
+        state.cookies = SeedKind::Sugarcane.cost(0.0);
+        assert!(tap_plot(&mut state, 0));
+        assert_eq!(state.cookies, 0.0);
+        assert!(!tap_plot(&mut state, 0), "still growing");
+
+        tick_garden(&mut state, SeedKind::Sugarcane.grow_ticks());
+        assert!(state.garden[0].as_ref().unwrap().is_mature());
+        assert!(tap_plot(&mut state, 0));
+        assert_eq!(state.sugar, 1);
+        assert!(state.garden[0].is_none());
+    }
+
+    #[test]
+    fn garden_grows_faster_with_milk_and_slow_markets() {
+        let mut state = CookieState::new();
+        state.market_phase = MarketPhase::Normal;
+        let base = state.garden_growth_rate();
+        state.milk = 1.0;
+        assert!((state.garden_growth_rate() - base * 2.0).abs() < 1e-9);
+        state.market_phase = MarketPhase::Crash;
+        assert!(state.garden_growth_rate() > base * 2.0);
+    }
+
+    #[test]
+    fn second_aura_opens_at_level_five_and_skips_the_first() {
+        let mut state = CookieState::new();
+        state.dragon_level = DRAGON_DUAL_AURA_LEVEL - 1;
+        state.dragon_aura = DragonAura::BreathOfRiches;
+        assert!(!cycle_dragon_aura(&mut state, true));
+
+        state.dragon_level = DRAGON_DUAL_AURA_LEVEL;
+        assert!(cycle_dragon_aura(&mut state, true));
+        assert_eq!(state.dragon_aura2, DragonAura::DragonCursor);
+        assert!(state.has_dragon_aura(&DragonAura::BreathOfRiches));
+        assert!(state.has_dragon_aura(&DragonAura::DragonCursor));
+        assert!(state.dragon_click_modifier() > 1.0);
+        // Cycling the first slot jumps over the aura in the second
+        assert!(cycle_dragon_aura(&mut state, false));
+        assert_eq!(state.dragon_aura, DragonAura::ElderPact);
+    }
+
+    #[test]
+    fn golden_claims_trigger_unlocked_dragon_abilities() {
+        let mut state = CookieState::new();
+        state.producers[0].count = 10;
+        state.dragon_level = 4; // Hoard + Roar
+        assert_eq!(
+            state.dragon_abilities(),
+            vec![DragonAbility::Hoard, DragonAbility::Roar]
+        );
+        state.active_buffs.push(ActiveBuff::new(GoldenEffect::ClickFrenzy { multiplier: 2.0 }, 10));
+        spawn_golden(&mut state);
+        // Pin the roll to a click frenzy: the other effects change the CPS
+        // (or the banked cookies it depends on) that the Hoard pays out on.
+        let seed = (1..)
+            .find(|&seed| {
+                state.rng_state = seed;
+                matches!(pick_golden_effect(&mut state), GoldenEffect::ClickFrenzy { .. })
+            })
+            .unwrap();
+        state.rng_state = seed;
+        let cps = state.total_cps();
+        let before = state.cookies;
+        assert!(claim_golden(&mut state));
+        assert!((state.cookies - before - cps * 2.0 * 4.0).abs() < 1e-9);
+        assert_eq!(state.active_buffs[0].ticks_left, 60);
+    }
+
+    #[test]
+    fn dragon_hunt_is_offered_answered_and_expires() {
+        let mut state = CookieState::new();
+        state.dragon_hunt_next = 10;
+        tick_dragon_hunt(&mut state, 10);
+        assert_eq!(state.dragon_hunt, None, "no hunts before the egg hatches");
+
+        state.dragon_level = 1;
+        tick_dragon_hunt(&mut state, 10);
+        assert_eq!(state.dragon_hunt, Some(DRAGON_HUNT_WINDOW));
+        let fed = state.dragon_fed_total;
+        assert!(answer_dragon_hunt(&mut state, HuntChoice::Rest));
+        assert_eq!(state.dragon_fed_total, fed + 5);
+        assert_eq!(state.dragon_hunt, None);
+        assert!(state.dragon_hunt_next >= 1800);
+        assert!(!answer_dragon_hunt(&mut state, HuntChoice::Forage));
+
+        state.dragon_hunt = Some(5);
+        tick_dragon_hunt(&mut state, 5);
+        assert_eq!(state.dragon_hunt, None);
+    }
+
+    #[test]
+    fn futures_walk_is_deterministic_per_seed() {
+        let mut a = Futures::new(1234);
+        let mut b = Futures::new(1234);
+        let mut c = Futures::new(4321);
+        a.advance(FUTURES_STEP_TICKS * 500);
+        // Splitting the ticks up doesn't change the walk
+        for _ in 0..500 * FUTURES_STEP_TICKS {
+            b.advance(1);
+        }
+        c.advance(FUTURES_STEP_TICKS * 500);
+        assert_eq!(a, b);
+        assert_ne!(a.price, c.price);
+        assert!(a.history.iter().all(|p| (0.25..=4.0).contains(p)));
+    }
+
+    #[test]
+    fn futures_walk_ignores_the_game_rng() {
+        let mut state = CookieState::new();
+        let mut expected = state.futures.clone();
+        state.rng_state = 999;
+        tick(&mut state, 35);
+        expected.advance(35);
+        assert_eq!(state.futures.price, expected.price);
+        assert_eq!(state.futures.seed, expected.seed);
+    }
+
+    #[test]
+    fn futures_invest_and_cash_out() {
+        let mut state = CookieState::new();
+        assert!(!sell_futures(&mut state));
+        state.cookies = 1000.0;
+        state.futures.price = 1.0;
+        assert!(invest_futures(&mut state, 0.5));
+        assert_eq!(state.cookies, 500.0);
+
+        state.futures.price = 1.5;
+        let all_time = state.cookies_all_time;
+        assert!(sell_futures(&mut state));
+        assert!((state.cookies - 1250.0).abs() < 1e-9);
+        assert!((state.cookies_all_time - all_time - 250.0).abs() < 1e-9);
+        assert!(!state.futures.has_position());
+
+        // A loss comes back short and doesn't touch the all-time total
+        state.futures.price = 1.0;
+        assert!(invest_futures(&mut state, 1.0));
+        state.futures.price = 0.5;
+        let all_time = state.cookies_all_time;
+        assert!(sell_futures(&mut state));
+        assert!((state.cookies - 625.0).abs() < 1e-9);
+        assert_eq!(state.cookies_all_time, all_time);
+    }
+
+    #[test]
+    fn futures_unlock_with_mine_and_positions_end_on_prestige() {
+        let mut state = CookieState::new();
+        assert!(!state.is_tab_unlocked("futures"));
+        let mine = state
+            .milestones
+            .iter()
+            .position(|m| m.condition == MilestoneCondition::ProducerCount(ProducerKind::Mine, 10))
+            .unwrap();
+        state.milestones[mine].status = MilestoneStatus::Claimed;
+        assert!(state.is_tab_unlocked("futures"));
+
+        state.cookies = 100.0;
+        invest_futures(&mut state, 1.0);
+        state.futures.price = 2.0;
+        state.cookies_all_time = 1e9;
+        perform_prestige(&mut state);
+        assert!(!state.futures.has_position());
+        assert_eq!(state.futures.price, 2.0);
+    }
+
+    #[test]
+    fn garden_survives_prestige() {
+        let mut state = CookieState::new();
+        state.garden[3] = Some(Plant { seed: SeedKind::Cronerice, growth: 10.0 });
+        state.cookies_all_time = 1e9;
+        perform_prestige(&mut state);
+        assert_eq!(state.garden[3].as_ref().map(|p| p.seed), Some(SeedKind::Cronerice));
+        assert!(state.is_tab_unlocked("garden"));
+    }
+
     #[test]
     fn buy_prestige_upgrade_success() {
         let mut state = CookieState::new();
@@ -2074,14 +3464,6 @@ mod tests {
 
     // ── 相場表示ヘルパー ──────────────────────────────────
 
-    #[test]
-    fn 市場の残り秒数はticksを10で割った値() {
-        assert_eq!(market_seconds_left(0), 0);
-        assert_eq!(market_seconds_left(9), 0);
-        assert_eq!(market_seconds_left(10), 1);
-        assert_eq!(market_seconds_left(230), 23);
-    }
-
     #[test]
     fn 買い時判定はコスト倍率が1未満のフェーズのみ() {
         assert!(is_market_buy_time(&MarketPhase::Crash));
@@ -2092,57 +3474,42 @@ mod tests {
     }
 
     #[test]
-    fn 暴落のワイド表示は割引率と残り時間を含む() {
-        let text = market_banner_wide(&MarketPhase::Crash, 230);
-        assert_eq!(text, "💥 市場: 暴落！ 生産者75%OFF (残り23秒)");
+    fn 暴落のワイド表示は割引率を含む() {
+        let text = market_banner_wide(&MarketPhase::Crash);
+        assert_eq!(text, "💥 市場: 暴落！ 生産者75%OFF");
     }
 
     #[test]
     fn 不景気のワイド表示は50パーセントオフ() {
-        let text = market_banner_wide(&MarketPhase::Bear, 450);
-        assert_eq!(text, "📉 市場: 不景気 生産者50%OFF (残り45秒)");
+        let text = market_banner_wide(&MarketPhase::Bear);
+        assert_eq!(text, "📉 市場: 不景気 生産者50%OFF");
     }
 
     #[test]
     fn 好景気のワイド表示はcpsとコストの倍率を含む() {
-        let text = market_banner_wide(&MarketPhase::Bull, 600);
-        assert_eq!(text, "📈 市場: 好景気 CPS×1.5 コスト×1.6 (残り60秒)");
+        let text = market_banner_wide(&MarketPhase::Bull);
+        assert_eq!(text, "📈 市場: 好景気 CPS×1.5 コスト×1.6");
     }
 
     #[test]
     fn バブルのワイド表示は整数倍率を小数なしで表示() {
-        let text = market_banner_wide(&MarketPhase::Bubble, 150);
-        assert_eq!(text, "🚀 市場: バブル！ CPS×3 コスト×3 (残り15秒)");
+        let text = market_banner_wide(&MarketPhase::Bubble);
+        assert_eq!(text, "🚀 市場: バブル！ CPS×3 コスト×3");
     }
 
     #[test]
     fn 通常のワイド表示は効果テキストなし() {
-        let text = market_banner_wide(&MarketPhase::Normal, 500);
-        assert_eq!(text, "📊 市場: 通常 (残り50秒)");
+        let text = market_banner_wide(&MarketPhase::Normal);
+        assert_eq!(text, "📊 市場: 通常");
     }
 
     #[test]
     fn ナロー表示は短縮形で割引や倍率を含む() {
-        assert_eq!(
-            market_banner_narrow(&MarketPhase::Crash, 230),
-            "💥暴落！75%OFF(23s)"
-        );
-        assert_eq!(
-            market_banner_narrow(&MarketPhase::Bear, 450),
-            "📉不景気50%OFF(45s)"
-        );
-        assert_eq!(
-            market_banner_narrow(&MarketPhase::Bull, 600),
-            "📈好景気 コスト×1.6(60s)"
-        );
-        assert_eq!(
-            market_banner_narrow(&MarketPhase::Bubble, 150),
-            "🚀バブル！ コスト×3(15s)"
-        );
-        assert_eq!(
-            market_banner_narrow(&MarketPhase::Normal, 500),
-            "📊通常(50s)"
-        );
+        assert_eq!(market_banner_narrow(&MarketPhase::Crash), "💥暴落！75%OFF");
+        assert_eq!(market_banner_narrow(&MarketPhase::Bear), "📉不景気50%OFF");
+        assert_eq!(market_banner_narrow(&MarketPhase::Bull), "📈好景気 コスト×1.6");
+        assert_eq!(market_banner_narrow(&MarketPhase::Bubble), "🚀バブル！ コスト×3");
+        assert_eq!(market_banner_narrow(&MarketPhase::Normal), "📊通常");
     }
 
     // ── 市場フェーズ変化の検出 ────────────────────────────
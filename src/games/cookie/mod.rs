@@ -2,6 +2,7 @@
 
 pub mod actions;
 pub mod logic;
+pub mod news;
 pub mod render;
 pub mod save;
 #[cfg(test)]
@@ -14,11 +15,17 @@ use std::rc::Rc;
 use ratzilla::ratatui::layout::Rect;
 use ratzilla::ratatui::Frame;
 
-use crate::input::{ClickState, InputEvent};
+use crate::arcade::{self, Reward};
+use crate::input::{ClickState, InputEvent, PAGE_DOWN_KEY, PAGE_UP_KEY};
+use crate::scores::Score;
 use crate::games::{Game, GameChoice};
+use crate::widgets::{page_scroll, scroll_by};
 
 use actions::*;
-use state::{CookieState, DragonAura, ProducerKind, SugarBoostKind};
+use state::{
+    ChallengeKind, ComboSkill, CookieState, HuntChoice, ProducerKind, SeedKind, SugarBoostKind,
+    FUTURES_BUY_FRACTIONS, QUEUE_BATCH,
+};
 
 pub struct CookieGame {
     pub state: CookieState,
@@ -28,16 +35,18 @@ pub struct CookieGame {
 
 impl CookieGame {
     pub fn new() -> Self {
-        let state = CookieState::new();
+        let mut state = CookieState::new();
 
         #[cfg(target_arch = "wasm32")]
-        let state = {
-            let mut s = state;
-            if save::load_game(&mut s) {
-                s.add_log("セーブデータをロードしました", true);
-            }
-            s
-        };
+        let loaded = save::load_game(&mut state);
+        #[cfg(not(target_arch = "wasm32"))]
+        let loaded = false;
+        if loaded {
+            state.add_log("セーブデータをロードしました", true);
+        } else if arcade::owns(Reward::CookieHeadStart) {
+            state.cookies += arcade::COOKIE_HEAD_START;
+            state.add_log(&format!("{}: クッキー {} 枚から", Reward::CookieHeadStart.name(), arcade::COOKIE_HEAD_START), true);
+        }
 
         Self {
             state,
@@ -47,6 +56,16 @@ impl CookieGame {
 
     /// Handle a click action by semantic action ID (direct dispatch, no context ambiguity).
     fn handle_click(&mut self, action_id: u16) -> bool {
+        // The path-choice dialog is modal: only its own buttons get through.
+        if self.state.research_confirm.is_some() {
+            match action_id {
+                CONFIRM_RESEARCH_PATH => {
+                    logic::confirm_research(&mut self.state);
+                }
+                _ => self.state.research_confirm = None,
+            }
+            return true;
+        }
         match action_id {
             CLICK_COOKIE => {
                 logic::click(&mut self.state);
@@ -61,6 +80,8 @@ impl CookieGame {
                 self.state.show_research = false;
                 self.state.show_milestones = false;
                 self.state.show_prestige = false;
+                self.state.show_garden = false;
+                self.state.show_futures = false;
                 true
             }
             TAB_UPGRADES => {
@@ -68,6 +89,8 @@ impl CookieGame {
                 self.state.show_research = false;
                 self.state.show_milestones = false;
                 self.state.show_prestige = false;
+                self.state.show_garden = false;
+                self.state.show_futures = false;
                 true
             }
             TAB_RESEARCH => {
@@ -75,6 +98,8 @@ impl CookieGame {
                 self.state.show_upgrades = false;
                 self.state.show_milestones = false;
                 self.state.show_prestige = false;
+                self.state.show_garden = false;
+                self.state.show_futures = false;
                 true
             }
             TAB_MILESTONES => {
@@ -82,6 +107,45 @@ impl CookieGame {
                 self.state.show_upgrades = false;
                 self.state.show_research = false;
                 self.state.show_prestige = false;
+                self.state.show_garden = false;
+                self.state.show_futures = false;
+                true
+            }
+            TAB_GARDEN => {
+                self.state.show_garden = true;
+                self.state.show_upgrades = false;
+                self.state.show_research = false;
+                self.state.show_milestones = false;
+                self.state.show_prestige = false;
+                self.state.show_futures = false;
+                true
+            }
+            TAB_FUTURES => {
+                self.state.show_futures = true;
+                self.state.show_upgrades = false;
+                self.state.show_research = false;
+                self.state.show_milestones = false;
+                self.state.show_prestige = false;
+                self.state.show_garden = false;
+                true
+            }
+            id if FUTURES_BUY.contains(id) => {
+                let idx = FUTURES_BUY.index(id).unwrap_or(0);
+                logic::invest_futures(&mut self.state, FUTURES_BUY_FRACTIONS[idx]);
+                true
+            }
+            FUTURES_SELL => {
+                logic::sell_futures(&mut self.state);
+                true
+            }
+            id if GARDEN_SEED.contains(id) => {
+                if let Some(seed) = SeedKind::from_index(GARDEN_SEED.index(id).unwrap_or(0)) {
+                    logic::select_seed(&mut self.state, seed);
+                }
+                true
+            }
+            id if GARDEN_PLOT.contains(id) => {
+                logic::tap_plot(&mut self.state, GARDEN_PLOT.index(id).unwrap_or(0));
                 true
             }
             TAB_PRESTIGE => {
@@ -89,33 +153,82 @@ impl CookieGame {
                 self.state.show_upgrades = false;
                 self.state.show_research = false;
                 self.state.show_milestones = false;
+                self.state.show_garden = false;
+                self.state.show_futures = false;
                 true
             }
-            id if (BUY_PRODUCER_BASE..BUY_PRODUCER_BASE + 12).contains(&id) => {
-                let idx = (id - BUY_PRODUCER_BASE) as usize;
+            id if BUY_PRODUCER.contains(id) => {
+                let idx = BUY_PRODUCER.index(id).unwrap_or(0);
                 if let Some(kind) = ProducerKind::from_index(idx) {
-                    logic::buy_producer(&mut self.state, &kind);
+                    if self.state.queue_mode {
+                        logic::queue_producer(&mut self.state, &kind, QUEUE_BATCH);
+                    } else {
+                        logic::buy_producer(&mut self.state, &kind);
+                    }
+                }
+                true
+            }
+            id if LEVEL_UP_PRODUCER.contains(id) => {
+                if let Some(kind) = ProducerKind::from_index(LEVEL_UP_PRODUCER.index(id).unwrap_or(0)) {
+                    logic::level_up_producer(&mut self.state, &kind);
+                }
+                true
+            }
+            TOGGLE_QUEUE_MODE => {
+                self.state.queue_mode = !self.state.queue_mode;
+                true
+            }
+            id if CANCEL_QUEUE.contains(id) => {
+                logic::cancel_queued(&mut self.state, CANCEL_QUEUE.index(id).unwrap_or(0));
+                true
+            }
+            CLOSE_PRODUCER_DETAIL => {
+                self.state.producer_detail = None;
+                true
+            }
+            TOGGLE_CPS_BREAKDOWN => {
+                logic::toggle_cps_breakdown(&mut self.state);
+                true
+            }
+            TOGGLE_PIN_MODE => {
+                self.state.pin_mode = !self.state.pin_mode;
+                true
+            }
+            id if PINNED_UPGRADE.contains(id) => {
+                let pin_idx = PINNED_UPGRADE.index(id).unwrap_or(0);
+                if let Some(&real_idx) = self.state.pinned_upgrades.get(pin_idx) {
+                    logic::buy_upgrade(&mut self.state, real_idx);
                 }
                 true
             }
-            id if (BUY_UPGRADE_BASE..BUY_UPGRADE_BASE + 26).contains(&id) => {
-                let display_idx = (id - BUY_UPGRADE_BASE) as usize;
+            id if BUY_UPGRADE.contains(id) => {
+                let display_idx = BUY_UPGRADE.index(id).unwrap_or(0);
                 let available = self.state.available_upgrades();
                 if let Some(&real_idx) = available.get(display_idx) {
-                    logic::buy_upgrade(&mut self.state, real_idx);
+                    if self.state.pin_mode {
+                        logic::toggle_pin_upgrade(&mut self.state, real_idx);
+                    } else {
+                        logic::buy_upgrade(&mut self.state, real_idx);
+                    }
                 }
                 true
             }
-            id if (BUY_RESEARCH_BASE..BUY_RESEARCH_BASE + 26).contains(&id) => {
-                let display_idx = (id - BUY_RESEARCH_BASE) as usize;
+            id if BUY_COMBO_SKILL.contains(id) => {
+                if let Some(skill) = BUY_COMBO_SKILL.index(id).and_then(ComboSkill::from_index) {
+                    logic::buy_combo_skill(&mut self.state, skill);
+                }
+                true
+            }
+            id if BUY_RESEARCH.contains(id) => {
+                let display_idx = BUY_RESEARCH.index(id).unwrap_or(0);
                 let visible = self.state.visible_research();
                 if let Some(&real_idx) = visible.get(display_idx) {
-                    logic::buy_research(&mut self.state, real_idx);
+                    logic::request_research(&mut self.state, real_idx);
                 }
                 true
             }
-            id if (CLAIM_MILESTONE_BASE..CLAIM_MILESTONE_BASE + 26).contains(&id) => {
-                let display_idx = (id - CLAIM_MILESTONE_BASE) as usize;
+            id if CLAIM_MILESTONE.contains(id) => {
+                let display_idx = CLAIM_MILESTONE.index(id).unwrap_or(0);
                 let ready = self.state.ready_milestones();
                 if let Some(&real_idx) = ready.get(display_idx) {
                     logic::claim_milestone(&mut self.state, real_idx);
@@ -126,6 +239,10 @@ impl CookieGame {
                 logic::claim_all_milestones(&mut self.state);
                 true
             }
+            CYCLE_COOKIE_ART => {
+                logic::cycle_cookie_art(&mut self.state);
+                true
+            }
             PRESTIGE_RESET => {
                 logic::perform_prestige(&mut self.state);
                 true
@@ -150,6 +267,22 @@ impl CookieGame {
                 self.state.prestige_scroll = 0;
                 true
             }
+            PRESTIGE_SEC_CHALLENGES => {
+                self.state.prestige_section = 4;
+                self.state.prestige_scroll = 0;
+                true
+            }
+            id if START_CHALLENGE.contains(id) => {
+                let idx = START_CHALLENGE.index(id).unwrap_or(0);
+                if let Some(kind) = ChallengeKind::from_index(idx) {
+                    logic::start_challenge(&mut self.state, kind);
+                }
+                true
+            }
+            ABANDON_CHALLENGE => {
+                logic::abandon_challenge(&mut self.state);
+                true
+            }
             PRESTIGE_SCROLL_UP => {
                 self.state.prestige_scroll = self.state.prestige_scroll.saturating_sub(3);
                 true
@@ -158,26 +291,46 @@ impl CookieGame {
                 self.state.prestige_scroll = self.state.prestige_scroll.saturating_add(3);
                 true
             }
-            id if (BUY_PRESTIGE_UPGRADE_BASE..BUY_PRESTIGE_UPGRADE_BASE + 26).contains(&id) => {
-                let idx = (id - BUY_PRESTIGE_UPGRADE_BASE) as usize;
+            UPGRADES_SCROLL_UP => {
+                scroll_by(&self.state.upgrades_scroll, -3);
+                true
+            }
+            UPGRADES_SCROLL_DOWN => {
+                scroll_by(&self.state.upgrades_scroll, 3);
+                true
+            }
+            MILESTONES_SCROLL_UP => {
+                scroll_by(&self.state.milestones_scroll, -3);
+                true
+            }
+            MILESTONES_SCROLL_DOWN => {
+                scroll_by(&self.state.milestones_scroll, 3);
+                true
+            }
+            id if BUY_PRESTIGE_UPGRADE.contains(id) => {
+                let idx = BUY_PRESTIGE_UPGRADE.index(id).unwrap_or(0);
                 logic::buy_prestige_upgrade(&mut self.state, idx);
                 true
             }
-            id if (DRAGON_FEED_BASE..DRAGON_FEED_BASE + 12).contains(&id) => {
-                let idx = (id - DRAGON_FEED_BASE) as usize;
+            id if DRAGON_FEED.contains(id) => {
+                let idx = DRAGON_FEED.index(id).unwrap_or(0);
                 if let Some(kind) = ProducerKind::from_index(idx) {
                     logic::feed_dragon(&mut self.state, &kind, 1);
                 }
                 true
             }
             DRAGON_CYCLE_AURA => {
-                let auras = DragonAura::all();
-                let current_idx = auras.iter().position(|a| *a == self.state.dragon_aura);
-                let next = match current_idx {
-                    Some(i) => auras[(i + 1) % auras.len()].clone(),
-                    None => auras[0].clone(),
-                };
-                logic::set_dragon_aura(&mut self.state, next);
+                logic::cycle_dragon_aura(&mut self.state, false);
+                true
+            }
+            DRAGON_CYCLE_AURA2 => {
+                logic::cycle_dragon_aura(&mut self.state, true);
+                true
+            }
+            id if DRAGON_HUNT.contains(id) => {
+                if let Some(&choice) = HuntChoice::all().get(DRAGON_HUNT.index(id).unwrap_or(0)) {
+                    logic::answer_dragon_hunt(&mut self.state, choice);
+                }
                 true
             }
             SUGAR_RUSH => {
@@ -192,6 +345,10 @@ impl CookieGame {
                 logic::activate_sugar_boost(&mut self.state, SugarBoostKind::Frenzy);
                 true
             }
+            CYCLE_ANIMATION_INTENSITY => {
+                logic::cycle_animation_intensity(&mut self.state);
+                true
+            }
             TOGGLE_AUTO_CLICKER => {
                 logic::toggle_auto_clicker(&mut self.state);
                 true
@@ -201,8 +358,65 @@ impl CookieGame {
     }
 
     /// Handle a keyboard key press (context-dependent, as before).
+    /// Double-tap: on the cookie it's a critical click; elsewhere unhandled
+    /// (the dispatcher then delivers a plain click).
+    fn handle_double_tap(&mut self, action_id: u16) -> bool {
+        match action_id {
+            CLICK_COOKIE => {
+                logic::critical_click(&mut self.state);
+                true
+            }
+            _ => false,
+        }
+    }
+
+    /// Long-press: on a producer it opens the detail card instead of buying.
+    fn handle_long_press(&mut self, action_id: u16) -> bool {
+        match action_id {
+            id if BUY_PRODUCER.contains(id) => {
+                match ProducerKind::from_index(BUY_PRODUCER.index(id).unwrap_or(0)) {
+                    Some(kind) => {
+                        self.state.producer_detail = Some(kind);
+                        self.state.show_cps_breakdown = false;
+                        true
+                    }
+                    None => false,
+                }
+            }
+            _ => false,
+        }
+    }
+
     fn handle_key(&mut self, key: char) -> bool {
+        if self.state.research_confirm.is_some() {
+            match key {
+                'y' => {
+                    logic::confirm_research(&mut self.state);
+                }
+                'n' | 'q' => self.state.research_confirm = None,
+                _ => return false,
+            }
+            return true;
+        }
         match key {
+            'q' if self.state.producer_detail.is_some() => {
+                self.state.producer_detail = None;
+                true
+            }
+            'q' if self.state.show_cps_breakdown => {
+                self.state.show_cps_breakdown = false;
+                true
+            }
+            '%' => {
+                logic::toggle_cps_breakdown(&mut self.state);
+                true
+            }
+            'l' if self.state.producer_detail.is_some() => {
+                if let Some(kind) = self.state.producer_detail.clone() {
+                    logic::level_up_producer(&mut self.state, &kind);
+                }
+                true
+            }
             'c' => {
                 logic::click(&mut self.state);
                 true
@@ -216,6 +430,8 @@ impl CookieGame {
                 self.state.show_research = false;
                 self.state.show_milestones = false;
                 self.state.show_prestige = false;
+                self.state.show_garden = false;
+                self.state.show_futures = false;
                 true
             }
             'r' => {
@@ -223,6 +439,8 @@ impl CookieGame {
                 self.state.show_upgrades = false;
                 self.state.show_milestones = false;
                 self.state.show_prestige = false;
+                self.state.show_garden = false;
+                self.state.show_futures = false;
                 true
             }
             'm' => {
@@ -230,6 +448,8 @@ impl CookieGame {
                 self.state.show_upgrades = false;
                 self.state.show_research = false;
                 self.state.show_prestige = false;
+                self.state.show_garden = false;
+                self.state.show_futures = false;
                 true
             }
             // Tab direct-set keys (used by keyboard shortcuts)
@@ -238,6 +458,8 @@ impl CookieGame {
                 self.state.show_research = false;
                 self.state.show_milestones = false;
                 self.state.show_prestige = false;
+                self.state.show_garden = false;
+                self.state.show_futures = false;
                 true
             }
             '|' => {
@@ -245,6 +467,8 @@ impl CookieGame {
                 self.state.show_research = false;
                 self.state.show_milestones = false;
                 self.state.show_prestige = false;
+                self.state.show_garden = false;
+                self.state.show_futures = false;
                 true
             }
             '\\' => {
@@ -252,6 +476,8 @@ impl CookieGame {
                 self.state.show_upgrades = false;
                 self.state.show_milestones = false;
                 self.state.show_prestige = false;
+                self.state.show_garden = false;
+                self.state.show_futures = false;
                 true
             }
             '}' => {
@@ -259,6 +485,8 @@ impl CookieGame {
                 self.state.show_upgrades = false;
                 self.state.show_research = false;
                 self.state.show_prestige = false;
+                self.state.show_garden = false;
+                self.state.show_futures = false;
                 true
             }
             '~' => {
@@ -266,6 +494,41 @@ impl CookieGame {
                 self.state.show_upgrades = false;
                 self.state.show_research = false;
                 self.state.show_milestones = false;
+                self.state.show_garden = false;
+                self.state.show_futures = false;
+                true
+            }
+            'f' => {
+                self.state.show_garden = !self.state.show_garden;
+                self.state.show_upgrades = false;
+                self.state.show_research = false;
+                self.state.show_milestones = false;
+                self.state.show_prestige = false;
+                self.state.show_futures = false;
+                true
+            }
+            't' => {
+                self.state.show_futures = !self.state.show_futures;
+                self.state.show_upgrades = false;
+                self.state.show_research = false;
+                self.state.show_milestones = false;
+                self.state.show_prestige = false;
+                self.state.show_garden = false;
+                true
+            }
+            '1' | '2' if self.state.show_futures => {
+                let idx = (key as u8 - b'1') as usize;
+                logic::invest_futures(&mut self.state, FUTURES_BUY_FRACTIONS[idx]);
+                true
+            }
+            's' if self.state.show_futures => {
+                logic::sell_futures(&mut self.state);
+                true
+            }
+            '1'..='4' if self.state.show_garden => {
+                if let Some(seed) = SeedKind::from_index((key as u8 - b'1') as usize) {
+                    logic::select_seed(&mut self.state, seed);
+                }
                 true
             }
             'p' if self.state.show_prestige => {
@@ -288,16 +551,22 @@ impl CookieGame {
                 true
             }
             '9' if self.state.show_prestige => {
-                let auras = DragonAura::all();
-                let current_idx = auras.iter().position(|a| *a == self.state.dragon_aura);
-                let next = match current_idx {
-                    Some(i) => auras[(i + 1) % auras.len()].clone(),
-                    None => auras[0].clone(),
-                };
-                logic::set_dragon_aura(&mut self.state, next);
+                logic::cycle_dragon_aura(&mut self.state, false);
+                true
+            }
+            // Second aura slot (Shift+D)
+            'D' if self.state.show_prestige => {
+                logic::cycle_dragon_aura(&mut self.state, true);
+                true
+            }
+            // Dragon hunt answers (Shift+H / G / N)
+            'H' | 'G' | 'N' if self.state.show_prestige && self.state.dragon_hunt.is_some() => {
+                if let Some(&choice) = HuntChoice::all().iter().find(|c| c.key() == key) {
+                    logic::answer_dragon_hunt(&mut self.state, choice);
+                }
                 true
             }
-            '1'..='9' | '0' | '-' | '=' if !self.state.show_upgrades && !self.state.show_research && !self.state.show_milestones && !self.state.show_prestige => {
+            '1'..='9' | '0' | '-' | '=' if !self.state.show_upgrades && !self.state.show_research && !self.state.show_milestones && !self.state.show_prestige && !self.state.show_garden && !self.state.show_futures => {
                 let kind = match key {
                     '1' => ProducerKind::Cursor,
                     '2' => ProducerKind::Grandma,
@@ -313,7 +582,15 @@ impl CookieGame {
                     '=' => ProducerKind::AntimatterCondenser,
                     _ => unreachable!(),
                 };
-                logic::buy_producer(&mut self.state, &kind);
+                if self.state.queue_mode {
+                    logic::queue_producer(&mut self.state, &kind, QUEUE_BATCH);
+                } else {
+                    logic::buy_producer(&mut self.state, &kind);
+                }
+                true
+            }
+            'b' if !self.state.show_upgrades && !self.state.show_research && !self.state.show_milestones && !self.state.show_prestige && !self.state.show_garden && !self.state.show_futures => {
+                self.state.queue_mode = !self.state.queue_mode;
                 true
             }
             // Sugar boost activation (Shift+R=Rush, Shift+F=Fever, Shift+Z=Frenzy)
@@ -329,6 +606,11 @@ impl CookieGame {
                 logic::activate_sugar_boost(&mut self.state, SugarBoostKind::Frenzy);
                 true
             }
+            // Animation intensity (Shift+V)
+            'V' if self.state.show_prestige => {
+                logic::cycle_animation_intensity(&mut self.state);
+                true
+            }
             // Auto-clicker toggle (Shift+A)
             'A' if self.state.show_prestige => {
                 logic::toggle_auto_clicker(&mut self.state);
@@ -347,15 +629,44 @@ impl CookieGame {
                 }
                 true
             }
+            PAGE_UP_KEY | PAGE_DOWN_KEY if self.state.show_upgrades => {
+                page_scroll(&self.state.upgrades_scroll, key)
+            }
+            PAGE_UP_KEY | PAGE_DOWN_KEY if self.state.show_milestones => {
+                page_scroll(&self.state.milestones_scroll, key)
+            }
             '!' if self.state.show_milestones => {
                 logic::claim_all_milestones(&mut self.state);
                 true
             }
+            '@' if self.state.show_milestones => {
+                logic::cycle_cookie_art(&mut self.state);
+                true
+            }
             'a'..='z' if self.state.show_upgrades => {
                 let display_idx = (key as u8 - b'a') as usize;
                 let available = self.state.available_upgrades();
                 if let Some(&real_idx) = available.get(display_idx) {
-                    logic::buy_upgrade(&mut self.state, real_idx);
+                    if self.state.pin_mode {
+                        logic::toggle_pin_upgrade(&mut self.state, real_idx);
+                    } else {
+                        logic::buy_upgrade(&mut self.state, real_idx);
+                    }
+                }
+                true
+            }
+            '1'..='6' if self.state.show_upgrades => {
+                if let Some(skill) = ComboSkill::from_index((key as u8 - b'1') as usize) {
+                    logic::buy_combo_skill(&mut self.state, skill);
+                }
+                true
+            }
+            // Shift+letter pins/unpins that upgrade regardless of pin mode
+            'A'..='Z' if self.state.show_upgrades => {
+                let display_idx = (key as u8 - b'A') as usize;
+                let available = self.state.available_upgrades();
+                if let Some(&real_idx) = available.get(display_idx) {
+                    logic::toggle_pin_upgrade(&mut self.state, real_idx);
                 }
                 true
             }
@@ -363,7 +674,7 @@ impl CookieGame {
                 let display_idx = (key as u8 - b'a') as usize;
                 let visible = self.state.visible_research();
                 if let Some(&real_idx) = visible.get(display_idx) {
-                    logic::buy_research(&mut self.state, real_idx);
+                    logic::request_research(&mut self.state, real_idx);
                 }
                 true
             }
@@ -381,6 +692,9 @@ impl Game for CookieGame {
         match event {
             InputEvent::Key(c) => self.handle_key(*c),
             InputEvent::Click(_, id) => self.handle_click(*id),
+            InputEvent::LongPress(_, id) => self.handle_long_press(*id),
+            InputEvent::DoubleTap(_, id) => self.handle_double_tap(*id),
+            InputEvent::Drag(..) => false,
         }
     }
 
@@ -390,15 +704,47 @@ impl Game for CookieGame {
         // オートセーブ (WASM環境のみ)
         self.save_countdown = self.save_countdown.saturating_sub(delta_ticks);
         if self.save_countdown == 0 {
-            #[cfg(target_arch = "wasm32")]
-            save::save_game(&self.state);
-            self.save_countdown = save::AUTOSAVE_INTERVAL;
+            self.save();
         }
     }
 
+    fn save(&mut self) {
+        #[cfg(target_arch = "wasm32")]
+        save::save_game(&self.state);
+        self.save_countdown = save::AUTOSAVE_INTERVAL;
+    }
+
     fn render(&self, f: &mut Frame, area: Rect, click_state: &Rc<RefCell<ClickState>>) {
         render::render(&self.state, f, area, click_state);
     }
+
+    fn score(&self) -> Option<Score> {
+        Some(Score::new(self.state.heavenly_chips, "天国チップ", "枚"))
+    }
+
+    #[cfg(any(feature = "debug-console", debug_assertions, test))]
+    fn debug_command(&mut self, args: &[&str]) -> Result<String, String> {
+        use crate::debug_console::{parse_amount, parse_count, unknown};
+        match args {
+            ["help"] => Ok("give cookies <n> / give chips <n> / spawn golden".into()),
+            ["give", "cookies", n] => {
+                let n = parse_amount(n)?;
+                self.state.cookies += n;
+                self.state.cookies_all_time += n;
+                Ok(format!("クッキー +{}", n))
+            }
+            ["give", "chips", n] => {
+                let n = parse_count(n)?;
+                self.state.heavenly_chips = self.state.heavenly_chips.saturating_add(n);
+                Ok(format!("天国チップ +{}", n))
+            }
+            ["spawn", "golden"] => {
+                logic::spawn_golden(&mut self.state);
+                Ok("ゴールデンクッキー出現".into())
+            }
+            _ => Err(unknown(args)),
+        }
+    }
 }
 
 #[cfg(test)]
@@ -430,6 +776,137 @@ mod tests {
         assert_eq!(game.state.producers[0].count, 1);
     }
 
+    #[test]
+    fn cookie_game_queue_mode_reserves_instead_of_buying() {
+        let mut game = CookieGame::new();
+        game.state.cookies = 100.0;
+        game.handle_input(&InputEvent::Key('b'));
+        assert!(game.state.queue_mode);
+        game.handle_input(&click(BUY_PRODUCER.id(0))); // cursor
+        assert_eq!(game.state.producers[0].count, 0);
+        assert_eq!(game.state.purchase_queue[0].remaining, QUEUE_BATCH);
+        game.handle_input(&click(CANCEL_QUEUE.id(0)));
+        assert!(game.state.purchase_queue.is_empty());
+    }
+
+    #[test]
+    fn cookie_game_pages_the_open_list() {
+        let mut game = CookieGame::new();
+        game.handle_input(&InputEvent::Key('u'));
+        assert!(game.handle_input(&InputEvent::Key(PAGE_DOWN_KEY)));
+        assert!(game.state.upgrades_scroll.get() > 0);
+        assert_eq!(game.state.milestones_scroll.get(), 0);
+        game.handle_input(&click(UPGRADES_SCROLL_UP));
+        game.handle_input(&InputEvent::Key(PAGE_UP_KEY));
+        assert_eq!(game.state.upgrades_scroll.get(), 0);
+
+        game.handle_input(&InputEvent::Key('m'));
+        game.handle_input(&click(MILESTONES_SCROLL_DOWN));
+        assert_eq!(game.state.milestones_scroll.get(), 3);
+    }
+
+    #[test]
+    fn cookie_game_pin_upgrades_from_upgrades_tab() {
+        let mut game = CookieGame::new();
+        game.handle_input(&InputEvent::Key('u'));
+        // Shift+letter pins without buying
+        game.state.cookies = 1e6;
+        game.handle_input(&InputEvent::Key('A'));
+        assert_eq!(game.state.pinned_upgrades, vec![0]);
+        assert!(!game.state.upgrades[0].purchased);
+
+        // Pin mode: taps pin instead of buying
+        game.handle_input(&click(TOGGLE_PIN_MODE));
+        game.handle_input(&click(BUY_UPGRADE.id(1)));
+        assert_eq!(game.state.pinned_upgrades, vec![0, 1]);
+        assert!(!game.state.upgrades[1].purchased);
+
+        // Tapping a pinned row on the producers view buys it
+        game.handle_input(&click(PINNED_UPGRADE.id(0)));
+        assert!(game.state.upgrades[0].purchased);
+        assert_eq!(game.state.pinned_upgrades, vec![1]);
+    }
+
+    #[test]
+    fn level_button_levels_up_instead_of_buying() {
+        let mut game = CookieGame::new();
+        game.state.producers[0].count = 1;
+        game.state.cookies = 1_000.0;
+        game.handle_input(&click(LEVEL_UP_PRODUCER.id(0)));
+        assert_eq!(game.state.producers[0].level, 1);
+        assert_eq!(game.state.producers[0].count, 1);
+
+        // Detail card: [l] levels up the open producer
+        game.state.cookies = 1e6;
+        game.state.producer_detail = Some(ProducerKind::Cursor);
+        game.handle_input(&InputEvent::Key('l'));
+        assert_eq!(game.state.producers[0].level, 2);
+    }
+
+    #[test]
+    fn upgrades_tab_number_keys_learn_combo_skills() {
+        let mut game = CookieGame::new();
+        game.state.show_upgrades = true;
+        game.state.cookies = 10_000.0;
+        assert!(game.handle_input(&InputEvent::Key('2')));
+        assert!(game.state.combo_skills.is_empty(), "root first");
+        game.handle_input(&InputEvent::Key('1'));
+        game.handle_input(&click(BUY_COMBO_SKILL.id(1)));
+        assert_eq!(game.state.combo_skills, vec![ComboSkill::Tempo, ComboSkill::KeenEye]);
+        assert_eq!(game.state.producers[0].count, 0, "digits don't buy producers here");
+    }
+
+    #[test]
+    fn cookie_game_double_tap_forces_critical_with_cooldown() {
+        let mut game = CookieGame::new();
+        let double_tap = InputEvent::DoubleTap(ClickScope::Game(GameChoice::Cookie), CLICK_COOKIE);
+        let crit = game.state.effective_click_power() * game.state.critical_multiplier();
+        assert!(game.handle_input(&double_tap));
+        assert!((game.state.cookies - crit).abs() < 0.001, "{}", game.state.cookies);
+        assert!(game.state.crit_click_cooldown > 0);
+
+        // On cooldown it's only a click (can still crit by chance, never forced)
+        assert!(!logic::critical_click(&mut game.state));
+        game.tick(state::CRIT_CLICK_COOLDOWN);
+        assert!(logic::critical_click(&mut game.state));
+
+        // Double-tap elsewhere is left for the dispatcher to turn into a click
+        let other = InputEvent::DoubleTap(ClickScope::Game(GameChoice::Cookie), TAB_UPGRADES);
+        assert!(!game.handle_input(&other));
+    }
+
+    #[test]
+    fn cookie_game_long_press_opens_producer_detail() {
+        let mut game = CookieGame::new();
+        game.state.cookies = 1000.0;
+        let hold = InputEvent::LongPress(ClickScope::Game(GameChoice::Cookie), BUY_PRODUCER.id(1));
+        assert!(game.handle_input(&hold));
+        assert_eq!(game.state.producer_detail, Some(ProducerKind::Grandma));
+        assert_eq!(game.state.producers[1].count, 0, "long-press must not buy");
+
+        game.handle_input(&InputEvent::Key('q'));
+        assert_eq!(game.state.producer_detail, None);
+        game.handle_input(&hold);
+        game.handle_input(&click(CLOSE_PRODUCER_DETAIL));
+        assert_eq!(game.state.producer_detail, None);
+    }
+
+    #[test]
+    fn cookie_game_stats_row_opens_cps_breakdown() {
+        let mut game = CookieGame::new();
+        game.state.show_upgrades = true;
+        assert!(game.handle_input(&click(TOGGLE_CPS_BREAKDOWN)));
+        assert!(game.state.show_cps_breakdown);
+        assert!(!game.state.show_upgrades, "the card lives on the producers tab");
+
+        game.handle_input(&InputEvent::Key('q'));
+        assert!(!game.state.show_cps_breakdown);
+        game.handle_input(&InputEvent::Key('%'));
+        assert!(game.state.show_cps_breakdown);
+        game.handle_input(&InputEvent::Key('%'));
+        assert!(!game.state.show_cps_breakdown);
+    }
+
     #[test]
     fn cookie_game_toggle_upgrades() {
         let mut game = CookieGame::new();
@@ -557,6 +1034,45 @@ mod tests {
         assert!(game.state.show_upgrades);
     }
 
+    #[test]
+    fn garden_tab_selects_seeds_and_plants_by_click() {
+        let mut game = CookieGame::new();
+        game.handle_input(&InputEvent::Key('f'));
+        assert!(game.state.show_garden);
+        game.handle_input(&InputEvent::Key('2'));
+        assert_eq!(game.state.selected_seed, SeedKind::Thumbcorn);
+        assert_eq!(game.state.producers[1].count, 0, "digits pick seeds, not producers");
+
+        game.state.cookies = 1e6;
+        game.handle_input(&click(GARDEN_PLOT.id(5)));
+        assert_eq!(
+            game.state.garden[5].as_ref().map(|p| p.seed),
+            Some(SeedKind::Thumbcorn)
+        );
+        game.handle_input(&click(TAB_PRODUCERS));
+        assert!(!game.state.show_garden);
+    }
+
+    #[test]
+    fn futures_tab_buys_and_sells_by_key_and_click() {
+        let mut game = CookieGame::new();
+        game.handle_input(&InputEvent::Key('t'));
+        assert!(game.state.show_futures);
+        game.state.cookies = 1000.0;
+        game.handle_input(&InputEvent::Key('1'));
+        assert_eq!(game.state.cookies, 900.0);
+        assert_eq!(game.state.producers[0].count, 0, "digits buy futures, not producers");
+
+        game.handle_input(&click(FUTURES_BUY.id(1)));
+        assert_eq!(game.state.cookies, 450.0);
+        game.handle_input(&click(FUTURES_SELL));
+        assert!(!game.state.futures.has_position());
+
+        game.handle_input(&InputEvent::Key('f'));
+        assert!(game.state.show_garden);
+        assert!(!game.state.show_futures);
+    }
+
     #[test]
     fn prestige_upgrade_via_input() {
         let mut game = CookieGame::new();
@@ -619,8 +1135,12 @@ mod tests {
         game.state.cookies = 1e12;
         game.handle_input(&InputEvent::Key('r'));
         game.handle_input(&InputEvent::Key('a'));
-        let purchased_count = game.state.research_nodes.iter().filter(|n| n.purchased).count();
-        assert!(purchased_count > 0);
+        // The first node of a path asks which path to commit to.
+        let real_idx = game.state.research_confirm.expect("path-choice confirm");
+        assert!(!game.state.research_nodes[real_idx].purchased);
+        game.handle_input(&InputEvent::Key('y'));
+        assert!(game.state.research_confirm.is_none());
+        assert!(game.state.research_nodes[real_idx].purchased);
     }
 
     #[test]
@@ -662,7 +1182,7 @@ mod tests {
     fn click_action_buy_producer() {
         let mut game = CookieGame::new();
         game.state.cookies = 100.0;
-        game.handle_input(&click(BUY_PRODUCER_BASE)); // Cursor
+        game.handle_input(&click(BUY_PRODUCER.id(0))); // Cursor
         assert_eq!(game.state.producers[0].count, 1);
     }
 
@@ -672,7 +1192,7 @@ mod tests {
         game.state.cookies = 100.0;
         // Even with upgrades tab open, click action directly buys producer
         game.state.show_upgrades = true;
-        game.handle_input(&click(BUY_PRODUCER_BASE));
+        game.handle_input(&click(BUY_PRODUCER.id(0)));
         assert_eq!(game.state.producers[0].count, 1);
     }
 
@@ -0,0 +1,167 @@
+//! News ticker — flavor headlines that react to the current run.
+//!
+//! Purely cosmetic: headlines are picked from [`HEADLINES`] by
+//! `state.news_seq`, never by `rng_state`, so showing news can't shift
+//! golden cookie or mini-event rolls.
+
+use super::logic::format_number;
+use super::state::{CookieState, MarketPhase, ProducerKind};
+
+/// How long one headline stays up (8 seconds).
+pub const NEWS_ROTATE_TICKS: u32 = 80;
+
+/// One entry of the message table: shown only while `when` holds.
+struct Headline {
+    when: fn(&CookieState) -> bool,
+    text: fn(&CookieState) -> String,
+}
+
+fn count(state: &CookieState, kind: ProducerKind) -> u32 {
+    state.producers.iter().find(|p| p.kind == kind).map_or(0, |p| p.count)
+}
+
+const HEADLINES: &[Headline] = &[
+    // Always available so the ticker is never empty
+    Headline {
+        when: |_| true,
+        text: |_| "町のパン屋「うちも負けていられない」".into(),
+    },
+    Headline {
+        when: |_| true,
+        text: |_| "専門家「クッキーは主食になりうる」".into(),
+    },
+    // Producers
+    Headline {
+        when: |s| count(s, ProducerKind::Cursor) >= 10,
+        text: |s| format!("カーソル {} 本が休まずクリック — 指の労働組合が結成へ", count(s, ProducerKind::Cursor)),
+    },
+    Headline {
+        when: |s| count(s, ProducerKind::Grandma) >= 1,
+        text: |s| format!("おばあちゃん {} 人、今日も元気に焼いています", count(s, ProducerKind::Grandma)),
+    },
+    Headline {
+        when: |s| count(s, ProducerKind::Grandma) >= 50,
+        text: |_| "「おばあちゃんが多すぎる」近隣住民から苦情".into(),
+    },
+    Headline {
+        when: |s| count(s, ProducerKind::Farm) >= 5,
+        text: |_| "クッキー畑が拡大中、小麦農家が転作を検討".into(),
+    },
+    Headline {
+        when: |s| count(s, ProducerKind::Mine) >= 5,
+        text: |_| "地下深くでチョコチップの鉱脈を発見".into(),
+    },
+    Headline {
+        when: |s| count(s, ProducerKind::Temple) >= 1,
+        text: |_| "謎の教団「クッキーこそ真理」と主張".into(),
+    },
+    Headline {
+        when: |s| count(s, ProducerKind::Portal) >= 1,
+        text: |_| "異次元から届いたクッキー、味は「少し紫」".into(),
+    },
+    Headline {
+        when: |s| count(s, ProducerKind::TimeMachine) >= 1,
+        text: |_| "未来の自分から「もっと焼け」とのメッセージ".into(),
+    },
+    // Market
+    Headline {
+        when: |s| s.market_phase == MarketPhase::Bubble,
+        text: |_| "クッキー相場が過熱！アナリスト「これはバブルだ」".into(),
+    },
+    Headline {
+        when: |s| s.market_phase == MarketPhase::Crash,
+        text: |_| "クッキー相場が暴落、投資家がやけ食い".into(),
+    },
+    Headline {
+        when: |s| s.market_phase == MarketPhase::Bull,
+        text: |_| "好景気でクッキー消費が過去最高に".into(),
+    },
+    Headline {
+        when: |s| s.market_phase == MarketPhase::Bear,
+        text: |_| "不景気でも「クッキーだけはやめられない」".into(),
+    },
+    // Dragon
+    Headline {
+        when: |s| s.dragon_level >= 1,
+        text: |s| format!("工場に住み着いた竜、すくすく成長中 (Lv.{})", s.dragon_level),
+    },
+    Headline {
+        when: |s| s.dragon_level >= 5,
+        text: |_| "竜、クッキーの山の上で昼寝 — 従業員は迂回".into(),
+    },
+    // Milestones of the run
+    Headline {
+        when: |s| s.cookies_all_time >= 1e6,
+        text: |s| format!("累計 {} 枚 — 町の道路がクッキーで舗装され始める", format_number(s.cookies_all_time)),
+    },
+    Headline {
+        when: |s| s.golden_cookies_claimed >= 10,
+        text: |_| "金色のクッキーの目撃情報が相次ぐ".into(),
+    },
+    Headline {
+        when: |s| !s.active_buffs.is_empty(),
+        text: |_| "工場の煙突から虹色の煙、住民騒然".into(),
+    },
+    Headline {
+        when: |s| s.prestige_count >= 1,
+        text: |s| format!("転生 {} 回目の工場長、「前世でも焼いていた」", s.prestige_count),
+    },
+];
+
+/// Headlines that can show right now, in table order.
+pub fn available(state: &CookieState) -> Vec<String> {
+    HEADLINES
+        .iter()
+        .filter(|h| (h.when)(state))
+        .map(|h| (h.text)(state))
+        .collect()
+}
+
+/// Rotate the headline every [`NEWS_ROTATE_TICKS`].
+pub fn tick_news(state: &mut CookieState, delta_ticks: u32) {
+    state.news_ticks_left = state.news_ticks_left.saturating_sub(delta_ticks);
+    if state.news_ticks_left > 0 && !state.news_headline.is_empty() {
+        return;
+    }
+    let headlines = available(state);
+    let index = state.news_seq as usize % headlines.len();
+    state.news_headline = headlines[index].clone();
+    state.news_seq = state.news_seq.wrapping_add(1);
+    state.news_ticks_left = NEWS_ROTATE_TICKS;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn headlines_react_to_state() {
+        let mut state = CookieState::new();
+        let base = available(&state).len();
+        assert!(base >= 1);
+
+        state.producers[1].count = 3; // grandmas
+        state.market_phase = MarketPhase::Bubble;
+        state.dragon_level = 2;
+        let news = available(&state);
+        assert!(news.iter().any(|h| h.contains("おばあちゃん 3 人")));
+        assert!(news.iter().any(|h| h.contains("バブル")));
+        assert!(news.iter().any(|h| h.contains("Lv.2")));
+        assert_eq!(news.len(), base + 3);
+    }
+
+    #[test]
+    fn ticker_rotates_without_touching_rng() {
+        let mut state = CookieState::new();
+        let rng = state.rng_state;
+        tick_news(&mut state, 1);
+        let first = state.news_headline.clone();
+        assert!(!first.is_empty());
+
+        tick_news(&mut state, NEWS_ROTATE_TICKS - 1);
+        assert_eq!(state.news_headline, first);
+        tick_news(&mut state, 1);
+        assert_ne!(state.news_headline, first);
+        assert_eq!(state.rng_state, rng);
+    }
+}
@@ -10,11 +10,61 @@ use ratzilla::ratatui::widgets::{Block, Borders, List, ListItem, Paragraph, Wrap
 use ratzilla::ratatui::Frame;
 
 use crate::input::ClickState;
-use crate::widgets::{Clickable, ClickableList, TabBar};
+use crate::widgets::{Clickable, ClickableGrid, ClickableList, CountdownChip, Gauge, Modal, TabBar};
 
 use super::actions::*;
 use super::logic::{format_number, is_market_buy_time, market_banner_narrow, market_banner_wide};
-use super::state::{CookieState, MarketPhase, ParticleStyle};
+use super::state::{
+    BuffKind, BuffSummary, ChallengeKind, ComboSkill, CookieArt, CookieState, DragonAbility, HuntChoice, MarketPhase, ParticlePanel, ParticleStyle,
+    ProducerKind, SeedKind, DRAGON_DUAL_AURA_LEVEL, FUTURES_BUY_FRACTIONS, GARDEN_H, GARDEN_W,
+    MAX_DRAGON_LEVEL, MAX_PINNED_UPGRADES, MAX_PRODUCER_LEVEL, PRODUCER_LEVEL_BONUS, QUEUE_BATCH,
+};
+
+fn buff_color(kind: BuffKind) -> Color {
+    match kind {
+        BuffKind::Production => Color::Magenta,
+        BuffKind::Click => Color::Cyan,
+    }
+}
+
+/// 残り時間のリング。満タン ● から ◕ ◑ ◔ と欠けていく。
+fn time_ring(ticks_left: u32, total_ticks: u32) -> char {
+    let frac = ticks_left as f64 / total_ticks.max(1) as f64;
+    if frac > 0.75 {
+        '●'
+    } else if frac > 0.5 {
+        '◕'
+    } else if frac > 0.25 {
+        '◑'
+    } else if frac > 0.0 {
+        '◔'
+    } else {
+        '○'
+    }
+}
+
+/// バフバーの 1 行 (「🌟 生産フィーバー ×49 2層 ◕ 0:05 +1待機」)。
+/// `gauge` なら残り時間のバーも付ける。
+fn buff_bar_line(b: &BuffSummary, gauge: bool) -> Line<'static> {
+    let color = buff_color(b.kind);
+    let stacks = if b.stacks > 1 { format!(" {}層", b.stacks) } else { String::new() };
+    let mut spans = vec![
+        Span::styled(
+            format!(" {} {} ×{:.0}{} ", b.kind.icon(), b.kind.name(), b.multiplier, stacks),
+            Style::default().fg(color).add_modifier(Modifier::BOLD),
+        ),
+        Span::styled(format!("{} ", time_ring(b.ticks_left, b.total_ticks)), Style::default().fg(color)),
+    ];
+    if gauge {
+        spans.push(Gauge::new(b.ticks_left as u64, b.total_ticks as u64, 10).ceil().color(color).span());
+        spans.push(Span::raw(" "));
+    }
+    spans.push(CountdownChip::new(b.ticks_left).color(color).span());
+    if b.queued > 0 {
+        spans.push(Span::styled(format!(" +{}待機", b.queued), Style::default().fg(Color::DarkGray)));
+    }
+    Line::from(spans)
+}
 
 /// Compact cookie art — 3 lines, 8 chars wide. Shared across all screen sizes.
 const COOKIE_ART: &[&[&str]] = &[
@@ -24,6 +74,34 @@ const COOKIE_ART: &[&[&str]] = &[
     &["╭━○━○━╮ ", "━○━━○━○━", "╰━○━○━╯ "],
 ];
 
+/// Milestone-unlocked cookie arts (same 3×8 frame as [`COOKIE_ART`]).
+const CHOCOLATE_ART: &[&[&str]] = &[
+    &["╭━■━■━╮ ", "━■━━■━■━", "╰━■━■━╯ "],
+    &["╭━▪━▪━╮ ", "━▪━━▪━▪━", "╰━▪━▪━╯ "],
+];
+const HEART_ART: &[&[&str]] = &[
+    &[" ╭━╮╭━╮ ", " ╰╮●●╭╯ ", "  ╰━━╯  "],
+    &[" ╭━╮╭━╮ ", " ╰╮○○╭╯ ", "  ╰━━╯  "],
+];
+const STAR_ART: &[&[&str]] = &[
+    &["  ✦  ✦  ", " ✦ ●● ✦ ", "  ✦  ✦  "],
+    &["  ✧  ✧  ", " ✧ ●● ✧ ", "  ✧  ✧  "],
+];
+const GOLDEN_ART: &[&[&str]] = &[
+    &["╭━◆━◆━╮ ", "━◆━━◆━◆━", "╰━◆━◆━╯ "],
+    &["╭━◇━◇━╮ ", "━◇━━◇━◇━", "╰━◇━◇━╯ "],
+];
+
+fn cookie_art_frames(art: CookieArt) -> &'static [&'static [&'static str]] {
+    match art {
+        CookieArt::Classic => COOKIE_ART,
+        CookieArt::Chocolate => CHOCOLATE_ART,
+        CookieArt::Heart => HEART_ART,
+        CookieArt::Star => STAR_ART,
+        CookieArt::Golden => GOLDEN_ART,
+    }
+}
+
 /// Compact cookie art — "pressed" state when clicked.
 const COOKIE_CLICK_ART: &[&[&str]] = &[
     &["╭●●●●●╮ ", "●●━━━●●━", "╰●●●●●╯ "],
@@ -75,7 +153,7 @@ pub fn render(state: &CookieState, f: &mut Frame, area: Rect, click_state: &Rc<R
     let buff_height = {
         let mut n = 0u16;
         if state.golden_event.is_some() { n += 1; }
-        n += state.active_buffs.len() as u16;
+        n += state.buff_summary().len() as u16;
         if state.active_discount > 0.0 { n += 1; }
         if n > 0 { n.min(4) } else { 0 }
     };
@@ -84,6 +162,8 @@ pub fn render(state: &CookieState, f: &mut Frame, area: Rect, click_state: &Rc<R
     // Includes one row for the dedicated market banner.
     let cookie_height: u16 = if is_narrow { 9 } else { 13 };
 
+    // News ticker: 1 row once the first headline is in
+    let news_rows = if state.news_headline.is_empty() { 0 } else { 1 };
     // Tab bar: horizontal (1 row) on all screen sizes
     let tab_rows = 1;
     let chunks = Layout::default()
@@ -91,6 +171,7 @@ pub fn render(state: &CookieState, f: &mut Frame, area: Rect, click_state: &Rc<R
         .constraints([
             Constraint::Length(cookie_height),
             Constraint::Length(buff_height),
+            Constraint::Length(news_rows), // news ticker
             Constraint::Length(tab_rows), // tab bar (horizontal)
             Constraint::Min(5),          // content
         ])
@@ -101,22 +182,53 @@ pub fn render(state: &CookieState, f: &mut Frame, area: Rect, click_state: &Rc<R
     if buff_height > 0 {
         render_buffs_and_golden(state, f, chunks[1], click_state);
     }
-    render_tab_bar(state, f, chunks[2], click_state);
+    if news_rows > 0 {
+        render_news_ticker(state, f, chunks[2]);
+    }
+    render_tab_bar(state, f, chunks[3], click_state);
+    let content = chunks[4];
     if state.show_prestige && state.is_tab_unlocked("prestige") {
-        render_prestige(state, f, chunks[3], click_state);
+        render_prestige(state, f, content, click_state);
+    } else if state.show_garden && state.is_tab_unlocked("garden") {
+        render_garden(state, f, content, click_state);
+    } else if state.show_futures && state.is_tab_unlocked("futures") {
+        render_futures(state, f, content, click_state);
     } else if state.show_milestones {
-        render_milestones(state, f, chunks[3], click_state);
+        render_milestones(state, f, content, click_state);
     } else if state.show_research && state.is_tab_unlocked("research") {
-        render_research(state, f, chunks[3], click_state);
+        render_research(state, f, content, click_state);
     } else if state.show_upgrades && state.is_tab_unlocked("upgrades") {
-        render_upgrades(state, f, chunks[3], click_state);
+        render_upgrades(state, f, content, click_state);
     } else {
-        render_producers(state, f, chunks[3], click_state);
+        render_producers(state, f, content, click_state);
     }
 
     if let Some(log_area) = log_area {
         render_log(state, f, log_area);
     }
+
+    if let Some(index) = state.research_confirm {
+        render_research_confirm(state, index, f, area, click_state);
+    }
+}
+
+/// One-line news ticker. Headlines wider than the row scroll sideways.
+fn render_news_ticker(state: &CookieState, f: &mut Frame, area: Rect) {
+    let text = format!("📰 {}", state.news_headline);
+    let width = area.width as usize;
+    let shown = if text.chars().count() * 2 <= width {
+        text
+    } else {
+        // Marquee: loop the headline with a gap, one step every 3 frames
+        let looped: Vec<char> = format!("{}　　　", text).chars().collect();
+        let offset = (state.anim_frame as usize / 3) % looped.len();
+        looped.iter().cycle().skip(offset).take(looped.len()).collect()
+    };
+    let widget = Paragraph::new(Line::from(Span::styled(
+        shown,
+        Style::default().fg(Color::Gray).add_modifier(Modifier::ITALIC),
+    )));
+    f.render_widget(widget, area);
 }
 
 /// Render horizontal tab bar for switching between Producers / Upgrades / Research / Milestones / Prestige.
@@ -130,67 +242,54 @@ fn render_tab_bar(
     let ready_count = state.ready_milestone_count();
     let is_narrow = area.width < 60;
 
-    // Determine active tab index
     let active = if state.show_prestige {
-        4
+        TAB_PRESTIGE
+    } else if state.show_garden {
+        TAB_GARDEN
+    } else if state.show_futures {
+        TAB_FUTURES
     } else if state.show_milestones {
-        3
+        TAB_MILESTONES
     } else if state.show_research {
-        2
+        TAB_RESEARCH
     } else if state.show_upgrades {
-        1
+        TAB_UPGRADES
     } else {
-        0
-    };
-
-    let tab_style = |idx: usize, base_color: Color| -> Style {
-        if idx == active {
-            Style::default()
-                .fg(Color::Black)
-                .bg(base_color)
-                .add_modifier(Modifier::BOLD)
-        } else {
-            Style::default().fg(base_color)
-        }
+        TAB_PRODUCERS
     };
 
     let milestone_color = if ready_count > 0 { Color::Green } else { Color::Cyan };
     let pending_chips = state.pending_heavenly_chips();
     let prestige_color = if pending_chips > 0 { Color::Yellow } else { Color::Blue };
 
-    // Build short labels for horizontal layout
-    let milestone_label = if is_narrow {
-        if ready_count > 0 { format!("目標{}", ready_count) } else { "目標".to_string() }
-    } else if ready_count > 0 {
-        format!("目標({})", ready_count)
-    } else {
-        "目標".to_string()
-    };
-
-    let prestige_label = if is_narrow {
-        if pending_chips > 0 { format!("転生+{}", pending_chips) } else { "転生".to_string() }
-    } else if pending_chips > 0 {
-        format!("転生(+{})", pending_chips)
-    } else {
-        "転生".to_string()
-    };
-
     let separator = if is_narrow { "|" } else { " │ " };
 
     let mut cs = click_state.borrow_mut();
-    let mut bar = TabBar::new(separator);
-    bar = bar.tab("生産", tab_style(0, Color::Green), TAB_PRODUCERS);
+    let mut bar = TabBar::new(separator).colored("生産", Color::Green, TAB_PRODUCERS);
     if state.is_tab_unlocked("upgrades") {
-        bar = bar.tab("強化", tab_style(1, Color::Magenta), TAB_UPGRADES);
+        bar = bar.colored("強化", Color::Magenta, TAB_UPGRADES);
     }
     if state.is_tab_unlocked("research") {
-        bar = bar.tab("研究", tab_style(2, Color::Cyan), TAB_RESEARCH);
+        bar = bar.colored("研究", Color::Cyan, TAB_RESEARCH);
+    }
+    bar = bar
+        .colored("目標", milestone_color, TAB_MILESTONES)
+        .badge(ready_count as u64);
+    if state.is_tab_unlocked("garden") {
+        let mature = state.garden.iter().flatten().filter(|p| p.is_mature()).count();
+        bar = bar
+            .colored("菜園", Color::LightGreen, TAB_GARDEN)
+            .badge(mature as u64);
+    }
+    if state.is_tab_unlocked("futures") {
+        bar = bar.colored("先物", Color::LightYellow, TAB_FUTURES);
     }
-    bar = bar.tab(milestone_label, tab_style(3, milestone_color), TAB_MILESTONES);
     if state.is_tab_unlocked("prestige") {
-        bar = bar.tab(prestige_label, tab_style(4, prestige_color), TAB_PRESTIGE);
+        bar = bar
+            .colored("転生", prestige_color, TAB_PRESTIGE)
+            .badge(pending_chips);
     }
-    bar.render(f, area, &mut cs);
+    bar.active(active).render(f, area, &mut cs);
 }
 
 fn render_cookie_display(
@@ -246,8 +345,8 @@ fn render_cookie_display(
         let idx = state.click_flash as usize % COOKIE_CLICK_ART.len();
         COOKIE_CLICK_ART[idx]
     } else {
-        let idx = (state.anim_frame / 5) as usize % COOKIE_ART.len();
-        COOKIE_ART[idx]
+        let frames = cookie_art_frames(state.cookie_art);
+        frames[(state.anim_frame / 5) as usize % frames.len()]
     };
 
     let click_label = if click_power > 1.0 {
@@ -273,10 +372,10 @@ fn render_cookie_display(
         Span::styled(" ─", Style::default().fg(Color::DarkGray))
     };
 
-    let mut lines: Vec<Line> = Vec::new();
+    let mut cl = ClickableList::new();
 
     // --- Row 0: Art[0] + cookie count ---
-    lines.push(Line::from(vec![
+    cl.push(Line::from(vec![
         Span::styled(cookie_art[0], Style::default().fg(cookie_color)),
         Span::styled(
             format!(" 🍪 {}", cookies_str),
@@ -285,7 +384,7 @@ fn render_cookie_display(
     ]));
 
     // --- Row 1: Art[1] + CPS with delta ---
-    lines.push(Line::from(vec![
+    cl.push(Line::from(vec![
         Span::styled(cookie_art[1], Style::default().fg(cookie_color)),
         Span::styled(
             format!(" {} {}/sec", spinner, cps_str),
@@ -317,7 +416,7 @@ fn render_cookie_display(
     } else {
         Span::styled("", Style::default())
     };
-    lines.push(Line::from(vec![
+    cl.push(Line::from(vec![
         Span::styled(cookie_art[2], Style::default().fg(cookie_color)),
         Span::styled(" ", Style::default()),
         Span::styled(&click_label, click_style),
@@ -328,9 +427,9 @@ fn render_cookie_display(
     // --- Market row: dedicated banner so buy-time phases stand out ---
     {
         let market_text = if w >= 60 {
-            format!(" {}", market_banner_wide(&state.market_phase, state.market_ticks_left))
+            format!(" {} 残り", market_banner_wide(&state.market_phase))
         } else {
-            format!(" {}", market_banner_narrow(&state.market_phase, state.market_ticks_left))
+            format!(" {} ", market_banner_narrow(&state.market_phase))
         };
         // Color follows the cost direction: green = cheap (buy now),
         // yellow = expensive (earn now), gray = neutral.
@@ -349,11 +448,15 @@ fn render_cookie_display(
         } else {
             Style::default().fg(market_color)
         };
-        lines.push(Line::from(Span::styled(market_text, market_style)));
+        cl.push(Line::from(vec![
+            Span::styled(market_text, market_style),
+            CountdownChip::new(state.market_ticks_left).color(market_color).span(),
+        ]));
     }
 
     // --- Row 3: Stats (clicks / milk / kitten / prestige / milestones) ---
-    lines.push(Line::from({
+    // Tapping this row opens the CPS breakdown card.
+    cl.push_clickable(Line::from({
         let mut spans = vec![
             Span::styled(
                 format!(" 👆{}", state.total_clicks),
@@ -384,8 +487,9 @@ fn render_cookie_display(
                 Style::default().fg(Color::Green).add_modifier(Modifier::BOLD),
             ));
         }
+        spans.push(Span::styled(" ⓘ内訳[%]", Style::default().fg(Color::DarkGray)));
         spans
-    }));
+    }), TOGGLE_CPS_BREAKDOWN);
 
     // --- Next Goal row: always visible, shows best next purchase with progress ---
     if let Some(goal) = state.best_next_purchase() {
@@ -396,14 +500,14 @@ fn render_cookie_display(
         let remaining = goal.cost - state.cookies;
 
         if goal.affordable {
-            lines.push(Line::from(vec![
+            cl.push(Line::from(vec![
                 Span::styled(
                     format!(" ◆ {} を買おう！", goal.name),
                     Style::default().fg(Color::Green).add_modifier(Modifier::BOLD),
                 ),
             ]));
         } else {
-            lines.push(Line::from(vec![
+            cl.push(Line::from(vec![
                 Span::styled(" ◆ 次: ", Style::default().fg(Color::Cyan)),
                 Span::styled(
                     goal.name.clone(),
@@ -428,7 +532,7 @@ fn render_cookie_display(
         let sparkline = build_sparkline(&state.cps_history, sparkline_width);
         let sparkline_color = cycling_color(state.anim_frame, 30);
 
-        lines.push(Line::from({
+        cl.push(Line::from({
             let mut spans = vec![
                 Span::styled(
                     " ┄┄ CPS ",
@@ -450,7 +554,7 @@ fn render_cookie_display(
         }));
 
         // --- Row 5: Production header ---
-        lines.push(Line::from(Span::styled(
+        cl.push(Line::from(Span::styled(
             " ┄┄ PRODUCTION ┄┄┄┄┄┄┄┄┄┄┄┄┄┄┄┄",
             Style::default().fg(Color::Green).add_modifier(Modifier::BOLD),
         )));
@@ -458,10 +562,10 @@ fn render_cookie_display(
         // --- Rows 6+: Producer contribution bars (dynamically sized) ---
         let contributions = state.producer_contributions();
         // Reserve 1 line for status bar; borders take 2 lines
-        let max_bar_rows = (h.saturating_sub(2) as usize).saturating_sub(lines.len() + 1).max(1);
+        let max_bar_rows = (h.saturating_sub(2) as usize).saturating_sub(cl.len() + 1).max(1);
 
         if contributions.is_empty() {
-            lines.push(Line::from(Span::styled(
+            cl.push(Line::from(Span::styled(
                 " (生産者を購入しましょう)",
                 Style::default().fg(Color::DarkGray),
             )));
@@ -494,7 +598,7 @@ fn render_cookie_display(
                         Style::default().fg(Color::DarkGray),
                     ));
                 }
-                lines.push(Line::from(row_spans));
+                cl.push(Line::from(row_spans));
             }
         }
     }
@@ -565,18 +669,24 @@ fn render_cookie_display(
     if status_spans.is_empty() {
         status_spans.push(Span::styled(" ", Style::default()));
     }
-    lines.push(Line::from(status_spans));
+    cl.push(Line::from(status_spans));
 
-    let widget = Paragraph::new(lines).block(
-        Block::default()
-            .borders(borders)
-            .border_style(Style::default().fg(border_color))
-            .title(title),
-    );
-    Clickable::new(widget, CLICK_COOKIE).render(f, area, &mut click_state.borrow_mut());
+    {
+        let mut cs = click_state.borrow_mut();
+        let widget = Paragraph::new(cl.lines().to_vec()).block(
+            Block::default()
+                .borders(borders)
+                .border_style(Style::default().fg(border_color))
+                .title(title),
+        );
+        Clickable::new(widget, CLICK_COOKIE).render(f, area, &mut cs);
+        // Registered after the cookie so the stats row wins the hit test
+        // (top border always present → top=1, bottom=1).
+        cl.register_targets(area, &mut cs, 1, 1, 0, 0);
+    }
 
     // Particles render on top of the cookie display
-    render_particles(state, f, area);
+    render_particles(state, f, area, &ParticlePanel::Cookie);
 }
 
 /// Build a sparkline string from a history of values.
@@ -600,6 +710,20 @@ fn build_sparkline(history: &[f64], max_width: usize) -> String {
         .collect()
 }
 
+/// Sparkline scaled to the history's own low..high range, so small price
+/// swings around 1.0 still show up.
+fn build_range_sparkline(history: &[f64], max_width: usize) -> String {
+    let data = &history[history.len().saturating_sub(max_width)..];
+    let low = data.iter().cloned().fold(f64::INFINITY, f64::min);
+    let high = data.iter().cloned().fold(f64::NEG_INFINITY, f64::max);
+    let range = (high - low).max(1e-9);
+    let line: String = data
+        .iter()
+        .map(|v| SPARKLINE_CHARS[(((v - low) / range * 6.0).round() as usize + 1).min(7)])
+        .collect();
+    format!("{}{}", " ".repeat(max_width - data.len()), line)
+}
+
 /// Get a cycling color based on animation frame for visual effects.
 fn cycling_color(anim_frame: u32, speed: u32) -> Color {
     let phase = (anim_frame / speed) % 4;
@@ -611,13 +735,13 @@ fn cycling_color(anim_frame: u32, speed: u32) -> Color {
     }
 }
 
-/// Render floating particles as overlays on the cookie display area.
-fn render_particles(state: &CookieState, f: &mut Frame, area: Rect) {
+/// Render floating particles belonging to `panel` as overlays on `area`.
+fn render_particles(state: &CookieState, f: &mut Frame, area: Rect, panel: &ParticlePanel) {
     let center_x = area.x + area.width / 2;
     let center_y = area.y + area.height / 2;
     let base_y = area.y + area.height;
 
-    for particle in &state.particles {
+    for particle in state.particles.iter().filter(|p| p.panel == *panel) {
         let progress = 1.0 - (particle.life as f32 / particle.max_life as f32);
 
         let (x, y, color, modifier) = match &particle.style {
@@ -684,6 +808,21 @@ fn render_particles(state: &CookieState, f: &mut Frame, area: Rect) {
                 let color = Color::Yellow;
                 (x, y, color, Modifier::BOLD)
             }
+            ParticleStyle::MilkSplash => {
+                // Left-anchored on the milk bar row (first inner row),
+                // dripping down and outward as the droplet ages.
+                let fall = (progress * 3.0) as u16;
+                let spread = (progress * 2.0) as i16;
+                let dir = if particle.col_offset % 2 == 0 { spread } else { -spread };
+                let y = area.y + 1 + fall;
+                let x = (area.x as i16 + 1 + particle.col_offset + dir).max(area.x as i16 + 1) as u16;
+                let color = if particle.life > particle.max_life / 2 {
+                    Color::White
+                } else {
+                    Color::Gray
+                };
+                (x, y, color, Modifier::BOLD)
+            }
         };
 
         if y >= area.y && y < area.y + area.height && x < area.x + area.width {
@@ -693,7 +832,7 @@ fn render_particles(state: &CookieState, f: &mut Frame, area: Rect) {
             let display_width = text_len.min(available);
             if display_width > 0 {
                 let particle_area = Rect::new(x, y, display_width.max(2), 1);
-                let widget = Paragraph::new(Span::styled(&particle.text, style));
+                let widget = Paragraph::new(Span::styled(particle.text.as_ref(), style));
                 f.render_widget(widget, particle_area);
             }
         }
@@ -711,7 +850,6 @@ fn render_buffs_and_golden(
 
     // Golden cookie indicator
     if let Some(ref event) = state.golden_event {
-        let secs_left = event.appear_ticks_left as f64 / 10.0;
         let blink = (state.anim_frame / 2).is_multiple_of(2);
         let golden_style = if blink {
             Style::default()
@@ -725,40 +863,16 @@ fn render_buffs_and_golden(
         lines.push(Line::from(vec![
             Span::styled(" 🍪 ゴールデンクッキー！ ", golden_style),
             Span::styled(
-                format!("タップで取得 (残り{:.0}秒)", secs_left),
+                "タップで取得 残り",
                 Style::default().fg(Color::White).add_modifier(Modifier::BOLD),
             ),
+            CountdownChip::new(event.appear_ticks_left).span(),
         ]));
     }
 
-    // Active buffs
-    for buff in &state.active_buffs {
-        let secs_left = buff.ticks_left as f64 / 10.0;
-        let bar_len = 10;
-        let max_ticks = match &buff.effect {
-            super::state::GoldenEffect::ProductionFrenzy { .. } => 70,
-            super::state::GoldenEffect::ClickFrenzy { .. } => 100,
-            _ => 70,
-        };
-        let filled = ((buff.ticks_left as f64 / max_ticks as f64) * bar_len as f64).ceil() as usize;
-        let bar: String = "█".repeat(filled.min(bar_len)) + &"░".repeat(bar_len - filled.min(bar_len));
-
-        let buff_color = match &buff.effect {
-            super::state::GoldenEffect::ProductionFrenzy { .. } => Color::Magenta,
-            super::state::GoldenEffect::ClickFrenzy { .. } => Color::Cyan,
-            _ => Color::Yellow,
-        };
-
-        lines.push(Line::from(vec![
-            Span::styled(
-                format!(" ⚡ {} ", buff.effect.detail()),
-                Style::default().fg(buff_color).add_modifier(Modifier::BOLD),
-            ),
-            Span::styled(
-                format!("{} {:.0}s", bar, secs_left),
-                Style::default().fg(buff_color),
-            ),
-        ]));
+    // Active buffs (one row per kind)
+    for summary in state.buff_summary() {
+        lines.push(buff_bar_line(&summary, true));
     }
 
     // Discount indicator
@@ -797,6 +911,15 @@ fn render_producers(
     area: Rect,
     click_state: &Rc<RefCell<ClickState>>,
 ) {
+    if let Some(kind) = &state.producer_detail {
+        render_producer_detail(state, kind, f, area, click_state);
+        return;
+    }
+    if state.show_cps_breakdown {
+        render_cps_breakdown(state, f, area, click_state);
+        return;
+    }
+
     let is_narrow = area.width < 60;
 
     // Find the best ROI (lowest payback time) among affordable producers, using synergy
@@ -817,7 +940,42 @@ fn render_producers(
 
     let mut cl = ClickableList::new();
 
+    // Pinned upgrades: what the player is saving up for (tap to buy once ready)
+    for (i, &real_idx) in state.pinned_upgrades.iter().enumerate() {
+        let upgrade = &state.upgrades[real_idx];
+        let cost = upgrade.cost * state.total_cost_modifier();
+        let unlocked = state.is_upgrade_unlocked(upgrade);
+        let pct = if cost > 0.0 { (state.cookies / cost * 100.0).min(100.0) } else { 100.0 };
+        let mut spans = vec![Span::styled(
+            format!(" 📌 {} ${} ", upgrade.name, format_number(cost.floor())),
+            Style::default().fg(Color::Magenta),
+        )];
+        if !unlocked {
+            let hint = match &upgrade.unlock_condition {
+                Some((kind, count)) => {
+                    let current = state.producers[kind.index()].count;
+                    format!("🔒 {} {}/{}台", kind.name(), current.min(*count), count)
+                }
+                None => "🔒 ミルク不足".to_string(),
+            };
+            spans.push(Span::styled(hint, Style::default().fg(Color::Red)));
+        } else if pct >= 100.0 {
+            spans.push(Span::styled(
+                "✔購入可",
+                Style::default().fg(Color::Green).add_modifier(Modifier::BOLD),
+            ));
+        } else {
+            spans.push(Span::styled(
+                format!("{:.0}%", pct),
+                Style::default().fg(Color::Cyan),
+            ));
+        }
+        cl.push_clickable(Line::from(spans), PINNED_UPGRADE.id(i));
+    }
+
     let visible_count = state.visible_producer_count();
+    // (list row, producer) for the ⬆Lv buttons overlaid after the list
+    let mut level_rows: Vec<(usize, &super::state::Producer)> = Vec::new();
 
     for (idx, p) in state.producers.iter().enumerate() {
         // Progressive disclosure: only show producers up to visible_count
@@ -968,7 +1126,10 @@ fn render_producers(
             Line::from(spans)
         };
 
-        cl.push_clickable(line, BUY_PRODUCER_BASE + p.kind.index() as u16);
+        if p.count > 0 {
+            level_rows.push((cl.len(), p));
+        }
+        cl.push_clickable(line, BUY_PRODUCER.id(p.kind.index()));
     }
 
     // Show teaser for next hidden producer tier
@@ -984,12 +1145,41 @@ fn render_producers(
         cl.push(teaser_line);
     }
 
+    // Purchase queue: mode toggle + pending entries with cancel buttons
+    let (mode_label, mode_style) = if state.queue_mode {
+        (
+            format!(" 📋 予約モード ON — タップで{}台予約 [b]", QUEUE_BATCH),
+            Style::default().fg(Color::Cyan).add_modifier(Modifier::BOLD),
+        )
+    } else {
+        (
+            " 📋 予約モード OFF [b]".to_string(),
+            Style::default().fg(Color::DarkGray),
+        )
+    };
+    cl.push_clickable(Line::from(Span::styled(mode_label, mode_style)), TOGGLE_QUEUE_MODE);
+    for (i, q) in state.purchase_queue.iter().enumerate() {
+        let cost = state.producers[q.kind.index()].cost() * state.total_cost_modifier();
+        let (marker, style) = if i == 0 {
+            ("▶", Style::default().fg(Color::Cyan))
+        } else {
+            (" ", Style::default().fg(Color::DarkGray))
+        };
+        cl.push_clickable(
+            Line::from(vec![
+                Span::styled(format!("   {} {} 残{}台 ${}", marker, q.kind.name(), q.remaining, format_number(cost.floor())), style),
+                Span::styled(" [✕取消]", Style::default().fg(Color::Red)),
+            ]),
+            CANCEL_QUEUE.id(i),
+        );
+    }
+
     let producer_border_color = if state.purchase_flash > 0 {
         Color::Yellow
     } else {
         Color::Green
     };
-    let title = if is_narrow { " 生産者 ◆=最高効率 " } else { " Producers ◆=最高効率 ★=回収速度 " };
+    let title = if is_narrow { " 生産者 ◆=最高効率 " } else { " Producers ◆=最高効率 ★=回収速度 長押し=詳細 " };
 
     // Register click targets (Borders::ALL → top=1, bottom=1)
     let mut cs = click_state.borrow_mut();
@@ -1004,6 +1194,176 @@ fn render_producers(
             .title(title),
     );
     f.render_widget(widget, area);
+
+    // ⬆Lv buttons at the right edge of owned rows (registered after the
+    // rows, so tapping them levels up instead of buying a unit)
+    const LEVEL_BUTTON_W: u16 = 6;
+    if area.width < LEVEL_BUTTON_W + 2 {
+        return;
+    }
+    let mut cs = click_state.borrow_mut();
+    for (row, p) in level_rows {
+        let y = area.y + 1 + row as u16;
+        if y + 1 >= area.y + area.height {
+            break;
+        }
+        let maxed = p.level >= MAX_PRODUCER_LEVEL;
+        let affordable = !maxed
+            && state.cookies >= p.level_up_cost() * state.total_cost_modifier();
+        let style = if maxed {
+            Style::default().fg(Color::Yellow)
+        } else if affordable {
+            Style::default().fg(Color::Cyan).add_modifier(Modifier::BOLD)
+        } else {
+            Style::default().fg(Color::DarkGray)
+        };
+        let label = if maxed { " ★Lv10".to_string() } else { format!(" ⬆Lv{:<2}", p.level) };
+        let rect = Rect::new(area.x + area.width - 1 - LEVEL_BUTTON_W, y, LEVEL_BUTTON_W, 1);
+        let button = Paragraph::new(Line::from(Span::styled(label, style)));
+        Clickable::new(button, LEVEL_UP_PRODUCER.id(p.kind.index())).render(f, rect, &mut cs);
+    }
+}
+
+/// Detail card for one producer, shown in place of the list after a long-press.
+fn render_producer_detail(
+    state: &CookieState,
+    kind: &ProducerKind,
+    f: &mut Frame,
+    area: Rect,
+    click_state: &Rc<RefCell<ClickState>>,
+) {
+    let p = &state.producers[kind.index()];
+    let bonus = state.synergy_bonus(kind) + state.count_scaling_bonus(kind);
+    let cps = p.cps_with_synergy(bonus);
+    let share = cps / state.total_cps().max(0.001) * 100.0;
+    let cost = p.cost() * state.total_cost_modifier();
+    let payback = match p.payback_seconds_with_synergy(bonus) {
+        Some(s) if s < 60.0 => format!("{}秒", s.round() as u32),
+        Some(s) if s < 3600.0 => format!("{}分", (s / 60.0).round() as u32),
+        Some(s) => format!("{}時間", (s / 3600.0).round() as u32),
+        None => "---".to_string(),
+    };
+    let locked_upgrades = state
+        .upgrades
+        .iter()
+        .filter(|u| !u.purchased && matches!(&u.unlock_condition, Some((k, _)) if k == kind))
+        .count();
+
+    let label = Style::default().fg(Color::Gray);
+    let value = Style::default().fg(Color::White).add_modifier(Modifier::BOLD);
+    let row = |name: &'static str, v: String| {
+        Line::from(vec![Span::styled(format!(" {:<10}", name), label), Span::styled(v, value)])
+    };
+
+    let mut cl = ClickableList::new();
+    cl.push(row("所有", format!("{}台", p.count)));
+    cl.push(row("生産", format!("{}/s (全体の{:.1}%)", format_number(cps), share)));
+    cl.push(row("1台あたり", format!("{}/s", format_number(p.next_unit_cps_with_synergy(bonus)))));
+    cl.push(row("ボーナス", format!("+{:.0}%", bonus * 100.0)));
+    cl.push(row("次の1台", format!("${} (回収{})", format_number(cost.floor()), payback)));
+    cl.push(row("レベル", format!("Lv{} (生産 +{:.0}%)", p.level, (p.level_multiplier() - 1.0) * 100.0)));
+    if locked_upgrades > 0 {
+        cl.push(row("関連強化", format!("{}個が台数待ち", locked_upgrades)));
+    }
+    cl.push(Line::from(""));
+    if p.level >= MAX_PRODUCER_LEVEL {
+        cl.push(Line::from(Span::styled(" ★ レベル最大", Style::default().fg(Color::Yellow))));
+    } else {
+        let level_cost = p.level_up_cost() * state.total_cost_modifier();
+        let style = if p.count > 0 && state.cookies >= level_cost {
+            Style::default().fg(Color::Cyan).add_modifier(Modifier::BOLD)
+        } else {
+            Style::default().fg(Color::DarkGray)
+        };
+        let hint = if p.count == 0 { " (1台以上必要)" } else { "" };
+        cl.push_clickable(
+            Line::from(Span::styled(
+                format!(
+                    " ⬆ Lv{}へ +{:.0}% ${}{} [l]",
+                    p.level + 1,
+                    PRODUCER_LEVEL_BONUS * 100.0,
+                    format_number(level_cost.floor()),
+                    hint
+                ),
+                style,
+            )),
+            LEVEL_UP_PRODUCER.id(kind.index()),
+        );
+    }
+    cl.push_clickable(
+        Line::from(Span::styled(" ◀ 一覧に戻る [q]", Style::default().fg(Color::Cyan))),
+        CLOSE_PRODUCER_DETAIL,
+    );
+
+    let mut cs = click_state.borrow_mut();
+    cl.register_targets(area, &mut cs, 1, 1, 0, 0);
+    drop(cs);
+
+    let items: Vec<ListItem> = cl.into_lines().into_iter().map(ListItem::new).collect();
+    let widget = List::new(items).block(
+        Block::default()
+            .borders(Borders::ALL)
+            .border_style(Style::default().fg(Color::Cyan))
+            .title(format!(" {} の詳細 ", kind.name())),
+    );
+    f.render_widget(widget, area);
+}
+
+/// CPS breakdown card: every step of the `total_cps` multiplier chain,
+/// shown in place of the producers list.
+fn render_cps_breakdown(
+    state: &CookieState,
+    f: &mut Frame,
+    area: Rect,
+    click_state: &Rc<RefCell<ClickState>>,
+) {
+    let b = state.cps_breakdown();
+    let label = Style::default().fg(Color::Gray);
+    let value = Style::default().fg(Color::White).add_modifier(Modifier::BOLD);
+    let row = |name: &str, v: String, style: Style| {
+        Line::from(vec![Span::styled(format!(" {:<12}", name), label), Span::styled(v, style)])
+    };
+
+    let mut cl = ClickableList::new();
+    cl.push(row("生産者", format!("{}/s", format_number(b.base)), value));
+    if b.percent_extra > 0.0 {
+        cl.push(row("CPS%強化", format!("+{}/s", format_number(b.percent_extra)), value));
+    }
+    for (name, factor) in b.factors() {
+        // Inactive steps (×1) are dimmed so the live ones stand out.
+        let style = if (factor - 1.0).abs() < 1e-9 {
+            Style::default().fg(Color::DarkGray)
+        } else if factor < 1.0 {
+            Style::default().fg(Color::Red).add_modifier(Modifier::BOLD)
+        } else {
+            Style::default().fg(Color::Green).add_modifier(Modifier::BOLD)
+        };
+        cl.push(row(name, format!("×{:.3}", factor), style));
+    }
+    cl.push(Line::from(Span::styled(" ────────────────────", Style::default().fg(Color::DarkGray))));
+    cl.push(row(
+        "合計",
+        format!("{}/s", format_number(b.total)),
+        Style::default().fg(Color::Yellow).add_modifier(Modifier::BOLD),
+    ));
+    cl.push(Line::from(""));
+    cl.push_clickable(
+        Line::from(Span::styled(" ◀ 一覧に戻る [q]", Style::default().fg(Color::Cyan))),
+        TOGGLE_CPS_BREAKDOWN,
+    );
+
+    let mut cs = click_state.borrow_mut();
+    cl.register_targets(area, &mut cs, 1, 1, 0, 0);
+    drop(cs);
+
+    let items: Vec<ListItem> = cl.into_lines().into_iter().map(ListItem::new).collect();
+    let widget = List::new(items).block(
+        Block::default()
+            .borders(Borders::ALL)
+            .border_style(Style::default().fg(Color::Cyan))
+            .title(" CPS の内訳 "),
+    );
+    f.render_widget(widget, area);
 }
 
 fn render_upgrades(
@@ -1022,10 +1382,27 @@ fn render_upgrades(
 
     let mut cl = ClickableList::new();
 
+    // Pin mode toggle: taps below pin instead of buying
+    if !available.is_empty() {
+        let (pin_label, pin_style) = if state.pin_mode {
+            (
+                format!(" 📌 ピン留めモード ON — タップで切替 ({}/{})", state.pinned_upgrades.len(), MAX_PINNED_UPGRADES),
+                Style::default().fg(Color::Magenta).add_modifier(Modifier::BOLD),
+            )
+        } else {
+            (
+                format!(" 📌 ピン留めモード OFF [Shift+キー] ({}/{})", state.pinned_upgrades.len(), MAX_PINNED_UPGRADES),
+                Style::default().fg(Color::DarkGray),
+            )
+        };
+        cl.push_clickable(Line::from(Span::styled(pin_label, pin_style)), TOGGLE_PIN_MODE);
+    }
+
     // === Upgrade items (all clickable) ===
-    for (i, (_, upgrade, unlocked)) in available.iter().enumerate() {
+    for (i, (real_idx, upgrade, unlocked)) in available.iter().enumerate() {
         let can_afford = state.cookies >= upgrade.cost && *unlocked;
         let cost_str = format_number(upgrade.cost);
+        let pin = if state.pinned_upgrades.contains(real_idx) { "📌" } else { "" };
 
         if *unlocked {
             // Check if this upgrade is blocked by exclusive group
@@ -1037,11 +1414,11 @@ fn render_upgrades(
                 // Another choice in the same group was already purchased
                 cl.push_clickable(Line::from(vec![
                     Span::styled(
-                        format!(" {} - {} ", upgrade.name, upgrade.description),
+                        format!(" {}{} - {} ", pin, upgrade.name, upgrade.description),
                         Style::default().fg(Color::DarkGray),
                     ),
                     Span::styled("⛔選択済", Style::default().fg(Color::Red)),
-                ]), BUY_UPGRADE_BASE + i as u16);
+                ]), BUY_UPGRADE.id(i));
             } else {
                 let text_style = if can_afford {
                     Style::default().fg(Color::White)
@@ -1050,7 +1427,7 @@ fn render_upgrades(
                 };
 
                 let mut spans = vec![Span::styled(
-                    format!(" {} - {} ({})", upgrade.name, upgrade.description, cost_str),
+                    format!(" {}{} - {} ({})", pin, upgrade.name, upgrade.description, cost_str),
                     text_style,
                 )];
 
@@ -1062,7 +1439,7 @@ fn render_upgrades(
                     ));
                 }
 
-                cl.push_clickable(Line::from(spans), BUY_UPGRADE_BASE + i as u16);
+                cl.push_clickable(Line::from(spans), BUY_UPGRADE.id(i));
             }
         } else {
             let hint = match &upgrade.unlock_condition {
@@ -1075,35 +1452,101 @@ fn render_upgrades(
 
             cl.push_clickable(Line::from(vec![
                 Span::styled(
-                    format!(" {} - {} ", upgrade.name, upgrade.description),
+                    format!(" {}{} - {} ", pin, upgrade.name, upgrade.description),
                     Style::default().fg(Color::DarkGray),
                 ),
                 Span::styled(hint, Style::default().fg(Color::Red)),
-            ]), BUY_UPGRADE_BASE + i as u16);
+            ]), BUY_UPGRADE.id(i));
         }
     }
 
-    // Register click targets (Borders::ALL → top=1, bottom=1)
+    if available.is_empty() {
+        cl.push(Line::from(Span::styled(
+            " (全て購入済み)",
+            Style::default().fg(Color::DarkGray),
+        )));
+    }
+
+    push_combo_skills(state, &mut cl);
+
+    // Long upgrade lists overflow phone screens: ▲▼ / PgUp・PgDn scroll.
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(Color::Magenta))
+        .title(" Upgrades ");
     let mut cs = click_state.borrow_mut();
-    cl.register_targets(area, &mut cs, 1, 1, 0, 0);
-    drop(cs);
+    cl.scrollable(&state.upgrades_scroll, UPGRADES_SCROLL_UP, UPGRADES_SCROLL_DOWN)
+        .block(block)
+        .arrow_color(Color::Magenta)
+        .render(f, area, &mut cs);
+}
 
-    let items: Vec<ListItem> = if cl.len() == 0 {
-        vec![ListItem::new(Span::styled(
-            " (全て購入済み)",
+/// Combo skill tree section of the upgrades tab ([1]-[6] or tap to learn).
+fn push_combo_skills(state: &CookieState, cl: &mut ClickableList) {
+    cl.push(Line::from(""));
+    cl.push(Line::from(vec![
+        Span::styled(
+            " ⚡ 連撃スキル",
+            Style::default().fg(Color::Yellow).add_modifier(Modifier::BOLD),
+        ),
+        Span::styled(
+            format!(
+                "  会心 ×{:.0} / {:.0}%・猶予 {:.1}秒",
+                state.critical_multiplier(),
+                state.critical_chance() * 100.0,
+                state.combo_window() as f64 / 10.0
+            ),
             Style::default().fg(Color::DarkGray),
-        ))]
-    } else {
-        cl.into_lines().into_iter().map(ListItem::new).collect()
-    };
+        ),
+    ]));
 
-    let widget = List::new(items).block(
-        Block::default()
-            .borders(Borders::ALL)
-            .border_style(Style::default().fg(Color::Magenta))
-            .title(" Upgrades "),
-    );
-    f.render_widget(widget, area);
+    for (i, skill) in ComboSkill::all().iter().enumerate() {
+        // Depth in the tree: root, its children, grandchildren
+        let branch = match skill.requires() {
+            None => " ",
+            Some(parent) if parent.requires().is_none() => "  └",
+            Some(_) => "     └",
+        };
+        let line = if state.has_combo_skill(*skill) {
+            Line::from(vec![
+                Span::styled(branch, Style::default().fg(Color::DarkGray)),
+                Span::styled(
+                    format!("✅ {} - {}", skill.name(), skill.description()),
+                    Style::default().fg(Color::Green),
+                ),
+            ])
+        } else if state.combo_skill_unlocked(*skill) {
+            let style = if state.cookies >= skill.cost() {
+                Style::default().fg(Color::White)
+            } else {
+                Style::default().fg(Color::DarkGray)
+            };
+            Line::from(vec![
+                Span::styled(branch, Style::default().fg(Color::DarkGray)),
+                Span::styled(
+                    format!(
+                        "[{}] {} - {} ({})",
+                        i + 1,
+                        skill.name(),
+                        skill.description(),
+                        format_number(skill.cost())
+                    ),
+                    style,
+                ),
+            ])
+        } else {
+            let parent = skill.requires().map(|p| p.name()).unwrap_or("");
+            Line::from(vec![
+                Span::styled(branch, Style::default().fg(Color::DarkGray)),
+                Span::styled(
+                    format!("[{}] {} - {} ", i + 1, skill.name(), skill.description()),
+                    Style::default().fg(Color::DarkGray),
+                ),
+                Span::styled(format!("🔒 {}が必要", parent), Style::default().fg(Color::Red)),
+            ])
+        };
+        cl.push_clickable(line, BUY_COMBO_SKILL.id(i));
+    }
 }
 
 fn render_research(
@@ -1114,100 +1557,185 @@ fn render_research(
 ) {
     use super::state::ResearchPath;
 
-    let path_name = match &state.research_path {
-        ResearchPath::None => "未選択",
-        ResearchPath::MassProduction => "量産路線",
-        ResearchPath::Quality => "品質路線",
-    };
-
-    let mut cl = ClickableList::new();
-    let mut key_idx: u8 = 0;
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(Color::Cyan))
+        .title(" Research ");
+    let inner = block.inner(area);
+    f.render_widget(block, area);
 
-    // Header showing current path (not clickable)
-    cl.push(Line::from(Span::styled(
-        format!(" 🔬 研究パス: {}", path_name),
-        Style::default()
-            .fg(Color::Cyan)
-            .add_modifier(Modifier::BOLD),
-    )));
+    let rows = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([Constraint::Length(1), Constraint::Min(0)])
+        .split(inner);
+    let header = if state.research_path == ResearchPath::None {
+        " 🔬 研究パス: 未選択 — 選べるのはどちらか一方".to_string()
+    } else {
+        format!(" 🔬 研究パス: {}", state.research_path.name())
+    };
+    f.render_widget(
+        Paragraph::new(Line::from(Span::styled(
+            header,
+            Style::default()
+                .fg(Color::Cyan)
+                .add_modifier(Modifier::BOLD),
+        ))),
+        rows[0],
+    );
 
+    // One column per path; each node is a tier on its path's trunk.
+    // Key letters / click IDs follow state.visible_research() (ARCHITECTURE.md Rule 2).
+    let visible = state.visible_research();
     let max_tier = state.research_max_tier();
-    // NOTE: key_idx below enumerates the same set as state.visible_research()
-    // (ARCHITECTURE.md Rule 2). If this filter changes, update visible_research() too.
-    for node in &state.research_nodes {
-        // Skip nodes from the wrong path (if path already chosen)
-        if state.research_path != ResearchPath::None && node.path != state.research_path {
-            continue;
-        }
-        if node.purchased {
-            cl.push(Line::from(vec![
-                Span::styled("     ", Style::default()),
-                Span::styled(
-                    format!("✅ {} - {}", node.name, node.description),
-                    Style::default().fg(Color::Green),
-                ),
-            ]));
-            continue;
-        }
-
-        let can_buy_tier = node.tier <= max_tier + 1;
-        let can_afford = state.cookies >= node.cost && can_buy_tier;
-
-        let path_icon = match &node.path {
-            ResearchPath::MassProduction => "⚙",
-            ResearchPath::Quality => "💎",
-            ResearchPath::None => "",
-        };
-
-        let action_id = BUY_RESEARCH_BASE + key_idx as u16;
-        key_idx += 1;
+    let cols = Layout::default()
+        .direction(Direction::Horizontal)
+        .constraints([Constraint::Percentage(50), Constraint::Percentage(50)])
+        .split(rows[1]);
+    let mut cs = click_state.borrow_mut();
+    for (col, path) in cols.iter().zip([ResearchPath::MassProduction, ResearchPath::Quality]) {
+        let excluded = state.research_path != ResearchPath::None && state.research_path != path;
+        let mut cl = ClickableList::new();
+        cl.push(Line::from(vec![
+            Span::styled(
+                format!(" {} {}", path.icon(), path.name()),
+                if excluded {
+                    Style::default().fg(Color::DarkGray)
+                } else {
+                    Style::default().fg(Color::Yellow).add_modifier(Modifier::BOLD)
+                },
+            ),
+            Span::styled(
+                if excluded { " 🚫" } else { "" },
+                Style::default().fg(Color::DarkGray),
+            ),
+        ]));
 
-        if can_buy_tier {
-            let text_style = if can_afford {
-                Style::default().fg(Color::White)
+        let nodes: Vec<(usize, &super::state::ResearchNode)> = state
+            .research_nodes
+            .iter()
+            .enumerate()
+            .filter(|(_, n)| n.path == path)
+            .collect();
+        for (pos, &(real_idx, node)) in nodes.iter().enumerate() {
+            let (branch, stem) = if pos + 1 == nodes.len() {
+                (" └─", "   ")
+            } else {
+                (" ├─", " │ ")
+            };
+            let prereq_met = node.tier <= max_tier + 1;
+            let affordable = state.cookies >= node.cost;
+            let (mark, name_style) = if node.purchased {
+                ("✅", Style::default().fg(Color::Green))
+            } else if excluded {
+                ("🚫", Style::default().fg(Color::DarkGray))
+            } else if !prereq_met {
+                ("🔒", Style::default().fg(Color::DarkGray))
+            } else if affordable {
+                ("▶", Style::default().fg(Color::White).add_modifier(Modifier::BOLD))
             } else {
+                ("・", Style::default().fg(Color::DarkGray))
+            };
+            let trunk_style = if node.purchased {
+                Style::default().fg(Color::Green)
+            } else if excluded || !prereq_met {
                 Style::default().fg(Color::DarkGray)
+            } else {
+                Style::default().fg(Color::Cyan)
             };
-
-            cl.push_clickable(Line::from(vec![
-                Span::styled(
-                    format!(
-                        " {} T{}: {} - {} ({})",
-                        path_icon,
-                        node.tier,
-                        node.name,
-                        node.description,
-                        format_number(node.cost)
-                    ),
-                    text_style,
-                ),
-            ]), action_id);
-        } else {
-            cl.push_clickable(Line::from(vec![
+            let key = visible.iter().position(|&i| i == real_idx);
+            let key_label = match key {
+                Some(k) if k < 26 => format!(" [{}]", (b'a' + k as u8) as char),
+                _ => String::new(),
+            };
+            let title = Line::from(vec![
+                Span::styled(branch, trunk_style),
+                Span::styled(format!("{} T{} {}", mark, node.tier, node.name), name_style),
+                Span::styled(key_label, Style::default().fg(Color::DarkGray)),
+            ]);
+            let detail = if node.purchased || excluded {
+                node.description.clone()
+            } else if !prereq_met {
+                format!("T{} の研究が必要", node.tier - 1)
+            } else {
+                format!("{} ({})", node.description, format_number(node.cost))
+            };
+            let detail = Line::from(vec![
+                Span::styled(stem, trunk_style),
                 Span::styled(
-                    format!(
-                        " {} T{}: {} 🔒 前段階の研究が必要",
-                        path_icon, node.tier, node.name
-                    ),
-                    Style::default().fg(Color::DarkGray),
+                    format!("  {}", detail),
+                    if affordable && prereq_met && !excluded && !node.purchased {
+                        Style::default().fg(Color::Gray)
+                    } else {
+                        Style::default().fg(Color::DarkGray)
+                    },
                 ),
-            ]), action_id);
+            ]);
+            match key {
+                Some(k) => {
+                    cl.push_clickable(title, BUY_RESEARCH.id(k));
+                    cl.push_clickable(detail, BUY_RESEARCH.id(k));
+                }
+                None => {
+                    cl.push(title);
+                    cl.push(detail);
+                }
+            }
         }
+        cl.render(f, *col, Block::default(), &mut cs, false, 0);
     }
+}
 
-    // Register click targets before consuming lines (Borders::ALL → top=1, bottom=1)
+/// Confirm dialog for the first research purchase (it picks the path for the run).
+fn render_research_confirm(
+    state: &CookieState,
+    index: usize,
+    f: &mut Frame,
+    area: Rect,
+    click_state: &Rc<RefCell<ClickState>>,
+) {
+    let Some(node) = state.research_nodes.get(index) else {
+        return;
+    };
+    let path = &node.path;
+    let body = vec![
+        Line::from(vec![
+            Span::styled(
+                format!(" {} {}", path.icon(), path.name()),
+                Style::default().fg(Color::Yellow).add_modifier(Modifier::BOLD),
+            ),
+            Span::styled(" で研究を始めますか？", Style::default().fg(Color::White)),
+        ]),
+        Line::from(Span::styled(
+            format!(" 「{}」 {} 枚", node.name, format_number(node.cost)),
+            Style::default().fg(Color::White),
+        )),
+        Line::from(""),
+        Line::from(Span::styled(
+            format!(" {} {} は転生するまで", path.rival().icon(), path.rival().name()),
+            Style::default().fg(Color::DarkGray),
+        )),
+        Line::from(Span::styled(
+            " 研究できなくなります",
+            Style::default().fg(Color::DarkGray),
+        )),
+    ];
     let mut cs = click_state.borrow_mut();
-    cl.register_targets(area, &mut cs, 1, 1, 0, 0);
-    drop(cs);
-
-    let items: Vec<ListItem> = cl.into_lines().into_iter().map(ListItem::new).collect();
-    let widget = List::new(items).block(
-        Block::default()
-            .borders(Borders::ALL)
-            .border_style(Style::default().fg(Color::Cyan))
-            .title(" Research "),
-    );
-    f.render_widget(widget, area);
+    Modal::new(" 研究パスの選択 ")
+        .border_color(Color::Cyan)
+        .body(body)
+        .button(
+            Span::styled(
+                "この路線に決める [y]",
+                Style::default().fg(Color::Cyan).add_modifier(Modifier::BOLD),
+            ),
+            CONFIRM_RESEARCH_PATH,
+        )
+        .button(
+            Span::styled("やめておく [n]", Style::default().fg(Color::Gray)),
+            CANCEL_RESEARCH_PATH,
+        )
+        .backdrop(CANCEL_RESEARCH_PATH)
+        .render(f, area, &mut cs);
 }
 
 fn render_milestones(
@@ -1222,11 +1750,15 @@ fn render_milestones(
     let ready = state.ready_milestone_count();
     let total = state.milestones.len();
 
-    // Milk bar
-    let milk_pct = state.milk * 100.0;
+    // Milk bar — drawn from `milk_display` so a claim visibly fills it;
+    // the not-yet-filled part of the new level shows as a pending segment.
+    let milk_pct = state.milk_display * 100.0;
     let bar_width = 20usize;
-    let filled = ((state.milk * bar_width as f64).round() as usize).min(bar_width);
-    let milk_bar: String = "█".repeat(filled) + &"░".repeat(bar_width - filled);
+    let filled = ((state.milk_display * bar_width as f64).round() as usize).min(bar_width);
+    let target = ((state.milk * bar_width as f64).round() as usize).clamp(filled, bar_width);
+    let milk_bar: String = "█".repeat(filled);
+    let milk_pending: String = "▓".repeat(target - filled);
+    let milk_rest: String = "░".repeat(bar_width - target);
 
     let mut cl = ClickableList::new();
 
@@ -1242,6 +1774,14 @@ fn render_milestones(
             milk_bar,
             Style::default().fg(Color::White),
         ),
+        Span::styled(
+            milk_pending,
+            Style::default().fg(Color::LightCyan).add_modifier(Modifier::BOLD),
+        ),
+        Span::styled(
+            milk_rest,
+            Style::default().fg(Color::White),
+        ),
         Span::styled(
             format!("  🐱×{:.2}", state.kitten_multiplier),
             Style::default()
@@ -1292,7 +1832,11 @@ fn render_milestones(
                 format!(" - {}", milestone.description),
                 Style::default().fg(Color::Green),
             ),
-        ]), CLAIM_MILESTONE_BASE + i as u16);
+            Span::styled(
+                format!(" 🎁{}", milestone.reward.label()),
+                Style::default().fg(Color::Yellow),
+            ),
+        ]), CLAIM_MILESTONE.id(i));
     }
 
     // === Locked milestones (show next few goals — not clickable) ===
@@ -1315,6 +1859,10 @@ fn render_milestones(
                 format!(" - {}", milestone.description),
                 Style::default().fg(Color::DarkGray),
             ),
+            Span::styled(
+                format!(" 🎁{}", milestone.reward.label()),
+                Style::default().fg(Color::DarkGray),
+            ),
         ]));
     }
     let locked_remaining = locked_milestones.len().saturating_sub(locked_show);
@@ -1351,6 +1899,21 @@ fn render_milestones(
         ]));
     }
 
+    // === Cookie art switch (only once an art has been unlocked) ===
+    let arts = state.unlocked_cookie_arts();
+    if arts.len() > 1 {
+        cl.push_clickable(Line::from(vec![
+            Span::styled(
+                format!(" 🎨 クッキー: {}", state.cookie_art.name()),
+                Style::default().fg(Color::White),
+            ),
+            Span::styled(
+                format!(" ({}/{}) ▶切替 [@]", state.cookie_art.index() + 1, CookieArt::all().len()),
+                Style::default().fg(Color::Cyan),
+            ),
+        ]), CYCLE_COOKIE_ART);
+    }
+
     // === Active effects summary (not clickable) ===
     cl.push(Line::from(Span::styled(
         " ─── 発動中の効果 ────────────────",
@@ -1400,20 +1963,8 @@ fn render_milestones(
     }
 
     // Active buffs
-    for buff in &state.active_buffs {
-        let (label, color) = match &buff.effect {
-            super::state::GoldenEffect::ProductionFrenzy { multiplier } => {
-                (format!("🌟 生産フレンジー ×{:.0} (残{}t)", multiplier, buff.ticks_left), Color::Magenta)
-            }
-            super::state::GoldenEffect::ClickFrenzy { multiplier } => {
-                (format!("👆 クリックフレンジー ×{:.0} (残{}t)", multiplier, buff.ticks_left), Color::Cyan)
-            }
-            super::state::GoldenEffect::InstantBonus { .. } => continue,
-        };
-        cl.push(Line::from(Span::styled(
-            format!(" {}", label),
-            Style::default().fg(color).add_modifier(Modifier::BOLD),
-        )));
+    for summary in state.buff_summary() {
+        cl.push(buff_bar_line(&summary, false));
     }
 
     // Discount
@@ -1448,8 +1999,196 @@ fn render_milestones(
             claimed, total
         ));
 
+    {
+        let mut cs = click_state.borrow_mut();
+        cl.scrollable(&state.milestones_scroll, MILESTONES_SCROLL_UP, MILESTONES_SCROLL_DOWN)
+            .block(block)
+            .wrap(true)
+            .arrow_color(border_color)
+            .render(f, area, &mut cs);
+    }
+
+    // Milk splash renders on top of the panel
+    render_particles(state, f, area, &ParticlePanel::Milestones);
+}
+
+/// Display width of one garden plot (`" 🌱42"` + gap).
+const GARDEN_CELL_W: u16 = 6;
+
+fn render_garden(
+    state: &CookieState,
+    f: &mut Frame,
+    area: Rect,
+    click_state: &Rc<RefCell<ClickState>>,
+) {
+    let seeds = SeedKind::all();
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Length(seeds.len() as u16 + 1), // seed picker (top border)
+            Constraint::Min(3),                          // plots
+        ])
+        .split(area);
+
+    // === Seed picker ===
+    {
+        let cps = state.total_cps();
+        let mut cl = ClickableList::new();
+        for (i, seed) in seeds.iter().enumerate() {
+            let selected = *seed == state.selected_seed;
+            let cost = seed.cost(cps);
+            let name_style = if selected {
+                Style::default().fg(Color::LightGreen).add_modifier(Modifier::BOLD)
+            } else {
+                Style::default().fg(Color::White)
+            };
+            let cost_color = if state.cookies >= cost { Color::Yellow } else { Color::DarkGray };
+            cl.push_clickable(Line::from(vec![
+                Span::styled(
+                    format!("{}[{}] {} {} ", if selected { "▶" } else { " " }, i + 1, seed.glyph(), seed.name()),
+                    name_style,
+                ),
+                Span::styled(format!("{} ", format_number(cost)), Style::default().fg(cost_color)),
+                Span::styled(seed.description(), Style::default().fg(Color::DarkGray)),
+            ]), GARDEN_SEED.id(i));
+        }
+        let block = Block::default()
+            .borders(Borders::TOP | Borders::LEFT | Borders::RIGHT)
+            .border_style(Style::default().fg(Color::LightGreen))
+            .title(format!(
+                " 菜園 成長×{:.2} ({}{}) ",
+                state.garden_growth_rate(),
+                state.market_phase.symbol(),
+                state.market_phase.name()
+            ));
+        let mut cs = click_state.borrow_mut();
+        cl.render(f, chunks[0], block, &mut cs, false, 0);
+    }
+
+    // === Plots (tap: plant on empty / harvest when mature) ===
+    let mut lines: Vec<Line> = Vec::new();
+    for row in 0..GARDEN_H {
+        let mut spans = vec![Span::raw(" ")];
+        for col in 0..GARDEN_W {
+            spans.push(match &state.garden[row * GARDEN_W + col] {
+                None => Span::styled("  ・  ", Style::default().fg(Color::DarkGray)),
+                Some(plant) if plant.is_mature() => Span::styled(
+                    format!(" {} ✔ ", plant.seed.glyph()),
+                    Style::default().fg(Color::Yellow).add_modifier(Modifier::BOLD),
+                ),
+                Some(plant) => Span::styled(
+                    format!(" 🌱{:>2} ", (plant.progress() * 100.0) as u32),
+                    Style::default().fg(Color::Green),
+                ),
+            });
+        }
+        lines.push(Line::from(spans));
+        lines.push(Line::from(""));
+    }
+    lines.push(Line::from(Span::styled(
+        " 空き区画をタップで植える / ✔ で収穫",
+        Style::default().fg(Color::DarkGray),
+    )));
+
+    let block = Block::default()
+        .borders(Borders::BOTTOM | Borders::LEFT | Borders::RIGHT)
+        .border_style(Style::default().fg(Color::LightGreen));
+    let grid = ClickableGrid::new(GARDEN_W, GARDEN_H, GARDEN_PLOT.base(), GARDEN_CELL_W).with_cell_height(2);
+    let mut cs = click_state.borrow_mut();
+    grid.register_targets(chunks[1], &block, &mut cs, 1); // padding_left=1 for leading space
+    f.render_widget(Paragraph::new(lines).block(block), chunks[1]);
+}
+
+fn render_futures(
+    state: &CookieState,
+    f: &mut Frame,
+    area: Rect,
+    click_state: &Rc<RefCell<ClickState>>,
+) {
+    let futures = &state.futures;
+    let prev = futures
+        .history
+        .len()
+        .checked_sub(2)
+        .map_or(futures.price, |i| futures.history[i]);
+    let change = (futures.price / prev - 1.0) * 100.0;
+    let (arrow, change_color) = if change > 0.0 {
+        ("▲", Color::Green)
+    } else if change < 0.0 {
+        ("▼", Color::Red)
+    } else {
+        ("―", Color::DarkGray)
+    };
+    let chart_w = (area.width as usize).saturating_sub(4).clamp(6, 40);
+
+    let mut cl = ClickableList::new();
+    cl.push(Line::from(vec![
+        Span::styled(" 🍪FUT ", Style::default().fg(Color::White)),
+        Span::styled(
+            format!("×{:.3} ", futures.price),
+            Style::default().fg(Color::LightYellow).add_modifier(Modifier::BOLD),
+        ),
+        Span::styled(format!("{} {:+.1}%", arrow, change), Style::default().fg(change_color)),
+    ]));
+    cl.push(Line::from(Span::styled(
+        format!(" {}", build_range_sparkline(&futures.history, chart_w)),
+        Style::default().fg(change_color),
+    )));
+    cl.push(Line::from(""));
+    if futures.has_position() {
+        let profit = futures.profit();
+        let profit_color = if profit >= 0.0 { Color::Green } else { Color::Red };
+        cl.push(Line::from(vec![
+            Span::styled(
+                format!(" 建玉 {} ", format_number(futures.position_value())),
+                Style::default().fg(Color::White),
+            ),
+            Span::styled(
+                format!("(元本 {}) ", format_number(futures.cost_basis)),
+                Style::default().fg(Color::DarkGray),
+            ),
+            Span::styled(
+                format!(
+                    "{}{} ({:+.1}%)",
+                    if profit >= 0.0 { "+" } else { "-" },
+                    format_number(profit.abs()),
+                    profit / futures.cost_basis * 100.0
+                ),
+                Style::default().fg(profit_color).add_modifier(Modifier::BOLD),
+            ),
+        ]));
+    } else {
+        cl.push(Line::from(Span::styled(" 建玉なし", Style::default().fg(Color::DarkGray))));
+    }
+    cl.push(Line::from(""));
+
+    for (i, fraction) in FUTURES_BUY_FRACTIONS.iter().enumerate() {
+        let amount = (state.cookies * fraction).floor();
+        let color = if amount >= 1.0 { Color::Yellow } else { Color::DarkGray };
+        cl.push_clickable(Line::from(vec![
+            Span::styled(
+                format!(" [{}] {:.0}% 購入 ", i + 1, fraction * 100.0),
+                Style::default().fg(color),
+            ),
+            Span::styled(format_number(amount), Style::default().fg(Color::DarkGray)),
+        ]), FUTURES_BUY.id(i));
+    }
+    let sell_color = if futures.has_position() { Color::Cyan } else { Color::DarkGray };
+    cl.push_clickable(
+        Line::from(Span::styled(" [s] 全て決済", Style::default().fg(sell_color))),
+        FUTURES_SELL,
+    );
+    cl.push(Line::from(Span::styled(
+        " 転生すると建玉は消えます",
+        Style::default().fg(Color::DarkGray),
+    )));
+
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(Color::LightYellow))
+        .title(" クッキー先物 ");
     let mut cs = click_state.borrow_mut();
-    cl.render(f, area, block, &mut cs, true, 0);
+    cl.render(f, area, block, &mut cs, false, 0);
 }
 
 fn render_prestige(
@@ -1488,20 +2227,14 @@ fn render_prestige(
 
     // === Sub-section tab selector (1 row, horizontal) ===
     {
-        let sec_style = |idx: u8, color: Color| -> Style {
-            if idx == section {
-                Style::default().fg(Color::Black).bg(color).add_modifier(Modifier::BOLD)
-            } else {
-                Style::default().fg(color)
-            }
-        };
-
         let mut cs = click_state.borrow_mut();
         TabBar::new("|")
-            .tab("転生UP", sec_style(0, Color::Yellow), PRESTIGE_SEC_UPGRADES)
-            .tab("ブースト", sec_style(1, Color::Rgb(255, 182, 193)), PRESTIGE_SEC_BOOSTS)
-            .tab("ドラゴン", sec_style(2, Color::Red), PRESTIGE_SEC_DRAGON)
-            .tab("統計", sec_style(3, Color::White), PRESTIGE_SEC_STATS)
+            .colored("転生UP", Color::Yellow, PRESTIGE_SEC_UPGRADES)
+            .colored("ブースト", Color::Rgb(255, 182, 193), PRESTIGE_SEC_BOOSTS)
+            .colored("ドラゴン", Color::Red, PRESTIGE_SEC_DRAGON)
+            .colored("統計", Color::White, PRESTIGE_SEC_STATS)
+            .colored("挑戦", Color::LightRed, PRESTIGE_SEC_CHALLENGES)
+            .active(PRESTIGE_SEC_UPGRADES + section as u16)
             .block(
                 Block::default()
                     .borders(Borders::TOP | Borders::LEFT | Borders::RIGHT)
@@ -1536,6 +2269,13 @@ fn render_prestige(
                 format!("CPS×{:.2}", state.prestige_multiplier),
                 Style::default().fg(Color::Magenta).add_modifier(Modifier::BOLD),
             ),
+            Span::styled(
+                state
+                    .active_challenge
+                    .map(|c| format!(" ⚔{}", c.name()))
+                    .unwrap_or_default(),
+                Style::default().fg(Color::LightRed),
+            ),
         ]));
 
         // Row 1: reset button or hint
@@ -1573,7 +2313,8 @@ fn render_prestige(
         0 => render_prestige_upgrades(state, f, content_area, click_state, available, border_color, scroll),
         1 => render_prestige_boosts(state, f, content_area, click_state, border_color, scroll),
         2 => render_prestige_dragon(state, f, content_area, click_state, border_color, scroll),
-        3 => render_prestige_stats(state, f, content_area, border_color, scroll),
+        3 => render_prestige_stats(state, f, content_area, click_state, border_color, scroll),
+        4 => render_prestige_challenges(state, f, content_area, click_state, pending, border_color, scroll),
         _ => render_prestige_upgrades(state, f, content_area, click_state, available, border_color, scroll),
     };
 
@@ -1659,7 +2400,7 @@ fn render_prestige_upgrades(
         )));
 
         for (i, upgrade) in path_upgrades {
-            let action_id = BUY_PRESTIGE_UPGRADE_BASE + i as u16;
+            let action_id = BUY_PRESTIGE_UPGRADE.id(i);
 
             if upgrade.purchased {
                 cl.push_clickable(
@@ -1773,7 +2514,6 @@ fn render_prestige_boosts(
 
     // Active boost status (not clickable)
     if let Some(ref boost) = state.active_sugar_boost {
-        let secs_left = boost.ticks_left as f64 / 10.0;
         cl.push(Line::from(vec![
             Span::styled(
                 format!(" ⚡ {} 発動中！ ", boost.kind.name()),
@@ -1783,12 +2523,12 @@ fn render_prestige_boosts(
             ),
             Span::styled(
                 format!(
-                    "CPS×{:.1} 残り{:.1}秒",
-                    boost.kind.multiplier() * state.prestige_sugar_boost_multiplier(),
-                    secs_left
+                    "CPS×{:.1} 残り",
+                    boost.kind.multiplier() * state.prestige_sugar_boost_multiplier()
                 ),
                 Style::default().fg(Color::Yellow),
             ),
+            CountdownChip::new(boost.ticks_left).color(Color::Yellow).span(),
         ]));
     }
 
@@ -1924,7 +2664,7 @@ fn render_prestige_dragon(
 ) -> u16 {
     let mut cl = ClickableList::new();
 
-    if state.dragon_level >= 7 {
+    if state.dragon_level >= MAX_DRAGON_LEVEL {
         cl.push(Line::from(Span::styled(
             " 🐉 ドラゴン Lv.MAX！",
             Style::default().fg(Color::Red).add_modifier(Modifier::BOLD),
@@ -1963,7 +2703,7 @@ fn render_prestige_dragon(
                 cl.push_clickable(Line::from(Span::styled(
                     format!("   ▶{} ({}台)", p.kind.name(), p.count),
                     Style::default().fg(Color::Red),
-                )), DRAGON_FEED_BASE + p.kind.index() as u16);
+                )), DRAGON_FEED.id(p.kind.index()));
             }
         }
     }
@@ -1994,20 +2734,89 @@ fn render_prestige_dragon(
             format!("    {}", state.dragon_aura.description()),
             Style::default().fg(Color::Magenta),
         )));
+        let dual = state.dragon_level >= DRAGON_DUAL_AURA_LEVEL;
+        if dual {
+            cl.push_clickable(
+                Line::from(vec![
+                    Span::styled(
+                        format!(" ▶第2: {}", state.dragon_aura2.name()),
+                        Style::default()
+                            .fg(Color::LightMagenta)
+                            .add_modifier(Modifier::BOLD),
+                    ),
+                    Span::styled(" ▶切替 [D]", Style::default().fg(Color::DarkGray)),
+                ]),
+                DRAGON_CYCLE_AURA2,
+            );
+            cl.push(Line::from(Span::styled(
+                format!("    {}", state.dragon_aura2.description()),
+                Style::default().fg(Color::LightMagenta),
+            )));
+        } else {
+            cl.push(Line::from(Span::styled(
+                format!(" 🔒 第2オーラ: Lv.{}で解放", DRAGON_DUAL_AURA_LEVEL),
+                Style::default().fg(Color::DarkGray),
+            )));
+        }
         // All aura options (not clickable, one per line)
         for aura in super::state::DragonAura::all().iter() {
-            let is_active = *aura == state.dragon_aura;
-            let marker = if is_active { "●" } else { "○" };
-            let color = if is_active {
-                Color::Magenta
+            let (marker, color) = if *aura == state.dragon_aura {
+                ("●", Color::Magenta)
+            } else if dual && *aura == state.dragon_aura2 {
+                ("◎", Color::LightMagenta)
             } else {
-                Color::DarkGray
+                ("○", Color::DarkGray)
             };
             cl.push(Line::from(Span::styled(
                 format!("   {}{}", marker, aura.name()),
                 Style::default().fg(color),
             )));
         }
+
+        // Abilities (fire on golden cookie claims)
+        cl.push(Line::from(Span::styled(
+            " ─── ⚔ 能力 ───────────────",
+            Style::default().fg(Color::Red).add_modifier(Modifier::BOLD),
+        )));
+        for ability in DragonAbility::all() {
+            let line = if state.dragon_level >= ability.unlock_level() {
+                Line::from(vec![
+                    Span::styled(
+                        format!("   ✔{} ", ability.name()),
+                        Style::default().fg(Color::Red),
+                    ),
+                    Span::styled(ability.description(), Style::default().fg(Color::DarkGray)),
+                ])
+            } else {
+                Line::from(Span::styled(
+                    format!("   🔒{} (Lv.{})", ability.name(), ability.unlock_level()),
+                    Style::default().fg(Color::DarkGray),
+                ))
+            };
+            cl.push(line);
+        }
+
+        // Dragon hunt
+        if let Some(left) = state.dragon_hunt {
+            cl.push(Line::from(Span::styled(
+                format!(" ─── 🏹 ドラゴン狩り (残り{}秒) ───", left.div_ceil(10)),
+                Style::default().fg(Color::Yellow).add_modifier(Modifier::BOLD),
+            )));
+            for (i, choice) in HuntChoice::all().iter().enumerate() {
+                cl.push_clickable(Line::from(vec![
+                    Span::styled(
+                        format!("   [{}] {} ", choice.key(), choice.name()),
+                        Style::default().fg(Color::Yellow),
+                    ),
+                    Span::styled(choice.description(), Style::default().fg(Color::DarkGray)),
+                ]), DRAGON_HUNT.id(i));
+            }
+        } else {
+            cl.push(Line::from(Span::styled(
+                format!(" 🏹 次の狩りまで 約{}秒", state.dragon_hunt_next / 10),
+                Style::default().fg(Color::DarkGray),
+            )));
+        }
     } else {
         cl.push(Line::from(Span::styled(
             " 🔒 ドラゴンはまだ目覚めていません",
@@ -2032,6 +2841,7 @@ fn render_prestige_stats(
     state: &CookieState,
     f: &mut Frame,
     area: Rect,
+    click_state: &Rc<RefCell<ClickState>>,
     border_color: Color,
     scroll: u16,
 ) -> u16 {
@@ -2040,7 +2850,8 @@ fn render_prestige_stats(
     let minutes = (play_seconds % 3600) / 60;
     let secs = play_seconds % 60;
 
-    let lines = vec![
+    let mut cl = ClickableList::new();
+    for line in [
         Line::from(Span::styled(
             format!(" ⏱ プレイ時間: {}h {}m {}s", hours, minutes, secs),
             Style::default().fg(Color::White),
@@ -2073,19 +2884,125 @@ fn render_prestige_stats(
             format!(" 🍬 砂糖(累計): {}", state.sugar_all_time),
             Style::default().fg(Color::Rgb(255, 182, 193)),
         )),
-    ];
+    ] {
+        cl.push(line);
+    }
+
+    // Settings (clickable)
+    cl.push(Line::from(""));
+    cl.push(Line::from(Span::styled(
+        " ─── ⚙ 設定 ─────────────────────",
+        Style::default().fg(Color::DarkGray),
+    )));
+    cl.push_clickable(Line::from(vec![
+        Span::styled(" ✨ 演出: ", Style::default().fg(Color::White)),
+        Span::styled(
+            format!("[{}] ", state.animation_intensity.label()),
+            Style::default().fg(Color::Yellow).add_modifier(Modifier::BOLD),
+        ),
+        Span::styled(
+            format!("(パーティクル上限 {}) [V]", state.animation_intensity.particle_cap()),
+            Style::default().fg(Color::DarkGray),
+        ),
+    ]), CYCLE_ANIMATION_INTENSITY);
+
+    let block = Block::default()
+        .borders(Borders::BOTTOM | Borders::LEFT | Borders::RIGHT)
+        .border_style(Style::default().fg(border_color));
+    let inner_width = block.inner(area).width;
+    let total_lines = estimate_wrapped_lines(cl.lines(), inner_width);
+
+    let mut cs = click_state.borrow_mut();
+    cl.render(f, area, block, &mut cs, true, scroll);
+
+    total_lines
+}
+
+/// Prestige sub-section: challenge runs
+fn render_prestige_challenges(
+    state: &CookieState,
+    f: &mut Frame,
+    area: Rect,
+    click_state: &Rc<RefCell<ClickState>>,
+    pending: u64,
+    border_color: Color,
+    scroll: u16,
+) -> u16 {
+    let mut cl = ClickableList::new();
+
+    if let Some(kind) = state.active_challenge {
+        let ratio = (state.cookies_all_time / kind.goal()).min(1.0);
+        cl.push(Line::from(Span::styled(
+            format!(" ⚔ 挑戦中: {} ({})", kind.name(), kind.description()),
+            Style::default().fg(Color::LightRed).add_modifier(Modifier::BOLD),
+        )));
+        cl.push(Line::from(vec![
+            Span::raw("   "),
+            Gauge::ratio(ratio, 16).color(Color::LightRed).span(),
+            Span::styled(
+                format!(
+                    " {}/{}",
+                    format_number(state.cookies_all_time),
+                    format_number(kind.goal())
+                ),
+                Style::default().fg(Color::White),
+            ),
+        ]));
+        cl.push_clickable(Line::from(Span::styled(
+            " ✖ 挑戦を中断 (報酬なし)",
+            Style::default().fg(Color::DarkGray),
+        )), ABANDON_CHALLENGE);
+        cl.push(Line::from(""));
+    } else {
+        cl.push(Line::from(Span::styled(
+            " 転生と同時に縛りを選ぶ。達成で天国チップ",
+            Style::default().fg(Color::DarkGray),
+        )));
+    }
+
+    for kind in ChallengeKind::all() {
+        let badge = if state.challenges_completed.contains(kind) { "🏆" } else { "・" };
+        let can_start = state.active_challenge.is_none() && pending > 0;
+        let line = Line::from(vec![
+            Span::styled(
+                format!(" {} {} ", badge, kind.name()),
+                Style::default()
+                    .fg(if can_start { Color::White } else { Color::DarkGray })
+                    .add_modifier(Modifier::BOLD),
+            ),
+            Span::styled(
+                format!(
+                    "{} / {}枚で +{}👼",
+                    kind.description(),
+                    format_number(kind.goal()),
+                    kind.reward_chips()
+                ),
+                Style::default().fg(Color::DarkGray),
+            ),
+        ]);
+        if can_start {
+            cl.push_clickable(line, START_CHALLENGE.id(kind.index()));
+        } else {
+            cl.push(line);
+        }
+    }
+
+    if state.active_challenge.is_none() && pending == 0 {
+        cl.push(Line::from(Span::styled(
+            " (転生できるようになると開始できます)",
+            Style::default().fg(Color::DarkGray),
+        )));
+    }
+
+    let block = Block::default()
+        .borders(Borders::BOTTOM | Borders::LEFT | Borders::RIGHT)
+        .border_style(Style::default().fg(border_color));
+    let inner_width = block.inner(area).width;
+    let total_lines = estimate_wrapped_lines(cl.lines(), inner_width);
+
+    let mut cs = click_state.borrow_mut();
+    cl.render(f, area, block, &mut cs, true, scroll);
 
-    let inner_width = area.width.saturating_sub(2);
-    let total_lines = estimate_wrapped_lines(&lines, inner_width);
-    let widget = Paragraph::new(lines)
-        .block(
-            Block::default()
-                .borders(Borders::BOTTOM | Borders::LEFT | Borders::RIGHT)
-                .border_style(Style::default().fg(border_color)),
-        )
-        .wrap(Wrap { trim: false })
-        .scroll((scroll, 0));
-    f.render_widget(widget, area);
     total_lines
 }
 
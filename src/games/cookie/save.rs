@@ -15,13 +15,14 @@ use serde::{Deserialize, Serialize};
 
 #[cfg(any(target_arch = "wasm32", test))]
 use super::state::{
-    CookieState, DragonAura, MarketPhase, MilestoneStatus, ProducerKind, ResearchPath,
+    AnimationIntensity, ChallengeKind, ComboSkill, CookieArt, CookieState, Plant, SeedKind, DragonAura, MarketPhase, MilestoneStatus, ProducerKind,
+    ResearchPath, MAX_PINNED_UPGRADES, MAX_PRODUCER_LEVEL,
 };
 
 /// セーブデータのフォーマットバージョン。
 /// フィールド追加時にインクリメントすること。
 #[cfg(any(target_arch = "wasm32", test))]
-const SAVE_VERSION: u32 = 2;
+const SAVE_VERSION: u32 = 11;
 
 /// 互換性を維持できる最小バージョン。
 /// 既存フィールドの意味変更や削除など破壊的変更を行った場合のみインクリメントする。
@@ -30,9 +31,14 @@ const SAVE_VERSION: u32 = 2;
 const MIN_COMPATIBLE_VERSION: u32 = 1;
 
 /// localStorage のキー。
-#[cfg(target_arch = "wasm32")]
 const STORAGE_KEY: &str = "cookie_factory_save";
 
+/// アクティブなプロファイル用に名前空間化した保存キー。
+/// 設定画面のリセットはこのキーの中身をゴミ箱 ([`crate::storage::move_to_trash`]) へ移す。
+pub fn storage_key() -> String {
+    crate::profile::scoped_key(STORAGE_KEY)
+}
+
 /// オートセーブの間隔 (tick数)。10 ticks/sec × 30秒 = 300 ticks。
 pub const AUTOSAVE_INTERVAL: u32 = 300;
 
@@ -103,7 +109,49 @@ struct GameSave {
     // ドラゴン
     dragon_level: u32,
     dragon_aura: u8, // 0=None, 1=BreathOfRiches, 2=DragonCursor, 3=ElderPact, 4=DragonHarvest
+    /// 第2オーラ (dragon_aura と同じ番号)
+    dragon_aura2: u8,
     dragon_fed_total: u32,
+
+    // ピン留め
+    /// ピン留めしたアップグレードの index
+    pinned_upgrades: Vec<usize>,
+
+    // 設定
+    animation_intensity: u8, // 0=Full, 1=Reduced, 2=Off
+
+    // 挑戦ラン
+    /// 挑戦中の ChallengeKind::index()
+    active_challenge: Option<usize>,
+    /// 達成済み (バッジ) の ChallengeKind::index()
+    challenges_completed: Vec<usize>,
+    /// 挑戦の報酬で得た天国チップ (heavenly_chips に含まれる)
+    bonus_chips: u64,
+
+    // 菜園
+    /// 各区画の (SeedKind::index(), growth)。空き区画は None。
+    garden: Vec<Option<(usize, f64)>>,
+    /// 選択中の SeedKind::index()
+    selected_seed: usize,
+
+    // 先物 (チャート履歴は保存しない)
+    futures_price: f64,
+    futures_seed: u32,
+    futures_units: f64,
+    futures_cost_basis: f64,
+
+    // 連撃スキル
+    /// 習得済みの ComboSkill::index()
+    combo_skills: Vec<usize>,
+
+    /// 各プロデューサーのレベル。ProducerKind::all() の順。
+    producer_levels: Vec<u32>,
+
+    // クッキーの見た目 (マイルストーン報酬。転生でも消えない)
+    /// 解放済みの CookieArt::index()
+    cookie_arts: Vec<usize>,
+    /// 選択中の CookieArt::index()
+    cookie_art: usize,
 }
 
 /// CookieState からセーブ用データを抽出する。
@@ -183,7 +231,35 @@ fn extract_save(state: &CookieState) -> SaveData {
             // Dragon
             dragon_level: state.dragon_level,
             dragon_aura: state.dragon_aura.index() as u8,
+            dragon_aura2: state.dragon_aura2.index() as u8,
             dragon_fed_total: state.dragon_fed_total,
+            // Pins
+            pinned_upgrades: state.pinned_upgrades.clone(),
+            // Settings
+            animation_intensity: state.animation_intensity.index(),
+            // Challenges
+            active_challenge: state.active_challenge.map(|c| c.index()),
+            challenges_completed: state.challenges_completed.iter().map(|c| c.index()).collect(),
+            bonus_chips: state.bonus_chips,
+            // Garden
+            garden: state
+                .garden
+                .iter()
+                .map(|plot| plot.as_ref().map(|p| (p.seed.index(), p.growth)))
+                .collect(),
+            selected_seed: state.selected_seed.index(),
+            // Futures
+            futures_price: state.futures.price,
+            futures_seed: state.futures.seed,
+            futures_units: state.futures.units,
+            futures_cost_basis: state.futures.cost_basis,
+            // Combo skills
+            combo_skills: state.combo_skills.iter().map(|s| s.index()).collect(),
+            // Producer levels
+            producer_levels: state.producers.iter().map(|p| p.level).collect(),
+            // Cookie art
+            cookie_arts: state.cookie_arts.iter().map(|a| a.index()).collect(),
+            cookie_art: state.cookie_art.index(),
         },
     }
 }
@@ -194,6 +270,18 @@ fn kind_from_index(idx: usize) -> Option<ProducerKind> {
     ProducerKind::all().get(idx).cloned()
 }
 
+/// DragonAura::index() の番号から DragonAura を返す。不明な番号は None。
+#[cfg(any(target_arch = "wasm32", test))]
+fn aura_from_index(idx: u8) -> DragonAura {
+    match idx {
+        1 => DragonAura::BreathOfRiches,
+        2 => DragonAura::DragonCursor,
+        3 => DragonAura::ElderPact,
+        4 => DragonAura::DragonHarvest,
+        _ => DragonAura::None,
+    }
+}
+
 /// セーブデータを CookieState に復元する。
 /// 定義の個数が合わない場合は無視して新規データの方を使う。
 #[cfg(any(target_arch = "wasm32", test))]
@@ -210,6 +298,9 @@ fn apply_save(state: &mut CookieState, save: &GameSave) {
             p.multiplier = *mult;
         }
     }
+    for (p, &level) in state.producers.iter_mut().zip(&save.producer_levels) {
+        p.level = level.min(MAX_PRODUCER_LEVEL);
+    }
 
     // アップグレード復元
     for (i, &purchased) in save.upgrade_purchased.iter().enumerate() {
@@ -258,6 +349,7 @@ fn apply_save(state: &mut CookieState, save: &GameSave) {
     }
 
     state.milk = save.milk;
+    state.milk_display = save.milk;
     state.kitten_multiplier = save.kitten_multiplier;
 
     // 転生データ復元
@@ -302,20 +394,77 @@ fn apply_save(state: &mut CookieState, save: &GameSave) {
 
     // ドラゴン復元
     state.dragon_level = save.dragon_level;
-    state.dragon_aura = match save.dragon_aura {
-        1 => DragonAura::BreathOfRiches,
-        2 => DragonAura::DragonCursor,
-        3 => DragonAura::ElderPact,
-        4 => DragonAura::DragonHarvest,
-        _ => DragonAura::None,
-    };
+    state.dragon_aura = aura_from_index(save.dragon_aura);
+    state.dragon_aura2 = aura_from_index(save.dragon_aura2);
     state.dragon_fed_total = save.dragon_fed_total;
+
+    // ピン留め復元 (範囲外・購入済み・重複は捨てる)
+    state.pinned_upgrades.clear();
+    for &i in &save.pinned_upgrades {
+        let valid = state.upgrades.get(i).is_some_and(|u| !u.purchased);
+        if valid
+            && !state.pinned_upgrades.contains(&i)
+            && state.pinned_upgrades.len() < MAX_PINNED_UPGRADES
+        {
+            state.pinned_upgrades.push(i);
+        }
+    }
+
+    state.animation_intensity = AnimationIntensity::from_index(save.animation_intensity);
+    state.particles.set_cap(state.animation_intensity.particle_cap());
+
+    // 挑戦ラン復元 (不明な index は捨てる)
+    state.active_challenge = save.active_challenge.and_then(ChallengeKind::from_index);
+    state.challenges_completed.clear();
+    for kind in save.challenges_completed.iter().filter_map(|&i| ChallengeKind::from_index(i)) {
+        if !state.challenges_completed.contains(&kind) {
+            state.challenges_completed.push(kind);
+        }
+    }
+    state.bonus_chips = save.bonus_chips.min(save.heavenly_chips);
+
+    // 菜園復元 (区画数が合わない分・不明な種は空き区画)
+    for (i, plot) in save.garden.iter().enumerate() {
+        if let Some(slot) = state.garden.get_mut(i) {
+            *slot = plot.and_then(|(seed, growth)| {
+                Some(Plant { seed: SeedKind::from_index(seed)?, growth })
+            });
+        }
+    }
+    state.selected_seed = SeedKind::from_index(save.selected_seed).unwrap_or(SeedKind::Bakerwheat);
+
+    // 先物復元 (v6 以前のセーブは seed = 0 なので初期相場のまま)
+    if save.futures_seed != 0 && save.futures_price > 0.0 {
+        state.futures.price = save.futures_price;
+        state.futures.seed = save.futures_seed;
+    }
+    state.futures.units = save.futures_units.max(0.0);
+    state.futures.cost_basis = save.futures_cost_basis.max(0.0);
+
+    // 連撃スキル復元 (不明な index・重複は捨てる)
+    state.combo_skills.clear();
+    for skill in save.combo_skills.iter().filter_map(|&i| ComboSkill::from_index(i)) {
+        if !state.combo_skills.contains(&skill) {
+            state.combo_skills.push(skill);
+        }
+    }
+
+    // クッキーの見た目復元 (未解放のものは選べない)
+    state.cookie_arts.clear();
+    for art in save.cookie_arts.iter().filter_map(|&i| CookieArt::from_index(i)) {
+        if art != CookieArt::Classic && !state.cookie_arts.contains(&art) {
+            state.cookie_arts.push(art);
+        }
+    }
+    state.cookie_art = CookieArt::from_index(save.cookie_art)
+        .filter(|a| state.unlocked_cookie_arts().contains(a))
+        .unwrap_or(CookieArt::Classic);
 }
 
 /// localStorage にアクセスする。WASM 環境でのみ動作。
 #[cfg(target_arch = "wasm32")]
-fn get_storage() -> Option<web_sys::Storage> {
-    web_sys::window()?.local_storage().ok()?
+fn get_storage() -> Option<crate::storage::Store> {
+    crate::storage::get()
 }
 
 /// ゲーム状態を localStorage に保存する。
@@ -334,7 +483,7 @@ pub fn save_game(state: &CookieState) {
     };
 
     if let Some(storage) = get_storage() {
-        if let Err(e) = storage.set_item(STORAGE_KEY, &json) {
+        if let Err(e) = storage.set_item(&storage_key(), &json) {
             web_sys::console::warn_1(
                 &format!("Cookie Factory: localStorage への保存に失敗: {e:?}").into(),
             );
@@ -351,7 +500,7 @@ pub fn load_game(state: &mut CookieState) -> bool {
         None => return false,
     };
 
-    let json = match storage.get_item(STORAGE_KEY) {
+    let json = match storage.get_item(&storage_key()) {
         Ok(Some(j)) => j,
         _ => return false,
     };
@@ -366,7 +515,7 @@ pub fn load_game(state: &mut CookieState) -> bool {
                 .into(),
             );
             // 壊れたデータを削除
-            let _ = storage.remove_item(STORAGE_KEY);
+            let _ = storage.remove_item(&storage_key());
             return false;
         }
     };
@@ -379,7 +528,7 @@ pub fn load_game(state: &mut CookieState) -> bool {
             )
             .into(),
         );
-        let _ = storage.remove_item(STORAGE_KEY);
+        let _ = storage.remove_item(&storage_key());
         return false;
     }
 
@@ -397,18 +546,51 @@ pub fn load_game(state: &mut CookieState) -> bool {
     true
 }
 
-/// セーブデータを削除する。
-#[cfg(target_arch = "wasm32")]
-pub fn delete_save() {
-    if let Some(storage) = get_storage() {
-        let _ = storage.remove_item(STORAGE_KEY);
+/// メインメニュー用の 1 行の進捗要約。セーブが無ければ `None`。
+/// セーブ全体を読み込むので、メニューに入るときに 1 回だけ呼ぶこと。
+pub fn progress_summary() -> Option<String> {
+    #[cfg(target_arch = "wasm32")]
+    {
+        let mut state = CookieState::new();
+        load_game(&mut state).then(|| summarize(&state))
     }
+    #[cfg(not(target_arch = "wasm32"))]
+    None
+}
+
+#[cfg(any(target_arch = "wasm32", test))]
+fn summarize(state: &CookieState) -> String {
+    format!(
+        "{} 枚 / {}/秒",
+        super::logic::format_number(state.cookies),
+        super::logic::format_number(state.total_cps())
+    )
+}
+
+/// extract → JSON → apply → extract の前後の JSON を返す (fuzz ハーネスの往復検査用)。
+#[cfg(test)]
+pub(crate) fn roundtrip_json(state: &CookieState) -> (String, String) {
+    let before = serde_json::to_string(&extract_save(state)).unwrap();
+    let loaded: SaveData = serde_json::from_str(&before).unwrap();
+    let mut restored = CookieState::new();
+    apply_save(&mut restored, &loaded.game);
+    let after = serde_json::to_string(&extract_save(&restored)).unwrap();
+    (before, after)
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    #[test]
+    fn menu_summary_shows_cookies_and_cps() {
+        let mut s = CookieState::new();
+        s.cookies = 1234.0;
+        let text = summarize(&s);
+        assert!(text.starts_with(&crate::games::cookie::logic::format_number(1234.0)), "{}", text);
+        assert!(text.ends_with("/秒"), "{}", text);
+    }
+
     #[test]
     fn extract_and_apply_roundtrip() {
         let mut original = CookieState::new();
@@ -419,6 +601,7 @@ mod tests {
         original.producers[0].count = 10;
         original.producers[0].multiplier = 2.0;
         original.producers[2].count = 5;
+        original.producers[2].level = 4;
         original.upgrades[0].purchased = true;
         original.upgrades[1].purchased = true;
         original.synergy_multiplier = 2.0;
@@ -453,7 +636,28 @@ mod tests {
         // Dragon
         original.dragon_level = 3;
         original.dragon_aura = DragonAura::BreathOfRiches;
+        original.dragon_aura2 = DragonAura::ElderPact;
         original.dragon_fed_total = 85;
+        // Pins
+        original.pinned_upgrades = vec![5, 3];
+        // Settings
+        original.animation_intensity = AnimationIntensity::Reduced;
+        // Challenges
+        original.active_challenge = Some(ChallengeKind::Expensive);
+        original.challenges_completed = vec![ChallengeKind::NoClick];
+        original.bonus_chips = 5;
+        // Garden
+        original.garden[2] = Some(Plant { seed: SeedKind::Sugarcane, growth: 123.5 });
+        original.selected_seed = SeedKind::Cronerice;
+        // Futures
+        original.futures.advance(55);
+        original.futures.units = 300.0;
+        original.futures.cost_basis = 250.0;
+        // Combo skills
+        original.combo_skills = vec![ComboSkill::Tempo, ComboSkill::HeavyHand];
+        // Cookie art
+        original.cookie_arts = vec![CookieArt::Heart, CookieArt::Star];
+        original.cookie_art = CookieArt::Star;
 
         let save = extract_save(&original);
         let json = serde_json::to_string(&save).unwrap();
@@ -471,6 +675,8 @@ mod tests {
         assert_eq!(restored.producers[0].count, 10);
         assert!((restored.producers[0].multiplier - 2.0).abs() < 0.001);
         assert_eq!(restored.producers[2].count, 5);
+        assert_eq!(restored.producers[2].level, 4);
+        assert_eq!(restored.producers[0].level, 0);
         assert!(restored.upgrades[0].purchased);
         assert!(restored.upgrades[1].purchased);
         assert!(!restored.upgrades[2].purchased);
@@ -503,7 +709,44 @@ mod tests {
         // Dragon
         assert_eq!(restored.dragon_level, 3);
         assert_eq!(restored.dragon_aura, DragonAura::BreathOfRiches);
+        assert_eq!(restored.dragon_aura2, DragonAura::ElderPact);
         assert_eq!(restored.dragon_fed_total, 85);
+        // Pins
+        assert_eq!(restored.pinned_upgrades, vec![5, 3]);
+        // Settings
+        assert_eq!(restored.animation_intensity, AnimationIntensity::Reduced);
+        assert_eq!(restored.particles.cap(), 10);
+        // Challenges
+        assert_eq!(restored.active_challenge, Some(ChallengeKind::Expensive));
+        assert_eq!(restored.challenges_completed, vec![ChallengeKind::NoClick]);
+        assert_eq!(restored.bonus_chips, 5);
+        // Garden
+        assert_eq!(restored.garden[2], Some(Plant { seed: SeedKind::Sugarcane, growth: 123.5 }));
+        assert!(restored.garden[0].is_none());
+        assert_eq!(restored.selected_seed, SeedKind::Cronerice);
+        // Futures: same price and seed, so the walk continues identically
+        assert_eq!(restored.futures.price, original.futures.price);
+        assert_eq!(restored.futures.seed, original.futures.seed);
+        assert_eq!(restored.futures.units, 300.0);
+        assert_eq!(restored.futures.cost_basis, 250.0);
+        // Combo skills
+        assert_eq!(restored.combo_skills, vec![ComboSkill::Tempo, ComboSkill::HeavyHand]);
+        // Cookie art
+        assert_eq!(restored.cookie_arts, vec![CookieArt::Heart, CookieArt::Star]);
+        assert_eq!(restored.cookie_art, CookieArt::Star);
+    }
+
+    #[test]
+    fn invalid_pins_are_dropped_on_load() {
+        let mut original = CookieState::new();
+        original.upgrades[0].purchased = true;
+        original.pinned_upgrades = vec![0, 2, 2, 9999, 4, 6, 7];
+
+        let save = extract_save(&original);
+        let mut restored = CookieState::new();
+        apply_save(&mut restored, &save.game);
+
+        assert_eq!(restored.pinned_upgrades, vec![2, 4, 6]);
     }
 
     #[test]
@@ -1,5 +1,9 @@
 //! Cookie Factory game state definitions.
 
+use std::borrow::Cow;
+use std::cell::Cell;
+use std::collections::VecDeque;
+
 /// Kinds of producers (auto-clickers).
 #[derive(Clone, Debug, PartialEq)]
 pub enum ProducerKind {
@@ -172,6 +176,45 @@ pub struct NextGoal {
     pub progress: f64,
 }
 
+/// Each step of the [`CookieState::total_cps`] multiplier chain, for the
+/// CPS breakdown view. Every factor is a multiplier (1.0 = no effect).
+#[derive(Clone, Debug, PartialEq)]
+pub struct CpsBreakdown {
+    /// Producers' CPS with synergies and count scaling.
+    pub base: f64,
+    /// Added on top of `base` by CPS-percent upgrades.
+    pub percent_extra: f64,
+    pub kitten: f64,
+    pub prestige: f64,
+    pub research: f64,
+    pub dragon: f64,
+    pub market: f64,
+    /// Production frenzy buffs (after the research buff-effect modifier).
+    pub buffs: f64,
+    pub buff_combo: f64,
+    pub sugar: f64,
+    pub savings: f64,
+    /// Final product: `(base + percent_extra) × every factor`.
+    pub total: f64,
+}
+
+impl CpsBreakdown {
+    /// `(label, factor)` for each multiplier, in the order they are applied.
+    pub fn factors(&self) -> [(&'static str, f64); 9] {
+        [
+            ("子猫 (ミルク)", self.kitten),
+            ("転生", self.prestige),
+            ("研究", self.research),
+            ("ドラゴン", self.dragon),
+            ("市場", self.market),
+            ("フィーバー", self.buffs),
+            ("バフコンボ", self.buff_combo),
+            ("砂糖ブースト", self.sugar),
+            ("貯蓄ボーナス", self.savings),
+        ]
+    }
+}
+
 /// ROI (Return on Investment) information for a producer.
 #[allow(dead_code)]
 #[derive(Clone, Debug)]
@@ -186,6 +229,12 @@ pub struct RoiInfo {
     pub affordable: bool,
 }
 
+/// CPS bonus per producer level (+10% to that type).
+pub const PRODUCER_LEVEL_BONUS: f64 = 0.10;
+
+/// Highest level a producer type can reach.
+pub const MAX_PRODUCER_LEVEL: u32 = 10;
+
 /// A single type of producer.
 #[derive(Clone, Debug)]
 pub struct Producer {
@@ -193,6 +242,8 @@ pub struct Producer {
     pub count: u32,
     /// Multiplier from upgrades (default 1.0).
     pub multiplier: f64,
+    /// Level bought with cookies, separate from `count` (reset on prestige).
+    pub level: u32,
 }
 
 impl Producer {
@@ -201,9 +252,23 @@ impl Producer {
             kind,
             count: 0,
             multiplier: 1.0,
+            level: 0,
         }
     }
 
+    /// CPS multiplier from levels: +10% per level.
+    pub fn level_multiplier(&self) -> f64 {
+        1.0 + self.level as f64 * PRODUCER_LEVEL_BONUS
+    }
+
+    /// Cost of the next level (before market/discount modifiers).
+    ///
+    /// Starts at 50 units' base cost and quadruples every level, so levels
+    /// stay a late sink next to buying more units.
+    pub fn level_up_cost(&self) -> f64 {
+        self.kind.base_cost() * 50.0 * 4f64.powi(self.level as i32)
+    }
+
     /// Current cost to buy the next one.
     ///
     /// Each producer has its own cost growth rate: cheap producers
@@ -214,7 +279,7 @@ impl Producer {
 
     /// Base CPS from this producer type (without synergy).
     pub fn base_cps(&self) -> f64 {
-        self.count as f64 * self.kind.base_rate() * self.multiplier
+        self.count as f64 * self.kind.base_rate() * self.multiplier * self.level_multiplier()
     }
 
     /// CPS with synergy bonus applied.
@@ -224,7 +289,7 @@ impl Producer {
 
     /// CPS gained by buying the next unit (with synergy).
     pub fn next_unit_cps_with_synergy(&self, synergy_bonus: f64) -> f64 {
-        self.kind.base_rate() * self.multiplier * (1.0 + synergy_bonus)
+        self.kind.base_rate() * self.multiplier * self.level_multiplier() * (1.0 + synergy_bonus)
     }
 
     /// Payback time in seconds with synergy.
@@ -246,7 +311,7 @@ impl Producer {
     /// Next unit CPS without synergy (used in tests).
     #[cfg(test)]
     pub fn next_unit_cps(&self) -> f64 {
-        self.kind.base_rate() * self.multiplier
+        self.kind.base_rate() * self.multiplier * self.level_multiplier()
     }
 
     /// Payback without synergy (used in tests).
@@ -325,6 +390,91 @@ impl GoldenEffect {
             GoldenEffect::InstantBonus { cps_seconds } => format!("CPS×{}秒分GET！", cps_seconds),
         }
     }
+
+    /// 時間の掛かるバフとしての種類。即時効果は `None`。
+    pub fn buff_kind(&self) -> Option<BuffKind> {
+        match self {
+            GoldenEffect::ProductionFrenzy { .. } => Some(BuffKind::Production),
+            GoldenEffect::ClickFrenzy { .. } => Some(BuffKind::Click),
+            GoldenEffect::InstantBonus { .. } => None,
+        }
+    }
+
+    /// バフの倍率 (即時効果は 1.0)。
+    pub fn multiplier(&self) -> f64 {
+        match self {
+            GoldenEffect::ProductionFrenzy { multiplier } | GoldenEffect::ClickFrenzy { multiplier } => *multiplier,
+            GoldenEffect::InstantBonus { .. } => 1.0,
+        }
+    }
+}
+
+/// バフの種類ごとの積み方。
+///
+/// - 同じ効果 (種類も倍率も同じ) がもう掛かっていれば、残り時間を延ばすだけ (Refresh)。
+/// - 違う効果は [`stack_limit`](Self::stack_limit) 個まで同時に掛かって掛け算になる (Stack)。
+/// - 上限を超えた分は待機列に並び、枠が空いたら順に始まる (Queue)。
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum BuffKind {
+    Production,
+    Click,
+}
+
+impl BuffKind {
+    pub const ALL: [BuffKind; 2] = [BuffKind::Production, BuffKind::Click];
+
+    /// 同時に掛かる数の上限。生産は 2 つまで、クリックは 1 つずつ。
+    pub fn stack_limit(self) -> usize {
+        match self {
+            BuffKind::Production => 2,
+            BuffKind::Click => 1,
+        }
+    }
+
+    pub fn name(self) -> &'static str {
+        match self {
+            BuffKind::Production => "生産フィーバー",
+            BuffKind::Click => "クリックラッシュ",
+        }
+    }
+
+    pub fn icon(self) -> &'static str {
+        match self {
+            BuffKind::Production => "🌟",
+            BuffKind::Click => "👆",
+        }
+    }
+}
+
+/// バフバーの 1 行 (種類ごとにまとめた表示用の値)。
+#[derive(Clone, Debug, PartialEq)]
+pub struct BuffSummary {
+    pub kind: BuffKind,
+    /// 掛かっている倍率の積。
+    pub multiplier: f64,
+    /// 同時に掛かっている数。
+    pub stacks: usize,
+    /// いちばん早く切れるバフの残り tick と、その分母。
+    pub ticks_left: u32,
+    pub total_ticks: u32,
+    /// 待機列に並んでいる数。
+    pub queued: usize,
+}
+
+/// 積み上限を超えて待てるバフの数。これを超えた分は捨てる。
+pub const MAX_QUEUED_BUFFS: usize = 3;
+
+/// バフを足したときに実際に取った積み方。
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum BuffStacking {
+    /// 同じ効果の残り時間を延ばした。
+    Refresh,
+    /// 新しく掛かった。
+    Stack,
+    /// 待機列に並んだ。
+    Queue,
+    /// 待機列も満杯で捨てた。
+    Dropped,
 }
 
 /// Active golden cookie event.
@@ -342,6 +492,20 @@ pub struct ActiveBuff {
     pub effect: GoldenEffect,
     /// Ticks remaining for this buff.
     pub ticks_left: u32,
+    /// 残り時間リングの分母。延長されたら伸びる。
+    pub total_ticks: u32,
+}
+
+impl ActiveBuff {
+    pub fn new(effect: GoldenEffect, ticks: u32) -> Self {
+        Self { effect, ticks_left: ticks, total_ticks: ticks }
+    }
+
+    /// 残り時間を `ticks` 延ばす。
+    pub fn extend(&mut self, ticks: u32) {
+        self.ticks_left += ticks;
+        self.total_ticks = self.total_ticks.max(self.ticks_left);
+    }
 }
 
 /// Mini-event types — smaller, more frequent events that auto-fire.
@@ -402,6 +566,80 @@ pub enum MilestoneStatus {
     Claimed,
 }
 
+/// Cosmetic art for the main cookie, unlocked by milestone rewards.
+/// Unlocks and the current choice survive prestige.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum CookieArt {
+    /// The default chocolate-chip cookie (always available).
+    Classic,
+    Chocolate,
+    Heart,
+    Star,
+    Golden,
+}
+
+impl CookieArt {
+    pub fn all() -> &'static [CookieArt] {
+        &[
+            CookieArt::Classic,
+            CookieArt::Chocolate,
+            CookieArt::Heart,
+            CookieArt::Star,
+            CookieArt::Golden,
+        ]
+    }
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            CookieArt::Classic => "定番チョコチップ",
+            CookieArt::Chocolate => "ダブルチョコ",
+            CookieArt::Heart => "ハートクッキー",
+            CookieArt::Star => "星型クッキー",
+            CookieArt::Golden => "黄金のクッキー",
+        }
+    }
+
+    pub fn index(&self) -> usize {
+        match self {
+            CookieArt::Classic => 0,
+            CookieArt::Chocolate => 1,
+            CookieArt::Heart => 2,
+            CookieArt::Star => 3,
+            CookieArt::Golden => 4,
+        }
+    }
+
+    pub fn from_index(idx: usize) -> Option<CookieArt> {
+        Self::all().get(idx).copied()
+    }
+}
+
+/// What a milestone gives when claimed, on top of the 4% milk every
+/// milestone grants.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum MilestoneReward {
+    /// Milk only.
+    Milk,
+    /// Sugar lumps.
+    Sugar(u64),
+    /// One-time cookies worth this many seconds of CPS at claim time.
+    CookieLump(f64),
+    /// Unlocks a cookie art for the main panel.
+    Art(CookieArt),
+}
+
+impl MilestoneReward {
+    /// Short label shown next to the milestone before it is claimed.
+    pub fn label(&self) -> String {
+        match self {
+            MilestoneReward::Milk => "🥛ミルク".into(),
+            MilestoneReward::Sugar(n) => format!("🍬砂糖×{}", n),
+            MilestoneReward::CookieLump(secs) => format!("🍪CPS{}分ぶん", (secs / 60.0).round()),
+            MilestoneReward::Art(art) => format!("🎨{}", art.name()),
+        }
+    }
+}
+
 /// A milestone (achievement) definition.
 #[derive(Clone, Debug)]
 pub struct Milestone {
@@ -409,6 +647,7 @@ pub struct Milestone {
     pub description: String,
     pub condition: MilestoneCondition,
     pub status: MilestoneStatus,
+    pub reward: MilestoneReward,
 }
 
 /// Particle style for different visual effects.
@@ -424,13 +663,27 @@ pub enum ParticleStyle {
     Celebration,
     /// Combo indicator text.
     Combo,
+    /// Milk droplet splashing up and falling back (milestone claim).
+    MilkSplash,
+}
+
+/// Which panel a particle is drawn over.  Each panel's render function
+/// draws only its own particles, using its own area as the origin.
+#[derive(Clone, Debug, PartialEq)]
+pub enum ParticlePanel {
+    /// The main cookie display (clicks, sparkles, celebrations).
+    Cookie,
+    /// The milestones panel (milk splash on claim).
+    Milestones,
 }
 
 /// A floating text particle (e.g. "+1" rising from click area).
 #[derive(Clone, Debug)]
 pub struct Particle {
-    /// Text to display.
-    pub text: String,
+    /// Text to display.  Fixed glyphs borrow a `'static` str; computed
+    /// text ("+N", combo) is owned, built in a buffer from
+    /// [`ParticlePool::text_buf`].
+    pub text: Cow<'static, str>,
     /// Column offset from the center of the cookie display.
     pub col_offset: i16,
     /// Remaining lifetime in ticks (starts high, counts down).
@@ -441,6 +694,341 @@ pub struct Particle {
     pub style: ParticleStyle,
     /// Row offset for celebration particles (signed, from center).
     pub row_offset: i16,
+    /// Panel this particle belongs to.
+    pub panel: ParticlePanel,
+    /// Cookies shown by a "+N" click particle (0 for decorative ones).
+    /// Coincident clicks add into this instead of spawning new particles.
+    pub amount: f64,
+}
+
+/// Upper bound on live particles at [`AnimationIntensity::Full`].
+pub const MAX_PARTICLES: usize = 30;
+
+/// A click "+N" particle younger than this (ticks) absorbs further clicks
+/// instead of a new particle being spawned.
+pub const CLICK_MERGE_TICKS: u32 = 3;
+
+/// How much visual effect to draw.  Lower settings shrink the particle
+/// cap so rapid clicking stays smooth on low-end phones.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Default)]
+pub enum AnimationIntensity {
+    #[default]
+    Full,
+    Reduced,
+    Off,
+}
+
+impl AnimationIntensity {
+    pub fn particle_cap(self) -> usize {
+        match self {
+            AnimationIntensity::Full => MAX_PARTICLES,
+            AnimationIntensity::Reduced => 10,
+            AnimationIntensity::Off => 0,
+        }
+    }
+
+    pub fn label(self) -> &'static str {
+        match self {
+            AnimationIntensity::Full => "標準",
+            AnimationIntensity::Reduced => "控えめ",
+            AnimationIntensity::Off => "オフ",
+        }
+    }
+
+    /// Full → Reduced → Off → Full.
+    pub fn next(self) -> Self {
+        match self {
+            AnimationIntensity::Full => AnimationIntensity::Reduced,
+            AnimationIntensity::Reduced => AnimationIntensity::Off,
+            AnimationIntensity::Off => AnimationIntensity::Full,
+        }
+    }
+
+    pub fn index(self) -> u8 {
+        match self {
+            AnimationIntensity::Full => 0,
+            AnimationIntensity::Reduced => 1,
+            AnimationIntensity::Off => 2,
+        }
+    }
+
+    pub fn from_index(i: u8) -> Self {
+        match i {
+            1 => AnimationIntensity::Reduced,
+            2 => AnimationIntensity::Off,
+            _ => AnimationIntensity::Full,
+        }
+    }
+}
+
+/// Fixed-capacity particle storage.  The buffer is allocated once for
+/// [`MAX_PARTICLES`] and never grows; spawning past the cap drops the
+/// oldest particle, so a click storm costs O(1) per spawn.
+///
+/// Owned text buffers of dropped particles are kept and handed back out
+/// by [`ParticlePool::text_buf`], so once warmed up, spawning and expiring
+/// particles allocates nothing.
+#[derive(Debug)]
+pub struct ParticlePool {
+    items: VecDeque<Particle>,
+    cap: usize,
+    /// Cleared text buffers from dropped particles, ready for reuse.
+    spare: Vec<String>,
+}
+
+impl ParticlePool {
+    pub fn new(cap: usize) -> Self {
+        Self {
+            items: VecDeque::with_capacity(MAX_PARTICLES),
+            cap: cap.min(MAX_PARTICLES),
+            spare: Vec::with_capacity(MAX_PARTICLES),
+        }
+    }
+
+    pub fn cap(&self) -> usize {
+        self.cap
+    }
+
+    /// Change the cap, dropping the oldest particles if over it.
+    pub fn set_cap(&mut self, cap: usize) {
+        self.cap = cap.min(MAX_PARTICLES);
+        while self.items.len() > self.cap {
+            self.evict_oldest();
+        }
+    }
+
+    /// An empty buffer for a particle's computed text, reusing the
+    /// allocation of a dropped particle when one is available.
+    pub fn text_buf(&mut self) -> String {
+        self.spare.pop().unwrap_or_default()
+    }
+
+    /// Add a particle (a no-op when the cap is 0).
+    pub fn spawn(&mut self, p: Particle) {
+        if self.cap == 0 {
+            self.recycle(p);
+            return;
+        }
+        if self.items.len() >= self.cap {
+            self.evict_oldest();
+        }
+        self.items.push_back(p);
+    }
+
+    /// Age every particle and drop the expired ones.
+    pub fn tick(&mut self, delta_ticks: u32) {
+        for p in &mut self.items {
+            p.life = p.life.saturating_sub(delta_ticks);
+        }
+        // Expired particles are spread through the queue, so rotate through
+        // it once instead of `retain` (which would drop their buffers).
+        for _ in 0..self.items.len() {
+            let Some(p) = self.items.pop_front() else { break };
+            if p.life > 0 {
+                self.items.push_back(p);
+            } else {
+                self.recycle(p);
+            }
+        }
+    }
+
+    /// The newest plain click "+N" particle (criticals carry no `amount`)
+    /// if it is still within [`CLICK_MERGE_TICKS`] of being spawned.
+    pub fn mergeable_click(&mut self) -> Option<&mut Particle> {
+        self.items
+            .iter_mut()
+            .rev()
+            .find(|p| p.style == ParticleStyle::Click && p.amount > 0.0)
+            .filter(|p| p.max_life.saturating_sub(p.life) < CLICK_MERGE_TICKS)
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = &Particle> {
+        self.items.iter()
+    }
+
+    pub fn len(&self) -> usize {
+        self.items.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.items.is_empty()
+    }
+
+    pub fn clear(&mut self) {
+        while !self.items.is_empty() {
+            self.evict_oldest();
+        }
+    }
+
+    fn evict_oldest(&mut self) {
+        if let Some(p) = self.items.pop_front() {
+            self.recycle(p);
+        }
+    }
+
+    /// Keep an owned text buffer for [`Self::text_buf`].
+    fn recycle(&mut self, p: Particle) {
+        if let Cow::Owned(mut buf) = p.text {
+            if self.spare.len() < MAX_PARTICLES {
+                buf.clear();
+                self.spare.push(buf);
+            }
+        }
+    }
+}
+
+/// Maximum number of distinct entries in the purchase queue.
+pub const MAX_PURCHASE_QUEUE: usize = 5;
+
+/// Units added per queue action ("buy 10 when affordable").
+pub const QUEUE_BATCH: u32 = 10;
+
+/// Maximum number of pinned (wishlisted) upgrades.
+pub const MAX_PINNED_UPGRADES: usize = 3;
+
+/// Ticks before a double-tap critical click can fire again (5 seconds).
+pub const CRIT_CLICK_COOLDOWN: u32 = 50;
+
+/// Base critical click multiplier (before combo skills).
+pub const BASE_CRIT_MULTIPLIER: f64 = 10.0;
+
+/// Ticks without a click before the combo resets (1.5 seconds, before combo skills).
+pub const BASE_COMBO_WINDOW: u32 = 15;
+
+/// 連撃スキル: a small tree bought with cookies (upgrades tab) that
+/// strengthens critical clicks and keeps combos alive longer.
+///
+/// ```text
+///            連打の呼吸
+///         ┌──────┼──────┐
+///       見切り  会心の一撃  無我の境地
+///         │      │
+///       鷹の目   粉砕
+/// ```
+///
+/// Lost on prestige like regular upgrades.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ComboSkill {
+    /// Root: combo window +0.5s.
+    Tempo,
+    /// Crit chance +2%.
+    KeenEye,
+    /// Crit multiplier +5.
+    HeavyHand,
+    /// Combo window +1s.
+    Flow,
+    /// Crit chance +3% (requires KeenEye).
+    Hawkeye,
+    /// Crit multiplier +10 (requires HeavyHand).
+    Shatter,
+}
+
+impl ComboSkill {
+    pub fn all() -> &'static [ComboSkill] {
+        &[
+            ComboSkill::Tempo,
+            ComboSkill::KeenEye,
+            ComboSkill::HeavyHand,
+            ComboSkill::Flow,
+            ComboSkill::Hawkeye,
+            ComboSkill::Shatter,
+        ]
+    }
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            ComboSkill::Tempo => "連打の呼吸",
+            ComboSkill::KeenEye => "見切り",
+            ComboSkill::HeavyHand => "会心の一撃",
+            ComboSkill::Flow => "無我の境地",
+            ComboSkill::Hawkeye => "鷹の目",
+            ComboSkill::Shatter => "粉砕",
+        }
+    }
+
+    pub fn description(&self) -> &'static str {
+        match self {
+            ComboSkill::Tempo => "コンボ猶予 +0.5秒",
+            ComboSkill::KeenEye => "クリティカル率 +2%",
+            ComboSkill::HeavyHand => "クリティカル倍率 +5",
+            ComboSkill::Flow => "コンボ猶予 +1秒",
+            ComboSkill::Hawkeye => "クリティカル率 +3%",
+            ComboSkill::Shatter => "クリティカル倍率 +10",
+        }
+    }
+
+    pub fn cost(&self) -> f64 {
+        match self {
+            ComboSkill::Tempo => 500.0,
+            ComboSkill::KeenEye => 5_000.0,
+            ComboSkill::HeavyHand => 20_000.0,
+            ComboSkill::Flow => 100_000.0,
+            ComboSkill::Hawkeye => 1_000_000.0,
+            ComboSkill::Shatter => 5_000_000.0,
+        }
+    }
+
+    /// Skill that must be learned first.
+    pub fn requires(&self) -> Option<ComboSkill> {
+        match self {
+            ComboSkill::Tempo => None,
+            ComboSkill::KeenEye | ComboSkill::HeavyHand | ComboSkill::Flow => {
+                Some(ComboSkill::Tempo)
+            }
+            ComboSkill::Hawkeye => Some(ComboSkill::KeenEye),
+            ComboSkill::Shatter => Some(ComboSkill::HeavyHand),
+        }
+    }
+
+    /// Added critical chance (fraction).
+    fn crit_chance_bonus(&self) -> f64 {
+        match self {
+            ComboSkill::KeenEye => 0.02,
+            ComboSkill::Hawkeye => 0.03,
+            _ => 0.0,
+        }
+    }
+
+    /// Added critical multiplier.
+    fn crit_multiplier_bonus(&self) -> f64 {
+        match self {
+            ComboSkill::HeavyHand => 5.0,
+            ComboSkill::Shatter => 10.0,
+            _ => 0.0,
+        }
+    }
+
+    /// Added combo window (ticks).
+    fn combo_window_bonus(&self) -> u32 {
+        match self {
+            ComboSkill::Tempo => 5,
+            ComboSkill::Flow => 10,
+            _ => 0,
+        }
+    }
+
+    pub fn index(&self) -> usize {
+        match self {
+            ComboSkill::Tempo => 0,
+            ComboSkill::KeenEye => 1,
+            ComboSkill::HeavyHand => 2,
+            ComboSkill::Flow => 3,
+            ComboSkill::Hawkeye => 4,
+            ComboSkill::Shatter => 5,
+        }
+    }
+
+    pub fn from_index(idx: usize) -> Option<ComboSkill> {
+        ComboSkill::all().get(idx).copied()
+    }
+}
+
+/// A reserved producer purchase, bought automatically in `tick` once affordable.
+#[derive(Clone, Debug, PartialEq)]
+pub struct QueuedPurchase {
+    pub kind: ProducerKind,
+    /// Units still to buy.
+    pub remaining: u32,
 }
 
 /// Log entry for the Cookie game.
@@ -588,6 +1176,33 @@ pub enum ResearchPath {
     Quality,
 }
 
+impl ResearchPath {
+    pub fn name(&self) -> &'static str {
+        match self {
+            ResearchPath::None => "未選択",
+            ResearchPath::MassProduction => "量産路線",
+            ResearchPath::Quality => "品質路線",
+        }
+    }
+
+    pub fn icon(&self) -> &'static str {
+        match self {
+            ResearchPath::None => "",
+            ResearchPath::MassProduction => "⚙",
+            ResearchPath::Quality => "💎",
+        }
+    }
+
+    /// The path that choosing this one locks out.
+    pub fn rival(&self) -> ResearchPath {
+        match self {
+            ResearchPath::None => ResearchPath::None,
+            ResearchPath::MassProduction => ResearchPath::Quality,
+            ResearchPath::Quality => ResearchPath::MassProduction,
+        }
+    }
+}
+
 /// Research node effect.
 #[derive(Clone, Debug, PartialEq)]
 pub enum ResearchEffect {
@@ -679,6 +1294,17 @@ impl MarketPhase {
         }
     }
 
+    /// Garden growth speed. A slow market leaves hands free for the fields.
+    pub fn garden_growth_multiplier(&self) -> f64 {
+        match self {
+            MarketPhase::Bull => 0.9,
+            MarketPhase::Bear => 1.2,
+            MarketPhase::Normal => 1.0,
+            MarketPhase::Bubble => 0.7,
+            MarketPhase::Crash => 1.5,
+        }
+    }
+
     pub fn symbol(&self) -> &str {
         match self {
             MarketPhase::Bull => "📈",
@@ -694,7 +1320,13 @@ impl MarketPhase {
 // Dragon — 育成 & オーラシステム
 // ═══════════════════════════════════════════════════════
 
-/// Dragon aura — passive bonus, choose one at a time.
+/// Dragon level at which a second aura slot opens.
+pub const DRAGON_DUAL_AURA_LEVEL: u32 = 5;
+
+/// Highest dragon level (the last step of [`CookieState::dragon_feed_cost`]).
+pub const MAX_DRAGON_LEVEL: u32 = 7;
+
+/// Dragon aura — passive bonus. One slot, two from [`DRAGON_DUAL_AURA_LEVEL`].
 #[derive(Clone, Debug, PartialEq)]
 pub enum DragonAura {
     /// No aura selected.
@@ -751,6 +1383,384 @@ impl DragonAura {
     }
 }
 
+/// Dragon ability — fires on every golden cookie claim once the dragon
+/// reaches its unlock level.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum DragonAbility {
+    /// 財宝の番人: CPS × 2秒 × level of extra cookies.
+    Hoard,
+    /// 咆哮: every active buff lasts 5 seconds longer.
+    Roar,
+    /// 竜炎: 25% chance the next golden cookie shows up within 5 seconds.
+    Flame,
+}
+
+impl DragonAbility {
+    pub fn all() -> &'static [DragonAbility] {
+        &[DragonAbility::Hoard, DragonAbility::Roar, DragonAbility::Flame]
+    }
+
+    pub fn name(&self) -> &str {
+        match self {
+            DragonAbility::Hoard => "財宝の番人",
+            DragonAbility::Roar => "咆哮",
+            DragonAbility::Flame => "竜炎",
+        }
+    }
+
+    pub fn description(&self) -> &str {
+        match self {
+            DragonAbility::Hoard => "ゴールデン取得でCPS×(2×Lv)秒分",
+            DragonAbility::Roar => "ゴールデン取得でバフ+5秒",
+            DragonAbility::Flame => "25%で次のゴールデンがすぐ出現",
+        }
+    }
+
+    pub fn unlock_level(&self) -> u32 {
+        match self {
+            DragonAbility::Hoard => 2,
+            DragonAbility::Roar => 4,
+            DragonAbility::Flame => 6,
+        }
+    }
+}
+
+/// How long a dragon hunt waits for an answer (30 seconds).
+pub const DRAGON_HUNT_WINDOW: u32 = 300;
+
+/// Answers to the periodic dragon hunt (ドラゴン狩り).
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum HuntChoice {
+    /// 狩りに出す: 40% + 5% per level to win CPS × 120 seconds, else nothing.
+    Hunt,
+    /// 採集させる: CPS × 30 seconds, guaranteed.
+    Forage,
+    /// 休ませる: +5 feeding progress (not at max level).
+    Rest,
+}
+
+impl HuntChoice {
+    pub fn all() -> &'static [HuntChoice] {
+        &[HuntChoice::Hunt, HuntChoice::Forage, HuntChoice::Rest]
+    }
+
+    pub fn name(&self) -> &str {
+        match self {
+            HuntChoice::Hunt => "狩りに出す",
+            HuntChoice::Forage => "採集させる",
+            HuntChoice::Rest => "休ませる",
+        }
+    }
+
+    pub fn description(&self) -> &str {
+        match self {
+            HuntChoice::Hunt => "成功でCPS×120秒分 (Lvで成功率UP)",
+            HuntChoice::Forage => "確実にCPS×30秒分",
+            HuntChoice::Rest => "育成+5",
+        }
+    }
+
+    /// Key shown in the hunt panel (Prestige tab).
+    pub fn key(&self) -> char {
+        match self {
+            HuntChoice::Hunt => 'H',
+            HuntChoice::Forage => 'G',
+            HuntChoice::Rest => 'N',
+        }
+    }
+}
+
+// ═══════════════════════════════════════════════════════
+// Garden — 菜園 (Farm のマイルストーンで解放)
+// ═══════════════════════════════════════════════════════
+
+/// Garden plot size (columns × rows).
+pub const GARDEN_W: usize = 4;
+pub const GARDEN_H: usize = 3;
+
+/// Seeds bought with cookies and planted in the garden.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum SeedKind {
+    /// パン小麦: harvest for CPS × 60 seconds of cookies.
+    Bakerwheat,
+    /// 親指コーン: harvest for a click frenzy.
+    Thumbcorn,
+    /// 老婆米: harvest for a production frenzy.
+    Cronerice,
+    /// 砂糖きび: harvest for sugar.
+    Sugarcane,
+}
+
+impl SeedKind {
+    pub fn all() -> &'static [SeedKind] {
+        &[
+            SeedKind::Bakerwheat,
+            SeedKind::Thumbcorn,
+            SeedKind::Cronerice,
+            SeedKind::Sugarcane,
+        ]
+    }
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            SeedKind::Bakerwheat => "パン小麦",
+            SeedKind::Thumbcorn => "親指コーン",
+            SeedKind::Cronerice => "老婆米",
+            SeedKind::Sugarcane => "砂糖きび",
+        }
+    }
+
+    pub fn description(&self) -> &'static str {
+        match self {
+            SeedKind::Bakerwheat => "CPS×60秒分のクッキー",
+            SeedKind::Thumbcorn => "クリック×3 (20秒)",
+            SeedKind::Cronerice => "生産×1.5 (30秒)",
+            SeedKind::Sugarcane => "砂糖+1",
+        }
+    }
+
+    /// Glyph for a mature plant (sprouts share one glyph).
+    pub fn glyph(&self) -> &'static str {
+        match self {
+            SeedKind::Bakerwheat => "🌾",
+            SeedKind::Thumbcorn => "🌽",
+            SeedKind::Cronerice => "🍚",
+            SeedKind::Sugarcane => "🎋",
+        }
+    }
+
+    /// Seed price in seconds of current CPS (floored by `min_cost`).
+    fn cost_seconds(&self) -> f64 {
+        match self {
+            SeedKind::Bakerwheat => 30.0,
+            SeedKind::Thumbcorn => 60.0,
+            SeedKind::Cronerice => 90.0,
+            SeedKind::Sugarcane => 300.0,
+        }
+    }
+
+    fn min_cost(&self) -> f64 {
+        match self {
+            SeedKind::Bakerwheat => 1_000.0,
+            SeedKind::Thumbcorn => 5_000.0,
+            SeedKind::Cronerice => 10_000.0,
+            SeedKind::Sugarcane => 50_000.0,
+        }
+    }
+
+    /// Seed price at the given CPS.
+    pub fn cost(&self, cps: f64) -> f64 {
+        (cps * self.cost_seconds()).max(self.min_cost())
+    }
+
+    /// Growth ticks to maturity at speed 1.0.
+    pub fn grow_ticks(&self) -> u32 {
+        match self {
+            SeedKind::Bakerwheat => 600,   // 1 min
+            SeedKind::Thumbcorn => 1200,   // 2 min
+            SeedKind::Cronerice => 1800,   // 3 min
+            SeedKind::Sugarcane => 6000,   // 10 min
+        }
+    }
+
+    pub fn index(&self) -> usize {
+        match self {
+            SeedKind::Bakerwheat => 0,
+            SeedKind::Thumbcorn => 1,
+            SeedKind::Cronerice => 2,
+            SeedKind::Sugarcane => 3,
+        }
+    }
+
+    pub fn from_index(idx: usize) -> Option<SeedKind> {
+        SeedKind::all().get(idx).copied()
+    }
+}
+
+/// A seed growing in one garden plot.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Plant {
+    pub seed: SeedKind,
+    /// Growth so far (ticks at speed 1.0); mature at `seed.grow_ticks()`.
+    pub growth: f64,
+}
+
+impl Plant {
+    pub fn is_mature(&self) -> bool {
+        self.growth >= self.seed.grow_ticks() as f64
+    }
+
+    /// Growth progress (0.0 to 1.0).
+    pub fn progress(&self) -> f64 {
+        (self.growth / self.seed.grow_ticks() as f64).min(1.0)
+    }
+}
+
+// ═══════════════════════════════════════════════════════
+// Futures — クッキー先物 (Mine のマイルストーンで解放)
+// ═══════════════════════════════════════════════════════
+
+/// Ticks between futures price steps (1 second).
+pub const FUTURES_STEP_TICKS: u32 = 10;
+/// Price samples kept for the futures chart.
+pub const FUTURES_HISTORY_LEN: usize = 40;
+/// Share of current cookies invested by each buy button.
+pub const FUTURES_BUY_FRACTIONS: [f64; 2] = [0.1, 0.5];
+/// Largest single-step move (±8%).
+const FUTURES_VOLATILITY: f64 = 0.08;
+/// Pull back toward par (1.0) each step, so the price never drifts away for good.
+const FUTURES_REVERSION: f64 = 0.05;
+const FUTURES_MIN_PRICE: f64 = 0.25;
+const FUTURES_MAX_PRICE: f64 = 4.0;
+
+/// Cookie futures ticker and the player's position in it.
+///
+/// The price is a random walk driven by its own xorshift seed (not
+/// `rng_state`), so the same seed always draws the same chart regardless
+/// of golden cookies or other random events.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Futures {
+    /// Current price (1.0 = par).
+    pub price: f64,
+    /// Walk RNG state (xorshift32, never 0).
+    pub seed: u32,
+    /// Ticks until the next price step.
+    pub ticks_to_step: u32,
+    /// Recent prices for the chart (not saved).
+    pub history: Vec<f64>,
+    /// Units held (cookies invested / price at the time).
+    pub units: f64,
+    /// Cookies paid for the units held.
+    pub cost_basis: f64,
+}
+
+impl Futures {
+    pub fn new(seed: u32) -> Self {
+        Self {
+            price: 1.0,
+            seed: seed.max(1),
+            ticks_to_step: FUTURES_STEP_TICKS,
+            history: Vec::new(),
+            units: 0.0,
+            cost_basis: 0.0,
+        }
+    }
+
+    /// Advance the walk by one step.
+    pub fn step(&mut self) {
+        let mut x = self.seed;
+        x ^= x << 13;
+        x ^= x >> 17;
+        x ^= x << 5;
+        self.seed = x;
+        let shock = (x as f64 / u32::MAX as f64 - 0.5) * 2.0 * FUTURES_VOLATILITY;
+        let price = self.price * (1.0 + shock);
+        self.price = (price + (1.0 - price) * FUTURES_REVERSION)
+            .clamp(FUTURES_MIN_PRICE, FUTURES_MAX_PRICE);
+        self.history.push(self.price);
+        if self.history.len() > FUTURES_HISTORY_LEN {
+            self.history.remove(0);
+        }
+    }
+
+    /// Advance by `delta_ticks`, stepping once every [`FUTURES_STEP_TICKS`].
+    pub fn advance(&mut self, delta_ticks: u32) {
+        let mut remaining = delta_ticks;
+        while remaining >= self.ticks_to_step {
+            remaining -= self.ticks_to_step;
+            self.ticks_to_step = FUTURES_STEP_TICKS;
+            self.step();
+        }
+        self.ticks_to_step -= remaining;
+    }
+
+    pub fn has_position(&self) -> bool {
+        self.units > 0.0
+    }
+
+    /// Cookies the position would pay out if sold now.
+    pub fn position_value(&self) -> f64 {
+        self.units * self.price
+    }
+
+    /// Unrealized gain (negative for a loss).
+    pub fn profit(&self) -> f64 {
+        self.position_value() - self.cost_basis
+    }
+}
+
+// ═══════════════════════════════════════════════════════
+// Challenge runs — 縛りプレイ
+// ═══════════════════════════════════════════════════════
+
+/// Challenge run, started from the Prestige tab together with a reset.
+/// Its modifier applies until the goal is reached or the run ends.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ChallengeKind {
+    /// クリック禁止: manual clicks and the auto-clicker produce nothing.
+    NoClick,
+    /// カーソル封印: Cursors produce nothing.
+    NoCursors,
+    /// 物価高騰: all costs +50%.
+    Expensive,
+}
+
+impl ChallengeKind {
+    pub fn all() -> &'static [ChallengeKind] {
+        &[
+            ChallengeKind::NoClick,
+            ChallengeKind::NoCursors,
+            ChallengeKind::Expensive,
+        ]
+    }
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            ChallengeKind::NoClick => "クリック禁止",
+            ChallengeKind::NoCursors => "カーソル封印",
+            ChallengeKind::Expensive => "物価高騰",
+        }
+    }
+
+    pub fn description(&self) -> &'static str {
+        match self {
+            ChallengeKind::NoClick => "クリック・オートクリックが無効",
+            ChallengeKind::NoCursors => "カーソルの生産が0",
+            ChallengeKind::Expensive => "購入コスト+50%",
+        }
+    }
+
+    /// Cookies to bake in the challenge run to complete it.
+    pub fn goal(&self) -> f64 {
+        match self {
+            ChallengeKind::NoClick => 1e9,
+            ChallengeKind::NoCursors => 1e9,
+            ChallengeKind::Expensive => 5e9,
+        }
+    }
+
+    /// Bonus heavenly chips for each completion.
+    pub fn reward_chips(&self) -> u64 {
+        match self {
+            ChallengeKind::NoClick => 5,
+            ChallengeKind::NoCursors => 3,
+            ChallengeKind::Expensive => 5,
+        }
+    }
+
+    pub fn index(&self) -> usize {
+        match self {
+            ChallengeKind::NoClick => 0,
+            ChallengeKind::NoCursors => 1,
+            ChallengeKind::Expensive => 2,
+        }
+    }
+
+    pub fn from_index(idx: usize) -> Option<ChallengeKind> {
+        ChallengeKind::all().get(idx).copied()
+    }
+}
+
 /// Full state of a Cookie Factory game.
 pub struct CookieState {
     /// Total cookies accumulated.
@@ -769,6 +1779,8 @@ pub struct CookieState {
     pub log: Vec<CookieLogEntry>,
     /// Whether showing upgrades panel.
     pub show_upgrades: bool,
+    /// Scroll offset of the upgrades list (clamped by `ScrollableTab` in render).
+    pub upgrades_scroll: Cell<u16>,
     /// Whether showing research panel.
     pub show_research: bool,
     /// Animation frame counter (incremented every tick).
@@ -777,8 +1789,10 @@ pub struct CookieState {
     pub click_flash: u32,
     /// Purchase celebration flash timer.
     pub purchase_flash: u32,
-    /// Active floating particles.
-    pub particles: Vec<Particle>,
+    /// Active floating particles (capped by `animation_intensity`).
+    pub particles: ParticlePool,
+    /// Visual effect setting; sets the particle cap.
+    pub animation_intensity: AnimationIntensity,
     /// Synergy bonus multiplier (from upgrades, default 1.0).
     pub synergy_multiplier: f64,
     /// Cross-synergy bonuses: (source, target, bonus_per_unit).
@@ -789,6 +1803,8 @@ pub struct CookieState {
     pub golden_event: Option<GoldenCookieEvent>,
     /// Active buffs from claimed golden cookies.
     pub active_buffs: Vec<ActiveBuff>,
+    /// 積み上限を超えて待っているバフ (古い順)。
+    pub queued_buffs: Vec<ActiveBuff>,
     /// Total golden cookies claimed (for stats).
     pub golden_cookies_claimed: u32,
     /// Pseudo-random state for deterministic golden cookie spawning.
@@ -805,12 +1821,21 @@ pub struct CookieState {
     pub milestones: Vec<Milestone>,
     /// Milk level: achieved milestones / total milestones (0.0 to 1.0+).
     pub milk: f64,
+    /// Milk level shown by the milestones bar. Eases toward `milk` each
+    /// tick so claiming a milestone visibly fills the bar (UI only).
+    pub milk_display: f64,
     /// Whether showing milestones panel.
     pub show_milestones: bool,
+    /// Scroll offset of the milestones list (clamped by `ScrollableTab` in render).
+    pub milestones_scroll: Cell<u16>,
     /// Flash timer for milestone achievement notification.
     pub milestone_flash: u32,
     /// Kitten multiplier applied to CPS (computed from milk × kitten upgrades).
     pub kitten_multiplier: f64,
+    /// Cookie arts unlocked by milestone rewards (Classic is implicit; kept on prestige).
+    pub cookie_arts: Vec<CookieArt>,
+    /// Cookie art drawn on the main panel.
+    pub cookie_art: CookieArt,
 
     // === Prestige (転生) system — survives reset ===
     /// Total prestige resets performed.
@@ -825,7 +1850,7 @@ pub struct CookieState {
     pub cookies_all_runs: f64,
     /// Whether showing the prestige/stats panel.
     pub show_prestige: bool,
-    /// Active sub-section within the Prestige tab (0=upgrades, 1=boosts, 2=dragon, 3=stats).
+    /// Active sub-section within the Prestige tab (0=upgrades, 1=boosts, 2=dragon, 3=stats, 4=challenges).
     pub prestige_section: u8,
     /// Scroll offset within the active Prestige sub-section (in lines).
     pub prestige_scroll: u16,
@@ -849,6 +1874,20 @@ pub struct CookieState {
     /// Ticks until next auto-click (internal timer).
     pub auto_clicker_timer: u32,
 
+    // === Purchase queue ===
+    /// Reserved producer purchases, processed front-first in `tick`.
+    /// Not saved: it's a short-lived instruction, not progress.
+    pub purchase_queue: Vec<QueuedPurchase>,
+    /// When true, clicking a producer queues `QUEUE_BATCH` units instead of buying one.
+    pub queue_mode: bool,
+
+    // === Upgrade pins ===
+    /// Upgrades the player is working toward (indices into `upgrades`, at most
+    /// `MAX_PINNED_UPGRADES`). Shown on the producers view; unpinned on purchase.
+    pub pinned_upgrades: Vec<usize>,
+    /// When true, tapping an upgrade toggles its pin instead of buying it.
+    pub pin_mode: bool,
+
     // === Statistics — survives reset ===
     /// Total ticks played across all runs.
     pub total_ticks: u64,
@@ -864,6 +1903,18 @@ pub struct CookieState {
     pub combo_count: u32,
     /// Peak combo in current session.
     pub best_combo: u32,
+    /// Ticks until a double-tap critical click is available again.
+    pub crit_click_cooldown: u32,
+    /// Learned combo skills (reset on prestige).
+    pub combo_skills: Vec<ComboSkill>,
+
+    // === UI state (not saved) ===
+    /// Producer whose detail card replaces the producers list (opened by long-press).
+    pub producer_detail: Option<ProducerKind>,
+    /// Research node waiting for the path-choice confirm dialog (first purchase only).
+    pub research_confirm: Option<usize>,
+    /// CPS breakdown card replaces the producers list (opened from the stats row).
+    pub show_cps_breakdown: bool,
 
     // === Analytics (not saved) ===
     /// CPS history for sparkline graph (sampled every 10 ticks = 1 second).
@@ -893,12 +1944,50 @@ pub struct CookieState {
     pub market_ticks_left: u32,
 
     // === Dragon (転生後も保持) ===
-    /// Dragon level (0 = egg, max 7).
+    /// Dragon level (0 = egg, max [`MAX_DRAGON_LEVEL`]).
     pub dragon_level: u32,
     /// Selected dragon aura.
     pub dragon_aura: DragonAura,
+    /// Second aura slot (active from [`DRAGON_DUAL_AURA_LEVEL`]).
+    pub dragon_aura2: DragonAura,
     /// Total producers fed to dragon (across all feeding).
     pub dragon_fed_total: u32,
+    /// Ticks left to answer the current dragon hunt (None = no hunt). Not saved.
+    pub dragon_hunt: Option<u32>,
+    /// Ticks until the next dragon hunt is offered. Not saved.
+    pub dragon_hunt_next: u32,
+
+    // === Garden (転生後も保持) ===
+    /// Whether showing the garden panel.
+    pub show_garden: bool,
+    /// Garden plots, row-major (`GARDEN_W * GARDEN_H`).
+    pub garden: Vec<Option<Plant>>,
+    /// Seed planted by tapping an empty plot.
+    pub selected_seed: SeedKind,
+
+    // === Futures (相場は転生後も継続、建玉は転生で消える) ===
+    /// Whether showing the futures panel.
+    pub show_futures: bool,
+    /// Cookie futures ticker and position.
+    pub futures: Futures,
+
+    // === Challenge runs ===
+    /// Challenge in progress this run (cleared on completion or reset).
+    pub active_challenge: Option<ChallengeKind>,
+    /// Challenges completed at least once (badges). Survives reset.
+    pub challenges_completed: Vec<ChallengeKind>,
+    /// Heavenly chips awarded by challenges (included in `heavenly_chips`,
+    /// excluded from the cookies → chips calculation).
+    pub bonus_chips: u64,
+
+    // === News ticker (not saved) ===
+    /// Headline currently shown above the tab bar.
+    pub news_headline: String,
+    /// Ticks until the next headline.
+    pub news_ticks_left: u32,
+    /// How many headlines have been shown (picks the next one without
+    /// touching `rng_state`, so the ticker never changes golden cookie rolls).
+    pub news_seq: u32,
 }
 
 impl CookieState {
@@ -924,16 +2013,19 @@ impl CookieState {
                 is_important: true,
             }],
             show_upgrades: false,
+            upgrades_scroll: Cell::new(0),
             show_research: false,
             anim_frame: 0,
             click_flash: 0,
             purchase_flash: 0,
-            particles: Vec::new(),
+            particles: ParticlePool::new(MAX_PARTICLES),
+            animation_intensity: AnimationIntensity::Full,
             synergy_multiplier: 1.0,
             cross_synergies: Vec::new(),
             golden_next_spawn: 200, // First golden cookie after 20 seconds (faster early excitement)
             golden_event: None,
             active_buffs: Vec::new(),
+            queued_buffs: Vec::new(),
             golden_cookies_claimed: 0,
             rng_state: 42,
             count_scalings: Vec::new(),
@@ -942,9 +2034,13 @@ impl CookieState {
             active_discount: 0.0,
             milestones,
             milk: 0.0,
+            milk_display: 0.0,
             show_milestones: false,
+            milestones_scroll: Cell::new(0),
             milestone_flash: 0,
             kitten_multiplier: 1.0,
+            cookie_arts: Vec::new(),
+            cookie_art: CookieArt::Classic,
             // Prestige fields
             prestige_count: 0,
             heavenly_chips: 0,
@@ -963,6 +2059,12 @@ impl CookieState {
             // Auto-clicker
             auto_clicker_enabled: false,
             auto_clicker_timer: 0,
+            // Purchase queue
+            purchase_queue: Vec::new(),
+            queue_mode: false,
+            // Upgrade pins
+            pinned_upgrades: Vec::new(),
+            pin_mode: false,
             // Statistics
             total_ticks: 0,
             best_cps: 0.0,
@@ -971,6 +2073,12 @@ impl CookieState {
             click_cooldown: 0,
             combo_count: 0,
             best_combo: 0,
+            crit_click_cooldown: 0,
+            combo_skills: Vec::new(),
+            // UI state
+            producer_detail: None,
+            research_confirm: None,
+            show_cps_breakdown: false,
             // Analytics
             cps_history: Vec::new(),
             cps_sample_counter: 0,
@@ -987,7 +2095,21 @@ impl CookieState {
             // Dragon
             dragon_level: 0,
             dragon_aura: DragonAura::None,
+            dragon_aura2: DragonAura::None,
             dragon_fed_total: 0,
+            dragon_hunt: None,
+            dragon_hunt_next: 1800, // First hunt after ~3 minutes with a dragon
+            show_garden: false,
+            garden: vec![None; GARDEN_W * GARDEN_H],
+            selected_seed: SeedKind::Bakerwheat,
+            show_futures: false,
+            futures: Futures::new(7),
+            active_challenge: None,
+            challenges_completed: Vec::new(),
+            bonus_chips: 0,
+            news_headline: String::new(),
+            news_ticks_left: 0,
+            news_seq: 0,
         }
     }
 
@@ -1793,54 +2915,63 @@ impl CookieState {
                 description: "クッキーを100枚焼く".into(),
                 condition: MilestoneCondition::TotalCookies(100.0),
                 status: MilestoneStatus::Locked,
+                reward: MilestoneReward::Milk,
             },
             Milestone {
                 name: "駆け出しベイカー".into(),
                 description: "クッキーを1,000枚焼く".into(),
                 condition: MilestoneCondition::TotalCookies(1_000.0),
                 status: MilestoneStatus::Locked,
+                reward: MilestoneReward::Milk,
             },
             Milestone {
                 name: "パン屋の朝".into(),
                 description: "クッキーを10,000枚焼く".into(),
                 condition: MilestoneCondition::TotalCookies(10_000.0),
                 status: MilestoneStatus::Locked,
+                reward: MilestoneReward::Sugar(1),
             },
             Milestone {
                 name: "繁盛店".into(),
                 description: "クッキーを100,000枚焼く".into(),
                 condition: MilestoneCondition::TotalCookies(100_000.0),
                 status: MilestoneStatus::Locked,
+                reward: MilestoneReward::Milk,
             },
             Milestone {
                 name: "クッキー長者".into(),
                 description: "クッキーを1,000,000枚焼く".into(),
                 condition: MilestoneCondition::TotalCookies(1_000_000.0),
                 status: MilestoneStatus::Locked,
+                reward: MilestoneReward::Sugar(2),
             },
             Milestone {
                 name: "クッキー大王".into(),
                 description: "クッキーを10,000,000枚焼く".into(),
                 condition: MilestoneCondition::TotalCookies(10_000_000.0),
                 status: MilestoneStatus::Locked,
+                reward: MilestoneReward::Milk,
             },
             Milestone {
                 name: "クッキー財閥".into(),
                 description: "クッキーを100,000,000枚焼く".into(),
                 condition: MilestoneCondition::TotalCookies(100_000_000.0),
                 status: MilestoneStatus::Locked,
+                reward: MilestoneReward::Sugar(3),
             },
             Milestone {
                 name: "クッキー王朝".into(),
                 description: "クッキーを1,000,000,000枚焼く".into(),
                 condition: MilestoneCondition::TotalCookies(1_000_000_000.0),
                 status: MilestoneStatus::Locked,
+                reward: MilestoneReward::Milk,
             },
             Milestone {
                 name: "クッキー帝国".into(),
                 description: "クッキーを10,000,000,000枚焼く".into(),
                 condition: MilestoneCondition::TotalCookies(10_000_000_000.0),
                 status: MilestoneStatus::Locked,
+                reward: MilestoneReward::Art(CookieArt::Golden),
             },
             // === Click milestones ===
             Milestone {
@@ -1848,18 +2979,21 @@ impl CookieState {
                 description: "100回クリック".into(),
                 condition: MilestoneCondition::TotalClicks(100),
                 status: MilestoneStatus::Locked,
+                reward: MilestoneReward::Milk,
             },
             Milestone {
                 name: "連打の達人".into(),
                 description: "1,000回クリック".into(),
                 condition: MilestoneCondition::TotalClicks(1_000),
                 status: MilestoneStatus::Locked,
+                reward: MilestoneReward::Art(CookieArt::Chocolate),
             },
             Milestone {
                 name: "指が止まらない".into(),
                 description: "10,000回クリック".into(),
                 condition: MilestoneCondition::TotalClicks(10_000),
                 status: MilestoneStatus::Locked,
+                reward: MilestoneReward::Sugar(3),
             },
             // === CPS milestones ===
             Milestone {
@@ -1867,42 +3001,49 @@ impl CookieState {
                 description: "CPS 10 達成".into(),
                 condition: MilestoneCondition::CpsReached(10.0),
                 status: MilestoneStatus::Locked,
+                reward: MilestoneReward::CookieLump(600.0),
             },
             Milestone {
                 name: "小さな工場".into(),
                 description: "CPS 100 達成".into(),
                 condition: MilestoneCondition::CpsReached(100.0),
                 status: MilestoneStatus::Locked,
+                reward: MilestoneReward::Milk,
             },
             Milestone {
                 name: "工業化の波".into(),
                 description: "CPS 500 達成".into(),
                 condition: MilestoneCondition::CpsReached(500.0),
                 status: MilestoneStatus::Locked,
+                reward: MilestoneReward::CookieLump(900.0),
             },
             Milestone {
                 name: "産業革命".into(),
                 description: "CPS 1,000 達成".into(),
                 condition: MilestoneCondition::CpsReached(1_000.0),
                 status: MilestoneStatus::Locked,
+                reward: MilestoneReward::Milk,
             },
             Milestone {
                 name: "大量生産".into(),
                 description: "CPS 5,000 達成".into(),
                 condition: MilestoneCondition::CpsReached(5_000.0),
                 status: MilestoneStatus::Locked,
+                reward: MilestoneReward::CookieLump(1_200.0),
             },
             Milestone {
                 name: "クッキー王国".into(),
                 description: "CPS 10,000 達成".into(),
                 condition: MilestoneCondition::CpsReached(10_000.0),
                 status: MilestoneStatus::Locked,
+                reward: MilestoneReward::CookieLump(1_800.0),
             },
             Milestone {
                 name: "無限の生産力".into(),
                 description: "CPS 100,000 達成".into(),
                 condition: MilestoneCondition::CpsReached(100_000.0),
                 status: MilestoneStatus::Locked,
+                reward: MilestoneReward::CookieLump(3_600.0),
             },
             // === Producer milestones ===
             Milestone {
@@ -1910,66 +3051,77 @@ impl CookieState {
                 description: "Cursor 10台".into(),
                 condition: MilestoneCondition::ProducerCount(ProducerKind::Cursor, 10),
                 status: MilestoneStatus::Locked,
+                reward: MilestoneReward::Milk,
             },
             Milestone {
                 name: "Cursor軍団".into(),
                 description: "Cursor 50台".into(),
                 condition: MilestoneCondition::ProducerCount(ProducerKind::Cursor, 50),
                 status: MilestoneStatus::Locked,
+                reward: MilestoneReward::Milk,
             },
             Milestone {
                 name: "Cursorの海".into(),
                 description: "Cursor 100台".into(),
                 condition: MilestoneCondition::ProducerCount(ProducerKind::Cursor, 100),
                 status: MilestoneStatus::Locked,
+                reward: MilestoneReward::Milk,
             },
             Milestone {
                 name: "おばあちゃんの集い".into(),
                 description: "Grandma 10台".into(),
                 condition: MilestoneCondition::ProducerCount(ProducerKind::Grandma, 10),
                 status: MilestoneStatus::Locked,
+                reward: MilestoneReward::Milk,
             },
             Milestone {
                 name: "おばあちゃんの楽園".into(),
                 description: "Grandma 50台".into(),
                 condition: MilestoneCondition::ProducerCount(ProducerKind::Grandma, 50),
                 status: MilestoneStatus::Locked,
+                reward: MilestoneReward::Art(CookieArt::Heart),
             },
             Milestone {
                 name: "農場主".into(),
                 description: "Farm 10台".into(),
                 condition: MilestoneCondition::ProducerCount(ProducerKind::Farm, 10),
                 status: MilestoneStatus::Locked,
+                reward: MilestoneReward::Milk,
             },
             Milestone {
                 name: "大農場経営".into(),
                 description: "Farm 50台".into(),
                 condition: MilestoneCondition::ProducerCount(ProducerKind::Farm, 50),
                 status: MilestoneStatus::Locked,
+                reward: MilestoneReward::Milk,
             },
             Milestone {
                 name: "鉱山王".into(),
                 description: "Mine 10台".into(),
                 condition: MilestoneCondition::ProducerCount(ProducerKind::Mine, 10),
                 status: MilestoneStatus::Locked,
+                reward: MilestoneReward::Milk,
             },
             Milestone {
                 name: "深層採掘".into(),
                 description: "Mine 50台".into(),
                 condition: MilestoneCondition::ProducerCount(ProducerKind::Mine, 50),
                 status: MilestoneStatus::Locked,
+                reward: MilestoneReward::Milk,
             },
             Milestone {
                 name: "工場長".into(),
                 description: "Factory 10台".into(),
                 condition: MilestoneCondition::ProducerCount(ProducerKind::Factory, 10),
                 status: MilestoneStatus::Locked,
+                reward: MilestoneReward::Milk,
             },
             Milestone {
                 name: "産業コンツェルン".into(),
                 description: "Factory 50台".into(),
                 condition: MilestoneCondition::ProducerCount(ProducerKind::Factory, 50),
                 status: MilestoneStatus::Locked,
+                reward: MilestoneReward::Milk,
             },
             // === New producer milestones ===
             Milestone {
@@ -1977,36 +3129,42 @@ impl CookieState {
                 description: "Temple 10台".into(),
                 condition: MilestoneCondition::ProducerCount(ProducerKind::Temple, 10),
                 status: MilestoneStatus::Locked,
+                reward: MilestoneReward::Milk,
             },
             Milestone {
                 name: "大神殿".into(),
                 description: "Temple 50台".into(),
                 condition: MilestoneCondition::ProducerCount(ProducerKind::Temple, 50),
                 status: MilestoneStatus::Locked,
+                reward: MilestoneReward::Sugar(3),
             },
             Milestone {
                 name: "魔法使い".into(),
                 description: "WzTower 10台".into(),
                 condition: MilestoneCondition::ProducerCount(ProducerKind::WizardTower, 10),
                 status: MilestoneStatus::Locked,
+                reward: MilestoneReward::Milk,
             },
             Milestone {
                 name: "大魔導師".into(),
                 description: "WzTower 50台".into(),
                 condition: MilestoneCondition::ProducerCount(ProducerKind::WizardTower, 50),
                 status: MilestoneStatus::Locked,
+                reward: MilestoneReward::Milk,
             },
             Milestone {
                 name: "宇宙輸送".into(),
                 description: "Shipment 10台".into(),
                 condition: MilestoneCondition::ProducerCount(ProducerKind::Shipment, 10),
                 status: MilestoneStatus::Locked,
+                reward: MilestoneReward::Milk,
             },
             Milestone {
                 name: "銀河帝国".into(),
                 description: "Shipment 50台".into(),
                 condition: MilestoneCondition::ProducerCount(ProducerKind::Shipment, 50),
                 status: MilestoneStatus::Locked,
+                reward: MilestoneReward::Art(CookieArt::Star),
             },
             // === Higher CPS milestones ===
             Milestone {
@@ -2014,12 +3172,14 @@ impl CookieState {
                 description: "CPS 1,000,000 達成".into(),
                 condition: MilestoneCondition::CpsReached(1_000_000.0),
                 status: MilestoneStatus::Locked,
+                reward: MilestoneReward::Milk,
             },
             Milestone {
                 name: "クッキー宇宙".into(),
                 description: "CPS 100,000,000 達成".into(),
                 condition: MilestoneCondition::CpsReached(100_000_000.0),
                 status: MilestoneStatus::Locked,
+                reward: MilestoneReward::Milk,
             },
             // === Higher cookie milestones ===
             Milestone {
@@ -2027,6 +3187,7 @@ impl CookieState {
                 description: "クッキーを1,000,000,000,000枚焼く".into(),
                 condition: MilestoneCondition::TotalCookies(1_000_000_000_000.0),
                 status: MilestoneStatus::Locked,
+                reward: MilestoneReward::Sugar(5),
             },
             // === Golden cookie milestones ===
             Milestone {
@@ -2034,18 +3195,21 @@ impl CookieState {
                 description: "ゴールデンクッキーを5回取得".into(),
                 condition: MilestoneCondition::GoldenClaimed(5),
                 status: MilestoneStatus::Locked,
+                reward: MilestoneReward::Milk,
             },
             Milestone {
                 name: "ゴールドハンター".into(),
                 description: "ゴールデンクッキーを25回取得".into(),
                 condition: MilestoneCondition::GoldenClaimed(25),
                 status: MilestoneStatus::Locked,
+                reward: MilestoneReward::Sugar(2),
             },
             Milestone {
                 name: "ゴールデンマスター".into(),
                 description: "ゴールデンクッキーを77回取得".into(),
                 condition: MilestoneCondition::GoldenClaimed(77),
                 status: MilestoneStatus::Locked,
+                reward: MilestoneReward::Milk,
             },
         ]
     }
@@ -2494,7 +3658,7 @@ impl CookieState {
 
     /// Producers needed to reach next dragon level.
     pub fn dragon_feed_cost(&self) -> u32 {
-        if self.dragon_level >= 7 {
+        if self.dragon_level >= MAX_DRAGON_LEVEL {
             return 0; // max level
         }
         match self.dragon_level {
@@ -2526,47 +3690,55 @@ impl CookieState {
         self.dragon_fed_total.saturating_sub(total_needed_for_current)
     }
 
+    /// Whether `aura` is equipped in an active slot (none while still an egg).
+    pub fn has_dragon_aura(&self, aura: &DragonAura) -> bool {
+        self.dragon_level > 0
+            && (self.dragon_aura == *aura
+                || (self.dragon_level >= DRAGON_DUAL_AURA_LEVEL && self.dragon_aura2 == *aura))
+    }
+
+    /// Abilities unlocked at the current dragon level.
+    pub fn dragon_abilities(&self) -> Vec<DragonAbility> {
+        DragonAbility::all()
+            .iter()
+            .copied()
+            .filter(|a| self.dragon_level >= a.unlock_level())
+            .collect()
+    }
+
     /// CPS multiplier from dragon aura.
     pub fn dragon_cps_modifier(&self) -> f64 {
-        if self.dragon_level == 0 {
-            return 1.0;
-        }
-        match self.dragon_aura {
-            DragonAura::BreathOfRiches => 1.0 + 0.15 * self.dragon_level as f64,
-            _ => 1.0,
+        if self.has_dragon_aura(&DragonAura::BreathOfRiches) {
+            1.0 + 0.15 * self.dragon_level as f64
+        } else {
+            1.0
         }
     }
 
     /// Click power multiplier from dragon aura.
     pub fn dragon_click_modifier(&self) -> f64 {
-        if self.dragon_level == 0 {
-            return 1.0;
-        }
-        match self.dragon_aura {
-            DragonAura::DragonCursor => 1.0 + 0.20 * self.dragon_level as f64,
-            _ => 1.0,
+        if self.has_dragon_aura(&DragonAura::DragonCursor) {
+            1.0 + 0.20 * self.dragon_level as f64
+        } else {
+            1.0
         }
     }
 
     /// Cost reduction from dragon aura (multiplicative).
     pub fn dragon_cost_modifier(&self) -> f64 {
-        if self.dragon_level == 0 {
-            return 1.0;
-        }
-        match self.dragon_aura {
-            DragonAura::ElderPact => (1.0 - 0.05 * self.dragon_level as f64).max(0.3),
-            _ => 1.0,
+        if self.has_dragon_aura(&DragonAura::ElderPact) {
+            (1.0 - 0.05 * self.dragon_level as f64).max(0.3)
+        } else {
+            1.0
         }
     }
 
     /// Golden cookie spawn speed modifier from dragon aura (< 1.0 = faster).
     pub fn dragon_golden_speed(&self) -> f64 {
-        if self.dragon_level == 0 {
-            return 1.0;
-        }
-        match self.dragon_aura {
-            DragonAura::DragonHarvest => (1.0 - 0.10 * self.dragon_level as f64).max(0.3),
-            _ => 1.0,
+        if self.has_dragon_aura(&DragonAura::DragonHarvest) {
+            (1.0 - 0.10 * self.dragon_level as f64).max(0.3)
+        } else {
+            1.0
         }
     }
 
@@ -2594,7 +3766,31 @@ impl CookieState {
         let dragon = self.dragon_cost_modifier();
         let discount = 1.0 - self.active_discount;
         let prestige = 1.0 - self.prestige_cost_reduction();
-        market * research * dragon * discount * prestige
+        market * research * dragon * discount * prestige * self.challenge_cost_modifier()
+    }
+
+    /// Cost multiplier from the active challenge (物価高騰: ×1.5).
+    pub fn challenge_cost_modifier(&self) -> f64 {
+        if self.active_challenge == Some(ChallengeKind::Expensive) {
+            1.5
+        } else {
+            1.0
+        }
+    }
+
+    /// Garden growth speed: milk (up to ×2 at full milk) × market phase.
+    pub fn garden_growth_rate(&self) -> f64 {
+        (1.0 + self.milk.min(1.0)) * self.market_phase.garden_growth_multiplier()
+    }
+
+    /// Whether the active challenge disables clicking (manual and auto).
+    pub fn clicks_disabled(&self) -> bool {
+        self.active_challenge == Some(ChallengeKind::NoClick)
+    }
+
+    /// Whether the active challenge zeroes this producer's output.
+    pub fn producer_disabled(&self, kind: &ProducerKind) -> bool {
+        self.active_challenge == Some(ChallengeKind::NoCursors) && *kind == ProducerKind::Cursor
     }
 
     /// Available heavenly chips (earned - spent).
@@ -2609,7 +3805,8 @@ impl CookieState {
     pub fn pending_heavenly_chips(&self) -> u64 {
         let total = self.cookies_all_runs + self.cookies_all_time;
         let total_chips = (total / 1e9).sqrt().floor() as u64;
-        total_chips.saturating_sub(self.heavenly_chips)
+        let baked_chips = self.heavenly_chips.saturating_sub(self.bonus_chips);
+        total_chips.saturating_sub(baked_chips)
     }
 
     /// Count of claimed milestones (milk applied).
@@ -2691,6 +3888,18 @@ impl CookieState {
             "research" => self.total_cps() >= 1000.0 || self.cookies_all_time >= 100_000.0,
             "milestones" => true,
             "prestige" => self.cookies_all_time >= 1_000_000_000.0 || self.prestige_count > 0,
+            // Milestones reset on prestige but the garden doesn't; keep it
+            // reachable while anything is still planted.
+            "garden" => {
+                self.milestones.iter().any(|m| {
+                    matches!(m.condition, MilestoneCondition::ProducerCount(ProducerKind::Farm, _))
+                        && m.status == MilestoneStatus::Claimed
+                }) || self.garden.iter().any(Option::is_some)
+            }
+            "futures" => self.milestones.iter().any(|m| {
+                matches!(m.condition, MilestoneCondition::ProducerCount(ProducerKind::Mine, _))
+                    && m.status == MilestoneStatus::Claimed
+            }),
             _ => true,
         }
     }
@@ -2762,6 +3971,7 @@ impl CookieState {
     fn cps_percent_extra(&self, base_total: f64) -> f64 {
         self.cps_percent_bonuses
             .iter()
+            .filter(|(target, _)| !self.producer_disabled(target))
             .map(|(target, pct)| {
                 let count = self.producers[target.index()].count as f64;
                 base_total * count * pct
@@ -2771,55 +3981,64 @@ impl CookieState {
 
     /// Total CPS including synergies, count scaling, CPS% bonuses, and active buffs.
     pub fn total_cps(&self) -> f64 {
+        self.cps_breakdown().total
+    }
+
+    /// The [`total_cps`](Self::total_cps) calculation with every step kept.
+    pub fn cps_breakdown(&self) -> CpsBreakdown {
         let research_syn = self.research_synergy_modifier();
         let research_cs = self.research_count_scaling_modifier();
 
         // Step 1: base CPS with synergies + count scaling
         let base: f64 = self.producers.iter().map(|p| {
+            if self.producer_disabled(&p.kind) {
+                return 0.0;
+            }
             let syn = self.synergy_bonus(&p.kind) * research_syn;
             let cs = self.count_scaling_bonus(&p.kind) * research_cs;
             p.cps_with_synergy(syn + cs)
         }).sum();
 
         // Step 2: CPS-percent bonuses (based on base total, to avoid infinite recursion)
-        let extra = self.cps_percent_extra(base);
-        let total = base + extra;
-
-        // Step 3: Apply kitten (milk) multiplier
-        let after_kitten = total * self.kitten_multiplier;
-
-        // Step 3.5: Apply prestige multiplier
-        let after_prestige = after_kitten * self.prestige_multiplier;
-
-        // Step 4: Apply research CPS multiplier
-        let after_research = after_prestige * self.research_cps_modifier();
+        let percent_extra = self.cps_percent_extra(base);
 
-        // Step 5: Apply dragon CPS aura
-        let after_dragon = after_research * self.dragon_cps_modifier();
-
-        // Step 6: Apply market phase
-        let after_market = after_dragon * self.market_phase.cps_multiplier();
-
-        // Step 7: Apply production frenzy buff (with research buff effect modifier)
+        // Step 7: production frenzy buff (with research buff effect modifier)
         let buff_effect_mult = self.research_buff_effect_modifier();
-        let mut multiplier = 1.0;
+        let mut buffs = 1.0;
         for buff in &self.active_buffs {
             if let GoldenEffect::ProductionFrenzy { multiplier: m } = &buff.effect {
                 let effective_m = 1.0 + (m - 1.0) * buff_effect_mult;
-                multiplier *= effective_m;
+                buffs *= effective_m;
             }
         }
 
-        // Step 7.5: Buff combo bonus — stacking different buff types is rewarded
-        let combo_mult = self.buff_combo_multiplier();
-
-        // Step 8: Apply sugar boost
-        let sugar_mult = self.sugar_boost_multiplier();
-
-        // Step 9: Apply savings bonus (reward for holding cookies)
-        let savings = self.savings_bonus();
-
-        after_market * multiplier * combo_mult * sugar_mult * savings
+        let mut breakdown = CpsBreakdown {
+            base,
+            percent_extra,
+            // Step 3: kitten (milk) multiplier
+            kitten: self.kitten_multiplier,
+            // Step 3.5: prestige multiplier
+            prestige: self.prestige_multiplier,
+            // Step 4: research CPS multiplier
+            research: self.research_cps_modifier(),
+            // Step 5: dragon CPS aura
+            dragon: self.dragon_cps_modifier(),
+            // Step 6: market phase
+            market: self.market_phase.cps_multiplier(),
+            buffs,
+            // Step 7.5: buff combo bonus — stacking different buff types is rewarded
+            buff_combo: self.buff_combo_multiplier(),
+            // Step 8: sugar boost
+            sugar: self.sugar_boost_multiplier(),
+            // Step 9: savings bonus (reward for holding cookies)
+            savings: self.savings_bonus(),
+            total: 0.0,
+        };
+        breakdown.total = breakdown
+            .factors()
+            .iter()
+            .fold(base + percent_extra, |cps, (_, factor)| cps * factor);
+        breakdown
     }
 
     /// Combo click multiplier: +2% per combo hit, max 3x at 100 combo.
@@ -2827,9 +4046,33 @@ impl CookieState {
         1.0 + (self.combo_count as f64 * 0.02).min(2.0)
     }
 
-    /// Critical click chance: 3% base + 0.1% per combo, max 15%.
+    /// Critical click chance: 3% base + 0.1% per combo, max 15%,
+    /// plus combo skill bonuses on top of the cap.
     pub fn critical_chance(&self) -> f64 {
-        (0.03 + self.combo_count as f64 * 0.001).min(0.15)
+        let skills: f64 = self.combo_skills.iter().map(|s| s.crit_chance_bonus()).sum();
+        (0.03 + self.combo_count as f64 * 0.001).min(0.15) + skills
+    }
+
+    /// Critical click multiplier: ×10 base + combo skills.
+    pub fn critical_multiplier(&self) -> f64 {
+        BASE_CRIT_MULTIPLIER
+            + self.combo_skills.iter().map(|s| s.crit_multiplier_bonus()).sum::<f64>()
+    }
+
+    /// Ticks without a click before the combo resets.
+    pub fn combo_window(&self) -> u32 {
+        BASE_COMBO_WINDOW + self.combo_skills.iter().map(|s| s.combo_window_bonus()).sum::<u32>()
+    }
+
+    pub fn has_combo_skill(&self, skill: ComboSkill) -> bool {
+        self.combo_skills.contains(&skill)
+    }
+
+    /// Whether `skill` can be learned now (prerequisite met, not yet learned).
+    /// Affordability is checked separately.
+    pub fn combo_skill_unlocked(&self, skill: ComboSkill) -> bool {
+        !self.has_combo_skill(skill)
+            && skill.requires().is_none_or(|r| self.has_combo_skill(r))
     }
 
     /// Savings bonus multiplier: holding more cookies gives a significant CPS bonus.
@@ -2900,6 +4143,34 @@ impl CookieState {
         }
     }
 
+    /// バフバー用に、掛かっている / 待っているバフを種類ごとにまとめる。
+    pub fn buff_summary(&self) -> Vec<BuffSummary> {
+        BuffKind::ALL
+            .iter()
+            .filter_map(|&kind| {
+                let active: Vec<&ActiveBuff> = self
+                    .active_buffs
+                    .iter()
+                    .filter(|b| b.effect.buff_kind() == Some(kind))
+                    .collect();
+                let queued = self
+                    .queued_buffs
+                    .iter()
+                    .filter(|b| b.effect.buff_kind() == Some(kind))
+                    .count();
+                let soonest = active.iter().min_by_key(|b| b.ticks_left)?;
+                Some(BuffSummary {
+                    kind,
+                    multiplier: active.iter().map(|b| b.effect.multiplier()).product(),
+                    stacks: active.len(),
+                    ticks_left: soonest.ticks_left,
+                    total_ticks: soonest.total_ticks,
+                    queued,
+                })
+            })
+            .collect()
+    }
+
     /// Whether any golden buff is active (for chain bonus check).
     pub fn has_active_golden_buff(&self) -> bool {
         self.active_buffs.iter().any(|b| {
@@ -2912,6 +4183,9 @@ impl CookieState {
 
     /// Effective cookies per click (with buffs, research, dragon, combo).
     pub fn effective_click_power(&self) -> f64 {
+        if self.clicks_disabled() {
+            return 0.0;
+        }
         let mut power = self.cookies_per_click;
 
         // Research: add CPS-based click bonus
@@ -3014,7 +4288,20 @@ impl CookieState {
             .collect()
     }
 
+    /// Cookie arts the player can pick, in [`CookieArt::all`] order.
+    pub fn unlocked_cookie_arts(&self) -> Vec<CookieArt> {
+        CookieArt::all()
+            .iter()
+            .copied()
+            .filter(|a| *a == CookieArt::Classic || self.cookie_arts.contains(a))
+            .collect()
+    }
+
+    /// 重要なものは読み上げ ([`crate::a11y`]) にも流す。
     pub fn add_log(&mut self, text: &str, is_important: bool) {
+        if is_important {
+            crate::a11y::announce(text);
+        }
         self.log.push(CookieLogEntry {
             text: text.to_string(),
             is_important,
@@ -3091,6 +4378,25 @@ mod tests {
         assert!((p.cps() - 10.0).abs() < 0.001); // 5 * 1.0 * 2.0
     }
 
+    #[test]
+    fn producer_cps_with_level() {
+        let mut p = Producer::new(ProducerKind::Grandma);
+        p.count = 5;
+        p.multiplier = 2.0;
+        p.level = 3;
+        assert!((p.cps() - 13.0).abs() < 0.001); // 5 * 1.0 * 2.0 * 1.3
+        assert!((p.next_unit_cps() - 2.6).abs() < 0.001);
+        assert!((p.cps_with_synergy(0.5) - 19.5).abs() < 0.001);
+    }
+
+    #[test]
+    fn producer_level_cost_scales_steeply() {
+        let mut p = Producer::new(ProducerKind::Cursor);
+        assert!((p.level_up_cost() - 750.0).abs() < 0.001);
+        p.level = 2;
+        assert!((p.level_up_cost() - 12_000.0).abs() < 0.001);
+    }
+
     #[test]
     fn state_total_cps() {
         let mut state = CookieState::new();
@@ -3102,6 +4408,23 @@ mod tests {
         assert!((state.total_cps() - expected).abs() < 0.01);
     }
 
+    #[test]
+    fn cps_breakdown_multiplies_out_to_total_cps() {
+        let mut state = CookieState::new();
+        state.producers[1].count = 10;
+        state.kitten_multiplier = 1.5;
+        state.prestige_multiplier = 2.0;
+        state.market_phase = MarketPhase::Bubble;
+        let b = state.cps_breakdown();
+        assert_eq!(b.kitten, 1.5);
+        assert_eq!(b.prestige, 2.0);
+        assert_eq!(b.market, MarketPhase::Bubble.cps_multiplier());
+        assert_eq!(b.dragon, 1.0);
+        let product: f64 = b.factors().iter().map(|(_, f)| f).product();
+        assert!((b.total - (b.base + b.percent_extra) * product).abs() < 1e-9);
+        assert_eq!(b.total, state.total_cps());
+    }
+
     #[test]
     fn producer_next_unit_cps() {
         let mut p = Producer::new(ProducerKind::Grandma);
@@ -3185,10 +4508,7 @@ mod tests {
     fn effective_click_with_buff() {
         let mut state = CookieState::new();
         state.cookies_per_click = 2.0;
-        state.active_buffs.push(ActiveBuff {
-            effect: GoldenEffect::ClickFrenzy { multiplier: 10.0 },
-            ticks_left: 100,
-        });
+        state.active_buffs.push(ActiveBuff::new(GoldenEffect::ClickFrenzy { multiplier: 10.0 }, 100));
         assert!((state.effective_click_power() - 20.0).abs() < 0.001);
     }
 
@@ -3197,10 +4517,7 @@ mod tests {
         let mut state = CookieState::new();
         state.producers[1].count = 5; // 5 grandmas = 5.0 cps base
         let base = state.total_cps();
-        state.active_buffs.push(ActiveBuff {
-            effect: GoldenEffect::ProductionFrenzy { multiplier: 7.0 },
-            ticks_left: 70,
-        });
+        state.active_buffs.push(ActiveBuff::new(GoldenEffect::ProductionFrenzy { multiplier: 7.0 }, 70));
         let buffed = state.total_cps();
         assert!((buffed - base * 7.0).abs() < 0.01);
     }
@@ -0,0 +1,12 @@
+//! Semantic action IDs for Grid Defense click targets.
+
+/// タイトル画面の難易度選択: action_id = DIFFICULTY_BASE + Difficulty::ALL の index
+pub const DIFFICULTY_BASE: u16 = 1;
+pub const START_WAVE: u16 = 5;
+pub const BACK_TO_TITLE: u16 = 6;
+
+/// ツール選択: action_id = TOOL_BASE + Tool::ALL の index
+pub const TOOL_BASE: u16 = 10;
+
+/// Grid click: action_id = GRID_CLICK_BASE + row * GRID_W + col
+pub const GRID_CLICK_BASE: u16 = 100;
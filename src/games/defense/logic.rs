@@ -0,0 +1,407 @@
+//! Grid Defense game logic — pure functions, fully testable.
+
+use super::state::{
+    DefenseState, Difficulty, Enemy, EnemyKind, Phase, Tool, Tower, TowerKind, FINAL_WAVE,
+};
+
+/// 敵の出現間隔 (tick)。
+pub const SPAWN_INTERVAL: u32 = 8;
+
+/// ウェーブごとの敵 HP 成長率。
+pub const HP_GROWTH: f64 = 1.2;
+
+/// 砲台の爆風半径 (マス)。
+pub const SPLASH_RADIUS: f64 = 1.0;
+
+/// 氷塔の減速時間 (tick) と減速中の速度倍率。
+pub const SLOW_TICKS: u32 = 30;
+pub const SLOW_FACTOR: f64 = 0.5;
+
+/// タワー売却時の返金率。
+pub const SELL_REFUND: f64 = 0.5;
+
+/// 被弾エフェクトの持続 tick 数。
+const HIT_FLASH_TICKS: u32 = 2;
+
+/// 難易度を決めてゲームを開始する (タイトル → プレイ中)。
+pub fn start_game(state: &mut DefenseState, difficulty: Difficulty) {
+    *state = DefenseState::new();
+    state.difficulty = difficulty;
+    state.phase = Phase::Playing;
+    state.lives = difficulty.starting_lives();
+    state.credits = difficulty.starting_credits();
+    state.add_log(&format!(
+        "難易度「{}」で開始。タワーを置いて W でウェーブ開始",
+        difficulty.name()
+    ));
+}
+
+/// Advance the game by `n` ticks (10 ticks/sec). プレイ中以外は何もしない。
+pub fn tick(state: &mut DefenseState, n: u32) {
+    for _ in 0..n {
+        if state.phase != Phase::Playing {
+            break;
+        }
+        tick_once(state);
+    }
+}
+
+fn tick_once(state: &mut DefenseState) {
+    for flash in state.hit_flash.iter_mut() {
+        flash.2 = flash.2.saturating_sub(1);
+    }
+    state.hit_flash.retain(|f| f.2 > 0);
+
+    spawn(state);
+    move_enemies(state);
+    fire_towers(state);
+    collect_dead(state);
+    check_wave_end(state);
+}
+
+/// ウェーブ `wave` の出現リスト。後半ほど数が増え、ランナーとブルートが混ざる。
+pub fn wave_composition(wave: u32) -> Vec<EnemyKind> {
+    let count = 5 + wave * 2;
+    (0..count)
+        .map(|i| {
+            if wave >= 3 && i % 5 == 4 {
+                EnemyKind::Brute
+            } else if wave >= 2 && i % 3 == 2 {
+                EnemyKind::Runner
+            } else {
+                EnemyKind::Grunt
+            }
+        })
+        .collect()
+}
+
+/// 次のウェーブを開始する。進行中なら false。
+pub fn start_wave(state: &mut DefenseState) -> bool {
+    if state.phase != Phase::Playing || state.wave_active() || state.wave >= FINAL_WAVE {
+        return false;
+    }
+    state.wave += 1;
+    let mut queue = wave_composition(state.wave);
+    // 先頭から出すので逆順に積む
+    queue.reverse();
+    state.spawn_queue = queue;
+    state.spawn_countdown = 0;
+    state.wave_running = true;
+    state.add_log(&format!("ウェーブ {}/{} 開始！", state.wave, FINAL_WAVE));
+    true
+}
+
+/// ウェーブ `wave` の敵 HP。
+pub fn enemy_hp(kind: EnemyKind, wave: u32, difficulty: Difficulty) -> f64 {
+    kind.base_hp() * HP_GROWTH.powi(wave.saturating_sub(1) as i32) * difficulty.hp_multiplier()
+}
+
+fn spawn(state: &mut DefenseState) {
+    if state.spawn_countdown > 0 {
+        state.spawn_countdown -= 1;
+        return;
+    }
+    let Some(kind) = state.spawn_queue.pop() else {
+        return;
+    };
+    let hp = enemy_hp(kind, state.wave, state.difficulty);
+    state.enemies.push(Enemy {
+        kind,
+        hp,
+        max_hp: hp,
+        pos: 0.0,
+        slow_ticks: 0,
+    });
+    state.spawn_countdown = SPAWN_INTERVAL;
+}
+
+fn move_enemies(state: &mut DefenseState) {
+    let goal = (state.path.len() - 1) as f64;
+    let mut leaked = 0;
+    state.enemies.retain_mut(|e| {
+        let mut speed = e.kind.speed();
+        if e.slow_ticks > 0 {
+            e.slow_ticks -= 1;
+            speed *= SLOW_FACTOR;
+        }
+        e.pos += speed;
+        if e.pos >= goal {
+            leaked += e.kind.leak_damage();
+            false
+        } else {
+            true
+        }
+    });
+    if leaked > 0 {
+        state.lives = state.lives.saturating_sub(leaked);
+        state.add_log(&format!("突破された！ライフ -{}", leaked));
+        if state.lives == 0 {
+            state.phase = Phase::Over(false);
+            state.add_log(&format!("陥落… ウェーブ {} で力尽きた", state.wave));
+        }
+    }
+}
+
+/// 敵の現在マス。
+pub fn enemy_cell(state: &DefenseState, enemy: &Enemy) -> (usize, usize) {
+    let i = (enemy.pos.floor() as usize).min(state.path.len() - 1);
+    state.path[i]
+}
+
+fn distance(a: (usize, usize), b: (usize, usize)) -> f64 {
+    let dx = a.0 as f64 - b.0 as f64;
+    let dy = a.1 as f64 - b.1 as f64;
+    (dx * dx + dy * dy).sqrt()
+}
+
+/// 射程内で最もゴールに近い敵 (先頭狙い)。
+fn pick_target(state: &DefenseState, tower: &Tower) -> Option<usize> {
+    state
+        .enemies
+        .iter()
+        .enumerate()
+        .filter(|(_, e)| e.hp > 0.0)
+        .filter(|(_, e)| distance((tower.x, tower.y), enemy_cell(state, e)) <= tower.kind.range())
+        .max_by(|a, b| a.1.pos.total_cmp(&b.1.pos))
+        .map(|(i, _)| i)
+}
+
+fn fire_towers(state: &mut DefenseState) {
+    for t in 0..state.towers.len() {
+        if state.towers[t].cooldown > 0 {
+            state.towers[t].cooldown -= 1;
+            continue;
+        }
+        let Some(target) = pick_target(state, &state.towers[t]) else {
+            continue;
+        };
+        let kind = state.towers[t].kind;
+        state.towers[t].cooldown = kind.cooldown();
+        let cell = enemy_cell(state, &state.enemies[target]);
+        match kind {
+            TowerKind::Arrow => state.enemies[target].hp -= kind.damage(),
+            TowerKind::Cannon => {
+                let path = &state.path;
+                for e in state.enemies.iter_mut() {
+                    let i = (e.pos.floor() as usize).min(path.len() - 1);
+                    if distance(path[i], cell) <= SPLASH_RADIUS {
+                        e.hp -= kind.damage();
+                    }
+                }
+            }
+            TowerKind::Frost => {
+                let e = &mut state.enemies[target];
+                e.hp -= kind.damage();
+                e.slow_ticks = SLOW_TICKS;
+            }
+        }
+        state.hit_flash.push((cell.0, cell.1, HIT_FLASH_TICKS));
+    }
+}
+
+fn collect_dead(state: &mut DefenseState) {
+    let mut bounty = 0;
+    let mut kills = 0;
+    state.enemies.retain(|e| {
+        if e.hp <= 0.0 {
+            bounty += e.kind.bounty();
+            kills += 1;
+            false
+        } else {
+            true
+        }
+    });
+    state.credits += bounty;
+    state.kills += kills;
+}
+
+/// ウェーブ終了ボーナス。
+pub fn wave_bonus(wave: u32) -> u32 {
+    20 + wave * 5
+}
+
+fn check_wave_end(state: &mut DefenseState) {
+    if state.phase != Phase::Playing || !state.wave_running || state.wave_active() {
+        return;
+    }
+    state.wave_running = false;
+    if state.wave >= FINAL_WAVE {
+        state.phase = Phase::Over(true);
+        state.add_log(&format!("全 {} ウェーブ防衛成功！", FINAL_WAVE));
+        return;
+    }
+    let bonus = wave_bonus(state.wave);
+    state.credits += bonus;
+    state.add_log(&format!(
+        "ウェーブ {} クリア！ボーナス +{}",
+        state.wave, bonus
+    ));
+}
+
+/// カーソル位置で現在のツールを使う。成功したら true。
+pub fn use_tool(state: &mut DefenseState) -> bool {
+    let (x, y) = (state.cursor_x, state.cursor_y);
+    match state.tool {
+        Tool::Build(kind) => build_tower(state, x, y, kind),
+        Tool::Sell => sell_tower(state, x, y),
+    }
+}
+
+pub fn can_build(state: &DefenseState, x: usize, y: usize, kind: TowerKind) -> bool {
+    state.phase == Phase::Playing
+        && !state.is_path(x, y)
+        && state.tower_at(x, y).is_none()
+        && state.credits >= kind.cost()
+}
+
+pub fn build_tower(state: &mut DefenseState, x: usize, y: usize, kind: TowerKind) -> bool {
+    if !can_build(state, x, y, kind) {
+        return false;
+    }
+    state.credits -= kind.cost();
+    state.towers.push(Tower {
+        kind,
+        x,
+        y,
+        cooldown: 0,
+    });
+    true
+}
+
+pub fn sell_tower(state: &mut DefenseState, x: usize, y: usize) -> bool {
+    let Some(i) = state.towers.iter().position(|t| t.x == x && t.y == y) else {
+        return false;
+    };
+    let tower = state.towers.remove(i);
+    let refund = (tower.kind.cost() as f64 * SELL_REFUND) as u32;
+    state.credits += refund;
+    state.add_log(&format!("{}を売却 +{}", tower.kind.name(), refund));
+    true
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn playing() -> DefenseState {
+        let mut s = DefenseState::new();
+        start_game(&mut s, Difficulty::Normal);
+        s
+    }
+
+    #[test]
+    fn difficulty_sets_lives_and_credits() {
+        let mut s = DefenseState::new();
+        start_game(&mut s, Difficulty::Hard);
+        assert_eq!(s.phase, Phase::Playing);
+        assert_eq!(s.lives, 10);
+        assert_eq!(s.credits, 100);
+        assert!(
+            enemy_hp(EnemyKind::Grunt, 1, Difficulty::Hard)
+                > enemy_hp(EnemyKind::Grunt, 1, Difficulty::Easy)
+        );
+    }
+
+    #[test]
+    fn towers_cannot_go_on_path_or_overlap() {
+        let mut s = playing();
+        let (px, py) = s.path[3];
+        assert!(!build_tower(&mut s, px, py, TowerKind::Arrow));
+        assert!(build_tower(&mut s, 1, 2, TowerKind::Arrow));
+        assert!(!build_tower(&mut s, 1, 2, TowerKind::Arrow));
+        assert_eq!(s.credits, 120 - 50);
+        assert!(sell_tower(&mut s, 1, 2));
+        assert_eq!(s.credits, 120 - 25);
+    }
+
+    #[test]
+    fn undefended_wave_costs_lives() {
+        let mut s = playing();
+        assert!(start_wave(&mut s));
+        assert!(!start_wave(&mut s), "cannot stack waves");
+        tick(&mut s, 2_000);
+        assert_eq!(s.lives, 15 - 7);
+        assert!(!s.wave_active());
+        assert_eq!(s.credits, 120 + wave_bonus(1));
+    }
+
+    #[test]
+    fn towers_kill_enemies_for_bounty() {
+        let mut s = playing();
+        s.credits = 1_000;
+        // 道の両側に弓塔を並べる
+        for x in [2, 5, 8, 11] {
+            assert!(build_tower(&mut s, x, 2, TowerKind::Arrow));
+            assert!(build_tower(&mut s, x, 0, TowerKind::Arrow));
+        }
+        let before = s.credits;
+        start_wave(&mut s);
+        tick(&mut s, 2_000);
+        assert_eq!(s.lives, 15, "first wave should not leak");
+        assert_eq!(s.kills, 7);
+        assert_eq!(
+            s.credits,
+            before + 7 * EnemyKind::Grunt.bounty() + wave_bonus(1)
+        );
+    }
+
+    #[test]
+    fn frost_slows_and_cannon_splashes() {
+        let mut s = playing();
+        s.enemies = vec![
+            Enemy {
+                kind: EnemyKind::Brute,
+                hp: 100.0,
+                max_hp: 100.0,
+                pos: 2.0,
+                slow_ticks: 0,
+            },
+            Enemy {
+                kind: EnemyKind::Brute,
+                hp: 100.0,
+                max_hp: 100.0,
+                pos: 2.5,
+                slow_ticks: 0,
+            },
+        ];
+        s.towers.push(Tower {
+            kind: TowerKind::Cannon,
+            x: 2,
+            y: 2,
+            cooldown: 0,
+        });
+        s.towers.push(Tower {
+            kind: TowerKind::Frost,
+            x: 3,
+            y: 2,
+            cooldown: 0,
+        });
+        fire_towers(&mut s);
+        assert!(
+            s.enemies[0].hp < 100.0 && s.enemies[1].hp < 100.0,
+            "splash hits both"
+        );
+        assert!(s.enemies.iter().any(|e| e.slow_ticks == SLOW_TICKS));
+    }
+
+    #[test]
+    fn losing_all_lives_ends_the_game() {
+        let mut s = playing();
+        s.lives = 1;
+        start_wave(&mut s);
+        tick(&mut s, 2_000);
+        assert_eq!(s.phase, Phase::Over(false));
+        assert_eq!(s.lives, 0);
+    }
+
+    #[test]
+    fn clearing_final_wave_wins() {
+        let mut s = playing();
+        s.wave = FINAL_WAVE - 1;
+        s.lives = 1_000;
+        start_wave(&mut s);
+        tick(&mut s, 5_000);
+        assert_eq!(s.phase, Phase::Over(true));
+        assert!(!start_wave(&mut s));
+    }
+}
@@ -0,0 +1,267 @@
+//! Grid Defense — ウェーブ制タワーディフェンス。
+//!
+//! コアループ:
+//!   1. タイトル画面で難易度 (ライフ・初期資金・敵 HP) を選ぶ
+//!   2. 道以外のマスにタワーを置く (盤面タップ or hjkl + Space)
+//!   3. ウェーブを開始 → 敵が道を進み、タワーが自動で迎撃
+//!   4. 撃破報酬とクリアボーナスでタワーを増やし、最終ウェーブまで守り抜く
+
+pub mod actions;
+pub mod logic;
+pub mod render;
+pub mod state;
+
+use std::cell::RefCell;
+use std::rc::Rc;
+
+use ratzilla::ratatui::layout::Rect;
+use ratzilla::ratatui::Frame;
+
+use crate::games::{Game, GameChoice};
+use crate::input::{ClickState, InputEvent};
+use crate::sound;
+use crate::widgets::ClickableGrid;
+
+use actions::*;
+use state::{DefenseState, Difficulty, Phase, Tool, GRID_W};
+
+pub struct DefenseGame {
+    pub state: DefenseState,
+}
+
+impl DefenseGame {
+    pub fn new() -> Self {
+        Self {
+            state: DefenseState::new(),
+        }
+    }
+
+    fn start(&mut self, index: usize) -> bool {
+        let Some(d) = Difficulty::ALL.get(index) else {
+            return false;
+        };
+        logic::start_game(&mut self.state, *d);
+        sound::play(sound::SELECT);
+        true
+    }
+
+    fn select_tool(&mut self, index: usize) -> bool {
+        let Some(tool) = Tool::ALL.get(index) else {
+            return false;
+        };
+        self.state.tool = *tool;
+        true
+    }
+
+    fn use_tool(&mut self) {
+        let ok = logic::use_tool(&mut self.state);
+        sound::play(match (ok, self.state.tool) {
+            (false, _) => sound::ERROR,
+            (true, Tool::Build(_)) => sound::PURCHASE,
+            (true, Tool::Sell) => sound::CLICK,
+        });
+    }
+
+    fn start_wave(&mut self) {
+        if logic::start_wave(&mut self.state) {
+            sound::play(sound::BOSS_APPEAR);
+        }
+    }
+
+    fn handle_key(&mut self, key: char) -> bool {
+        match self.state.phase {
+            Phase::Title => match key {
+                '1'..='3' => self.start(key as usize - '1' as usize),
+                _ => false,
+            },
+            Phase::Over(_) => match key {
+                'r' => {
+                    self.state.phase = Phase::Title;
+                    true
+                }
+                _ => false,
+            },
+            Phase::Playing => {
+                match key {
+                    '1'..='3' => return self.select_tool(key as usize - '1' as usize),
+                    's' => self.state.tool = Tool::Sell,
+                    'h' => self.state.move_cursor(-1, 0),
+                    'l' => self.state.move_cursor(1, 0),
+                    'k' => self.state.move_cursor(0, -1),
+                    'j' => self.state.move_cursor(0, 1),
+                    ' ' => self.use_tool(),
+                    'w' => self.start_wave(),
+                    _ => return false,
+                }
+                true
+            }
+        }
+    }
+
+    fn handle_click(&mut self, id: u16) -> bool {
+        match self.state.phase {
+            Phase::Title => match id {
+                id if (DIFFICULTY_BASE..START_WAVE).contains(&id) => {
+                    self.start((id - DIFFICULTY_BASE) as usize)
+                }
+                _ => false,
+            },
+            Phase::Over(_) => match id {
+                BACK_TO_TITLE => {
+                    self.state.phase = Phase::Title;
+                    true
+                }
+                _ => false,
+            },
+            Phase::Playing => match id {
+                START_WAVE => {
+                    self.start_wave();
+                    true
+                }
+                id if id >= GRID_CLICK_BASE => {
+                    if let Some((x, y)) = ClickableGrid::decode(GRID_CLICK_BASE, GRID_W, id) {
+                        self.state.cursor_x = x;
+                        self.state.cursor_y = y;
+                        self.use_tool();
+                    }
+                    true
+                }
+                id if id >= TOOL_BASE => self.select_tool((id - TOOL_BASE) as usize),
+                _ => false,
+            },
+        }
+    }
+}
+
+impl Game for DefenseGame {
+    fn choice(&self) -> GameChoice {
+        GameChoice::Defense
+    }
+
+    fn handle_input(&mut self, event: &InputEvent) -> bool {
+        match event {
+            InputEvent::Key(c) => self.handle_key(*c),
+            InputEvent::Click(_, id) => self.handle_click(*id),
+            InputEvent::LongPress(..) | InputEvent::DoubleTap(..) | InputEvent::Drag(..) => false,
+        }
+    }
+
+    fn tick(&mut self, delta_ticks: u32) {
+        let was_playing = self.state.phase == Phase::Playing;
+        logic::tick(&mut self.state, delta_ticks);
+        if was_playing {
+            match self.state.phase {
+                Phase::Over(true) => sound::play(sound::LEVEL_UP),
+                Phase::Over(false) => sound::play(sound::HIT_HERO),
+                _ => {}
+            }
+        }
+    }
+
+    fn render(&self, f: &mut Frame, area: Rect, click_state: &Rc<RefCell<ClickState>>) {
+        render::render(&self.state, f, area, click_state);
+    }
+
+    #[cfg(any(feature = "debug-console", debug_assertions, test))]
+    fn debug_command(&mut self, args: &[&str]) -> Result<String, String> {
+        use crate::debug_console::{parse_count, unknown};
+        match args {
+            ["help"] => Ok(format!(
+                "give credits <n> / give lives <n> / set wave <1-{}>",
+                state::FINAL_WAVE
+            )),
+            ["give", "credits", n] => {
+                let n = parse_count(n)?.min(u32::MAX as u64) as u32;
+                self.state.credits = self.state.credits.saturating_add(n);
+                Ok(format!("資金 +{}", n))
+            }
+            ["give", "lives", n] => {
+                let cap = self.state.difficulty.starting_lives();
+                let n = parse_count(n)?.min(cap as u64) as u32;
+                self.state.lives = self.state.lives.saturating_add(n).min(cap);
+                Ok(format!("ライフ {}", self.state.lives))
+            }
+            ["set", "wave", n] => {
+                let wave = parse_count(n)?;
+                if wave == 0 || wave > state::FINAL_WAVE as u64 {
+                    return Err(format!("ウェーブは 1〜{}", state::FINAL_WAVE));
+                }
+                if self.state.phase != Phase::Playing || self.state.wave_active() {
+                    return Err("ウェーブ間の準備中のみ変更できる".into());
+                }
+                self.state.wave = wave as u32 - 1;
+                Ok(format!("次のウェーブ: {}", wave))
+            }
+            _ => Err(unknown(args)),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::input::ClickScope;
+    use state::TowerKind;
+
+    fn click(id: u16) -> InputEvent {
+        InputEvent::Click(ClickScope::Game(GameChoice::Defense), id)
+    }
+
+    #[test]
+    fn title_screen_picks_difficulty() {
+        let mut g = DefenseGame::new();
+        assert_eq!(g.state.phase, Phase::Title);
+        assert!(
+            !g.handle_input(&InputEvent::Key('w')),
+            "no wave from the title"
+        );
+        assert!(g.handle_input(&click(DIFFICULTY_BASE + 2)));
+        assert_eq!(g.state.phase, Phase::Playing);
+        assert_eq!(g.state.difficulty, Difficulty::Hard);
+    }
+
+    #[test]
+    fn grid_tap_places_selected_tower() {
+        let mut g = DefenseGame::new();
+        g.handle_input(&InputEvent::Key('2'));
+        g.handle_input(&click(TOOL_BASE + 1));
+        assert_eq!(g.state.tool, Tool::Build(TowerKind::Cannon));
+        // (2, 2) は道ではない
+        assert!(g.handle_input(&click(GRID_CLICK_BASE + (2 * GRID_W + 2) as u16)));
+        assert_eq!(
+            g.state.tower_at(2, 2).map(|t| t.kind),
+            Some(TowerKind::Cannon)
+        );
+
+        g.handle_input(&InputEvent::Key('s'));
+        g.handle_input(&click(GRID_CLICK_BASE + (2 * GRID_W + 2) as u16));
+        assert!(g.state.towers.is_empty());
+    }
+
+    #[test]
+    fn keyboard_cursor_and_wave_start() {
+        let mut g = DefenseGame::new();
+        g.handle_input(&InputEvent::Key('1'));
+        g.handle_input(&InputEvent::Key('k'));
+        g.handle_input(&InputEvent::Key(' '));
+        assert!(g.state.tower_at(1, 2).is_some());
+        g.handle_input(&InputEvent::Key('w'));
+        assert_eq!(g.state.wave, 1);
+        assert!(
+            !g.handle_input(&InputEvent::Key('q')),
+            "q falls through to the menu"
+        );
+    }
+
+    #[test]
+    fn result_screen_returns_to_title() {
+        let mut g = DefenseGame::new();
+        g.handle_input(&InputEvent::Key('1'));
+        g.state.lives = 1;
+        g.handle_input(&InputEvent::Key('w'));
+        g.tick(2_000);
+        assert_eq!(g.state.phase, Phase::Over(false));
+        assert!(g.handle_input(&click(BACK_TO_TITLE)));
+        assert_eq!(g.state.phase, Phase::Title);
+    }
+}
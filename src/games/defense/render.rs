@@ -0,0 +1,461 @@
+//! Grid Defense rendering.
+
+use std::cell::RefCell;
+use std::rc::Rc;
+
+use ratzilla::ratatui::layout::{Constraint, Direction, Layout, Rect};
+use ratzilla::ratatui::style::{Color, Modifier, Style};
+use ratzilla::ratatui::text::{Line, Span};
+use ratzilla::ratatui::widgets::{Block, Borders, Paragraph, Wrap};
+use ratzilla::ratatui::Frame;
+
+use crate::input::{is_narrow_layout, ClickState};
+use crate::widgets::{ClickableGrid, ClickableList};
+
+use super::actions::*;
+use super::logic::{can_build, enemy_cell, wave_bonus};
+use super::state::{
+    DefenseState, Difficulty, Enemy, Phase, Tool, TowerKind, FINAL_WAVE, GRID_H, GRID_W,
+};
+
+/// 盤面の高さ (枠込み)。
+const GRID_AREA_H: u16 = GRID_H as u16 + 2;
+/// 盤面の幅 (1 マス 2 桁 + 左余白 1 + 枠)。
+const GRID_AREA_W: u16 = GRID_W as u16 * 2 + 3;
+
+pub fn render(
+    state: &DefenseState,
+    f: &mut Frame,
+    area: Rect,
+    click_state: &Rc<RefCell<ClickState>>,
+) {
+    match state.phase {
+        Phase::Title => render_title(f, area, click_state),
+        Phase::Playing => {
+            if is_narrow_layout(area.width) {
+                render_narrow(state, f, area, click_state);
+            } else {
+                render_wide(state, f, area, click_state);
+            }
+        }
+        Phase::Over(victory) => render_result(state, victory, f, area, click_state),
+    }
+}
+
+fn render_wide(
+    state: &DefenseState,
+    f: &mut Frame,
+    area: Rect,
+    click_state: &Rc<RefCell<ClickState>>,
+) {
+    let h_chunks = Layout::default()
+        .direction(Direction::Horizontal)
+        .constraints([Constraint::Length(GRID_AREA_W), Constraint::Min(28)])
+        .split(area);
+    let left = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([Constraint::Length(GRID_AREA_H), Constraint::Min(4)])
+        .split(h_chunks[0]);
+    let right = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([Constraint::Length(3), Constraint::Min(10)])
+        .split(h_chunks[1]);
+
+    render_grid(state, f, left[0], click_state);
+    render_log(state, f, left[1]);
+    render_header(state, f, right[0]);
+    render_controls(state, f, right[1], click_state);
+}
+
+fn render_narrow(
+    state: &DefenseState,
+    f: &mut Frame,
+    area: Rect,
+    click_state: &Rc<RefCell<ClickState>>,
+) {
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Length(3),
+            Constraint::Length(GRID_AREA_H),
+            Constraint::Min(8),
+        ])
+        .split(area);
+    render_header(state, f, chunks[0]);
+    render_grid(state, f, chunks[1], click_state);
+    render_controls(state, f, chunks[2], click_state);
+}
+
+fn render_header(state: &DefenseState, f: &mut Frame, area: Rect) {
+    let lives_color = if state.lives <= 3 {
+        Color::Red
+    } else {
+        Color::LightRed
+    };
+    let spans = vec![
+        Span::styled(
+            format!(" ♥{}", state.lives),
+            Style::default()
+                .fg(lives_color)
+                .add_modifier(Modifier::BOLD),
+        ),
+        Span::styled(
+            format!("  ¢{}", state.credits),
+            Style::default()
+                .fg(Color::Yellow)
+                .add_modifier(Modifier::BOLD),
+        ),
+        Span::styled(
+            format!("  W{}/{}", state.wave, FINAL_WAVE),
+            Style::default().fg(Color::Cyan),
+        ),
+        Span::styled(
+            format!("  撃破{}", state.kills),
+            Style::default().fg(Color::DarkGray),
+        ),
+    ];
+    let widget = Paragraph::new(Line::from(spans)).block(
+        Block::default()
+            .borders(Borders::ALL)
+            .border_style(Style::default().fg(Color::LightMagenta))
+            .title(format!(" Grid Defense [{}] ", state.difficulty.name())),
+    );
+    f.render_widget(widget, area);
+}
+
+fn tower_color(kind: TowerKind) -> Color {
+    match kind {
+        TowerKind::Arrow => Color::LightGreen,
+        TowerKind::Cannon => Color::LightRed,
+        TowerKind::Frost => Color::LightCyan,
+    }
+}
+
+fn enemy_color(enemy: &Enemy) -> Color {
+    let ratio = enemy.hp / enemy.max_hp;
+    if enemy.slow_ticks > 0 {
+        Color::Cyan
+    } else if ratio > 0.6 {
+        Color::White
+    } else if ratio > 0.3 {
+        Color::Yellow
+    } else {
+        Color::Red
+    }
+}
+
+fn render_grid(
+    state: &DefenseState,
+    f: &mut Frame,
+    area: Rect,
+    click_state: &Rc<RefCell<ClickState>>,
+) {
+    // マスごとに最も先行している敵を表示する
+    let mut front: Vec<Option<&Enemy>> = vec![None; GRID_W * GRID_H];
+    for e in &state.enemies {
+        let (x, y) = enemy_cell(state, e);
+        let slot = &mut front[y * GRID_W + x];
+        if slot.is_none_or(|cur| e.pos > cur.pos) {
+            *slot = Some(e);
+        }
+    }
+    let cursor_tower = match state.tool {
+        Tool::Build(kind) => Some(kind),
+        Tool::Sell => None,
+    };
+
+    let mut lines = Vec::with_capacity(GRID_H);
+    for y in 0..GRID_H {
+        let mut spans = vec![Span::raw(" ")];
+        for x in 0..GRID_W {
+            let (text, mut style) = if let Some(e) = front[y * GRID_W + x] {
+                (
+                    format!("{} ", e.kind.symbol()),
+                    Style::default()
+                        .fg(enemy_color(e))
+                        .add_modifier(Modifier::BOLD),
+                )
+            } else if let Some(t) = state.tower_at(x, y) {
+                (
+                    format!("{} ", t.kind.symbol()),
+                    Style::default()
+                        .fg(tower_color(t.kind))
+                        .add_modifier(Modifier::BOLD),
+                )
+            } else if state.is_path(x, y) {
+                ("░░".to_string(), Style::default().fg(Color::DarkGray))
+            } else {
+                (
+                    ". ".to_string(),
+                    Style::default().fg(Color::Rgb(60, 60, 60)),
+                )
+            };
+            if state
+                .hit_flash
+                .iter()
+                .any(|(hx, hy, _)| *hx == x && *hy == y)
+            {
+                style = style.bg(Color::Rgb(120, 40, 0));
+            }
+            if (x, y) == (state.cursor_x, state.cursor_y) {
+                let ok = match cursor_tower {
+                    Some(kind) => can_build(state, x, y, kind),
+                    None => state.tower_at(x, y).is_some(),
+                };
+                style = style
+                    .bg(if ok {
+                        Color::Rgb(0, 90, 0)
+                    } else {
+                        Color::Rgb(90, 0, 0)
+                    })
+                    .add_modifier(Modifier::REVERSED);
+            }
+            spans.push(Span::styled(text, style));
+        }
+        lines.push(Line::from(spans));
+    }
+
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(Color::Magenta))
+        .title(" 盤面 ");
+
+    let grid = ClickableGrid::new(GRID_W, GRID_H, GRID_CLICK_BASE, 2);
+    let mut cs = click_state.borrow_mut();
+    grid.register_targets(area, &block, &mut cs, 1);
+    f.render_widget(Paragraph::new(lines).block(block), area);
+}
+
+fn render_controls(
+    state: &DefenseState,
+    f: &mut Frame,
+    area: Rect,
+    click_state: &Rc<RefCell<ClickState>>,
+) {
+    let mut cl = ClickableList::new();
+
+    for (i, tool) in Tool::ALL.iter().enumerate() {
+        let selected = state.tool == *tool;
+        let marker = if selected { "▶" } else { " " };
+        let line = match tool {
+            Tool::Build(kind) => {
+                let affordable = state.credits >= kind.cost();
+                let name_style = if selected {
+                    Style::default()
+                        .fg(tower_color(*kind))
+                        .add_modifier(Modifier::BOLD)
+                } else if affordable {
+                    Style::default().fg(Color::White)
+                } else {
+                    Style::default().fg(Color::DarkGray)
+                };
+                Line::from(vec![
+                    Span::styled(
+                        format!("{}[{}] {} {}", marker, i + 1, kind.symbol(), kind.name()),
+                        name_style,
+                    ),
+                    Span::styled(
+                        format!(" ¢{}", kind.cost()),
+                        Style::default().fg(if affordable {
+                            Color::Yellow
+                        } else {
+                            Color::Red
+                        }),
+                    ),
+                    Span::styled(
+                        format!(" {}", kind.description()),
+                        Style::default().fg(Color::DarkGray),
+                    ),
+                ])
+            }
+            Tool::Sell => {
+                let style = if selected {
+                    Style::default()
+                        .fg(Color::LightRed)
+                        .add_modifier(Modifier::BOLD)
+                } else {
+                    Style::default().fg(Color::White)
+                };
+                Line::from(Span::styled(
+                    format!("{}[S] 売却 (半額返金)", marker),
+                    style,
+                ))
+            }
+        };
+        cl.push_clickable(line, TOOL_BASE + i as u16);
+    }
+    cl.push(Line::from(""));
+
+    let (wave_label, wave_style) = if state.wave_active() {
+        (
+            format!(
+                " ウェーブ {} 進行中… 残り{}体",
+                state.wave,
+                state.spawn_queue.len() + state.enemies.len()
+            ),
+            Style::default().fg(Color::DarkGray),
+        )
+    } else {
+        (
+            format!(
+                " [W] ウェーブ {} 開始 (クリアで ¢{})",
+                state.wave + 1,
+                wave_bonus(state.wave + 1)
+            ),
+            Style::default()
+                .fg(Color::LightMagenta)
+                .add_modifier(Modifier::BOLD),
+        )
+    };
+    cl.push_clickable(Line::from(Span::styled(wave_label, wave_style)), START_WAVE);
+    cl.push(Line::from(""));
+    cl.push(Line::from(Span::styled(
+        " 盤面タップで設置 / hjkl+Space",
+        Style::default().fg(Color::DarkGray),
+    )));
+    cl.push(Line::from(Span::styled(
+        " g=兵 r=俊足 B=巨兵 (抜けると♥-3)",
+        Style::default().fg(Color::DarkGray),
+    )));
+
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(Color::Cyan))
+        .title(" 司令室 ");
+    let mut cs = click_state.borrow_mut();
+    cl.render(f, area, block, &mut cs, false, 0);
+}
+
+fn render_log(state: &DefenseState, f: &mut Frame, area: Rect) {
+    let visible = area.height.saturating_sub(2) as usize;
+    let lines: Vec<Line> = state
+        .log
+        .iter()
+        .rev()
+        .take(visible)
+        .enumerate()
+        .map(|(i, entry)| {
+            let color = if i == 0 {
+                Color::White
+            } else {
+                Color::DarkGray
+            };
+            Line::from(Span::styled(
+                format!(" {}", entry),
+                Style::default().fg(color),
+            ))
+        })
+        .collect();
+    let widget = Paragraph::new(lines)
+        .block(
+            Block::default()
+                .borders(Borders::ALL)
+                .border_style(Style::default().fg(Color::Blue))
+                .title(" Log "),
+        )
+        .wrap(Wrap { trim: false });
+    f.render_widget(widget, area);
+}
+
+fn render_title(f: &mut Frame, area: Rect, click_state: &Rc<RefCell<ClickState>>) {
+    let mut cl = ClickableList::new();
+    cl.push(Line::from(""));
+    cl.push(Line::from(Span::styled(
+        "  ▓▓ GRID DEFENSE ▓▓",
+        Style::default()
+            .fg(Color::LightMagenta)
+            .add_modifier(Modifier::BOLD),
+    )));
+    cl.push(Line::from(Span::styled(
+        format!("  道沿いにタワーを置き、{} ウェーブを守り抜け", FINAL_WAVE),
+        Style::default().fg(Color::DarkGray),
+    )));
+    cl.push(Line::from(""));
+    cl.push(Line::from(Span::styled(
+        "  ── 難易度を選択 ──",
+        Style::default()
+            .fg(Color::Cyan)
+            .add_modifier(Modifier::BOLD),
+    )));
+    for (i, d) in Difficulty::ALL.iter().enumerate() {
+        let color = match d {
+            Difficulty::Easy => Color::LightGreen,
+            Difficulty::Normal => Color::Yellow,
+            Difficulty::Hard => Color::LightRed,
+        };
+        cl.push_clickable(
+            Line::from(vec![
+                Span::styled(
+                    format!("  [{}] {:<6}", i + 1, d.name()),
+                    Style::default().fg(color).add_modifier(Modifier::BOLD),
+                ),
+                Span::styled(
+                    format!(
+                        " ♥{} ¢{} 敵HP×{:.1}",
+                        d.starting_lives(),
+                        d.starting_credits(),
+                        d.hp_multiplier()
+                    ),
+                    Style::default().fg(Color::DarkGray),
+                ),
+            ]),
+            DIFFICULTY_BASE + i as u16,
+        );
+    }
+
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(Color::LightMagenta))
+        .title(" Grid Defense ");
+    let mut cs = click_state.borrow_mut();
+    cl.render(f, area, block, &mut cs, false, 0);
+}
+
+fn render_result(
+    state: &DefenseState,
+    victory: bool,
+    f: &mut Frame,
+    area: Rect,
+    click_state: &Rc<RefCell<ClickState>>,
+) {
+    let mut cl = ClickableList::new();
+    cl.push(Line::from(""));
+    let (headline, color) = if victory {
+        ("  ★ 防衛成功！ ★", Color::LightYellow)
+    } else {
+        ("  ✕ 陥落 ✕", Color::Red)
+    };
+    cl.push(Line::from(Span::styled(
+        headline,
+        Style::default().fg(color).add_modifier(Modifier::BOLD),
+    )));
+    cl.push(Line::from(""));
+    cl.push(Line::from(Span::styled(
+        format!(
+            "  難易度 {}  ウェーブ {}/{}  撃破 {}  残り♥{}",
+            state.difficulty.name(),
+            state.wave,
+            FINAL_WAVE,
+            state.kills,
+            state.lives
+        ),
+        Style::default().fg(Color::White),
+    )));
+    cl.push(Line::from(""));
+    cl.push_clickable(
+        Line::from(Span::styled(
+            "  [R] タイトルへ戻る",
+            Style::default()
+                .fg(Color::Cyan)
+                .add_modifier(Modifier::BOLD),
+        )),
+        BACK_TO_TITLE,
+    );
+
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(color))
+        .title(" Grid Defense ");
+    let mut cs = click_state.borrow_mut();
+    cl.render(f, area, block, &mut cs, false, 0);
+}
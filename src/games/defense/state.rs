@@ -0,0 +1,354 @@
+//! Grid Defense — game state.
+//!
+//! 純粋なデータ定義のみ。ロジックは logic.rs に置く。
+//!
+//! 盤面は固定サイズで、敵は左端から右端へ決まった道 (`path`) を進む。
+//! 道以外のマスにタワーを置いて迎え撃つ。
+
+pub const GRID_W: usize = 16;
+pub const GRID_H: usize = 9;
+
+/// このウェーブを守り切れば勝利。
+pub const FINAL_WAVE: u32 = 15;
+
+/// 道の折れ点。隣り合う点は同じ行か同じ列にあること。
+const PATH_WAYPOINTS: [(usize, usize); 6] = [(0, 1), (12, 1), (12, 4), (3, 4), (3, 7), (15, 7)];
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Difficulty {
+    Easy,
+    Normal,
+    Hard,
+}
+
+impl Difficulty {
+    pub const ALL: [Difficulty; 3] = [Difficulty::Easy, Difficulty::Normal, Difficulty::Hard];
+
+    pub fn name(self) -> &'static str {
+        match self {
+            Difficulty::Easy => "かんたん",
+            Difficulty::Normal => "ふつう",
+            Difficulty::Hard => "むずかしい",
+        }
+    }
+
+    pub fn starting_lives(self) -> u32 {
+        match self {
+            Difficulty::Easy => 20,
+            Difficulty::Normal => 15,
+            Difficulty::Hard => 10,
+        }
+    }
+
+    pub fn starting_credits(self) -> u32 {
+        match self {
+            Difficulty::Easy => 150,
+            Difficulty::Normal => 120,
+            Difficulty::Hard => 100,
+        }
+    }
+
+    /// 敵 HP の倍率。
+    pub fn hp_multiplier(self) -> f64 {
+        match self {
+            Difficulty::Easy => 0.8,
+            Difficulty::Normal => 1.0,
+            Difficulty::Hard => 1.3,
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum TowerKind {
+    Arrow,
+    Cannon,
+    Frost,
+}
+
+impl TowerKind {
+    pub const ALL: [TowerKind; 3] = [TowerKind::Arrow, TowerKind::Cannon, TowerKind::Frost];
+
+    pub fn name(self) -> &'static str {
+        match self {
+            TowerKind::Arrow => "弓塔",
+            TowerKind::Cannon => "砲台",
+            TowerKind::Frost => "氷塔",
+        }
+    }
+
+    pub fn symbol(self) -> char {
+        match self {
+            TowerKind::Arrow => 'A',
+            TowerKind::Cannon => 'C',
+            TowerKind::Frost => 'F',
+        }
+    }
+
+    pub fn cost(self) -> u32 {
+        match self {
+            TowerKind::Arrow => 50,
+            TowerKind::Cannon => 100,
+            TowerKind::Frost => 80,
+        }
+    }
+
+    pub fn damage(self) -> f64 {
+        match self {
+            TowerKind::Arrow => 4.0,
+            TowerKind::Cannon => 12.0,
+            TowerKind::Frost => 1.0,
+        }
+    }
+
+    /// 射程 (マス, セル中心間の直線距離)。
+    pub fn range(self) -> f64 {
+        match self {
+            TowerKind::Arrow => 2.5,
+            TowerKind::Cannon => 2.0,
+            TowerKind::Frost => 2.0,
+        }
+    }
+
+    /// 発射間隔 (tick)。
+    pub fn cooldown(self) -> u32 {
+        match self {
+            TowerKind::Arrow => 5,
+            TowerKind::Cannon => 20,
+            TowerKind::Frost => 10,
+        }
+    }
+
+    pub fn description(self) -> &'static str {
+        match self {
+            TowerKind::Arrow => "速射・単体",
+            TowerKind::Cannon => "低速・範囲",
+            TowerKind::Frost => "減速",
+        }
+    }
+}
+
+/// 盤面タップ時の操作。
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Tool {
+    Build(TowerKind),
+    Sell,
+}
+
+impl Tool {
+    pub const ALL: [Tool; 4] = [
+        Tool::Build(TowerKind::Arrow),
+        Tool::Build(TowerKind::Cannon),
+        Tool::Build(TowerKind::Frost),
+        Tool::Sell,
+    ];
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum EnemyKind {
+    Grunt,
+    Runner,
+    Brute,
+}
+
+impl EnemyKind {
+    pub fn symbol(self) -> char {
+        match self {
+            EnemyKind::Grunt => 'g',
+            EnemyKind::Runner => 'r',
+            EnemyKind::Brute => 'B',
+        }
+    }
+
+    /// 基本 HP (ウェーブ・難易度で倍率がかかる)。
+    pub fn base_hp(self) -> f64 {
+        match self {
+            EnemyKind::Grunt => 10.0,
+            EnemyKind::Runner => 6.0,
+            EnemyKind::Brute => 40.0,
+        }
+    }
+
+    /// 移動速度 (マス/tick)。
+    pub fn speed(self) -> f64 {
+        match self {
+            EnemyKind::Grunt => 0.08,
+            EnemyKind::Runner => 0.14,
+            EnemyKind::Brute => 0.05,
+        }
+    }
+
+    pub fn bounty(self) -> u32 {
+        match self {
+            EnemyKind::Grunt => 5,
+            EnemyKind::Runner => 6,
+            EnemyKind::Brute => 20,
+        }
+    }
+
+    /// ゴールに抜けられたときに失うライフ。
+    pub fn leak_damage(self) -> u32 {
+        match self {
+            EnemyKind::Brute => 3,
+            _ => 1,
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct Enemy {
+    pub kind: EnemyKind,
+    pub hp: f64,
+    pub max_hp: f64,
+    /// 道に沿った位置 (`path` の index, 小数部は次のマスへの進行度)。
+    pub pos: f64,
+    /// 減速の残り tick。
+    pub slow_ticks: u32,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct Tower {
+    pub kind: TowerKind,
+    pub x: usize,
+    pub y: usize,
+    pub cooldown: u32,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Phase {
+    /// タイトル画面 (難易度選択)。
+    Title,
+    Playing,
+    /// 終了画面。`true` なら全ウェーブ防衛成功。
+    Over(bool),
+}
+
+pub struct DefenseState {
+    pub phase: Phase,
+    pub difficulty: Difficulty,
+    /// 道のマス (スタート → ゴール順)。
+    pub path: Vec<(usize, usize)>,
+    pub towers: Vec<Tower>,
+    pub enemies: Vec<Enemy>,
+    pub lives: u32,
+    pub credits: u32,
+    /// 開始済みのウェーブ数。
+    pub wave: u32,
+    /// このウェーブでまだ出現していない敵。
+    pub spawn_queue: Vec<EnemyKind>,
+    pub spawn_countdown: u32,
+    /// 開始したウェーブの終了処理 (ボーナス支払い) がまだ。
+    pub wave_running: bool,
+    pub tool: Tool,
+    pub cursor_x: usize,
+    pub cursor_y: usize,
+    pub kills: u32,
+    /// 被弾エフェクト `(x, y, 残り tick)`。
+    pub hit_flash: Vec<(usize, usize, u32)>,
+    pub log: Vec<String>,
+}
+
+impl DefenseState {
+    pub fn new() -> Self {
+        Self {
+            phase: Phase::Title,
+            difficulty: Difficulty::Normal,
+            path: build_path(),
+            towers: Vec::new(),
+            enemies: Vec::new(),
+            lives: 0,
+            credits: 0,
+            wave: 0,
+            spawn_queue: Vec::new(),
+            spawn_countdown: 0,
+            wave_running: false,
+            tool: Tool::Build(TowerKind::Arrow),
+            cursor_x: 1,
+            cursor_y: 3,
+            kills: 0,
+            hit_flash: Vec::new(),
+            log: Vec::new(),
+        }
+    }
+
+    pub fn is_path(&self, x: usize, y: usize) -> bool {
+        self.path.contains(&(x, y))
+    }
+
+    pub fn tower_at(&self, x: usize, y: usize) -> Option<&Tower> {
+        self.towers.iter().find(|t| t.x == x && t.y == y)
+    }
+
+    /// ウェーブ進行中 (出現待ちか盤面に敵がいる)。
+    pub fn wave_active(&self) -> bool {
+        !self.spawn_queue.is_empty() || !self.enemies.is_empty()
+    }
+
+    pub fn move_cursor(&mut self, dx: i32, dy: i32) {
+        self.cursor_x = (self.cursor_x as i32 + dx).clamp(0, GRID_W as i32 - 1) as usize;
+        self.cursor_y = (self.cursor_y as i32 + dy).clamp(0, GRID_H as i32 - 1) as usize;
+    }
+
+    pub fn add_log(&mut self, text: &str) {
+        self.log.push(text.to_string());
+        if self.log.len() > 30 {
+            self.log.remove(0);
+        }
+    }
+}
+
+impl Default for DefenseState {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// 折れ点を直線で結んで道のマス列に展開する。
+fn build_path() -> Vec<(usize, usize)> {
+    let mut path = vec![PATH_WAYPOINTS[0]];
+    for pair in PATH_WAYPOINTS.windows(2) {
+        let (mut x, mut y) = pair[0];
+        let (tx, ty) = pair[1];
+        while (x, y) != (tx, ty) {
+            if x < tx {
+                x += 1;
+            } else if x > tx {
+                x -= 1;
+            } else if y < ty {
+                y += 1;
+            } else {
+                y -= 1;
+            }
+            path.push((x, y));
+        }
+    }
+    path
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn path_is_contiguous_and_spans_the_board() {
+        let s = DefenseState::new();
+        assert_eq!(s.path.first(), Some(&(0, 1)));
+        assert_eq!(s.path.last(), Some(&(GRID_W - 1, 7)));
+        for w in s.path.windows(2) {
+            let (a, b) = (w[0], w[1]);
+            let d = a.0.abs_diff(b.0) + a.1.abs_diff(b.1);
+            assert_eq!(d, 1, "{:?} -> {:?}", a, b);
+        }
+        for (x, y) in &s.path {
+            assert!(*x < GRID_W && *y < GRID_H);
+        }
+    }
+
+    #[test]
+    fn cursor_is_clamped_to_board() {
+        let mut s = DefenseState::new();
+        s.move_cursor(-10, -10);
+        assert_eq!((s.cursor_x, s.cursor_y), (0, 0));
+        s.move_cursor(100, 100);
+        assert_eq!((s.cursor_x, s.cursor_y), (GRID_W - 1, GRID_H - 1));
+    }
+}
@@ -0,0 +1,8 @@
+//! Semantic action IDs for Deep Drill click targets.
+
+pub const DIG: u16 = 1;
+pub const SELL: u16 = 2;
+pub const PRESTIGE: u16 = 3;
+
+/// 強化購入: action_id = BUY_UPGRADE_BASE + Upgrade::index()
+pub const BUY_UPGRADE_BASE: u16 = 10;
@@ -0,0 +1,310 @@
+//! Deep Drill game logic — pure functions, fully testable.
+
+use super::state::{DrillState, Ore, Upgrade, ORE_COUNT};
+
+/// 強化なし・コアなしの掘削速度 (m/秒)。
+pub const BASE_DIG_RATE: f64 = 1.0;
+
+/// 岩盤の硬さが 2 倍になる深度 (m)。冷却装置で伸びる。
+pub const HARDNESS_SCALE: f64 = 200.0;
+
+/// 強化なしの貨物室容量。
+pub const BASE_CARGO: u64 = 50;
+
+/// コア回収ができる最低深度 (m)。
+pub const PRESTIGE_MIN_DEPTH: f64 = 500.0;
+
+/// 売却フラッシュの持続 tick 数。
+pub const SELL_FLASH_TICKS: u32 = 10;
+
+/// Advance the drill by `n` ticks (10 ticks/sec).
+pub fn tick(state: &mut DrillState, n: u32) {
+    for _ in 0..n {
+        tick_once(state);
+    }
+    state.anim_frame = state.anim_frame.wrapping_add(n);
+    state.sell_flash = state.sell_flash.saturating_sub(n);
+}
+
+fn tick_once(state: &mut DrillState) {
+    state.total_ticks += 1;
+    tick_conveyor(state);
+    let meters = dig_rate(state) / 10.0;
+    advance(state, meters);
+}
+
+/// 掘削速度 (m/秒)。ドリル刃とコアで伸び、深度 (岩盤の硬さ) で落ちる。
+pub fn dig_rate(state: &DrillState) -> f64 {
+    let bit = 1.0 + 0.5 * state.level(Upgrade::Bit) as f64;
+    BASE_DIG_RATE * bit * core_multiplier(state) / hardness(state)
+}
+
+/// 現在深度での岩盤の硬さ (1.0 = 地表)。
+pub fn hardness(state: &DrillState) -> f64 {
+    let scale = HARDNESS_SCALE * (1.0 + 0.5 * state.level(Upgrade::Cooling) as f64);
+    1.0 + state.depth / scale
+}
+
+/// 地核コアによる倍率 (掘削速度・売値の両方に乗る)。
+pub fn core_multiplier(state: &DrillState) -> f64 {
+    1.0 + 0.1 * state.cores as f64
+}
+
+pub fn cargo_capacity(state: &DrillState) -> u64 {
+    BASE_CARGO * (1 + state.level(Upgrade::Cargo) as u64)
+}
+
+/// 自動搬出の売却間隔 (tick)。未購入なら `None`。
+pub fn conveyor_interval(state: &DrillState) -> Option<u32> {
+    match state.level(Upgrade::Conveyor) {
+        0 => None,
+        lv => Some(100u32.saturating_sub(20 * (lv - 1)).max(10)),
+    }
+}
+
+/// `upgrade` を現在レベルから 1 上げるコスト。
+pub fn upgrade_cost(state: &DrillState, upgrade: Upgrade) -> u64 {
+    let lv = state.level(upgrade) as i32;
+    (upgrade.base_cost() as f64 * upgrade.cost_growth().powi(lv)).round() as u64
+}
+
+/// 貨物室の鉱石の売却額 (コア倍率込み)。
+pub fn cargo_value(state: &DrillState) -> u64 {
+    let raw: u64 = Ore::ALL
+        .iter()
+        .map(|o| state.cargo[o.index()] * o.value())
+        .sum();
+    (raw as f64 * core_multiplier(state)).floor() as u64
+}
+
+/// 今コア回収したら得られるコア数。
+pub fn prestige_reward(state: &DrillState) -> u64 {
+    if state.run_max_depth < PRESTIGE_MIN_DEPTH {
+        return 0;
+    }
+    (state.run_max_depth / 100.0).sqrt().floor() as u64
+}
+
+/// `meters` だけ掘り進める。貨物室が満杯なら止まる。
+/// 1m 掘るごとにその深度の鉱石を 1 個得る。
+fn advance(state: &mut DrillState, meters: f64) {
+    if state.cargo_used() >= cargo_capacity(state) {
+        if !state.stalled {
+            state.stalled = true;
+            state.add_log("貨物室が満杯！売却してください");
+        }
+        return;
+    }
+    state.stalled = false;
+
+    let prev_ore = Ore::at_depth(state.depth);
+    state.depth += meters;
+    state.dig_remainder += meters;
+    let capacity = cargo_capacity(state);
+    while state.dig_remainder >= 1.0 {
+        state.dig_remainder -= 1.0;
+        if state.cargo_used() >= capacity {
+            break;
+        }
+        let ore = Ore::at_depth(state.depth);
+        state.cargo[ore.index()] += 1;
+    }
+
+    if state.depth > state.run_max_depth {
+        state.run_max_depth = state.depth;
+    }
+    if state.depth > state.best_depth {
+        state.best_depth = state.depth;
+    }
+    let ore = Ore::at_depth(state.depth);
+    if ore != prev_ore {
+        state.add_log(&format!("{}m: {}の層に到達！", ore.unlock_depth() as u64, ore.name()));
+    }
+}
+
+fn tick_conveyor(state: &mut DrillState) {
+    let Some(interval) = conveyor_interval(state) else {
+        return;
+    };
+    if state.conveyor_countdown > 0 {
+        state.conveyor_countdown -= 1;
+        return;
+    }
+    state.conveyor_countdown = interval;
+    if state.cargo_used() > 0 {
+        sell_all(state);
+    }
+}
+
+/// 手動掘削 (タップ)。自動掘削 0.5 秒ぶん、最低 1m。
+pub fn manual_dig(state: &mut DrillState) {
+    let meters = (dig_rate(state) * 0.5).max(1.0);
+    advance(state, meters);
+}
+
+/// 貨物室の鉱石を全て売却し、得た gold を返す。
+pub fn sell_all(state: &mut DrillState) -> u64 {
+    let value = cargo_value(state);
+    if value == 0 && state.cargo_used() == 0 {
+        return 0;
+    }
+    state.cargo = [0; ORE_COUNT];
+    state.gold += value;
+    state.run_gold_earned += value;
+    state.last_sale = value;
+    state.sell_flash = SELL_FLASH_TICKS;
+    state.stalled = false;
+    value
+}
+
+/// 強化を購入する。gold 不足なら false。
+pub fn buy_upgrade(state: &mut DrillState, upgrade: Upgrade) -> bool {
+    let cost = upgrade_cost(state, upgrade);
+    if state.gold < cost {
+        return false;
+    }
+    state.gold -= cost;
+    state.upgrades[upgrade.index()] += 1;
+    state.add_log(&format!(
+        "{} Lv{} (-{}G)",
+        upgrade.name(),
+        state.level(upgrade),
+        cost
+    ));
+    true
+}
+
+/// コア回収 (転生)。1 回目の呼び出しで確認状態に入り、2 回目で実行する。
+/// 実行したら true。
+pub fn request_prestige(state: &mut DrillState) -> bool {
+    if prestige_reward(state) == 0 {
+        state.confirm_prestige = false;
+        state.add_log(&format!("コア回収には {}m 到達が必要", PRESTIGE_MIN_DEPTH as u64));
+        return false;
+    }
+    if !state.confirm_prestige {
+        state.confirm_prestige = true;
+        return false;
+    }
+    prestige(state);
+    true
+}
+
+fn prestige(state: &mut DrillState) {
+    let reward = prestige_reward(state);
+    state.cores += reward;
+    state.prestige_count += 1;
+    state.depth = 0.0;
+    state.run_max_depth = 0.0;
+    state.gold = 0;
+    state.cargo = [0; ORE_COUNT];
+    state.upgrades = Default::default();
+    state.run_gold_earned = 0;
+    state.dig_remainder = 0.0;
+    state.conveyor_countdown = 0;
+    state.confirm_prestige = false;
+    state.stalled = false;
+    state.add_log(&format!("地核コア +{} を回収！地表から再出発", reward));
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn digs_automatically_and_collects_ore() {
+        let mut s = DrillState::new();
+        tick(&mut s, 100); // 10 秒
+        assert!(s.depth > 9.0 && s.depth < 10.0, "depth = {}", s.depth);
+        assert_eq!(s.cargo[Ore::Coal.index()], s.depth.floor() as u64);
+    }
+
+    #[test]
+    fn deeper_is_slower() {
+        let mut s = DrillState::new();
+        let surface = dig_rate(&s);
+        s.depth = 1_000.0;
+        assert!(dig_rate(&s) < surface);
+        s.upgrades[Upgrade::Cooling.index()] = 2;
+        let cooled = dig_rate(&s);
+        s.upgrades[Upgrade::Cooling.index()] = 0;
+        assert!(cooled > dig_rate(&s));
+    }
+
+    #[test]
+    fn full_cargo_stalls_until_sold() {
+        let mut s = DrillState::new();
+        s.cargo[Ore::Coal.index()] = cargo_capacity(&s);
+        let before = s.depth;
+        tick(&mut s, 20);
+        assert_eq!(s.depth, before);
+        assert!(s.stalled);
+
+        assert_eq!(sell_all(&mut s), BASE_CARGO);
+        assert_eq!(s.gold, BASE_CARGO);
+        tick(&mut s, 20);
+        assert!(s.depth > before);
+    }
+
+    #[test]
+    fn conveyor_sells_automatically() {
+        let mut s = DrillState::new();
+        s.upgrades[Upgrade::Conveyor.index()] = 1;
+        s.cargo[Ore::Iron.index()] = 3;
+        tick(&mut s, 1);
+        assert_eq!(s.cargo_used(), 0);
+        assert_eq!(s.gold, 3 * Ore::Iron.value());
+    }
+
+    #[test]
+    fn upgrade_costs_grow_and_require_gold() {
+        let mut s = DrillState::new();
+        assert!(!buy_upgrade(&mut s, Upgrade::Bit));
+        s.gold = 1_000;
+        let first = upgrade_cost(&s, Upgrade::Bit);
+        assert!(buy_upgrade(&mut s, Upgrade::Bit));
+        assert_eq!(s.gold, 1_000 - first);
+        assert!(upgrade_cost(&s, Upgrade::Bit) > first);
+        assert_eq!(s.level(Upgrade::Bit), 1);
+    }
+
+    #[test]
+    fn manual_dig_moves_at_least_one_meter() {
+        let mut s = DrillState::new();
+        manual_dig(&mut s);
+        assert!(s.depth >= 1.0);
+        assert_eq!(s.cargo_used(), 1);
+    }
+
+    #[test]
+    fn prestige_needs_depth_and_confirmation() {
+        let mut s = DrillState::new();
+        assert!(!request_prestige(&mut s));
+        assert!(!s.confirm_prestige);
+
+        s.depth = 900.0;
+        s.run_max_depth = 900.0;
+        s.best_depth = 900.0;
+        s.gold = 500;
+        s.upgrades[Upgrade::Bit.index()] = 3;
+        assert!(!request_prestige(&mut s), "first press only asks for confirmation");
+        assert!(s.confirm_prestige);
+        assert!(request_prestige(&mut s));
+
+        assert_eq!(s.cores, 3); // sqrt(9)
+        assert_eq!(s.depth, 0.0);
+        assert_eq!(s.gold, 0);
+        assert_eq!(s.level(Upgrade::Bit), 0);
+        assert_eq!(s.best_depth, 900.0, "best depth survives prestige");
+        assert!(dig_rate(&s) > BASE_DIG_RATE);
+    }
+
+    #[test]
+    fn cores_boost_sale_value() {
+        let mut s = DrillState::new();
+        s.cargo[Ore::Gold.index()] = 10;
+        let plain = cargo_value(&s);
+        s.cores = 5;
+        assert_eq!(cargo_value(&s), (plain as f64 * 1.5) as u64);
+    }
+}
@@ -0,0 +1,238 @@
+//! Deep Drill — 縦に掘り進める放置採掘ゲーム。
+//!
+//! コアループ:
+//!   1. ドリルが自動で掘り進み、深度に応じた鉱石が貨物室に溜まる
+//!   2. 鉱石を売却して gold を得る (自動搬出を買えば放置で売れる)
+//!   3. gold でドリル刃・冷却・貨物室・自動搬出を強化
+//!   4. 深くなるほど岩盤が硬くなり減速する → 地核コアを回収して再出発
+
+pub mod actions;
+pub mod logic;
+pub mod render;
+pub mod save;
+pub mod state;
+
+use std::cell::RefCell;
+use std::rc::Rc;
+
+use ratzilla::ratatui::layout::Rect;
+use ratzilla::ratatui::Frame;
+
+use crate::games::{Game, GameChoice};
+use crate::input::{ClickState, InputEvent};
+use crate::scores::Score;
+use crate::sound;
+
+use actions::*;
+use state::{DrillState, Upgrade};
+
+pub struct DrillGame {
+    pub state: DrillState,
+    save_countdown: u32,
+}
+
+/// プレイヤー操作。キーとクリックの両方をここに正規化する。
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum DrillAction {
+    Dig,
+    Sell,
+    Prestige,
+    Buy(Upgrade),
+}
+
+impl DrillGame {
+    pub fn new() -> Self {
+        #[allow(unused_mut)]
+        let mut state = DrillState::new();
+
+        #[cfg(target_arch = "wasm32")]
+        if save::load_game(&mut state) {
+            state.add_log("セーブデータをロードしました");
+        }
+
+        Self {
+            state,
+            save_countdown: save::AUTOSAVE_INTERVAL,
+        }
+    }
+
+    fn key_to_action(ch: char) -> Option<DrillAction> {
+        match ch {
+            ' ' | 'd' => Some(DrillAction::Dig),
+            's' => Some(DrillAction::Sell),
+            'p' => Some(DrillAction::Prestige),
+            '1'..='4' => Upgrade::ALL
+                .get(ch as usize - '1' as usize)
+                .map(|u| DrillAction::Buy(*u)),
+            _ => None,
+        }
+    }
+
+    fn click_to_action(id: u16) -> Option<DrillAction> {
+        match id {
+            DIG => Some(DrillAction::Dig),
+            SELL => Some(DrillAction::Sell),
+            PRESTIGE => Some(DrillAction::Prestige),
+            id if id >= BUY_UPGRADE_BASE => Upgrade::ALL
+                .get((id - BUY_UPGRADE_BASE) as usize)
+                .map(|u| DrillAction::Buy(*u)),
+            _ => None,
+        }
+    }
+
+    fn apply(&mut self, action: DrillAction) {
+        // コア回収の確認は、他の操作を挟んだら取り消す。
+        if action != DrillAction::Prestige {
+            self.state.confirm_prestige = false;
+        }
+        let save_worthy = match action {
+            DrillAction::Dig => {
+                logic::manual_dig(&mut self.state);
+                false
+            }
+            DrillAction::Sell => {
+                if logic::sell_all(&mut self.state) > 0 {
+                    sound::play(sound::PURCHASE);
+                }
+                true
+            }
+            DrillAction::Buy(u) => {
+                let ok = logic::buy_upgrade(&mut self.state, u);
+                sound::play(if ok { sound::ENHANCE } else { sound::ERROR });
+                ok
+            }
+            DrillAction::Prestige => {
+                let done = logic::request_prestige(&mut self.state);
+                if done {
+                    sound::play(sound::LEVEL_UP);
+                }
+                done
+            }
+        };
+        if save_worthy {
+            self.flush_save();
+        }
+    }
+
+    fn flush_save(&mut self) {
+        #[cfg(any(target_arch = "wasm32", test))]
+        save::save_game(&self.state);
+        self.save_countdown = save::AUTOSAVE_INTERVAL;
+    }
+}
+
+impl Game for DrillGame {
+    fn choice(&self) -> GameChoice {
+        GameChoice::Drill
+    }
+
+    fn handle_input(&mut self, event: &InputEvent) -> bool {
+        let action = match event {
+            InputEvent::Key(c) => Self::key_to_action(*c),
+            InputEvent::Click(_, id) => Self::click_to_action(*id),
+            InputEvent::LongPress(..) | InputEvent::DoubleTap(..) | InputEvent::Drag(..) => None,
+        };
+        match action {
+            Some(a) => {
+                self.apply(a);
+                true
+            }
+            None => false,
+        }
+    }
+
+    fn tick(&mut self, delta_ticks: u32) {
+        logic::tick(&mut self.state, delta_ticks);
+        self.save_countdown = self.save_countdown.saturating_sub(delta_ticks);
+        if self.save_countdown == 0 {
+            self.flush_save();
+        }
+    }
+
+    fn save(&mut self) {
+        self.flush_save();
+    }
+
+    fn render(&self, f: &mut Frame, area: Rect, click_state: &Rc<RefCell<ClickState>>) {
+        render::render(&self.state, f, area, click_state);
+    }
+
+    fn score(&self) -> Option<Score> {
+        Some(Score::new(self.state.best_depth.floor() as u64, "最深記録", "m"))
+    }
+
+    #[cfg(any(feature = "debug-console", debug_assertions, test))]
+    fn debug_command(&mut self, args: &[&str]) -> Result<String, String> {
+        use crate::debug_console::{parse_amount, parse_count, unknown};
+        match args {
+            ["help"] => Ok("give gold <n> / set depth <m>".into()),
+            ["give", "gold", n] => {
+                let n = parse_count(n)?;
+                self.state.gold = self.state.gold.saturating_add(n);
+                Ok(format!("ゴールド +{}", n))
+            }
+            ["set", "depth", m] => {
+                let m = parse_amount(m)?;
+                let s = &mut self.state;
+                s.depth = m;
+                s.run_max_depth = s.run_max_depth.max(m);
+                s.best_depth = s.best_depth.max(m);
+                Ok(format!("深度 {}m", m))
+            }
+            _ => Err(unknown(args)),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::input::ClickScope;
+
+    fn click(id: u16) -> InputEvent {
+        InputEvent::Click(ClickScope::Game(GameChoice::Drill), id)
+    }
+
+    #[test]
+    fn keys_and_clicks_map_to_same_actions() {
+        let mut g = DrillGame::new();
+        assert!(g.handle_input(&InputEvent::Key('d')));
+        assert!(g.handle_input(&click(DIG)));
+        assert_eq!(g.state.cargo_used(), 2);
+        assert!(g.handle_input(&click(SELL)));
+        assert_eq!(g.state.gold, 2);
+        assert!(!g.handle_input(&InputEvent::Key('q')), "q falls through to the menu");
+    }
+
+    #[test]
+    fn buy_upgrade_via_click_resets_autosave_timer() {
+        let mut g = DrillGame::new();
+        g.state.gold = 1_000;
+        g.tick(100);
+        assert_eq!(g.save_countdown, save::AUTOSAVE_INTERVAL - 100);
+        g.handle_input(&click(BUY_UPGRADE_BASE + Upgrade::Cargo.index() as u16));
+        assert_eq!(g.state.level(Upgrade::Cargo), 1);
+        assert_eq!(g.save_countdown, save::AUTOSAVE_INTERVAL);
+    }
+
+    #[test]
+    fn save_hook_flushes_without_waiting_for_the_timer() {
+        let mut g = DrillGame::new();
+        g.tick(100);
+        g.save();
+        assert_eq!(g.save_countdown, save::AUTOSAVE_INTERVAL);
+    }
+
+    #[test]
+    fn other_actions_cancel_prestige_confirmation() {
+        let mut g = DrillGame::new();
+        g.state.run_max_depth = 600.0;
+        g.handle_input(&InputEvent::Key('p'));
+        assert!(g.state.confirm_prestige);
+        g.handle_input(&InputEvent::Key('d'));
+        assert!(!g.state.confirm_prestige);
+        g.handle_input(&InputEvent::Key('p'));
+        g.handle_input(&InputEvent::Key('p'));
+        assert_eq!(g.state.cores, 2);
+    }
+}
@@ -0,0 +1,367 @@
+//! Deep Drill rendering.
+
+use std::cell::RefCell;
+use std::rc::Rc;
+
+use ratzilla::ratatui::layout::{Constraint, Direction, Layout, Rect};
+use ratzilla::ratatui::style::{Color, Modifier, Style};
+use ratzilla::ratatui::text::{Line, Span};
+use ratzilla::ratatui::widgets::{Block, Borders, Paragraph, Wrap};
+use ratzilla::ratatui::Frame;
+
+use crate::input::{is_narrow_layout, ClickState};
+use crate::widgets::ClickableList;
+
+use super::actions::*;
+use super::logic::{
+    cargo_capacity, cargo_value, conveyor_interval, core_multiplier, dig_rate, hardness,
+    prestige_reward, upgrade_cost, PRESTIGE_MIN_DEPTH,
+};
+use super::state::{DrillState, Ore, Upgrade};
+
+/// 坑道表示の 1 行あたりの深さ (m)。
+const METERS_PER_ROW: f64 = 5.0;
+
+/// ドリルヘッドのアニメーション。
+const DRILL_FRAMES: &[&str] = &["╲▼╱", "─▼─", "╱▼╲", "─▼─"];
+
+pub fn render(
+    state: &DrillState,
+    f: &mut Frame,
+    area: Rect,
+    click_state: &Rc<RefCell<ClickState>>,
+) {
+    if is_narrow_layout(area.width) {
+        render_narrow(state, f, area, click_state);
+    } else {
+        render_wide(state, f, area, click_state);
+    }
+}
+
+fn render_wide(
+    state: &DrillState,
+    f: &mut Frame,
+    area: Rect,
+    click_state: &Rc<RefCell<ClickState>>,
+) {
+    let h_chunks = Layout::default()
+        .direction(Direction::Horizontal)
+        .constraints([Constraint::Length(24), Constraint::Min(30)])
+        .split(area);
+    let right = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Length(3), // Header
+            Constraint::Min(16),   // Controls
+            Constraint::Length(6), // Log
+        ])
+        .split(h_chunks[1]);
+
+    render_shaft(state, f, h_chunks[0]);
+    render_header(state, f, right[0]);
+    render_controls(state, f, right[1], click_state);
+    render_log(state, f, right[2]);
+}
+
+fn render_narrow(
+    state: &DrillState,
+    f: &mut Frame,
+    area: Rect,
+    click_state: &Rc<RefCell<ClickState>>,
+) {
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Length(3), // Header
+            Constraint::Length(9), // Shaft
+            Constraint::Min(14),   // Controls
+        ])
+        .split(area);
+
+    render_header(state, f, chunks[0]);
+    render_shaft(state, f, chunks[1]);
+    render_controls(state, f, chunks[2], click_state);
+}
+
+fn ore_color(ore: Ore) -> Color {
+    match ore {
+        Ore::Coal => Color::DarkGray,
+        Ore::Copper => Color::LightRed,
+        Ore::Iron => Color::Gray,
+        Ore::Silver => Color::White,
+        Ore::Gold => Color::Yellow,
+        Ore::Diamond => Color::LightCyan,
+        Ore::Mythril => Color::LightMagenta,
+    }
+}
+
+fn render_header(state: &DrillState, f: &mut Frame, area: Rect) {
+    let mut spans = vec![
+        Span::styled(
+            format!(" {:.0}m", state.depth),
+            Style::default().fg(Color::LightGreen).add_modifier(Modifier::BOLD),
+        ),
+        Span::styled(
+            format!("  {}G", state.gold),
+            Style::default().fg(Color::Yellow).add_modifier(Modifier::BOLD),
+        ),
+    ];
+    if state.sell_flash > 0 {
+        spans.push(Span::styled(
+            format!(" +{}G", state.last_sale),
+            Style::default()
+                .fg(Color::LightYellow)
+                .add_modifier(Modifier::BOLD | Modifier::REVERSED),
+        ));
+    }
+    if state.cores > 0 {
+        spans.push(Span::styled(
+            format!("  コア{}", state.cores),
+            Style::default().fg(Color::LightMagenta),
+        ));
+    }
+    let widget = Paragraph::new(Line::from(spans)).block(
+        Block::default()
+            .borders(Borders::ALL)
+            .border_style(Style::default().fg(Color::LightGreen))
+            .title(" Deep Drill "),
+    );
+    f.render_widget(widget, area);
+}
+
+/// 地中の断面図。ドリルヘッドを上から 1/3 の位置に固定し、深度に応じて
+/// 地層がスクロールする。岩盤中の鉱石記号は行の絶対位置から決定的に
+/// 散らすので、掘り進めても模様がちらつかない。
+fn render_shaft(state: &DrillState, f: &mut Frame, area: Rect) {
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(Color::DarkGray))
+        .title(format!(" {} ", Ore::at_depth(state.depth).name()));
+    let inner = block.inner(area);
+    let rows = inner.height as i64;
+    let width = inner.width as usize;
+    let head_row = rows / 3;
+    let shaft_w = 3usize.min(width);
+    let shaft_left = width.saturating_sub(shaft_w) / 2;
+
+    let mut lines = Vec::new();
+    for r in 0..rows {
+        let row_depth = state.depth + (r - head_row) as f64 * METERS_PER_ROW;
+        if row_depth < -METERS_PER_ROW {
+            lines.push(Line::from(""));
+            continue;
+        }
+        if row_depth < 0.0 {
+            lines.push(Line::from(Span::styled(
+                "▁".repeat(width),
+                Style::default().fg(Color::Green),
+            )));
+            continue;
+        }
+        let abs_row = (row_depth / METERS_PER_ROW).floor() as u64;
+        let ore = Ore::at_depth(row_depth);
+        let mut spans = Vec::with_capacity(width);
+        for col in 0..width {
+            let in_shaft = col >= shaft_left && col < shaft_left + shaft_w;
+            if r < head_row && in_shaft {
+                spans.push(Span::raw(" "));
+            } else if r == head_row && in_shaft {
+                let frame = DRILL_FRAMES[(state.anim_frame / 2) as usize % DRILL_FRAMES.len()];
+                let ch = frame.chars().nth(col - shaft_left).unwrap_or(' ');
+                let color = if state.stalled { Color::Red } else { Color::LightYellow };
+                spans.push(Span::styled(
+                    ch.to_string(),
+                    Style::default().fg(color).add_modifier(Modifier::BOLD),
+                ));
+            } else if rock_hash(abs_row, col as u64).is_multiple_of(7) {
+                spans.push(Span::styled(
+                    ore.symbol().to_string(),
+                    Style::default().fg(ore_color(ore)),
+                ));
+            } else {
+                spans.push(Span::styled("░", Style::default().fg(Color::DarkGray)));
+            }
+        }
+        lines.push(Line::from(spans));
+    }
+
+    f.render_widget(Paragraph::new(lines).block(block), area);
+}
+
+fn rock_hash(row: u64, col: u64) -> u64 {
+    let mut x = row.wrapping_mul(0x9E37_79B9_7F4A_7C15) ^ col.wrapping_mul(0xC2B2_AE3D_27D4_EB4F);
+    x ^= x >> 29;
+    x = x.wrapping_mul(0xBF58_476D_1CE4_E5B9);
+    x ^ (x >> 32)
+}
+
+fn render_controls(
+    state: &DrillState,
+    f: &mut Frame,
+    area: Rect,
+    click_state: &Rc<RefCell<ClickState>>,
+) {
+    let mut cl = ClickableList::new();
+
+    // 掘削ステータス
+    cl.push(Line::from(vec![
+        Span::styled(
+            format!(" 掘削 {:.2}m/s", dig_rate(state)),
+            Style::default().fg(Color::LightGreen),
+        ),
+        Span::styled(
+            format!("  硬さ×{:.1}", hardness(state)),
+            Style::default().fg(Color::DarkGray),
+        ),
+        Span::styled(
+            format!("  最深 {:.0}m", state.best_depth),
+            Style::default().fg(Color::DarkGray),
+        ),
+    ]));
+
+    // 貨物室
+    let used = state.cargo_used();
+    let cap = cargo_capacity(state);
+    let bar_w = 10u64;
+    let filled = (used * bar_w).checked_div(cap).unwrap_or(0).min(bar_w);
+    let bar_color = if used >= cap { Color::Red } else { Color::Cyan };
+    let mut cargo_line = vec![
+        Span::styled(" 貨物 ", Style::default().fg(Color::White)),
+        Span::styled("█".repeat(filled as usize), Style::default().fg(bar_color)),
+        Span::styled(
+            "░".repeat((bar_w - filled) as usize),
+            Style::default().fg(Color::DarkGray),
+        ),
+        Span::styled(format!(" {}/{}", used, cap), Style::default().fg(bar_color)),
+    ];
+    if let Some(interval) = conveyor_interval(state) {
+        cargo_line.push(Span::styled(
+            format!("  自動搬出 {:.0}秒毎", interval as f64 / 10.0),
+            Style::default().fg(Color::DarkGray),
+        ));
+    }
+    cl.push(Line::from(cargo_line));
+    cl.push(Line::from(""));
+
+    // 操作
+    cl.push_clickable(
+        Line::from(Span::styled(
+            " [D] 掘る",
+            Style::default().fg(Color::LightGreen).add_modifier(Modifier::BOLD),
+        )),
+        DIG,
+    );
+    let value = cargo_value(state);
+    let sell_style = if used > 0 {
+        Style::default().fg(Color::Yellow)
+    } else {
+        Style::default().fg(Color::DarkGray)
+    };
+    cl.push_clickable(
+        Line::from(Span::styled(format!(" [S] 売却 +{}G", value), sell_style)),
+        SELL,
+    );
+    let reward = prestige_reward(state);
+    let (prestige_label, prestige_style) = if state.confirm_prestige {
+        (
+            format!(" [P] もう一度でコア+{} (深度/G/強化リセット)", reward),
+            Style::default().fg(Color::LightMagenta).add_modifier(Modifier::BOLD | Modifier::REVERSED),
+        )
+    } else if reward > 0 {
+        (
+            format!(" [P] コア回収 +{} (×{:.1}→×{:.1})", reward, core_multiplier(state), 1.0 + 0.1 * (state.cores + reward) as f64),
+            Style::default().fg(Color::LightMagenta),
+        )
+    } else {
+        (
+            format!(" [P] コア回収 ({}m で解放)", PRESTIGE_MIN_DEPTH as u64),
+            Style::default().fg(Color::DarkGray),
+        )
+    };
+    cl.push_clickable(Line::from(Span::styled(prestige_label, prestige_style)), PRESTIGE);
+    cl.push(Line::from(""));
+
+    // 強化
+    cl.push(Line::from(Span::styled(
+        " ── 強化 ──",
+        Style::default().fg(Color::Cyan).add_modifier(Modifier::BOLD),
+    )));
+    for (i, u) in Upgrade::ALL.iter().enumerate() {
+        let cost = upgrade_cost(state, *u);
+        let affordable = state.gold >= cost;
+        let name_style = if affordable {
+            Style::default().fg(Color::White)
+        } else {
+            Style::default().fg(Color::DarkGray)
+        };
+        let cost_style = if affordable {
+            Style::default().fg(Color::Yellow)
+        } else {
+            Style::default().fg(Color::Red)
+        };
+        cl.push_clickable(
+            Line::from(vec![
+                Span::styled(format!(" [{}] {} Lv{}", i + 1, u.name(), state.level(*u)), name_style),
+                Span::styled(format!(" {}G", cost), cost_style),
+                Span::styled(format!(" {}", u.description()), Style::default().fg(Color::DarkGray)),
+            ]),
+            BUY_UPGRADE_BASE + i as u16,
+        );
+    }
+    cl.push(Line::from(""));
+
+    // 鉱層 (到達済みの層だけ名前を出す)
+    cl.push(Line::from(Span::styled(
+        " ── 鉱層 ──",
+        Style::default().fg(Color::Cyan).add_modifier(Modifier::BOLD),
+    )));
+    for ore in Ore::ALL {
+        let line = if state.best_depth >= ore.unlock_depth() {
+            Line::from(vec![
+                Span::styled(format!(" {} ", ore.symbol()), Style::default().fg(ore_color(ore))),
+                Span::styled(format!("{:<6}", ore.name()), Style::default().fg(ore_color(ore))),
+                Span::styled(
+                    format!(" {:>5}m {:>4}G  x{}", ore.unlock_depth() as u64, ore.value(), state.cargo[ore.index()]),
+                    Style::default().fg(Color::DarkGray),
+                ),
+            ])
+        } else {
+            Line::from(Span::styled(
+                format!(" ? ???    {:>5}m", ore.unlock_depth() as u64),
+                Style::default().fg(Color::DarkGray),
+            ))
+        };
+        cl.push(line);
+    }
+
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(Color::Cyan))
+        .title(" 採掘基地 ");
+    let mut cs = click_state.borrow_mut();
+    cl.render(f, area, block, &mut cs, false, 0);
+}
+
+fn render_log(state: &DrillState, f: &mut Frame, area: Rect) {
+    let visible = area.height.saturating_sub(2) as usize;
+    let lines: Vec<Line> = state
+        .log
+        .iter()
+        .rev()
+        .take(visible)
+        .enumerate()
+        .map(|(i, entry)| {
+            let color = if i == 0 { Color::White } else { Color::DarkGray };
+            Line::from(Span::styled(format!(" {}", entry), Style::default().fg(color)))
+        })
+        .collect();
+    let widget = Paragraph::new(lines)
+        .block(
+            Block::default()
+                .borders(Borders::ALL)
+                .border_style(Style::default().fg(Color::Blue))
+                .title(" Log "),
+        )
+        .wrap(Wrap { trim: false });
+    f.render_widget(widget, area);
+}
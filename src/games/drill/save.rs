@@ -0,0 +1,218 @@
+//! Deep Drill セーブ/ロード機能。
+//!
+//! 永続化するのは進行 (深度・gold・貨物・強化・コア) のみ。
+//! フラッシュやログ等の演出 state は保存しない。
+
+#[cfg(any(target_arch = "wasm32", test))]
+use serde::{Deserialize, Serialize};
+
+#[cfg(any(target_arch = "wasm32", test))]
+use super::state::DrillState;
+
+#[cfg(any(target_arch = "wasm32", test))]
+const SAVE_VERSION: u32 = 1;
+
+const STORAGE_KEY: &str = "deep_drill_save";
+
+/// アクティブなプロファイル用に名前空間化した保存キー。
+/// 設定画面のリセットはこのキーの中身をゴミ箱 ([`crate::storage::move_to_trash`]) へ移す。
+pub fn storage_key() -> String {
+    crate::profile::scoped_key(STORAGE_KEY)
+}
+
+/// イベントベース保存の保険として走らせる定期セーブ間隔 (tick 数)。
+pub const AUTOSAVE_INTERVAL: u32 = 300;
+
+#[cfg(any(target_arch = "wasm32", test))]
+#[derive(Serialize, Deserialize)]
+struct SaveData {
+    version: u32,
+    game: GameSave,
+}
+
+#[cfg(any(target_arch = "wasm32", test))]
+#[derive(Serialize, Deserialize, Default)]
+#[serde(default)]
+struct GameSave {
+    depth: f64,
+    run_max_depth: f64,
+    best_depth: f64,
+    gold: u64,
+    /// `Ore::index()` 順。
+    cargo: Vec<u64>,
+    /// `Upgrade::index()` 順。
+    upgrades: Vec<u32>,
+    cores: u64,
+    prestige_count: u32,
+    run_gold_earned: u64,
+    total_ticks: u64,
+}
+
+#[cfg(any(target_arch = "wasm32", test))]
+fn extract_save(state: &DrillState) -> SaveData {
+    SaveData {
+        version: SAVE_VERSION,
+        game: GameSave {
+            depth: state.depth,
+            run_max_depth: state.run_max_depth,
+            best_depth: state.best_depth,
+            gold: state.gold,
+            cargo: state.cargo.to_vec(),
+            upgrades: state.upgrades.to_vec(),
+            cores: state.cores,
+            prestige_count: state.prestige_count,
+            run_gold_earned: state.run_gold_earned,
+            total_ticks: state.total_ticks,
+        },
+    }
+}
+
+#[cfg(any(target_arch = "wasm32", test))]
+fn apply_save(state: &mut DrillState, save: &GameSave) {
+    // NaN / 負値の深度は壊れたセーブとみなして地表に戻す。
+    let sane = |d: f64| if d.is_finite() && d > 0.0 { d } else { 0.0 };
+    state.depth = sane(save.depth);
+    state.run_max_depth = sane(save.run_max_depth).max(state.depth);
+    state.best_depth = sane(save.best_depth).max(state.run_max_depth);
+    state.gold = save.gold;
+    for (i, slot) in state.cargo.iter_mut().enumerate() {
+        *slot = save.cargo.get(i).copied().unwrap_or(0);
+    }
+    for (i, slot) in state.upgrades.iter_mut().enumerate() {
+        *slot = save.upgrades.get(i).copied().unwrap_or(0);
+    }
+    state.cores = save.cores;
+    state.prestige_count = save.prestige_count;
+    state.run_gold_earned = save.run_gold_earned;
+    state.total_ticks = save.total_ticks;
+}
+
+#[cfg(any(target_arch = "wasm32", test))]
+fn get_storage() -> Option<crate::storage::Store> {
+    crate::storage::get()
+}
+
+/// テストでも [`crate::storage`] のメモリ置き場に書く (画面遷移時の保存を
+/// 外から確かめられるように)。
+#[cfg(any(target_arch = "wasm32", test))]
+pub fn save_game(state: &DrillState) {
+    let json = match serde_json::to_string(&extract_save(state)) {
+        Ok(j) => j,
+        Err(e) => {
+            web_sys::console::warn_1(&format!("Deep Drill: セーブのシリアライズに失敗: {e}").into());
+            return;
+        }
+    };
+    if let Some(storage) = get_storage() {
+        if let Err(e) = storage.set_item(&storage_key(), &json) {
+            web_sys::console::warn_1(
+                &format!("Deep Drill: localStorage への保存に失敗: {e:?}").into(),
+            );
+        }
+    }
+}
+
+#[cfg(target_arch = "wasm32")]
+pub fn load_game(state: &mut DrillState) -> bool {
+    let Some(storage) = get_storage() else {
+        return false;
+    };
+    let json = match storage.get_item(&storage_key()) {
+        Ok(Some(j)) => j,
+        _ => return false,
+    };
+    let save_data: SaveData = match serde_json::from_str(&json) {
+        Ok(d) => d,
+        Err(e) => {
+            web_sys::console::warn_1(
+                &format!("Deep Drill: セーブデータのパースに失敗 (破棄します): {e}").into(),
+            );
+            let _ = storage.remove_item(&storage_key());
+            return false;
+        }
+    };
+    apply_save(state, &save_data.game);
+    true
+}
+
+/// メインメニュー用の 1 行の進捗要約。セーブが無ければ `None`。
+/// セーブ全体を読み込むので、メニューに入るときに 1 回だけ呼ぶこと。
+pub fn progress_summary() -> Option<String> {
+    #[cfg(target_arch = "wasm32")]
+    {
+        let mut state = DrillState::new();
+        load_game(&mut state).then(|| summarize(&state))
+    }
+    #[cfg(not(target_arch = "wasm32"))]
+    None
+}
+
+#[cfg(any(target_arch = "wasm32", test))]
+fn summarize(state: &DrillState) -> String {
+    format!("深度 {:.0}m / 最深 {:.0}m", state.depth, state.best_depth)
+}
+
+/// extract → JSON → apply → extract の前後の JSON を返す (fuzz ハーネスの往復検査用)。
+#[cfg(test)]
+pub(crate) fn roundtrip_json(state: &DrillState) -> (String, String) {
+    let before = serde_json::to_string(&extract_save(state)).unwrap();
+    let loaded: SaveData = serde_json::from_str(&before).unwrap();
+    let mut restored = DrillState::new();
+    apply_save(&mut restored, &loaded.game);
+    let after = serde_json::to_string(&extract_save(&restored)).unwrap();
+    (before, after)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::games::drill::state::{Ore, Upgrade};
+
+    #[test]
+    fn menu_summary_shows_depths() {
+        let mut s = DrillState::new();
+        s.depth = 120.4;
+        s.best_depth = 980.0;
+        assert_eq!(summarize(&s), "深度 120m / 最深 980m");
+    }
+
+    #[test]
+    fn extract_and_apply_roundtrip() {
+        let mut original = DrillState::new();
+        original.depth = 432.5;
+        original.run_max_depth = 500.0;
+        original.best_depth = 1_200.0;
+        original.gold = 9_876;
+        original.cargo[Ore::Silver.index()] = 12;
+        original.upgrades[Upgrade::Conveyor.index()] = 2;
+        original.cores = 7;
+        original.prestige_count = 2;
+        original.total_ticks = 12_345;
+
+        let json = serde_json::to_string(&extract_save(&original)).unwrap();
+        let parsed: SaveData = serde_json::from_str(&json).unwrap();
+        let mut restored = DrillState::new();
+        apply_save(&mut restored, &parsed.game);
+
+        assert_eq!(restored.depth, original.depth);
+        assert_eq!(restored.run_max_depth, original.run_max_depth);
+        assert_eq!(restored.best_depth, original.best_depth);
+        assert_eq!(restored.gold, original.gold);
+        assert_eq!(restored.cargo, original.cargo);
+        assert_eq!(restored.upgrades, original.upgrades);
+        assert_eq!(restored.cores, original.cores);
+        assert_eq!(restored.prestige_count, original.prestige_count);
+        assert_eq!(restored.total_ticks, original.total_ticks);
+    }
+
+    #[test]
+    fn missing_fields_fall_back_to_defaults() {
+        let parsed: SaveData =
+            serde_json::from_str(r#"{"version":1,"game":{"gold":5,"depth":-3.0}}"#).unwrap();
+        let mut s = DrillState::new();
+        apply_save(&mut s, &parsed.game);
+        assert_eq!(s.gold, 5);
+        assert_eq!(s.depth, 0.0);
+        assert_eq!(s.cargo_used(), 0);
+    }
+}
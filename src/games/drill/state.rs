@@ -0,0 +1,272 @@
+//! Deep Drill — game state.
+//!
+//! 純粋なデータ定義のみ。ロジックは logic.rs に置く。
+//!
+//! ## 進行軸
+//!
+//! 1. **深度**: ドリルが自動で掘り進む。深いほど岩盤が硬く掘削速度が落ちる
+//! 2. **鉱石**: 深度ごとに掘れる鉱石が変わる。貨物室に溜めて売却し gold にする
+//! 3. **強化**: gold でドリル刃・冷却・貨物室・自動搬出を強化する
+//! 4. **コア回収 (転生)**: 深度・gold・強化を捨てて地核コアを得る。コアは永続倍率
+
+/// 鉱石の種類。深度順に並べる (index が大きいほど深い層)。
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Ore {
+    Coal,
+    Copper,
+    Iron,
+    Silver,
+    Gold,
+    Diamond,
+    Mythril,
+}
+
+pub const ORE_COUNT: usize = 7;
+
+impl Ore {
+    pub const ALL: [Ore; ORE_COUNT] = [
+        Ore::Coal,
+        Ore::Copper,
+        Ore::Iron,
+        Ore::Silver,
+        Ore::Gold,
+        Ore::Diamond,
+        Ore::Mythril,
+    ];
+
+    pub fn index(self) -> usize {
+        self as usize
+    }
+
+    pub fn name(self) -> &'static str {
+        match self {
+            Ore::Coal => "石炭",
+            Ore::Copper => "銅",
+            Ore::Iron => "鉄",
+            Ore::Silver => "銀",
+            Ore::Gold => "金",
+            Ore::Diamond => "ダイヤ",
+            Ore::Mythril => "ミスリル",
+        }
+    }
+
+    /// 坑道表示に使う 1 文字。
+    pub fn symbol(self) -> char {
+        match self {
+            Ore::Coal => '∙',
+            Ore::Copper => '○',
+            Ore::Iron => '◇',
+            Ore::Silver => '◆',
+            Ore::Gold => '★',
+            Ore::Diamond => '◈',
+            Ore::Mythril => '✦',
+        }
+    }
+
+    /// この鉱石の層が始まる深度 (m)。
+    pub fn unlock_depth(self) -> f64 {
+        match self {
+            Ore::Coal => 0.0,
+            Ore::Copper => 50.0,
+            Ore::Iron => 150.0,
+            Ore::Silver => 400.0,
+            Ore::Gold => 1_000.0,
+            Ore::Diamond => 2_500.0,
+            Ore::Mythril => 6_000.0,
+        }
+    }
+
+    /// 1 個あたりの売値 (gold)。
+    pub fn value(self) -> u64 {
+        match self {
+            Ore::Coal => 1,
+            Ore::Copper => 4,
+            Ore::Iron => 12,
+            Ore::Silver => 40,
+            Ore::Gold => 150,
+            Ore::Diamond => 600,
+            Ore::Mythril => 2_500,
+        }
+    }
+
+    /// 指定深度で掘れる鉱石 (その深度を含む最も深い層)。
+    pub fn at_depth(depth: f64) -> Ore {
+        Ore::ALL
+            .iter()
+            .rev()
+            .copied()
+            .find(|o| depth >= o.unlock_depth())
+            .unwrap_or(Ore::Coal)
+    }
+}
+
+/// gold で買える強化。
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Upgrade {
+    /// ドリル刃: 掘削速度 +50% / level
+    Bit,
+    /// 冷却装置: 岩盤の硬さによる減速を和らげる
+    Cooling,
+    /// 貨物室: 積載量 +50 / level
+    Cargo,
+    /// 自動搬出: 貨物を定期的に自動売却する (level で間隔短縮)
+    Conveyor,
+}
+
+pub const UPGRADE_COUNT: usize = 4;
+
+impl Upgrade {
+    pub const ALL: [Upgrade; UPGRADE_COUNT] =
+        [Upgrade::Bit, Upgrade::Cooling, Upgrade::Cargo, Upgrade::Conveyor];
+
+    pub fn index(self) -> usize {
+        self as usize
+    }
+
+    pub fn name(self) -> &'static str {
+        match self {
+            Upgrade::Bit => "ドリル刃",
+            Upgrade::Cooling => "冷却装置",
+            Upgrade::Cargo => "貨物室",
+            Upgrade::Conveyor => "自動搬出",
+        }
+    }
+
+    pub fn description(self) -> &'static str {
+        match self {
+            Upgrade::Bit => "掘削速度 +50%",
+            Upgrade::Cooling => "硬い岩盤での減速を軽減",
+            Upgrade::Cargo => "積載量 +50",
+            Upgrade::Conveyor => "貨物を自動で売却",
+        }
+    }
+
+    /// Lv0 → Lv1 のコスト。
+    pub fn base_cost(self) -> u64 {
+        match self {
+            Upgrade::Bit => 15,
+            Upgrade::Cooling => 60,
+            Upgrade::Cargo => 25,
+            Upgrade::Conveyor => 200,
+        }
+    }
+
+    /// レベルごとのコスト倍率。
+    pub fn cost_growth(self) -> f64 {
+        match self {
+            Upgrade::Bit => 1.6,
+            Upgrade::Cooling => 1.9,
+            Upgrade::Cargo => 1.5,
+            Upgrade::Conveyor => 3.0,
+        }
+    }
+}
+
+/// Deep Drill のゲーム状態。
+pub struct DrillState {
+    /// 現在の深度 (m)。
+    pub depth: f64,
+    /// 今回の周回で到達した最深 (m)。コア回収量の基準。
+    pub run_max_depth: f64,
+    /// 全周回を通じた最深記録 (m)。
+    pub best_depth: f64,
+    pub gold: u64,
+    /// 貨物室の鉱石。`Ore::index()` 順。
+    pub cargo: [u64; ORE_COUNT],
+    /// 強化レベル。`Upgrade::index()` 順。
+    pub upgrades: [u32; UPGRADE_COUNT],
+    /// 地核コア (転生通貨)。1 個につき掘削速度と売値 +10%。
+    pub cores: u64,
+    /// コア回収 (転生) 回数。
+    pub prestige_count: u32,
+    /// 今回の周回で稼いだ gold の累計。
+    pub run_gold_earned: u64,
+    /// 鉱石 1 個ぶんに満たない掘削量の端数 (m)。
+    pub dig_remainder: f64,
+    /// 自動搬出の次回売却までの tick 数。
+    pub conveyor_countdown: u32,
+    /// コア回収の確認待ち (もう一度押すと実行)。
+    pub confirm_prestige: bool,
+    /// 貨物満載で停止中かどうか (ログの連投防止)。
+    pub stalled: bool,
+    pub total_ticks: u64,
+    pub anim_frame: u32,
+    /// 売却フラッシュの残り tick 数と直近の売却額。
+    pub sell_flash: u32,
+    pub last_sale: u64,
+    pub log: Vec<String>,
+}
+
+impl DrillState {
+    pub fn new() -> Self {
+        Self {
+            depth: 0.0,
+            run_max_depth: 0.0,
+            best_depth: 0.0,
+            gold: 0,
+            cargo: [0; ORE_COUNT],
+            upgrades: [0; UPGRADE_COUNT],
+            cores: 0,
+            prestige_count: 0,
+            run_gold_earned: 0,
+            dig_remainder: 0.0,
+            conveyor_countdown: 0,
+            confirm_prestige: false,
+            stalled: false,
+            total_ticks: 0,
+            anim_frame: 0,
+            sell_flash: 0,
+            last_sale: 0,
+            log: vec!["Deep Drill 起動。地の底を目指せ！".into()],
+        }
+    }
+
+    pub fn level(&self, u: Upgrade) -> u32 {
+        self.upgrades[u.index()]
+    }
+
+    pub fn cargo_used(&self) -> u64 {
+        self.cargo.iter().sum()
+    }
+
+    pub fn add_log(&mut self, text: &str) {
+        self.log.push(text.to_string());
+        if self.log.len() > 30 {
+            self.log.remove(0);
+        }
+    }
+}
+
+impl Default for DrillState {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn ore_layers_follow_depth() {
+        assert_eq!(Ore::at_depth(0.0), Ore::Coal);
+        assert_eq!(Ore::at_depth(49.9), Ore::Coal);
+        assert_eq!(Ore::at_depth(50.0), Ore::Copper);
+        assert_eq!(Ore::at_depth(1_200.0), Ore::Gold);
+        assert_eq!(Ore::at_depth(1e9), Ore::Mythril);
+        for w in Ore::ALL.windows(2) {
+            assert!(w[0].unlock_depth() < w[1].unlock_depth());
+            assert!(w[0].value() < w[1].value());
+        }
+    }
+
+    #[test]
+    fn indices_match_all_order() {
+        for (i, o) in Ore::ALL.iter().enumerate() {
+            assert_eq!(o.index(), i);
+        }
+        for (i, u) in Upgrade::ALL.iter().enumerate() {
+            assert_eq!(u.index(), i);
+        }
+    }
+}
@@ -1,5 +1,8 @@
 //! Semantic action IDs for Tiny Factory click targets.
 
+use super::grid::{VIEW_H, VIEW_W};
+use crate::action::{assert_disjoint, ActionRange};
+
 pub const SELECT_MINER: u16 = 1;
 pub const SELECT_SMELTER: u16 = 2;
 pub const SELECT_ASSEMBLER: u16 = 3;
@@ -8,6 +11,100 @@ pub const SELECT_FABRICATOR: u16 = 5;
 pub const SELECT_BELT: u16 = 6;
 pub const SELECT_DELETE: u16 = 7;
 pub const TOGGLE_MINER_MODE: u16 = 8;
+/// 流れ調査の対象アイテムを切り替える
+pub const CYCLE_FLOW_ITEM: u16 = 9;
+/// 出荷ダッシュボードの開閉
+pub const TOGGLE_DASHBOARD: u16 = 10;
+/// 出荷目標 (個/分) の増減
+pub const EXPORT_TARGET_UP: u16 = 11;
+pub const EXPORT_TARGET_DOWN: u16 = 12;
+/// 調査パネル (受け入れフィルタ・優先度) の開閉
+pub const TOGGLE_INSPECT: u16 = 13;
+/// 調べている機械の入力優先度を切り替える
+pub const INSPECT_PRIORITY: u16 = 14;
+/// 統計画面 (アイテム別の生産・消費グラフ) の開閉
+pub const TOGGLE_STATS: u16 = 15;
+pub const SELECT_RAIL: u16 = 16;
+pub const SELECT_STATION: u16 = 17;
+/// 調べている駅に台車を置く
+pub const PLACE_CART: u16 = 18;
+/// 調べている駅の積み降ろしを切り替える
+pub const STATION_MODE: u16 = 19;
+pub const SELECT_DRONE: u16 = 31;
+/// 調べている故障機械を修理する
+pub const REPAIR_MACHINE: u16 = 32;
+pub const SELECT_LIFT: u16 = 33;
+/// 調べているリフトの働き (上へ/下へ/受け取る) を切り替える
+pub const LIFT_MODE: u16 = 34;
+/// 上下の階へ移る
+pub const FLOOR_UP: u16 = 35;
+pub const FLOOR_DOWN: u16 = 36;
+/// いちばん上に階を建て増す
+pub const BUILD_FLOOR: u16 = 37;
+pub const SELECT_FILTER: u16 = 38;
+pub const SELECT_GREENERY: u16 = 39;
+/// 汚染マップの表示切替
+pub const TOGGLE_POLLUTION: u16 = 46;
+/// レシピ帳の開閉
+pub const TOGGLE_RECIPES: u16 = 47;
+/// レシピ帳の絞り込みを消す
+pub const CLEAR_RECIPE_FILTER: u16 = 48;
+
+/// 建設メニューの項目 (BUILD_MENU_TOOLS の index)
+pub const BUILD_MENU: ActionRange = ActionRange::new(20, 10);
+pub const BUILD_MENU_CLOSE: u16 = 30;
+
+/// モード選択画面: FactoryMode::ALL の index
+pub const MODE_PICK: ActionRange = ActionRange::new(60, 2);
+
+/// 調査パネル: 受け入れを切り替えるアイテム (ItemKind::ALL の index)
+pub const INSPECT_ITEM: ActionRange = ActionRange::new(40, 6);
+/// 調査パネル: 編集する機械の辺 (Direction::ALL の index)
+pub const INSPECT_SIDE: ActionRange = ActionRange::new(50, 4);
+
+/// Grid click: index = viewport_row * VIEW_W + viewport_col
+/// ([`crate::widgets::ClickableGrid`] が encode / decode する)
+pub const GRID_CLICK: ActionRange = ActionRange::new(100, (VIEW_W * VIEW_H) as u16);
+
+/// この画面の全 ID。重なりはコンパイル時に検査する。
+pub const ALL_ACTIONS: &[ActionRange] = &[
+    ActionRange::single(SELECT_MINER),
+    ActionRange::single(SELECT_SMELTER),
+    ActionRange::single(SELECT_ASSEMBLER),
+    ActionRange::single(SELECT_EXPORTER),
+    ActionRange::single(SELECT_FABRICATOR),
+    ActionRange::single(SELECT_BELT),
+    ActionRange::single(SELECT_DELETE),
+    ActionRange::single(TOGGLE_MINER_MODE),
+    ActionRange::single(CYCLE_FLOW_ITEM),
+    ActionRange::single(TOGGLE_DASHBOARD),
+    ActionRange::single(EXPORT_TARGET_UP),
+    ActionRange::single(EXPORT_TARGET_DOWN),
+    ActionRange::single(TOGGLE_INSPECT),
+    ActionRange::single(INSPECT_PRIORITY),
+    ActionRange::single(TOGGLE_STATS),
+    ActionRange::single(SELECT_RAIL),
+    ActionRange::single(SELECT_STATION),
+    ActionRange::single(PLACE_CART),
+    ActionRange::single(STATION_MODE),
+    ActionRange::single(SELECT_DRONE),
+    ActionRange::single(REPAIR_MACHINE),
+    ActionRange::single(SELECT_LIFT),
+    ActionRange::single(LIFT_MODE),
+    ActionRange::single(FLOOR_UP),
+    ActionRange::single(FLOOR_DOWN),
+    ActionRange::single(BUILD_FLOOR),
+    ActionRange::single(SELECT_FILTER),
+    ActionRange::single(SELECT_GREENERY),
+    ActionRange::single(TOGGLE_POLLUTION),
+    ActionRange::single(TOGGLE_RECIPES),
+    ActionRange::single(CLEAR_RECIPE_FILTER),
+    ActionRange::single(BUILD_MENU_CLOSE),
+    BUILD_MENU,
+    INSPECT_ITEM,
+    INSPECT_SIDE,
+    MODE_PICK,
+    GRID_CLICK,
+];
 
-/// Grid click: action_id = GRID_CLICK_BASE + viewport_row * VIEW_W + viewport_col
-pub const GRID_CLICK_BASE: u16 = 100;
+const _: () = assert_disjoint(ALL_ACTIONS);
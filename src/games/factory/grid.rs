@@ -17,6 +17,29 @@ pub enum Direction {
 }
 
 impl Direction {
+    /// 時計回り (上 → 右 → 下 → 左)。機械の辺ごとのフィルタの並び順。
+    pub const ALL: [Direction; 4] = [Direction::Up, Direction::Right, Direction::Down, Direction::Left];
+
+    /// [`Direction::ALL`] での位置。
+    pub fn index(&self) -> usize {
+        match self {
+            Direction::Up => 0,
+            Direction::Right => 1,
+            Direction::Down => 2,
+            Direction::Left => 3,
+        }
+    }
+
+    /// 表示名 (機械の辺として)。
+    pub fn name(&self) -> &'static str {
+        match self {
+            Direction::Up => "上",
+            Direction::Right => "右",
+            Direction::Down => "下",
+            Direction::Left => "左",
+        }
+    }
+
     /// Delta (dx, dy) for this direction.
     pub fn delta(&self) -> (i32, i32) {
         match self {
@@ -49,6 +72,51 @@ pub enum ItemKind {
 }
 
 impl ItemKind {
+    pub const ALL: [ItemKind; 6] = [
+        ItemKind::IronOre,
+        ItemKind::IronPlate,
+        ItemKind::Gear,
+        ItemKind::CopperOre,
+        ItemKind::CopperPlate,
+        ItemKind::Circuit,
+    ];
+
+    /// `ALL` 内の位置 (統計の配列の添字)。
+    pub fn index(&self) -> usize {
+        match self {
+            ItemKind::IronOre => 0,
+            ItemKind::IronPlate => 1,
+            ItemKind::Gear => 2,
+            ItemKind::CopperOre => 3,
+            ItemKind::CopperPlate => 4,
+            ItemKind::Circuit => 5,
+        }
+    }
+
+    /// 表示名。
+    pub fn name(&self) -> &'static str {
+        match self {
+            ItemKind::IronOre => "鉄鉱石",
+            ItemKind::IronPlate => "鉄板",
+            ItemKind::Gear => "歯車",
+            ItemKind::CopperOre => "銅鉱石",
+            ItemKind::CopperPlate => "銅板",
+            ItemKind::Circuit => "回路",
+        }
+    }
+
+    /// レシピ帳の絞り込みでキー入力と照らし合わせる英名 (小文字)。
+    pub fn search_name(&self) -> &'static str {
+        match self {
+            ItemKind::IronOre => "iron ore",
+            ItemKind::IronPlate => "iron plate",
+            ItemKind::Gear => "gear",
+            ItemKind::CopperOre => "copper ore",
+            ItemKind::CopperPlate => "copper plate",
+            ItemKind::Circuit => "circuit",
+        }
+    }
+
     pub fn symbol(&self) -> char {
         match self {
             ItemKind::IronOre => 'o',
@@ -90,6 +158,26 @@ pub enum MachineKind {
 }
 
 impl MachineKind {
+    /// 統計画面の並び順。
+    pub const ALL: [MachineKind; 5] = [
+        MachineKind::Miner,
+        MachineKind::Smelter,
+        MachineKind::Assembler,
+        MachineKind::Fabricator,
+        MachineKind::Exporter,
+    ];
+
+    /// `ALL` 内の位置 (統計の配列の添字)。
+    pub fn index(&self) -> usize {
+        match self {
+            MachineKind::Miner => 0,
+            MachineKind::Smelter => 1,
+            MachineKind::Assembler => 2,
+            MachineKind::Fabricator => 3,
+            MachineKind::Exporter => 4,
+        }
+    }
+
     pub fn name(&self) -> &str {
         match self {
             MachineKind::Miner => "Miner",
@@ -121,6 +209,17 @@ impl MachineKind {
         }
     }
 
+    /// 1 回仕上げるごとに区画へ出す汚染。
+    pub fn pollution(&self) -> u32 {
+        match self {
+            MachineKind::Miner => 1,
+            MachineKind::Smelter => 3,
+            MachineKind::Assembler => 2,
+            MachineKind::Exporter => 0,
+            MachineKind::Fabricator => 4,
+        }
+    }
+
     /// Output produced (None for Exporter).
     pub fn output(&self) -> Option<ItemKind> {
         match self {
@@ -145,6 +244,40 @@ impl MachineKind {
     }
 }
 
+/// 機械 1 回ぶんの加工: `inputs` を 1 個ずつ食べて `output` を 1 個出す。
+/// 実際の加工は logic の tick が行う。これはレシピ帳に出すための一覧。
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Recipe {
+    pub machine: MachineKind,
+    /// 空なら採掘 (何も食べずに湧く)。
+    pub inputs: &'static [ItemKind],
+    pub output: ItemKind,
+}
+
+/// 全レシピ (生産チェーンの上流から順)。Exporter は何も作らないので含めない。
+pub const RECIPES: [Recipe; 6] = [
+    Recipe { machine: MachineKind::Miner, inputs: &[], output: ItemKind::IronOre },
+    Recipe { machine: MachineKind::Miner, inputs: &[], output: ItemKind::CopperOre },
+    Recipe { machine: MachineKind::Smelter, inputs: &[ItemKind::IronOre], output: ItemKind::IronPlate },
+    Recipe { machine: MachineKind::Smelter, inputs: &[ItemKind::CopperOre], output: ItemKind::CopperPlate },
+    Recipe { machine: MachineKind::Assembler, inputs: &[ItemKind::IronPlate], output: ItemKind::Gear },
+    Recipe {
+        machine: MachineKind::Fabricator,
+        inputs: &[ItemKind::IronPlate, ItemKind::CopperPlate],
+        output: ItemKind::Circuit,
+    },
+];
+
+/// `item` を作るレシピ。
+pub fn recipes_making(item: ItemKind) -> impl Iterator<Item = &'static Recipe> {
+    RECIPES.iter().filter(move |r| r.output == item)
+}
+
+/// `item` を材料にするレシピ。
+pub fn recipes_using(item: ItemKind) -> impl Iterator<Item = &'static Recipe> {
+    RECIPES.iter().filter(move |r| r.inputs.contains(&item))
+}
+
 /// Miner production mode.
 #[derive(Clone, Copy, Debug, PartialEq)]
 pub enum MinerMode {
@@ -152,6 +285,39 @@ pub enum MinerMode {
     Copper,
 }
 
+/// 入力の優先度。1 つのベルトの隣に受け取れる機械が複数あるとき、
+/// 高いほうに先に渡す (同じならベルトの進行方向の順)。
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub enum InputPriority {
+    Low,
+    Normal,
+    High,
+}
+
+impl InputPriority {
+    pub fn name(&self) -> &'static str {
+        match self {
+            InputPriority::Low => "低",
+            InputPriority::Normal => "通常",
+            InputPriority::High => "高",
+        }
+    }
+
+    /// 通常 → 高 → 低 → 通常 の順に切り替える。
+    pub fn next(self) -> InputPriority {
+        match self {
+            InputPriority::Normal => InputPriority::High,
+            InputPriority::High => InputPriority::Low,
+            InputPriority::Low => InputPriority::Normal,
+        }
+    }
+}
+
+/// 受け入れリスト。空なら何でも通す。
+pub fn filter_allows(filter: &[ItemKind], item: &ItemKind) -> bool {
+    filter.is_empty() || filter.contains(item)
+}
+
 /// A machine placed on the grid.
 #[derive(Clone, Debug)]
 pub struct Machine {
@@ -166,6 +332,13 @@ pub struct Machine {
     pub max_buffer: usize,
     /// Miner mode (only relevant for Miner kind).
     pub mode: MinerMode,
+    /// 辺ごとの受け入れリスト ([`Direction::ALL`] の順)。
+    /// その辺に接したベルトからは、リストにあるアイテムだけを受け取る。
+    pub side_filters: [Vec<ItemKind>; 4],
+    /// 入力の優先度。
+    pub priority: InputPriority,
+    /// 故障中。直すまで加工も採掘も出荷もしない (入出力のバッファはそのまま)。
+    pub broken: bool,
     // ── Statistics ──
     /// Total items produced by this machine.
     pub stat_produced: u64,
@@ -186,6 +359,9 @@ impl Machine {
             progress: 0,
             max_buffer: 5,
             mode: MinerMode::Iron,
+            side_filters: Default::default(),
+            priority: InputPriority::Normal,
+            broken: false,
             stat_produced: 0,
             stat_revenue: 0,
             stat_active_ticks: 0,
@@ -214,6 +390,9 @@ pub struct Belt {
     pub trail_item: Option<ItemKind>,
     /// 残像の残り表示 tick 数（0 で非表示）。
     pub trail_ticks: u8,
+    /// 受け入れリスト。空でなければ、リストにあるアイテムしか
+    /// このベルトに乗らない (隣のベルトや機械の出口から)。
+    pub filter: Vec<ItemKind>,
 }
 
 impl Belt {
@@ -223,10 +402,198 @@ impl Belt {
             item_from: None,
             trail_item: None,
             trail_ticks: 0,
+            filter: Vec::new(),
+        }
+    }
+}
+
+/// 駅のバッファに貯められる数。
+pub const STATION_CAPACITY: usize = 20;
+
+/// 台車 1 台に積める数。
+pub const CART_CAPACITY: usize = 20;
+
+/// 駅での台車への積み降ろし。
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum StationMode {
+    /// 隣のベルトから受け取って貯め、台車に積む。
+    Load,
+    /// 台車から降ろして貯め、隣のベルトへ流す。
+    Unload,
+    /// 台車は停まらずに通過する。
+    Pass,
+}
+
+impl StationMode {
+    pub fn name(&self) -> &'static str {
+        match self {
+            StationMode::Load => "積込",
+            StationMode::Unload => "荷降ろし",
+            StationMode::Pass => "通過",
+        }
+    }
+
+    /// 積込 → 荷降ろし → 通過 → 積込 の順に切り替える。
+    pub fn next(self) -> StationMode {
+        match self {
+            StationMode::Load => StationMode::Unload,
+            StationMode::Unload => StationMode::Pass,
+            StationMode::Pass => StationMode::Load,
+        }
+    }
+}
+
+/// 線路上の駅。台車はつながった駅を番号順に巡回する。
+#[derive(Clone, Debug)]
+pub struct Station {
+    /// 設置順の番号 (1 始まり)。台車の巡回順になる。
+    pub id: u32,
+    pub mode: StationMode,
+    /// 積み降ろし待ちのアイテム (最大 [`STATION_CAPACITY`])。
+    pub buffer: Vec<ItemKind>,
+}
+
+impl Station {
+    pub fn new(id: u32) -> Self {
+        Self { id, mode: StationMode::Load, buffer: Vec::new() }
+    }
+}
+
+/// 線路を走る台車。セルではなく `FactoryState::carts` に持つ。
+#[derive(Clone, Debug, PartialEq)]
+pub struct Cart {
+    pub x: usize,
+    pub y: usize,
+    /// 積荷 (最大 [`CART_CAPACITY`])。
+    pub cargo: Vec<ItemKind>,
+    /// 向かっている駅。着いたらそこで積み降ろしする。
+    pub target: Option<(usize, usize)>,
+    /// 目的の駅に着いてからの tick 数。走行中は 0。
+    pub dwell: u32,
+}
+
+impl Cart {
+    pub fn new(x: usize, y: usize) -> Self {
+        Self { x, y, cargo: Vec::new(), target: Some((x, y)), dwell: 0 }
+    }
+}
+
+/// 汚染を数える区画の一辺 (マス)。
+pub const REGION_SIZE: usize = 8;
+pub const REGIONS_W: usize = GRID_W.div_ceil(REGION_SIZE);
+pub const REGIONS_H: usize = GRID_H.div_ceil(REGION_SIZE);
+
+/// 区画の汚染の段階。汚れているほど機械が止まる tick が増える。
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum PollutionLevel {
+    Clean,
+    Hazy,
+    Smog,
+}
+
+impl PollutionLevel {
+    /// かすみ・スモッグになる汚染量。
+    pub const HAZY_AT: u32 = 200;
+    pub const SMOG_AT: u32 = 400;
+
+    pub fn of(pollution: u32) -> PollutionLevel {
+        if pollution >= Self::SMOG_AT {
+            PollutionLevel::Smog
+        } else if pollution >= Self::HAZY_AT {
+            PollutionLevel::Hazy
+        } else {
+            PollutionLevel::Clean
+        }
+    }
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            PollutionLevel::Clean => "きれい",
+            PollutionLevel::Hazy => "かすみ",
+            PollutionLevel::Smog => "スモッグ",
+        }
+    }
+
+    /// この段階で機械が休む間隔 (N tick に 1 回止まる)。`None` は止まらない。
+    pub fn pause_every(&self) -> Option<u64> {
+        match self {
+            PollutionLevel::Clean => None,
+            PollutionLevel::Hazy => Some(4),
+            PollutionLevel::Smog => Some(2),
+        }
+    }
+
+    /// 機械の速さ (%)。
+    pub fn speed_pct(&self) -> u64 {
+        match self.pause_every() {
+            Some(n) => 100 - 100 / n,
+            None => 100,
+        }
+    }
+}
+
+/// リフトのかごに貯められる数。
+pub const LIFT_CAPACITY: usize = 10;
+
+/// リフトの働き。リフトは別の階の同じ位置にあるリフトと縦につながる。
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum LiftMode {
+    /// 隣のベルトから受け取り、上の階のリフトへ送る。
+    Up,
+    /// 隣のベルトから受け取り、下の階のリフトへ送る。
+    Down,
+    /// 別の階から届いた品を隣のベルトへ流す。
+    Out,
+}
+
+impl LiftMode {
+    pub fn name(&self) -> &'static str {
+        match self {
+            LiftMode::Up => "上へ送る",
+            LiftMode::Down => "下へ送る",
+            LiftMode::Out => "受け取る",
+        }
+    }
+
+    /// 上へ → 下へ → 受け取る → 上へ の順に切り替える。
+    pub fn next(self) -> LiftMode {
+        match self {
+            LiftMode::Up => LiftMode::Down,
+            LiftMode::Down => LiftMode::Out,
+            LiftMode::Out => LiftMode::Up,
         }
     }
 }
 
+/// 階をまたいで品を運ぶリフト (1×1)。
+#[derive(Clone, Debug, PartialEq)]
+pub struct Lift {
+    pub mode: LiftMode,
+    /// 運ぶ前か、流す前の品 (最大 [`LIFT_CAPACITY`])。
+    pub buffer: Vec<ItemKind>,
+}
+
+impl Lift {
+    pub fn new() -> Self {
+        Self { mode: LiftMode::Up, buffer: Vec::new() }
+    }
+}
+
+/// 整備ドローンの基地。近くの故障した機械へ飛んでいき、時間をかけて直す。
+#[derive(Clone, Debug, PartialEq)]
+pub struct Drone {
+    /// 修理中の機械のアンカー。
+    pub target: Option<(usize, usize)>,
+    /// 修理の進み具合 (tick)。
+    pub progress: u32,
+}
+
+impl Drone {
+    pub fn new() -> Self {
+        Self { target: None, progress: 0 }
+    }
+}
+
 /// What's in a grid cell.
 #[derive(Clone, Debug)]
 pub enum Cell {
@@ -235,6 +602,24 @@ pub enum Cell {
     /// Part of a 2×2 machine; the actual Machine data lives at the anchor cell.
     MachinePart { anchor_x: usize, anchor_y: usize },
     Belt(Belt),
+    /// 台車の線路 (向きなし。つながったマスへ走る)。
+    Rail,
+    Station(Station),
+    /// 整備ドローンの基地 (1×1)。
+    Drone(Drone),
+    /// 階をまたぐリフト (1×1)。
+    Lift(Lift),
+    /// 区画の汚染を吸う浄化フィルタ (1×1)。
+    Filter,
+    /// 区画の汚染を少しずつ吸う緑地 (1×1)。
+    Greenery,
+}
+
+impl Cell {
+    /// 台車が走れるマス (線路か駅)。
+    pub fn is_track(&self) -> bool {
+        matches!(self, Cell::Rail | Cell::Station(_))
+    }
 }
 
 
@@ -283,6 +668,19 @@ mod tests {
         assert_eq!(MachineKind::Smelter.output(), Some(ItemKind::IronPlate));
     }
 
+    #[test]
+    fn every_item_has_a_recipe_and_chains_back_to_a_miner() {
+        for item in ItemKind::ALL {
+            let recipe = recipes_making(item).next().expect("作り方が無いアイテム");
+            for &input in recipe.inputs {
+                assert!(recipes_making(input).next().is_some(), "{input:?}");
+            }
+        }
+        let users: Vec<_> = recipes_using(ItemKind::IronPlate).map(|r| r.output).collect();
+        assert_eq!(users, vec![ItemKind::Gear, ItemKind::Circuit]);
+        assert_eq!(recipes_using(ItemKind::Circuit).count(), 0);
+    }
+
     #[test]
     fn export_values() {
         assert_eq!(MachineKind::export_value(&ItemKind::IronOre), 1);
@@ -12,11 +12,13 @@ use std::rc::Rc;
 use ratzilla::ratatui::layout::Rect;
 use ratzilla::ratatui::Frame;
 
+use crate::arcade::{self, Reward};
 use crate::games::{Game, GameChoice};
-use crate::input::{ClickState, InputEvent};
+use crate::input::{ClickState, DragMotion, InputEvent, KeyRepeat};
+use crate::scores::Score;
 
 use actions::*;
-use state::{FactoryState, PlacementTool};
+use state::{FactoryMode, FactoryState, PlacementTool};
 
 use crate::widgets::ClickableGrid;
 
@@ -31,47 +33,127 @@ impl FactoryGame {
         }
     }
 
+    /// モード選択画面で `FactoryMode::ALL[index]` を選んで始める。
+    fn start_mode(&mut self, index: usize) -> bool {
+        let Some(mode) = FactoryMode::ALL.get(index) else {
+            return false;
+        };
+        logic::start_mode(&mut self.state, *mode);
+        if arcade::owns(Reward::FactorySeedMoney) {
+            self.state.money += arcade::FACTORY_SEED_MONEY;
+            self.state.add_log(&format!("{}: +${}", Reward::FactorySeedMoney.name(), arcade::FACTORY_SEED_MONEY));
+        }
+        true
+    }
+
+    /// ツールを選ぶ。キャンペーンで未解放の機械は選べない。
+    fn select_tool(&mut self, tool: PlacementTool) -> bool {
+        if logic::tool_unlocked(&self.state, &tool) {
+            self.state.tool = tool;
+        } else {
+            self.state.add_log("まだ使えません (目標を達成すると解放)");
+        }
+        true
+    }
+
     fn handle_click(&mut self, action_id: u16) -> bool {
+        if self.state.mode.is_none() {
+            return MODE_PICK.index(action_id).is_some_and(|i| self.start_mode(i));
+        }
         match action_id {
-            SELECT_MINER => {
-                self.state.tool = PlacementTool::Miner;
+            SELECT_MINER => self.select_tool(PlacementTool::Miner),
+            SELECT_SMELTER => self.select_tool(PlacementTool::Smelter),
+            SELECT_ASSEMBLER => self.select_tool(PlacementTool::Assembler),
+            SELECT_EXPORTER => self.select_tool(PlacementTool::Exporter),
+            SELECT_FABRICATOR => self.select_tool(PlacementTool::Fabricator),
+            SELECT_BELT => self.select_tool(PlacementTool::Belt),
+            SELECT_RAIL => self.select_tool(PlacementTool::Rail),
+            SELECT_STATION => self.select_tool(PlacementTool::Station),
+            SELECT_DRONE => self.select_tool(PlacementTool::Drone),
+            SELECT_LIFT => self.select_tool(PlacementTool::Lift),
+            SELECT_FILTER => self.select_tool(PlacementTool::Filter),
+            SELECT_GREENERY => self.select_tool(PlacementTool::Greenery),
+            TOGGLE_POLLUTION => {
+                logic::toggle_pollution_overlay(&mut self.state);
+                true
+            }
+            LIFT_MODE => logic::cycle_lift_mode(&mut self.state),
+            FLOOR_UP => logic::step_floor(&mut self.state, 1),
+            FLOOR_DOWN => logic::step_floor(&mut self.state, -1),
+            BUILD_FLOOR => logic::build_floor(&mut self.state),
+            REPAIR_MACHINE => logic::repair_inspected(&mut self.state),
+            PLACE_CART => logic::place_cart(&mut self.state),
+            STATION_MODE => logic::cycle_station_mode(&mut self.state),
+            SELECT_DELETE => self.select_tool(PlacementTool::Delete),
+            TOGGLE_MINER_MODE => {
+                logic::toggle_miner_mode(&mut self.state);
                 true
             }
-            SELECT_SMELTER => {
-                self.state.tool = PlacementTool::Smelter;
+            CYCLE_FLOW_ITEM => {
+                logic::cycle_flow_item(&mut self.state);
                 true
             }
-            SELECT_ASSEMBLER => {
-                self.state.tool = PlacementTool::Assembler;
+            TOGGLE_DASHBOARD => {
+                self.toggle_dashboard();
                 true
             }
-            SELECT_EXPORTER => {
-                self.state.tool = PlacementTool::Exporter;
+            TOGGLE_STATS => {
+                self.toggle_stats();
                 true
             }
-            SELECT_FABRICATOR => {
-                self.state.tool = PlacementTool::Fabricator;
+            TOGGLE_RECIPES => {
+                self.toggle_recipes();
                 true
             }
-            SELECT_BELT => {
-                self.state.tool = PlacementTool::Belt;
+            CLEAR_RECIPE_FILTER => {
+                self.state.recipe_filter.clear();
                 true
             }
-            SELECT_DELETE => {
-                self.state.tool = PlacementTool::Delete;
+            EXPORT_TARGET_UP => {
+                logic::adjust_export_target(&mut self.state, 1);
                 true
             }
-            TOGGLE_MINER_MODE => {
-                logic::toggle_miner_mode(&mut self.state);
+            EXPORT_TARGET_DOWN => {
+                logic::adjust_export_target(&mut self.state, -1);
                 true
             }
-            id if id >= GRID_CLICK_BASE => {
+            BUILD_MENU_CLOSE => {
+                self.state.build_menu = None;
+                true
+            }
+            TOGGLE_INSPECT => {
+                self.toggle_inspect();
+                true
+            }
+            INSPECT_PRIORITY => logic::cycle_inspect_priority(&mut self.state),
+            id if INSPECT_ITEM.contains(id) => {
+                let item = grid::ItemKind::ALL[INSPECT_ITEM.index(id).unwrap_or(0)];
+                logic::toggle_inspect_filter(&mut self.state, item)
+            }
+            id if INSPECT_SIDE.contains(id) => {
+                self.state.inspect_side = grid::Direction::ALL[INSPECT_SIDE.index(id).unwrap_or(0)];
+                true
+            }
+            id if BUILD_MENU.contains(id) => {
+                logic::build_from_menu(&mut self.state, BUILD_MENU.index(id).unwrap_or(0));
+                true
+            }
+            id if GRID_CLICK.contains(id) => {
+                // メニューの外をタップしたら閉じるだけ (誤設置を防ぐ)
+                if self.state.build_menu.take().is_some() {
+                    return true;
+                }
                 if let Some((vx_offset, vy_offset)) =
-                    ClickableGrid::decode(GRID_CLICK_BASE, grid::VIEW_W, id)
+                    ClickableGrid::decode(GRID_CLICK.base(), grid::VIEW_W, id)
                 {
                     self.state.cursor_x = self.state.viewport_x + vx_offset;
                     self.state.cursor_y = self.state.viewport_y + vy_offset;
-                    logic::place(&mut self.state);
+                    // ツール未選択なら、空きマスのタップでその場の建設メニューを開く
+                    if self.state.tool == PlacementTool::None {
+                        logic::open_build_menu(&mut self.state);
+                    } else {
+                        logic::place(&mut self.state);
+                    }
                 }
                 true
             }
@@ -79,39 +161,202 @@ impl FactoryGame {
         }
     }
 
+    /// グリッドの長押しは、ツールを選んだままでもそのマスの建設メニューを開く
+    /// (ツールバーへ持ち替えに行かずに済む)。
+    fn handle_long_press(&mut self, action_id: u16) -> bool {
+        if self.state.mode.is_none() || !GRID_CLICK.contains(action_id) {
+            return false;
+        }
+        let Some((vx, vy)) = ClickableGrid::decode(GRID_CLICK.base(), grid::VIEW_W, action_id)
+        else {
+            return false;
+        };
+        self.state.cursor_x = self.state.viewport_x + vx;
+        self.state.cursor_y = self.state.viewport_y + vy;
+        self.state.build_menu = None;
+        logic::open_build_menu(&mut self.state);
+        true
+    }
+
+    /// 出荷ダッシュボードを開閉する。統計画面・レシピ帳とは同じ場所を使うので排他。
+    fn toggle_dashboard(&mut self) {
+        self.state.show_dashboard = !self.state.show_dashboard;
+        self.state.show_stats = false;
+        self.state.show_recipes = false;
+    }
+
+    /// 統計画面を開閉する。
+    fn toggle_stats(&mut self) {
+        self.state.show_stats = !self.state.show_stats;
+        self.state.show_dashboard = false;
+        self.state.show_recipes = false;
+    }
+
+    /// レシピ帳を開閉する。閉じたら絞り込みは忘れる。
+    fn toggle_recipes(&mut self) {
+        self.state.show_recipes = !self.state.show_recipes;
+        self.state.show_dashboard = false;
+        self.state.show_stats = false;
+        self.state.recipe_filter.clear();
+    }
+
+    /// 調査パネルを開閉する。開くときはカーソル位置の機械かベルトが対象。
+    fn toggle_inspect(&mut self) {
+        if self.state.inspect.take().is_none() && !logic::open_inspect(&mut self.state) {
+            self.state.add_log("調べる機械かベルトにカーソルを合わせてください");
+        }
+    }
+
+    /// Belt ツール選択中のドラッグで、なぞったマスにベルトを一筆で敷く。
+    fn handle_drag(&mut self, m: &DragMotion) -> bool {
+        if self.state.tool != PlacementTool::Belt || self.state.build_menu.is_some() {
+            return false;
+        }
+        let to_cell = |id: u16| -> Option<(usize, usize)> {
+            if !GRID_CLICK.contains(id) {
+                return None;
+            }
+            ClickableGrid::decode(GRID_CLICK.base(), grid::VIEW_W, id)
+                .map(|(vx, vy)| (self.state.viewport_x + vx, self.state.viewport_y + vy))
+        };
+        if m.first {
+            self.state.belt_stroke = to_cell(m.origin);
+        }
+        let (Some(from), Some(to)) = (self.state.belt_stroke, m.target.and_then(to_cell)) else {
+            // グリッド外へはみ出した step は捨てる (ストロークは継続)
+            return true;
+        };
+        logic::lay_belt_path(&mut self.state, from, to);
+        self.state.belt_stroke = Some(to);
+        self.state.cursor_x = to.0;
+        self.state.cursor_y = to.1;
+        true
+    }
+
     fn handle_key(&mut self, key: char) -> bool {
+        if self.state.mode.is_none() {
+            return match key {
+                '1' | '2' => self.start_mode(key as usize - '1' as usize),
+                _ => false,
+            };
+        }
+        // レシピ帳表示中は英字と空白が絞り込みの入力、'-' で 1 字消す、
+        // '?' か Esc で閉じる。工場の操作キーと被るので他のキーもここで止める
+        if self.state.show_recipes {
+            match key {
+                '?' | 'q' => self.toggle_recipes(),
+                '-' => {
+                    self.state.recipe_filter.pop();
+                }
+                c => {
+                    logic::push_recipe_filter(&mut self.state, c);
+                }
+            }
+            return true;
+        }
+        // 調査パネル表示中は 1-6 で受け入れ切替、n で辺、p で優先度、
+        // x で故障の修理、駅なら m で積み降ろし、c で台車、リフトなら m で働き
+        if self.state.inspect.is_some() {
+            match key {
+                '1'..='6' => {
+                    let item = grid::ItemKind::ALL[key as usize - '1' as usize];
+                    logic::toggle_inspect_filter(&mut self.state, item);
+                    return true;
+                }
+                'n' => {
+                    logic::cycle_inspect_side(&mut self.state);
+                    return true;
+                }
+                'p' => {
+                    logic::cycle_inspect_priority(&mut self.state);
+                    return true;
+                }
+                'm' => {
+                    if !logic::cycle_station_mode(&mut self.state) {
+                        logic::cycle_lift_mode(&mut self.state);
+                    }
+                    return true;
+                }
+                'c' => {
+                    logic::place_cart(&mut self.state);
+                    return true;
+                }
+                'x' => {
+                    logic::repair_inspected(&mut self.state);
+                    return true;
+                }
+                _ => {}
+            }
+        }
+        // 建設メニュー表示中は 1-5 / b でメニューから設置、e で閉じる
+        if self.state.build_menu.is_some() {
+            let index = match key {
+                '1'..='5' => Some(key as usize - '1' as usize),
+                'b' => Some(5),
+                _ => None,
+            };
+            if let Some(index) = index {
+                logic::build_from_menu(&mut self.state, index);
+                return true;
+            }
+            if key == 'e' {
+                self.state.build_menu = None;
+                return true;
+            }
+        }
         match key {
             // Tool selection
-            '1' => {
-                self.state.tool = PlacementTool::Miner;
+            '1' => self.select_tool(PlacementTool::Miner),
+            '2' => self.select_tool(PlacementTool::Smelter),
+            '3' => self.select_tool(PlacementTool::Assembler),
+            '4' => self.select_tool(PlacementTool::Exporter),
+            '5' => self.select_tool(PlacementTool::Fabricator),
+            'b' => self.select_tool(PlacementTool::Belt),
+            'r' => self.select_tool(PlacementTool::Rail),
+            'o' => self.select_tool(PlacementTool::Station),
+            'u' => self.select_tool(PlacementTool::Drone),
+            'v' => self.select_tool(PlacementTool::Lift),
+            'w' => self.select_tool(PlacementTool::Filter),
+            'a' => self.select_tool(PlacementTool::Greenery),
+            'z' => {
+                logic::toggle_pollution_overlay(&mut self.state);
                 true
             }
-            '2' => {
-                self.state.tool = PlacementTool::Smelter;
+            'd' => self.select_tool(PlacementTool::Delete),
+            't' => {
+                logic::toggle_miner_mode(&mut self.state);
                 true
             }
-            '3' => {
-                self.state.tool = PlacementTool::Assembler;
+            'f' => {
+                logic::cycle_flow_item(&mut self.state);
                 true
             }
-            '4' => {
-                self.state.tool = PlacementTool::Exporter;
+            'g' => {
+                self.toggle_dashboard();
                 true
             }
-            '5' => {
-                self.state.tool = PlacementTool::Fabricator;
+            's' => {
+                self.toggle_stats();
                 true
             }
-            'b' => {
-                self.state.tool = PlacementTool::Belt;
+            '+' if self.state.show_dashboard => {
+                logic::adjust_export_target(&mut self.state, 1);
                 true
             }
-            'd' => {
-                self.state.tool = PlacementTool::Delete;
+            '-' if self.state.show_dashboard => {
+                logic::adjust_export_target(&mut self.state, -1);
                 true
             }
-            't' => {
-                logic::toggle_miner_mode(&mut self.state);
+            '?' => {
+                self.toggle_recipes();
+                true
+            }
+            'e' => logic::open_build_menu(&mut self.state),
+            ']' => logic::step_floor(&mut self.state, 1),
+            '[' => logic::step_floor(&mut self.state, -1),
+            '^' => logic::build_floor(&mut self.state),
+            'i' => {
+                self.toggle_inspect();
                 true
             }
             // Cursor movement (WASD-style + arrow-like)
@@ -132,6 +377,9 @@ impl FactoryGame {
                 true
             }
             // Place
+            ' ' if self.state.tool == PlacementTool::None => {
+                logic::open_build_menu(&mut self.state)
+            }
             ' ' => {
                 logic::place(&mut self.state);
                 true
@@ -150,16 +398,51 @@ impl Game for FactoryGame {
         match event {
             InputEvent::Key(c) => self.handle_key(*c),
             InputEvent::Click(_, id) => self.handle_click(*id),
+            InputEvent::Drag(_, m) => self.handle_drag(m),
+            InputEvent::LongPress(_, id) => self.handle_long_press(*id),
+            InputEvent::DoubleTap(..) => false,
         }
     }
 
     fn tick(&mut self, delta_ticks: u32) {
+        // モードを選ぶまでは工場を動かさない
+        if self.state.mode.is_none() {
+            return;
+        }
+        let milestones_before = self.state.milestones_done;
         logic::tick_n(&mut self.state, delta_ticks);
+        let reached = (self.state.milestones_done - milestones_before) as u64;
+        arcade::earn("目標達成", reached * arcade::MILESTONE_TOKENS);
     }
 
     fn render(&self, f: &mut Frame, area: Rect, click_state: &Rc<RefCell<ClickState>>) {
         render::render(&self.state, f, area, click_state);
     }
+
+    fn key_repeat(&self) -> Option<KeyRepeat> {
+        // 広いグリッドを端まで動かせるよう、カーソルは速めに流す
+        Some(KeyRepeat::movement(250.0, 60.0))
+    }
+
+    fn score(&self) -> Option<Score> {
+        let per_min =
+            logic::throughput_per_sec(&self.state.recent_export_ticks, self.state.total_ticks) * 60.0;
+        Some(Score::new(per_min.round() as u64, "出荷ペース", "個/分"))
+    }
+
+    #[cfg(any(feature = "debug-console", debug_assertions, test))]
+    fn debug_command(&mut self, args: &[&str]) -> Result<String, String> {
+        use crate::debug_console::{parse_count, unknown};
+        match args {
+            ["help"] => Ok("give money <n>".into()),
+            ["give", "money", n] => {
+                let n = parse_count(n)?;
+                self.state.money = self.state.money.saturating_add(n);
+                Ok(format!("資金 +{}", n))
+            }
+            _ => Err(unknown(args)),
+        }
+    }
 }
 
 #[cfg(test)]
@@ -172,18 +455,150 @@ mod tests {
         InputEvent::Click(ClickScope::Game(GameChoice::Factory), id)
     }
 
+    /// モード選択を済ませたゲーム。全機械が使え、資金は初期値のまま。
+    fn started_game() -> FactoryGame {
+        let mut game = FactoryGame::new();
+        game.state.mode = Some(FactoryMode::Sandbox);
+        game
+    }
+
     #[test]
-    fn factory_game_select_tool() {
+    fn mode_select_gates_input_and_ticks_until_picked() {
+        let mut game = FactoryGame::new();
+        assert!(game.state.mode.is_none());
+        // 選ぶまではツールも時間も動かない
+        assert!(!game.handle_input(&InputEvent::Key('b')));
+        game.tick(10);
+        assert_eq!(game.state.total_ticks, 0);
+
+        assert!(game.handle_input(&click(MODE_PICK.id(1))));
+        assert_eq!(game.state.mode, Some(FactoryMode::Sandbox));
+        assert_eq!(game.state.money, logic::SANDBOX_MONEY);
+        game.handle_input(&InputEvent::Key('5'));
+        assert_eq!(game.state.tool, PlacementTool::Fabricator);
+    }
+
+    #[test]
+    fn long_press_opens_the_build_menu_even_with_a_tool_selected() {
+        let mut game = started_game();
+        game.handle_input(&InputEvent::Key('5'));
+        assert_eq!(game.state.tool, PlacementTool::Fabricator);
+
+        let cell = GRID_CLICK.id(grid::VIEW_W + 2);
+        assert!(game.handle_input(&InputEvent::LongPress(ClickScope::Game(GameChoice::Factory), cell)));
+        assert_eq!(game.state.build_menu, Some((2, 1)));
+        assert!(matches!(game.state.grid[1][2], grid::Cell::Empty), "long-press never places");
+        assert_eq!(game.state.tool, PlacementTool::Fabricator);
+
+        // 埋まっているマスでは開かない (カーソルだけ移る)
+        game.handle_input(&click(BUILD_MENU.id(1))); // Smelter
+        assert!(!matches!(game.state.grid[1][2], grid::Cell::Empty));
+        game.handle_input(&InputEvent::LongPress(ClickScope::Game(GameChoice::Factory), cell));
+        assert_eq!(game.state.build_menu, None);
+        assert_eq!((game.state.cursor_x, game.state.cursor_y), (2, 1));
+    }
+
+    #[test]
+    fn campaign_locks_machines_until_milestones() {
         let mut game = FactoryGame::new();
         game.handle_input(&InputEvent::Key('1'));
+        assert_eq!(game.state.mode, Some(FactoryMode::Campaign));
+        game.handle_input(&InputEvent::Key('3')); // Assembler はまだ
+        assert_eq!(game.state.tool, PlacementTool::None);
+        game.handle_input(&click(SELECT_FABRICATOR));
+        assert_eq!(game.state.tool, PlacementTool::None);
+        // 建設メニューからも置けない
+        game.handle_input(&click(GRID_CLICK.id(0)));
+        game.handle_input(&click(BUILD_MENU.id(2)));
+        assert!(matches!(game.state.grid[0][0], grid::Cell::Empty));
+
+        game.state.milestones_done = 1;
+        game.handle_input(&InputEvent::Key('3'));
+        assert_eq!(game.state.tool, PlacementTool::Assembler);
+    }
+
+    #[test]
+    fn factory_game_select_tool() {
+        let mut game = started_game();
+        game.handle_input(&InputEvent::Key('1'));
         assert_eq!(game.state.tool, PlacementTool::Miner);
         game.handle_input(&InputEvent::Key('b'));
         assert_eq!(game.state.tool, PlacementTool::Belt);
     }
 
+    #[test]
+    fn factory_game_f_key_cycles_flow_item() {
+        let mut game = started_game();
+        assert!(game.handle_input(&InputEvent::Key('f')));
+        assert_eq!(game.state.flow_item, Some(grid::ItemKind::IronOre));
+        assert!(game.handle_input(&click(CYCLE_FLOW_ITEM)));
+        assert_eq!(game.state.flow_item, Some(grid::ItemKind::IronPlate));
+    }
+
+    #[test]
+    fn factory_game_dashboard_toggle_and_target_keys() {
+        let mut game = started_game();
+        // ダッシュボードが閉じている間は +/- を消費しない
+        assert!(!game.handle_input(&InputEvent::Key('+')));
+        assert!(game.handle_input(&InputEvent::Key('g')));
+        assert!(game.state.show_dashboard);
+        game.handle_input(&InputEvent::Key('+'));
+        assert_eq!(game.state.export_target_per_min, 70);
+        game.handle_input(&click(EXPORT_TARGET_DOWN));
+        game.handle_input(&click(EXPORT_TARGET_DOWN));
+        assert_eq!(game.state.export_target_per_min, 50);
+        game.handle_input(&click(TOGGLE_DASHBOARD));
+        assert!(!game.state.show_dashboard);
+    }
+
+    #[test]
+    fn factory_game_stats_screen_replaces_dashboard() {
+        let mut game = started_game();
+        game.handle_input(&InputEvent::Key('g'));
+        assert!(game.handle_input(&InputEvent::Key('s')));
+        assert!(game.state.show_stats);
+        assert!(!game.state.show_dashboard);
+        game.handle_input(&click(TOGGLE_DASHBOARD));
+        assert!(game.state.show_dashboard);
+        assert!(!game.state.show_stats);
+        game.handle_input(&click(TOGGLE_STATS));
+        assert!(game.state.show_stats);
+        game.handle_input(&click(TOGGLE_STATS));
+        assert!(!game.state.show_stats);
+    }
+
+    #[test]
+    fn factory_game_recipe_book_captures_letters_as_filter() {
+        let mut game = started_game();
+        game.handle_input(&InputEvent::Key('s'));
+        assert!(game.handle_input(&InputEvent::Key('?')));
+        assert!(game.state.show_recipes);
+        assert!(!game.state.show_stats);
+
+        // 開いている間は 'b' や 'l' もツール選択・カーソル移動にならない
+        for c in "bell".chars() {
+            game.handle_input(&InputEvent::Key(c));
+        }
+        assert_eq!(game.state.recipe_filter, "bell");
+        assert_eq!(game.state.tool, PlacementTool::None);
+        assert_eq!(game.state.cursor_x, 0);
+        game.handle_input(&InputEvent::Key('-'));
+        assert_eq!(game.state.recipe_filter, "bel");
+        game.handle_input(&click(CLEAR_RECIPE_FILTER));
+        assert!(game.state.recipe_filter.is_empty());
+
+        // Esc (= 'q') で閉じる
+        game.handle_input(&InputEvent::Key('g'));
+        assert!(game.handle_input(&InputEvent::Key('q')));
+        assert!(!game.state.show_recipes);
+        assert!(game.state.recipe_filter.is_empty());
+        game.handle_input(&click(TOGGLE_RECIPES));
+        assert!(game.state.show_recipes);
+    }
+
     #[test]
     fn factory_game_move_cursor() {
-        let mut game = FactoryGame::new();
+        let mut game = started_game();
         game.handle_input(&InputEvent::Key('l'));
         assert_eq!(game.state.cursor_x, 1);
         game.handle_input(&InputEvent::Key('j'));
@@ -192,7 +607,7 @@ mod tests {
 
     #[test]
     fn factory_game_place_and_tick() {
-        let mut game = FactoryGame::new();
+        let mut game = started_game();
         game.handle_input(&InputEvent::Key('1')); // select miner
         game.handle_input(&InputEvent::Key(' ')); // place
 
@@ -209,17 +624,105 @@ mod tests {
 
     #[test]
     fn factory_game_belt_direction_follows_cursor() {
-        let mut game = FactoryGame::new();
+        let mut game = started_game();
         assert_eq!(game.state.belt_direction, grid::Direction::Right);
         game.handle_input(&InputEvent::Key('j')); // move down
         assert_eq!(game.state.belt_direction, grid::Direction::Down);
     }
 
+    #[test]
+    fn factory_game_inspect_panel_edits_side_filter_and_priority() {
+        let mut game = started_game();
+        // 空きマスでは開かない
+        game.handle_input(&InputEvent::Key('i'));
+        assert!(game.state.inspect.is_none());
+
+        game.handle_input(&InputEvent::Key('2')); // smelter
+        game.handle_input(&InputEvent::Key(' '));
+        game.handle_input(&InputEvent::Key('l')); // 機械の右上のマスでもアンカーを調べる
+        game.handle_input(&InputEvent::Key('i'));
+        assert_eq!(game.state.inspect, Some((0, 0)));
+
+        // 右辺で鉄鉱石のみ受け入れ、'1' はツール選択にならない
+        game.handle_input(&InputEvent::Key('n'));
+        game.handle_input(&InputEvent::Key('1'));
+        assert_eq!(game.state.tool, PlacementTool::Smelter);
+        game.handle_input(&click(INSPECT_PRIORITY));
+        if let grid::Cell::Machine(m) = &game.state.grid[0][0] {
+            assert_eq!(m.side_filters[grid::Direction::Right.index()], vec![grid::ItemKind::IronOre]);
+            assert!(m.side_filters[grid::Direction::Up.index()].is_empty());
+            assert_eq!(m.priority, grid::InputPriority::High);
+        } else {
+            panic!("smelter not placed");
+        }
+
+        game.handle_input(&click(TOGGLE_INSPECT));
+        assert!(game.state.inspect.is_none());
+    }
+
+    #[test]
+    fn factory_game_station_panel_sets_mode_and_places_cart() {
+        let mut game = started_game();
+        game.handle_input(&InputEvent::Key('o'));
+        assert_eq!(game.state.tool, PlacementTool::Station);
+        game.handle_input(&InputEvent::Key(' '));
+        game.handle_input(&InputEvent::Key('i'));
+        assert_eq!(game.state.inspect, Some((0, 0)));
+
+        game.handle_input(&InputEvent::Key('m'));
+        assert!(matches!(&game.state.grid[0][0],
+            grid::Cell::Station(s) if s.mode == grid::StationMode::Unload));
+        game.handle_input(&click(PLACE_CART));
+        assert_eq!(game.state.carts.len(), 1);
+        assert_eq!(game.state.money, 50 - logic::STATION_COST - logic::CART_COST);
+    }
+
     // ── Click action tests ──────────────────────────────────────
 
+    #[test]
+    fn tap_empty_cell_without_tool_opens_build_menu() {
+        let mut game = started_game();
+        game.handle_input(&click(GRID_CLICK.id(2)));
+        assert_eq!(game.state.build_menu, Some((2, 0)));
+        // Exporter (index 3) from the popup
+        game.handle_input(&click(BUILD_MENU.id(3)));
+        assert!(matches!(game.state.grid[0][2], grid::Cell::Machine(_)));
+        assert!(game.state.build_menu.is_none());
+
+        // Tapping outside the popup only closes it
+        game.handle_input(&click(GRID_CLICK.id(10)));
+        game.handle_input(&click(GRID_CLICK.id(12)));
+        assert!(game.state.build_menu.is_none());
+        assert!(matches!(game.state.grid[0][12], grid::Cell::Empty));
+    }
+
+    fn drag(origin: u16, target: Option<u16>, first: bool) -> InputEvent {
+        InputEvent::Drag(
+            ClickScope::Game(GameChoice::Factory),
+            DragMotion { origin, target, delta: (0, 0), first },
+        )
+    }
+
+    #[test]
+    fn drag_with_belt_tool_lays_belt_stroke() {
+        let mut game = started_game();
+        // ツール無しのドラッグは無視される
+        assert!(!game.handle_input(&drag(GRID_CLICK.id(0), Some(GRID_CLICK.id(1)), true)));
+
+        game.handle_input(&click(SELECT_BELT));
+        let below = GRID_CLICK.id(grid::VIEW_W + 2);
+        assert!(game.handle_input(&drag(GRID_CLICK.id(0), Some(GRID_CLICK.id(2)), true)));
+        assert!(game.handle_input(&drag(GRID_CLICK.id(0), Some(below), false)));
+        for (x, y) in [(0, 0), (1, 0), (2, 0), (2, 1)] {
+            assert!(matches!(game.state.grid[y][x], grid::Cell::Belt(_)), "({x},{y})");
+        }
+        assert_eq!((game.state.cursor_x, game.state.cursor_y), (2, 1));
+        assert_eq!(game.state.money, 50 - 4 * 2);
+    }
+
     #[test]
     fn click_action_select_tool() {
-        let mut game = FactoryGame::new();
+        let mut game = started_game();
         game.handle_input(&click(SELECT_MINER));
         assert_eq!(game.state.tool, PlacementTool::Miner);
         game.handle_input(&click(SELECT_BELT));
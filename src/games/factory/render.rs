@@ -6,16 +6,26 @@ use std::rc::Rc;
 use ratzilla::ratatui::layout::{Alignment, Constraint, Direction as LayoutDir, Layout, Rect};
 use ratzilla::ratatui::style::{Color, Modifier, Style};
 use ratzilla::ratatui::text::{Line, Span};
-use ratzilla::ratatui::widgets::{Block, Borders, Paragraph, Wrap};
+use ratzilla::ratatui::widgets::{Block, Borders, Clear, Paragraph, Wrap};
 use ratzilla::ratatui::Frame;
 
 use crate::input::{is_narrow_layout, ClickState};
-use crate::widgets::{ClickableGrid, ClickableList};
+use crate::numfmt;
+use crate::time::TICKS_PER_SEC;
+use crate::widgets::{ClickableGrid, ClickableList, Gauge};
 
 use super::actions::*;
-use super::grid::{anchor_of, machine_at, Belt, Cell, MachineKind, MinerMode, GRID_H, GRID_W, VIEW_H, VIEW_W};
-use super::logic::throughput_per_sec;
-use super::state::{FactoryState, PlacementTool};
+use super::grid::{
+    anchor_of, machine_at, recipes_making, recipes_using, Belt, Cell, Direction, ItemKind, Lift, LiftMode, MachineKind, MinerMode,
+    PollutionLevel, Station, StationMode, CART_CAPACITY, GRID_H, GRID_W, LIFT_CAPACITY, STATION_CAPACITY, VIEW_H, VIEW_W,
+};
+use super::logic::{
+    broken_machines, build_floor_blocker, can_build, export_shortfall, export_trend, exporter_rates, filter_label, flow_report, item_trends,
+    current_milestone, machine_consumes, pollution_at, recipe_book_items, machine_utilization, throughput_per_sec, tool_cost, tool_unlocked,
+    repair_cost, ExporterRate, FlowReport, CART_COST, FLOOR_COST, DRONE_REPAIR_TICKS, MILESTONE_EXPORT_PER_MIN, MILESTONE_GEARS,
+    BUILD_MENU_TOOLS, EXPORT_TARGET_STEP, THROUGHPUT_ACHIEVEMENT_PER_MIN, ZERO_STALL_ACHIEVEMENT_TICKS,
+};
+use super::state::{FactoryAchievement, FactoryMode, FactoryState, Milestone, PlacementTool};
 
 /// Spinner for active machines.
 const SPINNER: &[char] = &['◐', '◓', '◑', '◒'];
@@ -26,6 +36,11 @@ pub fn render(
     area: Rect,
     click_state: &Rc<RefCell<ClickState>>,
 ) {
+    if state.mode.is_none() {
+        render_mode_select(f, area, click_state);
+        return;
+    }
+
     let is_narrow = is_narrow_layout(area.width);
 
     if is_narrow {
@@ -35,6 +50,73 @@ pub fn render(
     }
 }
 
+/// 入場時のモード選択画面。
+fn render_mode_select(f: &mut Frame, area: Rect, click_state: &Rc<RefCell<ClickState>>) {
+    let mut cl = ClickableList::new();
+    cl.push(Line::from(""));
+    cl.push(Line::from(Span::styled(
+        "  ▣ TINY FACTORY ▣",
+        Style::default().fg(Color::Yellow).add_modifier(Modifier::BOLD),
+    )));
+    cl.push(Line::from(Span::styled(
+        "  鉱石を掘り、加工して、出荷ラインを自動化しよう",
+        Style::default().fg(Color::DarkGray),
+    )));
+    cl.push(Line::from(""));
+    cl.push(Line::from(Span::styled(
+        "  ── モードを選択 ──",
+        Style::default().fg(Color::Cyan).add_modifier(Modifier::BOLD),
+    )));
+    for (i, mode) in FactoryMode::ALL.iter().enumerate() {
+        let color = match mode {
+            FactoryMode::Campaign => Color::LightGreen,
+            FactoryMode::Sandbox => Color::LightBlue,
+        };
+        cl.push_clickable(
+            Line::from(vec![
+                Span::styled(
+                    format!("  [{}] {:<8}", i + 1, mode.name()),
+                    Style::default().fg(color).add_modifier(Modifier::BOLD),
+                ),
+                Span::styled(format!(" {}", mode.description()), Style::default().fg(Color::DarkGray)),
+            ]),
+            MODE_PICK.id(i),
+        );
+    }
+    cl.push(Line::from(""));
+    cl.push(Line::from(Span::styled("  キャンペーンの目標:", Style::default().fg(Color::DarkGray))));
+    for (i, m) in Milestone::ALL.iter().enumerate() {
+        let unlock = m.unlocks().map(|k| format!(" → {} 解放", k.name())).unwrap_or_default();
+        cl.push(Line::from(Span::styled(
+            format!("   {}. {}{}", i + 1, m.description(), unlock),
+            Style::default().fg(Color::DarkGray),
+        )));
+    }
+
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(Color::Yellow))
+        .title(" Tiny Factory ");
+    let mut cs = click_state.borrow_mut();
+    cl.render(f, area, block, &mut cs, false, 0);
+}
+
+/// キャンペーンの目標の進み具合。
+fn milestone_progress(state: &FactoryState, m: Milestone) -> String {
+    match m {
+        Milestone::FirstPlate => String::new(),
+        Milestone::AutomateGears => format!(
+            " {}/{}",
+            state.produced_count[ItemKind::Gear.index()].min(MILESTONE_GEARS),
+            MILESTONE_GEARS
+        ),
+        Milestone::Export10PerMin => {
+            let per_min = throughput_per_sec(&state.recent_export_ticks, state.total_ticks) * 60.0;
+            format!(" {:.0}/{:.0}個/分", per_min, MILESTONE_EXPORT_PER_MIN)
+        }
+    }
+}
+
 fn render_wide(
     state: &FactoryState,
     f: &mut Frame,
@@ -52,7 +134,7 @@ fn render_wide(
         .constraints([
             Constraint::Length(3),                       // Header
             Constraint::Length(VIEW_H as u16 + 2),       // Grid (viewport height + border)
-            Constraint::Min(12),                         // Tool panel (7 tools + description)
+            Constraint::Min(14),                         // Tool panel (10 tools + description)
         ])
         .split(h_chunks[0]);
 
@@ -64,7 +146,17 @@ fn render_wide(
     render_header(state, f, left_chunks[0], false);
     render_grid(state, f, left_chunks[1], click_state);
     render_tool_panel(state, f, left_chunks[2], click_state);
-    render_stats(state, f, right_chunks[0]);
+    if state.inspect.is_some() {
+        render_inspect(state, f, right_chunks[0], click_state);
+    } else if state.show_recipes {
+        render_recipe_book(state, f, right_chunks[0], click_state);
+    } else if state.show_stats {
+        render_stats_screen(state, f, right_chunks[0], click_state);
+    } else if state.show_dashboard {
+        render_dashboard(state, f, right_chunks[0], click_state);
+    } else {
+        render_stats(state, f, right_chunks[0]);
+    }
     render_log(state, f, right_chunks[1]);
 }
 
@@ -79,13 +171,24 @@ fn render_narrow(
         .constraints([
             Constraint::Length(3),                       // Header
             Constraint::Length(VIEW_H as u16 + 2),       // Grid (viewport)
-            Constraint::Length(12),                       // Tool panel
+            Constraint::Length(14),                       // Tool panel
         ])
         .split(area);
 
     render_header(state, f, chunks[0], true);
     render_grid(state, f, chunks[1], click_state);
-    render_tool_panel(state, f, chunks[2], click_state);
+    // 狭い画面は右パネルが無いので、調査パネルとダッシュボードはツール欄と差し替える
+    if state.inspect.is_some() {
+        render_inspect(state, f, chunks[2], click_state);
+    } else if state.show_recipes {
+        render_recipe_book(state, f, chunks[2], click_state);
+    } else if state.show_stats {
+        render_stats_screen(state, f, chunks[2], click_state);
+    } else if state.show_dashboard {
+        render_dashboard(state, f, chunks[2], click_state);
+    } else {
+        render_tool_panel(state, f, chunks[2], click_state);
+    }
 }
 
 fn render_header(state: &FactoryState, f: &mut Frame, area: Rect, is_narrow: bool) {
@@ -99,7 +202,7 @@ fn render_header(state: &FactoryState, f: &mut Frame, area: Rect, is_narrow: boo
 
     // Income rate ($/sec) based on total earnings and time elapsed
     let income_str = if state.total_ticks > 0 && state.total_money_earned > 0 {
-        let seconds = state.total_ticks as f64 / 10.0;
+        let seconds = state.total_ticks as f64 / TICKS_PER_SEC as f64;
         let rate = state.total_money_earned as f64 / seconds;
         if rate >= 1.0 {
             format!(" ${:.1}/s", rate)
@@ -149,10 +252,20 @@ fn render_header(state: &FactoryState, f: &mut Frame, area: Rect, is_narrow: boo
         String::new()
     };
 
-    let spans = if is_narrow {
+    // 実績トーストはツール表示より優先して目立たせる
+    let toast = state.achievement_toast.map(|(a, _)| {
+        Span::styled(
+            format!(" ★実績: {}", a.name()),
+            Style::default()
+                .fg(Color::LightMagenta)
+                .add_modifier(Modifier::BOLD | Modifier::REVERSED),
+        )
+    });
+
+    let mut spans = if is_narrow {
         vec![
             Span::styled(
-                format!("{}${} Exp:{}", money_anim, state.money, state.total_exported),
+                format!("{}${} Exp:{}", money_anim, numfmt::format(state.money as f64), numfmt::format(state.total_exported as f64)),
                 money_style,
             ),
             Span::styled(flash_str, flash_style),
@@ -164,7 +277,9 @@ fn render_header(state: &FactoryState, f: &mut Frame, area: Rect, is_narrow: boo
             Span::styled(
                 format!(
                     "{} $: {}    Exported: {}",
-                    money_anim, state.money, state.total_exported,
+                    money_anim,
+                    numfmt::format(state.money as f64),
+                    numfmt::format(state.total_exported as f64),
                 ),
                 money_style,
             ),
@@ -178,6 +293,19 @@ fn render_header(state: &FactoryState, f: &mut Frame, area: Rect, is_narrow: boo
             ),
         ]
     };
+    if let Some(toast) = toast {
+        if !is_narrow {
+            spans.pop();
+        }
+        spans.push(toast);
+    }
+    let broken = broken_machines(state);
+    if broken > 0 {
+        spans.push(Span::styled(
+            format!(" ⚠故障{}", broken),
+            Style::default().fg(Color::Red).add_modifier(Modifier::BOLD),
+        ));
+    }
 
     let widget = Paragraph::new(Line::from(spans))
         .block(
@@ -200,6 +328,12 @@ fn tool_name(tool: &PlacementTool) -> String {
         PlacementTool::Exporter => "Exporter ($15)".into(),
         PlacementTool::Fabricator => "Fabricator ($75)".into(),
         PlacementTool::Belt => "Belt ($2)".into(),
+        PlacementTool::Rail => "Rail ($3)".into(),
+        PlacementTool::Station => "Station ($20)".into(),
+        PlacementTool::Drone => "Drone ($40)".into(),
+        PlacementTool::Lift => "Lift ($35)".into(),
+        PlacementTool::Filter => "Filter ($30)".into(),
+        PlacementTool::Greenery => "Greenery ($5)".into(),
         PlacementTool::Delete => "Delete".into(),
     }
 }
@@ -357,6 +491,20 @@ fn miner_bl_chars(mode: MinerMode) -> &'static str {
     }
 }
 
+/// Broken indicator: TL's second char becomes '✕'.
+fn machine_cell_chars_broken(kind: MachineKind) -> String {
+    let tl = machine_cell_chars(kind, 0, 0, &super::grid::Machine::new(kind));
+    let first = tl.chars().next().unwrap_or(' ');
+    format!("{}✕", first)
+}
+
+/// 修理の進み具合を 1 文字で (░▒▓█)。
+fn progress_glyph(progress: u32, total: u32) -> char {
+    const STEPS: [char; 4] = ['░', '▒', '▓', '█'];
+    let i = (progress as usize * STEPS.len() / total.max(1) as usize).min(STEPS.len() - 1);
+    STEPS[i]
+}
+
 /// Blocked indicator: replace TL's second char with '!'
 fn machine_cell_chars_blocked(kind: MachineKind, dx: usize, dy: usize) -> &'static str {
     match (kind, dx, dy) {
@@ -434,6 +582,18 @@ fn render_grid(
 
     // Pre-compute I/O hints for adjacent cells when cursor is on a machine
     let io_hints = compute_io_hints(state);
+    // 流れ調査中は、対象アイテムを実際に消費している機械を強調する
+    let flow_sinks: Vec<(usize, usize)> = state
+        .flow_item
+        .map(|item| {
+            flow_report(state, item)
+                .sinks
+                .iter()
+                .filter(|s| s.per_min > 0.0)
+                .map(|s| s.anchor)
+                .collect()
+        })
+        .unwrap_or_default();
 
     for y in vy..(vy + VIEW_H).min(GRID_H) {
         let mut spans: Vec<Span> = Vec::new();
@@ -465,7 +625,9 @@ fn render_grid(
                     } else {
                         machine_color(m.kind)
                     };
-                    let chars = if blocked && dx == 0 && dy == 0 {
+                    let chars = if m.broken && dx == 0 && dy == 0 {
+                        machine_cell_chars_broken(m.kind)
+                    } else if blocked && dx == 0 && dy == 0 {
                         machine_cell_chars_blocked(m.kind, dx, dy).to_string()
                     } else if m.kind == MachineKind::Miner && dx == 0 && dy == 1 {
                         // Miner BL: show mode indicator
@@ -473,7 +635,12 @@ fn render_grid(
                     } else {
                         machine_cell_chars(m.kind, dx, dy, m).to_string()
                     };
-                    let style = if m.kind == MachineKind::Exporter && state.export_flash > 0 {
+                    let style = if m.broken {
+                        // 故障: 赤の反転で止まっていることを目立たせる
+                        Style::default()
+                            .fg(Color::Red)
+                            .add_modifier(Modifier::BOLD | Modifier::REVERSED)
+                    } else if m.kind == MachineKind::Exporter && state.export_flash > 0 {
                         // 出荷フラッシュ: 金色の反転表示で「売れた！」を強調
                         Style::default()
                             .fg(Color::LightYellow)
@@ -505,6 +672,33 @@ fn render_grid(
                         belt_idle_visual(x, y, state.anim_frame)
                     }
                 }
+                Cell::Rail => (rail_glyph(&state.grid, x, y), Style::default().fg(Color::Gray)),
+                Cell::Station(s) => {
+                    let next = s.buffer.first().map_or(' ', |i| i.symbol());
+                    (format!("▣{}", next), Style::default().fg(station_color(s.mode)).add_modifier(Modifier::BOLD))
+                }
+                // 修理中は 2 文字目に進み具合
+                Cell::Drone(d) => match d.target {
+                    Some(_) => (
+                        format!("⊕{}", progress_glyph(d.progress, DRONE_REPAIR_TICKS)),
+                        Style::default().fg(Color::LightMagenta).add_modifier(Modifier::BOLD),
+                    ),
+                    None => ("⊕ ".to_string(), Style::default().fg(Color::LightMagenta)),
+                },
+                Cell::Lift(l) => {
+                    let next = l.buffer.first().map_or(' ', |i| i.symbol());
+                    (format!("{}{}", lift_glyph(l.mode), next), Style::default().fg(lift_color(l.mode)).add_modifier(Modifier::BOLD))
+                }
+                Cell::Filter => ("⊜ ".to_string(), Style::default().fg(Color::LightCyan).add_modifier(Modifier::BOLD)),
+                Cell::Greenery => ("♣ ".to_string(), Style::default().fg(Color::Green)),
+            };
+            // 台車は線路・駅の上に重ねて描く。2 文字目は積荷の先頭
+            let (text, base_style) = match state.carts.iter().find(|c| (c.x, c.y) == (x, y)) {
+                Some(cart) => (
+                    format!("◘{}", cart.cargo.first().map_or(' ', |i| i.symbol())),
+                    Style::default().fg(Color::LightYellow).add_modifier(Modifier::BOLD),
+                ),
+                None => (text, base_style),
             };
 
             // Cursor highlighting: highlight 2×2 block if cursor is on any part of a machine
@@ -514,11 +708,21 @@ fn render_grid(
                 x == state.cursor_x && y == state.cursor_y
             };
 
+            let is_flow_sink = anchor_of(&state.grid, x, y)
+                .is_some_and(|anchor| flow_sinks.contains(&anchor));
+
             let style = if is_highlighted {
                 Style::default()
                     .fg(Color::Black)
                     .bg(Color::Yellow)
                     .add_modifier(Modifier::BOLD)
+            } else if is_flow_sink {
+                base_style.bg(Color::Magenta)
+            } else if state.show_pollution {
+                match pollution_color(PollutionLevel::of(pollution_at(state, x, y))) {
+                    Some(bg) => base_style.bg(bg),
+                    None => base_style,
+                }
             } else {
                 base_style
             };
@@ -530,8 +734,8 @@ fn render_grid(
     }
 
     let title = format!(
-        " Grid ({},{}) {}×{} ",
-        state.cursor_x, state.cursor_y, GRID_W, GRID_H
+        " {}F Grid ({},{}) {}×{} ",
+        state.floor + 1, state.cursor_x, state.cursor_y, GRID_W, GRID_H
     );
     let block = Block::default()
         .borders(Borders::ALL)
@@ -539,12 +743,164 @@ fn render_grid(
         .title(title);
 
     // Register click targets via ClickableGrid builder (co-located with render)
-    let grid = ClickableGrid::new(VIEW_W, VIEW_H, GRID_CLICK_BASE, 2);
+    let grid = ClickableGrid::new(VIEW_W, VIEW_H, GRID_CLICK.base(), 2);
     let mut cs = click_state.borrow_mut();
     grid.register_targets(area, &block, &mut cs, 1); // padding_left=1 for leading space
+    // Belt ツールでなぞって敷けるよう、グリッド全体をドラッグ領域にする
+    cs.add_drag_area(block.inner(area));
 
     let widget = Paragraph::new(lines).block(block);
     f.render_widget(widget, area);
+
+    // 建設メニューはグリッドの上に重ねる (後から登録したターゲットが優先される)
+    render_build_menu(state, f, area, &mut cs);
+}
+
+/// 線路のつながりに合わせた罫線。右へつながるときは 2 文字目も線でつなぐ。
+fn rail_glyph(grid: &[Vec<Cell>], x: usize, y: usize) -> String {
+    let track = |dx: i32, dy: i32| {
+        let (nx, ny) = (x as i32 + dx, y as i32 + dy);
+        nx >= 0
+            && ny >= 0
+            && (nx as usize) < GRID_W
+            && (ny as usize) < GRID_H
+            && grid[ny as usize][nx as usize].is_track()
+    };
+    let (up, down, left, right) = (track(0, -1), track(0, 1), track(-1, 0), track(1, 0));
+    let ch = match (up, down, left, right) {
+        (true, true, true, true) => '╬',
+        (true, true, true, false) => '╣',
+        (true, true, false, true) => '╠',
+        (true, false, true, true) => '╩',
+        (false, true, true, true) => '╦',
+        (false, true, false, true) => '╔',
+        (false, true, true, false) => '╗',
+        (true, false, false, true) => '╚',
+        (true, false, true, false) => '╝',
+        (true, _, false, false) | (_, true, false, false) => '║',
+        _ => '═',
+    };
+    format!("{}{}", ch, if right { '═' } else { ' ' })
+}
+
+fn station_color(mode: StationMode) -> Color {
+    match mode {
+        StationMode::Load => Color::LightGreen,
+        StationMode::Unload => Color::LightCyan,
+        StationMode::Pass => Color::DarkGray,
+    }
+}
+
+/// 汚染マップの背景色。きれいな区画は塗らない。
+fn pollution_color(level: PollutionLevel) -> Option<Color> {
+    match level {
+        PollutionLevel::Clean => None,
+        PollutionLevel::Hazy => Some(Color::Rgb(90, 80, 20)),
+        PollutionLevel::Smog => Some(Color::Rgb(110, 30, 30)),
+    }
+}
+
+fn lift_glyph(mode: LiftMode) -> char {
+    match mode {
+        LiftMode::Up => '⇑',
+        LiftMode::Down => '⇓',
+        LiftMode::Out => '⇕',
+    }
+}
+
+fn lift_color(mode: LiftMode) -> Color {
+    match mode {
+        LiftMode::Up | LiftMode::Down => Color::LightBlue,
+        LiftMode::Out => Color::LightCyan,
+    }
+}
+
+/// アイテムの内訳 (「o×3 =×2」)。空なら「空」。
+fn item_counts(items: &[ItemKind]) -> String {
+    let parts: Vec<String> = ItemKind::ALL
+        .iter()
+        .filter_map(|k| {
+            let n = items.iter().filter(|i| *i == k).count();
+            (n > 0).then(|| format!("{}×{}", k.symbol(), n))
+        })
+        .collect();
+    if parts.is_empty() {
+        "空".into()
+    } else {
+        parts.join(" ")
+    }
+}
+
+/// 空きマスの建設メニュー。タップしたマスの右 (収まらなければ左) に出す。
+/// 資金不足・スペース不足の機械は灰色でタップ不可。
+fn render_build_menu(state: &FactoryState, f: &mut Frame, grid_area: Rect, cs: &mut ClickState) {
+    let Some((x, y)) = state.build_menu else {
+        return;
+    };
+    if x < state.viewport_x || y < state.viewport_y {
+        return;
+    }
+    let width: u16 = 24;
+    let height: u16 = BUILD_MENU_TOOLS.len() as u16 + 3;
+    if grid_area.width < width || grid_area.height < height {
+        return;
+    }
+
+    // グリッド内側 (枠 1 + 先頭スペース 1) から見たセルの画面座標
+    let cell_col = grid_area.x + 2 + (x - state.viewport_x) as u16 * 2;
+    let cell_row = grid_area.y + 1 + (y - state.viewport_y) as u16;
+    let right = grid_area.x + grid_area.width;
+    let left_x = if cell_col + 2 + width <= right {
+        cell_col + 2
+    } else {
+        cell_col.saturating_sub(width).max(grid_area.x)
+    };
+    let top_y = cell_row.min(grid_area.y + grid_area.height - height);
+    let popup = Rect::new(left_x, top_y, width, height);
+
+    let mut cl = ClickableList::new();
+    for (i, tool) in BUILD_MENU_TOOLS.iter().enumerate() {
+        let key = if *tool == PlacementTool::Belt { 'b' } else { (b'1' + i as u8) as char };
+        let label = format!(" {} {:<10} ${:<3}", key, tool_name_short(tool), tool_cost(tool));
+        if can_build(state, x, y, tool) {
+            cl.push_clickable(
+                Line::from(Span::styled(label, Style::default().fg(tool_color(tool)))),
+                BUILD_MENU.id(i),
+            );
+        } else {
+            cl.push(Line::from(Span::styled(label, Style::default().fg(Color::DarkGray))));
+        }
+    }
+    cl.push_clickable(
+        Line::from(Span::styled(" e 閉じる", Style::default().fg(Color::Gray))),
+        BUILD_MENU_CLOSE,
+    );
+
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(Color::Yellow))
+        .title(" 建設 ");
+    f.render_widget(Clear, popup);
+    cl.render(f, popup, block, cs, false, 0);
+}
+
+fn tool_name_short(tool: &PlacementTool) -> &'static str {
+    match tool {
+        PlacementTool::None => "None",
+        PlacementTool::Miner => "Miner",
+        PlacementTool::Smelter => "Smelter",
+        PlacementTool::Assembler => "Assembler",
+        PlacementTool::Exporter => "Exporter",
+        PlacementTool::Fabricator => "Fabricator",
+        PlacementTool::Belt => "Belt",
+        PlacementTool::Rail => "Rail",
+        PlacementTool::Station => "Station",
+        PlacementTool::Drone => "Drone",
+        PlacementTool::Lift => "Lift",
+        PlacementTool::Filter => "Filter",
+        PlacementTool::Greenery => "Greenery",
+        PlacementTool::Delete => "Delete",
+    }
 }
 
 /// Per-kind aggregated stats for display.
@@ -604,15 +960,13 @@ fn collect_stats(state: &FactoryState) -> [KindStats; 5] {
 
 /// Render a utilization bar like [████░░░░] 62%
 fn util_bar(util: f64, width: usize) -> Vec<Span<'static>> {
-    let filled = (util * width as f64).round() as usize;
-    let empty = width.saturating_sub(filled);
-    let pct = (util * 100.0) as u32;
-    let color = if pct >= 80 { Color::Green } else if pct >= 40 { Color::Yellow } else { Color::Red };
-    vec![
-        Span::styled("█".repeat(filled), Style::default().fg(color)),
-        Span::styled("░".repeat(empty), Style::default().fg(Color::DarkGray)),
-        Span::styled(format!("{:>3}%", pct), Style::default().fg(color)),
-    ]
+    Gauge::ratio(util, width)
+        .color(Color::Red)
+        .threshold(0.4, Color::Yellow)
+        .threshold(0.8, Color::Green)
+        .empty_color(Color::DarkGray)
+        .label(format!("{:>3}%", (util * 100.0) as u32))
+        .spans()
 }
 
 fn render_stats(state: &FactoryState, f: &mut Frame, area: Rect) {
@@ -624,11 +978,16 @@ fn render_stats(state: &FactoryState, f: &mut Frame, area: Rect) {
 
     // Summary header
     lines.push(Line::from(vec![
-        Span::styled(format!(" ${:<8}", state.money), Style::default().fg(Color::Yellow).add_modifier(Modifier::BOLD)),
-        Span::styled(format!(" 出荷:{}", state.total_exported), Style::default().fg(Color::Green)),
+        Span::styled(format!(" ${:<8}", numfmt::format(state.money as f64)), Style::default().fg(Color::Yellow).add_modifier(Modifier::BOLD)),
+        Span::styled(format!(" 出荷:{}", numfmt::format(state.total_exported as f64)), Style::default().fg(Color::Green)),
     ]));
     lines.push(Line::from(""));
 
+    if let Some(item) = state.flow_item {
+        flow_lines(&flow_report(state, item), &mut lines);
+        lines.push(Line::from(""));
+    }
+
     // Per-kind rows
     for (i, s) in stats.iter().enumerate() {
         if s.count == 0 { continue; }
@@ -665,7 +1024,7 @@ fn render_stats(state: &FactoryState, f: &mut Frame, area: Rect) {
 
     // Income rate
     if state.total_ticks > 0 && state.total_money_earned > 0 {
-        let rate = state.total_money_earned as f64 / (state.total_ticks as f64 / 10.0);
+        let rate = state.total_money_earned as f64 / (state.total_ticks as f64 / TICKS_PER_SEC as f64);
         lines.push(Line::from(""));
         lines.push(Line::from(Span::styled(
             format!(" 収入: ${:.1}/s", rate),
@@ -680,6 +1039,26 @@ fn render_stats(state: &FactoryState, f: &mut Frame, area: Rect) {
         )));
     }
 
+    // 実績の進捗
+    lines.push(Line::from(""));
+    lines.push(Line::from(Span::styled(
+        format!(" 実績 {}/{}", state.achievements.len(), FactoryAchievement::ALL.len()),
+        Style::default().fg(Color::LightMagenta).add_modifier(Modifier::BOLD),
+    )));
+    for a in FactoryAchievement::ALL {
+        if state.has_achievement(a) {
+            lines.push(Line::from(Span::styled(
+                format!("  ★ {}", a.name()),
+                Style::default().fg(Color::LightMagenta),
+            )));
+        } else {
+            lines.push(Line::from(Span::styled(
+                format!("  ☆ {} {}", a.name(), achievement_progress(state, a)),
+                Style::default().fg(Color::DarkGray),
+            )));
+        }
+    }
+
     let widget = Paragraph::new(lines)
         .style(Style::default().fg(Color::White))
         .block(
@@ -691,6 +1070,560 @@ fn render_stats(state: &FactoryState, f: &mut Frame, area: Rect) {
     f.render_widget(widget, area);
 }
 
+/// スパークラインの段階 (低い順)。
+const SPARK: [char; 8] = ['▁', '▂', '▃', '▄', '▅', '▆', '▇', '█'];
+
+/// 区間ペースを `max` 基準のスパークラインにする。0 の区間は空白
+/// (出荷ゼロと「少しだけ出荷」を見分けるため)。
+fn sparkline(buckets: &[f64], max: f64) -> String {
+    buckets
+        .iter()
+        .map(|&v| {
+            if v <= 0.0 || max <= 0.0 {
+                ' '
+            } else {
+                let level = ((v / max) * (SPARK.len() - 1) as f64).round() as usize;
+                SPARK[level.min(SPARK.len() - 1)]
+            }
+        })
+        .collect()
+}
+
+/// 出荷ダッシュボード: Exporter ごとの直近 5 分のペースを縦に積んだ
+/// スパークラインと、目標ペースとの差。全行で同じ縦軸を使い、台どうしを
+/// 見比べられるようにする。
+fn render_dashboard(
+    state: &FactoryState,
+    f: &mut Frame,
+    area: Rect,
+    click_state: &Rc<RefCell<ClickState>>,
+) {
+    let rates = exporter_rates(state);
+    let total: f64 = rates.iter().map(|r| r.per_min).sum();
+    let shortfall = export_shortfall(state, &rates);
+    let max = rates
+        .iter()
+        .flat_map(|r| r.buckets.iter().copied())
+        .fold(0.0, f64::max);
+
+    let mut cl = ClickableList::new();
+    cl.push(Line::from(vec![
+        Span::styled(format!(" 合計 {:.1}/分", total), Style::default().fg(Color::Green).add_modifier(Modifier::BOLD)),
+        Span::styled(
+            format!("  目標 {}/分", state.export_target_per_min),
+            Style::default().fg(Color::Yellow),
+        ),
+    ]));
+    match shortfall {
+        Some(gap) => cl.push(Line::from(Span::styled(
+            format!(" ▼ 不足 {:.1}/分", gap),
+            Style::default().fg(Color::Red).add_modifier(Modifier::BOLD | Modifier::REVERSED),
+        ))),
+        None => cl.push(Line::from(Span::styled(" ✔ 目標達成", Style::default().fg(Color::Green)))),
+    }
+    cl.push_clickable(
+        Line::from(Span::styled(
+            format!(" ▶目標 +{} [+]", EXPORT_TARGET_STEP),
+            Style::default().fg(Color::Cyan),
+        )),
+        EXPORT_TARGET_UP,
+    );
+    cl.push_clickable(
+        Line::from(Span::styled(
+            format!(" ▶目標 -{} [-]", EXPORT_TARGET_STEP),
+            Style::default().fg(Color::Cyan),
+        )),
+        EXPORT_TARGET_DOWN,
+    );
+    cl.push(Line::from(""));
+
+    if rates.is_empty() {
+        cl.push(Line::from(Span::styled(" Exporter がありません", Style::default().fg(Color::DarkGray))));
+    }
+    for r in &rates {
+        dashboard_row(&mut cl, r, max, shortfall.is_some());
+    }
+    cl.push(Line::from(Span::styled(
+        "  (5分前 → 今, 30秒/目盛)",
+        Style::default().fg(Color::DarkGray),
+    )));
+    cl.push(Line::from(""));
+    cl.push_clickable(
+        Line::from(Span::styled(" ▶閉じる [g]", Style::default().fg(Color::DarkGray))),
+        TOGGLE_DASHBOARD,
+    );
+
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(if shortfall.is_some() { Color::Red } else { Color::Green }))
+        .title(" 出荷ダッシュボード ");
+    let mut cs = click_state.borrow_mut();
+    cl.render(f, area, block, &mut cs, false, 0);
+}
+
+/// 統計画面: アイテムごとの生産 (▲) と消費 (▼) の推移、出荷の推移と累計、
+/// 機械の種類ごとの稼働率。推移は全アイテムで同じ縦軸を使う。
+fn render_stats_screen(
+    state: &FactoryState,
+    f: &mut Frame,
+    area: Rect,
+    click_state: &Rc<RefCell<ClickState>>,
+) {
+    let trends = item_trends(state);
+    let exports = export_trend(state);
+    let max = trends
+        .iter()
+        .flat_map(|t| t.produced.iter().chain(t.consumed.iter()).copied())
+        .fold(0.0, f64::max);
+
+    let mut cl = ClickableList::new();
+    cl.push(Line::from(vec![
+        Span::styled(
+            format!(" 出荷 累計{}個", state.total_exported),
+            Style::default().fg(Color::Green).add_modifier(Modifier::BOLD),
+        ),
+        Span::styled(format!("  ${}", state.total_money_earned), Style::default().fg(Color::Yellow)),
+    ]));
+    let export_max = exports.iter().copied().fold(0.0, f64::max);
+    cl.push(Line::from(vec![
+        Span::styled("  出荷 ", Style::default().fg(Color::Green)),
+        Span::styled(sparkline(&exports, export_max), Style::default().fg(Color::LightYellow)),
+    ]));
+    cl.push(Line::from(""));
+
+    let active: Vec<_> = trends
+        .iter()
+        .filter(|t| t.produced_per_min > 0.0 || t.consumed_per_min > 0.0)
+        .collect();
+    if active.is_empty() {
+        cl.push(Line::from(Span::styled(
+            " まだ生産の記録がありません",
+            Style::default().fg(Color::DarkGray),
+        )));
+    }
+    for t in active {
+        let short = t.consumed_per_min > t.produced_per_min;
+        cl.push(Line::from(vec![
+            Span::styled(
+                format!(" {}{}", t.item.symbol(), t.item.name()),
+                Style::default().fg(t.item.color()).add_modifier(Modifier::BOLD),
+            ),
+            Span::styled(format!(" ▲{:.1}", t.produced_per_min), Style::default().fg(Color::Green)),
+            Span::styled(
+                format!(" ▼{:.1}/分", t.consumed_per_min),
+                Style::default().fg(if short { Color::Red } else { Color::White }),
+            ),
+        ]));
+        cl.push(Line::from(vec![
+            Span::styled("  ▲", Style::default().fg(Color::Green)),
+            Span::styled(sparkline(&t.produced, max), Style::default().fg(t.item.color())),
+            Span::styled(" ▼", Style::default().fg(Color::White)),
+            Span::styled(sparkline(&t.consumed, max), Style::default().fg(t.item.color())),
+        ]));
+    }
+    cl.push(Line::from(Span::styled(
+        "  (5分前 → 今, 20秒/目盛)",
+        Style::default().fg(Color::DarkGray),
+    )));
+    cl.push(Line::from(""));
+
+    cl.push(Line::from(Span::styled(
+        " 稼働率",
+        Style::default().fg(Color::Cyan).add_modifier(Modifier::BOLD),
+    )));
+    for (kind, util) in MachineKind::ALL.iter().zip(machine_utilization(state)) {
+        let Some(util) = util else { continue };
+        let mut spans = vec![Span::styled(format!("  {:<10}", kind.name()), Style::default().fg(Color::White))];
+        spans.extend(util_bar(util, 8));
+        cl.push(Line::from(spans));
+    }
+    cl.push(Line::from(""));
+    cl.push_clickable(
+        Line::from(Span::styled(" ▶閉じる [s]", Style::default().fg(Color::DarkGray))),
+        TOGGLE_STATS,
+    );
+
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(Color::Cyan))
+        .title(" 統計 ");
+    let mut cs = click_state.borrow_mut();
+    cl.render(f, area, block, &mut cs, false, 0);
+}
+
+/// レシピ帳: 絞り込みに一致するアイテムごとに、作り方 (機械と材料) と使い道。
+fn render_recipe_book(
+    state: &FactoryState,
+    f: &mut Frame,
+    area: Rect,
+    click_state: &Rc<RefCell<ClickState>>,
+) {
+    let item_label = |item: ItemKind| format!("{}{}", item.symbol(), item.name());
+
+    let mut cl = ClickableList::new();
+    cl.push(Line::from(vec![
+        Span::styled(" 検索: ", Style::default().fg(Color::Cyan).add_modifier(Modifier::BOLD)),
+        Span::styled(format!("{}_", state.recipe_filter), Style::default().fg(Color::White)),
+        Span::styled("  英字で絞り込み [-]1字消す", Style::default().fg(Color::DarkGray)),
+    ]));
+    cl.push_clickable(
+        Line::from(Span::styled(" ▶閉じる [?]", Style::default().fg(Color::DarkGray))),
+        TOGGLE_RECIPES,
+    );
+    if !state.recipe_filter.is_empty() {
+        cl.push_clickable(
+            Line::from(Span::styled(" ▶絞り込みを消す", Style::default().fg(Color::DarkGray))),
+            CLEAR_RECIPE_FILTER,
+        );
+    }
+
+    let items = recipe_book_items(&state.recipe_filter);
+    if items.is_empty() {
+        cl.push(Line::from(Span::styled(
+            " 一致するアイテムがありません",
+            Style::default().fg(Color::DarkGray),
+        )));
+    }
+    for item in items {
+        cl.push(Line::from(vec![
+            Span::styled(
+                format!(" {}", item_label(item)),
+                Style::default().fg(item.color()).add_modifier(Modifier::BOLD),
+            ),
+            Span::styled(format!(" {}", item.search_name()), Style::default().fg(Color::DarkGray)),
+            Span::styled(
+                format!("  出荷${}", MachineKind::export_value(&item)),
+                Style::default().fg(Color::Yellow),
+            ),
+        ]));
+        for r in recipes_making(item) {
+            let from = if r.inputs.is_empty() {
+                "採掘".to_string()
+            } else {
+                r.inputs.iter().map(|&i| item_label(i)).collect::<Vec<_>>().join(" + ")
+            };
+            cl.push(Line::from(vec![
+                Span::styled("  作る: ", Style::default().fg(Color::Green)),
+                Span::styled(format!("{} ← {}", r.machine.name(), from), Style::default().fg(Color::White)),
+            ]));
+        }
+        let uses: Vec<String> = recipes_using(item)
+            .map(|r| format!("{}→{}", r.machine.name(), item_label(r.output)))
+            .collect();
+        let uses = if uses.is_empty() { "出荷のみ".to_string() } else { uses.join(", ") };
+        cl.push(Line::from(vec![
+            Span::styled("  使う: ", Style::default().fg(Color::Magenta)),
+            Span::styled(uses, Style::default().fg(Color::White)),
+        ]));
+    }
+
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(Color::Cyan))
+        .title(" レシピ帳 ");
+    let mut cs = click_state.borrow_mut();
+    cl.render(f, area, block, &mut cs, false, 0);
+}
+
+/// 調査パネル: 機械は辺ごとの受け入れリストと入力優先度、ベルトは受け入れリスト。
+fn render_inspect(
+    state: &FactoryState,
+    f: &mut Frame,
+    area: Rect,
+    click_state: &Rc<RefCell<ClickState>>,
+) {
+    let Some((x, y)) = state.inspect else { return };
+    if let Cell::Station(s) = &state.grid[y][x] {
+        render_station_panel(state, s, (x, y), f, area, click_state);
+        return;
+    }
+    if let Cell::Lift(l) = &state.grid[y][x] {
+        render_lift_panel(state, l, (x, y), f, area, click_state);
+        return;
+    }
+    let mut cl = ClickableList::new();
+    // (アイテム, この機械の入力になるか, 受け入れリストにあるか)
+    let items: Vec<(ItemKind, bool, bool)> = match &state.grid[y][x] {
+        Cell::Machine(m) => {
+            cl.push(Line::from(Span::styled(
+                format!(" {} ({},{})", m.kind.name(), x, y),
+                Style::default().fg(Color::Yellow).add_modifier(Modifier::BOLD),
+            )));
+            let level = PollutionLevel::of(pollution_at(state, x, y));
+            if level != PollutionLevel::Clean {
+                cl.push(Line::from(Span::styled(
+                    format!(" 汚染で{} 速さ{}%", level.name(), level.speed_pct()),
+                    Style::default().fg(Color::LightRed),
+                )));
+            }
+            cl.push_clickable(
+                Line::from(Span::styled(
+                    format!(" ▶入力優先度: {} [p]", m.priority.name()),
+                    Style::default().fg(Color::Cyan),
+                )),
+                INSPECT_PRIORITY,
+            );
+            if m.broken {
+                cl.push_clickable(
+                    Line::from(Span::styled(
+                        format!(" ⚠故障中 ▶修理 ${} [x]", repair_cost(m.kind)),
+                        Style::default().fg(Color::Red).add_modifier(Modifier::BOLD),
+                    )),
+                    REPAIR_MACHINE,
+                );
+            }
+            for (i, side) in Direction::ALL.iter().enumerate() {
+                let selected = *side == state.inspect_side;
+                let style = if selected {
+                    Style::default().fg(Color::White).add_modifier(Modifier::BOLD | Modifier::REVERSED)
+                } else {
+                    Style::default().fg(Color::DarkGray)
+                };
+                cl.push_clickable(
+                    Line::from(Span::styled(
+                        format!(" {}{}辺: {}", if selected { "▶" } else { " " }, side.name(), filter_label(&m.side_filters[i])),
+                        style,
+                    )),
+                    INSPECT_SIDE.id(i),
+                );
+            }
+            cl.push(Line::from(Span::styled(
+                format!(" {}辺で受け入れる [n]で辺を切替", state.inspect_side.name()),
+                Style::default().fg(Color::DarkGray),
+            )));
+            let filter = &m.side_filters[state.inspect_side.index()];
+            ItemKind::ALL
+                .iter()
+                .map(|&k| (k, machine_consumes(m.kind, k), filter.contains(&k)))
+                .collect()
+        }
+        Cell::Belt(b) => {
+            cl.push(Line::from(Span::styled(
+                format!(" Belt ({},{})", x, y),
+                Style::default().fg(Color::White).add_modifier(Modifier::BOLD),
+            )));
+            cl.push(Line::from(Span::styled(
+                format!(" 受け入れ: {}", filter_label(&b.filter)),
+                Style::default().fg(Color::Cyan),
+            )));
+            ItemKind::ALL.iter().map(|&k| (k, true, b.filter.contains(&k))).collect()
+        }
+        _ => Vec::new(),
+    };
+
+    if items.iter().all(|&(_, usable, _)| !usable) {
+        cl.push(Line::from(Span::styled(" 入力なし", Style::default().fg(Color::DarkGray))));
+    }
+    for (i, &(item, usable, on)) in items.iter().enumerate() {
+        if !usable {
+            continue;
+        }
+        let style = if on {
+            Style::default().fg(item.color()).add_modifier(Modifier::BOLD)
+        } else {
+            Style::default().fg(Color::DarkGray)
+        };
+        cl.push_clickable(
+            Line::from(Span::styled(
+                format!(" [{}] {} {}{}", i + 1, if on { "☑" } else { "☐" }, item.symbol(), item.name()),
+                style,
+            )),
+            INSPECT_ITEM.id(i),
+        );
+    }
+    cl.push(Line::from(Span::styled(
+        " 何も選ばなければすべて受け入れ",
+        Style::default().fg(Color::DarkGray),
+    )));
+    cl.push_clickable(
+        Line::from(Span::styled(" ▶閉じる [i]", Style::default().fg(Color::DarkGray))),
+        TOGGLE_INSPECT,
+    );
+
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(Color::Cyan))
+        .title(" 調査 ");
+    let mut cs = click_state.borrow_mut();
+    cl.render(f, area, block, &mut cs, false, 0);
+}
+
+/// 調査パネル (駅): 積み降ろしの切替、バッファ、台車の配置。
+fn render_station_panel(
+    state: &FactoryState,
+    s: &Station,
+    pos: (usize, usize),
+    f: &mut Frame,
+    area: Rect,
+    click_state: &Rc<RefCell<ClickState>>,
+) {
+    let mut cl = ClickableList::new();
+    cl.push(Line::from(Span::styled(
+        format!(" 駅 {} ({},{})", s.id, pos.0, pos.1),
+        Style::default().fg(station_color(s.mode)).add_modifier(Modifier::BOLD),
+    )));
+    cl.push_clickable(
+        Line::from(Span::styled(
+            format!(" ▶積み降ろし: {} [m]", s.mode.name()),
+            Style::default().fg(Color::Cyan),
+        )),
+        STATION_MODE,
+    );
+    cl.push(Line::from(Span::styled(
+        format!(" 在庫 {}/{}: {}", s.buffer.len(), STATION_CAPACITY, item_counts(&s.buffer)),
+        Style::default().fg(Color::White),
+    )));
+    cl.push(Line::from(""));
+    cl.push(Line::from(Span::styled(
+        format!(" 台車 {}台", state.carts.len()),
+        Style::default().fg(Color::LightYellow).add_modifier(Modifier::BOLD),
+    )));
+    for cart in &state.carts {
+        let here = if (cart.x, cart.y) == pos { "停車中" } else { "" };
+        cl.push(Line::from(Span::styled(
+            format!("  ◘({},{}) {}/{} {} {}", cart.x, cart.y, cart.cargo.len(), CART_CAPACITY, item_counts(&cart.cargo), here),
+            Style::default().fg(Color::LightYellow),
+        )));
+    }
+    cl.push_clickable(
+        Line::from(Span::styled(
+            format!(" ▶ここに台車を置く ${} [c]", CART_COST),
+            Style::default().fg(Color::Cyan),
+        )),
+        PLACE_CART,
+    );
+    cl.push(Line::from(Span::styled(
+        " 台車はつながった駅を番号順に巡回",
+        Style::default().fg(Color::DarkGray),
+    )));
+    cl.push(Line::from(Span::styled(
+        " (通過の駅には停まらない)",
+        Style::default().fg(Color::DarkGray),
+    )));
+    cl.push_clickable(
+        Line::from(Span::styled(" ▶閉じる [i]", Style::default().fg(Color::DarkGray))),
+        TOGGLE_INSPECT,
+    );
+
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(Color::Cyan))
+        .title(" 調査 ");
+    let mut cs = click_state.borrow_mut();
+    cl.render(f, area, block, &mut cs, false, 0);
+}
+
+/// 調査パネル (リフト): 働きの切替とバッファ。
+fn render_lift_panel(
+    state: &FactoryState,
+    l: &Lift,
+    pos: (usize, usize),
+    f: &mut Frame,
+    area: Rect,
+    click_state: &Rc<RefCell<ClickState>>,
+) {
+    let mut cl = ClickableList::new();
+    cl.push(Line::from(Span::styled(
+        format!(" リフト {}F ({},{})", state.floor + 1, pos.0, pos.1),
+        Style::default().fg(lift_color(l.mode)).add_modifier(Modifier::BOLD),
+    )));
+    cl.push_clickable(
+        Line::from(Span::styled(
+            format!(" ▶働き: {} [m]", l.mode.name()),
+            Style::default().fg(Color::Cyan),
+        )),
+        LIFT_MODE,
+    );
+    cl.push(Line::from(Span::styled(
+        format!(" 在庫 {}/{}: {}", l.buffer.len(), LIFT_CAPACITY, item_counts(&l.buffer)),
+        Style::default().fg(Color::White),
+    )));
+    cl.push(Line::from(""));
+    let partner = match l.mode {
+        LiftMode::Up => format!(" {}F の同じ位置の「受け取る」リフトへ", state.floor + 2),
+        LiftMode::Down if state.floor > 0 => format!(" {}F の同じ位置の「受け取る」リフトへ", state.floor),
+        LiftMode::Down => " 1F より下はありません".into(),
+        LiftMode::Out => " 上下の階から届いた品をベルトへ出す".into(),
+    };
+    cl.push(Line::from(Span::styled(partner, Style::default().fg(Color::DarkGray))));
+    cl.push(Line::from(Span::styled(
+        " ベルトから受けて送る / 届いた品を出す",
+        Style::default().fg(Color::DarkGray),
+    )));
+    cl.push_clickable(
+        Line::from(Span::styled(" ▶閉じる [i]", Style::default().fg(Color::DarkGray))),
+        TOGGLE_INSPECT,
+    );
+
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(Color::Cyan))
+        .title(" 調査 ");
+    let mut cs = click_state.borrow_mut();
+    cl.render(f, area, block, &mut cs, false, 0);
+}
+
+/// ダッシュボードの Exporter 1 台分の行。目標未達の間は止まっている台を赤くする。
+fn dashboard_row(cl: &mut ClickableList, r: &ExporterRate, max: f64, short: bool) {
+    let idle = r.per_min <= 0.0;
+    let label_color = if idle && short { Color::Red } else { Color::Green };
+    cl.push(Line::from(vec![
+        Span::styled(
+            format!(" ({:>2},{:>2}) {:>5.1}/分 ", r.anchor.0, r.anchor.1, r.per_min),
+            Style::default().fg(label_color),
+        ),
+        Span::styled(sparkline(&r.buckets, max), Style::default().fg(Color::LightYellow)),
+    ]));
+}
+
+/// 流れ調査: 供給 vs 消費と、消費している機械の内訳。
+fn flow_lines(report: &FlowReport, lines: &mut Vec<Line<'static>>) {
+    let short = report.demand_per_min > report.supply_per_min;
+    lines.push(Line::from(Span::styled(
+        format!(" 流れ: {}{}", report.item.symbol(), report.item.name()),
+        Style::default().fg(report.item.color()).add_modifier(Modifier::BOLD),
+    )));
+    lines.push(Line::from(vec![
+        Span::styled(
+            format!("  供給 {:.1}/分", report.supply_per_min),
+            Style::default().fg(Color::Green),
+        ),
+        Span::styled(
+            format!("  消費 {:.1}/分", report.demand_per_min),
+            Style::default().fg(if short { Color::Red } else { Color::White }),
+        ),
+    ]));
+    if report.sinks.is_empty() {
+        lines.push(Line::from(Span::styled(
+            "  消費する機械なし",
+            Style::default().fg(Color::DarkGray),
+        )));
+    }
+    for s in &report.sinks {
+        let color = if s.per_min > 0.0 { Color::Magenta } else { Color::DarkGray };
+        lines.push(Line::from(Span::styled(
+            format!("  {}({},{}) {:.1}/分", s.kind.name(), s.anchor.0, s.anchor.1, s.per_min),
+            Style::default().fg(color),
+        )));
+    }
+}
+
+/// 未解除の実績の進捗表示。
+fn achievement_progress(state: &FactoryState, a: FactoryAchievement) -> String {
+    match a {
+        FactoryAchievement::FirstFullChain => a.description().to_string(),
+        FactoryAchievement::Throughput100 => {
+            let per_min = throughput_per_sec(&state.recent_export_ticks, state.total_ticks) * 60.0;
+            format!("{:.0}/{:.0}個/分", per_min, THROUGHPUT_ACHIEVEMENT_PER_MIN)
+        }
+        FactoryAchievement::ZeroStall10Min => format!(
+            "{}/{}分",
+            state.stall_free_ticks / 600,
+            ZERO_STALL_ACHIEVEMENT_TICKS / 600
+        ),
+    }
+}
+
 fn render_log(state: &FactoryState, f: &mut Frame, area: Rect) {
     let visible_height = area.height.saturating_sub(2) as usize;
 
@@ -722,13 +1655,19 @@ fn render_log(state: &FactoryState, f: &mut Frame, area: Rect) {
 /// Tool descriptions for each placement tool.
 fn tool_description(tool: &PlacementTool) -> &'static str {
     match tool {
-        PlacementTool::None => "ツールを選択してグリッドをタップ",
+        PlacementTool::None => "空きマスをタップで建設メニュー",
         PlacementTool::Miner => "鉱石を自動生産",
         PlacementTool::Smelter => "鉱石→板に精錬(鉄/銅自動判別)",
         PlacementTool::Assembler => "鉄板(=)→歯車(*)を組立。入力:鉄板",
         PlacementTool::Exporter => "アイテムを売却して$に変換。何でも受付",
         PlacementTool::Fabricator => "鉄板+銅板→回路(#)を製造。2種入力",
         PlacementTool::Belt => "アイテムを自動で運ぶベルトコンベア",
+        PlacementTool::Rail => "台車の線路。駅どうしをつなぐ",
+        PlacementTool::Station => "台車の駅。調べて[i]台車を置く",
+        PlacementTool::Drone => "周囲の故障した機械を自動で修理",
+        PlacementTool::Lift => "別の階の同じ位置のリフトへ運ぶ。調べて[m]で向き",
+        PlacementTool::Filter => "区画の汚染をよく吸う浄化フィルタ",
+        PlacementTool::Greenery => "区画の汚染を少し吸う安い緑地",
         PlacementTool::Delete => "設置済みの機械やベルトを撤去します",
     }
 }
@@ -743,6 +1682,12 @@ fn tool_color(tool: &PlacementTool) -> Color {
         PlacementTool::Exporter => Color::Green,
         PlacementTool::Fabricator => Color::LightBlue,
         PlacementTool::Belt => Color::White,
+        PlacementTool::Rail => Color::Gray,
+        PlacementTool::Station => Color::LightGreen,
+        PlacementTool::Drone => Color::LightMagenta,
+        PlacementTool::Lift => Color::LightBlue,
+        PlacementTool::Filter => Color::LightCyan,
+        PlacementTool::Greenery => Color::Green,
         PlacementTool::Delete => Color::Red,
     }
 }
@@ -761,13 +1706,44 @@ fn render_tool_panel(
         (PlacementTool::Exporter, "Exporter", "$15".into(), SELECT_EXPORTER),
         (PlacementTool::Fabricator, "Fabricator", "$75".into(), SELECT_FABRICATOR),
         (PlacementTool::Belt, "Belt", "$2".into(), SELECT_BELT),
+        (PlacementTool::Rail, "Rail", "$3".into(), SELECT_RAIL),
+        (PlacementTool::Station, "Station", "$20".into(), SELECT_STATION),
+        (PlacementTool::Drone, "Drone", "$40".into(), SELECT_DRONE),
+        (PlacementTool::Lift, "Lift", "$35".into(), SELECT_LIFT),
+        (PlacementTool::Filter, "Filter", "$30".into(), SELECT_FILTER),
+        (PlacementTool::Greenery, "Greenery", "$5".into(), SELECT_GREENERY),
         (PlacementTool::Delete, "Delete", "---".into(), SELECT_DELETE),
     ];
 
-    let mut cl = ClickableList::new();
-
-    // Tool selection rows (all clickable)
-    for (tool, label, cost, action_id) in &tools {
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(Color::DarkGray))
+        .title(" ツール ");
+    let inner = block.inner(area);
+    f.render_widget(block, area);
+
+    // Tool buttons (all clickable; locked ones log why) in a grid at the
+    // top — two columns when there is room, so the rest of the panel
+    // stays visible on narrow screens.
+    let cols = if inner.width >= 32 { 2 } else { 1 };
+    let rows = tools.len().div_ceil(cols) as u16;
+    let chunks = Layout::default()
+        .direction(LayoutDir::Vertical)
+        .constraints([Constraint::Length(rows), Constraint::Min(0)])
+        .split(inner);
+    let (tool_area, rest_area) = (chunks[0], chunks[1]);
+    let mut toolbar = ClickableGrid::new(cols, rows as usize, 0, inner.width / cols as u16)
+        .align(Alignment::Left);
+    for (i, (tool, label, cost, action_id)) in tools.iter().enumerate() {
+        let (col, row) = (i % cols, i / cols);
+        if !tool_unlocked(state, tool) {
+            toolbar = toolbar.button_with_id(col, row, Line::from(vec![
+                Span::styled("   ", Style::default()),
+                Span::styled(format!("{:<10}", label), Style::default().fg(Color::DarkGray)),
+                Span::styled("未解放", Style::default().fg(Color::DarkGray)),
+            ]), *action_id);
+            continue;
+        }
         let is_selected = std::mem::discriminant(&state.tool) == std::mem::discriminant(tool);
         let color = tool_color(tool);
 
@@ -788,16 +1764,27 @@ fn render_tool_panel(
             Style::default().fg(Color::DarkGray)
         };
 
-        cl.push_clickable(Line::from(vec![
+        toolbar = toolbar.button_with_id(col, row, Line::from(vec![
             Span::styled(format!(" {} ", marker), marker_style),
             Span::styled(format!("{:<10}", label), label_style),
-            Span::styled(format!("{:<6}", cost), label_style),
+            Span::styled(cost.clone(), label_style),
         ]), *action_id);
     }
+    let mut cs = click_state.borrow_mut();
+    toolbar.render(f, tool_area, Block::default(), &mut cs);
+
+    let mut cl = ClickableList::new();
 
     // Description of selected tool (not clickable)
+    // キャンペーン中は区切りの空行の代わりに挑戦中の目標を出す
     let desc = tool_description(&state.tool);
-    cl.push(Line::from(""));
+    match current_milestone(state) {
+        Some(m) => cl.push(Line::from(Span::styled(
+            format!(" 目標: {}{}", m.description(), milestone_progress(state, m)),
+            Style::default().fg(Color::LightGreen).add_modifier(Modifier::BOLD),
+        ))),
+        None => cl.push(Line::from("")),
+    }
     cl.push(Line::from(Span::styled(
         format!(" {}", desc),
         Style::default()
@@ -815,12 +1802,93 @@ fn render_tool_panel(
         )), TOGGLE_MINER_MODE);
     }
 
-    let block = Block::default()
-        .borders(Borders::ALL)
-        .border_style(Style::default().fg(Color::DarkGray))
-        .title(" ツール ");
-    let mut cs = click_state.borrow_mut();
-    cl.render(f, area, block, &mut cs, false, 0);
+    // 階の移動と増築
+    let floors = state.floors.len();
+    cl.push(Line::from(Span::styled(
+        format!(" 階: {}F / {}F", state.floor + 1, floors),
+        Style::default().fg(Color::LightBlue).add_modifier(Modifier::BOLD),
+    )));
+    if state.floor + 1 < floors {
+        cl.push_clickable(Line::from(Span::styled(
+            " ▶上の階へ []]",
+            Style::default().fg(Color::LightBlue),
+        )), FLOOR_UP);
+    }
+    if state.floor > 0 {
+        cl.push_clickable(Line::from(Span::styled(
+            " ▶下の階へ [[]",
+            Style::default().fg(Color::LightBlue),
+        )), FLOOR_DOWN);
+    }
+    match build_floor_blocker(state) {
+        None => cl.push_clickable(Line::from(Span::styled(
+            format!(" ▶増築 ${} [^]", FLOOR_COST),
+            Style::default().fg(Color::LightBlue).add_modifier(Modifier::BOLD),
+        )), BUILD_FLOOR),
+        Some(reason) => cl.push_clickable(Line::from(Span::styled(
+            format!(" 増築 ${}: {}", FLOOR_COST, reason),
+            Style::default().fg(Color::DarkGray),
+        )), BUILD_FLOOR),
+    }
+
+    // 流れ調査の切替 (狭い画面では Stats が無いので要約もここに出す)
+    match state.flow_item {
+        Some(item) => {
+            let report = flow_report(state, item);
+            cl.push_clickable(Line::from(Span::styled(
+                format!(" ▶流れ: {}{} [f]", item.symbol(), item.name()),
+                Style::default().fg(item.color()).add_modifier(Modifier::BOLD),
+            )), CYCLE_FLOW_ITEM);
+            cl.push(Line::from(Span::styled(
+                format!(
+                    "  供給{:.1} 消費{:.1}/分 ({}台)",
+                    report.supply_per_min,
+                    report.demand_per_min,
+                    report.sinks.iter().filter(|s| s.per_min > 0.0).count()
+                ),
+                Style::default().fg(Color::Magenta),
+            )));
+        }
+        None => {
+            cl.push_clickable(Line::from(Span::styled(
+                " ▶流れ調査 [f]",
+                Style::default().fg(Color::DarkGray),
+            )), CYCLE_FLOW_ITEM);
+        }
+    }
+
+    // 汚染マップ: 表示中はカーソルの区画の汚れ具合も出す
+    if state.show_pollution {
+        let p = pollution_at(state, state.cursor_x, state.cursor_y);
+        let level = PollutionLevel::of(p);
+        cl.push_clickable(Line::from(Span::styled(
+            format!(" ▶汚染: {} {} 速さ{}% [z]", p, level.name(), level.speed_pct()),
+            Style::default().fg(pollution_color(level).unwrap_or(Color::Green)).add_modifier(Modifier::BOLD),
+        )), TOGGLE_POLLUTION);
+    } else {
+        cl.push_clickable(Line::from(Span::styled(
+            " ▶汚染マップ [z]",
+            Style::default().fg(Color::DarkGray),
+        )), TOGGLE_POLLUTION);
+    }
+    cl.push_clickable(Line::from(Span::styled(
+        " ▶出荷ダッシュボード [g]",
+        Style::default().fg(Color::DarkGray),
+    )), TOGGLE_DASHBOARD);
+    cl.push_clickable(Line::from(Span::styled(
+        " ▶統計グラフ [s]",
+        Style::default().fg(Color::DarkGray),
+    )), TOGGLE_STATS);
+    cl.push_clickable(Line::from(Span::styled(
+        " ▶レシピ帳 [?]",
+        Style::default().fg(Color::DarkGray),
+    )), TOGGLE_RECIPES);
+    cl.push_clickable(Line::from(Span::styled(
+        " ▶調べる (フィルタ/優先度) [i]",
+        Style::default().fg(Color::DarkGray),
+    )), TOGGLE_INSPECT);
+
+    cl.render(f, rest_area, Block::default(), &mut cs, false, 0);
 }
 
 
@@ -848,6 +1916,12 @@ mod tests {
         assert_eq!(after, "▒ ");
     }
 
+    #[test]
+    fn スパークラインは最大値基準で段階化し0は空白にする() {
+        assert_eq!(sparkline(&[0.0, 1.0, 4.0, 8.0], 8.0), " ▂▅█");
+        assert_eq!(sparkline(&[0.0, 0.0], 0.0), "  ");
+    }
+
     #[test]
     fn 待機ベルトの表示は常にセル幅2文字() {
         for x in 0..8 {
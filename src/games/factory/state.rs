@@ -1,6 +1,6 @@
 //! Tiny Factory game state.
 
-use super::grid::{Cell, GRID_H, GRID_W, VIEW_H, VIEW_W};
+use super::grid::{Cart, Cell, ItemKind, MachineKind, GRID_H, GRID_W, REGIONS_H, REGIONS_W, VIEW_H, VIEW_W};
 
 /// What the player is placing.
 #[derive(Clone, Debug, PartialEq)]
@@ -12,9 +12,169 @@ pub enum PlacementTool {
     Exporter,
     Fabricator,
     Belt, // uses current belt direction
+    Rail,
+    Station,
+    Drone,
+    Lift,
+    Filter,
+    Greenery,
     Delete,
 }
 
+/// 遊び方。ゲームに入ったときに選ぶ。
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum FactoryMode {
+    /// 目標を順に達成して機械を解放していく。
+    Campaign,
+    /// 最初から全ての機械が使え、資金も多い。
+    Sandbox,
+}
+
+impl FactoryMode {
+    pub const ALL: [FactoryMode; 2] = [FactoryMode::Campaign, FactoryMode::Sandbox];
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            FactoryMode::Campaign => "キャンペーン",
+            FactoryMode::Sandbox => "サンドボックス",
+        }
+    }
+
+    pub fn description(&self) -> &'static str {
+        match self {
+            FactoryMode::Campaign => "目標を達成して機械を解放していく",
+            FactoryMode::Sandbox => "全機械を解放済み・資金たっぷり",
+        }
+    }
+}
+
+/// キャンペーンの目標。上から順に 1 つずつ挑戦する。
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Milestone {
+    /// 初めて板 (鉄板か銅板) を作る。
+    FirstPlate,
+    /// 歯車を一定数作る。
+    AutomateGears,
+    /// 出荷ペースが毎分 10 個に達する。
+    Export10PerMin,
+}
+
+impl Milestone {
+    pub const ALL: [Milestone; 3] = [
+        Milestone::FirstPlate,
+        Milestone::AutomateGears,
+        Milestone::Export10PerMin,
+    ];
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            Milestone::FirstPlate => "はじめての板",
+            Milestone::AutomateGears => "歯車の自動化",
+            Milestone::Export10PerMin => "毎分10出荷",
+        }
+    }
+
+    pub fn description(&self) -> &'static str {
+        match self {
+            Milestone::FirstPlate => "Smelter で鉱石を板にする",
+            Milestone::AutomateGears => "Assembler で歯車を作る",
+            Milestone::Export10PerMin => "出荷ペース 10個/分 に到達",
+        }
+    }
+
+    /// 達成すると使えるようになる機械。
+    pub fn unlocks(&self) -> Option<MachineKind> {
+        match self {
+            Milestone::FirstPlate => Some(MachineKind::Assembler),
+            Milestone::AutomateGears => Some(MachineKind::Fabricator),
+            Milestone::Export10PerMin => None,
+        }
+    }
+
+    /// 達成報酬 ($)。
+    pub fn reward(&self) -> u64 {
+        match self {
+            Milestone::FirstPlate => 40,
+            Milestone::AutomateGears => 80,
+            Milestone::Export10PerMin => 150,
+        }
+    }
+}
+
+/// Factory 固有の実績。自動化の節目ごとに 1 回だけ解除される。
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum FactoryAchievement {
+    /// 初めて歯車か回路 (2 段以上の加工品) を出荷した。
+    FirstFullChain,
+    /// 出荷ペースが毎分 100 個に達した。
+    Throughput100,
+    /// 停滞なしで 10 分間稼働し続けた。
+    ZeroStall10Min,
+}
+
+impl FactoryAchievement {
+    pub const ALL: [FactoryAchievement; 3] = [
+        FactoryAchievement::FirstFullChain,
+        FactoryAchievement::Throughput100,
+        FactoryAchievement::ZeroStall10Min,
+    ];
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            FactoryAchievement::FirstFullChain => "はじめての完成品",
+            FactoryAchievement::Throughput100 => "毎分100個",
+            FactoryAchievement::ZeroStall10Min => "無停滞10分",
+        }
+    }
+
+    pub fn description(&self) -> &'static str {
+        match self {
+            FactoryAchievement::FirstFullChain => "歯車か回路を出荷する",
+            FactoryAchievement::Throughput100 => "出荷ペース 100個/分 に到達",
+            FactoryAchievement::ZeroStall10Min => "出口詰まりなしで10分稼働",
+        }
+    }
+}
+
+/// 統計画面の 1 区間分の集計。`logic::tick_n` で区間ごとに締める。
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct StatsSample {
+    /// `ItemKind::ALL` 順の生産数。
+    pub produced: [u32; 6],
+    /// `ItemKind::ALL` 順の消費数 (出荷も含む)。
+    pub consumed: [u32; 6],
+    /// 出荷数。
+    pub exported: u32,
+    /// `MachineKind::ALL` 順の、稼働していた機械 tick の合計。
+    pub active_ticks: [u32; 5],
+    /// `MachineKind::ALL` 順の、設置されていた機械 tick の合計。
+    pub machine_ticks: [u32; 5],
+}
+
+/// 工場の 1 階分の盤面と、その階にいたときの視点。
+///
+/// 今いる階の盤面は [`FactoryState::grid`] / [`FactoryState::carts`] に出して
+/// あるので、`floors` のその位置には中身のない抜け殻が入っている。
+#[derive(Clone, Debug, Default)]
+pub struct FactoryFloor {
+    pub grid: Vec<Vec<Cell>>,
+    pub carts: Vec<Cart>,
+    pub pollution: Vec<Vec<u32>>,
+    pub cursor: (usize, usize),
+    pub viewport: (usize, usize),
+}
+
+impl FactoryFloor {
+    /// 何も置いていない新しい階。
+    pub fn empty() -> Self {
+        Self {
+            grid: vec![vec![Cell::Empty; GRID_W]; GRID_H],
+            pollution: vec![vec![0; REGIONS_W]; REGIONS_H],
+            ..Self::default()
+        }
+    }
+}
+
 /// Factory game state.
 pub struct FactoryState {
     /// 2D grid [y][x].
@@ -51,6 +211,70 @@ pub struct FactoryState {
     /// Viewport top-left corner (scroll offset).
     pub viewport_x: usize,
     pub viewport_y: usize,
+    /// 歯車・回路の出荷数 (「はじめての完成品」実績の判定用)。
+    pub finished_goods_exported: u64,
+    /// 出口詰まりの機械が 1 台もない状態が続いている tick 数。
+    /// Exporter が無い間は稼働とみなさず 0 のまま。
+    pub stall_free_ticks: u64,
+    /// 解除済みの実績 (解除順)。
+    pub achievements: Vec<FactoryAchievement>,
+    /// 直近に解除した実績のトースト表示と残り tick 数。
+    pub achievement_toast: Option<(FactoryAchievement, u32)>,
+    /// 建設メニュー (空きマスのポップアップ) を開いているマス。
+    /// ツールバーでツールを選ばずに、その場で機械を選んで置ける。
+    pub build_menu: Option<(usize, usize)>,
+    /// ドラッグでベルトを敷いている最中の、最後に敷いたマス。
+    /// 次のドラッグ step はここから指の位置まで L 字に敷く。
+    pub belt_stroke: Option<(usize, usize)>,
+    /// 直近に生産されたアイテム (tick, 種類)。流れ調査の供給ペース用。
+    pub recent_produced: Vec<(u64, ItemKind)>,
+    /// 直近に機械が消費したアイテム (tick, 機械のアンカー, 種類)。
+    /// 流れ調査で「どの機械がどれだけ食べているか」を出す。
+    pub recent_consumed: Vec<(u64, (usize, usize), ItemKind)>,
+    /// 流れ調査で追跡中のアイテム。消費している機械をグリッド上で強調する。
+    pub flow_item: Option<ItemKind>,
+    /// 直近 5 分の出荷 (tick, Exporter のアンカー)。ダッシュボードのグラフ用。
+    pub export_history: Vec<(u64, (usize, usize))>,
+    /// 出荷ペースの目標 (個/分)。ダッシュボードで不足分を強調する。
+    pub export_target_per_min: u32,
+    /// 出荷ダッシュボードを開いているか。
+    pub show_dashboard: bool,
+    /// 調べているマス (機械のアンカーかベルト)。受け入れフィルタと
+    /// 入力の優先度を設定するパネルを出す。
+    pub inspect: Option<(usize, usize)>,
+    /// 調べている機械の、受け入れリストを編集中の辺。
+    pub inspect_side: super::grid::Direction,
+    /// 集計中の統計区間。
+    pub stats_current: StatsSample,
+    /// 締めた統計区間 (古い順、直近 5 分ぶん)。
+    pub stats_history: Vec<StatsSample>,
+    /// 統計画面を開いているか。
+    pub show_stats: bool,
+    /// 選んだ遊び方。`None` の間はモード選択画面を出す。
+    pub mode: Option<FactoryMode>,
+    /// キャンペーンで達成済みの目標の数 (= 挑戦中の目標の `Milestone::ALL` 内の位置)。
+    pub milestones_done: usize,
+    /// 線路上の台車。
+    pub carts: Vec<Cart>,
+    /// 次に置く駅の番号。
+    pub next_station_id: u32,
+    /// 故障判定用の乱数 (xorshift64)。固定の種から始めるので同じ工場なら
+    /// 同じ順に壊れる。
+    pub rng_state: u64,
+    /// これまでに起きた故障の回数。
+    pub breakdowns: u64,
+    /// 階ごとの盤面 (1F から順)。今いる階 (`floor`) の分は抜け殻。
+    pub floors: Vec<FactoryFloor>,
+    /// 今いる階 (`floors` の添字、0 = 1F)。
+    pub floor: usize,
+    /// 今いる階の区画ごとの汚染 [ry][rx] (区画は `REGION_SIZE` マス四方)。
+    pub pollution: Vec<Vec<u32>>,
+    /// グリッドに汚染マップを重ねて表示するか。
+    pub show_pollution: bool,
+    /// レシピ帳を開いているか。開いている間は英字キーが絞り込みの入力になる。
+    pub show_recipes: bool,
+    /// レシピ帳の絞り込み (小文字の英字と空白)。
+    pub recipe_filter: String,
 }
 
 impl FactoryState {
@@ -74,9 +298,42 @@ impl FactoryState {
             recent_export_ticks: Vec::new(),
             viewport_x: 0,
             viewport_y: 0,
+            finished_goods_exported: 0,
+            stall_free_ticks: 0,
+            achievements: Vec::new(),
+            achievement_toast: None,
+            build_menu: None,
+            belt_stroke: None,
+            recent_produced: Vec::new(),
+            recent_consumed: Vec::new(),
+            flow_item: None,
+            export_history: Vec::new(),
+            export_target_per_min: 60,
+            show_dashboard: false,
+            inspect: None,
+            inspect_side: super::grid::Direction::Up,
+            stats_current: StatsSample::default(),
+            stats_history: Vec::new(),
+            show_stats: false,
+            mode: None,
+            milestones_done: 0,
+            carts: Vec::new(),
+            next_station_id: 1,
+            rng_state: 0x2545_F491_4F6C_DD1D,
+            breakdowns: 0,
+            floors: vec![FactoryFloor::default()],
+            floor: 0,
+            pollution: vec![vec![0; REGIONS_W]; REGIONS_H],
+            show_pollution: false,
+            show_recipes: false,
+            recipe_filter: String::new(),
         }
     }
 
+    pub fn has_achievement(&self, a: FactoryAchievement) -> bool {
+        self.achievements.contains(&a)
+    }
+
     pub fn add_log(&mut self, text: &str) {
         self.log.push(text.to_string());
         if self.log.len() > 30 {
@@ -0,0 +1,326 @@
+//! Long-run fuzz harness (test-only).
+//!
+//! 各ゲームにランダムな `InputEvent` 列と tick バッチを流し込み、ステップごとに
+//! 不変条件を検査する。ステートマシン共通のバグ — panic、通貨の負値/NaN、
+//! HP の範囲外、セーブ往復で値が変わる — をまとめて捕まえるのが目的。
+//!
+//! - 乱数はシード固定の xorshift。失敗時はシードとステップ番号を表示するので
+//!   そのまま再現できる。
+//! - キーは印字可能 ASCII 全域、クリック ID は `0..(そのゲームの ID の上限)`
+//!   から選ぶ。上限は `actions::ALL_ACTIONS` を持つゲームでは [`end_of`] で
+//!   引き、それ以外はそのゲームで最後の ID 区間の終端を渡す。
+//!   そのゲームで意味のない入力は「何も起きない」ことが期待値。
+//! - tick は `1..=MAX_TICK_BATCH` のバッチで進める (放置中の長時間 tick を模す)。
+//! - セーブを持つゲームは、最後に extract → JSON → apply → extract の
+//!   往復で JSON が一致することを確かめる。
+
+use crate::action::end_of;
+use crate::games::{Game, GameChoice};
+use crate::input::{ClickScope, InputEvent};
+
+/// 1 ステップで進める最大 tick 数。
+const MAX_TICK_BATCH: u64 = 50;
+
+/// 1 シードあたりのステップ数。
+const STEPS: usize = 400;
+
+/// ゲームごとのシード数。シード数 × ステップ数で数千イベントになる。
+const SEEDS: u64 = 12;
+
+struct Rng(u64);
+
+impl Rng {
+    fn new(seed: u64) -> Self {
+        // 0 は xorshift の不動点なので避ける
+        Self(seed.wrapping_mul(0x9E37_79B9_7F4A_7C15) | 1)
+    }
+
+    fn next(&mut self) -> u64 {
+        self.0 ^= self.0 << 13;
+        self.0 ^= self.0 >> 7;
+        self.0 ^= self.0 << 17;
+        self.0
+    }
+
+    fn below(&mut self, n: u64) -> u64 {
+        self.next() % n
+    }
+}
+
+#[derive(Debug)]
+enum Step {
+    Input(InputEvent),
+    Tick(u32),
+}
+
+/// キー 4 : クリック 4 : tick 1 の重みで 1 ステップ選ぶ。
+/// クリックの 1/4 は長押し・ダブルタップにする。
+/// クリック ID は `0..click_ids` から選ぶ。
+fn random_step(rng: &mut Rng, choice: GameChoice, click_ids: u16) -> Step {
+    match rng.below(9) {
+        0..=3 => Step::Input(InputEvent::Key((b' ' + rng.below(95) as u8) as char)),
+        4..=7 => {
+            let scope = ClickScope::Game(choice);
+            let id = rng.below(click_ids as u64) as u16;
+            Step::Input(match rng.below(8) {
+                0 => InputEvent::LongPress(scope, id),
+                1 => InputEvent::DoubleTap(scope, id),
+                _ => InputEvent::Click(scope, id),
+            })
+        }
+        _ => Step::Tick(1 + rng.below(MAX_TICK_BATCH) as u32),
+    }
+}
+
+/// シードごとに新しいゲームを作ってランダム入力を流し込み、各ステップの後で
+/// `check` を、最後に `finish` を呼ぶ。`id_end` はそのゲームの action ID の
+/// 排他的な上限。
+fn fuzz<G: Game>(
+    new: impl Fn() -> G,
+    id_end: u16,
+    check: impl Fn(&G) -> Result<(), String>,
+    finish: impl Fn(&G) -> Result<(), String>,
+) {
+    for seed in 0..SEEDS {
+        let mut game = new();
+        let choice = game.choice();
+        let mut rng = Rng::new(seed);
+        for i in 0..STEPS {
+            let step = random_step(&mut rng, choice.clone(), id_end);
+            match &step {
+                Step::Input(ev) => {
+                    game.handle_input(ev);
+                }
+                Step::Tick(n) => game.tick(*n),
+            }
+            if let Err(msg) = check(&game) {
+                panic!(
+                    "{:?} seed {} step {} ({:?}): {}",
+                    choice, seed, i, step, msg
+                );
+            }
+        }
+        if let Err(msg) = finish(&game) {
+            panic!("{:?} seed {} after {} steps: {}", choice, seed, STEPS, msg);
+        }
+    }
+}
+
+fn ensure(cond: bool, msg: impl FnOnce() -> String) -> Result<(), String> {
+    if cond {
+        Ok(())
+    } else {
+        Err(msg())
+    }
+}
+
+fn ensure_roundtrip((before, after): (String, String)) -> Result<(), String> {
+    ensure(before == after, || {
+        format!(
+            "save → load round-trip changed the state\n before: {}\n  after: {}",
+            before, after
+        )
+    })
+}
+
+fn no_save<G>(_: &G) -> Result<(), String> {
+    Ok(())
+}
+
+#[test]
+fn fuzz_cookie() {
+    use super::cookie::{actions, save, CookieGame};
+    fuzz(
+        CookieGame::new,
+        end_of(actions::ALL_ACTIONS),
+        |g| {
+            let s = &g.state;
+            ensure(s.cookies.is_finite() && s.cookies >= 0.0, || {
+                format!("cookies = {}", s.cookies)
+            })?;
+            ensure(s.cookies_all_time.is_finite(), || {
+                format!("cookies_all_time = {}", s.cookies_all_time)
+            })
+        },
+        |g| ensure_roundtrip(save::roundtrip_json(&g.state)),
+    );
+}
+
+#[test]
+fn fuzz_factory() {
+    use super::factory::grid::{GRID_H, GRID_W};
+    use super::factory::{actions, FactoryGame};
+    fuzz(
+        FactoryGame::new,
+        end_of(actions::ALL_ACTIONS),
+        |g| {
+            let s = &g.state;
+            ensure(s.cursor_x < GRID_W && s.cursor_y < GRID_H, || {
+                format!("cursor out of grid: ({}, {})", s.cursor_x, s.cursor_y)
+            })
+        },
+        no_save,
+    );
+}
+
+#[test]
+fn fuzz_rpg() {
+    use super::rpg::{actions, RpgGame};
+    fuzz(
+        RpgGame::new,
+        end_of(actions::ALL_ACTIONS),
+        |g| {
+            let s = &g.state;
+            ensure(s.hp <= s.effective_max_hp(), || {
+                format!("hp {} > max {}", s.hp, s.effective_max_hp())
+            })?;
+            ensure(s.mp <= s.max_mp, || {
+                format!("mp {} > max {}", s.mp, s.max_mp)
+            })
+        },
+        no_save,
+    );
+}
+
+#[test]
+fn fuzz_abyss() {
+    use super::abyss::actions::ENHANCE_EQUIPMENT_BASE;
+    use super::abyss::state::EQUIPMENT_COUNT;
+    use super::abyss::{save, AbyssGame};
+    fuzz(
+        AbyssGame::new,
+        ENHANCE_EQUIPMENT_BASE + EQUIPMENT_COUNT as u16,
+        |g| {
+            let s = &g.state;
+            ensure(s.hero_hp <= s.hero_max_hp(), || {
+                format!("hero hp {} > max {}", s.hero_hp, s.hero_max_hp())
+            })?;
+            ensure(s.current_enemy.hp <= s.current_enemy.max_hp, || {
+                format!(
+                    "enemy hp {} > max {}",
+                    s.current_enemy.hp, s.current_enemy.max_hp
+                )
+            })
+        },
+        |g| ensure_roundtrip(save::roundtrip_json(&g.state)),
+    );
+}
+
+#[test]
+fn fuzz_godfield() {
+    use super::godfield::actions::TARGET_BASE;
+    use super::godfield::GodFieldGame;
+    fuzz(
+        GodFieldGame::new,
+        TARGET_BASE + 16, // mod.rs が受け付ける対象プレイヤーの範囲
+        |g| {
+            for p in &g.state.players {
+                ensure(p.hp <= p.max_hp, || {
+                    format!("{} hp {} > max {}", p.name, p.hp, p.max_hp)
+                })?;
+                ensure(p.alive == (p.hp > 0), || {
+                    format!("{} alive={} with hp {}", p.name, p.alive, p.hp)
+                })?;
+            }
+            Ok(())
+        },
+        no_save,
+    );
+}
+
+#[test]
+fn fuzz_metropolis() {
+    use super::metropolis::state::{VIEW_H, VIEW_W};
+    use super::metropolis::{save, MetropolisGame, ACT_GRID_CELL_BASE};
+    fuzz(
+        MetropolisGame::new,
+        ACT_GRID_CELL_BASE + (VIEW_W * VIEW_H) as u16,
+        |_| Ok(()),
+        |g| ensure_roundtrip(save::roundtrip_json(&g.state)),
+    );
+}
+
+#[test]
+fn fuzz_drill() {
+    use super::drill::actions::BUY_UPGRADE_BASE;
+    use super::drill::state::UPGRADE_COUNT;
+    use super::drill::{logic, save, DrillGame};
+    fuzz(
+        DrillGame::new,
+        BUY_UPGRADE_BASE + UPGRADE_COUNT as u16,
+        |g| {
+            let s = &g.state;
+            ensure(s.depth.is_finite() && s.depth >= 0.0, || {
+                format!("depth = {}", s.depth)
+            })?;
+            ensure(s.cargo_used() <= logic::cargo_capacity(s), || {
+                format!(
+                    "cargo {} > capacity {}",
+                    s.cargo_used(),
+                    logic::cargo_capacity(s)
+                )
+            })
+        },
+        |g| ensure_roundtrip(save::roundtrip_json(&g.state)),
+    );
+}
+
+#[test]
+fn fuzz_port() {
+    use super::port::actions::SELECT_SHIP_BASE;
+    use super::port::state::{Good, Port, MAX_SHIPS};
+    use super::port::{logic, save, PortGame};
+    fuzz(
+        PortGame::new,
+        SELECT_SHIP_BASE + MAX_SHIPS as u16,
+        |g| {
+            let s = &g.state;
+            ensure(s.selected_ship < s.ships.len(), || {
+                format!("selected ship {} of {}", s.selected_ship, s.ships.len())
+            })?;
+            for p in Port::ALL {
+                for good in Good::ALL {
+                    let price = s.price(p, good);
+                    let base = logic::base_price(p, good);
+                    ensure(
+                        price.is_finite()
+                            && price >= base * 0.3 - 1e-9
+                            && price <= base * 3.0 + 1e-9,
+                        || format!("{} {} price {}", p.name(), good.name(), price),
+                    )?;
+                }
+            }
+            Ok(())
+        },
+        |g| ensure_roundtrip(save::roundtrip_json(&g.state)),
+    );
+}
+
+#[test]
+fn fuzz_defense() {
+    use super::defense::actions::GRID_CLICK_BASE;
+    use super::defense::state::{GRID_H, GRID_W};
+    use super::defense::DefenseGame;
+    fuzz(
+        DefenseGame::new,
+        GRID_CLICK_BASE + (GRID_W * GRID_H) as u16,
+        |g| {
+            let s = &g.state;
+            ensure(s.lives <= s.difficulty.starting_lives(), || {
+                format!("lives {}", s.lives)
+            })?;
+            for t in &s.towers {
+                ensure(!s.is_path(t.x, t.y), || {
+                    format!("tower on path at ({}, {})", t.x, t.y)
+                })?;
+            }
+            for e in &s.enemies {
+                ensure(e.hp <= e.max_hp, || {
+                    format!("enemy hp {} > max {}", e.hp, e.max_hp)
+                })?;
+            }
+            Ok(())
+        },
+        no_save,
+    );
+}
@@ -390,7 +390,10 @@ pub fn confirm_weapons(state: &mut GfState) -> bool {
 
 /// Human attacks `target_idx` with currently selected weapons.
 pub fn human_attack(state: &mut GfState, target_idx: usize) -> bool {
-    if !state.players[target_idx].alive || target_idx == state.human_idx() {
+    if target_idx >= state.players.len()
+        || !state.players[target_idx].alive
+        || target_idx == state.human_idx()
+    {
         return false;
     }
     let weapons = state.selected_weapons.clone();
@@ -23,7 +23,7 @@ use actions::*;
 use state::{Card, CardKind, GfState, Phase};
 
 pub struct GodFieldGame {
-    state: GfState,
+    pub(crate) state: GfState,
 }
 
 impl GodFieldGame {
@@ -55,6 +55,7 @@ impl Game for GodFieldGame {
         match event {
             InputEvent::Key(ch) => handle_key(&mut self.state, *ch),
             InputEvent::Click(_, id) => handle_click(&mut self.state, *id),
+            InputEvent::LongPress(..) | InputEvent::DoubleTap(..) | InputEvent::Drag(..) => false,
         }
     }
 
@@ -315,6 +316,18 @@ mod tests {
         assert_ne!(g.state.turn, 0);
     }
 
+    #[test]
+    fn out_of_range_target_is_ignored() {
+        let mut g = make_game();
+        g.state.players[0].hand = vec![Card::Sword];
+        g.handle_input(&click(ACTION_ATTACK));
+        g.handle_input(&click(HAND_BASE));
+        g.handle_input(&click(ACTION_CONFIRM_WEAPONS));
+        // 4 人卓に存在しない 12 番目のプレイヤー
+        assert!(!g.handle_input(&click(TARGET_BASE + 11)));
+        assert_eq!(g.state.phase, Phase::PlayerSelectTarget);
+    }
+
     #[test]
     fn attack_disabled_without_weapons() {
         let mut g = make_game();
@@ -30,6 +30,7 @@ use ratzilla::ratatui::Frame;
 
 use crate::games::{Game, GameChoice};
 use crate::input::{ClickState, InputEvent};
+use crate::scores::Score;
 use crate::sound;
 
 use crate::widgets::ClickableGrid;
@@ -317,6 +318,7 @@ impl Game for MetropolisGame {
                 'K' => ACT_PANEL_SCROLL_UP,
                 _ => return false,
             },
+            InputEvent::LongPress(..) | InputEvent::DoubleTap(..) | InputEvent::Drag(..) => return false,
         };
 
         match action_id {
@@ -435,10 +437,7 @@ impl Game for MetropolisGame {
         // 30 秒間隔 (= 300 ticks) は cookie/save と揃えている。
         self.save_countdown = self.save_countdown.saturating_sub(delta_ticks);
         if self.save_countdown == 0 {
-            // best-effort autosave: 失敗時の戻り値は無視する (warn は save_game 内で出力済)。
-            #[cfg(target_arch = "wasm32")]
-            let _ = save::save_game(&self.state);
-            self.save_countdown = save::AUTOSAVE_INTERVAL;
+            self.save();
         }
 
         if let (Some(s), Some(e)) = (t_start, perf_now_ms()) {
@@ -446,6 +445,13 @@ impl Game for MetropolisGame {
         }
     }
 
+    fn save(&mut self) {
+        // best-effort: 失敗時の戻り値は無視する (warn は save_game 内で出力済)。
+        #[cfg(target_arch = "wasm32")]
+        let _ = save::save_game(&self.state);
+        self.save_countdown = save::AUTOSAVE_INTERVAL;
+    }
+
     fn render(&self, f: &mut Frame, area: Rect, click_state: &Rc<RefCell<ClickState>>) {
         let t_start = perf_now_ms();
         render::render(&self.state, f, area, click_state);
@@ -480,6 +486,24 @@ impl Game for MetropolisGame {
             }
         }
     }
+
+    fn score(&self) -> Option<Score> {
+        Some(Score::new(self.state.population() as u64, "人口", "人"))
+    }
+
+    #[cfg(any(feature = "debug-console", debug_assertions, test))]
+    fn debug_command(&mut self, args: &[&str]) -> Result<String, String> {
+        use crate::debug_console::{parse_count, unknown};
+        match args {
+            ["help"] => Ok("give cash <n>".into()),
+            ["give", "cash", n] => {
+                let n = parse_count(n)?.min(i64::MAX as u64) as i64;
+                self.state.cash = self.state.cash.saturating_add(n);
+                Ok(format!("資金 +{}", n))
+            }
+            _ => Err(unknown(args)),
+        }
+    }
 }
 
 /// タブ切替時にパネルの縦スクロールを先頭にリセットする。
@@ -61,9 +61,14 @@ const SAVE_VERSION: u32 = 8;
 const MIN_COMPATIBLE_VERSION: u32 = 4;
 
 /// localStorage のキー。
-#[cfg(target_arch = "wasm32")]
 const STORAGE_KEY: &str = "metropolis_save";
 
+/// アクティブなプロファイル用に名前空間化した保存キー。
+/// 設定画面のリセットはこのキーの中身をゴミ箱 ([`crate::storage::move_to_trash`]) へ移す。
+pub fn storage_key() -> String {
+    crate::profile::scoped_key(STORAGE_KEY)
+}
+
 /// オートセーブ間隔 (tick数)。10 ticks/sec × 30秒 = 300 ticks。
 pub const AUTOSAVE_INTERVAL: u32 = 300;
 
@@ -737,8 +742,8 @@ pub(super) fn apply_save(state: &mut City, save: &GameSave) {
 
 /// localStorage を取得する。WASM 環境のみ。
 #[cfg(target_arch = "wasm32")]
-fn get_storage() -> Option<web_sys::Storage> {
-    web_sys::window()?.local_storage().ok()?
+fn get_storage() -> Option<crate::storage::Store> {
+    crate::storage::get()
 }
 
 /// f64 の wall-clock ms を u64 に安全にキャストする。
@@ -779,7 +784,7 @@ pub fn save_game(state: &City) -> bool {
             return false;
         }
     };
-    if let Err(e) = storage.set_item(STORAGE_KEY, &json) {
+    if let Err(e) = storage.set_item(&storage_key(), &json) {
         web_sys::console::warn_1(
             &format!("Idle Metropolis: localStorage への書き込みに失敗: {e:?}").into(),
         );
@@ -796,7 +801,7 @@ pub fn load_game(state: &mut City) -> bool {
         Some(s) => s,
         None => return false,
     };
-    let json = match storage.get_item(STORAGE_KEY) {
+    let json = match storage.get_item(&storage_key()) {
         Ok(Some(j)) => j,
         _ => return false,
     };
@@ -809,7 +814,7 @@ pub fn load_game(state: &mut City) -> bool {
                 )
                 .into(),
             );
-            let _ = storage.remove_item(STORAGE_KEY);
+            let _ = storage.remove_item(&storage_key());
             return false;
         }
     };
@@ -821,7 +826,7 @@ pub fn load_game(state: &mut City) -> bool {
             )
             .into(),
         );
-        let _ = storage.remove_item(STORAGE_KEY);
+        let _ = storage.remove_item(&storage_key());
         return false;
     }
     if save_data.version < SAVE_VERSION {
@@ -895,18 +900,45 @@ pub fn apply_offline_bonus_with_persist(state: &mut City, last_ms: u64) -> Optio
     Some(bonus)
 }
 
-/// セーブデータを削除する。設定画面の「データをリセット」から呼ばれる。
-#[cfg(target_arch = "wasm32")]
-pub fn delete_save() {
-    if let Some(storage) = get_storage() {
-        let _ = storage.remove_item(STORAGE_KEY);
+/// メインメニュー用の 1 行の進捗要約。セーブが無ければ `None`。
+/// セーブ全体を読み込むので、メニューに入るときに 1 回だけ呼ぶこと。
+pub fn progress_summary() -> Option<String> {
+    #[cfg(target_arch = "wasm32")]
+    {
+        let mut state = City::new();
+        load_game(&mut state).then(|| summarize(&state))
     }
+    #[cfg(not(target_arch = "wasm32"))]
+    None
+}
+
+#[cfg(any(target_arch = "wasm32", test))]
+fn summarize(state: &City) -> String {
+    format!("人口 {} / 資金 ${}", state.population(), state.cash)
+}
+
+/// extract → JSON → apply → extract の前後の JSON を返す (fuzz ハーネスの往復検査用)。
+#[cfg(test)]
+pub(crate) fn roundtrip_json(state: &City) -> (String, String) {
+    let before = serde_json::to_string(&extract_save(state)).unwrap();
+    let loaded: SaveData = serde_json::from_str(&before).unwrap();
+    let mut restored = City::new();
+    apply_save(&mut restored, &loaded.game);
+    let after = serde_json::to_string(&extract_save(&restored)).unwrap();
+    (before, after)
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    #[test]
+    fn menu_summary_shows_population_and_cash() {
+        let mut c = City::new();
+        c.cash = 4321;
+        assert_eq!(summarize(&c), format!("人口 {} / 資金 $4321", c.population()));
+    }
+
     /// 状態を一通りいじって、save → load で完全復元できることを確認する。
     #[test]
     fn extract_apply_roundtrip() {
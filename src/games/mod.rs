@@ -1,67 +1,51 @@
-//! Game trait and game selection logic.
+//! Game implementations and game selection logic.
 
 pub mod abyss;
 pub mod cookie;
+pub mod defense;
+pub mod drill;
 pub mod factory;
+#[cfg(test)]
+mod fuzz;
 pub mod godfield;
 pub mod metropolis;
+pub mod port;
 pub mod rpg;
 
-use std::cell::RefCell;
-use std::rc::Rc;
+pub use engine::game::{Game, GameChoice};
 
-use ratzilla::ratatui::layout::Rect;
-use ratzilla::ratatui::Frame;
-
-use crate::input::{ClickState, InputEvent};
-
-/// Trait that all games implement.
-pub trait Game {
-    /// Identify which game this is.  Used by the dispatcher to validate
-    /// `ClickScope::Game(_)` events match the currently active game.
-    fn choice(&self) -> GameChoice;
-
-    /// Handle an input event. Returns true if the event was consumed.
-    fn handle_input(&mut self, event: &InputEvent) -> bool;
-
-    /// Advance game logic by `delta_ticks` discrete ticks.
-    fn tick(&mut self, delta_ticks: u32);
-
-    /// Render the game into the given area.
-    fn render(&self, f: &mut Frame, area: Rect, click_state: &Rc<RefCell<ClickState>>);
+/// メインメニューに出すセーブ状況。
+#[derive(Clone, Debug, PartialEq)]
+pub enum SaveStatus {
+    /// まだ遊んだことがない。
+    New,
+    /// 続きから遊べる。中身は 1 行の進捗要約。
+    Continue(String),
 }
 
-/// Which game the player has selected (or is choosing).
-#[derive(Clone, Debug, PartialEq)]
-pub enum GameChoice {
-    Cookie,
-    Factory,
-    Rpg,
-    Abyss,
-    Godfield,
-    Metropolis,
+/// メニュー用にセーブ状況を調べる。進捗を残さないゲームは `None`。
+///
+/// セーブ全体の読み込みを伴うので、メニューに入るときに 1 回だけ呼び、
+/// 結果を [`crate::screens::MenuScreen`] に持たせる (毎フレーム呼ばない)。
+pub fn load_save_status(choice: &GameChoice) -> Option<SaveStatus> {
+    let summary = match choice {
+        GameChoice::Cookie => cookie::save::progress_summary(),
+        GameChoice::Abyss => abyss::save::progress_summary(),
+        GameChoice::Metropolis => metropolis::save::progress_summary(),
+        GameChoice::Drill => drill::save::progress_summary(),
+        GameChoice::Port => port::save::progress_summary(),
+        // 本編はセーブしないが、導入を見たかどうかだけは覚えている
+        GameChoice::Rpg => {
+            return (!rpg::save::load_intro_seen()).then_some(SaveStatus::New);
+        }
+        GameChoice::Factory | GameChoice::Godfield | GameChoice::Defense => return None,
+    };
+    Some(summary.map_or(SaveStatus::New, SaveStatus::Continue))
 }
 
-/// Top-level application state.
-pub enum AppState {
-    /// Showing game selection menu.
-    Menu {
-        /// Vertical scroll offset (in visual rows) for the game list,
-        /// so it stays usable when more games exist than fit on screen.
-        scroll: u16,
-        /// 0-based index of the currently highlighted game card.
-        /// Arrow keys move it; Enter/Space (= A button) launches.
-        selected: u8,
-    },
-    /// Showing settings screen (data reset).
-    /// `confirm_reset` is `Some(game)` when a confirmation dialog is shown.
-    Settings {
-        confirm_reset: Option<GameChoice>,
-    },
-    /// Playing a game.
-    Playing {
-        game: Box<dyn Game>,
-    },
+/// [`GameChoice::all`] の順に並べた全ゲームのセーブ状況。
+pub fn load_all_save_status() -> Vec<Option<SaveStatus>> {
+    GameChoice::all().iter().map(load_save_status).collect()
 }
 
 /// Create a game instance from a choice.
@@ -73,5 +57,40 @@ pub fn create_game(choice: &GameChoice) -> Box<dyn Game> {
         GameChoice::Abyss => Box::new(abyss::AbyssGame::new()),
         GameChoice::Godfield => Box::new(godfield::GodFieldGame::new()),
         GameChoice::Metropolis => Box::new(metropolis::MetropolisGame::new()),
+        GameChoice::Drill => Box::new(drill::DrillGame::new()),
+        GameChoice::Port => Box::new(port::PortGame::new()),
+        GameChoice::Defense => Box::new(defense::DefenseGame::new()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn game_choice_id_roundtrip() {
+        for c in GameChoice::all() {
+            assert_eq!(GameChoice::from_id(c.id()), Some(c));
+        }
+        assert_eq!(GameChoice::from_id("career"), None);
+    }
+
+    #[test]
+    fn score_export_covers_progress_games() {
+        for c in GameChoice::all() {
+            let has_score = create_game(&c).score().is_some();
+            // 対戦 / 1 回完結型は比べる数値を持たない
+            let expected = !matches!(c, GameChoice::Godfield | GameChoice::Defense);
+            assert_eq!(has_score, expected, "{:?}", c);
+        }
+    }
+
+    #[test]
+    fn key_repeat_is_opt_in_for_movement_games() {
+        for c in GameChoice::all() {
+            let repeats = create_game(&c).key_repeat().is_some();
+            let expected = matches!(c, GameChoice::Rpg | GameChoice::Factory);
+            assert_eq!(repeats, expected, "{:?}", c);
+        }
     }
 }
@@ -0,0 +1,15 @@
+//! Semantic action IDs for Port Trader click targets.
+
+pub const TAB_MARKET: u16 = 1;
+pub const TAB_FLEET: u16 = 2;
+pub const BUY_SHIP: u16 = 3;
+pub const CYCLE_FROM: u16 = 4;
+pub const CYCLE_TO: u16 = 5;
+pub const CYCLE_GOOD: u16 = 6;
+pub const CLEAR_ROUTE: u16 = 7;
+
+/// 市場タブの港切替: action_id = SELECT_PORT_BASE + Port::index()
+pub const SELECT_PORT_BASE: u16 = 10;
+
+/// 船の選択: action_id = SELECT_SHIP_BASE + 船の index
+pub const SELECT_SHIP_BASE: u16 = 20;
@@ -0,0 +1,424 @@
+//! Port Trader game logic — pure functions, fully testable.
+
+use super::state::{Cargo, Good, Port, PortState, Route, Ship, ShipStatus, MAX_SHIPS, PORT_COUNT};
+
+/// 相場が揺らぐ間隔 (tick)。
+pub const PRICE_UPDATE_TICKS: u64 = 10;
+
+/// 1 回の更新で基準価格へ戻ろうとする割合。
+const PRICE_REVERSION: f64 = 0.05;
+
+/// 1 回の更新で加わるノイズの最大幅 (基準価格比)。
+const PRICE_NOISE: f64 = 0.04;
+
+/// 1 個売買するごとに相場が動く割合。
+pub const PRICE_IMPACT_PER_UNIT: f64 = 0.004;
+
+/// 海図上の距離 1 あたりの航海 tick 数。
+pub const TICKS_PER_DISTANCE: f64 = 30.0;
+
+/// 2 隻目の船の価格。以降 1 隻ごとに倍になる。
+pub const SHIP_BASE_COST: u64 = 400;
+
+/// Advance the world by `n` ticks (10 ticks/sec).
+pub fn tick(state: &mut PortState, n: u32) {
+    for _ in 0..n {
+        tick_once(state);
+    }
+}
+
+fn tick_once(state: &mut PortState) {
+    state.total_ticks += 1;
+    if state.total_ticks.is_multiple_of(PRICE_UPDATE_TICKS) {
+        drift_prices(state);
+    }
+    for i in 0..state.ships.len() {
+        step_ship(state, i);
+    }
+}
+
+fn rng_next(seed: &mut u32) -> u32 {
+    let mut x = *seed;
+    if x == 0 {
+        x = 0xDEAD_BEEF;
+    }
+    x ^= x << 13;
+    x ^= x >> 17;
+    x ^= x << 5;
+    *seed = x;
+    x
+}
+
+/// 港・商品ごとの基準価格。
+pub fn base_price(port: Port, good: Good) -> f64 {
+    good.base_price() * port.price_factor(good)
+}
+
+/// 相場の揺らぎ: 基準価格への回帰 + ランダムノイズ。基準の 0.3〜3 倍に収める。
+fn drift_prices(state: &mut PortState) {
+    for p in Port::ALL {
+        for g in Good::ALL {
+            let base = base_price(p, g);
+            let r = rng_next(&mut state.rng_state) % 2001;
+            let noise = (r as f64 / 1000.0 - 1.0) * PRICE_NOISE * base;
+            let price = &mut state.prices[p.index()][g.index()];
+            *price += (base - *price) * PRICE_REVERSION + noise;
+            *price = price.clamp(base * 0.3, base * 3.0);
+        }
+    }
+}
+
+fn clamp_price(state: &mut PortState, port: Port, good: Good) {
+    let base = base_price(port, good);
+    let price = &mut state.prices[port.index()][good.index()];
+    *price = price.clamp(base * 0.3, base * 3.0);
+}
+
+/// 港間の航海時間 (tick)。
+pub fn travel_ticks(from: Port, to: Port) -> u32 {
+    let (x1, y1) = from.position();
+    let (x2, y2) = to.position();
+    let dist = ((x1 - x2).powi(2) + (y1 - y2).powi(2)).sqrt();
+    (dist * TICKS_PER_DISTANCE).ceil().max(1.0) as u32
+}
+
+/// 航路 1 往復で見込める 1 個あたりの粗利 (現在の相場で計算)。
+pub fn route_margin(state: &PortState, route: &Route) -> f64 {
+    state.price(route.to, route.good) - state.price(route.from, route.good)
+}
+
+/// 次に買う船の価格。上限に達していれば `None`。
+pub fn next_ship_cost(state: &PortState) -> Option<u64> {
+    if state.ships.len() >= MAX_SHIPS {
+        return None;
+    }
+    let owned = state.ships.len().saturating_sub(1) as u32;
+    Some(SHIP_BASE_COST * 2u64.pow(owned))
+}
+
+/// 新しい船を購入する。新造船は横浜に停泊する。gold 不足・上限なら false。
+pub fn buy_ship(state: &mut PortState) -> bool {
+    let Some(cost) = next_ship_cost(state) else {
+        state.add_log(&format!("船団は最大 {} 隻です", MAX_SHIPS));
+        return false;
+    };
+    if state.gold < cost {
+        return false;
+    }
+    state.gold -= cost;
+    let ship = Ship::new(state.ships.len(), Port::Yokohama);
+    state.add_log(&format!("{} を購入 (-{}G)", ship.name, cost));
+    state.ships.push(ship);
+    state.selected_ship = state.ships.len() - 1;
+    true
+}
+
+/// 船の航路を設定する。出発港と到着港が同じ航路は拒否する。
+pub fn set_route(state: &mut PortState, ship: usize, route: Option<Route>) -> bool {
+    if matches!(route, Some(r) if r.from == r.to) {
+        return false;
+    }
+    let Some(s) = state.ships.get_mut(ship) else {
+        return false;
+    };
+    s.route = route;
+    s.waiting = false;
+    true
+}
+
+/// 選択中の船の航路の一部を 1 つ先に進める。未設定なら現在地発の航路を作る。
+pub fn cycle_route(state: &mut PortState, ship: usize, field: RouteField) {
+    let Some(s) = state.ships.get(ship) else {
+        return;
+    };
+    let here = match s.status {
+        ShipStatus::Docked(p) => p,
+        ShipStatus::Sailing { to, .. } => to,
+    };
+    let mut route = s.route.unwrap_or(Route {
+        from: here,
+        to: next_port(here),
+        good: Good::Rice,
+    });
+    match field {
+        RouteField::From => {
+            route.from = next_port(route.from);
+            if route.from == route.to {
+                route.from = next_port(route.from);
+            }
+        }
+        RouteField::To => {
+            route.to = next_port(route.to);
+            if route.to == route.from {
+                route.to = next_port(route.to);
+            }
+        }
+        RouteField::Good => {
+            route.good =
+                Good::from_index((route.good.index() + 1) % Good::ALL.len()).unwrap_or(Good::Rice);
+        }
+    }
+    set_route(state, ship, Some(route));
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum RouteField {
+    From,
+    To,
+    Good,
+}
+
+pub fn next_port(p: Port) -> Port {
+    Port::from_index((p.index() + 1) % PORT_COUNT).unwrap_or(p)
+}
+
+pub fn prev_port(p: Port) -> Port {
+    Port::from_index((p.index() + PORT_COUNT - 1) % PORT_COUNT).unwrap_or(p)
+}
+
+fn step_ship(state: &mut PortState, i: usize) {
+    match state.ships[i].status {
+        ShipStatus::Sailing {
+            from,
+            to,
+            elapsed,
+            total,
+        } => {
+            if elapsed + 1 >= total {
+                state.ships[i].status = ShipStatus::Docked(to);
+            } else {
+                state.ships[i].status = ShipStatus::Sailing {
+                    from,
+                    to,
+                    elapsed: elapsed + 1,
+                    total,
+                };
+            }
+        }
+        ShipStatus::Docked(port) => dispatch(state, i, port),
+    }
+}
+
+/// 停泊中の船の行動: 到着港なら売却、出発港なら仕入れて出港、
+/// それ以外の港なら航路に合流する。
+fn dispatch(state: &mut PortState, i: usize, port: Port) {
+    let Some(route) = state.ships[i].route else {
+        return;
+    };
+    if port == route.to {
+        if state.ships[i].cargo.is_some() {
+            sell_cargo(state, i, port);
+        }
+        depart(state, i, port, route.from);
+    } else if port == route.from {
+        if state.ships[i].cargo.is_none() && !load_cargo(state, i, port, route.good) {
+            return;
+        }
+        depart(state, i, port, route.to);
+    } else {
+        let dest = if state.ships[i].cargo.is_some() {
+            route.to
+        } else {
+            route.from
+        };
+        depart(state, i, port, dest);
+    }
+}
+
+fn depart(state: &mut PortState, i: usize, from: Port, to: Port) {
+    state.ships[i].status = ShipStatus::Sailing {
+        from,
+        to,
+        elapsed: 0,
+        total: travel_ticks(from, to),
+    };
+}
+
+/// 出発港で積めるだけ仕入れる。1 個も買えなければ false (資金待ち)。
+fn load_cargo(state: &mut PortState, i: usize, port: Port, good: Good) -> bool {
+    let price = state.price(port, good);
+    let unit = price.ceil().max(1.0) as u64;
+    let capacity = state.ships[i].capacity as u64;
+    let qty = (state.gold / unit).min(capacity);
+    if qty == 0 {
+        if !state.ships[i].waiting {
+            state.ships[i].waiting = true;
+            let name = state.ships[i].name;
+            state.add_log(&format!(
+                "{}: 資金不足で{}を仕入れられない",
+                name,
+                good.name()
+            ));
+        }
+        return false;
+    }
+    let cost = unit * qty;
+    state.gold -= cost;
+    state.prices[port.index()][good.index()] *= 1.0 + PRICE_IMPACT_PER_UNIT * qty as f64;
+    clamp_price(state, port, good);
+    let ship = &mut state.ships[i];
+    ship.waiting = false;
+    ship.cargo = Some(Cargo {
+        good,
+        qty: qty as u32,
+        cost,
+    });
+    true
+}
+
+/// 積荷を全て売却し、利益 (売値 − 仕入れ値) を返す。
+fn sell_cargo(state: &mut PortState, i: usize, port: Port) -> i64 {
+    let Some(cargo) = state.ships[i].cargo.take() else {
+        return 0;
+    };
+    let unit = state.price(port, cargo.good).floor().max(0.0) as u64;
+    let revenue = unit * cargo.qty as u64;
+    state.gold += revenue;
+    state.prices[port.index()][cargo.good.index()] *=
+        1.0 - PRICE_IMPACT_PER_UNIT * cargo.qty as f64;
+    clamp_price(state, port, cargo.good);
+    let profit = revenue as i64 - cargo.cost as i64;
+    state.ships[i].profit += profit;
+    state.total_profit += profit;
+    let name = state.ships[i].name;
+    state.add_log(&format!(
+        "{}: {}で{}x{}を売却 +{}G (利益{:+}G)",
+        name,
+        port.name(),
+        cargo.good.name(),
+        cargo.qty,
+        revenue,
+        profit
+    ));
+    profit
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn route(from: Port, to: Port, good: Good) -> Route {
+        Route { from, to, good }
+    }
+
+    #[test]
+    fn travel_time_scales_with_distance() {
+        let near = travel_ticks(Port::Yokohama, Port::Kobe);
+        let far = travel_ticks(Port::Hakodate, Port::Naha);
+        assert!(near < far);
+        assert_eq!(travel_ticks(Port::Kobe, Port::Yokohama), near);
+    }
+
+    #[test]
+    fn ship_buys_sails_and_sells_for_profit() {
+        let mut s = PortState::new();
+        assert!(set_route(
+            &mut s,
+            0,
+            Some(route(Port::Yokohama, Port::Naha, Good::Silk))
+        ));
+        tick(&mut s, 1);
+        let cargo = s.ships[0].cargo.expect("loaded at origin");
+        assert_eq!(cargo.good, Good::Silk);
+        assert_eq!(s.gold, 500 - cargo.cost);
+
+        let trip = travel_ticks(Port::Yokohama, Port::Naha);
+        // 到着 (trip tick) + 売却 (1 tick)
+        tick(&mut s, trip + 1);
+        assert!(s.ships[0].cargo.is_none());
+        assert!(s.total_profit > 0, "silk 0.7x → 1.5x should pay");
+        assert!(s.gold > 500);
+        assert!(matches!(
+            s.ships[0].status,
+            ShipStatus::Sailing {
+                to: Port::Yokohama,
+                ..
+            }
+        ));
+    }
+
+    #[test]
+    fn trading_moves_local_prices() {
+        let mut s = PortState::new();
+        let before = s.price(Port::Yokohama, Good::Silk);
+        set_route(
+            &mut s,
+            0,
+            Some(route(Port::Yokohama, Port::Naha, Good::Silk)),
+        );
+        tick(&mut s, 1);
+        assert!(s.price(Port::Yokohama, Good::Silk) > before);
+    }
+
+    #[test]
+    fn ship_waits_when_broke() {
+        let mut s = PortState::new();
+        s.gold = 0;
+        set_route(
+            &mut s,
+            0,
+            Some(route(Port::Yokohama, Port::Kobe, Good::Silk)),
+        );
+        tick(&mut s, 5);
+        assert_eq!(s.ships[0].status, ShipStatus::Docked(Port::Yokohama));
+        assert!(s.ships[0].waiting);
+        let warnings = s.log.iter().filter(|l| l.contains("資金不足")).count();
+        assert_eq!(warnings, 1, "waiting is logged once");
+    }
+
+    #[test]
+    fn prices_drift_but_stay_bounded() {
+        let mut s = PortState::new();
+        tick(&mut s, 10_000);
+        for p in Port::ALL {
+            for g in Good::ALL {
+                let base = base_price(p, g);
+                let price = s.price(p, g);
+                assert!(price >= base * 0.3 && price <= base * 3.0);
+            }
+        }
+        assert_ne!(
+            s.price(Port::Kobe, Good::Tea),
+            base_price(Port::Kobe, Good::Tea)
+        );
+    }
+
+    #[test]
+    fn same_port_route_is_rejected() {
+        let mut s = PortState::new();
+        assert!(!set_route(
+            &mut s,
+            0,
+            Some(route(Port::Kobe, Port::Kobe, Good::Tea))
+        ));
+        assert!(s.ships[0].route.is_none());
+    }
+
+    #[test]
+    fn cycling_route_never_produces_same_port() {
+        let mut s = PortState::new();
+        for _ in 0..12 {
+            cycle_route(&mut s, 0, RouteField::From);
+            let r = s.ships[0].route.unwrap();
+            assert_ne!(r.from, r.to);
+            cycle_route(&mut s, 0, RouteField::To);
+            let r = s.ships[0].route.unwrap();
+            assert_ne!(r.from, r.to);
+        }
+    }
+
+    #[test]
+    fn ship_costs_double_and_cap_at_max() {
+        let mut s = PortState::new();
+        s.gold = 1_000_000;
+        assert_eq!(next_ship_cost(&s), Some(SHIP_BASE_COST));
+        assert!(buy_ship(&mut s));
+        assert_eq!(next_ship_cost(&s), Some(SHIP_BASE_COST * 2));
+        while s.ships.len() < MAX_SHIPS {
+            assert!(buy_ship(&mut s));
+        }
+        assert_eq!(next_ship_cost(&s), None);
+        assert!(!buy_ship(&mut s));
+        assert_eq!(s.selected_ship, MAX_SHIPS - 1);
+    }
+}
@@ -0,0 +1,292 @@
+//! Port Trader — 港から港へ船を走らせる交易ゲーム。
+//!
+//! コアループ:
+//!   1. 市場タブで港ごとの相場を見比べる
+//!   2. 船に「出発港・到着港・積荷」の航路を設定すると自動で往復交易する
+//!   3. 稼いだ gold で船を買い足し、航路を増やす
+//!   4. 相場は時間で揺らぎ、売買でも動く → 儲からなくなった航路は組み替える
+
+pub mod actions;
+pub mod logic;
+pub mod render;
+pub mod save;
+pub mod state;
+
+use std::cell::RefCell;
+use std::rc::Rc;
+
+use ratzilla::ratatui::layout::Rect;
+use ratzilla::ratatui::Frame;
+
+use crate::games::{Game, GameChoice};
+use crate::input::{ClickState, InputEvent};
+use crate::scores::Score;
+use crate::sound;
+
+use actions::*;
+use logic::RouteField;
+use state::{Port, PortState, Tab};
+
+pub struct PortGame {
+    pub state: PortState,
+    save_countdown: u32,
+}
+
+/// プレイヤー操作。キーとクリックの両方をここに正規化する。
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum PortAction {
+    SwitchTab(Tab),
+    ViewPort(Port),
+    PrevPort,
+    NextPort,
+    SelectShip(usize),
+    PrevShip,
+    NextShip,
+    Cycle(RouteField),
+    ClearRoute,
+    BuyShip,
+}
+
+impl PortGame {
+    pub fn new() -> Self {
+        #[allow(unused_mut)]
+        let mut state = PortState::new();
+
+        #[cfg(target_arch = "wasm32")]
+        if save::load_game(&mut state) {
+            state.add_log("セーブデータをロードしました");
+        }
+
+        Self {
+            state,
+            save_countdown: save::AUTOSAVE_INTERVAL,
+        }
+    }
+
+    fn key_to_action(&self, ch: char) -> Option<PortAction> {
+        match ch {
+            'f' => Some(PortAction::SwitchTab(Tab::Fleet)),
+            'm' => Some(PortAction::SwitchTab(Tab::Market)),
+            'n' => Some(PortAction::BuyShip),
+            _ => match self.state.tab {
+                Tab::Market => match ch {
+                    'h' => Some(PortAction::PrevPort),
+                    'l' => Some(PortAction::NextPort),
+                    _ => None,
+                },
+                Tab::Fleet => match ch {
+                    'k' => Some(PortAction::PrevShip),
+                    'j' => Some(PortAction::NextShip),
+                    '1' => Some(PortAction::Cycle(RouteField::From)),
+                    '2' => Some(PortAction::Cycle(RouteField::To)),
+                    '3' => Some(PortAction::Cycle(RouteField::Good)),
+                    'x' => Some(PortAction::ClearRoute),
+                    _ => None,
+                },
+            },
+        }
+    }
+
+    fn click_to_action(id: u16) -> Option<PortAction> {
+        match id {
+            TAB_MARKET => Some(PortAction::SwitchTab(Tab::Market)),
+            TAB_FLEET => Some(PortAction::SwitchTab(Tab::Fleet)),
+            BUY_SHIP => Some(PortAction::BuyShip),
+            CYCLE_FROM => Some(PortAction::Cycle(RouteField::From)),
+            CYCLE_TO => Some(PortAction::Cycle(RouteField::To)),
+            CYCLE_GOOD => Some(PortAction::Cycle(RouteField::Good)),
+            CLEAR_ROUTE => Some(PortAction::ClearRoute),
+            id if id >= SELECT_SHIP_BASE => {
+                Some(PortAction::SelectShip((id - SELECT_SHIP_BASE) as usize))
+            }
+            id if id >= SELECT_PORT_BASE => {
+                Port::from_index((id - SELECT_PORT_BASE) as usize).map(PortAction::ViewPort)
+            }
+            _ => None,
+        }
+    }
+
+    fn apply(&mut self, action: PortAction) {
+        let s = &mut self.state;
+        let save_worthy = match action {
+            PortAction::SwitchTab(tab) => {
+                s.tab = tab;
+                false
+            }
+            PortAction::ViewPort(p) => {
+                s.market_port = p;
+                false
+            }
+            PortAction::PrevPort => {
+                s.market_port = logic::prev_port(s.market_port);
+                false
+            }
+            PortAction::NextPort => {
+                s.market_port = logic::next_port(s.market_port);
+                false
+            }
+            PortAction::SelectShip(i) => {
+                if i < s.ships.len() {
+                    s.selected_ship = i;
+                    sound::play(sound::SELECT);
+                }
+                false
+            }
+            PortAction::PrevShip => {
+                s.selected_ship = s.selected_ship.saturating_sub(1);
+                false
+            }
+            PortAction::NextShip => {
+                s.selected_ship = (s.selected_ship + 1).min(s.ships.len().saturating_sub(1));
+                false
+            }
+            PortAction::Cycle(field) => {
+                logic::cycle_route(s, s.selected_ship, field);
+                sound::play(sound::CLICK);
+                true
+            }
+            PortAction::ClearRoute => logic::set_route(s, s.selected_ship, None),
+            PortAction::BuyShip => {
+                let ok = logic::buy_ship(s);
+                sound::play(if ok { sound::PURCHASE } else { sound::ERROR });
+                ok
+            }
+        };
+        if save_worthy {
+            self.flush_save();
+        }
+    }
+
+    fn flush_save(&mut self) {
+        #[cfg(target_arch = "wasm32")]
+        save::save_game(&self.state);
+        self.save_countdown = save::AUTOSAVE_INTERVAL;
+    }
+}
+
+impl Game for PortGame {
+    fn choice(&self) -> GameChoice {
+        GameChoice::Port
+    }
+
+    fn handle_input(&mut self, event: &InputEvent) -> bool {
+        let action = match event {
+            InputEvent::Key(c) => self.key_to_action(*c),
+            InputEvent::Click(_, id) => Self::click_to_action(*id),
+            InputEvent::LongPress(..) | InputEvent::DoubleTap(..) | InputEvent::Drag(..) => None,
+        };
+        match action {
+            Some(a) => {
+                self.apply(a);
+                true
+            }
+            None => false,
+        }
+    }
+
+    fn tick(&mut self, delta_ticks: u32) {
+        logic::tick(&mut self.state, delta_ticks);
+        self.save_countdown = self.save_countdown.saturating_sub(delta_ticks);
+        if self.save_countdown == 0 {
+            self.flush_save();
+        }
+    }
+
+    fn save(&mut self) {
+        self.flush_save();
+    }
+
+    fn render(&self, f: &mut Frame, area: Rect, click_state: &Rc<RefCell<ClickState>>) {
+        render::render(&self.state, f, area, click_state);
+    }
+
+    fn score(&self) -> Option<Score> {
+        Some(Score::new(self.state.total_profit.max(0) as u64, "累計利益", "G"))
+    }
+
+    #[cfg(any(feature = "debug-console", debug_assertions, test))]
+    fn debug_command(&mut self, args: &[&str]) -> Result<String, String> {
+        use crate::debug_console::{parse_count, unknown};
+        match args {
+            ["help"] => Ok("give gold <n>".into()),
+            ["give", "gold", n] => {
+                let n = parse_count(n)?;
+                self.state.gold = self.state.gold.saturating_add(n);
+                Ok(format!("ゴールド +{}", n))
+            }
+            _ => Err(unknown(args)),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::input::ClickScope;
+    use state::Good;
+
+    fn click(id: u16) -> InputEvent {
+        InputEvent::Click(ClickScope::Game(GameChoice::Port), id)
+    }
+
+    #[test]
+    fn route_can_be_built_by_keys_or_clicks() {
+        let mut g = PortGame::new();
+        assert!(g.handle_input(&InputEvent::Key('2')));
+        assert!(g.handle_input(&click(CYCLE_GOOD)));
+        let r = g.state.ships[0].route.expect("route created");
+        assert_eq!(r.from, Port::Yokohama);
+        assert_ne!(r.to, r.from);
+        assert_eq!(r.good, Good::Tea);
+
+        assert!(g.handle_input(&click(CLEAR_ROUTE)));
+        assert!(g.state.ships[0].route.is_none());
+    }
+
+    #[test]
+    fn route_edits_reset_autosave_timer() {
+        let mut g = PortGame::new();
+        g.tick(100);
+        assert_eq!(g.save_countdown, save::AUTOSAVE_INTERVAL - 100);
+        g.handle_input(&click(CYCLE_FROM));
+        assert_eq!(g.save_countdown, save::AUTOSAVE_INTERVAL);
+    }
+
+    #[test]
+    fn tab_scoped_keys_and_port_tabs() {
+        let mut g = PortGame::new();
+        assert!(
+            !g.handle_input(&InputEvent::Key('l')),
+            "port keys only on market tab"
+        );
+        g.handle_input(&InputEvent::Key('m'));
+        assert_eq!(g.state.tab, Tab::Market);
+        g.handle_input(&InputEvent::Key('l'));
+        assert_eq!(g.state.market_port, Port::Kobe);
+        g.handle_input(&click(SELECT_PORT_BASE + Port::Naha.index() as u16));
+        assert_eq!(g.state.market_port, Port::Naha);
+        assert!(
+            !g.handle_input(&InputEvent::Key('1')),
+            "route keys only on fleet tab"
+        );
+        assert!(
+            !g.handle_input(&InputEvent::Key('q')),
+            "q falls through to the menu"
+        );
+    }
+
+    #[test]
+    fn selecting_ships_stays_in_range() {
+        let mut g = PortGame::new();
+        g.state.gold = 10_000;
+        g.handle_input(&InputEvent::Key('n'));
+        assert_eq!(g.state.ships.len(), 2);
+        g.handle_input(&click(SELECT_SHIP_BASE));
+        assert_eq!(g.state.selected_ship, 0);
+        g.handle_input(&click(SELECT_SHIP_BASE + 5));
+        assert_eq!(g.state.selected_ship, 0);
+        g.handle_input(&InputEvent::Key('j'));
+        g.handle_input(&InputEvent::Key('j'));
+        assert_eq!(g.state.selected_ship, 1);
+    }
+}
@@ -0,0 +1,418 @@
+//! Port Trader rendering.
+
+use std::cell::RefCell;
+use std::rc::Rc;
+
+use ratzilla::ratatui::layout::{Constraint, Direction, Layout, Rect};
+use ratzilla::ratatui::style::{Color, Modifier, Style};
+use ratzilla::ratatui::text::{Line, Span};
+use ratzilla::ratatui::widgets::{Block, Borders, Paragraph, Wrap};
+use ratzilla::ratatui::Frame;
+
+use crate::input::{is_narrow_layout, ClickState};
+use crate::widgets::{ClickableList, TabBar};
+
+use super::actions::*;
+use super::logic::{base_price, next_ship_cost, route_margin, travel_ticks};
+use super::state::{Good, Port, PortState, Ship, ShipStatus, Tab};
+
+pub fn render(state: &PortState, f: &mut Frame, area: Rect, click_state: &Rc<RefCell<ClickState>>) {
+    let narrow = is_narrow_layout(area.width);
+    let log_h = if narrow { 0 } else { 6 };
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Length(3),     // Header
+            Constraint::Length(1),     // Tabs
+            Constraint::Min(10),       // Body
+            Constraint::Length(log_h), // Log
+        ])
+        .split(area);
+
+    render_header(state, f, chunks[0]);
+    render_tabs(state, f, chunks[1], click_state);
+    match state.tab {
+        Tab::Market => render_market(state, f, chunks[2], click_state),
+        Tab::Fleet => render_fleet(state, f, chunks[2], click_state),
+    }
+    if !narrow {
+        render_log(state, f, chunks[3]);
+    }
+}
+
+fn render_header(state: &PortState, f: &mut Frame, area: Rect) {
+    let sailing = state
+        .ships
+        .iter()
+        .filter(|s| matches!(s.status, ShipStatus::Sailing { .. }))
+        .count();
+    let profit_color = if state.total_profit >= 0 {
+        Color::LightGreen
+    } else {
+        Color::Red
+    };
+    let spans = vec![
+        Span::styled(
+            format!(" {}G", state.gold),
+            Style::default()
+                .fg(Color::Yellow)
+                .add_modifier(Modifier::BOLD),
+        ),
+        Span::styled(
+            format!("  船 {}隻 (航海中 {})", state.ships.len(), sailing),
+            Style::default().fg(Color::White),
+        ),
+        Span::styled(
+            format!("  累計利益 {:+}G", state.total_profit),
+            Style::default().fg(profit_color),
+        ),
+    ];
+    let widget = Paragraph::new(Line::from(spans)).block(
+        Block::default()
+            .borders(Borders::ALL)
+            .border_style(Style::default().fg(Color::LightBlue))
+            .title(" Port Trader "),
+    );
+    f.render_widget(widget, area);
+}
+
+fn tab_style(active: bool, color: Color) -> Style {
+    if active {
+        Style::default()
+            .fg(Color::Black)
+            .bg(color)
+            .add_modifier(Modifier::BOLD)
+    } else {
+        Style::default().fg(color)
+    }
+}
+
+fn render_tabs(
+    state: &PortState,
+    f: &mut Frame,
+    area: Rect,
+    click_state: &Rc<RefCell<ClickState>>,
+) {
+    let bar = TabBar::new(" │ ")
+        .tab(
+            "⚓船団",
+            tab_style(state.tab == Tab::Fleet, Color::LightBlue),
+            TAB_FLEET,
+        )
+        .tab(
+            "📈市場",
+            tab_style(state.tab == Tab::Market, Color::Yellow),
+            TAB_MARKET,
+        );
+    let mut cs = click_state.borrow_mut();
+    bar.render(f, area, &mut cs);
+}
+
+/// 相場の基準価格比に応じた色 (安い=緑 / 高い=赤)。
+fn price_color(port: Port, good: Good, price: f64) -> Color {
+    let ratio = price / base_price(port, good);
+    if ratio < 0.9 {
+        Color::LightGreen
+    } else if ratio > 1.1 {
+        Color::LightRed
+    } else {
+        Color::White
+    }
+}
+
+/// `good` を `from` で買ったとき、最も高く売れる港とその価格。
+fn best_market(state: &PortState, from: Port, good: Good) -> (Port, f64) {
+    Port::ALL
+        .iter()
+        .filter(|p| **p != from)
+        .map(|p| (*p, state.price(*p, good)))
+        .fold(
+            (from, 0.0),
+            |best, cur| if cur.1 > best.1 { cur } else { best },
+        )
+}
+
+fn render_market(
+    state: &PortState,
+    f: &mut Frame,
+    area: Rect,
+    click_state: &Rc<RefCell<ClickState>>,
+) {
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([Constraint::Length(1), Constraint::Min(5)])
+        .split(area);
+
+    let mut bar = TabBar::new("·");
+    for p in Port::ALL {
+        bar = bar.tab(
+            p.name(),
+            tab_style(p == state.market_port, Color::Cyan),
+            SELECT_PORT_BASE + p.index() as u16,
+        );
+    }
+    {
+        let mut cs = click_state.borrow_mut();
+        bar.render(f, chunks[0], &mut cs);
+    }
+
+    let here = state.market_port;
+    let mut cl = ClickableList::new();
+    cl.push(Line::from(Span::styled(
+        " 商品  相場      → 高値の港",
+        Style::default().fg(Color::DarkGray),
+    )));
+    for g in Good::ALL {
+        let price = state.price(here, g);
+        let (dest, dest_price) = best_market(state, here, g);
+        let margin = dest_price - price;
+        let margin_color = if margin > 0.0 {
+            Color::LightGreen
+        } else {
+            Color::Red
+        };
+        cl.push(Line::from(vec![
+            Span::styled(
+                format!(" {:<4}", g.name()),
+                Style::default().fg(Color::White),
+            ),
+            Span::styled(
+                format!("{:>6.1}G", price),
+                Style::default().fg(price_color(here, g, price)),
+            ),
+            Span::styled(
+                format!("  → {} {:.1}G", dest.name(), dest_price),
+                Style::default().fg(Color::DarkGray),
+            ),
+            Span::styled(
+                format!(" ({:+.1})", margin),
+                Style::default().fg(margin_color),
+            ),
+        ]));
+    }
+    cl.push(Line::from(""));
+    cl.push(Line::from(Span::styled(
+        " 緑=割安 赤=割高。売買すると相場が動きます",
+        Style::default().fg(Color::DarkGray),
+    )));
+
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(Color::Yellow))
+        .title(format!(" {}の相場 ", here.name()));
+    let mut cs = click_state.borrow_mut();
+    cl.render(f, chunks[1], block, &mut cs, false, 0);
+}
+
+fn ship_status_spans(ship: &Ship) -> Vec<Span<'static>> {
+    match ship.status {
+        ShipStatus::Docked(p) => {
+            let (label, color) = if ship.waiting {
+                (format!(" {}で資金待ち", p.name()), Color::Red)
+            } else if ship.route.is_none() {
+                (format!(" {}に停泊", p.name()), Color::DarkGray)
+            } else {
+                (format!(" {}で荷役中", p.name()), Color::White)
+            };
+            vec![Span::styled(label, Style::default().fg(color))]
+        }
+        ShipStatus::Sailing {
+            from,
+            to,
+            elapsed,
+            total,
+        } => {
+            let bar_w = 6u32;
+            let filled = (elapsed * bar_w / total.max(1)).min(bar_w);
+            vec![
+                Span::styled(
+                    format!(" {}→{} ", from.name(), to.name()),
+                    Style::default().fg(Color::Cyan),
+                ),
+                Span::styled(
+                    "█".repeat(filled as usize),
+                    Style::default().fg(Color::Cyan),
+                ),
+                Span::styled(
+                    "░".repeat((bar_w - filled) as usize),
+                    Style::default().fg(Color::DarkGray),
+                ),
+            ]
+        }
+    }
+}
+
+fn render_fleet(
+    state: &PortState,
+    f: &mut Frame,
+    area: Rect,
+    click_state: &Rc<RefCell<ClickState>>,
+) {
+    let mut cl = ClickableList::new();
+
+    for (i, ship) in state.ships.iter().enumerate() {
+        let selected = i == state.selected_ship;
+        let marker = if selected { "▶" } else { " " };
+        let name_style = if selected {
+            Style::default()
+                .fg(Color::LightBlue)
+                .add_modifier(Modifier::BOLD)
+        } else {
+            Style::default().fg(Color::White)
+        };
+        let mut spans = vec![Span::styled(format!("{}{}", marker, ship.name), name_style)];
+        spans.extend(ship_status_spans(ship));
+        if let Some(c) = ship.cargo {
+            spans.push(Span::styled(
+                format!(" {}x{}", c.good.name(), c.qty),
+                Style::default().fg(Color::Yellow),
+            ));
+        }
+        cl.push_clickable(Line::from(spans), SELECT_SHIP_BASE + i as u16);
+    }
+    cl.push(Line::from(""));
+
+    if let Some(ship) = state.ships.get(state.selected_ship) {
+        cl.push(Line::from(Span::styled(
+            format!(" ── {} の航路 (利益 {:+}G) ──", ship.name, ship.profit),
+            Style::default()
+                .fg(Color::Cyan)
+                .add_modifier(Modifier::BOLD),
+        )));
+        match ship.route {
+            Some(r) => {
+                let margin = route_margin(state, &r);
+                let margin_color = if margin > 0.0 {
+                    Color::LightGreen
+                } else {
+                    Color::Red
+                };
+                cl.push_clickable(
+                    Line::from(Span::styled(
+                        format!(" [1] 出発港: {} で仕入れ", r.from.name()),
+                        Style::default().fg(Color::White),
+                    )),
+                    CYCLE_FROM,
+                );
+                cl.push_clickable(
+                    Line::from(Span::styled(
+                        format!(
+                            " [2] 到着港: {} で売却 (片道 {:.0}秒)",
+                            r.to.name(),
+                            travel_ticks(r.from, r.to) as f64 / 10.0
+                        ),
+                        Style::default().fg(Color::White),
+                    )),
+                    CYCLE_TO,
+                );
+                cl.push_clickable(
+                    Line::from(vec![
+                        Span::styled(
+                            format!(" [3] 積荷: {}", r.good.name()),
+                            Style::default().fg(Color::White),
+                        ),
+                        Span::styled(
+                            format!("  1個あたり {:+.1}G", margin),
+                            Style::default().fg(margin_color),
+                        ),
+                    ]),
+                    CYCLE_GOOD,
+                );
+                cl.push_clickable(
+                    Line::from(Span::styled(
+                        " [X] 航路を解除",
+                        Style::default().fg(Color::DarkGray),
+                    )),
+                    CLEAR_ROUTE,
+                );
+            }
+            None => {
+                cl.push(Line::from(Span::styled(
+                    " 航路なし — 出発港・到着港・積荷を選ぼう",
+                    Style::default().fg(Color::DarkGray),
+                )));
+                cl.push_clickable(
+                    Line::from(Span::styled(
+                        " [1] 出発港を選ぶ",
+                        Style::default().fg(Color::White),
+                    )),
+                    CYCLE_FROM,
+                );
+                cl.push_clickable(
+                    Line::from(Span::styled(
+                        " [2] 到着港を選ぶ",
+                        Style::default().fg(Color::White),
+                    )),
+                    CYCLE_TO,
+                );
+                cl.push_clickable(
+                    Line::from(Span::styled(
+                        " [3] 積荷を選ぶ",
+                        Style::default().fg(Color::White),
+                    )),
+                    CYCLE_GOOD,
+                );
+            }
+        }
+    }
+    cl.push(Line::from(""));
+
+    match next_ship_cost(state) {
+        Some(cost) => {
+            let style = if state.gold >= cost {
+                Style::default().fg(Color::Yellow)
+            } else {
+                Style::default().fg(Color::DarkGray)
+            };
+            cl.push_clickable(
+                Line::from(Span::styled(
+                    format!(" [N] 新しい船を購入 {}G", cost),
+                    style,
+                )),
+                BUY_SHIP,
+            );
+        }
+        None => cl.push(Line::from(Span::styled(
+            " 船団は最大規模です",
+            Style::default().fg(Color::DarkGray),
+        ))),
+    }
+
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(Color::LightBlue))
+        .title(" 船団 ");
+    let mut cs = click_state.borrow_mut();
+    cl.render(f, area, block, &mut cs, false, 0);
+}
+
+fn render_log(state: &PortState, f: &mut Frame, area: Rect) {
+    let visible = area.height.saturating_sub(2) as usize;
+    let lines: Vec<Line> = state
+        .log
+        .iter()
+        .rev()
+        .take(visible)
+        .enumerate()
+        .map(|(i, entry)| {
+            let color = if i == 0 {
+                Color::White
+            } else {
+                Color::DarkGray
+            };
+            Line::from(Span::styled(
+                format!(" {}", entry),
+                Style::default().fg(color),
+            ))
+        })
+        .collect();
+    let widget = Paragraph::new(lines)
+        .block(
+            Block::default()
+                .borders(Borders::ALL)
+                .border_style(Style::default().fg(Color::Blue))
+                .title(" Log "),
+        )
+        .wrap(Wrap { trim: false });
+    f.render_widget(widget, area);
+}
@@ -0,0 +1,281 @@
+//! Port Trader セーブ/ロード機能。
+//!
+//! 永続化するのは資金・相場・船団 (航路・積荷・利益) のみ。
+//! 航海中の船は目的港に停泊した状態で復元する (航海の途中経過は保存しない)。
+
+#[cfg(any(target_arch = "wasm32", test))]
+use serde::{Deserialize, Serialize};
+
+#[cfg(any(target_arch = "wasm32", test))]
+use super::state::{Cargo, Good, Port, PortState, Route, Ship, ShipStatus};
+
+#[cfg(any(target_arch = "wasm32", test))]
+const SAVE_VERSION: u32 = 1;
+
+const STORAGE_KEY: &str = "port_trader_save";
+
+/// アクティブなプロファイル用に名前空間化した保存キー。
+/// 設定画面のリセットはこのキーの中身をゴミ箱 ([`crate::storage::move_to_trash`]) へ移す。
+pub fn storage_key() -> String {
+    crate::profile::scoped_key(STORAGE_KEY)
+}
+
+/// イベントベース保存の保険として走らせる定期セーブ間隔 (tick 数)。
+pub const AUTOSAVE_INTERVAL: u32 = 300;
+
+#[cfg(any(target_arch = "wasm32", test))]
+#[derive(Serialize, Deserialize)]
+struct SaveData {
+    version: u32,
+    game: GameSave,
+}
+
+#[cfg(any(target_arch = "wasm32", test))]
+#[derive(Serialize, Deserialize, Default)]
+#[serde(default)]
+struct GameSave {
+    gold: u64,
+    /// `[Port::index()][Good::index()]` 順。
+    prices: Vec<Vec<f64>>,
+    ships: Vec<ShipSave>,
+    total_profit: i64,
+    total_ticks: u64,
+    rng_state: u32,
+}
+
+/// 港・商品は index で保存する。
+#[cfg(any(target_arch = "wasm32", test))]
+#[derive(Serialize, Deserialize, Default)]
+#[serde(default)]
+struct ShipSave {
+    port: usize,
+    /// (from, to, good)
+    route: Option<(usize, usize, usize)>,
+    /// (good, qty, cost)
+    cargo: Option<(usize, u32, u64)>,
+    profit: i64,
+}
+
+#[cfg(any(target_arch = "wasm32", test))]
+fn extract_save(state: &PortState) -> SaveData {
+    let ships = state
+        .ships
+        .iter()
+        .map(|s| ShipSave {
+            port: match s.status {
+                ShipStatus::Docked(p) => p.index(),
+                ShipStatus::Sailing { to, .. } => to.index(),
+            },
+            route: s
+                .route
+                .map(|r| (r.from.index(), r.to.index(), r.good.index())),
+            cargo: s.cargo.map(|c| (c.good.index(), c.qty, c.cost)),
+            profit: s.profit,
+        })
+        .collect();
+    SaveData {
+        version: SAVE_VERSION,
+        game: GameSave {
+            gold: state.gold,
+            prices: state.prices.iter().map(|row| row.to_vec()).collect(),
+            ships,
+            total_profit: state.total_profit,
+            total_ticks: state.total_ticks,
+            rng_state: state.rng_state,
+        },
+    }
+}
+
+#[cfg(any(target_arch = "wasm32", test))]
+fn apply_save(state: &mut PortState, save: &GameSave) {
+    state.gold = save.gold;
+    for (p, row) in state.prices.iter_mut().enumerate() {
+        for (g, price) in row.iter_mut().enumerate() {
+            // 壊れた値は初期相場のまま残す。
+            if let Some(v) = save.prices.get(p).and_then(|r| r.get(g)) {
+                if v.is_finite() && *v > 0.0 {
+                    *price = *v;
+                }
+            }
+        }
+    }
+    if !save.ships.is_empty() {
+        state.ships = save
+            .ships
+            .iter()
+            .take(super::state::MAX_SHIPS)
+            .enumerate()
+            .map(|(i, s)| {
+                let port = Port::from_index(s.port).unwrap_or(Port::Yokohama);
+                let mut ship = Ship::new(i, port);
+                ship.route = s.route.and_then(|(f, t, g)| {
+                    let route = Route {
+                        from: Port::from_index(f)?,
+                        to: Port::from_index(t)?,
+                        good: Good::from_index(g)?,
+                    };
+                    (route.from != route.to).then_some(route)
+                });
+                ship.cargo = s.cargo.and_then(|(g, qty, cost)| {
+                    Some(Cargo {
+                        good: Good::from_index(g)?,
+                        qty,
+                        cost,
+                    })
+                });
+                ship.profit = s.profit;
+                ship
+            })
+            .collect();
+    }
+    state.total_profit = save.total_profit;
+    state.total_ticks = save.total_ticks;
+    if save.rng_state != 0 {
+        state.rng_state = save.rng_state;
+    }
+}
+
+#[cfg(target_arch = "wasm32")]
+fn get_storage() -> Option<crate::storage::Store> {
+    crate::storage::get()
+}
+
+#[cfg(target_arch = "wasm32")]
+pub fn save_game(state: &PortState) {
+    let json = match serde_json::to_string(&extract_save(state)) {
+        Ok(j) => j,
+        Err(e) => {
+            web_sys::console::warn_1(
+                &format!("Port Trader: セーブのシリアライズに失敗: {e}").into(),
+            );
+            return;
+        }
+    };
+    if let Some(storage) = get_storage() {
+        if let Err(e) = storage.set_item(&storage_key(), &json) {
+            web_sys::console::warn_1(
+                &format!("Port Trader: localStorage への保存に失敗: {e:?}").into(),
+            );
+        }
+    }
+}
+
+#[cfg(target_arch = "wasm32")]
+pub fn load_game(state: &mut PortState) -> bool {
+    let Some(storage) = get_storage() else {
+        return false;
+    };
+    let json = match storage.get_item(&storage_key()) {
+        Ok(Some(j)) => j,
+        _ => return false,
+    };
+    let save_data: SaveData = match serde_json::from_str(&json) {
+        Ok(d) => d,
+        Err(e) => {
+            web_sys::console::warn_1(
+                &format!("Port Trader: セーブデータのパースに失敗 (破棄します): {e}").into(),
+            );
+            let _ = storage.remove_item(&storage_key());
+            return false;
+        }
+    };
+    apply_save(state, &save_data.game);
+    true
+}
+
+/// メインメニュー用の 1 行の進捗要約。セーブが無ければ `None`。
+/// セーブ全体を読み込むので、メニューに入るときに 1 回だけ呼ぶこと。
+pub fn progress_summary() -> Option<String> {
+    #[cfg(target_arch = "wasm32")]
+    {
+        let mut state = PortState::new();
+        load_game(&mut state).then(|| summarize(&state))
+    }
+    #[cfg(not(target_arch = "wasm32"))]
+    None
+}
+
+#[cfg(any(target_arch = "wasm32", test))]
+fn summarize(state: &PortState) -> String {
+    format!("{}G / 船 {} 隻", state.gold, state.ships.len())
+}
+
+/// extract → JSON → apply → extract の前後の JSON を返す (fuzz ハーネスの往復検査用)。
+#[cfg(test)]
+pub(crate) fn roundtrip_json(state: &PortState) -> (String, String) {
+    let before = serde_json::to_string(&extract_save(state)).unwrap();
+    let loaded: SaveData = serde_json::from_str(&before).unwrap();
+    let mut restored = PortState::new();
+    apply_save(&mut restored, &loaded.game);
+    let after = serde_json::to_string(&extract_save(&restored)).unwrap();
+    (before, after)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn menu_summary_shows_gold_and_fleet() {
+        let s = PortState::new();
+        assert_eq!(summarize(&s), format!("{}G / 船 1 隻", s.gold));
+    }
+
+    #[test]
+    fn extract_and_apply_roundtrip() {
+        let mut original = PortState::new();
+        original.gold = 4_321;
+        original.prices[Port::Kobe.index()][Good::Tea.index()] = 12.5;
+        original.ships.push(Ship::new(1, Port::Naha));
+        original.ships[0].route = Some(Route {
+            from: Port::Nagasaki,
+            to: Port::Hakodate,
+            good: Good::Pottery,
+        });
+        original.ships[0].cargo = Some(Cargo {
+            good: Good::Pottery,
+            qty: 20,
+            cost: 360,
+        });
+        original.ships[0].status = ShipStatus::Sailing {
+            from: Port::Nagasaki,
+            to: Port::Hakodate,
+            elapsed: 40,
+            total: 250,
+        };
+        original.ships[1].profit = 99;
+        original.total_profit = 1_000;
+        original.total_ticks = 777;
+
+        let json = serde_json::to_string(&extract_save(&original)).unwrap();
+        let parsed: SaveData = serde_json::from_str(&json).unwrap();
+        let mut restored = PortState::new();
+        apply_save(&mut restored, &parsed.game);
+
+        assert_eq!(restored.gold, original.gold);
+        assert_eq!(restored.prices, original.prices);
+        assert_eq!(restored.ships.len(), 2);
+        assert_eq!(restored.ships[0].route, original.ships[0].route);
+        assert_eq!(restored.ships[0].cargo, original.ships[0].cargo);
+        assert_eq!(restored.ships[0].status, ShipStatus::Docked(Port::Hakodate));
+        assert_eq!(restored.ships[1].status, ShipStatus::Docked(Port::Naha));
+        assert_eq!(restored.ships[1].profit, 99);
+        assert_eq!(restored.total_profit, original.total_profit);
+        assert_eq!(restored.total_ticks, original.total_ticks);
+        assert_eq!(restored.rng_state, original.rng_state);
+    }
+
+    #[test]
+    fn invalid_entries_fall_back_to_defaults() {
+        let parsed: SaveData = serde_json::from_str(
+            r#"{"version":1,"game":{"gold":5,"ships":[{"port":99,"route":[2,2,0]}]}}"#,
+        )
+        .unwrap();
+        let mut s = PortState::new();
+        apply_save(&mut s, &parsed.game);
+        assert_eq!(s.gold, 5);
+        assert_eq!(s.ships[0].status, ShipStatus::Docked(Port::Yokohama));
+        assert!(s.ships[0].route.is_none(), "same-port route is dropped");
+        assert_eq!(s.price(Port::Naha, Good::Sugar), 15.0);
+    }
+}
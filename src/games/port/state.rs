@@ -0,0 +1,279 @@
+//! Port Trader — game state.
+//!
+//! 純粋なデータ定義のみ。ロジックは logic.rs に置く。
+//!
+//! 港ごとに商品の相場が違う。船に「A 港で X を買って B 港で売る」航路を
+//! 割り当てると、以後は自動で往復して差額を稼ぐ。相場は時間で揺らぎ、
+//! 売買量に応じて動く (同じ航路に船を並べすぎると儲けが薄くなる)。
+
+pub const PORT_COUNT: usize = 5;
+pub const GOOD_COUNT: usize = 5;
+
+/// 所有できる船の上限。船団タブの 1 画面に収まる数に抑える。
+pub const MAX_SHIPS: usize = 8;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Port {
+    Hakodate,
+    Yokohama,
+    Kobe,
+    Nagasaki,
+    Naha,
+}
+
+impl Port {
+    pub const ALL: [Port; PORT_COUNT] = [
+        Port::Hakodate,
+        Port::Yokohama,
+        Port::Kobe,
+        Port::Nagasaki,
+        Port::Naha,
+    ];
+
+    pub fn index(self) -> usize {
+        self as usize
+    }
+
+    pub fn from_index(i: usize) -> Option<Port> {
+        Self::ALL.get(i).copied()
+    }
+
+    pub fn name(self) -> &'static str {
+        match self {
+            Port::Hakodate => "函館",
+            Port::Yokohama => "横浜",
+            Port::Kobe => "神戸",
+            Port::Nagasaki => "長崎",
+            Port::Naha => "那覇",
+        }
+    }
+
+    /// 海図上の座標。航海時間は港間の直線距離に比例する。
+    pub fn position(self) -> (f64, f64) {
+        match self {
+            Port::Hakodate => (8.0, 0.0),
+            Port::Yokohama => (7.0, 4.0),
+            Port::Kobe => (4.0, 5.0),
+            Port::Nagasaki => (0.0, 7.0),
+            Port::Naha => (1.0, 11.0),
+        }
+    }
+
+    /// 相場の基準倍率 (`Good::base_price` に掛ける)。
+    /// 1 未満はその港の特産 (安く買える)、1 超は需要地 (高く売れる)。
+    pub fn price_factor(self, good: Good) -> f64 {
+        const TABLE: [[f64; GOOD_COUNT]; PORT_COUNT] = [
+            // 米    茶    絹    陶器  砂糖
+            [0.7, 1.4, 1.3, 1.2, 1.5], // 函館
+            [1.2, 1.1, 0.7, 1.3, 1.2], // 横浜
+            [1.0, 0.8, 1.2, 0.9, 1.3], // 神戸
+            [1.3, 1.2, 1.4, 0.6, 1.1], // 長崎
+            [1.4, 1.3, 1.5, 1.2, 0.6], // 那覇
+        ];
+        TABLE[self.index()][good.index()]
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Good {
+    Rice,
+    Tea,
+    Silk,
+    Pottery,
+    Sugar,
+}
+
+impl Good {
+    pub const ALL: [Good; GOOD_COUNT] = [
+        Good::Rice,
+        Good::Tea,
+        Good::Silk,
+        Good::Pottery,
+        Good::Sugar,
+    ];
+
+    pub fn index(self) -> usize {
+        self as usize
+    }
+
+    pub fn from_index(i: usize) -> Option<Good> {
+        Self::ALL.get(i).copied()
+    }
+
+    pub fn name(self) -> &'static str {
+        match self {
+            Good::Rice => "米",
+            Good::Tea => "茶",
+            Good::Silk => "絹",
+            Good::Pottery => "陶器",
+            Good::Sugar => "砂糖",
+        }
+    }
+
+    pub fn base_price(self) -> f64 {
+        match self {
+            Good::Rice => 10.0,
+            Good::Tea => 20.0,
+            Good::Silk => 45.0,
+            Good::Pottery => 30.0,
+            Good::Sugar => 25.0,
+        }
+    }
+}
+
+/// 船に割り当てる交易路。`from` で `good` を買い、`to` で売る。
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Route {
+    pub from: Port,
+    pub to: Port,
+    pub good: Good,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum ShipStatus {
+    Docked(Port),
+    Sailing {
+        from: Port,
+        to: Port,
+        elapsed: u32,
+        total: u32,
+    },
+}
+
+/// 積荷 (商品・数量・仕入れ総額)。
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Cargo {
+    pub good: Good,
+    pub qty: u32,
+    pub cost: u64,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct Ship {
+    pub name: &'static str,
+    pub capacity: u32,
+    pub status: ShipStatus,
+    pub route: Option<Route>,
+    pub cargo: Option<Cargo>,
+    /// この船が稼いだ利益の累計。
+    pub profit: i64,
+    /// 資金不足で出港できず待機中 (ログの連投防止)。
+    pub waiting: bool,
+}
+
+pub const SHIP_NAMES: [&str; MAX_SHIPS] = [
+    "朝凪丸",
+    "海燕丸",
+    "白鷺丸",
+    "疾風丸",
+    "北斗丸",
+    "瑞鶴丸",
+    "昴丸",
+    "黒潮丸",
+];
+
+impl Ship {
+    pub fn new(index: usize, port: Port) -> Self {
+        Self {
+            name: SHIP_NAMES[index % MAX_SHIPS],
+            capacity: 20,
+            status: ShipStatus::Docked(port),
+            route: None,
+            cargo: None,
+            profit: 0,
+            waiting: false,
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Tab {
+    Market,
+    Fleet,
+}
+
+pub struct PortState {
+    pub gold: u64,
+    /// 相場 `[港][商品]`。
+    pub prices: [[f64; GOOD_COUNT]; PORT_COUNT],
+    pub ships: Vec<Ship>,
+    pub tab: Tab,
+    /// 市場タブで表示中の港 (ナロー表示用)。
+    pub market_port: Port,
+    /// 船団タブで選択中の船。
+    pub selected_ship: usize,
+    pub total_profit: i64,
+    pub total_ticks: u64,
+    pub rng_state: u32,
+    pub log: Vec<String>,
+}
+
+impl PortState {
+    pub fn new() -> Self {
+        let mut prices = [[0.0; GOOD_COUNT]; PORT_COUNT];
+        for p in Port::ALL {
+            for g in Good::ALL {
+                prices[p.index()][g.index()] = g.base_price() * p.price_factor(g);
+            }
+        }
+        Self {
+            gold: 500,
+            prices,
+            ships: vec![Ship::new(0, Port::Yokohama)],
+            tab: Tab::Fleet,
+            market_port: Port::Yokohama,
+            selected_ship: 0,
+            total_profit: 0,
+            total_ticks: 0,
+            rng_state: 0x2545_F491,
+            log: vec!["Port Trader へようこそ！船に航路を設定しよう".into()],
+        }
+    }
+
+    pub fn price(&self, port: Port, good: Good) -> f64 {
+        self.prices[port.index()][good.index()]
+    }
+
+    pub fn add_log(&mut self, text: &str) {
+        self.log.push(text.to_string());
+        if self.log.len() > 30 {
+            self.log.remove(0);
+        }
+    }
+}
+
+impl Default for PortState {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn every_good_has_a_cheap_origin_and_a_market() {
+        for g in Good::ALL {
+            let factors: Vec<f64> = Port::ALL.iter().map(|p| p.price_factor(g)).collect();
+            assert!(
+                factors.iter().any(|f| *f < 1.0),
+                "{} has no origin",
+                g.name()
+            );
+            assert!(
+                factors.iter().any(|f| *f > 1.2),
+                "{} has no market",
+                g.name()
+            );
+        }
+    }
+
+    #[test]
+    fn new_state_starts_with_one_idle_ship() {
+        let s = PortState::new();
+        assert_eq!(s.ships.len(), 1);
+        assert!(s.ships[0].route.is_none());
+        assert_eq!(s.price(Port::Naha, Good::Sugar), 15.0);
+    }
+}
@@ -0,0 +1,141 @@
+//! Contextual action bar — the row of buttons at the bottom of the screen.
+//!
+//! Every action the current scene / overlay accepts from the keyboard is
+//! listed here as a tappable button, so the game is fully playable on a
+//! phone without a keyboard. Each entry is just a label plus the key it
+//! stands for: a tap on [`ACTION_BAR`](super::actions::ACTION_BAR) `i`
+//! rebuilds the list from the same state and replays `actions(state)[i].key`
+//! through the normal key handler, so the bar can never drift from what
+//! the keys actually do.
+
+use crate::input::{PAGE_DOWN_KEY, PAGE_UP_KEY};
+
+use super::logic;
+use super::state::{Overlay, RpgState, Scene};
+
+/// Upper bound on buttons per context (size of the action ID range).
+pub const MAX_BAR_ACTIONS: usize = 8;
+
+/// One button on the bar.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct BarAction {
+    pub label: &'static str,
+    /// Key replayed through the normal key handler when tapped.
+    pub key: char,
+}
+
+const fn act(label: &'static str, key: char) -> BarAction {
+    BarAction { label, key }
+}
+
+const CURSOR_UP: BarAction = act("▲", 'k');
+const CURSOR_DOWN: BarAction = act("▼", 'j');
+const PAGE_UP: BarAction = act("▲", PAGE_UP_KEY);
+const PAGE_DOWN: BarAction = act("▼", PAGE_DOWN_KEY);
+const TAB_PREV: BarAction = act("◀", 'h');
+const TAB_NEXT: BarAction = act("▶", 'l');
+const CLOSE: BarAction = act("閉じる", 'b');
+
+/// The actions valid right now, in display order.
+pub fn actions(state: &RpgState) -> Vec<BarAction> {
+    let list = match state.overlay {
+        Some(overlay) => overlay_actions(state, overlay),
+        None => scene_actions(state),
+    };
+    debug_assert!(list.len() <= MAX_BAR_ACTIONS);
+    list
+}
+
+fn overlay_actions(state: &RpgState, overlay: Overlay) -> Vec<BarAction> {
+    match overlay {
+        // Forced picks: no close button.
+        Overlay::SkillChoice => vec![CURSOR_UP, CURSOR_DOWN, act("習得", 'a')],
+        Overlay::AbyssModifier => vec![CURSOR_UP, CURSOR_DOWN, act("選ぶ", 'a')],
+        Overlay::Inventory => {
+            vec![CURSOR_UP, CURSOR_DOWN, act("使う", 'a'), TAB_PREV, TAB_NEXT, CLOSE]
+        }
+        Overlay::SkillMenu => {
+            vec![CURSOR_UP, CURSOR_DOWN, act("唱える", 'a'), TAB_PREV, TAB_NEXT, CLOSE]
+        }
+        Overlay::Status => vec![PAGE_UP, PAGE_DOWN, act("視界", 'v'), TAB_PREV, TAB_NEXT, CLOSE],
+        Overlay::Bestiary => vec![PAGE_UP, PAGE_DOWN, TAB_PREV, TAB_NEXT, CLOSE],
+        Overlay::Shop => vec![CURSOR_UP, CURSOR_DOWN, act("買う", 'a'), CLOSE],
+        Overlay::Crafting => vec![CURSOR_UP, CURSOR_DOWN, act("作る", 'a'), CLOSE],
+        Overlay::DungeonSelect => vec![CURSOR_UP, CURSOR_DOWN, act("出発", 'a'), CLOSE],
+        Overlay::RunLog => vec![PAGE_UP, PAGE_DOWN, act("コピー", 'c'), CLOSE],
+        Overlay::QuestBoard if state.active_quest.is_some() => vec![act("破棄", 'a'), CLOSE],
+        Overlay::QuestBoard => vec![CURSOR_UP, CURSOR_DOWN, act("受ける", 'a'), CLOSE],
+        Overlay::PrayMenu => vec![act("祈る", 'a'), CLOSE],
+    }
+}
+
+fn scene_actions(state: &RpgState) -> Vec<BarAction> {
+    match state.scene {
+        Scene::GameClear if state.game_cleared => vec![act("深淵に挑む", '2')],
+        Scene::GameClear => Vec::new(),
+        Scene::Overworld | Scene::DungeonExplore if state.active_event.is_some() => vec![
+            CURSOR_UP,
+            CURSOR_DOWN,
+            act("決定", 'a'),
+            act("立ち去る", 'b'),
+            act("持ち物", 'i'),
+        ],
+        Scene::Overworld | Scene::DungeonExplore => {
+            let in_town = state.dungeon.as_ref().is_none_or(|m| m.is_overworld);
+            let mut list = Vec::new();
+            if !in_town {
+                list.push(if logic::adjacent_monster(state).is_some() {
+                    act("戦う", 'a')
+                } else {
+                    act("待機", 'a')
+                });
+            }
+            list.extend([act("持ち物", 'i'), act("スキル", 'z'), act("状態", 'x')]);
+            list
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn every_context_fits_the_action_range() {
+        let mut state = RpgState::new();
+        let overlays = [
+            Overlay::Inventory,
+            Overlay::Status,
+            Overlay::Shop,
+            Overlay::SkillMenu,
+            Overlay::QuestBoard,
+            Overlay::PrayMenu,
+            Overlay::SkillChoice,
+            Overlay::AbyssModifier,
+            Overlay::DungeonSelect,
+            Overlay::Crafting,
+            Overlay::RunLog,
+            Overlay::Bestiary,
+        ];
+        for overlay in overlays {
+            state.overlay = Some(overlay);
+            let list = actions(&state);
+            assert!(!list.is_empty() && list.len() <= MAX_BAR_ACTIONS, "{overlay:?}");
+            let forced = matches!(overlay, Overlay::SkillChoice | Overlay::AbyssModifier);
+            assert_eq!(list.contains(&CLOSE), !forced, "{overlay:?}");
+        }
+    }
+
+    #[test]
+    fn town_has_no_wait_button_but_the_dungeon_does() {
+        let mut state = RpgState::new();
+        state.active_event = None;
+        assert!(actions(&state).iter().all(|a| a.key != 'a'));
+
+        logic::enter_dungeon(&mut state, 1);
+        state.active_event = None;
+        let list = actions(&state);
+        assert_eq!(list[0].key, 'a');
+        assert!(list.iter().any(|a| a.key == 'i'));
+    }
+}
@@ -1,16 +1,19 @@
 //! Semantic action IDs for Dungeon Dive click targets.
 
-// ── Scene choices (1-based index) ──────────────────────────────
-pub const CHOICE_BASE: u16 = 10;
+use super::action_bar::MAX_BAR_ACTIONS;
+use crate::action::{assert_disjoint, ActionRange};
+
+// ── Scene choices ──────────────────────────────────────────────
+pub const CHOICE: ActionRange = ActionRange::new(10, 10);
 
 // ── Skill (overlay during dungeon) ─────────────────────────────
-pub const SKILL_BASE: u16 = 30;
+pub const SKILL: ActionRange = ActionRange::new(30, 10);
 
 // ── Overlay: Inventory ─────────────────────────────────────────
-pub const INV_USE_BASE: u16 = 60;
+pub const INV_USE: ActionRange = ActionRange::new(60, 20);
 
 // ── Overlay: Shop ──────────────────────────────────────────────
-pub const SHOP_BUY_BASE: u16 = 80;
+pub const SHOP_BUY: ActionRange = ActionRange::new(80, 20);
 
 // ── Overlay open ──────────────────────────────────────────────
 pub const OPEN_INVENTORY: u16 = 101;
@@ -21,16 +24,16 @@ pub const OPEN_SKILL_MENU: u16 = 103;
 pub const CLOSE_OVERLAY: u16 = 100;
 
 // ── Event choices ─────────────────────────────────────────────
-pub const EVENT_CHOICE_BASE: u16 = 120;
+pub const EVENT_CHOICE: ActionRange = ActionRange::new(120, 10);
 
 // ── Map tap zones (3×3 grid) ─────────────────────────────────
-pub const MAP_TAP_BASE: u16 = 140;
+pub const MAP_TAP: ActionRange = ActionRange::new(140, 9);
 
 // ── D-pad controller (3×3 grid) ─────────────────────────────
-pub const DPAD_BASE: u16 = 150;
+pub const DPAD: ActionRange = ActionRange::new(150, 9);
 
 // ── Quest board ──────────────────────────────────────────────
-pub const QUEST_ACCEPT_BASE: u16 = 170;
+pub const QUEST_ACCEPT: ActionRange = ActionRange::new(170, 5);
 pub const QUEST_ABANDON: u16 = 175;
 
 // ── Pray ─────────────────────────────────────────────────────
@@ -42,14 +45,73 @@ pub const PRAY_CONFIRM: u16 = 180;
 /// Standing on event → confirm primary choice.
 /// Otherwise → wait one turn.
 pub const AB_A_BUTTON: u16 = 190;
-/// Open the unified menu (持ち物 / スキル / ステータス tabs).
+/// Open the unified menu (持ち物 / スキル / ステータス / 図鑑 tabs).
 pub const AB_B_BUTTON: u16 = 191;
 
 // ── Unified menu tab switch ──────────────────────────────────
 pub const MENU_TAB_INVENTORY: u16 = 200;
 pub const MENU_TAB_SKILL: u16 = 201;
 pub const MENU_TAB_STATUS: u16 = 202;
+pub const MENU_TAB_BESTIARY: u16 = 203;
+
+// ── Overlay scroll (▲▼ on long overlays) ─────────────────────
+pub const OVERLAY_SCROLL_UP: u16 = 205;
+pub const OVERLAY_SCROLL_DOWN: u16 = 206;
 
 // ── Skill choice overlay (level-up forced pick) ──────────────
 pub const SKILL_CHOICE_LEFT: u16 = 210;
 pub const SKILL_CHOICE_RIGHT: u16 = 211;
+
+// ── Abyss modifier overlay (curse/boon forced pick, 3 slots) ─
+pub const ABYSS_PICK: ActionRange = ActionRange::new(220, 3);
+
+// ── Overlay: Crafting (workshop) ─────────────────────────────
+pub const CRAFT: ActionRange = ActionRange::new(230, 10);
+
+// ── Overlay: Dungeon select (forest / ruins / abyss) ─────────
+pub const DUNGEON_PICK: ActionRange = ActionRange::new(240, 3);
+
+// ── Overlay: Run log viewer ──────────────────────────────────
+pub const RUN_LOG_COPY: u16 = 250;
+
+// ── Overlay: Status — map view distance toggle ───────────────
+pub const VIEW_DISTANCE: u16 = 251;
+
+// ── Contextual action bar (bottom row: action_bar::actions index) ─
+pub const ACTION_BAR: ActionRange = ActionRange::new(260, MAX_BAR_ACTIONS as u16);
+
+/// この画面の全 ID。重なりはコンパイル時に検査する。
+pub const ALL_ACTIONS: &[ActionRange] = &[
+    ActionRange::single(OPEN_INVENTORY),
+    ActionRange::single(OPEN_STATUS),
+    ActionRange::single(OPEN_SKILL_MENU),
+    ActionRange::single(CLOSE_OVERLAY),
+    ActionRange::single(QUEST_ABANDON),
+    ActionRange::single(PRAY_CONFIRM),
+    ActionRange::single(AB_A_BUTTON),
+    ActionRange::single(AB_B_BUTTON),
+    ActionRange::single(MENU_TAB_INVENTORY),
+    ActionRange::single(MENU_TAB_SKILL),
+    ActionRange::single(MENU_TAB_STATUS),
+    ActionRange::single(MENU_TAB_BESTIARY),
+    ActionRange::single(OVERLAY_SCROLL_UP),
+    ActionRange::single(OVERLAY_SCROLL_DOWN),
+    ActionRange::single(SKILL_CHOICE_LEFT),
+    ActionRange::single(SKILL_CHOICE_RIGHT),
+    ActionRange::single(RUN_LOG_COPY),
+    ActionRange::single(VIEW_DISTANCE),
+    CHOICE,
+    SKILL,
+    INV_USE,
+    SHOP_BUY,
+    EVENT_CHOICE,
+    MAP_TAP,
+    DPAD,
+    QUEST_ACCEPT,
+    ABYSS_PICK,
+    CRAFT,
+    DUNGEON_PICK,
+    ACTION_BAR,
+];
+
+const _: () = assert_disjoint(ALL_ACTIONS);
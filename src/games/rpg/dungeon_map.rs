@@ -7,8 +7,9 @@
 #![allow(clippy::needless_range_loop)]
 
 use super::state::{
-    elite_chance, enemy_affix_info, enemy_info, floor_enemies, vault_chance, CellType, DungeonMap,
-    EnemyAffix, EnemyKind, Facing, MapCell, Monster, Room, Tile, VaultKind, ALL_ENEMY_AFFIXES,
+    elite_chance, enemy_affix_info, enemy_info, floor_enemies, site_enemies, vault_chance,
+    CellType, DungeonMap, DungeonSite, EnemyAffix, EnemyKind, Facing, MapCell, Monster, Room, Tile,
+    VaultKind, ABYSS_WARDEN_INTERVAL, ALL_ENEMY_AFFIXES, FOREST_FLOORS,
 };
 
 // ── RNG (same LCG as logic.rs) ──────────────────────────────
@@ -228,6 +229,68 @@ pub fn generate_map(floor: u32, rng_seed: &mut u64) -> DungeonMap {
     }
 }
 
+/// Generate a floor of the endless abyss (post-clear).
+///
+/// Reuses the deepest regular floor's layout / enemy table (B9F — the
+/// boss arena is never rebuilt) and layers the scaling on top: every
+/// monster's HP is multiplied by `hp_pct`. ATK scaling is applied at
+/// attack time from `RpgState::abyss_enemy_atk_pct`.
+pub fn generate_abyss_map(depth: u32, hp_pct: u32, rng_seed: &mut u64) -> DungeonMap {
+    let mut map = generate_map(super::state::MAX_FLOOR - 1, rng_seed);
+    map.floor_num = super::state::MAX_FLOOR + depth;
+    // Every ABYSS_WARDEN_INTERVAL layers the warden blocks the stairs.
+    if depth.is_multiple_of(ABYSS_WARDEN_INTERVAL) {
+        place_boss_on_stairs(&mut map, EnemyKind::AbyssWarden);
+    }
+    for m in &mut map.monsters {
+        m.max_hp = (m.max_hp * hp_pct / 100).max(1);
+        m.hp = m.max_hp;
+    }
+    map
+}
+
+/// Generate a floor of the forest: the regular layout for `floor` with
+/// the forest's own enemy table, and its lord guarding the stairs of the
+/// bottom floor.
+pub fn generate_forest_map(floor: u32, rng_seed: &mut u64) -> DungeonMap {
+    let mut map = generate_map(floor, rng_seed);
+    let pool = site_enemies(DungeonSite::Forest, floor);
+    for m in &mut map.monsters {
+        m.kind = pool[rng_range(rng_seed, pool.len() as u32) as usize];
+        let info = enemy_info(m.kind);
+        m.max_hp = match m.affix {
+            Some(a) => (info.max_hp * enemy_affix_info(a).hp_pct / 100).max(1),
+            None => info.max_hp,
+        };
+        m.hp = m.max_hp;
+    }
+    if floor >= FOREST_FLOORS {
+        place_boss_on_stairs(&mut map, EnemyKind::ForestLord);
+    }
+    map
+}
+
+/// Put an awake `kind` on the stairs tile (like the Demon Lord on B10F),
+/// replacing anything already standing there.
+fn place_boss_on_stairs(map: &mut DungeonMap, kind: EnemyKind) {
+    let stairs = (0..map.height)
+        .flat_map(|y| (0..map.width).map(move |x| (x, y)))
+        .find(|&(x, y)| map.grid[y][x].cell_type == CellType::Stairs);
+    let Some((x, y)) = stairs else { return; };
+    map.monsters.retain(|m| (m.x, m.y) != (x, y));
+    let info = enemy_info(kind);
+    map.monsters.push(Monster {
+        kind,
+        x,
+        y,
+        hp: info.max_hp,
+        max_hp: info.max_hp,
+        awake: true,
+        charging: false,
+        affix: None,
+    });
+}
+
 /// Spawn monster entities on walkable tiles (room floors, away from
 /// entrance/stairs/event cells). The bottom floor (B10F) spawns the
 /// Demon Lord at the stairs cell as a boss encounter.
@@ -667,7 +730,7 @@ fn place_events(
     let total = candidates.len();
     let dist = room_distribution(floor, total);
 
-    let plan: [(usize, CellType); 11] = [
+    let plan: [(usize, CellType); 12] = [
         (dist.treasures, CellType::Treasure),
         (dist.traps, CellType::Trap),
         (dist.springs, CellType::Spring),
@@ -680,6 +743,8 @@ fn place_events(
         (dist.idols, CellType::Idol),
         (dist.peddlers, CellType::Peddler),
         (dist.eggs, CellType::MonsterEgg),
+        // 野営地は最後に置く (既存イベントの配置を変えないため)。
+        (dist.camps, CellType::Camp),
     ];
 
     let mut placed = 0usize;
@@ -692,8 +757,18 @@ fn place_events(
             break;
         }
     }
+
+    // 旅の商人は居る階と居ない階がある。
+    if rng_range(rng_seed, 100) < MERCHANT_CHANCE_PCT {
+        if let Some(&(x, y)) = candidates.get(placed) {
+            grid[y][x].cell_type = CellType::Merchant;
+        }
+    }
 }
 
+/// 旅の商人がその階に現れる確率 (%)。
+const MERCHANT_CHANCE_PCT: u32 = 35;
+
 struct RoomDist {
     treasures: usize,
     traps: usize,
@@ -706,6 +781,7 @@ struct RoomDist {
     idols: usize,
     peddlers: usize,
     eggs: usize,
+    camps: usize,
 }
 
 /// Room distribution: how many of each event type based on floor and total cells.
@@ -737,6 +813,8 @@ fn room_distribution(floor: u32, total: usize) -> RoomDist {
         idols: n(pct[8]),
         peddlers: n(pct[9]),
         eggs: n(pct[10]),
+        // スタミナを立て直せるよう、どの階にも最低 1 つは野営地を置く。
+        camps: n(0.01).max(1),
     }
 }
 
@@ -1095,6 +1173,36 @@ mod tests {
         }
     }
 
+    #[test]
+    fn abyss_floor_scales_monster_hp_and_has_no_boss() {
+        use super::super::state::{EnemyKind, MAX_FLOOR};
+        let (mut a, mut b) = (7u64, 7u64);
+        let base = generate_map(MAX_FLOOR - 1, &mut a);
+        let abyss = generate_abyss_map(3, 150, &mut b);
+        assert_eq!(abyss.floor_num, MAX_FLOOR + 3);
+        assert_eq!(base.monsters.len(), abyss.monsters.len());
+        for (m0, m1) in base.monsters.iter().zip(&abyss.monsters) {
+            assert_eq!(m1.max_hp, m0.max_hp * 150 / 100);
+            assert_eq!(m1.hp, m1.max_hp);
+        }
+        assert!(abyss.monsters.iter().all(|m| m.kind != EnemyKind::DemonLord));
+    }
+
+    #[test]
+    fn forest_uses_its_own_table_and_lord_guards_the_last_stairs() {
+        let mut seed = 11u64;
+        let first = generate_forest_map(1, &mut seed);
+        let pool = site_enemies(DungeonSite::Forest, 1);
+        assert!(first.monsters.iter().all(|m| pool.contains(&m.kind)));
+
+        let last = generate_forest_map(FOREST_FLOORS, &mut seed);
+        let lord = last.monsters.iter().find(|m| m.kind == EnemyKind::ForestLord).unwrap();
+        assert_eq!(last.grid[lord.y][lord.x].cell_type, CellType::Stairs);
+
+        let warden = generate_abyss_map(ABYSS_WARDEN_INTERVAL, 100, &mut seed);
+        assert!(warden.monsters.iter().any(|m| m.kind == EnemyKind::AbyssWarden));
+    }
+
     #[test]
     fn all_floors_generate_valid_maps() {
         for floor in 1..=10 {
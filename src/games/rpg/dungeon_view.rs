@@ -2,7 +2,14 @@
 //!
 //! Renders a top-down tile map centered on the player. Each tile is 2 chars
 //! wide and 1 row tall. Visibility is room-aware: inside a room you see
-//! the whole room; in a corridor you see a radius-2 area.
+//! the whole room; in a corridor you see a radius-2 area. With the light
+//! out only the 8 neighbours are visible and the remembered map is hidden.
+//!
+//! Visible tiles and the enemies standing on them fade with distance from
+//! the player (a charging enemy stays bright), walls facing open
+//! floor carry per-theme decorations, and corridor tiles touching a room
+//! draw as doorways. How far the view reaches is the player's
+//! [`ViewDistance`](super::state::ViewDistance) (applied by the caller).
 
 // 2D grid rendering uses index-based loops for clarity.
 #![allow(clippy::needless_range_loop)]
@@ -19,13 +26,14 @@ use super::state::{CellType, DungeonMap, FloorTheme, Tile};
 /// Compute the set of (x, y) coordinates that are currently visible.
 /// - In a room: all tiles with the same room_id + 1-tile border around room edges
 /// - In a corridor: all tiles within radius 2 (5x5 square)
-pub fn compute_visibility(map: &DungeonMap) -> HashSet<(usize, usize)> {
+/// - In darkness (`dark`): only the tiles within radius 1, rooms or not
+pub fn compute_visibility(map: &DungeonMap, dark: bool) -> HashSet<(usize, usize)> {
     let mut visible = HashSet::new();
     let px = map.player_x;
     let py = map.player_y;
     let cell = map.player_cell();
 
-    if let Some(room_id) = cell.room_id {
+    if let (false, Some(room_id)) = (dark, cell.room_id) {
         // In a room: find the room and reveal all tiles + 1-tile border
         if let Some(room) = map.rooms.get(room_id as usize) {
             let x_start = room.x.saturating_sub(1);
@@ -39,8 +47,8 @@ pub fn compute_visibility(map: &DungeonMap) -> HashSet<(usize, usize)> {
             }
         }
     } else {
-        // In corridor: radius 2
-        let radius: i32 = 2;
+        // In corridor: radius 2 (1 in darkness)
+        let radius: i32 = if dark { 1 } else { 2 };
         for dy in -radius..=radius {
             for dx in -radius..=radius {
                 let vx = px as i32 + dx;
@@ -69,18 +77,91 @@ fn theme_colors(theme: FloorTheme) -> (Color, Color) {
     }
 }
 
+/// Wall decoration per theme: (2-char glyph, color). Drawn on some of the
+/// walls that face open floor so rooms don't read as flat blocks.
+fn wall_decoration(theme: FloorTheme) -> (&'static str, Color) {
+    match theme {
+        FloorTheme::Village => ("\u{2593}\u{2592}", Color::Rgb(170, 120, 70)),        // ▓▒ planks
+        FloorTheme::MossyRuins => ("\u{2593}\u{2591}", Color::Rgb(90, 160, 80)),      // ▓░ moss
+        FloorTheme::Underground => ("\u{2588}\u{2248}", Color::Rgb(90, 130, 200)),    // █≈ seeping water
+        FloorTheme::AncientTemple => ("\u{259e}\u{259a}", Color::Rgb(210, 180, 100)), // ▞▚ carvings
+        FloorTheme::VolcanicDepths => ("\u{2593}\u{2593}", Color::Rgb(240, 120, 40)), // ▓▓ glowing seams
+        FloorTheme::DemonCastle => ("\u{259a}\u{259e}", Color::Rgb(180, 60, 100)),    // ▚▞ banners
+    }
+}
+
+/// Fully lit floor dot (cells whose marker has no color of their own).
+const FLOOR_LIT: Color = Color::Rgb(170, 170, 170);
+
+/// Enemy colors before depth shading: (awake, asleep), regular then elite.
+/// RGB so [`shade`] can dim them; a charging enemy is never dimmed.
+const ENEMY_COLORS: (Color, Color) = (Color::Rgb(230, 60, 60), Color::Rgb(180, 80, 80));
+const ELITE_COLORS: (Color, Color) = (Color::Rgb(220, 80, 220), Color::Rgb(180, 80, 180));
+
+/// Darken `color` by Chebyshev distance `d` from the player: full
+/// brightness next to the player, down to 40% at the edge of sight.
+/// Named colors (event markers) are left alone so they stay readable.
+fn shade(color: Color, d: i32) -> Color {
+    let k = (1.0 - 0.1 * (d - 1).max(0) as f64).max(0.4);
+    match color {
+        Color::Rgb(r, g, b) => Color::Rgb(
+            (r as f64 * k) as u8,
+            (g as f64 * k) as u8,
+            (b as f64 * k) as u8,
+        ),
+        other => other,
+    }
+}
+
+/// Whether a walkable tile is next to (x, y) in the 4 directions.
+fn faces_floor(map: &DungeonMap, x: usize, y: usize) -> bool {
+    [(0i32, -1i32), (1, 0), (0, 1), (-1, 0)].iter().any(|&(dx, dy)| {
+        let (nx, ny) = (x as i32 + dx, y as i32 + dy);
+        map.in_bounds(nx, ny) && map.cell(nx as usize, ny as usize).is_walkable()
+    })
+}
+
+/// Wall glyph and color at (x, y): a theme decoration on roughly one in
+/// five floor-facing walls (fixed per tile), plain blocks elsewhere.
+fn wall_look(map: &DungeonMap, theme: FloorTheme, x: usize, y: usize, wall_color: Color) -> (String, Color) {
+    if (x * 7 + y * 13).is_multiple_of(5) && faces_floor(map, x, y) {
+        let (glyph, color) = wall_decoration(theme);
+        (glyph.to_string(), color)
+    } else {
+        ("\u{2588}\u{2588}".to_string(), wall_color)
+    }
+}
+
+/// A plain corridor tile that opens onto a room floor.
+fn is_doorway(map: &DungeonMap, x: usize, y: usize) -> bool {
+    let cell = map.cell(x, y);
+    cell.tile == Tile::Corridor
+        && cell.cell_type == CellType::Corridor
+        && [(0i32, -1i32), (1, 0), (0, 1), (-1, 0)].iter().any(|&(dx, dy)| {
+            let (nx, ny) = (x as i32 + dx, y as i32 + dy);
+            map.in_bounds(nx, ny) && map.cell(nx as usize, ny as usize).tile == Tile::RoomFloor
+        })
+}
+
+/// Doorway glyph (2 chars wide).
+const DOORWAY: &str = "\u{25af} "; // ▯
+
 // ── 2D Map Rendering ─────────────────────────────────────────
 
 /// Render a 2D top-down map with tile-based rendering, dynamically sized.
 ///
 /// Each tile is rendered as 2 chars wide × 1 row tall.
 /// Viewport: `n * 2` columns × `n` rows.
+/// `pan` shifts the view center away from the player (in tiles, positive = look left/up).
+/// `dark` (light ran out) shrinks visibility and draws remembered tiles as fog.
 pub fn render_map_2d(
     map: &DungeonMap,
     theme: FloorTheme,
     max_w: usize,
     max_h: usize,
     pet: Option<&super::state::Pet>,
+    pan: (i32, i32),
+    dark: bool,
 ) -> Vec<Line<'static>> {
     let (wall_color, _floor_color) = theme_colors(theme);
     let fog_color = Color::Rgb(25, 25, 25);
@@ -101,9 +182,11 @@ pub fn render_map_2d(
 
     let px = map.player_x as i32;
     let py = map.player_y as i32;
+    // 表示の中心。ドラッグで見回している間はプレイヤーからずらす (タイル単位)
+    let (cx, cy) = (px - pan.0, py - pan.1);
 
     // Compute visibility
-    let visible = compute_visibility(map);
+    let visible = compute_visibility(map, dark);
 
     // Buffer: (2-char string, Style)
     let mut buf: Vec<Vec<(String, Style)>> = Vec::with_capacity(gh);
@@ -117,9 +200,9 @@ pub fn render_map_2d(
     let fg = |c: Color| Style::default().fg(c);
 
     for vy in 0..n {
-        let my = py - radius + vy as i32;
+        let my = cy - radius + vy as i32;
         for vx in 0..n {
-            let mx = px - radius + vx as i32;
+            let mx = cx - radius + vx as i32;
 
             if !map.in_bounds(mx, my) {
                 buf[vy][vx] = ("  ".to_string(), Style::default());
@@ -135,6 +218,8 @@ pub fn render_map_2d(
             // Check for monster/pet at this tile
             let monster = map.monsters.iter().find(|m| m.hp > 0 && m.x == ux && m.y == uy);
             let is_pet = pet.is_some_and(|p| p.x == ux && p.y == uy);
+            // Depth shading: farther tiles (and the enemies on them) are dimmer
+            let d = (mx - px).abs().max((my - py).abs());
 
             if is_player {
                 buf[vy][vx] = ("\u{ff20}".to_string(), fg(Color::White)); // ＠
@@ -146,33 +231,42 @@ pub fn render_map_2d(
                 // must be readable on the map itself, not only in the log.
                 let style = if m.charging {
                     fg(Color::LightRed).add_modifier(Modifier::BOLD)
-                } else if m.affix.is_some() {
-                    // Elite mobs render in magenta so they stand out from regular reds.
-                    if m.awake { fg(Color::Magenta) } else { fg(Color::Rgb(180, 80, 180)) }
-                } else if m.awake {
-                    fg(Color::Red)
                 } else {
-                    fg(Color::Rgb(180, 80, 80))
+                    // Elite mobs render in magenta so they stand out from regular reds.
+                    let (awake, asleep) = if m.affix.is_some() { ELITE_COLORS } else { ENEMY_COLORS };
+                    fg(shade(if m.awake { awake } else { asleep }, d))
                 };
                 buf[vy][vx] = (glyph, style);
             } else if is_visible {
                 match cell.tile {
                     Tile::Wall => {
-                        buf[vy][vx] = ("\u{2588}\u{2588}".to_string(), fg(wall_color));
+                        let (glyph, color) = wall_look(map, theme, ux, uy, wall_color);
+                        buf[vy][vx] = (glyph, fg(shade(color, d)));
                     }
                     Tile::RoomFloor | Tile::Corridor => {
-                        let (ch, color) = cell_marker(cell);
-                        buf[vy][vx] = (ch, fg(color));
+                        let (ch, color) = if is_doorway(map, ux, uy) {
+                            (DOORWAY.to_string(), wall_color)
+                        } else {
+                            cell_marker(cell)
+                        };
+                        let color = if color == Color::Reset { FLOOR_LIT } else { color };
+                        let mut style = fg(shade(color, d));
+                        if cell.cell_type == CellType::Stairs {
+                            style = style.add_modifier(Modifier::BOLD);
+                        }
+                        buf[vy][vx] = (ch, style);
                     }
                 }
-            } else if cell.revealed {
+            } else if cell.revealed && !dark {
                 // Revealed but not currently visible — very dark
                 match cell.tile {
                     Tile::Wall => {
                         buf[vy][vx] = ("\u{2588}\u{2588}".to_string(), fg(dark_wall_color));
                     }
                     Tile::RoomFloor | Tile::Corridor => {
-                        if ch_is_floor(cell) {
+                        if is_doorway(map, ux, uy) {
+                            buf[vy][vx] = (DOORWAY.to_string(), fg(dark_wall_color));
+                        } else if ch_is_floor(cell) {
                             buf[vy][vx] = ("\u{00b7} ".to_string(), fg(dark_floor_color));
                         } else {
                             let (ch, _) = cell_marker(cell);
@@ -231,6 +325,8 @@ fn cell_marker(cell: &super::state::MapCell) -> (String, Color) {
             CellType::Idol => ("\u{2734} ".to_string(), Color::Yellow),
             CellType::Peddler => ("$ ".to_string(), Color::Yellow),
             CellType::MonsterEgg => ("\u{25cf} ".to_string(), Color::Magenta),
+            CellType::Camp => ("\u{25b2} ".to_string(), Color::LightRed),
+            CellType::Merchant => ("M ".to_string(), Color::LightYellow),
             // Overworld branches handled above; unreachable here.
             CellType::DungeonEntrance
             | CellType::ShopTile
@@ -273,7 +369,7 @@ mod tests {
         map.grid[map.player_y][map.player_x].visited = true;
         map.grid[map.player_y][map.player_x].revealed = true;
         // Request space for 11 tiles (max_w=22, max_h=11)
-        let lines = render_map_2d(&map, FloorTheme::MossyRuins, 22, 11, None);
+        let lines = render_map_2d(&map, FloorTheme::MossyRuins, 22, 11, None, (0, 0), false);
         // 11 tiles → 11 rows
         assert_eq!(lines.len(), 11);
         // 11 tiles → 11 spans (each 2-char wide)
@@ -286,7 +382,7 @@ mod tests {
         let mut map = generate_map(1, &mut seed);
         map.grid[map.player_y][map.player_x].visited = true;
         map.grid[map.player_y][map.player_x].revealed = true;
-        let lines = render_map_2d(&map, FloorTheme::Underground, 22, 11, None);
+        let lines = render_map_2d(&map, FloorTheme::Underground, 22, 11, None, (0, 0), false);
         // Player should be at the center
         let center_row = lines.len() / 2;
         let center_col = lines[0].spans.len() / 2;
@@ -294,6 +390,19 @@ mod tests {
         assert_eq!(center_span.content.as_ref(), "\u{ff20}"); // ＠
     }
 
+    #[test]
+    fn map_2d_pan_shifts_player_off_center() {
+        let mut seed = 42u64;
+        let mut map = generate_map(1, &mut seed);
+        map.grid[map.player_y][map.player_x].visited = true;
+        map.grid[map.player_y][map.player_x].revealed = true;
+        let lines = render_map_2d(&map, FloorTheme::Underground, 22, 11, None, (2, 1), false);
+        let center = lines.len() / 2;
+        // 中心を左上へずらすと、＠ は右下に見える
+        assert_eq!(lines[center + 1].spans[center + 2].content.as_ref(), "\u{ff20}");
+        assert_ne!(lines[center].spans[center].content.as_ref(), "\u{ff20}");
+    }
+
     #[test]
     fn チャージ中の敵はマップ上で強調表示される() {
         use ratzilla::ratatui::style::Modifier;
@@ -329,7 +438,7 @@ mod tests {
             });
         }
 
-        let lines = render_map_2d(&map, FloorTheme::Underground, 22, 11, None);
+        let lines = render_map_2d(&map, FloorTheme::Underground, 22, 11, None, (0, 0), false);
         let center = lines.len() / 2;
         let span_at = |pos: (usize, usize)| {
             let vy = (center as i32 + (pos.1 as i32 - py as i32)) as usize;
@@ -353,15 +462,105 @@ mod tests {
         );
     }
 
+    #[test]
+    fn enemies_dim_with_their_distance_from_the_player() {
+        use crate::games::rpg::state::{EnemyKind, Monster};
+
+        let mut seed = 42u64;
+        let mut map = generate_map(1, &mut seed);
+        map.monsters.clear();
+        let (px, py) = (map.player_x as i32, map.player_y as i32);
+        let dist = |&(x, y): &(usize, usize)| (x as i32 - px).abs().max((y as i32 - py).abs());
+        // Nearest and farthest visible floor inside the 11-tile view
+        let mut spots: Vec<(usize, usize)> = compute_visibility(&map, false)
+            .into_iter()
+            .filter(|&(x, y)| map.cell(x, y).is_walkable())
+            .filter(|p| (1..=5).contains(&dist(p)))
+            .collect();
+        spots.sort_by_key(|p| (dist(p), *p));
+        let (near, far) = (spots[0], *spots.last().unwrap());
+        assert!(dist(&far) >= 2, "the start room is bigger than 3x3");
+        for (x, y) in [near, far] {
+            map.monsters.push(Monster {
+                kind: EnemyKind::Golem,
+                x, y, hp: 60, max_hp: 60,
+                awake: true,
+                charging: false,
+                affix: None,
+            });
+        }
+
+        let lines = render_map_2d(&map, FloorTheme::Underground, 22, 11, None, (0, 0), false);
+        let center = (lines.len() / 2) as i32;
+        let fg_at = |(x, y): (usize, usize)| {
+            let vy = (center + y as i32 - py) as usize;
+            let vx = (center + x as i32 - px) as usize;
+            lines[vy].spans[vx].style.fg
+        };
+        assert_eq!(fg_at(near), Some(ENEMY_COLORS.0));
+        assert_eq!(fg_at(far), Some(shade(ENEMY_COLORS.0, dist(&far))));
+        assert_ne!(fg_at(near), fg_at(far));
+    }
+
+    #[test]
+    fn shading_dims_with_distance_but_keeps_named_colors() {
+        let wall = Color::Rgb(100, 100, 100);
+        assert_eq!(shade(wall, 1), wall);
+        assert_eq!(shade(wall, 3), Color::Rgb(80, 80, 80));
+        assert_eq!(shade(wall, 20), Color::Rgb(40, 40, 40));
+        assert_eq!(shade(Color::Yellow, 5), Color::Yellow);
+    }
+
+    #[test]
+    fn corridor_meeting_a_room_is_a_doorway() {
+        let mut seed = 42u64;
+        let map = generate_map(1, &mut seed);
+        let door = (0..map.height)
+            .flat_map(|y| (0..map.width).map(move |x| (x, y)))
+            .find(|&(x, y)| is_doorway(&map, x, y));
+        let (x, y) = door.expect("rooms connect to corridors");
+        assert_eq!(map.cell(x, y).tile, Tile::Corridor);
+        // Room floor itself is never a doorway
+        assert!(!is_doorway(&map, map.player_x, map.player_y));
+    }
+
+    #[test]
+    fn wall_decorations_differ_per_theme_and_face_floor() {
+        let mut seed = 42u64;
+        let map = generate_map(1, &mut seed);
+        let wall_color = Color::Rgb(1, 2, 3);
+        // Walls buried in rock stay plain
+        for y in 0..map.height {
+            for x in 0..map.width {
+                if map.cell(x, y).tile == Tile::Wall && !faces_floor(&map, x, y) {
+                    assert_eq!(wall_look(&map, FloorTheme::MossyRuins, x, y, wall_color).1, wall_color);
+                }
+            }
+        }
+        assert_ne!(
+            wall_decoration(FloorTheme::MossyRuins),
+            wall_decoration(FloorTheme::DemonCastle)
+        );
+    }
+
     #[test]
     fn compute_visibility_room() {
         let mut seed = 42u64;
         let map = generate_map(1, &mut seed);
         // Player starts in a room
-        let vis = compute_visibility(&map);
+        let vis = compute_visibility(&map, false);
         // Should see multiple tiles (the room)
         assert!(vis.len() > 4);
         // Player position should be visible
         assert!(vis.contains(&(map.player_x, map.player_y)));
     }
+
+    #[test]
+    fn darkness_limits_visibility_to_neighbours() {
+        let mut seed = 42u64;
+        let map = generate_map(1, &mut seed);
+        let vis = compute_visibility(&map, true);
+        assert!(vis.len() <= 9);
+        assert!(vis.contains(&(map.player_x, map.player_y)));
+    }
 }
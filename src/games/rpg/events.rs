@@ -3,7 +3,8 @@
 //! inline (monster entities on the grid).
 
 use super::state::{
-    CellType, DungeonEvent, EnemyKind, EventAction, EventChoice, FloorTheme, ItemKind,
+    item_info, CellType, DungeonEvent, EnemyKind, EventAction, EventChoice, FloorTheme, ItemKind,
+    ShopOffer, MAX_FLOOR,
 };
 
 // ── RNG ─────────────────────────────────────────────────────
@@ -41,6 +42,8 @@ pub fn generate_event(
         CellType::Idol => Some(idol_event(theme)),
         CellType::Peddler => Some(peddler_event(rng_seed)),
         CellType::MonsterEgg => Some(monster_egg_event(rng_seed)),
+        CellType::Camp => Some(camp_event(theme)),
+        CellType::Merchant => Some(merchant_event(rng_seed)),
         // Overworld tiles are dispatched separately because their event
         // content depends on RpgState (e.g. "first time meeting" flag).
         // See `logic::generate_overworld_event`.
@@ -111,6 +114,94 @@ fn well_event(theme: FloorTheme) -> DungeonEvent {
     }
 }
 
+fn camp_event(theme: FloorTheme) -> DungeonEvent {
+    let desc = match theme {
+        FloorTheme::MossyRuins => "崩れた柱の陰に、誰かが野営した跡がある。",
+        FloorTheme::Underground => "焚き火の跡と、湿った寝袋が残っている。",
+        FloorTheme::AncientTemple => "回廊の隅に、冒険者の野営地が残されている。",
+        FloorTheme::VolcanicDepths => "熱い岩陰に、火の要らない野営地がある。",
+        FloorTheme::Village | FloorTheme::DemonCastle => "荒らされた野営地。まだ温もりがある…",
+    };
+    DungeonEvent {
+        description: vec![desc.into(), "ここなら一息つけそうだ。".into()],
+        choices: vec![
+            EventChoice {
+                label: "休む (スタミナ全快・満腹度-100・襲撃あり)".into(),
+                action: EventAction::RestAtCamp,
+            },
+            EventChoice {
+                label: "物資を漁る (スタミナ少し・罠あり)".into(),
+                action: EventAction::ScavengeCamp,
+            },
+            EventChoice { label: "立ち去る".into(), action: EventAction::Ignore },
+        ],
+    }
+}
+
+fn merchant_event(rng_seed: &mut u64) -> DungeonEvent {
+    let descs = [
+        "大きな荷を背負った旅の商人がいる。",
+        "灯りの下で、商人が品物を並べている。",
+        "「おや、こんな所で客とは珍しい」",
+    ];
+    let idx = rng_range(rng_seed, descs.len() as u32) as usize;
+    DungeonEvent {
+        description: vec![
+            descs[idx].into(),
+            "地上より割高だが、珍しい品もあるようだ。".into(),
+        ],
+        choices: vec![
+            EventChoice { label: "品物を見る".into(), action: EventAction::BrowseMerchant },
+            EventChoice { label: "立ち去る".into(), action: EventAction::Ignore },
+        ],
+    }
+}
+
+/// 旅の商人の値付け (村の店の値段に対する %)。
+pub const MERCHANT_MARKUP_PCT: u32 = 200;
+
+/// 旅の商人の品ぞろえ。薬草と松明は必ず、消耗品から 2 種、40% で
+/// 村にまだ並ばない一段上の装備を 1 つ。どれも出会い 1 回で買える数に限りがある。
+pub fn merchant_stock(floor: u32, rng_seed: &mut u64) -> Vec<ShopOffer> {
+    let offer = |kind: ItemKind, left: u32| ShopOffer {
+        kind,
+        price: item_info(kind).buy_price * MERCHANT_MARKUP_PCT / 100,
+        left: Some(left),
+    };
+    let mut stock = vec![offer(ItemKind::Herb, 2), offer(ItemKind::Torch, 2)];
+
+    let mut pool = vec![
+        ItemKind::MagicWater,
+        ItemKind::Bread,
+        ItemKind::Jerky,
+        ItemKind::StrengthPotion,
+        ItemKind::ReturnScroll,
+    ];
+    for _ in 0..2 {
+        let i = rng_range(rng_seed, pool.len() as u32) as usize;
+        stock.push(offer(pool.swap_remove(i), 1 + rng_range(rng_seed, 2)));
+    }
+
+    if rng_range(rng_seed, 100) < 40 {
+        let gear: [ItemKind; 2] = match floor {
+            0..=3 => [ItemKind::SteelSword, ItemKind::ChainMail],
+            _ => [ItemKind::HolySword, ItemKind::KnightArmor],
+        };
+        stock.push(offer(gear[rng_range(rng_seed, 2) as usize], 1));
+    }
+    stock
+}
+
+/// 野営地に寄ってくる魔物。深い階ほど手強い。
+fn camp_raider(floor: u32) -> EnemyKind {
+    match floor {
+        0..=2 => EnemyKind::Rat,
+        3..=5 => EnemyKind::Goblin,
+        6..=8 => EnemyKind::Skeleton,
+        _ => EnemyKind::DarkKnight,
+    }
+}
+
 fn idol_event(theme: FloorTheme) -> DungeonEvent {
     let desc = match theme {
         FloorTheme::MossyRuins => "苔むした神像が静かに立っている。",
@@ -278,6 +369,22 @@ fn npc_event(floor: u32, rng_seed: &mut u64) -> DungeonEvent {
 }
 
 fn stairs_event(floor: u32) -> DungeonEvent {
+    // 深淵 (魔王撃破後) の階段はどこまでも続く
+    if floor > MAX_FLOOR {
+        return DungeonEvent {
+            description: vec![
+                "底の見えない階段が続いている。深く潜るほど魔物は強くなる。".into(),
+                format!("現在: 深淵 {}層 (B{}F)", floor - MAX_FLOOR, floor),
+            ],
+            choices: vec![
+                EventChoice {
+                    label: format!("深淵 {}層へ降りる", floor - MAX_FLOOR + 1),
+                    action: EventAction::DescendStairs,
+                },
+                EventChoice { label: "探索を続ける".into(), action: EventAction::Continue },
+            ],
+        };
+    }
     let desc = if floor >= 10 {
         "巨大な扉の前に立っている。向こう側から圧倒的な魔力を感じる。"
     } else {
@@ -300,6 +407,19 @@ fn stairs_event(floor: u32) -> DungeonEvent {
 }
 
 fn entrance_event(floor: u32) -> DungeonEvent {
+    // 深淵は一方通行。引き返すと挑戦終了 (到達層を記録して村へ)
+    if floor > MAX_FLOOR {
+        return DungeonEvent {
+            description: vec![
+                "帰還の魔法陣が淡く光っている。".into(),
+                "戻ると深淵の挑戦は終わる。".into(),
+            ],
+            choices: vec![
+                EventChoice { label: "町に帰還する".into(), action: EventAction::ReturnToTown },
+                EventChoice { label: "探索を続ける".into(), action: EventAction::Continue },
+            ],
+        };
+    }
     if floor <= 1 {
         DungeonEvent {
             description: vec!["入口の階段がある。町へ戻れる。".into()],
@@ -350,6 +470,10 @@ pub struct EventOutcome {
     /// Issue #90: consume one of these from inventory before applying.
     /// If absent the outcome falls back to a "no offering" message.
     pub require_consume: Option<ItemKind>,
+    /// スタミナの増減 (野営地)。9999 は全快。
+    pub stamina_change: i32,
+    /// 旅の商人の店 (Shop overlay) を開く。
+    pub open_merchant: bool,
 }
 
 impl EventOutcome {
@@ -369,6 +493,8 @@ impl EventOutcome {
             spawn_pet: None,
             spawn_hostile: None,
             require_consume: None,
+            stamina_change: 0,
+            open_merchant: false,
         }
     }
 }
@@ -415,7 +541,11 @@ pub fn resolve_event(
             let avoid_chance = 40 + player_level * 5;
             if rng_range(rng_seed, 100) < avoid_chance {
                 EventOutcome {
-                    description: vec!["罠を見破った！ 慎重に回避した。".into()],
+                    description: vec![
+                        "罠を見破った！ 慎重に回避した。".into(),
+                        "外した金具から鉄鉱石を回収した。".into(),
+                    ],
+                    item: Some((ItemKind::IronOre, 1)),
                     ..EventOutcome::empty()
                 }
             } else {
@@ -696,6 +826,57 @@ pub fn resolve_event(
             satiety_change: 250,
             ..EventOutcome::empty()
         },
+        (EventAction::RestAtCamp, CellType::Camp) => {
+            // 30% の確率で寝込みを襲われる (回復はする)。
+            let raided = rng_range(rng_seed, 100) < 30;
+            let mut description = vec![
+                "火を起こして体を休めた。".into(),
+                "スタミナが全快し、傷も少し癒えた。".into(),
+            ];
+            let spawn_hostile = if raided {
+                description.push("…物音で目が覚めた！ 魔物が忍び寄っている！".into());
+                Some(camp_raider(floor))
+            } else {
+                None
+            };
+            EventOutcome {
+                description,
+                hp_change: (10 + floor * 3) as i32,
+                satiety_change: -100,
+                stamina_change: 9999,
+                spawn_hostile,
+                ..EventOutcome::empty()
+            }
+        }
+        (EventAction::ScavengeCamp, CellType::Camp) => {
+            let roll = rng_range(rng_seed, 100);
+            let mut out = EventOutcome {
+                description: vec!["野営地を漁った。".into()],
+                stamina_change: 40,
+                ..EventOutcome::empty()
+            };
+            if roll < 20 {
+                let dmg = 4 + floor * 2;
+                out.description.push(format!("仕掛け罠だ！ {}ダメージ！", dmg));
+                out.hp_change = -(dmg as i32);
+            } else if roll < 50 {
+                out.description.push("使いかけの松明を見つけた。".into());
+                out.item = Some((ItemKind::Torch, 1));
+            } else if roll < 80 {
+                out.description.push("保存食のパンを見つけた。".into());
+                out.item = Some((ItemKind::Bread, 1));
+            } else {
+                let gold = 10 + floor * 8 + rng_range(rng_seed, 15);
+                out.description.push(format!("隠し袋に{}G入っていた！", gold));
+                out.gold = gold as i32;
+            }
+            out
+        }
+        (EventAction::BrowseMerchant, CellType::Merchant) => EventOutcome {
+            description: vec!["「ゆっくり見ていってくれ。ただし数には限りがあるよ」".into()],
+            open_merchant: true,
+            ..EventOutcome::empty()
+        },
         (EventAction::Ignore | EventAction::Continue, _) => EventOutcome {
             description: vec!["先に進むことにした。".into()],
             ..EventOutcome::empty()
@@ -709,14 +890,14 @@ pub fn resolve_event(
 
 fn treasure_reward(floor: u32, rng_seed: &mut u64) -> EventOutcome {
     let roll = rng_range(rng_seed, 100);
-    if roll < 50 {
+    if roll < 45 {
         let gold = 15 + floor * 10 + rng_range(rng_seed, floor * 5);
         EventOutcome {
             description: vec!["宝箱を開けた！".into(), format!("{}Gを手に入れた！", gold)],
             gold: gold as i32,
             ..EventOutcome::empty()
         }
-    } else if roll < 80 {
+    } else if roll < 70 {
         let count = 1 + rng_range(rng_seed, 2);
         EventOutcome {
             description: vec![
@@ -726,6 +907,16 @@ fn treasure_reward(floor: u32, rng_seed: &mut u64) -> EventOutcome {
             item: Some((ItemKind::Herb, count)),
             ..EventOutcome::empty()
         }
+    } else if roll < 85 {
+        let count = 1 + rng_range(rng_seed, 2);
+        EventOutcome {
+            description: vec![
+                "宝箱を開けた！".into(),
+                format!("松明x{}を手に入れた！", count),
+            ],
+            item: Some((ItemKind::Torch, count)),
+            ..EventOutcome::empty()
+        }
     } else {
         let item = if floor >= 6 { ItemKind::StrengthPotion } else { ItemKind::MagicWater };
         let name = match item {
@@ -892,6 +1083,54 @@ mod tests {
         assert!(outcome.spawn_pet.is_some() || outcome.spawn_hostile.is_some());
     }
 
+    #[test]
+    fn camp_rest_restores_stamina_at_a_price() {
+        let mut seed = 42u64;
+        let event = generate_event(CellType::Camp, 4, FloorTheme::Underground, &mut seed).unwrap();
+        assert_eq!(event.choices[0].action, EventAction::RestAtCamp);
+        let outcome = resolve_event(&EventAction::RestAtCamp, CellType::Camp, 4, 1, &mut seed);
+        assert_eq!(outcome.stamina_change, 9999);
+        assert!(outcome.satiety_change < 0);
+        assert!(outcome.hp_change > 0);
+    }
+
+    #[test]
+    fn camp_raids_and_scavenge_traps_happen_sometimes() {
+        let (mut raided, mut trapped) = (false, false);
+        for s in 0..64u64 {
+            let mut seed = s;
+            let rest = resolve_event(&EventAction::RestAtCamp, CellType::Camp, 7, 1, &mut seed);
+            raided |= rest.spawn_hostile == Some(EnemyKind::Skeleton);
+            let scav = resolve_event(&EventAction::ScavengeCamp, CellType::Camp, 7, 1, &mut seed);
+            assert!(scav.stamina_change > 0);
+            trapped |= scav.hp_change < 0;
+        }
+        assert!(raided && trapped);
+    }
+
+    #[test]
+    fn merchant_stock_is_marked_up_and_limited() {
+        for s in 0..32u64 {
+            let mut seed = s;
+            let stock = merchant_stock(2, &mut seed);
+            assert!(stock.iter().any(|o| o.kind == ItemKind::Herb));
+            assert!(stock.iter().any(|o| o.kind == ItemKind::Torch));
+            for o in &stock {
+                assert_eq!(o.price, item_info(o.kind).buy_price * 2, "{:?} は村の倍額", o.kind);
+                assert!(matches!(o.left, Some(1..=2)));
+            }
+            let kinds: Vec<_> = stock.iter().map(|o| o.kind).collect();
+            assert!(kinds.iter().enumerate().all(|(i, k)| !kinds[i + 1..].contains(k)), "品が重複");
+        }
+        let rare = (0..64u64).any(|s| {
+            let mut seed = s;
+            merchant_stock(8, &mut seed)
+                .iter()
+                .any(|o| matches!(o.kind, ItemKind::HolySword | ItemKind::KnightArmor))
+        });
+        assert!(rare, "深層の商人はいずれ最上位の装備を持ってくる");
+    }
+
     #[test]
     fn resolve_stairs_descends() {
         let mut seed = 42u64;
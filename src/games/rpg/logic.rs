@@ -3,16 +3,23 @@
 //! Inline-combat roguelike: player and monsters share the grid.
 //! Each player action triggers a monster turn (chase + attack).
 
-use super::dungeon_map::generate_map;
-use super::events::{generate_event, resolve_event, EventOutcome};
+use crate::arcade;
+use crate::toast::{self, Severity};
+
+use super::dungeon_map::{generate_abyss_map, generate_forest_map, generate_map};
+use super::events::{generate_event, merchant_stock, resolve_event, EventOutcome};
 use super::lore::{atmosphere_text, floor_entry_text, floor_theme};
 use super::overworld_map::generate_overworld;
 use super::state::{
-    affix_info, element_name, enemy_affix_info, enemy_info, item_info, level_stats, shop_items,
-    skill_choice_pair, skill_element, skill_info, CellType, DungeonEvent, EnemyAffix, EnemyKind,
-    EventAction, EventChoice, Facing, InventoryItem, ItemCategory, ItemKind, Monster, Overlay, Pet,
-    PlayerBuffs, Quest, QuestKind, RpgState, Scene, SkillKind, Tile, ALL_AFFIXES, MAX_FLOOR,
-    MAX_LEVEL,
+    abyss_modifier_info, abyss_scale_pct, AbyssModifier, AbyssRun, ABYSS_LEADERBOARD_LEN,
+    ABYSS_MODIFIER_INTERVAL, ALL_ABYSS_MODIFIERS, ALL_DIFFICULTIES, difficulty_info, Difficulty,
+    affix_info, element_name, enemy_affix_info, enemy_info, equipment_upgrade, item_info,
+    known_recipes, level_stats, recipe_info, site_enemies, site_info,
+    skill_choice_pair, skill_element, skill_info, CellType, CraftOutput, DungeonEvent,
+    DungeonSite, EnemyAffix, EnemyKind, EventAction, EventChoice, Facing, InventoryItem,
+    ItemCategory, ItemKind, Monster, Overlay, Pet, PlayerBuffs, Quest, QuestKind, RpgState,
+    RunLogEntry, RunLogKind, Scene, SkillKind, Tile, ALL_AFFIXES, ALL_DUNGEON_SITES,
+    FOREST_FLOORS, LIGHT_DIM, LIGHT_MAX, MAX_FLOOR, MAX_LEVEL, STAMINA_LOW, STAMINA_MAX,
 };
 
 // ── Tick (no-op: command-based game) ─────────────────────────
@@ -36,7 +43,10 @@ pub fn tick(_state: &mut RpgState, _delta_ticks: u32) {}
 pub fn cursor_count(state: &RpgState) -> usize {
     match state.overlay {
         Some(Overlay::Inventory) => state.inventory.len().min(9),
-        Some(Overlay::Shop) => shop_items(state.max_floor_reached).len().min(9),
+        Some(Overlay::Shop) => state.shop_offers().len().min(9),
+        Some(Overlay::Crafting) => known_recipes(state.max_floor_reached).len().min(9),
+        Some(Overlay::DungeonSelect) => ALL_DUNGEON_SITES.len(),
+        Some(Overlay::RunLog) | Some(Overlay::Bestiary) => 0,
         Some(Overlay::SkillMenu) => available_skills(state).len(),
         Some(Overlay::QuestBoard) => {
             if state.active_quest.is_some() {
@@ -54,13 +64,16 @@ pub fn cursor_count(state: &RpgState) -> usize {
         }
         Some(Overlay::Status) => 0,
         Some(Overlay::SkillChoice) => 2,
+        Some(Overlay::AbyssModifier) => {
+            state.abyss.as_ref().map(|a| a.pending_choice.len()).unwrap_or(0)
+        }
         None => match state.scene {
             Scene::Overworld | Scene::DungeonExplore => state
                 .active_event
                 .as_ref()
                 .map(|e| e.choices.len())
                 .unwrap_or(0),
-            Scene::GameClear => 1,
+            Scene::GameClear => 2,
         },
     }
 }
@@ -120,16 +133,33 @@ pub fn enter_overworld(state: &mut RpgState) {
 /// on flags like `met_reception`.
 pub fn generate_overworld_event(state: &RpgState, cell_type: CellType) -> Option<DungeonEvent> {
     match cell_type {
-        CellType::DungeonEntrance => Some(DungeonEvent {
-            description: vec![
-                "ダンジョンの入口だ。深い闇が広がっている。".into(),
-                "奥には魔王が潜んでいるという…".into(),
-            ],
-            choices: vec![
-                EventChoice { label: "降りる (B1F へ)".into(), action: EventAction::EnterDungeon },
-                EventChoice { label: "やめておく".into(), action: EventAction::Ignore },
-            ],
-        }),
+        CellType::DungeonEntrance => {
+            let mut choices = vec![EventChoice {
+                label: format!("{}へ降りる (B1F へ)", site_info(state.site).name),
+                action: EventAction::EnterDungeon,
+            }];
+            let description = if state.game_cleared {
+                choices.push(EventChoice {
+                    label: "深淵に挑む (エンドレス)".into(),
+                    action: EventAction::EnterAbyss,
+                });
+                vec![
+                    "ダンジョンの入口だ。魔王なき後も闇は深まり続けている。".into(),
+                    format!("深淵の最高到達: {}層", state.best_abyss_depth()),
+                ]
+            } else {
+                vec![
+                    "ダンジョンの入口だ。深い闇が広がっている。".into(),
+                    "奥には魔王が潜んでいるという…".into(),
+                ]
+            };
+            choices.push(EventChoice {
+                label: "行き先を選ぶ".into(),
+                action: EventAction::OpenDungeonSelect,
+            });
+            choices.push(EventChoice { label: "やめておく".into(), action: EventAction::Ignore });
+            Some(DungeonEvent { description, choices })
+        }
         CellType::ShopTile => Some(DungeonEvent {
             description: vec![
                 "武器・道具屋。店主が並べた品を勧めてくる。".into(),
@@ -176,18 +206,31 @@ pub fn generate_overworld_event(state: &RpgState, cell_type: CellType) -> Option
                 EventChoice { label: "立ち去る".into(), action: EventAction::Ignore },
             ],
         }),
+        CellType::ReceptionNpc if !state.met_reception => {
+            // 初回: 難易度を選んで初期物資 (薬草x3 / パンx2 / 50G) を受け取る
+            let mut choices: Vec<EventChoice> = ALL_DIFFICULTIES
+                .iter()
+                .map(|&d| {
+                    let info = difficulty_info(d);
+                    EventChoice {
+                        label: format!("{}で始める ({})", info.name, info.description),
+                        action: EventAction::ChooseDifficulty(d),
+                    }
+                })
+                .collect();
+            choices.push(EventChoice { label: "離れる".into(), action: EventAction::Ignore });
+            Some(DungeonEvent {
+                description: vec![
+                    "受付嬢「ようこそ、冒険者さん！」".into(),
+                    "「この先にあるダンジョンには魔物が棲んでいます。」".into(),
+                    "「奥深くには魔王が潜んでいるとか…」".into(),
+                    "「心構えを選んだら、これを持って行ってください」".into(),
+                ],
+                choices,
+            })
+        }
         CellType::ReceptionNpc => {
-            let (desc, label) = if !state.met_reception {
-                (
-                    vec![
-                        "受付嬢「ようこそ、冒険者さん！」".into(),
-                        "「この先にあるダンジョンには魔物が棲んでいます。」".into(),
-                        "「奥深くには魔王が潜んでいるとか…」".into(),
-                        "「まずはこれを持って行ってください」".into(),
-                    ],
-                    "受け取る (薬草x3 / パンx2 / 50G)",
-                )
-            } else if state.game_cleared {
+            let (desc, label) = if state.game_cleared {
                 (
                     vec!["受付嬢「魔王討伐おめでとうございます！」".into()],
                     "雑談する",
@@ -206,13 +249,16 @@ pub fn generate_overworld_event(state: &RpgState, cell_type: CellType) -> Option
                     "話を聞く",
                 )
             };
-            Some(DungeonEvent {
-                description: desc,
-                choices: vec![
-                    EventChoice { label: label.into(), action: EventAction::TalkReception },
-                    EventChoice { label: "離れる".into(), action: EventAction::Ignore },
-                ],
-            })
+            let mut choices =
+                vec![EventChoice { label: label.into(), action: EventAction::TalkReception }];
+            if !state.last_run_log.is_empty() {
+                choices.push(EventChoice {
+                    label: "冒険記録を見る".into(),
+                    action: EventAction::OpenRunLog,
+                });
+            }
+            choices.push(EventChoice { label: "離れる".into(), action: EventAction::Ignore });
+            Some(DungeonEvent { description: desc, choices })
         }
         CellType::BlacksmithNpc => {
             let (desc, label) = if !state.met_blacksmith {
@@ -227,18 +273,21 @@ pub fn generate_overworld_event(state: &RpgState, cell_type: CellType) -> Option
                 (
                     vec![
                         "武具屋の親父「装備が必要なら声をかけてくれ。」".into(),
-                        "「店の方で売ってる。」".into(),
+                        "「素材を持ってくりゃ奥の工房で仕立ててやる。」".into(),
                     ],
                     "話を聞く",
                 )
             };
-            Some(DungeonEvent {
-                description: desc,
-                choices: vec![
-                    EventChoice { label: label.into(), action: EventAction::TalkBlacksmith },
-                    EventChoice { label: "離れる".into(), action: EventAction::Ignore },
-                ],
-            })
+            let mut choices =
+                vec![EventChoice { label: label.into(), action: EventAction::TalkBlacksmith }];
+            if state.met_blacksmith {
+                choices.push(EventChoice {
+                    label: "工房で作る".into(),
+                    action: EventAction::OpenCrafting,
+                });
+            }
+            choices.push(EventChoice { label: "離れる".into(), action: EventAction::Ignore });
+            Some(DungeonEvent { description: desc, choices })
         }
         CellType::VillagerNpc => Some(DungeonEvent {
             description: vec![villager_flavor(state).into()],
@@ -288,6 +337,15 @@ pub fn resolve_overworld_event_choice(state: &mut RpgState, choice_index: usize)
             enter_dungeon(state, 1);
             true
         }
+        EventAction::EnterAbyss => {
+            state.active_event = None;
+            start_abyss(state)
+        }
+        EventAction::OpenDungeonSelect => {
+            state.active_event = None;
+            state.open_overlay(Overlay::DungeonSelect);
+            true
+        }
         EventAction::OpenShop => {
             state.active_event = None;
             state.open_overlay(Overlay::Shop);
@@ -303,6 +361,17 @@ pub fn resolve_overworld_event_choice(state: &mut RpgState, choice_index: usize)
             state.open_overlay(Overlay::PrayMenu);
             true
         }
+        EventAction::OpenCrafting => {
+            state.active_event = None;
+            state.open_overlay(Overlay::Crafting);
+            true
+        }
+        EventAction::OpenRunLog => {
+            state.active_event = None;
+            state.run_log_copied = None;
+            state.open_overlay(Overlay::RunLog);
+            true
+        }
         EventAction::RestAtInn => {
             if state.gold < 10 {
                 state.add_log("お金が足りない (宿代10G)");
@@ -320,46 +389,35 @@ pub fn resolve_overworld_event_choice(state: &mut RpgState, choice_index: usize)
             true
         }
         EventAction::TalkReception => {
-            if !state.met_reception {
-                state.met_reception = true;
-                state.gold += 50;
-                add_item(state, ItemKind::Herb, 3);
-                add_item(state, ItemKind::Bread, 2);
-                state.add_log("薬草x3 / パンx2 / 50G を受け取った！");
-            }
+            grant_reception_kit(state);
+            state.active_event = None;
+            state.cursor = 0;
+            true
+        }
+        EventAction::ChooseDifficulty(difficulty) => {
+            state.difficulty = difficulty;
+            state.add_log(&format!("難易度: {}", difficulty_info(difficulty).name));
+            grant_reception_kit(state);
             state.active_event = None;
             state.cursor = 0;
             true
         }
         EventAction::TalkBlacksmith => {
-            if !state.met_blacksmith {
-                state.met_blacksmith = true;
-                // Codex P2 (#98): Overworld 化でダンジョンを先に経験してから
-                // 武具屋と初対面、というフローが起き得る。その時点で既に
-                // 拾った武器/防具を装備していたら、初期装備で上書きすると
-                // ダウングレードになるので、装備スロットが空のときだけ
-                // 自動装備する。アイテム自体は inventory に必ず追加するので
-                // 不要なら捨てる/置き換えることもできる。
-                state.inventory.push(InventoryItem {
-                    kind: ItemKind::WoodenSword, count: 1, affix: None,
-                });
-                let sword_idx = state.inventory.len() - 1;
-                if state.weapon_idx.is_none() {
-                    state.weapon_idx = Some(sword_idx);
-                }
-                state.inventory.push(InventoryItem {
-                    kind: ItemKind::TravelClothes, count: 1, affix: None,
-                });
-                let armor_idx = state.inventory.len() - 1;
-                if state.armor_idx.is_none() {
-                    state.armor_idx = Some(armor_idx);
-                }
-                state.add_log("木の剣と旅人の服を受け取った！");
-            }
+            grant_blacksmith_kit(state);
             state.active_event = None;
             state.cursor = 0;
             true
         }
+        EventAction::FastStart => {
+            grant_reception_kit(state);
+            grant_blacksmith_kit(state);
+            state.add_log("準備完了。ダンジョンへ向かおう。");
+            // Second tap goes straight down: show the entrance prompt now
+            // instead of making the player walk there.
+            state.active_event = generate_overworld_event(state, CellType::DungeonEntrance);
+            state.cursor = 0;
+            true
+        }
         EventAction::TalkVillager => {
             state.active_event = None;
             state.cursor = 0;
@@ -369,6 +427,66 @@ pub fn resolve_overworld_event_choice(state: &mut RpgState, choice_index: usize)
     }
 }
 
+/// 起動時の「スキップ」確認。一度でも村の導入 (受付嬢・武具屋) を
+/// 済ませたプレイヤーにだけ出す。
+pub fn fast_start_event(difficulty: Difficulty) -> DungeonEvent {
+    DungeonEvent {
+        description: vec![
+            "受付嬢「おかえりなさい、冒険者さん！」".into(),
+            "「いつもの支度で出発しますか？」".into(),
+            format!("(難易度: {} — 変えるときは村で受付嬢へ)", difficulty_info(difficulty).name),
+        ],
+        choices: vec![
+            EventChoice { label: "スキップ (初期装備で出発)".into(), action: EventAction::FastStart },
+            EventChoice { label: "村を見て回る".into(), action: EventAction::Ignore },
+        ],
+    }
+}
+
+/// 村の導入 (受付嬢・武具屋の両方と会話) を終えたか。
+pub fn intro_completed(state: &RpgState) -> bool {
+    state.met_reception && state.met_blacksmith
+}
+
+/// 受付嬢の初回配布 (薬草x3 / パンx2 / 50G)。2 回目以降は何もしない。
+fn grant_reception_kit(state: &mut RpgState) {
+    if !state.met_reception {
+        state.met_reception = true;
+        state.gold += 50;
+        add_item(state, ItemKind::Herb, 3);
+        add_item(state, ItemKind::Bread, 2);
+        state.add_log("薬草x3 / パンx2 / 50G を受け取った！");
+    }
+}
+
+/// 武具屋の初回配布 (木の剣 / 旅人の服)。2 回目以降は何もしない。
+fn grant_blacksmith_kit(state: &mut RpgState) {
+    if !state.met_blacksmith {
+        state.met_blacksmith = true;
+        // Codex P2 (#98): Overworld 化でダンジョンを先に経験してから
+        // 武具屋と初対面、というフローが起き得る。その時点で既に
+        // 拾った武器/防具を装備していたら、初期装備で上書きすると
+        // ダウングレードになるので、装備スロットが空のときだけ
+        // 自動装備する。アイテム自体は inventory に必ず追加するので
+        // 不要なら捨てる/置き換えることもできる。
+        state.inventory.push(InventoryItem {
+            kind: ItemKind::WoodenSword, count: 1, affix: None,
+        });
+        let sword_idx = state.inventory.len() - 1;
+        if state.weapon_idx.is_none() {
+            state.weapon_idx = Some(sword_idx);
+        }
+        state.inventory.push(InventoryItem {
+            kind: ItemKind::TravelClothes, count: 1, affix: None,
+        });
+        let armor_idx = state.inventory.len() - 1;
+        if state.armor_idx.is_none() {
+            state.armor_idx = Some(armor_idx);
+        }
+        state.add_log("木の剣と旅人の服を受け取った！");
+    }
+}
+
 // ── Quests ───────────────────────────────────────────────────
 
 /// Available quests at the town board (regenerates on visit).
@@ -518,19 +636,37 @@ fn pick_random_weapon(max_floor: u32) -> ItemKind {
 
 // ── Dungeon: Grid-Based Exploration ───────────────────────────
 
+/// Clear the per-run counters when a fresh descent starts (B1F or abyss 1).
+fn reset_run_stats(state: &mut RpgState) {
+    state.run_gold_earned = 0;
+    state.run_exp_earned = 0;
+    state.run_enemies_killed = 0;
+    state.run_rooms_explored = 0;
+    state.prayed_this_run = false;
+    state.buffs = PlayerBuffs::default();
+    state.no_damage_streak = 0;
+    state.light = LIGHT_MAX;
+    state.stamina = STAMINA_MAX;
+    state.run_log.clear();
+}
+
 pub fn enter_dungeon(state: &mut RpgState, floor: u32) {
     let first_entry = state.max_floor_reached == 0;
 
     if floor == 1 {
-        state.run_gold_earned = 0;
-        state.run_exp_earned = 0;
-        state.run_enemies_killed = 0;
-        state.run_rooms_explored = 0;
-        state.prayed_this_run = false;
-        state.buffs = PlayerBuffs::default();
+        reset_run_stats(state);
     }
 
-    let mut map = generate_map(floor, &mut state.rng_seed);
+    // B(MAX_FLOOR+1)F 以降は深淵。通常の生成に倍率を重ねる。
+    let abyss_depth = floor.saturating_sub(MAX_FLOOR);
+    let mut map = if abyss_depth > 0 {
+        state.abyss.get_or_insert_with(AbyssRun::default).depth = abyss_depth;
+        generate_abyss_map(abyss_depth, state.abyss_enemy_hp_pct(), &mut state.rng_seed)
+    } else if state.site == DungeonSite::Forest {
+        generate_forest_map(floor, &mut state.rng_seed)
+    } else {
+        generate_map(floor, &mut state.rng_seed)
+    };
 
     let px = map.player_x;
     let py = map.player_y;
@@ -563,8 +699,12 @@ pub fn enter_dungeon(state: &mut RpgState, floor: u32) {
     state.dungeon = Some(map);
     state.scene = Scene::DungeonExplore;
     state.active_event = None;
+    let site_name = site_info(state.current_site()).name;
+    state.record(RunLogKind::Floor, format!("{} B{}F に到着", site_name, floor));
 
-    if floor > state.max_floor_reached {
+    // 深淵は別記録 (abyss_records)。max_floor_reached はショップ等の解放に使うので
+    // 本編の最深 (MAX_FLOOR) で頭打ちにする。
+    if floor > state.max_floor_reached && abyss_depth == 0 {
         state.max_floor_reached = floor;
     }
 
@@ -586,8 +726,119 @@ pub fn enter_dungeon(state: &mut RpgState, floor: u32) {
         texts.push("  満腹度が0になるとHPが減るので食料を持参".into());
     }
 
+    if abyss_depth > 0 {
+        texts.push(String::new());
+        texts.push(format!(
+            "深淵 {}層 — 敵の強さ {}%",
+            abyss_depth,
+            abyss_scale_pct(abyss_depth)
+        ));
+    }
+
     state.scene_text = texts;
     state.add_log(&format!("B{}Fに踏み込んだ…", floor));
+
+    if abyss_depth > 0 && abyss_depth.is_multiple_of(ABYSS_MODIFIER_INTERVAL) {
+        offer_abyss_modifiers(state);
+    }
+}
+
+// ── Endless Abyss (post-clear) ───────────────────────────────
+
+/// 魔王撃破後のエンドレス深淵を 1 層目から始める。未クリアなら何もしない。
+pub fn start_abyss(state: &mut RpgState) -> bool {
+    if !state.game_cleared {
+        return false;
+    }
+    reset_run_stats(state);
+    state.abyss = Some(AbyssRun::default());
+    state.close_overlay();
+    enter_dungeon(state, MAX_FLOOR + 1);
+    state.add_log("深淵へ足を踏み入れた。どこまで潜れるか…");
+    true
+}
+
+/// 行き先選択 (DungeonSelect) で `idx` 番目のダンジョンに挑む。
+/// 未解禁なら何もしない。深淵は [`start_abyss`] に任せる。
+pub fn enter_site(state: &mut RpgState, idx: usize) -> bool {
+    let Some(&site) = ALL_DUNGEON_SITES.get(idx) else {
+        return false;
+    };
+    if !state.site_unlocked(site) {
+        state.add_log(&format!("まだ行けない ({})", site_info(site).unlock_hint));
+        return false;
+    }
+    if site == DungeonSite::Abyss {
+        return start_abyss(state);
+    }
+    state.site = site;
+    state.close_overlay();
+    enter_dungeon(state, 1);
+    true
+}
+
+/// 今の階の階段が、そのダンジョンの出口 (踏破済みの最下層) か。
+/// 遺跡の最下層は魔王戦 → クリア画面、深淵は底なしなので森だけ。
+fn is_site_exit(state: &RpgState, floor: u32) -> bool {
+    state.abyss.is_none() && state.site == DungeonSite::Forest && floor >= FOREST_FLOORS
+}
+
+/// まだ選んでいない呪い/恩恵から 3 つ (残りが少なければその数) を
+/// 候補にして選択オーバーレイを開く。候補が尽きていれば何もしない。
+fn offer_abyss_modifiers(state: &mut RpgState) {
+    let owned = state.abyss.as_ref().map(|a| a.modifiers.clone()).unwrap_or_default();
+    let mut pool: Vec<AbyssModifier> =
+        ALL_ABYSS_MODIFIERS.iter().copied().filter(|m| !owned.contains(m)).collect();
+    let mut picks = Vec::new();
+    while picks.len() < 3 && !pool.is_empty() {
+        let i = rng_range(state, pool.len() as u32) as usize;
+        picks.push(pool.remove(i));
+    }
+    if picks.is_empty() {
+        return;
+    }
+    if let Some(run) = &mut state.abyss {
+        run.pending_choice = picks;
+        state.open_overlay(Overlay::AbyssModifier);
+        state.add_log("深淵の囁き… 呪いか恩恵を1つ選べ");
+    }
+}
+
+/// 3 択から `idx` 番目の呪い/恩恵を選ぶ。候補が無ければ何もしない。
+pub fn confirm_abyss_modifier(state: &mut RpgState, idx: usize) -> bool {
+    let Some(run) = &mut state.abyss else { return false; };
+    let Some(&pick) = run.pending_choice.get(idx) else { return false; };
+    run.modifiers.push(pick);
+    run.pending_choice.clear();
+    state.close_overlay();
+    let info = abyss_modifier_info(pick);
+    let kind = if info.is_curse { "呪い" } else { "恩恵" };
+    state.add_log(&format!("{}「{}」を受けた: {}", kind, info.name, info.description));
+    true
+}
+
+/// 到達層 `depth` をランキング (降順) に入れる。入賞したら順位 (0 始まり)。
+pub fn record_abyss_depth(records: &mut Vec<u32>, depth: u32) -> Option<usize> {
+    if depth == 0 {
+        return None;
+    }
+    let rank = records.iter().position(|&d| depth > d).unwrap_or(records.len());
+    if rank >= ABYSS_LEADERBOARD_LEN {
+        return None;
+    }
+    records.insert(rank, depth);
+    records.truncate(ABYSS_LEADERBOARD_LEN);
+    Some(rank)
+}
+
+/// 深淵の挑戦を終える (帰還・死亡時)。到達層をランキングに記録する。
+fn end_abyss_run(state: &mut RpgState) {
+    let Some(run) = state.abyss.take() else { return; };
+    match record_abyss_depth(&mut state.abyss_records, run.depth) {
+        Some(0) => state.add_log(&format!("深淵 {}層 — 自己ベスト更新！", run.depth)),
+        Some(rank) => state.add_log(&format!("深淵 {}層 — ランキング{}位", run.depth, rank + 1)),
+        None => state.add_log(&format!("深淵 {}層で挑戦を終えた", run.depth)),
+    }
 }
 
 fn reveal_room(map: &mut super::state::DungeonMap, x: usize, y: usize) {
@@ -624,6 +875,7 @@ pub fn try_move(state: &mut RpgState, dir: Facing) -> bool {
         }
         MoveAction::AttackMonster(idx) => {
             attack_monster(state, idx);
+            spend_stamina(state, ATTACK_STAMINA_COST);
             on_player_action(state);
             true
         }
@@ -638,6 +890,7 @@ pub fn try_move(state: &mut RpgState, dir: Facing) -> bool {
                 map.last_dir = dir;
             }
             after_move(state, nx, ny);
+            spend_stamina(state, MOVE_STAMINA_COST);
             on_player_action(state);
             true
         }
@@ -648,12 +901,35 @@ pub fn try_move(state: &mut RpgState, dir: Facing) -> bool {
                 map.last_dir = dir;
             }
             after_move(state, nx, ny);
+            spend_stamina(state, MOVE_STAMINA_COST);
             on_player_action(state);
             true
         }
     }
 }
 
+/// 1 マス歩くときのスタミナ消費。
+const MOVE_STAMINA_COST: u32 = 1;
+/// 攻撃 1 回のスタミナ消費。
+const ATTACK_STAMINA_COST: u32 = 2;
+/// その場で一息入れたときのスタミナ回復。
+const WAIT_STAMINA_GAIN: u32 = 1;
+
+/// ダンジョン内の行動でスタミナを減らす。村では減らない。
+fn spend_stamina(state: &mut RpgState, cost: u32) {
+    if state.dungeon.as_ref().is_none_or(|m| m.is_overworld) || state.stamina == 0 {
+        return;
+    }
+    let before = state.stamina;
+    state.stamina = state.stamina.saturating_sub(cost);
+    if before > STAMINA_LOW && state.stamina <= STAMINA_LOW {
+        state.add_log("疲れてきた…");
+    }
+    if state.stamina == 0 {
+        state.add_log("スタミナが尽きた！ 攻撃に力が入らない…");
+    }
+}
+
 enum MoveAction {
     Blocked,
     Walk,
@@ -688,6 +964,7 @@ fn compute_move_target(state: &RpgState, dir: Facing) -> (MoveAction, usize, usi
 }
 
 fn after_move(state: &mut RpgState, nx: usize, ny: usize) {
+    let dark = state.is_dark();
     let was_visited;
     let cell_type;
     let event_done;
@@ -697,7 +974,10 @@ fn after_move(state: &mut RpgState, nx: usize, ny: usize) {
         was_visited = map.grid[ny][nx].visited;
         map.grid[ny][nx].visited = true;
         map.grid[ny][nx].revealed = true;
-        reveal_room(map, nx, ny);
+        // 暗闇では足元しか分からない。
+        if !dark {
+            reveal_room(map, nx, ny);
+        }
         cell_type = map.grid[ny][nx].cell_type;
         event_done = map.grid[ny][nx].event_done;
         is_overworld = map.is_overworld;
@@ -719,7 +999,11 @@ fn after_move(state: &mut RpgState, nx: usize, ny: usize) {
             state.cursor = 0;
         }
     } else if cell_type != CellType::Corridor && !event_done {
-        if let Some(event) = generate_event(cell_type, floor, theme, &mut state.rng_seed) {
+        if let Some(mut event) = generate_event(cell_type, floor, theme, &mut state.rng_seed) {
+            if cell_type == CellType::Stairs && is_site_exit(state, floor) {
+                event.description = vec!["木々の切れ間から村の灯りが見える。".into()];
+                event.choices[0].label = "森を抜けて村へ戻る".into();
+            }
             // Stay in DungeonExplore — the event is rendered as a popup
             // overlay on the same scene (see issue #89).
             state.active_event = Some(event);
@@ -739,10 +1023,35 @@ pub fn wait_in_place(state: &mut RpgState) -> bool {
         return false;
     }
     state.add_log("一息入れた…");
+    if !state.dungeon.as_ref().is_some_and(|m| m.is_overworld) {
+        state.stamina = (state.stamina + WAIT_STAMINA_GAIN).min(STAMINA_MAX);
+    }
     on_player_action(state);
     true
 }
 
+/// ドラッグ量ぶん 2D マップの表示中心をずらす。マップ外へ見回しすぎないよう
+/// マップの大きさでクランプする。マップが無い画面では何もしない。
+pub fn pan_map(state: &mut RpgState, delta: (i16, i16)) -> bool {
+    let Some(map) = &state.dungeon else {
+        return false;
+    };
+    let (max_x, max_y) = (map.width as i32 * 2, map.height as i32);
+    state.map_pan = (
+        (state.map_pan.0 + delta.0 as i32).clamp(-max_x, max_x),
+        (state.map_pan.1 + delta.1 as i32).clamp(-max_y, max_y),
+    );
+    true
+}
+
+/// 2D マップの描画距離を 遠 → 近 → 中 の順に切り替える。ターンは進めない。
+pub fn cycle_view_distance(state: &mut RpgState) -> bool {
+    state.view_distance = state.view_distance.next();
+    let msg = format!("視界の描画距離: {}", state.view_distance.name());
+    state.add_log(&msg);
+    true
+}
+
 /// Move in a direction with auto-walk through corridors.
 pub fn move_direction(state: &mut RpgState, dir: Facing) -> bool {
     if !try_move(state, dir) {
@@ -853,13 +1162,15 @@ pub fn attack_monster(state: &mut RpgState, idx: usize) {
         }
     }
 
-    let damage = base + bonus;
+    // スタミナ切れでは力が入らず、与えるダメージが半分になる。
+    let exhausted = state.is_exhausted();
+    let damage = if exhausted { ((base + bonus) / 2).max(1) } else { base + bonus };
 
     {
         let m = &mut state.dungeon.as_mut().unwrap().monsters[idx];
         m.hp = m.hp.saturating_sub(damage);
-        m.awake = true;
     }
+    wake_monster(state, idx);
 
     let weak_str = match einfo.weakness {
         Some(e) if Some(e) == player_element
@@ -871,6 +1182,20 @@ pub fn attack_monster(state: &mut RpgState, idx: usize) {
     } else {
         state.add_log(&format!("{}に{}ダメージ{}", m_name, damage, weak_str));
     }
+    state.record(
+        RunLogKind::Dealt(damage),
+        format!(
+            "{}に{} (ATK{} - DEF{}/2{}{}{}){}",
+            m_name,
+            damage,
+            player_atk,
+            eff_def,
+            if is_crit { " ×1.5" } else { "" },
+            if bonus > 0 { format!(" +属性{}", bonus) } else { String::new() },
+            if exhausted { " ÷2 疲労" } else { "" },
+            weak_str
+        ),
+    );
     if !weak_str.is_empty() {
         note_weakness_discovery(state, kind);
     }
@@ -904,13 +1229,43 @@ fn on_monster_killed(state: &mut RpgState, idx: usize, kind: EnemyKind, _hp_befo
         }
         None => (info.gold, info.exp),
     };
+    // No-damage streak: bonus from the streak built *before* this kill,
+    // then the clean kill extends it.
+    let streak_pct = state.streak_bonus_pct();
+    let (gold, exp) = (gold * streak_pct / 100, exp * streak_pct / 100);
+    // Abyss: rewards scale with depth like the enemies do.
+    let abyss_pct = abyss_scale_pct(state.abyss_depth());
+    let (mut gold, exp) = (gold * abyss_pct / 100, exp * abyss_pct / 100);
+    if state.has_abyss_modifier(AbyssModifier::Fortune) {
+        gold = gold * 3 / 2;
+    }
+    state.no_damage_streak += 1;
     state.exp += exp;
     state.gold += gold;
     state.run_gold_earned += gold;
     state.run_exp_earned += exp;
     state.run_enemies_killed += 1;
-    state.add_log(&format!("{}を倒した！ EXP+{} +{}G", display_name, exp, gold));
+    if streak_pct > 100 {
+        state.add_log(&format!(
+            "{}を倒した！ EXP+{} +{}G (無傷×{}.{})",
+            display_name, exp, gold, streak_pct / 100, streak_pct % 100 / 10
+        ));
+    } else {
+        state.add_log(&format!("{}を倒した！ EXP+{} +{}G", display_name, exp, gold));
+    }
+    state.record(
+        RunLogKind::Kill,
+        format!("{}を倒した EXP+{} +{}G", display_name, exp, gold),
+    );
     note_weakness_discovery(state, kind);
+    if state.note_kill(kind) {
+        toast::push(Severity::Success, format!("📖 図鑑: {}の撃破を記録", info.name));
+    }
+
+    if state.has_abyss_modifier(AbyssModifier::Bloodlust) {
+        let heal = (state.effective_max_hp() / 10).max(1);
+        state.hp = (state.hp + heal).min(state.effective_max_hp());
+    }
 
     // Drop
     if let Some((drop_item, pct)) = info.drop {
@@ -928,6 +1283,7 @@ fn on_monster_killed(state: &mut RpgState, idx: usize, kind: EnemyKind, _hp_befo
             };
             if m_affix.is_some() { affixed_chance += 30; }
             add_item(state, drop_item, 1);
+            state.note_drop(kind, false);
             state.add_log(&format!("{}をドロップ！", item_info(drop_item).name));
             // Bonus affixed equipment chance
             if rng_range(state, 100) < affixed_chance {
@@ -935,6 +1291,21 @@ fn on_monster_killed(state: &mut RpgState, idx: usize, kind: EnemyKind, _hp_befo
             }
         }
     }
+    if let Some((material, pct)) = info.material {
+        if rng_range(state, 100) < pct {
+            add_item(state, material, 1);
+            state.note_drop(kind, true);
+            state.add_log(&format!("素材: {}を手に入れた", item_info(material).name));
+        }
+    }
+
+    // Site loot table (separate from the per-enemy drop)
+    for &(item, pct) in site_info(state.current_site()).loot {
+        if rng_range(state, 100) < pct {
+            add_item(state, item, 1);
+            state.add_log(&format!("{}を拾った", item_info(item).name));
+        }
+    }
 
     // Quest progress: Slay
     let floor = state.dungeon.as_ref().unwrap().floor_num;
@@ -949,8 +1320,22 @@ fn on_monster_killed(state: &mut RpgState, idx: usize, kind: EnemyKind, _hp_befo
 
     check_level_up(state);
 
+    // Site bosses other than the Demon Lord open the way onward.
+    if kind == EnemyKind::ForestLord && !state.site_cleared(DungeonSite::Forest) {
+        state.cleared_sites.push(DungeonSite::Forest);
+        state.add_log("森の主を倒した！ 古代遺跡への道が開けた");
+    }
+    if kind == EnemyKind::AbyssWarden && !state.site_cleared(DungeonSite::Abyss) {
+        state.cleared_sites.push(DungeonSite::Abyss);
+        state.add_log("深淵の番人を討った！ 闇の底はまだ続いている…");
+    }
+    if matches!(kind, EnemyKind::ForestLord | EnemyKind::AbyssWarden) {
+        arcade::earn(enemy_info(kind).name, arcade::BOSS_TOKENS);
+    }
+
     // Game clear (Demon Lord)
     if kind == EnemyKind::DemonLord {
+        arcade::earn(enemy_info(kind).name, arcade::FINAL_BOSS_TOKENS);
         state.game_cleared = true;
         state.total_clears += 1;
         state.faith = state.faith.saturating_add(20);
@@ -999,6 +1384,9 @@ pub fn on_player_action(state: &mut RpgState) {
     tick_satiety(state);
     if state.hp == 0 { return; }
 
+    // Light (may spawn an ambusher before monsters act)
+    tick_light(state);
+
     // Wake monsters
     wake_monsters(state);
 
@@ -1025,7 +1413,8 @@ fn tick_satiety(state: &mut RpgState) {
     // rate keeps food meaningful (still depletes mid-floor) without making
     // it the dominant failure mode.
     if state.satiety > 0 {
-        if state.turn_count.is_multiple_of(2) {
+        // Abyss Famine curse: drain every turn.
+        if state.turn_count.is_multiple_of(2) || state.has_abyss_modifier(AbyssModifier::Famine) {
             state.satiety -= 1;
         }
         // Hunger thresholds
@@ -1045,6 +1434,39 @@ fn tick_satiety(state: &mut RpgState) {
     }
 }
 
+/// 闇からの奇襲率 (‰ / ターン)。明かりが尽きているとき。
+const DARK_AMBUSH_PERMILLE: u32 = 50;
+/// 火が弱い (`LIGHT_DIM` 未満) ときの奇襲率 (‰ / ターン)。
+const DIM_AMBUSH_PERMILLE: u32 = 5;
+
+fn tick_light(state: &mut RpgState) {
+    if state.light > 0 {
+        state.light -= 1;
+        if state.light == LIGHT_DIM {
+            state.add_log("松明の火が弱くなってきた…");
+        }
+        if state.light == 0 {
+            state.add_log("明かりが尽きた！ 周りが何も見えない…");
+        }
+    }
+    let permille = if state.light == 0 {
+        DARK_AMBUSH_PERMILLE
+    } else if state.light < LIGHT_DIM {
+        DIM_AMBUSH_PERMILLE
+    } else {
+        return;
+    };
+    if rng_range(state, 1000) >= permille {
+        return;
+    }
+    // 魔王の階では魔王を湧かせず、その手前の階の敵を使う。
+    let floor = state.dungeon.as_ref().map(|d| d.floor_num).unwrap_or(1).min(MAX_FLOOR - 1);
+    let pool = site_enemies(state.current_site(), floor);
+    let kind = pool[rng_range(state, pool.len() as u32) as usize];
+    state.add_log(&format!("闇の中から{}が襲いかかってきた！", enemy_info(kind).name));
+    spawn_hostile_near_player(state, kind);
+}
+
 /// Wake monsters that share the player's room or are within 3 tiles
 /// (corridor pursuit). Wakes all monsters in the same room when player
 /// enters; in corridors, radius-3 awareness.
@@ -1075,9 +1497,31 @@ fn wake_monsters(state: &mut RpgState) {
         })
         .collect();
 
-    if !to_wake.is_empty() {
-        let map = state.dungeon.as_mut().unwrap();
-        for i in to_wake { map.monsters[i].awake = true; }
+    for i in to_wake {
+        wake_monster(state, i);
+    }
+}
+
+/// Wake `monsters[idx]`. The moment a monster notices the player counts
+/// as one encounter in the bestiary.
+fn wake_monster(state: &mut RpgState, idx: usize) {
+    let Some(m) = state.dungeon.as_mut().and_then(|d| d.monsters.get_mut(idx)) else {
+        return;
+    };
+    if m.awake {
+        return;
+    }
+    m.awake = true;
+    let kind = m.kind;
+    note_bestiary_encounter(state, kind);
+}
+
+/// Count an encounter; a species seen for the first time gets a toast.
+fn note_bestiary_encounter(state: &mut RpgState, kind: EnemyKind) {
+    if state.note_encounter(kind) {
+        let name = enemy_info(kind).name;
+        state.add_log(&format!("図鑑に{}が登録された", name));
+        toast::push(Severity::Info, format!("📖 図鑑に新しい項目: {}", name));
     }
 }
 
@@ -1114,7 +1558,7 @@ fn monster_act(state: &mut RpgState, idx: usize) {
         };
         (
             m.x, m.y, m.charging, enemy_info(m.kind).can_charge, m.awake, m.hp,
-            m.effective_atk(), m.affix, m.display_name(),
+            m.effective_atk() * state.abyss_enemy_atk_pct() / 100, m.affix, m.display_name(),
         )
     };
     if hp == 0 || !awake { return; }
@@ -1131,11 +1575,26 @@ fn monster_act(state: &mut RpgState, idx: usize) {
     if charging {
         // Release charged attack
         if adjacent_to_player {
-            let damage = (eff_atk * 2).saturating_sub(state.total_def() / 2).max(1);
+            let damage = monster_hit_damage(state, eff_atk, m_affix, true);
             state.hp = state.hp.saturating_sub(damage);
             state.add_log(&format!("{}の渾身の一撃！ {}ダメージ！", m_name, damage));
+            state.record(
+                RunLogKind::Taken(damage),
+                format!(
+                    "{}の渾身の一撃で{} (ATK{}×2 - DEF{}/2)",
+                    m_name,
+                    damage,
+                    eff_atk,
+                    state.total_def()
+                ),
+            );
+            break_no_damage_streak(state);
         } else {
             state.add_log(&format!("{}の渾身の一撃は空振り…", m_name));
+            // Reading the telegraph and stepping away is the skill the
+            // streak rewards — count it like a clean win.
+            state.no_damage_streak += 1;
+            state.add_log(&format!("見切り！ 無傷連勝 {}", state.no_damage_streak));
         }
         state.dungeon.as_mut().unwrap().monsters[idx].charging = false;
         return;
@@ -1152,12 +1611,14 @@ fn monster_act(state: &mut RpgState, idx: usize) {
             return;
         }
         // Normal attack (+ Burning elites add fire splash damage)
-        let mut damage = eff_atk.saturating_sub(state.total_def() / 2).max(1);
-        if m_affix == Some(EnemyAffix::Burning) {
-            damage += 3;
-        }
+        let damage = monster_hit_damage(state, eff_atk, m_affix, false);
         state.hp = state.hp.saturating_sub(damage);
         state.add_log(&format!("{}の攻撃！ {}ダメージ！", m_name, damage));
+        state.record(
+            RunLogKind::Taken(damage),
+            format!("{}の攻撃で{} (ATK{} - DEF{}/2)", m_name, damage, eff_atk, state.total_def()),
+        );
+        break_no_damage_streak(state);
         return;
     }
 
@@ -1171,6 +1632,82 @@ fn monster_act(state: &mut RpgState, idx: usize) {
     }
 }
 
+/// Damage one monster hit deals to the player. `eff_atk` already includes
+/// the abyss ATK scaling. A charged release doubles ATK; Burning elites add
+/// fire splash to normal hits only.
+fn monster_hit_damage(
+    state: &RpgState,
+    eff_atk: u32,
+    affix: Option<EnemyAffix>,
+    charged: bool,
+) -> u32 {
+    let def = state.total_def() / 2;
+    let damage = if charged {
+        (eff_atk * 2).saturating_sub(def).max(1)
+    } else {
+        let base = eff_atk.saturating_sub(def).max(1);
+        if affix == Some(EnemyAffix::Burning) { base + 3 } else { base }
+    };
+    abyss_damage_taken(state, damage)
+}
+
+/// How dangerous a monster is to the player right now.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Threat {
+    Low,
+    Medium,
+    High,
+}
+
+impl Threat {
+    /// Shape-coded level (▲ count) so it reads without relying on color.
+    pub fn glyph(self) -> &'static str {
+        match self {
+            Threat::Low => "▲",
+            Threat::Medium => "▲▲",
+            Threat::High => "▲▲▲",
+        }
+    }
+
+    pub fn label(self) -> &'static str {
+        match self {
+            Threat::Low => "低",
+            Threat::Medium => "中",
+            Threat::High => "高",
+        }
+    }
+}
+
+/// Threat of `m`: how many of its next hits (a charged release if it is
+/// winding up) the player can survive at current HP. ≤2 hits is High,
+/// ≤4 Medium.
+pub fn monster_threat(state: &RpgState, m: &Monster) -> Threat {
+    let eff_atk = m.effective_atk() * state.abyss_enemy_atk_pct() / 100;
+    let damage = monster_hit_damage(state, eff_atk, m.affix, m.charging);
+    match state.hp.div_ceil(damage) {
+        0..=2 => Threat::High,
+        3..=4 => Threat::Medium,
+        _ => Threat::Low,
+    }
+}
+
+/// Damage the player takes after abyss boons (Bulwark cuts 25%).
+fn abyss_damage_taken(state: &RpgState, damage: u32) -> u32 {
+    if state.has_abyss_modifier(AbyssModifier::Bulwark) {
+        (damage * 3 / 4).max(1)
+    } else {
+        damage
+    }
+}
+
+/// Reset the no-damage streak after a monster hit lands.
+fn break_no_damage_streak(state: &mut RpgState) {
+    if state.no_damage_streak >= 2 {
+        state.add_log(&format!("無傷連勝が途切れた… ({}連勝)", state.no_damage_streak));
+    }
+    state.no_damage_streak = 0;
+}
+
 /// Pick the monster's target — prefer player, fall back to pet if much closer.
 fn best_target(state: &RpgState, mx: usize, my: usize) -> (i32, i32) {
     let map = state.dungeon.as_ref().unwrap();
@@ -1387,7 +1924,7 @@ pub fn tame_with_treat(state: &mut RpgState, treat_inv_idx: usize) -> bool {
         let info = enemy_info(kind);
         state.add_log(&format!("{}は餌を食べたがそっぽを向いた…", info.name));
         // Wake the monster (now hostile)
-        state.dungeon.as_mut().unwrap().monsters[idx].awake = true;
+        wake_monster(state, idx);
     }
     on_player_action(state);
     true
@@ -1433,6 +1970,11 @@ pub fn resolve_event_choice(state: &mut RpgState, choice_index: usize) -> bool {
     if !succeeded {
         return false;
     }
+    let what = event.description.first().cloned().unwrap_or_default();
+    state.record(
+        RunLogKind::Event,
+        format!("{} → {}", what, event.choices[choice_index].label),
+    );
 
     // Stairs / Entrance are persistent landmarks, not consumable events:
     // the player must be able to come back later to descend or return to
@@ -1446,7 +1988,14 @@ pub fn resolve_event_choice(state: &mut RpgState, choice_index: usize) -> bool {
     }
     state.active_event = None;
 
-    if outcome.descend {
+    if outcome.descend && is_site_exit(state, floor) {
+        state.add_log("森を抜けた。");
+        retreat_to_town(state);
+        // 森の主を倒していれば、次の入口は遺跡を指す。
+        if state.site_cleared(DungeonSite::Forest) {
+            state.site = DungeonSite::Ruins;
+        }
+    } else if outcome.descend {
         let next_floor = floor + 1;
         enter_dungeon(state, next_floor);
     } else if outcome.ascend {
@@ -1456,6 +2005,13 @@ pub fn resolve_event_choice(state: &mut RpgState, choice_index: usize) -> bool {
         enter_dungeon(state, prev_floor);
     } else if outcome.return_to_town {
         retreat_to_town(state);
+    } else if outcome.open_merchant {
+        for desc in &outcome.description {
+            if !desc.is_empty() { state.add_log(desc); }
+        }
+        // 品ぞろえは overlay を開いてから入れる (open_overlay が前の店を片付けるため)。
+        state.open_overlay(Overlay::Shop);
+        state.merchant = Some(merchant_stock(floor, &mut state.rng_seed));
     } else if state.dungeon.is_some() {
         for desc in &outcome.description {
             if !desc.is_empty() { state.add_log(desc); }
@@ -1547,6 +2103,13 @@ fn apply_event_outcome(state: &mut RpgState, outcome: &EventOutcome) -> bool {
             state.satiety = state.satiety.saturating_sub((-outcome.satiety_change) as u32);
         }
     }
+    if outcome.stamina_change == 9999 {
+        state.stamina = STAMINA_MAX;
+    } else if outcome.stamina_change > 0 {
+        state.stamina = (state.stamina + outcome.stamina_change as u32).min(STAMINA_MAX);
+    } else if outcome.stamina_change < 0 {
+        state.stamina = state.stamina.saturating_sub((-outcome.stamina_change) as u32);
+    }
     if outcome.faith_change > 0 {
         state.faith = state.faith.saturating_add(outcome.faith_change);
     }
@@ -1623,6 +2186,7 @@ fn spawn_hostile_near_player(state: &mut RpgState, kind: EnemyKind) {
         charging: false,
         affix: None,
     });
+    note_bestiary_encounter(state, kind);
 }
 
 pub fn retreat_to_town(state: &mut RpgState) {
@@ -1649,6 +2213,8 @@ pub fn retreat_to_town(state: &mut RpgState) {
     } else {
         state.add_log("村に戻った。");
     }
+    finish_run_log(state, format!("村へ帰還 (+{}G / {}体撃破)", run_gold + bonus, run_kills));
+    end_abyss_run(state);
     enter_overworld(state);
 }
 
@@ -1656,16 +2222,69 @@ pub fn return_bonus(floor: u32, rooms_explored: u32) -> u32 {
     floor * rooms_explored * 3
 }
 
+/// 進行中の探索の冒険記録を締めて、受付で見返せる `last_run_log` に移す。
+fn finish_run_log(state: &mut RpgState, outcome: String) {
+    state.record(RunLogKind::Floor, outcome);
+    state.last_run_log = std::mem::take(&mut state.run_log);
+}
+
+/// 冒険記録を共有用のテキストにする。先頭に集計、以降 1 行 1 エントリ。
+pub fn run_log_text(entries: &[RunLogEntry]) -> String {
+    let deepest = entries.iter().map(|e| e.floor).max().unwrap_or(0);
+    let kills = entries.iter().filter(|e| e.kind == RunLogKind::Kill).count();
+    let (dealt, taken) = entries.iter().fold((0u32, 0u32), |(d, t), e| match e.kind {
+        RunLogKind::Dealt(n) => (d + n, t),
+        RunLogKind::Taken(n) => (d, t + n),
+        _ => (d, t),
+    });
+    let turns = match (entries.first(), entries.last()) {
+        (Some(a), Some(b)) => b.turn.saturating_sub(a.turn),
+        _ => 0,
+    };
+    let mut out = format!(
+        "冒険記録: 最深 B{}F / {}体撃破 / 与ダメ {} / 被ダメ {} / {}ターン\n",
+        deepest, kills, dealt, taken, turns
+    );
+    for e in entries {
+        out.push_str(&format!("[B{}F {:>4}] {} {}\n", e.floor, e.turn, e.kind.mark(), e.text));
+    }
+    out
+}
+
 fn process_dungeon_death(state: &mut RpgState) {
-    let run_gold = state.run_gold_earned;
-    let pre_run_gold = state.gold.saturating_sub(run_gold);
-    let extra_penalty = pre_run_gold / 5;
-    let lost_gold = (run_gold + extra_penalty).min(state.gold);
-    state.gold -= lost_gold;
+    finish_run_log(state, "力尽きた".into());
+    match state.difficulty {
+        Difficulty::Ironman => {
+            end_abyss_run(state);
+            // 周回の進行を捨てて村の導入から。深淵ランキング・難易度・ログだけ残す。
+            let abyss_records = std::mem::take(&mut state.abyss_records);
+            let log = std::mem::take(&mut state.log);
+            let last_run_log = std::mem::take(&mut state.last_run_log);
+            *state = RpgState::new();
+            state.difficulty = Difficulty::Ironman;
+            state.abyss_records = abyss_records;
+            state.log = log;
+            state.last_run_log = last_run_log;
+            state.save_wiped = true;
+            state.add_log("力尽きた… 冒険の記録はすべて失われた");
+            enter_overworld(state);
+            return;
+        }
+        Difficulty::Casual => state.add_log("力尽きた… 所持金は無事だった"),
+        Difficulty::Normal => {
+            let run_gold = state.run_gold_earned;
+            let pre_run_gold = state.gold.saturating_sub(run_gold);
+            let extra_penalty = pre_run_gold / 5;
+            let lost_gold = (run_gold + extra_penalty).min(state.gold);
+            state.gold -= lost_gold;
+            state.add_log(&format!("力尽きた… {}G失った", lost_gold));
+        }
+    }
     state.hp = state.max_hp / 2;
     state.mp = state.max_mp / 2;
     state.satiety = state.satiety_max / 2;
-    state.add_log(&format!("力尽きた… {}G失った", lost_gold));
+    state.no_damage_streak = 0;
+    end_abyss_run(state);
     enter_overworld(state);
 }
 
@@ -1746,12 +2365,16 @@ fn cast_damage_skill(state: &mut RpgState, skill: SkillKind, idx: usize) {
     {
         let m = &mut state.dungeon.as_mut().unwrap().monsters[idx];
         m.hp = m.hp.saturating_sub(damage);
-        m.awake = true;
     }
+    wake_monster(state, idx);
 
     let weak_str = if is_weak { " [弱点!]" } else { "" };
     let name = einfo.name;
     state.add_log(&format!("{}！ {}に{}ダメージ{}", info.name, name, damage, weak_str));
+    state.record(
+        RunLogKind::Dealt(damage),
+        format!("{}で{}に{} (MAG{}){}", info.name, name, damage, mag, weak_str),
+    );
     if is_weak {
         note_weakness_discovery(state, kind);
     }
@@ -1767,7 +2390,8 @@ fn cast_damage_skill(state: &mut RpgState, skill: SkillKind, idx: usize) {
     }
 }
 
-fn adjacent_monster(state: &RpgState) -> Option<usize> {
+/// Index of a live monster next to the player (4-neighbourhood).
+pub fn adjacent_monster(state: &RpgState) -> Option<usize> {
     let map = state.dungeon.as_ref()?;
     let px = map.player_x as i32;
     let py = map.player_y as i32;
@@ -1795,6 +2419,7 @@ fn check_level_up(state: &mut RpgState) {
             state.hp = state.max_hp;
             state.mp = state.max_mp;
             state.add_log(&format!("レベルアップ！ Lv.{}", state.level));
+            toast::push(Severity::Success, format!("レベルアップ！ Lv.{}", state.level));
             // Pet levels up too
             let pet_msg = state.pet.as_mut().map(|p| {
                 p.level += 1;
@@ -1904,6 +2529,19 @@ pub fn use_item(state: &mut RpgState, inv_index: usize) -> bool {
             ItemKind::PetTreat => {
                 return tame_with_treat(state, inv_index);
             }
+            ItemKind::Torch => {
+                let in_dungeon = state.dungeon.as_ref().is_some_and(|m| !m.is_overworld);
+                if !in_dungeon {
+                    state.add_log("ここでは使えない");
+                    return false;
+                }
+                if state.light >= LIGHT_MAX {
+                    state.add_log("明かりは十分だ");
+                    return false;
+                }
+                state.light = (state.light + iinfo.value).min(LIGHT_MAX);
+                state.add_log(&format!("松明を灯した！ 明かり+{}", iinfo.value));
+            }
             ItemKind::ReturnScroll => {
                 // Only useful inside the dungeon — refuse in the village.
                 let in_dungeon = state
@@ -1949,6 +2587,10 @@ pub fn use_item(state: &mut RpgState, inv_index: usize) -> bool {
             state.add_log(&format!("{}を装備した", display));
             return true;
         }
+        ItemCategory::Material => {
+            state.add_log("素材は村の工房で使う");
+            return false;
+        }
     }
 
     // Consume one
@@ -1961,20 +2603,107 @@ pub fn use_item(state: &mut RpgState, inv_index: usize) -> bool {
 
 // ── Shop ─────────────────────────────────────────────────────
 
+/// 開いている店 ([`RpgState::shop_offers`]) の `shop_index` 番目を 1 つ買う。
+/// 旅の商人の品は出会いごとの残り数を 1 減らす。
 pub fn buy_item(state: &mut RpgState, shop_index: usize) -> bool {
-    let shop = shop_items(state.max_floor_reached);
-    if shop_index >= shop.len() {
+    let Some(offer) = state.shop_offers().get(shop_index).copied() else {
+        return false;
+    };
+    let iinfo = item_info(offer.kind);
+    if offer.left == Some(0) {
+        state.add_log(&format!("{}は売り切れだ", iinfo.name));
         return false;
     }
-    let (kind, _) = shop[shop_index];
-    let iinfo = item_info(kind);
-    if state.gold < iinfo.buy_price {
+    if state.gold < offer.price {
         state.add_log("お金が足りない");
         return false;
     }
-    state.gold -= iinfo.buy_price;
-    add_item(state, kind, 1);
-    state.add_log(&format!("{}を購入 ({}G)", iinfo.name, iinfo.buy_price));
+    state.gold -= offer.price;
+    if let Some(stock) = &mut state.merchant {
+        if let Some(left) = &mut stock[shop_index].left {
+            *left -= 1;
+        }
+    }
+    add_item(state, offer.kind, 1);
+    state.add_log(&format!("{}を購入 ({}G)", iinfo.name, offer.price));
+    true
+}
+
+// ── Crafting ─────────────────────────────────────────────────
+
+/// Remove `count` of the plain (non-affixed) `kind` stack. Caller checks
+/// [`RpgState::item_count`] first.
+fn remove_items(state: &mut RpgState, kind: ItemKind, count: u32) {
+    for _ in 0..count {
+        match state.inventory.iter().position(|i| i.kind == kind && i.affix.is_none()) {
+            Some(idx) => consume_inventory_slot(state, idx),
+            None => return,
+        }
+    }
+}
+
+/// 工房で `recipe_index` 番目のレシピを作る。素材不足や鍛え直す装備が
+/// ないときは何も消費せず false。
+pub fn craft(state: &mut RpgState, recipe_index: usize) -> bool {
+    let recipes = known_recipes(state.max_floor_reached);
+    let Some(&recipe) = recipes.get(recipe_index) else {
+        return false;
+    };
+    let info = recipe_info(recipe);
+    if info.inputs.iter().any(|&(kind, n)| state.item_count(kind) < n) {
+        state.add_log("素材が足りない");
+        return false;
+    }
+    // 鍛え直しは先に対象を確かめる (素材だけ消えるのを防ぐ)。
+    let slot = match info.output {
+        CraftOutput::UpgradeWeapon => Some(state.weapon_idx),
+        CraftOutput::UpgradeArmor => Some(state.armor_idx),
+        CraftOutput::Item(..) => None,
+    };
+    if let Some(slot) = slot {
+        let upgradable = slot
+            .and_then(|i| state.inventory.get(i))
+            .and_then(|item| equipment_upgrade(item.kind))
+            .is_some();
+        if !upgradable {
+            state.add_log("鍛え直せる装備がない");
+            return false;
+        }
+    }
+
+    for &(kind, n) in info.inputs {
+        remove_items(state, kind, n);
+    }
+    match info.output {
+        CraftOutput::Item(kind, n) => {
+            add_item(state, kind, n);
+            state.add_log(&format!("{}x{}を作った！", item_info(kind).name, n));
+        }
+        CraftOutput::UpgradeWeapon | CraftOutput::UpgradeArmor => {
+            // Materials are never equipped, so the slot index survived
+            // `remove_items`' re-anchoring.
+            let weapon = info.output == CraftOutput::UpgradeWeapon;
+            let Some(idx) = (if weapon { state.weapon_idx } else { state.armor_idx }) else {
+                return true;
+            };
+            let old = state.inventory[idx].clone();
+            let Some(next) = equipment_upgrade(old.kind) else {
+                return true;
+            };
+            let upgraded = InventoryItem { kind: next, count: 1, affix: old.affix };
+            let after = upgraded.display_name();
+            if old.count > 1 {
+                // 買い重ねた未付与の装備は 1 本だけ鍛え直して装備し直す。
+                state.inventory[idx].count -= 1;
+                state.inventory.push(upgraded);
+                let new_idx = Some(state.inventory.len() - 1);
+                if weapon { state.weapon_idx = new_idx; } else { state.armor_idx = new_idx; }
+            } else {
+                state.inventory[idx] = upgraded;
+            }
+            state.add_log(&format!("{}を{}に鍛え直した！", old.display_name(), after));
+        }
+    }
     true
 }
 
@@ -2009,6 +2738,21 @@ mod tests {
         assert!(s.met_blacksmith);
     }
 
+    #[test]
+    fn fast_start_grants_kits_then_offers_dungeon_in_two_taps() {
+        let mut s = RpgState::new();
+        s.active_event = Some(fast_start_event(s.difficulty));
+        assert!(resolve_event_choice(&mut s, 0));
+        assert!(intro_completed(&s));
+        assert!(s.weapon().is_some());
+        assert!(s.armor().is_some());
+        assert_eq!(s.gold, 50);
+        // Second tap: the entrance prompt is already open.
+        assert!(resolve_event_choice(&mut s, 0));
+        assert_eq!(s.scene, Scene::DungeonExplore);
+        assert_eq!(s.dungeon.as_ref().map(|d| d.floor_num), Some(1));
+    }
+
     /// Codex P2 (#98): もしプレイヤーがダンジョンを先に経験して
     /// 既に強い装備を着けている状態で武具屋と初対面した場合、
     /// 木の剣 / 旅人の服 で装備を上書きしてはいけない。
@@ -2213,6 +2957,92 @@ mod tests {
         assert!(s.hp < before);
     }
 
+    #[test]
+    fn light_drains_per_turn_and_refills_with_torch() {
+        let mut s = RpgState::new();
+        s.light = 5;
+        enter_dungeon(&mut s, 1);
+        assert_eq!(s.light, LIGHT_MAX, "a new run starts with a full light");
+        on_player_action(&mut s);
+        assert_eq!(s.light, LIGHT_MAX - 1);
+
+        s.light = 0;
+        assert!(s.is_dark());
+        add_item(&mut s, ItemKind::Torch, 1);
+        let idx = s.inventory.iter().position(|i| i.kind == ItemKind::Torch).unwrap();
+        assert!(use_item(&mut s, idx));
+        assert!(!s.is_dark());
+        assert_eq!(s.item_count(ItemKind::Torch), 0);
+    }
+
+    #[test]
+    fn stamina_drains_on_moves_and_recovers_on_wait() {
+        let mut s = RpgState::new();
+        s.stamina = 3;
+        enter_dungeon(&mut s, 1);
+        assert_eq!(s.stamina, STAMINA_MAX, "a new run starts with full stamina");
+
+        s.stamina = 1;
+        spend_stamina(&mut s, MOVE_STAMINA_COST);
+        assert_eq!(s.stamina, 0);
+        assert!(s.is_exhausted());
+        assert!(s.log.iter().any(|l| l.contains("スタミナが尽きた")));
+
+        s.active_event = None;
+        s.scene = Scene::DungeonExplore;
+        assert!(wait_in_place(&mut s));
+        assert_eq!(s.stamina, WAIT_STAMINA_GAIN);
+        assert!(!s.is_exhausted());
+    }
+
+    #[test]
+    fn exhausted_attacks_deal_half_damage() {
+        let hit = |stamina: u32| {
+            let mut s = RpgState::new();
+            enter_dungeon(&mut s, 1);
+            s.rng_seed = 12345;
+            s.stamina = stamina;
+            let map = s.dungeon.as_mut().unwrap();
+            map.monsters.clear();
+            map.monsters.push(Monster {
+                kind: EnemyKind::Rat, x: 0, y: 0, hp: 1000, max_hp: 1000,
+                awake: true, charging: false, affix: None,
+            });
+            attack_monster(&mut s, 0);
+            1000 - s.dungeon.as_ref().unwrap().monsters[0].hp
+        };
+        let fresh = hit(STAMINA_MAX);
+        let tired = hit(0);
+        assert_eq!(tired, (fresh / 2).max(1));
+    }
+
+    #[test]
+    fn camp_rest_refills_stamina() {
+        let mut s = RpgState::new();
+        enter_dungeon(&mut s, 1);
+        s.stamina = 0;
+        let mut seed = 7;
+        let outcome = resolve_event(&EventAction::RestAtCamp, CellType::Camp, 1, s.level, &mut seed);
+        apply_event_outcome(&mut s, &outcome);
+        assert_eq!(s.stamina, STAMINA_MAX);
+    }
+
+    #[test]
+    fn darkness_brings_ambushes() {
+        let mut s = RpgState::new();
+        enter_dungeon(&mut s, 1);
+        s.dungeon.as_mut().unwrap().monsters.clear();
+        s.light = 0;
+        s.hp = 9999;
+        for _ in 0..200 {
+            on_player_action(&mut s);
+            if !s.dungeon.as_ref().unwrap().monsters.is_empty() {
+                break;
+            }
+        }
+        assert!(!s.dungeon.as_ref().unwrap().monsters.is_empty());
+    }
+
     #[test]
     fn bump_attack_damages_monster() {
         let mut s = RpgState::new();
@@ -2270,6 +3100,33 @@ mod tests {
         assert_eq!(s.completed_quests, 1);
     }
 
+    #[test]
+    fn no_damage_streak_boosts_rewards_and_breaks_on_hit() {
+        let mut s = RpgState::new();
+        enter_dungeon(&mut s, 1);
+        let slime_gold = enemy_info(EnemyKind::Slime).gold;
+        let kill_slime = |s: &mut RpgState| {
+            let map = s.dungeon.as_mut().unwrap();
+            map.monsters.clear();
+            map.monsters.push(Monster {
+                kind: EnemyKind::Slime, x: 0, y: 0, hp: 1, max_hp: 12,
+                awake: true, charging: false, affix: None,
+            });
+            let before = s.gold;
+            attack_monster(s, 0);
+            s.gold - before
+        };
+        assert_eq!(kill_slime(&mut s), slime_gold);
+        assert_eq!(s.no_damage_streak, 1);
+        s.no_damage_streak = 10;
+        assert_eq!(s.streak_bonus_pct(), 200);
+        assert_eq!(kill_slime(&mut s), slime_gold * 2);
+
+        break_no_damage_streak(&mut s);
+        assert_eq!(s.no_damage_streak, 0);
+        assert_eq!(s.streak_bonus_pct(), 100);
+    }
+
     #[test]
     fn pray_consumes_for_run() {
         let mut s = RpgState::new();
@@ -2318,16 +3175,143 @@ mod tests {
         assert_eq!(s.gold, 80);
     }
 
+    #[test]
+    fn casual_death_keeps_gold() {
+        let mut s = RpgState::new();
+        s.difficulty = Difficulty::Casual;
+        s.gold = 100;
+        enter_dungeon(&mut s, 1);
+        s.run_gold_earned = 30;
+        s.hp = 0;
+        process_dungeon_death(&mut s);
+        assert_eq!(s.scene, Scene::Overworld);
+        assert_eq!(s.gold, 100);
+        assert_eq!(s.hp, s.max_hp / 2);
+    }
+
+    #[test]
+    fn ironman_death_wipes_the_run_but_keeps_records() {
+        let mut s = RpgState::new();
+        s.difficulty = Difficulty::Ironman;
+        s.gold = 500;
+        s.level = 7;
+        s.max_floor_reached = 6;
+        s.met_reception = true;
+        s.abyss_records = vec![4];
+        enter_dungeon(&mut s, 3);
+        s.hp = 0;
+        process_dungeon_death(&mut s);
+        assert_eq!(s.scene, Scene::Overworld);
+        assert_eq!((s.gold, s.level, s.max_floor_reached), (0, 1, 0));
+        assert!(!s.met_reception);
+        assert_eq!(s.abyss_records, vec![4]);
+        assert_eq!(s.difficulty, Difficulty::Ironman);
+        assert!(s.save_wiped);
+    }
+
+    #[test]
+    fn first_reception_talk_picks_the_difficulty() {
+        let mut s = RpgState::new();
+        s.active_event = generate_overworld_event(&s, CellType::ReceptionNpc);
+        let ironman = ALL_DIFFICULTIES.iter().position(|&d| d == Difficulty::Ironman).unwrap();
+        assert!(resolve_event_choice(&mut s, ironman));
+        assert_eq!(s.difficulty, Difficulty::Ironman);
+        assert!(s.met_reception);
+        // Later talks don't offer it again
+        let later = generate_overworld_event(&s, CellType::ReceptionNpc).unwrap();
+        assert!(later
+            .choices
+            .iter()
+            .all(|c| !matches!(c.action, EventAction::ChooseDifficulty(_))));
+    }
+
     #[test]
     fn buy_item_at_shop() {
         let mut s = RpgState::new();
         s.gold = 100;
-        let shop = shop_items(0);
-        let herb_idx = shop.iter().position(|(k, _)| *k == ItemKind::Herb).unwrap();
+        let shop = s.shop_offers();
+        let herb_idx = shop.iter().position(|o| o.kind == ItemKind::Herb).unwrap();
         assert!(buy_item(&mut s, herb_idx));
         assert_eq!(s.gold, 80);
     }
 
+    #[test]
+    fn wandering_merchant_sells_a_limited_stock() {
+        let mut s = RpgState::new();
+        enter_dungeon(&mut s, 3);
+        let (px, py) = {
+            let map = s.dungeon.as_ref().unwrap();
+            (map.player_x, map.player_y)
+        };
+        s.dungeon.as_mut().unwrap().grid[py][px].cell_type = CellType::Merchant;
+        s.active_event = generate_event(CellType::Merchant, 3, floor_theme(3), &mut s.rng_seed);
+        assert!(resolve_event_choice(&mut s, 0));
+        assert_eq!(s.overlay, Some(Overlay::Shop));
+        assert!(s.dungeon.as_ref().unwrap().grid[py][px].event_done, "商人は一度きり");
+
+        let offers = s.shop_offers();
+        let herb = offers.iter().position(|o| o.kind == ItemKind::Herb).unwrap();
+        assert_eq!(offers[herb].price, item_info(ItemKind::Herb).buy_price * 2);
+        s.gold = 10_000;
+        let herbs_before = s.item_count(ItemKind::Herb);
+        assert!(buy_item(&mut s, herb));
+        assert!(buy_item(&mut s, herb));
+        assert!(!buy_item(&mut s, herb), "残り数を超えては買えない");
+        assert_eq!(s.item_count(ItemKind::Herb), herbs_before + 2);
+        assert!(s.log.iter().any(|l| l.contains("売り切れ")));
+
+        // 閉じたら村の店に戻る
+        s.close_overlay();
+        assert!(s.merchant.is_none());
+        assert_eq!(s.shop_offers()[0].left, None);
+    }
+
+    #[test]
+    fn crafting_brews_potions_from_materials() {
+        let mut s = RpgState::new();
+        add_item(&mut s, ItemKind::WildHerb, 3);
+        assert!(craft(&mut s, 0));
+        assert_eq!(s.item_count(ItemKind::WildHerb), 1);
+        assert_eq!(s.item_count(ItemKind::Herb), 2);
+        // Not enough left for a second batch — nothing is consumed.
+        assert!(!craft(&mut s, 0));
+        assert_eq!(s.item_count(ItemKind::WildHerb), 1);
+        // Deeper recipes aren't on offer until the floor is reached.
+        assert!(!craft(&mut s, 1));
+    }
+
+    #[test]
+    fn reforge_upgrades_equipped_weapon_and_keeps_affix() {
+        use super::super::state::Recipe;
+        let mut s = RpgState::new();
+        s.max_floor_reached = 3;
+        let idx = known_recipes(3).iter().position(|&r| r == Recipe::ReforgeWeapon).unwrap();
+        add_item(&mut s, ItemKind::IronOre, 3);
+        add_item(&mut s, ItemKind::BeastFang, 2);
+        // No weapon equipped: materials stay.
+        assert!(!craft(&mut s, idx));
+        assert_eq!(s.item_count(ItemKind::IronOre), 3);
+
+        s.inventory.insert(0, InventoryItem {
+            kind: ItemKind::IronSword, count: 1, affix: Some(Affix::Sharp),
+        });
+        s.weapon_idx = Some(0);
+        assert!(craft(&mut s, idx));
+        let w = s.weapon().unwrap();
+        assert_eq!(w.kind, ItemKind::SteelSword);
+        assert_eq!(w.affix, Some(Affix::Sharp));
+        assert_eq!(s.item_count(ItemKind::IronOre), 0);
+        assert_eq!(s.item_count(ItemKind::BeastFang), 0);
+    }
+
+    #[test]
+    fn materials_cannot_be_used_directly() {
+        let mut s = RpgState::new();
+        add_item(&mut s, ItemKind::BeastFang, 1);
+        assert!(!use_item(&mut s, 0));
+        assert_eq!(s.item_count(ItemKind::BeastFang), 1);
+    }
+
     #[test]
     fn food_restores_satiety() {
         let mut s = RpgState::new();
@@ -2380,6 +3364,34 @@ mod tests {
         assert_eq!(available_skills(&s).len(), 2);
     }
 
+    #[test]
+    fn monster_threat_rises_with_low_hp_and_charging() {
+        let mut s = RpgState::new();
+        let info = enemy_info(EnemyKind::Golem);
+        let mut m = Monster {
+            kind: EnemyKind::Golem,
+            x: 0,
+            y: 0,
+            hp: info.max_hp,
+            max_hp: info.max_hp,
+            awake: true,
+            charging: false,
+            affix: None,
+        };
+        let hit = monster_hit_damage(&s, m.effective_atk(), None, false);
+        s.hp = hit * 10;
+        assert_eq!(monster_threat(&s, &m), Threat::Low);
+        s.hp = hit * 4;
+        assert_eq!(monster_threat(&s, &m), Threat::Medium);
+        s.hp = hit;
+        assert_eq!(monster_threat(&s, &m), Threat::High);
+        // A wound-up charge hits harder, so the same HP reads as riskier.
+        s.hp = hit * 5;
+        let calm = monster_threat(&s, &m);
+        m.charging = true;
+        assert!(monster_threat(&s, &m) > calm);
+    }
+
     /// Regression for codex review (P2): a Swift+can_charge enemy used to
     /// telegraph the charge AND release it on the same monster turn,
     /// because `monster_turn` ran `monster_act` twice without checking
@@ -2608,6 +3620,44 @@ mod tests {
         assert_eq!(s.known_weakness(EnemyKind::Slime), Some(Element::Fire));
     }
 
+    #[test]
+    fn 敵が気づくと図鑑に遭遇が記録され撃破も数えられる() {
+        let mut s = RpgState::new();
+        enter_dungeon(&mut s, 1);
+        for _ in 0..2 {
+            let map = s.dungeon.as_mut().unwrap();
+            map.monsters.clear();
+            map.monsters.push(Monster {
+                kind: EnemyKind::Slime, x: 0, y: 0, hp: 1, max_hp: 10,
+                awake: false, charging: false, affix: None,
+            });
+            attack_monster(&mut s, 0);
+        }
+
+        let entry = s.bestiary_entry(EnemyKind::Slime).expect("登録されている");
+        assert_eq!((entry.encountered, entry.killed), (2, 2));
+        assert_eq!(s.log.iter().filter(|l| l.contains("図鑑に")).count(), 1, "登録ログは初回だけ");
+        assert!(s.bestiary_entry(EnemyKind::Goblin).is_none());
+    }
+
+    #[test]
+    fn 起きている敵を殴っても遭遇は増えない() {
+        let mut s = RpgState::new();
+        enter_dungeon(&mut s, 1);
+        let map = s.dungeon.as_mut().unwrap();
+        map.monsters.clear();
+        map.monsters.push(Monster {
+            kind: EnemyKind::Rat, x: 0, y: 0, hp: 1000, max_hp: 1000,
+            awake: false, charging: false, affix: None,
+        });
+        attack_monster(&mut s, 0);
+        attack_monster(&mut s, 0);
+
+        let entry = s.bestiary_entry(EnemyKind::Rat).unwrap();
+        assert_eq!((entry.encountered, entry.killed), (1, 0));
+        assert!(!entry.drop_seen && !entry.material_seen);
+    }
+
     #[test]
     fn 未発見の敵の弱点は取得できない() {
         let s = RpgState::new();
@@ -2687,4 +3737,173 @@ mod tests {
             s.log
         );
     }
+
+    // ── Endless Abyss ─────────────────────────────────────────
+
+    #[test]
+    fn abyss_requires_game_clear_and_starts_past_the_boss_floor() {
+        let mut s = RpgState::new();
+        assert!(!start_abyss(&mut s));
+        assert!(s.abyss.is_none());
+
+        s.game_cleared = true;
+        s.max_floor_reached = MAX_FLOOR;
+        assert!(start_abyss(&mut s));
+        assert_eq!(s.abyss_depth(), 1);
+        let map = s.dungeon.as_ref().unwrap();
+        assert_eq!(map.floor_num, MAX_FLOOR + 1);
+        assert!(map.monsters.iter().all(|m| m.kind != EnemyKind::DemonLord));
+        // 深淵の層は本編の最深記録を押し上げない
+        assert_eq!(s.max_floor_reached, MAX_FLOOR);
+        assert_eq!(s.abyss_enemy_atk_pct(), abyss_scale_pct(1));
+    }
+
+    #[test]
+    fn abyss_offers_three_distinct_modifiers_every_interval() {
+        let mut s = RpgState::new();
+        s.game_cleared = true;
+        start_abyss(&mut s);
+        enter_dungeon(&mut s, MAX_FLOOR + 2);
+        assert!(s.overlay.is_none());
+
+        enter_dungeon(&mut s, MAX_FLOOR + ABYSS_MODIFIER_INTERVAL);
+        assert_eq!(s.overlay, Some(Overlay::AbyssModifier));
+        let choices = s.abyss.as_ref().unwrap().pending_choice.clone();
+        assert_eq!(choices.len(), 3);
+        assert!(choices.iter().all(|m| choices.iter().filter(|n| *n == m).count() == 1));
+        assert_eq!(cursor_count(&s), 3);
+
+        assert!(confirm_abyss_modifier(&mut s, 1));
+        assert!(s.overlay.is_none());
+        assert!(s.has_abyss_modifier(choices[1]));
+        assert!(!confirm_abyss_modifier(&mut s, 0), "no pending choice left");
+    }
+
+    #[test]
+    fn abyss_curses_and_boons_change_combat_numbers() {
+        let mut s = RpgState::new();
+        s.game_cleared = true;
+        start_abyss(&mut s);
+        let base = s.abyss_enemy_atk_pct();
+        s.abyss.as_mut().unwrap().modifiers = vec![AbyssModifier::Frenzy, AbyssModifier::Bulwark];
+        assert_eq!(s.abyss_enemy_atk_pct(), base + 25);
+        assert_eq!(abyss_damage_taken(&s, 20), 15);
+        assert_eq!(abyss_damage_taken(&s, 1), 1);
+    }
+
+    #[test]
+    fn leaving_the_abyss_records_depth_on_the_leaderboard() {
+        let mut s = RpgState::new();
+        s.game_cleared = true;
+        start_abyss(&mut s);
+        enter_dungeon(&mut s, MAX_FLOOR + 2);
+        retreat_to_town(&mut s);
+        assert!(s.abyss.is_none());
+        assert_eq!(s.abyss_records, vec![2]);
+        assert_eq!(s.scene, Scene::Overworld);
+
+        start_abyss(&mut s);
+        s.hp = 0;
+        process_dungeon_death(&mut s);
+        assert_eq!(s.abyss_records, vec![2, 1]);
+        assert_eq!(s.best_abyss_depth(), 2);
+    }
+
+    #[test]
+    fn abyss_leaderboard_keeps_top_entries_in_order() {
+        let mut records = vec![9, 7, 5, 3, 1];
+        assert_eq!(record_abyss_depth(&mut records, 6), Some(2));
+        assert_eq!(records, vec![9, 7, 6, 5, 3]);
+        assert_eq!(record_abyss_depth(&mut records, 2), None);
+        assert_eq!(record_abyss_depth(&mut records, 0), None);
+        assert_eq!(records.len(), ABYSS_LEADERBOARD_LEN);
+    }
+
+    #[test]
+    fn cleared_village_entrance_offers_the_abyss() {
+        let mut s = RpgState::new();
+        let labels = |s: &RpgState| {
+            generate_overworld_event(s, CellType::DungeonEntrance)
+                .unwrap()
+                .choices
+                .iter()
+                .map(|c| c.action.clone())
+                .collect::<Vec<_>>()
+        };
+        assert!(!labels(&s).contains(&EventAction::EnterAbyss));
+        s.game_cleared = true;
+        assert!(labels(&s).contains(&EventAction::EnterAbyss));
+
+        s.active_event = generate_overworld_event(&s, CellType::DungeonEntrance);
+        assert!(resolve_event_choice(&mut s, 1));
+        assert_eq!(s.abyss_depth(), 1);
+    }
+
+    #[test]
+    fn forest_lord_opens_the_ruins_and_the_last_stairs_lead_home() {
+        let mut s = RpgState::new();
+        assert_eq!(s.site, DungeonSite::Forest);
+        enter_dungeon(&mut s, FOREST_FLOORS);
+        let map = s.dungeon.as_mut().unwrap();
+        let (sx, sy) = map.monsters.iter().find(|m| m.kind == EnemyKind::ForestLord)
+            .map(|m| (m.x, m.y))
+            .expect("forest lord guards the stairs");
+        map.monsters.retain(|m| m.kind == EnemyKind::ForestLord);
+        map.monsters[0].hp = 1;
+        attack_monster(&mut s, 0);
+        assert!(s.site_cleared(DungeonSite::Forest));
+        assert!(s.site_unlocked(DungeonSite::Ruins));
+        // まだ森の中 — 階段は B4F ではなく村へ続く。
+        assert_eq!(s.site, DungeonSite::Forest);
+
+        let map = s.dungeon.as_mut().unwrap();
+        map.player_x = sx;
+        map.player_y = sy;
+        after_move(&mut s, sx, sy);
+        assert!(resolve_event_choice(&mut s, 0));
+        assert_eq!(s.scene, Scene::Overworld);
+        assert_eq!(s.site, DungeonSite::Ruins);
+    }
+
+    #[test]
+    fn run_log_records_the_run_and_is_offered_at_the_reception() {
+        let mut s = RpgState::new();
+        s.met_reception = true;
+        enter_dungeon(&mut s, 1);
+        let map = s.dungeon.as_mut().unwrap();
+        map.monsters.clear();
+        map.monsters.push(Monster {
+            kind: EnemyKind::Slime, x: 0, y: 0, hp: 1, max_hp: 10,
+            awake: true, charging: false, affix: None,
+        });
+        attack_monster(&mut s, 0);
+        retreat_to_town(&mut s);
+
+        assert!(s.run_log.is_empty());
+        let kinds: Vec<RunLogKind> = s.last_run_log.iter().map(|e| e.kind).collect();
+        assert_eq!(kinds.first(), Some(&RunLogKind::Floor));
+        assert!(kinds.contains(&RunLogKind::Kill));
+        assert!(kinds.iter().any(|k| matches!(k, RunLogKind::Dealt(_))));
+
+        let text = run_log_text(&s.last_run_log);
+        assert!(text.starts_with("冒険記録: 最深 B1F / 1体撃破"), "{}", text);
+        assert!(text.contains(enemy_info(EnemyKind::Slime).name));
+
+        let event = generate_overworld_event(&s, CellType::ReceptionNpc).unwrap();
+        assert!(event.choices.iter().any(|c| c.action == EventAction::OpenRunLog));
+    }
+
+    #[test]
+    fn dungeon_select_refuses_locked_sites() {
+        let mut s = RpgState::new();
+        s.open_overlay(Overlay::DungeonSelect);
+        assert!(!enter_site(&mut s, 1), "ruins need the forest lord");
+        assert!(!enter_site(&mut s, 2), "abyss needs the demon lord");
+        assert_eq!(s.scene, Scene::Overworld);
+
+        assert!(enter_site(&mut s, 0));
+        assert_eq!(s.scene, Scene::DungeonExplore);
+        assert_eq!(s.overlay, None);
+        assert_eq!(s.dungeon.as_ref().unwrap().floor_num, 1);
+    }
 }
@@ -4,6 +4,7 @@
 //! Movement against a monster tile = attack. Each player action triggers
 //! a monster turn (chase + attack). No separate battle screen.
 
+pub mod action_bar;
 pub mod actions;
 pub mod dungeon_map;
 pub mod dungeon_view;
@@ -12,6 +13,7 @@ pub mod logic;
 pub mod lore;
 pub mod overworld_map;
 pub mod render;
+pub mod save;
 pub mod state;
 #[cfg(test)]
 pub mod simulator;
@@ -22,21 +24,45 @@ use std::rc::Rc;
 use ratzilla::ratatui::layout::Rect;
 use ratzilla::ratatui::Frame;
 
+use crate::arcade::{self, Reward};
 use crate::games::{Game, GameChoice};
-use crate::input::{ClickState, InputEvent};
+use crate::input::{ClickState, InputEvent, KeyRepeat};
+use crate::scores::Score;
+use crate::widgets::{page_scroll, scroll_by};
 
 use actions::*;
-use state::{Overlay, RpgState, Scene};
+use state::{BestiaryEntry, Difficulty, ItemKind, Overlay, RpgState, Scene};
 
 pub struct RpgGame {
-    state: RpgState,
+    pub(crate) state: RpgState,
+    /// 村の導入を済ませたことがあるか (周回をまたぐメタフラグ)。
+    intro_seen: bool,
+    /// 最後に保存した深淵ランキング。変化したときだけ書き込む。
+    saved_abyss_records: Vec<u32>,
+    /// 最後に保存した難易度。変化したときだけ書き込む。
+    saved_difficulty: Difficulty,
+    /// 最後に保存した図鑑。変化したときだけ書き込む。
+    saved_bestiary: Vec<BestiaryEntry>,
 }
 
 impl RpgGame {
     pub fn new() -> Self {
-        Self {
-            state: RpgState::new(),
+        let intro_seen = save::load_intro_seen();
+        let mut state = RpgState::new();
+        state.abyss_records = save::load_abyss_records();
+        state.difficulty = save::load_difficulty();
+        state.bestiary = save::load_bestiary();
+        if arcade::owns(Reward::RpgStarterKit) {
+            logic::add_item(&mut state, ItemKind::Herb, arcade::RPG_STARTER_HERBS);
         }
+        if intro_seen {
+            // 2 周目以降: スキップ → 降りる の 2 タップで B1F へ。
+            state.active_event = Some(logic::fast_start_event(state.difficulty));
+        }
+        let saved_abyss_records = state.abyss_records.clone();
+        let saved_difficulty = state.difficulty;
+        let saved_bestiary = state.bestiary.clone();
+        Self { state, intro_seen, saved_abyss_records, saved_difficulty, saved_bestiary }
     }
 }
 
@@ -46,10 +72,40 @@ impl Game for RpgGame {
     }
 
     fn handle_input(&mut self, event: &InputEvent) -> bool {
-        match event {
+        // ドラッグ以外の入力はマップの見回しを解除してプレイヤー中心に戻す
+        if !matches!(event, InputEvent::Drag(..)) {
+            self.state.map_pan = (0, 0);
+        }
+        let handled = match event {
             InputEvent::Key(ch) => handle_key(&mut self.state, *ch),
             InputEvent::Click(_, id) => handle_click(&mut self.state, *id),
+            InputEvent::Drag(_, m) => logic::pan_map(&mut self.state, m.delta),
+            InputEvent::LongPress(..) | InputEvent::DoubleTap(..) => false,
+        };
+        // アイアンマンで力尽きた: 次の起動も村の導入から
+        if self.state.save_wiped {
+            self.state.save_wiped = false;
+            self.intro_seen = false;
+            save::wipe_intro_seen();
+        }
+        if !self.intro_seen && logic::intro_completed(&self.state) {
+            self.intro_seen = true;
+            save::store_intro_seen();
+        }
+        // 深淵の挑戦が終わると abyss_records が更新される
+        if self.state.abyss_records != self.saved_abyss_records {
+            self.saved_abyss_records = self.state.abyss_records.clone();
+            save::store_abyss_records(&self.saved_abyss_records);
+        }
+        if self.state.difficulty != self.saved_difficulty {
+            self.saved_difficulty = self.state.difficulty;
+            save::store_difficulty(self.saved_difficulty);
+        }
+        if self.state.bestiary != self.saved_bestiary {
+            self.saved_bestiary = self.state.bestiary.clone();
+            save::store_bestiary(&self.saved_bestiary);
         }
+        handled
     }
 
     fn tick(&mut self, delta_ticks: u32) {
@@ -59,6 +115,47 @@ impl Game for RpgGame {
     fn render(&self, f: &mut Frame, area: Rect, click_state: &Rc<RefCell<ClickState>>) {
         render::render(&self.state, f, area, click_state);
     }
+
+    fn key_repeat(&self) -> Option<KeyRepeat> {
+        // 1 歩ずつ確かめられるよう、カーソル操作より遅めに歩く
+        Some(KeyRepeat::movement(250.0, 150.0))
+    }
+
+    fn score(&self) -> Option<Score> {
+        // 深淵は魔王の階の先 (深淵 n 層 = B(MAX_FLOOR+n)F) として数える
+        let abyss_floor = match self.state.best_abyss_depth() {
+            0 => 0,
+            depth => state::MAX_FLOOR + depth,
+        };
+        let deepest = self.state.max_floor_reached.max(abyss_floor);
+        Some(Score::new(deepest as u64, "最深到達階", "F"))
+    }
+
+    #[cfg(any(feature = "debug-console", debug_assertions, test))]
+    fn debug_command(&mut self, args: &[&str]) -> Result<String, String> {
+        use crate::debug_console::{parse_count, unknown};
+        match args {
+            ["help"] => Ok(format!(
+                "give gold <n> / goto floor <1-{}> (set floor も可)",
+                state::MAX_FLOOR
+            )),
+            ["give", "gold", n] => {
+                let n = parse_count(n)?.min(u32::MAX as u64) as u32;
+                self.state.gold = self.state.gold.saturating_add(n);
+                Ok(format!("ゴールド +{}", n))
+            }
+            ["goto" | "set", "floor", n] => {
+                let floor = parse_count(n)?;
+                if floor == 0 || floor > state::MAX_FLOOR as u64 {
+                    return Err(format!("階層は 1〜{}", state::MAX_FLOOR));
+                }
+                self.state.site = state::DungeonSite::Ruins;
+                logic::enter_dungeon(&mut self.state, floor as u32);
+                Ok(format!("B{}F へ移動", floor))
+            }
+            _ => Err(unknown(args)),
+        }
+    }
 }
 
 // ── Input Handling ──────────────────────────────────────────
@@ -116,6 +213,13 @@ fn handle_key(state: &mut RpgState, ch: char) -> bool {
 }
 
 fn handle_click(state: &mut RpgState, id: u16) -> bool {
+    // Action bar buttons replay their key, whatever the scene.
+    if let Some(index) = ACTION_BAR.index(id) {
+        return match action_bar::actions(state).get(index) {
+            Some(action) => handle_key(state, action.key),
+            None => false,
+        };
+    }
     if state.overlay.is_some() {
         return handle_overlay_click(state, id);
     }
@@ -144,7 +248,7 @@ fn handle_dungeon_explore_key(state: &mut RpgState, ch: char) -> bool {
         'D' | 'd' | 'l' => logic::try_move(state, state::Facing::East),
         // A button — context-sensitive primary action.
         ' ' | 'A' | 'a' => trigger_a_button(state),
-        // B button — unified menu (持ち物 / スキル / ステータス).
+        // B button — unified menu (持ち物 / スキル / ステータス / 図鑑).
         'b' | 'B' | 'I' | 'i' => {
             state.open_overlay(Overlay::Inventory);
             true
@@ -158,6 +262,7 @@ fn handle_dungeon_explore_key(state: &mut RpgState, ch: char) -> bool {
             state.open_overlay(Overlay::Status);
             true
         }
+        'V' | 'v' => logic::cycle_view_distance(state),
         _ => false,
     }
 }
@@ -177,13 +282,7 @@ fn trigger_a_button(state: &mut RpgState) -> bool {
         if map.is_overworld {
             return false;
         }
-        let px = map.player_x as i32;
-        let py = map.player_y as i32;
-        if map
-            .monsters
-            .iter()
-            .any(|m| m.hp > 0 && (m.x as i32 - px).abs() + (m.y as i32 - py).abs() == 1)
-        {
+        if logic::adjacent_monster(state).is_some() {
             state.open_overlay(Overlay::SkillMenu);
             return true;
         }
@@ -206,7 +305,7 @@ fn handle_dungeon_explore_click(state: &mut RpgState, id: u16) -> bool {
 
 fn handle_dpad_tap(state: &mut RpgState, id: u16) -> bool {
     use crate::widgets::ClickableGrid;
-    let Some((col, row)) = ClickableGrid::decode(DPAD_BASE, 3, id) else {
+    let Some((col, row)) = ClickableGrid::decode(DPAD.base(), 3, id) else {
         return false;
     };
     let dir = match (col, row) {
@@ -224,7 +323,7 @@ fn handle_dpad_tap(state: &mut RpgState, id: u16) -> bool {
 
 fn handle_map_tap(state: &mut RpgState, id: u16) -> bool {
     use crate::widgets::ClickableGrid;
-    let Some((col, row)) = ClickableGrid::decode(MAP_TAP_BASE, 3, id) else {
+    let Some((col, row)) = ClickableGrid::decode(MAP_TAP.base(), 3, id) else {
         return false;
     };
     let screen_dir = match (col, row) {
@@ -287,8 +386,7 @@ fn handle_dungeon_event_click(state: &mut RpgState, id: u16) -> bool {
             .unwrap_or(0);
         return logic::resolve_event_choice(state, last);
     }
-    if (EVENT_CHOICE_BASE..EVENT_CHOICE_BASE + 10).contains(&id) {
-        let index = (id - EVENT_CHOICE_BASE) as usize;
+    if let Some(index) = EVENT_CHOICE.index(id) {
         return logic::resolve_event_choice(state, index);
     }
     handle_overlay_open_click(state, id)
@@ -316,6 +414,14 @@ fn handle_overlay_open_click(state: &mut RpgState, id: u16) -> bool {
 
 // ── Overlays ───────────────────────────────────────────────
 
+/// 前回の冒険記録を共有用テキストにしてクリップボードへ送る。
+/// 結果は RunLog overlay の下に出す。
+fn copy_run_log(state: &mut RpgState) -> bool {
+    let text = logic::run_log_text(&state.last_run_log);
+    state.run_log_copied = Some(crate::scores::copy_to_clipboard(&text));
+    true
+}
+
 fn handle_overlay_key(state: &mut RpgState, ch: char) -> bool {
     // SkillChoice is a forced pick: cancel/close shortcuts must not
     // dismiss it, otherwise the level-up gate would be bypassed and the
@@ -328,12 +434,23 @@ fn handle_overlay_key(state: &mut RpgState, ch: char) -> bool {
             _ => false,
         };
     }
+    // Abyss curse/boon pick is forced the same way.
+    if state.overlay == Some(Overlay::AbyssModifier) {
+        return match ch {
+            ' ' | 'A' | 'a' => logic::confirm_abyss_modifier(state, state.cursor),
+            '1'..='3' => logic::confirm_abyss_modifier(state, (ch as u8 - b'1') as usize),
+            _ => false,
+        };
+    }
 
     // B button / common close shortcuts work for every overlay.
     if matches!(ch, 'b' | 'B' | '0' | '-') {
         state.close_overlay();
         return true;
     }
+    if page_scroll(&state.overlay_scroll, ch) {
+        return true;
+    }
 
     // Tab cycle (h/l) when on a menu tab.
     if state.overlay.map(|o| o.is_menu_tab()).unwrap_or(false) {
@@ -342,6 +459,7 @@ fn handle_overlay_key(state: &mut RpgState, ch: char) -> bool {
                 let next = match state.overlay.unwrap() {
                     Overlay::Inventory => Overlay::SkillMenu,
                     Overlay::SkillMenu => Overlay::Status,
+                    Overlay::Status => Overlay::Bestiary,
                     _ => Overlay::Inventory,
                 };
                 state.open_overlay(next);
@@ -349,9 +467,10 @@ fn handle_overlay_key(state: &mut RpgState, ch: char) -> bool {
             }
             'h' => {
                 let next = match state.overlay.unwrap() {
+                    Overlay::Bestiary => Overlay::Status,
                     Overlay::Status => Overlay::SkillMenu,
                     Overlay::SkillMenu => Overlay::Inventory,
-                    _ => Overlay::Status,
+                    _ => Overlay::Bestiary,
                 };
                 state.open_overlay(next);
                 return true;
@@ -378,7 +497,30 @@ fn handle_overlay_key(state: &mut RpgState, ch: char) -> bool {
             }
             _ => false,
         },
-        Some(Overlay::Status) => false, // status has no clickable items
+        Some(Overlay::Crafting) => match ch {
+            ' ' | 'A' | 'a' => logic::craft(state, state.cursor),
+            '1'..='9' => {
+                let idx = (ch as u32 - '1' as u32) as usize;
+                logic::craft(state, idx)
+            }
+            _ => false,
+        },
+        Some(Overlay::DungeonSelect) => match ch {
+            ' ' | 'A' | 'a' => logic::enter_site(state, state.cursor),
+            '1'..='3' => {
+                let idx = (ch as u32 - '1' as u32) as usize;
+                logic::enter_site(state, idx)
+            }
+            _ => false,
+        },
+        Some(Overlay::RunLog) => match ch {
+            'c' | 'C' => copy_run_log(state),
+            _ => false,
+        },
+        Some(Overlay::Status) => match ch {
+            'V' | 'v' => logic::cycle_view_distance(state),
+            _ => false,
+        },
         Some(Overlay::SkillMenu) => match ch {
             ' ' | 'A' | 'a' => logic::use_skill(state, state.cursor),
             '1'..='9' => {
@@ -415,6 +557,10 @@ fn handle_overlay_key(state: &mut RpgState, ch: char) -> bool {
             '2' => logic::confirm_skill_choice(state, 1),
             _ => false,
         },
+        // Handled by the forced-pick guard at the top.
+        Some(Overlay::AbyssModifier) => false,
+        // Read-only list; scrolling is handled above.
+        Some(Overlay::Bestiary) => false,
         None => false,
     }
 }
@@ -429,13 +575,25 @@ fn handle_overlay_click(state: &mut RpgState, id: u16) -> bool {
             _ => false,
         };
     }
+    if state.overlay == Some(Overlay::AbyssModifier) {
+        return match id {
+            id if ABYSS_PICK.contains(id) => {
+                logic::confirm_abyss_modifier(state, ABYSS_PICK.index(id).unwrap_or(0))
+            }
+            _ => false,
+        };
+    }
 
     if id == CLOSE_OVERLAY {
         state.close_overlay();
         return true;
     }
+    if id == OVERLAY_SCROLL_UP || id == OVERLAY_SCROLL_DOWN {
+        scroll_by(&state.overlay_scroll, if id == OVERLAY_SCROLL_UP { -3 } else { 3 });
+        return true;
+    }
 
-    // Tab switch within the unified menu (Inventory / SkillMenu / Status).
+    // Tab switch within the unified menu (Inventory / SkillMenu / Status / Bestiary).
     if state.overlay.map(|o| o.is_menu_tab()).unwrap_or(false) {
         match id {
             MENU_TAB_INVENTORY => {
@@ -450,32 +608,50 @@ fn handle_overlay_click(state: &mut RpgState, id: u16) -> bool {
                 state.open_overlay(Overlay::Status);
                 return true;
             }
+            MENU_TAB_BESTIARY => {
+                state.open_overlay(Overlay::Bestiary);
+                return true;
+            }
             _ => {}
         }
     }
 
     match state.overlay {
         Some(Overlay::Inventory) => {
-            if (INV_USE_BASE..INV_USE_BASE + 20).contains(&id) {
-                return logic::use_item(state, (id - INV_USE_BASE) as usize);
+            if let Some(index) = INV_USE.index(id) {
+                return logic::use_item(state, index);
             }
             false
         }
         Some(Overlay::Shop) => {
-            if (SHOP_BUY_BASE..SHOP_BUY_BASE + 20).contains(&id) {
-                return logic::buy_item(state, (id - SHOP_BUY_BASE) as usize);
+            if let Some(index) = SHOP_BUY.index(id) {
+                return logic::buy_item(state, index);
+            }
+            false
+        }
+        Some(Overlay::Crafting) => {
+            if let Some(index) = CRAFT.index(id) {
+                return logic::craft(state, index);
+            }
+            false
+        }
+        Some(Overlay::Status) => id == VIEW_DISTANCE && logic::cycle_view_distance(state),
+        Some(Overlay::DungeonSelect) => {
+            if let Some(index) = DUNGEON_PICK.index(id) {
+                return logic::enter_site(state, index);
             }
             false
         }
+        Some(Overlay::RunLog) => id == RUN_LOG_COPY && copy_run_log(state),
         Some(Overlay::SkillMenu) => {
-            if (SKILL_BASE..SKILL_BASE + 10).contains(&id) {
-                return logic::use_skill(state, (id - SKILL_BASE) as usize);
+            if let Some(index) = SKILL.index(id) {
+                return logic::use_skill(state, index);
             }
             false
         }
         Some(Overlay::QuestBoard) => {
-            if (QUEST_ACCEPT_BASE..QUEST_ACCEPT_BASE + 5).contains(&id) {
-                return logic::accept_quest(state, (id - QUEST_ACCEPT_BASE) as usize);
+            if let Some(index) = QUEST_ACCEPT.index(id) {
+                return logic::accept_quest(state, index);
             }
             if id == QUEST_ABANDON {
                 return logic::abandon_quest(state);
@@ -504,12 +680,20 @@ fn handle_overlay_click(state: &mut RpgState, id: u16) -> bool {
 // ── Game Clear ──────────────────────────────────────────────
 
 fn handle_game_clear_key(state: &mut RpgState, ch: char) -> bool {
-    let _ = state;
-    ch == '1' || ch == ' '
+    match ch {
+        '2' => logic::start_abyss(state),
+        ' ' | 'A' | 'a' if state.cursor == 1 => logic::start_abyss(state),
+        '1' | ' ' => true,
+        _ => false,
+    }
 }
 
-fn handle_game_clear_click(_state: &mut RpgState, id: u16) -> bool {
-    id == CHOICE_BASE
+fn handle_game_clear_click(state: &mut RpgState, id: u16) -> bool {
+    // 1. メニューに戻る / 2. 深淵に挑む
+    match CHOICE.index(id) {
+        Some(1) => logic::start_abyss(state),
+        index => index == Some(0),
+    }
 }
 
 // ── Tests ───────────────────────────────────────────────────
@@ -532,6 +716,93 @@ mod tests {
         logic::enter_dungeon(&mut g.state, 1);
     }
 
+    #[test]
+    fn game_clear_screen_leads_into_the_abyss() {
+        let mut g = make_game();
+        g.state.active_event = None;
+        g.state.scene = Scene::GameClear;
+        assert!(!g.handle_input(&click(CHOICE.id(1))), "locked until the lord falls");
+        assert!(g.state.abyss.is_none());
+
+        g.state.game_cleared = true;
+        g.state.scene = Scene::GameClear;
+        assert!(g.handle_input(&InputEvent::Key('2')));
+        assert_eq!(g.state.scene, Scene::DungeonExplore);
+        assert_eq!(g.state.abyss_depth(), 1);
+    }
+
+    #[test]
+    fn abyss_modifier_pick_cannot_be_dismissed() {
+        let mut g = make_game();
+        g.state.active_event = None;
+        g.state.game_cleared = true;
+        logic::start_abyss(&mut g.state);
+        logic::enter_dungeon(&mut g.state, state::MAX_FLOOR + state::ABYSS_MODIFIER_INTERVAL);
+        assert_eq!(g.state.overlay, Some(Overlay::AbyssModifier));
+        for ch in ['b', '0', '-', 'q'] {
+            g.handle_input(&InputEvent::Key(ch));
+            assert_eq!(g.state.overlay, Some(Overlay::AbyssModifier));
+        }
+        assert!(g.handle_input(&click(ABYSS_PICK.id(2))));
+        assert!(g.state.overlay.is_none());
+        assert_eq!(g.state.abyss.as_ref().unwrap().modifiers.len(), 1);
+    }
+
+    #[test]
+    fn score_counts_abyss_depth_past_the_boss_floor() {
+        let mut g = make_game();
+        g.state.max_floor_reached = state::MAX_FLOOR;
+        g.state.abyss_records = vec![4];
+        assert_eq!(g.score().unwrap().value, (state::MAX_FLOOR + 4) as u64);
+    }
+
+    #[test]
+    fn drag_pans_map_and_next_tap_recenters() {
+        use crate::input::DragMotion;
+        let mut g = make_game();
+        into_dungeon(&mut g);
+        let drag = |delta| {
+            InputEvent::Drag(
+                ClickScope::Game(GameChoice::Rpg),
+                DragMotion { origin: MAP_TAP.id(0), target: None, delta, first: false },
+            )
+        };
+        assert!(g.handle_input(&drag((4, -1))));
+        assert!(g.handle_input(&drag((2, 0))));
+        assert_eq!(g.state.map_pan, (6, -1));
+        // 見回しすぎはマップの大きさでクランプ
+        g.handle_input(&drag((i16::MAX, 0)));
+        let w = g.state.dungeon.as_ref().unwrap().width as i32;
+        assert_eq!(g.state.map_pan.0, w * 2);
+
+        g.handle_input(&InputEvent::Key('z'));
+        assert_eq!(g.state.map_pan, (0, 0));
+    }
+
+    #[test]
+    fn action_bar_taps_replay_their_keys() {
+        let mut g = make_game();
+        into_dungeon(&mut g);
+        g.state.active_event = None;
+        let find = |g: &RpgGame, label: &str| {
+            action_bar::actions(&g.state).iter().position(|a| a.label == label).unwrap()
+        };
+
+        let inv = find(&g, "持ち物");
+        assert!(g.handle_input(&click(ACTION_BAR.id(inv))));
+        assert_eq!(g.state.overlay, Some(Overlay::Inventory));
+        let next_tab = find(&g, "▶");
+        assert!(g.handle_input(&click(ACTION_BAR.id(next_tab))));
+        assert_eq!(g.state.overlay, Some(Overlay::SkillMenu));
+        let close = find(&g, "閉じる");
+        assert!(g.handle_input(&click(ACTION_BAR.id(close))));
+        assert!(g.state.overlay.is_none());
+
+        // 今の場面に無いボタンは何もしない
+        let last = action_bar::MAX_BAR_ACTIONS - 1;
+        assert!(!g.handle_input(&click(ACTION_BAR.id(last))));
+    }
+
     #[test]
     fn starts_in_overworld_with_village_loaded() {
         let g = make_game();
@@ -563,6 +834,20 @@ mod tests {
         g.handle_input(&InputEvent::Key('S'));
     }
 
+    #[test]
+    fn view_distance_cycles_from_map_and_status() {
+        use state::ViewDistance;
+        let mut g = make_game();
+        into_dungeon(&mut g);
+        assert_eq!(g.state.view_distance, ViewDistance::Far);
+        assert!(g.handle_input(&InputEvent::Key('v')));
+        assert_eq!(g.state.view_distance, ViewDistance::Near);
+        g.handle_input(&InputEvent::Key('I'));
+        g.handle_input(&click(MENU_TAB_STATUS));
+        assert!(g.handle_input(&click(VIEW_DISTANCE)));
+        assert_eq!(g.state.view_distance, ViewDistance::Normal);
+    }
+
     #[test]
     fn retreat_returns_to_overworld() {
         let mut g = make_game();
@@ -582,6 +867,19 @@ mod tests {
         assert_eq!(g.state.overlay, None);
     }
 
+    #[test]
+    fn overlay_scroll_pages_and_resets_on_tab_switch() {
+        use crate::input::PAGE_DOWN_KEY;
+        let mut g = make_game();
+        g.handle_input(&InputEvent::Key('I'));
+        assert!(g.handle_input(&InputEvent::Key(PAGE_DOWN_KEY)));
+        g.handle_input(&click(OVERLAY_SCROLL_DOWN));
+        assert!(g.state.overlay_scroll.get() > 3);
+        g.handle_input(&click(MENU_TAB_STATUS));
+        assert_eq!(g.state.overlay, Some(Overlay::Status));
+        assert_eq!(g.state.overlay_scroll.get(), 0);
+    }
+
     #[test]
     fn skill_overlay_opens_in_dungeon() {
         let mut g = make_game();
@@ -661,6 +959,21 @@ mod tests {
         assert_eq!(g.state.overlay, Some(Overlay::Inventory));
     }
 
+    #[test]
+    fn bestiary_is_the_fourth_menu_tab() {
+        let mut g = make_game();
+        g.state.overlay = Some(Overlay::Status);
+        g.handle_input(&InputEvent::Key('l'));
+        assert_eq!(g.state.overlay, Some(Overlay::Bestiary));
+        g.handle_input(&InputEvent::Key('l'));
+        assert_eq!(g.state.overlay, Some(Overlay::Inventory));
+        g.handle_input(&InputEvent::Key('h'));
+        assert_eq!(g.state.overlay, Some(Overlay::Bestiary));
+        g.handle_input(&click(MENU_TAB_SKILL));
+        g.handle_input(&click(MENU_TAB_BESTIARY));
+        assert_eq!(g.state.overlay, Some(Overlay::Bestiary));
+    }
+
     #[test]
     fn failed_peddler_purchase_keeps_event_alive() {
         // Codex P1 (#95): a failed buy (insufficient gold) must NOT consume
@@ -7,22 +7,28 @@
 use std::cell::RefCell;
 use std::rc::Rc;
 
-use ratzilla::ratatui::layout::{Constraint, Direction, Layout, Rect};
+use ratzilla::ratatui::layout::{Alignment, Constraint, Direction, Layout, Rect};
 use ratzilla::ratatui::style::{Color, Modifier, Style};
 use ratzilla::ratatui::text::{Line, Span};
 use ratzilla::ratatui::widgets::{Block, Borders, Paragraph, Wrap};
 use ratzilla::ratatui::Frame;
 
 use crate::input::{is_narrow_layout, ClickState};
-use crate::widgets::{Clickable, ClickableGrid, ClickableList, TabBar};
+use crate::theme;
+use crate::widgets::{Clickable, ClickableGrid, ClickableList, Gauge, Modal, TabBar};
 
+use super::action_bar;
 use super::actions::*;
 use super::dungeon_view;
-use super::logic::{available_quests, available_skills, return_bonus};
+use super::logic::{
+    available_quests, available_skills, monster_threat, return_bonus, run_log_text, Threat,
+};
 use super::lore::{floor_theme, theme_name};
 use super::state::{
-    affix_info, element_name, item_info, skill_element, skill_info, Element, Overlay,
-    RpgState, Scene,
+    abyss_modifier_info, abyss_scale_pct, affix_info, difficulty_info, element_name, enemy_info,
+    item_info, known_recipes, recipe_info, site_info, skill_element, skill_info, Difficulty,
+    Element, ItemKind, Overlay, RpgState, RunLogKind, Scene, ALL_DUNGEON_SITES, ALL_ENEMY_KINDS,
+    ALL_RECIPES, LIGHT_DIM, LIGHT_MAX, STAMINA_LOW, STAMINA_MAX,
 };
 
 pub fn render(
@@ -30,16 +36,36 @@ pub fn render(
     f: &mut Frame,
     area: Rect,
     click_state: &Rc<RefCell<ClickState>>,
+) {
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([Constraint::Min(0), Constraint::Length(1)])
+        .split(area);
+    render_screen(state, f, chunks[0], click_state);
+    // Drawn last so its targets survive overlays that reset the click map (Modal).
+    render_action_bar(state, f, chunks[1], click_state);
+}
+
+fn render_screen(
+    state: &RpgState,
+    f: &mut Frame,
+    area: Rect,
+    click_state: &Rc<RefCell<ClickState>>,
 ) {
     if let Some(overlay) = state.overlay {
         match overlay {
             Overlay::Inventory => render_inventory(state, f, area, click_state),
             Overlay::Status => render_status(state, f, area, click_state),
             Overlay::Shop => render_shop(state, f, area, click_state),
+            Overlay::Crafting => render_crafting(state, f, area, click_state),
+            Overlay::DungeonSelect => render_dungeon_select(state, f, area, click_state),
+            Overlay::RunLog => render_run_log(state, f, area, click_state),
+            Overlay::Bestiary => render_bestiary(state, f, area, click_state),
             Overlay::SkillMenu => render_skill_menu(state, f, area, click_state),
             Overlay::QuestBoard => render_quest_board(state, f, area, click_state),
             Overlay::PrayMenu => render_pray_menu(state, f, area, click_state),
             Overlay::SkillChoice => render_skill_choice(state, f, area, click_state),
+            Overlay::AbyssModifier => render_abyss_modifier_choice(state, f, area, click_state),
         }
         return;
     }
@@ -50,21 +76,51 @@ pub fn render(
     }
 }
 
+// ── Action bar ──────────────────────────────────────────────
+
+/// Bottom row of buttons for whatever the current scene accepts
+/// (see [`action_bar`]). The first one is the A-button action.
+fn render_action_bar(
+    state: &RpgState,
+    f: &mut Frame,
+    area: Rect,
+    click_state: &Rc<RefCell<ClickState>>,
+) {
+    let theme = theme::active();
+    let mut bar = TabBar::new(" ");
+    for (i, action) in action_bar::actions(state).into_iter().enumerate() {
+        let style = if action.key == 'a' {
+            Style::default().fg(Color::Black).bg(theme.accent).add_modifier(Modifier::BOLD)
+        } else {
+            Style::default().fg(Color::White).bg(Color::DarkGray)
+        };
+        bar = bar.tab(action.label, style, ACTION_BAR.id(i));
+    }
+    let mut cs = click_state.borrow_mut();
+    bar.render(f, area, &mut cs);
+}
+
 // ── Helper: HP bar ──────────────────────────────────────────
 
-fn hp_bar(current: u32, max: u32, width: usize) -> (String, Color) {
-    let ratio = if max > 0 { current as f64 / max as f64 } else { 0.0 };
-    let filled = (ratio * width as f64).round() as usize;
-    let empty = width.saturating_sub(filled);
-    let bar = "\u{2588}".repeat(filled) + &"\u{2591}".repeat(empty);
-    let color = if ratio > 0.5 {
-        Color::Green
-    } else if ratio > 0.25 {
-        Color::Yellow
-    } else {
-        Color::Red
-    };
-    (bar, color)
+/// HP ゲージ。色は [`theme::active`] から引き、色覚サポート配色では
+/// ♥/♡ の数でも残量が読めるようにする。
+fn hp_gauge(current: u32, max: u32, width: usize) -> Gauge {
+    let theme = theme::active();
+    let gauge = Gauge::new(current as u64, max as u64, width)
+        .color(theme.danger)
+        .threshold(0.25, theme.warn)
+        .threshold(0.5, theme.good);
+    if theme.shapes { gauge.glyphs("\u{2665}", "\u{2661}") } else { gauge }
+}
+
+/// 危険度の表示色。
+fn threat_color(threat: Threat) -> Color {
+    let theme = theme::active();
+    match threat {
+        Threat::Low => theme.good,
+        Threat::Medium => theme.warn,
+        Threat::High => theme.danger,
+    }
 }
 
 /// 属性ごとの表示色（弱点表示で使用）。
@@ -85,6 +141,26 @@ fn satiety_color(s: u32, max: u32) -> Color {
     else { Color::Red }
 }
 
+fn light_color(light: u32) -> Color {
+    if light == 0 {
+        Color::DarkGray
+    } else if light < LIGHT_DIM {
+        Color::Red
+    } else {
+        Color::LightYellow
+    }
+}
+
+fn stamina_color(stamina: u32) -> Color {
+    if stamina == 0 {
+        Color::DarkGray
+    } else if stamina <= STAMINA_LOW {
+        Color::Yellow
+    } else {
+        Color::Green
+    }
+}
+
 fn borders_for(area_width: u16) -> Borders {
     if is_narrow_layout(area_width) {
         Borders::TOP | Borders::BOTTOM
@@ -143,23 +219,15 @@ fn render_status_bar(
     is_narrow: bool,
 ) {
     let hp_w = if is_narrow { 6 } else { 10 };
-    let (hp_bar_str, hp_color) = hp_bar(state.hp, state.effective_max_hp(), hp_w);
+    let hp = hp_gauge(state.hp, state.effective_max_hp(), hp_w);
 
     let mp_w = if is_narrow { 4 } else { 6 };
-    let mp_ratio = if state.max_mp > 0 { state.mp as f64 / state.max_mp as f64 } else { 0.0 };
-    let mp_filled = (mp_ratio * mp_w as f64).round() as usize;
-    let mp_empty = mp_w - mp_filled;
-    let mp_bar_str = "\u{2588}".repeat(mp_filled) + &"\u{2591}".repeat(mp_empty);
+    let mp = Gauge::new(state.mp as u64, state.max_mp as u64, mp_w).color(Color::Blue);
 
     // Satiety bar
     let sat_w = if is_narrow { 4 } else { 6 };
-    let sat_ratio = if state.satiety_max > 0 {
-        state.satiety as f64 / state.satiety_max as f64
-    } else { 0.0 };
-    let sat_filled = (sat_ratio * sat_w as f64).round() as usize;
-    let sat_empty = sat_w - sat_filled;
-    let sat_bar_str = "\u{2588}".repeat(sat_filled) + &"\u{2591}".repeat(sat_empty);
-    let sat_color = satiety_color(state.satiety, state.satiety_max);
+    let sat = Gauge::new(state.satiety as u64, state.satiety_max as u64, sat_w)
+        .color(satiety_color(state.satiety, state.satiety_max));
 
     let mut spans = vec![
         Span::styled(
@@ -167,24 +235,36 @@ fn render_status_bar(
             Style::default().fg(Color::Yellow).add_modifier(Modifier::BOLD),
         ),
         Span::styled(" HP", Style::default().fg(Color::Gray)),
-        Span::styled(hp_bar_str, Style::default().fg(hp_color)),
+        hp.span(),
         Span::styled(
             format!("{}/{}", state.hp, state.effective_max_hp()),
             Style::default().fg(Color::White),
         ),
         Span::styled(" MP", Style::default().fg(Color::Gray)),
-        Span::styled(mp_bar_str, Style::default().fg(Color::Blue)),
+        mp.span(),
         Span::styled(
             format!("{}/{}", state.mp, state.max_mp),
             Style::default().fg(Color::White),
         ),
         Span::styled(" 食", Style::default().fg(Color::Gray)),
-        Span::styled(sat_bar_str, Style::default().fg(sat_color)),
-        Span::styled(
-            format!(" {}G", state.gold),
-            Style::default().fg(Color::Yellow),
-        ),
+        sat.span(),
     ];
+    // 明かりとスタミナはダンジョンの中だけ意味を持つ。
+    if state.dungeon.as_ref().is_some_and(|m| !m.is_overworld) {
+        let light_w = if is_narrow { 4 } else { 6 };
+        let light = Gauge::new(state.light as u64, LIGHT_MAX as u64, light_w)
+            .color(light_color(state.light));
+        spans.push(Span::styled(" 灯", Style::default().fg(Color::Gray)));
+        spans.push(light.span());
+        let stamina = Gauge::new(state.stamina as u64, STAMINA_MAX as u64, light_w)
+            .color(stamina_color(state.stamina));
+        spans.push(Span::styled(" 気", Style::default().fg(Color::Gray)));
+        spans.push(stamina.span());
+    }
+    spans.push(Span::styled(
+        format!(" {}G", state.gold),
+        Style::default().fg(Color::Yellow),
+    ));
 
     if state.buffs.shield_turns > 0 || state.buffs.berserk_turns > 0 || state.buffs.potion_turns > 0 {
         let mut s = String::from(" ");
@@ -194,6 +274,19 @@ fn render_status_bar(
         spans.push(Span::styled(s, Style::default().fg(Color::Magenta)));
     }
 
+    if state.no_damage_streak > 0 {
+        let pct = state.streak_bonus_pct();
+        let label = if is_narrow {
+            format!(" 無傷{}", state.no_damage_streak)
+        } else {
+            format!(" 無傷{}連 ×{}.{}", state.no_damage_streak, pct / 100, pct % 100 / 10)
+        };
+        spans.push(Span::styled(
+            label,
+            Style::default().fg(Color::LightGreen).add_modifier(Modifier::BOLD),
+        ));
+    }
+
     let title = if is_narrow { " Dungeon " } else { " Dungeon Dive " };
     let block = Block::default()
         .borders(borders)
@@ -213,12 +306,21 @@ fn render_floor_indicator(state: &RpgState, f: &mut Frame, area: Rect, borders:
             .border_style(Style::default().fg(Color::DarkGray));
 
         if map.is_overworld {
-            // Village indicator: just label + facility legend hint.
+            // Village indicator: label + difficulty + facility legend hint.
+            let difficulty_color = match state.difficulty {
+                Difficulty::Casual => Color::Green,
+                Difficulty::Normal => Color::Gray,
+                Difficulty::Ironman => Color::Red,
+            };
             let line = Line::from(vec![
                 Span::styled(
                     " 〈村〉 ",
                     Style::default().fg(Color::Yellow).add_modifier(Modifier::BOLD),
                 ),
+                Span::styled(
+                    format!("[{}] ", difficulty_info(state.difficulty).name),
+                    Style::default().fg(difficulty_color).add_modifier(Modifier::BOLD),
+                ),
                 Span::styled(
                     format!("〈{}〉 ", theme_name(theme)),
                     Style::default().fg(Color::Cyan),
@@ -253,15 +355,31 @@ fn render_floor_indicator(state: &RpgState, f: &mut Frame, area: Rect, borders:
             Span::styled(" 敵0", Style::default().fg(Color::DarkGray))
         };
 
-        let line = Line::from(vec![
+        let mut spans = vec![
             Span::styled(
-                format!(" B{}F ", map.floor_num),
+                format!(" {} B{}F ", site_info(state.current_site()).name, map.floor_num),
                 Style::default().fg(Color::Yellow).add_modifier(Modifier::BOLD),
             ),
             Span::styled(
                 format!("〈{}〉", theme_name(theme)),
                 Style::default().fg(Color::Cyan),
             ),
+        ];
+        if let Some(run) = &state.abyss {
+            spans.push(Span::styled(
+                format!(" 深淵{}層 敵{}%", run.depth, state.abyss_enemy_atk_pct()),
+                Style::default().fg(Color::Magenta).add_modifier(Modifier::BOLD),
+            ));
+            if !run.modifiers.is_empty() {
+                let names: Vec<&str> =
+                    run.modifiers.iter().map(|m| abyss_modifier_info(*m).name).collect();
+                spans.push(Span::styled(
+                    format!(" [{}]", names.join("・")),
+                    Style::default().fg(Color::Magenta),
+                ));
+            }
+        }
+        spans.extend([
             Span::styled(
                 format!(" 探索:{}", state.run_rooms_explored),
                 Style::default().fg(Color::Gray),
@@ -269,6 +387,7 @@ fn render_floor_indicator(state: &RpgState, f: &mut Frame, area: Rect, borders:
             monster_span,
             bonus_span,
         ]);
+        let line = Line::from(spans);
 
         f.render_widget(Paragraph::new(vec![line]).block(block), area);
     }
@@ -350,10 +469,22 @@ fn render_2d_map(
         None => return,
     };
 
-    let inner_w = area.width.saturating_sub(2) as usize;
-    let inner_h = area.height.saturating_sub(2) as usize;
-
-    let map_lines = dungeon_view::render_map_2d(map, theme, inner_w, inner_h, state.pet.as_ref());
+    // 描画距離より広い分は描かない (一辺 max_tiles タイル、横は 2 セル/タイル)
+    let max_tiles = state.view_distance.max_tiles();
+    let inner_w = (area.width.saturating_sub(2) as usize).min(max_tiles * 2);
+    let inner_h = (area.height.saturating_sub(2) as usize).min(max_tiles);
+
+    let pan = (state.map_pan.0 / 2, state.map_pan.1);
+    let mut map_lines =
+        dungeon_view::render_map_2d(
+            map,
+            theme,
+            inner_w,
+            inner_h,
+            state.pet.as_ref(),
+            pan,
+            state.is_dark(),
+        );
 
     let block = Block::default()
         .borders(borders)
@@ -363,12 +494,17 @@ fn render_2d_map(
     if inner.height >= 3 && inner.width >= 6 {
         let cell_w = inner.width / 3;
         let cell_h = inner.height / 3;
-        let grid = ClickableGrid::new(3, 3, MAP_TAP_BASE, cell_w).with_cell_height(cell_h);
+        let grid = ClickableGrid::new(3, 3, MAP_TAP.base(), cell_w).with_cell_height(cell_h);
         let mut cs = click_state.borrow_mut();
         grid.register_targets(area, &block, &mut cs, 0);
+        // なぞるとマップを見回せる (離して次にタップすると中心に戻る)
+        cs.add_drag_area(inner);
     }
 
-    f.render_widget(Paragraph::new(map_lines).block(block), area);
+    // 描画距離を絞ってマップが枠より小さいときは、枠の中央に置く
+    let top_pad = (inner.height as usize).saturating_sub(map_lines.len()) / 2;
+    map_lines.splice(0..0, vec![Line::from(""); top_pad]);
+    f.render_widget(Paragraph::new(map_lines).alignment(Alignment::Center).block(block), area);
 }
 
 fn render_explore_panel(
@@ -409,18 +545,22 @@ fn render_explore_panel(
         if let Some(m) = map.monsters.iter().find(|m| {
             m.hp > 0 && (m.x as i32 - px).abs() + (m.y as i32 - py).abs() == 1
         }) {
-            let (hpb, c) = hp_bar(m.hp, m.max_hp, 8);
             // Elite mobs adopt the magenta highlight from the map view.
             let name_color = if m.affix.is_some() { Color::Magenta } else { Color::Red };
+            let threat = monster_threat(state, m);
             cl.push(Line::from(vec![
                 Span::styled(
                     format!(" 敵: {}", m.display_name()),
                     Style::default().fg(name_color).add_modifier(Modifier::BOLD),
                 ),
+                Span::styled(
+                    format!(" {}{}", threat.glyph(), threat.label()),
+                    Style::default().fg(threat_color(threat)).add_modifier(Modifier::BOLD),
+                ),
             ]));
             cl.push(Line::from(vec![
                 Span::styled(" HP", Style::default().fg(Color::Gray)),
-                Span::styled(hpb, Style::default().fg(c)),
+                hp_gauge(m.hp, m.max_hp, 8).span(),
                 Span::styled(
                     format!(" {}/{}", m.hp, m.max_hp),
                     Style::default().fg(Color::White),
@@ -457,14 +597,13 @@ fn render_explore_panel(
 
         // Pet HP if any
         if let Some(p) = &state.pet {
-            let (hpb, c) = hp_bar(p.hp, p.max_hp, 6);
             cl.push(Line::from(vec![
                 Span::styled(
                     format!(" {}", p.name),
                     Style::default().fg(Color::Cyan),
                 ),
                 Span::styled(" HP", Style::default().fg(Color::Gray)),
-                Span::styled(hpb, Style::default().fg(c)),
+                hp_gauge(p.hp, p.max_hp, 6).span(),
                 Span::styled(
                     format!(" {}/{}", p.hp, p.max_hp),
                     Style::default().fg(Color::White),
@@ -548,9 +687,6 @@ fn render_dpad(
         return;
     }
 
-    let col_w = area.width / 3;
-    let cell_h = (area.height / 3).max(1);
-
     let dir_style = |dir: Facing| -> Style {
         let nx = map.player_x as i32 + dir.dx();
         let ny = map.player_y as i32 + dir.dy();
@@ -571,53 +707,29 @@ fn render_dpad(
         Style::default().fg(Color::White).add_modifier(Modifier::BOLD)
     };
 
-    let center_in = |label: &str, width: usize| -> String {
-        let label_len = label.chars().count();
-        let pad_left = width.saturating_sub(label_len) / 2;
-        let pad_right = width.saturating_sub(pad_left + label_len);
-        format!("{}{}{}", " ".repeat(pad_left), label, " ".repeat(pad_right))
-    };
-
-    let cw = col_w as usize;
-    let blank = " ".repeat(cw);
-    let lines = vec![
-        Line::from(vec![
-            Span::raw(blank.clone()),
-            Span::styled(center_in("[ \u{25b2} ]", cw), dir_style(Facing::North)),
-            Span::raw(blank.clone()),
-        ]),
-        Line::from(vec![
-            Span::styled(center_in("[ \u{25c0} ]", cw), dir_style(Facing::West)),
-            Span::raw(blank.clone()),
-            Span::styled(center_in("[ \u{25b6} ]", cw), dir_style(Facing::East)),
-        ]),
-        Line::from(vec![
-            Span::raw(blank.clone()),
-            Span::styled(center_in("[ \u{25bc} ]", cw), dir_style(Facing::South)),
-            Span::raw(blank),
-        ]),
-    ];
-
-    f.render_widget(Paragraph::new(lines), area);
-
-    let grid = ClickableGrid::new(3, 3, DPAD_BASE, col_w).with_cell_height(cell_h);
-    let no_block = Block::default();
+    // 四隅は空白 (タップしても何も起きないので的にもしない)
     let mut cs = click_state.borrow_mut();
-    grid.register_targets(area, &no_block, &mut cs, 0);
+    ClickableGrid::fill(3, 3, DPAD.base(), area)
+        .button(1, 0, Line::styled("[ \u{25b2} ]", dir_style(Facing::North)))
+        .button(0, 1, Line::styled("[ \u{25c0} ]", dir_style(Facing::West)))
+        .button(2, 1, Line::styled("[ \u{25b6} ]", dir_style(Facing::East)))
+        .button(1, 2, Line::styled("[ \u{25bc} ]", dir_style(Facing::South)))
+        .render(f, area, Block::default(), &mut cs);
 }
 
 fn render_hp_warning(cl: &mut ClickableList, state: &RpgState) {
+    let theme = theme::active();
     let max_hp = state.effective_max_hp();
     let hp_ratio = if max_hp > 0 { state.hp as f64 / max_hp as f64 } else { 1.0 };
     if hp_ratio <= 0.25 && hp_ratio > 0.0 {
         cl.push(Line::from(Span::styled(
             " ※ 体力が危険！",
-            Style::default().fg(Color::Red).add_modifier(Modifier::BOLD),
+            Style::default().fg(theme.danger).add_modifier(Modifier::BOLD),
         )));
     } else if hp_ratio <= 0.5 {
         cl.push(Line::from(Span::styled(
             " ※ 傷が痛む…",
-            Style::default().fg(Color::Yellow),
+            Style::default().fg(theme.warn),
         )));
     }
     if state.satiety == 0 {
@@ -728,7 +840,7 @@ fn render_event_popup(
                 ),
                 Span::styled(choice.label.clone(), label_style),
             ]),
-            EVENT_CHOICE_BASE + i as u16,
+            EVENT_CHOICE.id(i),
         );
     }
 
@@ -764,31 +876,12 @@ fn render_log(state: &RpgState, f: &mut Frame, area: Rect, borders: Borders) {
     );
 }
 
-// ── Choice Helpers ──────────────────────────────────────────
-
-/// Game Clear screen reuses this for its single "メニューに戻る" button.
-fn push_choice(cl: &mut ClickableList, index: usize, label: &str) {
-    cl.push_clickable(
-        Line::from(vec![
-            Span::styled(
-                "   ",
-                Style::default().fg(Color::Yellow).add_modifier(Modifier::BOLD),
-            ),
-            Span::styled(
-                format!("{}. ", index + 1),
-                Style::default().fg(Color::Cyan),
-            ),
-            Span::styled(label.to_string(), Style::default().fg(Color::White)),
-        ]),
-        CHOICE_BASE + index as u16,
-    );
-}
-
 // ── Overlays ────────────────────────────────────────────────
 
-/// Render the unified menu tab bar (持ち物 / スキル / ステータス).
+/// Render the unified menu tab bar (持ち物 / スキル / ステータス / 図鑑).
 /// Returns the area below the tab bar for the panel content.
 fn render_menu_tabs(
+    state: &RpgState,
     f: &mut Frame,
     area: Rect,
     active: Overlay,
@@ -798,25 +891,19 @@ fn render_menu_tabs(
         return area;
     }
     let tab_area = Rect::new(area.x, area.y, area.width, 1);
-    let style_for = |o: Overlay| -> Style {
-        if o == active {
-            Style::default().fg(Color::Yellow).add_modifier(Modifier::BOLD)
-        } else {
-            Style::default().fg(Color::DarkGray)
-        }
+    let active_id = match active {
+        Overlay::SkillMenu => MENU_TAB_SKILL,
+        Overlay::Status => MENU_TAB_STATUS,
+        Overlay::Bestiary => MENU_TAB_BESTIARY,
+        _ => MENU_TAB_INVENTORY,
     };
     let bar = TabBar::new(" │ ")
-        .tab(
-            "持ち物",
-            style_for(Overlay::Inventory),
-            MENU_TAB_INVENTORY,
-        )
-        .tab("スキル", style_for(Overlay::SkillMenu), MENU_TAB_SKILL)
-        .tab(
-            "ステータス",
-            style_for(Overlay::Status),
-            MENU_TAB_STATUS,
-        );
+        .colored("持ち物", Color::Yellow, MENU_TAB_INVENTORY)
+        .colored("スキル", Color::Yellow, MENU_TAB_SKILL)
+        .badge(state.pending_skill_choice.is_some() as u64)
+        .colored("ステータス", Color::Yellow, MENU_TAB_STATUS)
+        .colored("図鑑", Color::Yellow, MENU_TAB_BESTIARY)
+        .active(active_id);
     let mut cs = click_state.borrow_mut();
     bar.render(f, tab_area, &mut cs);
     Rect::new(area.x, area.y + 1, area.width, area.height - 1)
@@ -828,7 +915,7 @@ fn render_inventory(
     area: Rect,
     click_state: &Rc<RefCell<ClickState>>,
 ) {
-    let area = render_menu_tabs(f, area, Overlay::Inventory, click_state);
+    let area = render_menu_tabs(state, f, area, Overlay::Inventory, click_state);
     let borders = borders_for(area.width);
     let mut cl = ClickableList::new();
 
@@ -889,7 +976,7 @@ fn render_inventory(
                             Style::default().fg(Color::DarkGray),
                         ),
                     ]),
-                    INV_USE_BASE + i as u16,
+                    INV_USE.id(i),
                 );
             } else {
                 cl.push(Line::from(Span::styled(
@@ -918,7 +1005,10 @@ fn render_inventory(
         ));
 
     let mut cs = click_state.borrow_mut();
-    cl.render(f, area, block, &mut cs, false, 0);
+    cl.scrollable(&state.overlay_scroll, OVERLAY_SCROLL_UP, OVERLAY_SCROLL_DOWN)
+        .block(block)
+        .arrow_color(Color::Green)
+        .render(f, area, &mut cs);
 }
 
 fn render_status(
@@ -927,7 +1017,7 @@ fn render_status(
     area: Rect,
     click_state: &Rc<RefCell<ClickState>>,
 ) {
-    let area = render_menu_tabs(f, area, Overlay::Status, click_state);
+    let area = render_menu_tabs(state, f, area, Overlay::Status, click_state);
     let borders = borders_for(area.width);
     let mut cl = ClickableList::new();
 
@@ -1046,6 +1136,17 @@ fn render_status(
     }
 
     cl.push(Line::from(""));
+    cl.push_clickable(
+        Line::from(vec![
+            Span::styled(" 👁 マップの描画距離: ", Style::default().fg(Color::Gray)),
+            Span::styled(
+                format!("{} ▶", state.view_distance.name()),
+                Style::default().fg(Color::Cyan).add_modifier(Modifier::BOLD),
+            ),
+            Span::styled(" [v] 近いほど軽い", Style::default().fg(Color::DarkGray)),
+        ]),
+        VIEW_DISTANCE,
+    );
     cl.push_clickable(
         Line::from(Span::styled(" ✕ 閉じる", Style::default().fg(Color::Yellow))),
         CLOSE_OVERLAY,
@@ -1060,7 +1161,10 @@ fn render_status(
         ));
 
     let mut cs = click_state.borrow_mut();
-    cl.render(f, area, block, &mut cs, false, 0);
+    cl.scrollable(&state.overlay_scroll, OVERLAY_SCROLL_UP, OVERLAY_SCROLL_DOWN)
+        .block(block)
+        .arrow_color(Color::Cyan)
+        .render(f, area, &mut cs);
 }
 
 fn render_shop(
@@ -1078,10 +1182,20 @@ fn render_shop(
     )));
     cl.push(Line::from(""));
 
-    let shop = super::state::shop_items(state.max_floor_reached);
-    for (i, &(kind, _)) in shop.iter().enumerate() {
-        let iinfo = item_info(kind);
-        let affordable = state.gold >= iinfo.buy_price;
+    let is_merchant = state.merchant.is_some();
+    if is_merchant {
+        cl.push(Line::from(Span::styled(
+            " 旅の商人: 割高・品数限り (この場限り)",
+            Style::default().fg(Color::LightYellow),
+        )));
+        cl.push(Line::from(""));
+    }
+
+    let shop = state.shop_offers();
+    for (i, offer) in shop.iter().enumerate() {
+        let iinfo = item_info(offer.kind);
+        let sold_out = offer.left == Some(0);
+        let affordable = state.gold >= offer.price && !sold_out;
         let selected = i == state.cursor;
         let color = if !affordable {
             Color::DarkGray
@@ -1103,15 +1217,23 @@ fn render_shop(
                         Style::default().fg(Color::DarkGray),
                     ),
                     Span::styled(
-                        format!("{} {}G", iinfo.name, iinfo.buy_price),
+                        format!("{} {}G", iinfo.name, offer.price),
                         Style::default().fg(color),
                     ),
+                    Span::styled(
+                        match offer.left {
+                            Some(0) => " 売り切れ".to_string(),
+                            Some(n) => format!(" 残り{}", n),
+                            None => String::new(),
+                        },
+                        Style::default().fg(Color::LightYellow),
+                    ),
                     Span::styled(
                         format!(" - {}", iinfo.description),
                         Style::default().fg(Color::DarkGray),
                     ),
                 ]),
-                SHOP_BUY_BASE + i as u16,
+                SHOP_BUY.id(i),
             );
         }
     }
@@ -1126,7 +1248,7 @@ fn render_shop(
         .borders(borders)
         .border_style(Style::default().fg(Color::Green))
         .title(Span::styled(
-            " ショップ ",
+            if is_merchant { " 旅の商人 " } else { " ショップ " },
             Style::default().fg(Color::Green).add_modifier(Modifier::BOLD),
         ));
 
@@ -1134,13 +1256,324 @@ fn render_shop(
     cl.render(f, area, block, &mut cs, false, 0);
 }
 
+fn render_crafting(
+    state: &RpgState,
+    f: &mut Frame,
+    area: Rect,
+    click_state: &Rc<RefCell<ClickState>>,
+) {
+    let borders = borders_for(area.width);
+    let mut cl = ClickableList::new();
+
+    let materials: Vec<String> = [ItemKind::WildHerb, ItemKind::IronOre, ItemKind::BeastFang]
+        .iter()
+        .map(|&k| format!("{}x{}", item_info(k).name, state.item_count(k)))
+        .collect();
+    cl.push(Line::from(Span::styled(
+        format!(" 素材: {}", materials.join(" / ")),
+        Style::default().fg(Color::Yellow).add_modifier(Modifier::BOLD),
+    )));
+    cl.push(Line::from(""));
+
+    let recipes = known_recipes(state.max_floor_reached);
+    for (i, &recipe) in recipes.iter().enumerate().take(9) {
+        let info = recipe_info(recipe);
+        let craftable = info.inputs.iter().all(|&(k, n)| state.item_count(k) >= n);
+        let selected = i == state.cursor;
+        let color = if !craftable {
+            Color::DarkGray
+        } else if selected {
+            Color::Yellow
+        } else {
+            Color::White
+        };
+        let prefix = if selected { "▶" } else { " " };
+        let inputs: Vec<String> = info
+            .inputs
+            .iter()
+            .map(|&(k, n)| format!("{}x{}", item_info(k).name, n))
+            .collect();
+        cl.push_clickable(
+            Line::from(vec![
+                Span::styled(
+                    format!(" {} ", prefix),
+                    Style::default().fg(Color::Yellow).add_modifier(Modifier::BOLD),
+                ),
+                Span::styled(format!("{}. ", i + 1), Style::default().fg(Color::DarkGray)),
+                Span::styled(info.name, Style::default().fg(color)),
+                Span::styled(
+                    format!(" {} → {}", inputs.join("+"), info.description),
+                    Style::default().fg(Color::DarkGray),
+                ),
+            ]),
+            CRAFT.id(i),
+        );
+    }
+    if let Some(next) = ALL_RECIPES
+        .iter()
+        .map(|&r| recipe_info(r))
+        .filter(|r| r.min_floor > state.max_floor_reached)
+        .min_by_key(|r| r.min_floor)
+    {
+        cl.push(Line::from(Span::styled(
+            format!("   B{}F 到達で「{}」が解禁", next.min_floor, next.name),
+            Style::default().fg(Color::DarkGray),
+        )));
+    }
+
+    cl.push(Line::from(""));
+    cl.push_clickable(
+        Line::from(Span::styled(" ✕ 閉じる", Style::default().fg(Color::Yellow))),
+        CLOSE_OVERLAY,
+    );
+
+    let block = Block::default()
+        .borders(borders)
+        .border_style(Style::default().fg(Color::Green))
+        .title(Span::styled(
+            " 工房 ",
+            Style::default().fg(Color::Green).add_modifier(Modifier::BOLD),
+        ));
+
+    let mut cs = click_state.borrow_mut();
+    cl.render(f, area, block, &mut cs, false, 0);
+}
+
+fn render_dungeon_select(
+    state: &RpgState,
+    f: &mut Frame,
+    area: Rect,
+    click_state: &Rc<RefCell<ClickState>>,
+) {
+    let borders = borders_for(area.width);
+    let mut cl = ClickableList::new();
+
+    for (i, &site) in ALL_DUNGEON_SITES.iter().enumerate() {
+        let info = site_info(site);
+        let unlocked = state.site_unlocked(site);
+        let selected = i == state.cursor;
+        let color = if !unlocked {
+            Color::DarkGray
+        } else if selected {
+            Color::Yellow
+        } else {
+            Color::White
+        };
+        let prefix = if selected { "▶" } else { " " };
+        let floors = match info.floors {
+            Some(n) => format!("全{}階", n),
+            None => "底なし".into(),
+        };
+        let status = if state.site_cleared(site) {
+            Span::styled(" クリア済", Style::default().fg(Color::Green))
+        } else if unlocked {
+            Span::styled(" 挑戦可", Style::default().fg(Color::Cyan))
+        } else {
+            Span::styled(format!(" 🔒 {}", info.unlock_hint), Style::default().fg(Color::DarkGray))
+        };
+        cl.push_clickable(
+            Line::from(vec![
+                Span::styled(
+                    format!(" {} ", prefix),
+                    Style::default().fg(Color::Yellow).add_modifier(Modifier::BOLD),
+                ),
+                Span::styled(format!("{}. ", i + 1), Style::default().fg(Color::DarkGray)),
+                Span::styled(info.name, Style::default().fg(color)),
+                Span::styled(
+                    format!(" ({} / 主: {})", floors, enemy_info(info.boss).name),
+                    Style::default().fg(Color::DarkGray),
+                ),
+                status,
+            ]),
+            DUNGEON_PICK.id(i),
+        );
+        cl.push(Line::from(Span::styled(
+            format!("      {}", info.description),
+            Style::default().fg(Color::DarkGray),
+        )));
+    }
+
+    cl.push(Line::from(""));
+    cl.push_clickable(
+        Line::from(Span::styled(" ✕ 閉じる", Style::default().fg(Color::Yellow))),
+        CLOSE_OVERLAY,
+    );
+
+    let block = Block::default()
+        .borders(borders)
+        .border_style(Style::default().fg(Color::Cyan))
+        .title(Span::styled(
+            " 行き先 ",
+            Style::default().fg(Color::Cyan).add_modifier(Modifier::BOLD),
+        ));
+
+    let mut cs = click_state.borrow_mut();
+    cl.render(f, area, block, &mut cs, false, 0);
+}
+
+/// モンスター図鑑。遭遇した敵だけ能力と撃破数を出し、ドロップは
+/// 実際に拾ったものだけ明かす。
+fn render_bestiary(
+    state: &RpgState,
+    f: &mut Frame,
+    area: Rect,
+    click_state: &Rc<RefCell<ClickState>>,
+) {
+    let area = render_menu_tabs(state, f, area, Overlay::Bestiary, click_state);
+    let borders = borders_for(area.width);
+    let mut cl = ClickableList::new();
+
+    let unknown = Style::default().fg(Color::DarkGray);
+    cl.push(Line::from(Span::styled(
+        format!(" 登録 {}/{}", state.bestiary.len(), ALL_ENEMY_KINDS.len()),
+        Style::default().fg(Color::White),
+    )));
+    for kind in ALL_ENEMY_KINDS {
+        let Some(entry) = state.bestiary_entry(kind) else {
+            cl.push(Line::from(Span::styled(" ？ ？？？", unknown)));
+            continue;
+        };
+        let info = enemy_info(kind);
+        cl.push(Line::from(""));
+        cl.push(Line::from(vec![
+            Span::styled(
+                format!(" {} {}", info.glyph, info.name),
+                Style::default().fg(Color::Yellow).add_modifier(Modifier::BOLD),
+            ),
+            Span::styled(
+                format!("  遭遇 {} / 撃破 {}", entry.encountered, entry.killed),
+                Style::default().fg(Color::Gray),
+            ),
+        ]));
+        let weak = if state.weakness_known(kind) {
+            state.known_weakness(kind).map_or("なし", element_name)
+        } else {
+            "？"
+        };
+        cl.push(Line::from(Span::styled(
+            format!(
+                "   HP{} ATK{} DEF{}  EXP{} {}G  弱点:{}",
+                info.max_hp, info.atk, info.def, info.exp, info.gold, weak
+            ),
+            Style::default().fg(Color::White),
+        )));
+        let drop_text = |slot: Option<(ItemKind, u32)>, seen: bool| match slot {
+            Some((item, pct)) if seen => format!("{} {}%", item_info(item).name, pct),
+            Some(_) => "？？？".to_string(),
+            None => "なし".to_string(),
+        };
+        cl.push(Line::from(Span::styled(
+            format!(
+                "   ドロップ: {}  素材: {}",
+                drop_text(info.drop, entry.drop_seen),
+                drop_text(info.material, entry.material_seen)
+            ),
+            Style::default().fg(Color::Cyan),
+        )));
+    }
+
+    cl.push(Line::from(""));
+    cl.push_clickable(
+        Line::from(Span::styled(" ✕ 閉じる", Style::default().fg(Color::Yellow))),
+        CLOSE_OVERLAY,
+    );
+
+    let block = Block::default()
+        .borders(borders)
+        .border_style(Style::default().fg(Color::Cyan))
+        .title(Span::styled(
+            " モンスター図鑑 ",
+            Style::default().fg(Color::Cyan).add_modifier(Modifier::BOLD),
+        ));
+
+    let mut cs = click_state.borrow_mut();
+    cl.scrollable(&state.overlay_scroll, OVERLAY_SCROLL_UP, OVERLAY_SCROLL_DOWN)
+        .block(block)
+        .arrow_color(Color::Cyan)
+        .render(f, area, &mut cs);
+}
+
+fn render_run_log(
+    state: &RpgState,
+    f: &mut Frame,
+    area: Rect,
+    click_state: &Rc<RefCell<ClickState>>,
+) {
+    let borders = borders_for(area.width);
+    let mut cl = ClickableList::new();
+
+    // 集計行は共有テキストと同じものを出す。
+    let text = run_log_text(&state.last_run_log);
+    let summary = text.lines().next().unwrap_or_default();
+    cl.push(Line::from(Span::styled(
+        format!(" {}", summary),
+        Style::default().fg(Color::Yellow).add_modifier(Modifier::BOLD),
+    )));
+    cl.push_clickable(
+        Line::from(Span::styled(
+            " ▶ 共有用にコピー [c]",
+            Style::default().fg(Color::Green).add_modifier(Modifier::BOLD),
+        )),
+        RUN_LOG_COPY,
+    );
+    match state.run_log_copied {
+        Some(true) => cl.push(Line::from(Span::styled(
+            " ✓ クリップボードにコピーしました",
+            Style::default().fg(Color::Green),
+        ))),
+        Some(false) => cl.push(Line::from(Span::styled(
+            " ✕ コピーできません",
+            Style::default().fg(Color::Red),
+        ))),
+        None => {}
+    }
+    cl.push(Line::from(""));
+
+    for entry in &state.last_run_log {
+        let color = match entry.kind {
+            RunLogKind::Floor => Color::Cyan,
+            RunLogKind::Event => Color::Magenta,
+            RunLogKind::Kill => Color::Yellow,
+            RunLogKind::Dealt(_) => Color::White,
+            RunLogKind::Taken(_) => Color::Red,
+        };
+        cl.push(Line::from(vec![
+            Span::styled(
+                format!(" B{}F {:>4} ", entry.floor, entry.turn),
+                Style::default().fg(Color::DarkGray),
+            ),
+            Span::styled(format!("{} {}", entry.kind.mark(), entry.text), Style::default().fg(color)),
+        ]));
+    }
+
+    cl.push(Line::from(""));
+    cl.push_clickable(
+        Line::from(Span::styled(" ✕ 閉じる", Style::default().fg(Color::Yellow))),
+        CLOSE_OVERLAY,
+    );
+
+    let block = Block::default()
+        .borders(borders)
+        .border_style(Style::default().fg(Color::Cyan))
+        .title(Span::styled(
+            " 冒険記録 ",
+            Style::default().fg(Color::Cyan).add_modifier(Modifier::BOLD),
+        ));
+
+    let mut cs = click_state.borrow_mut();
+    cl.scrollable(&state.overlay_scroll, OVERLAY_SCROLL_UP, OVERLAY_SCROLL_DOWN)
+        .block(block)
+        .arrow_color(Color::Cyan)
+        .render(f, area, &mut cs);
+}
+
 fn render_skill_menu(
     state: &RpgState,
     f: &mut Frame,
     area: Rect,
     click_state: &Rc<RefCell<ClickState>>,
 ) {
-    let area = render_menu_tabs(f, area, Overlay::SkillMenu, click_state);
+    let area = render_menu_tabs(state, f, area, Overlay::SkillMenu, click_state);
     let borders = borders_for(area.width);
     let mut cl = ClickableList::new();
 
@@ -1187,7 +1620,7 @@ fn render_skill_menu(
                         ),
                         Span::styled(label, label_style),
                     ]),
-                    SKILL_BASE + i as u16,
+                    SKILL.id(i),
                 );
             } else {
                 cl.push(Line::from(Span::styled(
@@ -1270,7 +1703,7 @@ fn render_quest_board(
                     ),
                     Span::styled(q.description(), label_style),
                 ]),
-                QUEST_ACCEPT_BASE + i as u16,
+                QUEST_ACCEPT.id(i),
             );
             cl.push(Line::from(Span::styled(
                 format!("    報酬: {}G / {}EXP", q.reward_gold, q.reward_exp),
@@ -1433,9 +1866,9 @@ fn render_skill_choice(
     cl.render(f, area, block, &mut cs, false, 0);
 }
 
-// ── Game Clear ──────────────────────────────────────────────
+// ── Abyss Modifier (curse/boon forced pick) ─────────────────
 
-fn render_game_clear(
+fn render_abyss_modifier_choice(
     state: &RpgState,
     f: &mut Frame,
     area: Rect,
@@ -1443,40 +1876,122 @@ fn render_game_clear(
 ) {
     let borders = borders_for(area.width);
     let mut cl = ClickableList::new();
+    let (depth, choices) = match &state.abyss {
+        Some(run) => (run.depth, run.pending_choice.as_slice()),
+        None => (0, [].as_slice()),
+    };
+
     cl.push(Line::from(""));
     cl.push(Line::from(Span::styled(
-        " \u{2605}\u{2605}\u{2605} DUNGEON CLEAR \u{2605}\u{2605}\u{2605}",
-        Style::default().fg(Color::Yellow).add_modifier(Modifier::BOLD),
-    )));
-    cl.push(Line::from(""));
-    cl.push(Line::from(Span::styled(
-        " 魔王を倒し、ダンジョンを制覇した！",
-        Style::default().fg(Color::White),
-    )));
-    cl.push(Line::from(""));
-    cl.push(Line::from(Span::styled(
-        format!(
-            " レベル: {}  クリア: {}回  所持金: {}G",
-            state.level, state.total_clears, state.gold
-        ),
-        Style::default().fg(Color::Yellow),
+        format!(" 深淵{}層 — 呪いか恩恵を1つ選ぶ", depth),
+        Style::default().fg(Color::Magenta).add_modifier(Modifier::BOLD),
     )));
     cl.push(Line::from(""));
     cl.push(Line::from(Span::styled(
-        " 冒険をありがとう！",
-        Style::default().fg(Color::Cyan).add_modifier(Modifier::BOLD),
+        " ※ 選んだものはこの挑戦が終わるまで続く",
+        Style::default().fg(Color::DarkGray),
     )));
     cl.push(Line::from(""));
-    push_choice(&mut cl, 0, "メニューに戻る");
+
+    for (i, m) in choices.iter().enumerate() {
+        let info = abyss_modifier_info(*m);
+        let (tag, color) = if info.is_curse { ("呪", Color::Red) } else { ("恵", Color::Green) };
+        let selected = state.cursor == i;
+        cl.push_clickable(
+            Line::from(vec![
+                Span::styled(
+                    format!(" {}[{}] ", if selected { "▶" } else { " " }, i + 1),
+                    Style::default().fg(Color::Yellow).add_modifier(Modifier::BOLD),
+                ),
+                Span::styled(format!("{} ", tag), Style::default().fg(color)),
+                Span::styled(
+                    info.name,
+                    Style::default().fg(Color::White).add_modifier(Modifier::BOLD),
+                ),
+            ]),
+            ABYSS_PICK.id(i),
+        );
+        cl.push(Line::from(Span::styled(
+            format!("       {}", info.description),
+            Style::default().fg(Color::Gray),
+        )));
+        cl.push(Line::from(""));
+    }
 
     let block = Block::default()
         .borders(borders)
-        .border_style(Style::default().fg(Color::Yellow))
+        .border_style(Style::default().fg(Color::Magenta))
         .title(Span::styled(
-            " \u{2605} DUNGEON CLEAR \u{2605} ",
-            Style::default().fg(Color::Yellow).add_modifier(Modifier::BOLD),
+            " 深淵の囁き ",
+            Style::default().fg(Color::Magenta).add_modifier(Modifier::BOLD),
         ));
 
     let mut cs = click_state.borrow_mut();
     cl.render(f, area, block, &mut cs, false, 0);
 }
+
+// ── Game Clear ──────────────────────────────────────────────
+
+fn render_game_clear(
+    state: &RpgState,
+    f: &mut Frame,
+    area: Rect,
+    click_state: &Rc<RefCell<ClickState>>,
+) {
+    let mut body = vec![
+        Line::from(""),
+        Line::from(Span::styled(
+            " 魔王を倒し、ダンジョンを制覇した！",
+            Style::default().fg(Color::White),
+        )),
+        Line::from(""),
+        Line::from(Span::styled(
+            format!(
+                " レベル: {}  クリア: {}回  所持金: {}G",
+                state.level, state.total_clears, state.gold
+            ),
+            Style::default().fg(Color::Yellow),
+        )),
+        Line::from(""),
+        Line::from(Span::styled(
+            " 冒険をありがとう！",
+            Style::default().fg(Color::Cyan).add_modifier(Modifier::BOLD),
+        )),
+        Line::from(""),
+        Line::from(Span::styled(
+            format!(
+                " 魔王の先に深淵が口を開けている… (1層ごとに敵 +{}%)",
+                abyss_scale_pct(1) - 100
+            ),
+            Style::default().fg(Color::Magenta),
+        )),
+    ];
+    if state.abyss_records.is_empty() {
+        body.push(Line::from(Span::styled(
+            " 深淵ランキング: まだ記録なし",
+            Style::default().fg(Color::DarkGray),
+        )));
+    } else {
+        body.push(Line::from(Span::styled(
+            " 深淵ランキング:",
+            Style::default().fg(Color::Magenta).add_modifier(Modifier::BOLD),
+        )));
+        for (rank, depth) in state.abyss_records.iter().enumerate() {
+            body.push(Line::from(Span::styled(
+                format!("   {}位  {}層", rank + 1, depth),
+                Style::default().fg(Color::Gray),
+            )));
+        }
+    }
+
+    let mut cs = click_state.borrow_mut();
+    Modal::new(Span::styled(
+        " \u{2605}\u{2605}\u{2605} DUNGEON CLEAR \u{2605}\u{2605}\u{2605} ",
+        Style::default().fg(Color::Yellow).add_modifier(Modifier::BOLD),
+    ))
+    .body(body)
+    .button("1. メニューに戻る", CHOICE.id(0))
+    .button("2. 深淵に挑む (エンドレス)", CHOICE.id(1))
+    .selected(state.cursor)
+    .render(f, area, &mut cs);
+}
@@ -0,0 +1,238 @@
+//! Dungeon Dive のメタ情報 (周回をまたぐフラグ) の永続化。
+//!
+//! ゲーム本体の進行はセーブしない (毎回村から始まる) が、
+//! 「村の導入を一度済ませた」ことだけは覚えておき、2 周目以降の
+//! 起動時にスキップ確認を出す。深淵 (エンドレス) の到達層ランキングと
+//! 最後に選んだ難易度、モンスター図鑑もここに残す。アイアンマンで力尽きると導入済み
+//! フラグを消す ([`wipe_intro_seen`])。
+//!
+//! キーは [`crate::profile::scoped_key`] でプロファイルごとに分ける。
+//! localStorage は WASM ビルドでのみ触る。native (cargo test) では
+//! 読み込みは常に `false`、書き込みは no-op。
+
+use super::state::{BestiaryEntry, Difficulty, ALL_DIFFICULTIES, ALL_ENEMY_KINDS};
+
+/// localStorage のキー。値は "1" のみ。
+#[cfg(target_arch = "wasm32")]
+const META_KEY: &str = "dungeon_dive_meta";
+
+/// 深淵ランキングのキー。値は到達層のカンマ区切り (降順)。
+#[cfg(target_arch = "wasm32")]
+const ABYSS_KEY: &str = "dungeon_dive_abyss";
+
+/// 難易度のキー。値は `ALL_DIFFICULTIES` の添字。
+#[cfg(target_arch = "wasm32")]
+const DIFFICULTY_KEY: &str = "dungeon_dive_difficulty";
+
+/// 図鑑のキー。値は [`format_bestiary`] の形式。
+#[cfg(target_arch = "wasm32")]
+const BESTIARY_KEY: &str = "dungeon_dive_bestiary";
+
+#[cfg(target_arch = "wasm32")]
+fn get_storage() -> Option<crate::storage::Store> {
+    crate::storage::get()
+}
+
+/// 村の導入を済ませたことがあるか。
+pub fn load_intro_seen() -> bool {
+    #[cfg(target_arch = "wasm32")]
+    {
+        get_storage()
+            .and_then(|s| s.get_item(&crate::profile::scoped_key(META_KEY)).ok().flatten())
+            .is_some()
+    }
+    #[cfg(not(target_arch = "wasm32"))]
+    false
+}
+
+/// 村の導入を済ませたことを記録する。失敗しても次回スキップが
+/// 出ないだけなので握りつぶす。
+pub fn store_intro_seen() {
+    #[cfg(target_arch = "wasm32")]
+    if let Some(storage) = get_storage() {
+        let _ = storage.set_item(&crate::profile::scoped_key(META_KEY), "1");
+    }
+}
+
+/// アイアンマンで力尽きたとき、導入済みフラグを消して次回は村の導入から
+/// 始めさせる。深淵ランキングと難易度は残す。
+pub fn wipe_intro_seen() {
+    #[cfg(target_arch = "wasm32")]
+    if let Some(storage) = get_storage() {
+        let _ = storage.remove_item(&crate::profile::scoped_key(META_KEY));
+    }
+}
+
+/// 難易度の保存値を読む。不明な値は ふつう。
+pub fn parse_difficulty(raw: &str) -> Difficulty {
+    raw.trim()
+        .parse::<usize>()
+        .ok()
+        .and_then(|i| ALL_DIFFICULTIES.get(i).copied())
+        .unwrap_or_default()
+}
+
+/// 最後に選んだ難易度を読み込む。未保存なら ふつう。
+pub fn load_difficulty() -> Difficulty {
+    #[cfg(target_arch = "wasm32")]
+    {
+        get_storage()
+            .and_then(|s| s.get_item(&crate::profile::scoped_key(DIFFICULTY_KEY)).ok().flatten())
+            .map(|raw| parse_difficulty(&raw))
+            .unwrap_or_default()
+    }
+    #[cfg(not(target_arch = "wasm32"))]
+    Difficulty::default()
+}
+
+/// 難易度を保存する。失敗しても次回 ふつう に戻るだけなので握りつぶす。
+pub fn store_difficulty(difficulty: Difficulty) {
+    #[cfg(target_arch = "wasm32")]
+    if let Some(storage) = get_storage() {
+        let idx = ALL_DIFFICULTIES.iter().position(|&d| d == difficulty).unwrap_or(0);
+        let _ = storage.set_item(&crate::profile::scoped_key(DIFFICULTY_KEY), &idx.to_string());
+    }
+    #[cfg(not(target_arch = "wasm32"))]
+    let _ = difficulty;
+}
+
+/// 深淵ランキングの保存値を読む。壊れた要素は捨て、降順・最大件数に揃える。
+pub fn parse_abyss_records(raw: &str) -> Vec<u32> {
+    let mut records: Vec<u32> = raw
+        .split(',')
+        .filter_map(|v| v.trim().parse().ok())
+        .filter(|&d| d > 0)
+        .collect();
+    records.sort_unstable_by(|a, b| b.cmp(a));
+    records.truncate(super::state::ABYSS_LEADERBOARD_LEN);
+    records
+}
+
+/// 深淵ランキングを読み込む。未保存なら空。
+pub fn load_abyss_records() -> Vec<u32> {
+    #[cfg(target_arch = "wasm32")]
+    {
+        get_storage()
+            .and_then(|s| s.get_item(&crate::profile::scoped_key(ABYSS_KEY)).ok().flatten())
+            .map(|raw| parse_abyss_records(&raw))
+            .unwrap_or_default()
+    }
+    #[cfg(not(target_arch = "wasm32"))]
+    Vec::new()
+}
+
+/// 深淵ランキングを保存する。失敗しても記録が残らないだけなので握りつぶす。
+pub fn store_abyss_records(records: &[u32]) {
+    #[cfg(target_arch = "wasm32")]
+    if let Some(storage) = get_storage() {
+        let raw: Vec<String> = records.iter().map(|d| d.to_string()).collect();
+        let _ = storage.set_item(&crate::profile::scoped_key(ABYSS_KEY), &raw.join(","));
+    }
+    #[cfg(not(target_arch = "wasm32"))]
+    let _ = records;
+}
+
+/// 図鑑を保存値にする。1 種につき「敵の添字:遭遇:撃破:ドロップ」を
+/// カンマで並べる。ドロップは bit0 = `drop`、bit1 = `material`。
+pub fn format_bestiary(entries: &[BestiaryEntry]) -> String {
+    let raw: Vec<String> = entries
+        .iter()
+        .filter_map(|e| {
+            let idx = ALL_ENEMY_KINDS.iter().position(|&k| k == e.kind)?;
+            let drops = (e.drop_seen as u8) | ((e.material_seen as u8) << 1);
+            Some(format!("{}:{}:{}:{}", idx, e.encountered, e.killed, drops))
+        })
+        .collect();
+    raw.join(",")
+}
+
+/// 図鑑の保存値を読む。壊れた要素と重複した敵は捨てる。
+pub fn parse_bestiary(raw: &str) -> Vec<BestiaryEntry> {
+    let mut entries: Vec<BestiaryEntry> = Vec::new();
+    for item in raw.split(',') {
+        let Some(fields) = item.split(':').map(|v| v.trim().parse::<u32>().ok()).collect::<Option<Vec<_>>>() else {
+            continue;
+        };
+        let [idx, encountered, killed, drops] = fields[..] else {
+            continue;
+        };
+        let Some(&kind) = ALL_ENEMY_KINDS.get(idx as usize) else {
+            continue;
+        };
+        if entries.iter().any(|e| e.kind == kind) {
+            continue;
+        }
+        entries.push(BestiaryEntry {
+            kind,
+            encountered,
+            killed,
+            drop_seen: drops & 1 != 0,
+            material_seen: drops & 2 != 0,
+        });
+    }
+    entries
+}
+
+/// 図鑑を読み込む。未保存なら空。
+pub fn load_bestiary() -> Vec<BestiaryEntry> {
+    #[cfg(target_arch = "wasm32")]
+    {
+        get_storage()
+            .and_then(|s| s.get_item(&crate::profile::scoped_key(BESTIARY_KEY)).ok().flatten())
+            .map(|raw| parse_bestiary(&raw))
+            .unwrap_or_default()
+    }
+    #[cfg(not(target_arch = "wasm32"))]
+    Vec::new()
+}
+
+/// 図鑑を保存する。失敗しても次回の起動で少し巻き戻るだけなので握りつぶす。
+pub fn store_bestiary(entries: &[BestiaryEntry]) {
+    #[cfg(target_arch = "wasm32")]
+    if let Some(storage) = get_storage() {
+        let _ = storage.set_item(&crate::profile::scoped_key(BESTIARY_KEY), &format_bestiary(entries));
+    }
+    #[cfg(not(target_arch = "wasm32"))]
+    let _ = entries;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn abyss_records_parse_sorts_and_drops_garbage() {
+        assert_eq!(parse_abyss_records("3,12,x,0,7"), vec![12, 7, 3]);
+        assert_eq!(parse_abyss_records("1,2,3,4,5,6,7"), vec![7, 6, 5, 4, 3]);
+        assert!(parse_abyss_records("").is_empty());
+    }
+
+    #[test]
+    fn bestiary_roundtrips_and_drops_garbage() {
+        use crate::games::rpg::state::EnemyKind;
+        let mut slime = BestiaryEntry::new(EnemyKind::Slime);
+        slime.encountered = 4;
+        slime.killed = 3;
+        slime.material_seen = true;
+        let mut warden = BestiaryEntry::new(EnemyKind::AbyssWarden);
+        warden.encountered = 1;
+        warden.drop_seen = true;
+        let entries = vec![slime, warden];
+        let raw = format_bestiary(&entries);
+        assert_eq!(raw, "0:4:3:2,11:1:0:1");
+        assert_eq!(parse_bestiary(&raw), entries);
+
+        let parsed = parse_bestiary("0:1:1:0,x,99:1:1:0,1:2,0:9:9:3");
+        assert_eq!(parsed.len(), 1);
+        assert_eq!(parsed[0].encountered, 1);
+        assert!(parse_bestiary("").is_empty());
+    }
+
+    #[test]
+    fn difficulty_parse_falls_back_to_normal() {
+        assert_eq!(parse_difficulty("2"), Difficulty::Ironman);
+        assert_eq!(parse_difficulty(" 1 "), Difficulty::Casual);
+        assert_eq!(parse_difficulty("9"), Difficulty::Normal);
+        assert_eq!(parse_difficulty("x"), Difficulty::Normal);
+    }
+}
@@ -37,7 +37,8 @@
 
 use super::logic;
 use super::state::{
-    enemy_info, EnemyKind, Facing, ItemCategory, ItemKind, Overlay, RpgState, Scene,
+    enemy_info, EnemyKind, Facing, ItemCategory, ItemKind, Overlay, RpgState, Scene, LIGHT_DIM,
+    STAMINA_MAX,
 };
 
 // ── Policy ─────────────────────────────────────────────────
@@ -64,6 +65,8 @@ pub enum Action {
     CloseOverlay,
     /// Pick one of the two pending skill choices (0 = left, 1 = right).
     ConfirmSkillChoice(usize),
+    /// Pick one of the pending abyss curses/boons.
+    ConfirmAbyssModifier(usize),
     Noop,
 }
 
@@ -158,8 +161,18 @@ impl AutoExplorerPolicy {
                 }
                 Action::CloseOverlay
             }
-            Overlay::Status => Action::CloseOverlay,
+            Overlay::Status
+            | Overlay::Crafting
+            | Overlay::DungeonSelect
+            | Overlay::RunLog
+            | Overlay::Bestiary => {
+                Action::CloseOverlay
+            }
             Overlay::Shop => {
+                // 旅の商人は割高なので見るだけで立ち去る
+                if state.merchant.is_some() {
+                    return Action::CloseOverlay;
+                }
                 // 必要なものを順番に買う
                 let bread = state.inventory.iter().filter(|i| i.kind == ItemKind::Bread).map(|i| i.count).sum::<u32>();
                 let herb = state.inventory.iter().filter(|i| i.kind == ItemKind::Herb).map(|i| i.count).sum::<u32>();
@@ -169,6 +182,9 @@ impl AutoExplorerPolicy {
                 if herb < 5 && state.gold >= 20 {
                     return Action::BuyItem(ItemKind::Herb);
                 }
+                if state.gold >= 25 && state.item_count(ItemKind::Torch) < 2 {
+                    return Action::BuyItem(ItemKind::Torch);
+                }
                 if state.gold >= 50 && state.inventory.iter().filter(|i| i.kind == ItemKind::MagicWater).map(|i| i.count).sum::<u32>() < 3 {
                     return Action::BuyItem(ItemKind::MagicWater);
                 }
@@ -211,6 +227,7 @@ impl AutoExplorerPolicy {
                 // `state.pending_skill_choice` to differentiate builds.
                 Action::ConfirmSkillChoice(0)
             }
+            Overlay::AbyssModifier => Action::ConfirmAbyssModifier(0),
         }
     }
 
@@ -292,6 +309,11 @@ impl AutoExplorerPolicy {
             }
         }
 
+        // 明かりが弱い: 松明を灯す
+        if state.light < LIGHT_DIM && state.inventory.iter().any(|i| i.kind == ItemKind::Torch) {
+            return Action::UseItemByKind(ItemKind::Torch);
+        }
+
         // 隣接敵がいたら殴る
         if let Some(idx) = adjacent_enemy_idx(state) {
             let m = &map.monsters[idx];
@@ -388,6 +410,12 @@ impl AutoExplorerPolicy {
                         return Action::EventChoice(i);
                     }
                 }
+                EA::RestAtCamp => {
+                    if state.stamina * 2 < STAMINA_MAX {
+                        return Action::EventChoice(i);
+                    }
+                }
+                EA::ScavengeCamp => return Action::EventChoice(i),
                 _ => {}
             }
         }
@@ -731,16 +759,19 @@ impl Simulator {
                 }
             }
             Action::BuyItem(kind) => {
-                let shop = super::state::shop_items(self.state.max_floor_reached);
-                if let Some(idx) = shop.iter().position(|(k, _)| *k == kind) {
+                let shop = self.state.shop_offers();
+                if let Some(idx) = shop.iter().position(|o| o.kind == kind) {
                     logic::buy_item(&mut self.state, idx);
                 }
             }
             Action::ConfirmSkillChoice(i) => {
                 logic::confirm_skill_choice(&mut self.state, i);
             }
+            Action::ConfirmAbyssModifier(i) => {
+                logic::confirm_abyss_modifier(&mut self.state, i);
+            }
             Action::Retreat => { logic::retreat_to_town(&mut self.state); }
-            Action::CloseOverlay => { self.state.overlay = None; }
+            Action::CloseOverlay => { self.state.close_overlay(); }
             Action::Noop => {}
         }
     }
@@ -3,6 +3,8 @@
 //! Design: roguelike grid-based dungeon crawler with inline combat,
 //! satiety, random affixes, quests, prayer, and pets.
 
+use std::cell::Cell;
+
 // ── Elements ──────────────────────────────────────────────────
 
 #[derive(Clone, Copy, Debug, PartialEq)]
@@ -35,6 +37,10 @@ pub enum EnemyKind {
     Demon,
     Dragon,
     DemonLord,
+    /// 迷いの森の主 (森の最下層のボス)。
+    ForestLord,
+    /// 深淵の番人 (深淵 `ABYSS_WARDEN_INTERVAL` 層ごとのボス)。
+    AbyssWarden,
 }
 
 /// Rare prefix that mutates a normal enemy into an "elite" variant.
@@ -148,6 +154,8 @@ pub struct EnemyInfo {
     pub exp: u32,
     pub gold: u32,
     pub drop: Option<(ItemKind, u32)>, // (item, chance_pct 0-100)
+    /// 工房用の素材ドロップ (item, chance_pct)。`drop` とは別に判定する。
+    pub material: Option<(ItemKind, u32)>,
     pub weakness: Option<Element>,
     pub can_charge: bool,
     /// Whether this monster can be tamed by feeding.
@@ -161,56 +169,112 @@ pub fn enemy_info(kind: EnemyKind) -> EnemyInfo {
         EnemyKind::Slime => EnemyInfo {
             name: "スライム", glyph: 's', max_hp: 10, atk: 3, def: 1, exp: 5, gold: 8,
             drop: Some((ItemKind::Herb, 45)),
+            material: Some((ItemKind::WildHerb, 40)),
             weakness: Some(Element::Fire), can_charge: false, tameable: true,
         },
         EnemyKind::Rat => EnemyInfo {
             name: "大ネズミ", glyph: 'r', max_hp: 9, atk: 5, def: 0, exp: 4, gold: 6,
             drop: Some((ItemKind::Herb, 30)),
+            material: Some((ItemKind::BeastFang, 35)),
             weakness: None, can_charge: false, tameable: true,
         },
         EnemyKind::Goblin => EnemyInfo {
             name: "ゴブリン", glyph: 'g', max_hp: 26, atk: 9, def: 3, exp: 15, gold: 20,
             drop: Some((ItemKind::MagicWater, 35)),
+            material: Some((ItemKind::BeastFang, 30)),
             weakness: Some(Element::Fire), can_charge: false, tameable: true,
         },
         EnemyKind::Bat => EnemyInfo {
             name: "コウモリ", glyph: 'b', max_hp: 18, atk: 9, def: 2, exp: 10, gold: 12,
             drop: None,
+            material: Some((ItemKind::BeastFang, 40)),
             weakness: Some(Element::Thunder), can_charge: false, tameable: false,
         },
         EnemyKind::Skeleton => EnemyInfo {
             name: "スケルトン", glyph: 'k', max_hp: 45, atk: 14, def: 8, exp: 30, gold: 35,
             drop: Some((ItemKind::StrengthPotion, 20)),
+            material: Some((ItemKind::IronOre, 35)),
             weakness: Some(Element::Fire), can_charge: false, tameable: false,
         },
         EnemyKind::Golem => EnemyInfo {
             name: "ゴーレム", glyph: 'G', max_hp: 60, atk: 16, def: 14, exp: 40, gold: 50,
             drop: Some((ItemKind::MagicWater, 30)),
+            material: Some((ItemKind::IronOre, 50)),
             weakness: Some(Element::Thunder), can_charge: true, tameable: false,
         },
         EnemyKind::DarkKnight => EnemyInfo {
             name: "暗黒騎士", glyph: 'K', max_hp: 75, atk: 20, def: 15, exp: 55, gold: 70,
             drop: Some((ItemKind::StrengthPotion, 35)),
+            material: Some((ItemKind::IronOre, 40)),
             weakness: Some(Element::Thunder), can_charge: true, tameable: false,
         },
         EnemyKind::Demon => EnemyInfo {
             name: "デーモン", glyph: 'D', max_hp: 85, atk: 22, def: 12, exp: 65, gold: 80,
             drop: Some((ItemKind::MagicWater, 40)),
+            material: Some((ItemKind::BeastFang, 40)),
             weakness: Some(Element::Ice), can_charge: false, tameable: false,
         },
         EnemyKind::Dragon => EnemyInfo {
             name: "ドラゴン", glyph: 'R', max_hp: 120, atk: 28, def: 18, exp: 100, gold: 150,
             drop: Some((ItemKind::Herb, 50)),
+            material: Some((ItemKind::BeastFang, 60)),
             weakness: Some(Element::Ice), can_charge: true, tameable: false,
         },
         EnemyKind::DemonLord => EnemyInfo {
             name: "魔王", glyph: 'L', max_hp: 200, atk: 32, def: 20, exp: 300, gold: 500,
             drop: None,
+            material: None,
+            weakness: None, can_charge: true, tameable: false,
+        },
+        EnemyKind::ForestLord => EnemyInfo {
+            name: "森の主", glyph: 'T', max_hp: 70, atk: 11, def: 5, exp: 60, gold: 100,
+            drop: Some((ItemKind::StrengthPotion, 100)),
+            material: Some((ItemKind::WildHerb, 100)),
+            weakness: Some(Element::Fire), can_charge: true, tameable: false,
+        },
+        EnemyKind::AbyssWarden => EnemyInfo {
+            name: "深淵の番人", glyph: 'W', max_hp: 260, atk: 34, def: 22, exp: 350, gold: 600,
+            drop: None,
+            material: Some((ItemKind::BeastFang, 100)),
             weakness: None, can_charge: true, tameable: false,
         },
     }
 }
 
+/// 図鑑の並び順。保存値は添字なので、新しい敵は末尾に足すこと。
+pub const ALL_ENEMY_KINDS: [EnemyKind; 12] = [
+    EnemyKind::Slime,
+    EnemyKind::Rat,
+    EnemyKind::Goblin,
+    EnemyKind::Bat,
+    EnemyKind::Skeleton,
+    EnemyKind::Golem,
+    EnemyKind::DarkKnight,
+    EnemyKind::Demon,
+    EnemyKind::Dragon,
+    EnemyKind::DemonLord,
+    EnemyKind::ForestLord,
+    EnemyKind::AbyssWarden,
+];
+
+/// モンスター図鑑の 1 行。遭遇 (敵が気づいた) で作られ、周回をまたいで
+/// 残す (save.rs)。ドロップは `EnemyInfo` の `drop` / `material` を
+/// 実際に拾ったかどうかだけ覚える。
+#[derive(Clone, Debug, PartialEq)]
+pub struct BestiaryEntry {
+    pub kind: EnemyKind,
+    pub encountered: u32,
+    pub killed: u32,
+    pub drop_seen: bool,
+    pub material_seen: bool,
+}
+
+impl BestiaryEntry {
+    pub fn new(kind: EnemyKind) -> Self {
+        Self { kind, encountered: 0, killed: 0, drop_seen: false, material_seen: false }
+    }
+}
+
 /// Enemies that can appear on a given floor.
 pub fn floor_enemies(floor: u32) -> &'static [EnemyKind] {
     match floor {
@@ -251,6 +315,12 @@ pub enum ItemKind {
     PetTreat,
     /// 帰還の巻物 — 使用するとダンジョンから町へ即座に戻る (Town Portal 系)
     ReturnScroll,
+    /// 松明 — 明かりを補充する
+    Torch,
+    // Materials (戦闘・イベントで拾い、村の工房で加工する)
+    WildHerb,
+    IronOre,
+    BeastFang,
 }
 
 #[derive(Clone, Copy, Debug, PartialEq)]
@@ -259,6 +329,8 @@ pub enum ItemCategory {
     Weapon,
     Armor,
     Food,
+    /// 素材。そのままでは使えず、工房のレシピで消費する。
+    Material,
 }
 
 pub struct ItemInfo {
@@ -339,6 +411,22 @@ pub fn item_info(kind: ItemKind) -> ItemInfo {
             name: "帰還の巻物", description: "ダンジョンから町へ即座に戻る",
             category: ItemCategory::Consumable, buy_price: 150, value: 0,
         },
+        ItemKind::Torch => ItemInfo {
+            name: "松明", description: "明かり+120",
+            category: ItemCategory::Consumable, buy_price: 25, value: 120,
+        },
+        ItemKind::WildHerb => ItemInfo {
+            name: "野草", description: "素材: 薬の調合に使う",
+            category: ItemCategory::Material, buy_price: 0, value: 0,
+        },
+        ItemKind::IronOre => ItemInfo {
+            name: "鉄鉱石", description: "素材: 装備の鍛え直しに使う",
+            category: ItemCategory::Material, buy_price: 0, value: 0,
+        },
+        ItemKind::BeastFang => ItemInfo {
+            name: "魔獣の牙", description: "素材: 薬や装備の補強に使う",
+            category: ItemCategory::Material, buy_price: 0, value: 0,
+        },
     }
 }
 
@@ -353,6 +441,7 @@ pub fn shop_items(max_floor: u32) -> Vec<(ItemKind, u32)> {
         (ItemKind::TravelClothes, 1),
         (ItemKind::PetTreat, 99),
         (ItemKind::ReturnScroll, 99),
+        (ItemKind::Torch, 99),
     ];
     if max_floor >= 2 {
         items.push((ItemKind::Jerky, 99));
@@ -372,6 +461,105 @@ pub fn shop_items(max_floor: u32) -> Vec<(ItemKind, u32)> {
     items
 }
 
+/// 店に並ぶ品 1 つ。村の店は [`shop_items`] から作り、ダンジョンの旅の商人は
+/// 出会うたびに品ぞろえを作り直す (`events::merchant_stock`)。
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct ShopOffer {
+    pub kind: ItemKind,
+    pub price: u32,
+    /// 今回の出会いで買える残り数。`None` は無制限 (村の店)。
+    pub left: Option<u32>,
+}
+
+// ── Crafting (村の工房) ───────────────────────────────────────
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Recipe {
+    HerbBrew,
+    ManaBrew,
+    StrengthBrew,
+    ReforgeWeapon,
+    ReforgeArmor,
+}
+
+/// レシピの出来上がり。装備の鍛え直しは装備中の品を 1 段上に置き換える。
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum CraftOutput {
+    Item(ItemKind, u32),
+    UpgradeWeapon,
+    UpgradeArmor,
+}
+
+pub struct RecipeInfo {
+    pub name: &'static str,
+    pub description: &'static str,
+    pub inputs: &'static [(ItemKind, u32)],
+    pub output: CraftOutput,
+    /// この階層まで到達すると工房に並ぶ。
+    pub min_floor: u32,
+}
+
+pub const ALL_RECIPES: [Recipe; 5] = [
+    Recipe::HerbBrew,
+    Recipe::ManaBrew,
+    Recipe::ReforgeArmor,
+    Recipe::ReforgeWeapon,
+    Recipe::StrengthBrew,
+];
+
+pub fn recipe_info(recipe: Recipe) -> RecipeInfo {
+    match recipe {
+        Recipe::HerbBrew => RecipeInfo {
+            name: "薬草の調合", description: "薬草x2",
+            inputs: &[(ItemKind::WildHerb, 2)],
+            output: CraftOutput::Item(ItemKind::Herb, 2), min_floor: 0,
+        },
+        Recipe::ManaBrew => RecipeInfo {
+            name: "魔法の水の調合", description: "魔法の水x1",
+            inputs: &[(ItemKind::WildHerb, 2), (ItemKind::BeastFang, 1)],
+            output: CraftOutput::Item(ItemKind::MagicWater, 1), min_floor: 2,
+        },
+        Recipe::ReforgeArmor => RecipeInfo {
+            name: "防具の鍛え直し", description: "装備中の防具を1段上に",
+            inputs: &[(ItemKind::IronOre, 4)],
+            output: CraftOutput::UpgradeArmor, min_floor: 3,
+        },
+        Recipe::ReforgeWeapon => RecipeInfo {
+            name: "武器の鍛え直し", description: "装備中の武器を1段上に",
+            inputs: &[(ItemKind::IronOre, 3), (ItemKind::BeastFang, 2)],
+            output: CraftOutput::UpgradeWeapon, min_floor: 3,
+        },
+        Recipe::StrengthBrew => RecipeInfo {
+            name: "力の薬の調合", description: "力の薬x1",
+            inputs: &[(ItemKind::BeastFang, 3), (ItemKind::WildHerb, 1)],
+            output: CraftOutput::Item(ItemKind::StrengthPotion, 1), min_floor: 5,
+        },
+    }
+}
+
+/// Recipes on offer at the workshop, gated by max floor reached like
+/// [`shop_items`].
+pub fn known_recipes(max_floor: u32) -> Vec<Recipe> {
+    ALL_RECIPES
+        .iter()
+        .copied()
+        .filter(|&r| recipe_info(r).min_floor <= max_floor)
+        .collect()
+}
+
+/// 鍛え直し先 (同じ系統の 1 段上の装備)。最上位や素材は `None`。
+pub fn equipment_upgrade(kind: ItemKind) -> Option<ItemKind> {
+    match kind {
+        ItemKind::WoodenSword => Some(ItemKind::IronSword),
+        ItemKind::IronSword => Some(ItemKind::SteelSword),
+        ItemKind::SteelSword => Some(ItemKind::HolySword),
+        ItemKind::TravelClothes => Some(ItemKind::LeatherArmor),
+        ItemKind::LeatherArmor => Some(ItemKind::ChainMail),
+        ItemKind::ChainMail => Some(ItemKind::KnightArmor),
+        _ => None,
+    }
+}
+
 // ── Affixes (random magical equipment prefixes) ──────────────
 
 #[derive(Clone, Copy, Debug, PartialEq)]
@@ -572,6 +760,242 @@ pub fn level_stats(level: u32) -> LevelStats {
 pub const MAX_LEVEL: u32 = 10;
 pub const MAX_FLOOR: u32 = 10;
 
+// ── Endless Abyss (post-clear) ────────────────────────────────
+
+/// 深淵で 1 層潜るごとに敵 HP / ATK と報酬が増える割合 (%)。
+pub const ABYSS_SCALE_STEP_PCT: u32 = 12;
+/// この層数ごとに呪い/恩恵の 3 択が来る。
+pub const ABYSS_MODIFIER_INTERVAL: u32 = 3;
+/// 深淵の到達記録を何件まで残すか。
+pub const ABYSS_LEADERBOARD_LEN: usize = 5;
+
+/// 深淵の層ごとに選ぶ呪い/恩恵。1 回の挑戦中ずっと効き続ける。
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum AbyssModifier {
+    /// 恩恵: 敵を倒すたびに最大HPの 1/10 回復。
+    Bloodlust,
+    /// 恩恵: 獲得ゴールド +50%。
+    Fortune,
+    /// 恩恵: 受けるダメージ 25% 軽減。
+    Bulwark,
+    /// 呪い: 敵 ATK +25%。
+    Frenzy,
+    /// 呪い: 満腹度が毎ターン減る (通常は 2 ターンに 1)。
+    Famine,
+    /// 呪い: 以降の層の敵 HP +30%。
+    Horde,
+}
+
+pub const ALL_ABYSS_MODIFIERS: [AbyssModifier; 6] = [
+    AbyssModifier::Bloodlust,
+    AbyssModifier::Fortune,
+    AbyssModifier::Bulwark,
+    AbyssModifier::Frenzy,
+    AbyssModifier::Famine,
+    AbyssModifier::Horde,
+];
+
+pub struct AbyssModifierInfo {
+    pub name: &'static str,
+    pub description: &'static str,
+    pub is_curse: bool,
+}
+
+pub fn abyss_modifier_info(m: AbyssModifier) -> AbyssModifierInfo {
+    match m {
+        AbyssModifier::Bloodlust => AbyssModifierInfo {
+            name: "血の渇き", description: "敵を倒すたびに最大HPの1/10回復", is_curse: false,
+        },
+        AbyssModifier::Fortune => AbyssModifierInfo {
+            name: "黄金の加護", description: "獲得ゴールド +50%", is_curse: false,
+        },
+        AbyssModifier::Bulwark => AbyssModifierInfo {
+            name: "岩肌", description: "受けるダメージ 25%軽減", is_curse: false,
+        },
+        AbyssModifier::Frenzy => AbyssModifierInfo {
+            name: "狂化の呪い", description: "敵の攻撃力 +25%", is_curse: true,
+        },
+        AbyssModifier::Famine => AbyssModifierInfo {
+            name: "飢饉の呪い", description: "満腹度が2倍の速さで減る", is_curse: true,
+        },
+        AbyssModifier::Horde => AbyssModifierInfo {
+            name: "群れの呪い", description: "以降の層の敵HP +30%", is_curse: true,
+        },
+    }
+}
+
+/// 深淵の層 `depth` (1 始まり) での敵の強さ・報酬の倍率 (%)。
+pub fn abyss_scale_pct(depth: u32) -> u32 {
+    100 + depth * ABYSS_SCALE_STEP_PCT
+}
+
+/// 魔王撃破後に挑めるエンドレスの深淵。`depth` 層目は B(MAX_FLOOR+depth)F。
+#[derive(Clone, Debug, Default)]
+pub struct AbyssRun {
+    /// 現在の層 (1 始まり)。
+    pub depth: u32,
+    /// この挑戦で選んだ呪い/恩恵 (選択順)。
+    pub modifiers: Vec<AbyssModifier>,
+    /// 3 択の候補。`Overlay::AbyssModifier` が開いている間だけ空でない。
+    pub pending_choice: Vec<AbyssModifier>,
+}
+
+// ── Difficulty ────────────────────────────────────────────────
+
+/// 難易度。村の受付嬢との初回会話で選び、死亡時の扱いが変わる。
+/// 最後に選んだものは save.rs が覚えていて、次の起動時の初期値になる。
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub enum Difficulty {
+    /// 死んでも所持金を失わない。
+    Casual,
+    /// 死ぬと道中の稼ぎ + 持ち込み金の 1/5 を失う。
+    #[default]
+    Normal,
+    /// 死ぬと周回の進行がすべて消え、村の導入からやり直し。
+    Ironman,
+}
+
+/// 選択肢の並び順 (ふつうを先頭に)。セーブにはこの添字を書く。
+pub const ALL_DIFFICULTIES: [Difficulty; 3] =
+    [Difficulty::Normal, Difficulty::Casual, Difficulty::Ironman];
+
+pub struct DifficultyInfo {
+    pub name: &'static str,
+    pub description: &'static str,
+}
+
+pub fn difficulty_info(d: Difficulty) -> DifficultyInfo {
+    match d {
+        Difficulty::Casual => DifficultyInfo {
+            name: "カジュアル", description: "死んでも所持金はそのまま",
+        },
+        Difficulty::Normal => DifficultyInfo {
+            name: "ふつう", description: "死ぬと所持金の一部を失う",
+        },
+        Difficulty::Ironman => DifficultyInfo {
+            name: "アイアンマン", description: "死ぬと冒険の記録がすべて消える",
+        },
+    }
+}
+
+// ── Dungeon Sites (村から選べる行き先) ─────────────────────────
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum DungeonSite {
+    /// 迷いの森: 最初から行ける 3 階の浅いダンジョン。
+    Forest,
+    /// 古代遺跡: 本編 (B1F〜B10F、最下層に魔王)。森の踏破で解禁。
+    Ruins,
+    /// 深淵: 魔王撃破後のエンドレス (`AbyssRun`)。
+    Abyss,
+}
+
+pub const ALL_DUNGEON_SITES: [DungeonSite; 3] =
+    [DungeonSite::Forest, DungeonSite::Ruins, DungeonSite::Abyss];
+
+/// 迷いの森の階数。最下層の階段は主を倒すと村への出口になる。
+pub const FOREST_FLOORS: u32 = 3;
+/// 深淵でこの層ごとに番人が階段を塞ぐ。
+pub const ABYSS_WARDEN_INTERVAL: u32 = 5;
+
+pub struct DungeonSiteInfo {
+    pub name: &'static str,
+    pub description: &'static str,
+    /// 階数。`None` は底なし。
+    pub floors: Option<u32>,
+    pub boss: EnemyKind,
+    /// 解禁条件の説明 (選択画面でロック中に出す)。
+    pub unlock_hint: &'static str,
+    /// 敵を倒したとき、通常ドロップとは別に判定する追加の戦利品
+    /// (item, chance_pct)。古代遺跡は敵ごとのドロップのみ。
+    pub loot: &'static [(ItemKind, u32)],
+}
+
+pub fn site_info(site: DungeonSite) -> DungeonSiteInfo {
+    match site {
+        DungeonSite::Forest => DungeonSiteInfo {
+            name: "迷いの森",
+            description: "村はずれの森。薬草や食料がよく見つかる",
+            floors: Some(FOREST_FLOORS),
+            boss: EnemyKind::ForestLord,
+            unlock_hint: "最初から挑める",
+            loot: &[(ItemKind::WildHerb, 25), (ItemKind::Apple, 15)],
+        },
+        DungeonSite::Ruins => DungeonSiteInfo {
+            name: "古代遺跡",
+            description: "魔王が潜むという地下遺跡",
+            floors: Some(MAX_FLOOR),
+            boss: EnemyKind::DemonLord,
+            unlock_hint: "迷いの森の主を倒すと解禁",
+            loot: &[],
+        },
+        DungeonSite::Abyss => DungeonSiteInfo {
+            name: "深淵",
+            description: "潜るほど強くなる底なしの闇",
+            floors: None,
+            boss: EnemyKind::AbyssWarden,
+            unlock_hint: "魔王を倒すと解禁",
+            loot: &[(ItemKind::BeastFang, 20), (ItemKind::StrengthPotion, 8)],
+        },
+    }
+}
+
+/// Enemy table for `floor` of `site`. The ruins and the abyss share the
+/// original per-floor table ([`floor_enemies`]); the abyss reuses B9F's.
+pub fn site_enemies(site: DungeonSite, floor: u32) -> &'static [EnemyKind] {
+    match site {
+        DungeonSite::Forest => match floor {
+            1 => &[EnemyKind::Slime, EnemyKind::Rat],
+            2 => &[EnemyKind::Rat, EnemyKind::Bat, EnemyKind::Slime],
+            _ => &[EnemyKind::Bat, EnemyKind::Goblin],
+        },
+        DungeonSite::Ruins => floor_enemies(floor),
+        DungeonSite::Abyss => floor_enemies(MAX_FLOOR - 1),
+    }
+}
+
+// ── Run Log (冒険記録) ────────────────────────────────────────
+
+/// 冒険記録 1 行の種類。ビューアの色分けと共有テキストの集計に使う。
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum RunLogKind {
+    /// 階への到着・帰還・力尽きた
+    Floor,
+    /// ダンジョンイベントでの選択
+    Event,
+    /// 撃破
+    Kill,
+    /// 与えたダメージ (値は集計用)
+    Dealt(u32),
+    /// 受けたダメージ (値は集計用)
+    Taken(u32),
+}
+
+impl RunLogKind {
+    /// 共有テキストで行頭に付ける記号。
+    pub fn mark(self) -> &'static str {
+        match self {
+            RunLogKind::Floor => "▼",
+            RunLogKind::Event => "?",
+            RunLogKind::Kill => "★",
+            RunLogKind::Dealt(_) => "⚔",
+            RunLogKind::Taken(_) => "✚",
+        }
+    }
+}
+
+/// 冒険記録の 1 行。`floor` は記録した時点の階 (0 は村)。
+#[derive(Clone, Debug, PartialEq)]
+pub struct RunLogEntry {
+    pub turn: u64,
+    pub floor: u32,
+    pub kind: RunLogKind,
+    pub text: String,
+}
+
+/// 1 回の探索で残す冒険記録の上限。超えたら古い行から捨てる。
+pub const RUN_LOG_MAX: usize = 500;
+
 // ── Inventory Entry ───────────────────────────────────────────
 
 /// A stack of items in the inventory.
@@ -657,6 +1081,10 @@ pub enum CellType {
     Idol,
     Peddler,
     MonsterEgg,
+    /// 野営地。休む (スタミナ全快・襲われる恐れ) か漁る (物資) かを選ぶ。
+    Camp,
+    /// 旅の商人。割高だが品数限りの店 (Shop overlay) を開く。
+    Merchant,
     // ── Overworld (village) tiles ──
     /// 村の出口 → ダンジョン B1F へ降りる
     DungeonEntrance,
@@ -802,6 +1230,44 @@ impl DungeonMap {
     }
 }
 
+/// 2D マップの描画距離 (一辺のタイル数の上限)。狭いほど描く量が減って軽い。
+/// UI 専用で保存しない。
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub enum ViewDistance {
+    Near,
+    Normal,
+    #[default]
+    Far,
+}
+
+impl ViewDistance {
+    /// 一辺に描くタイル数の上限 (奇数、プレイヤーが中心)。
+    pub fn max_tiles(self) -> usize {
+        match self {
+            ViewDistance::Near => 11,
+            ViewDistance::Normal => 15,
+            ViewDistance::Far => 21,
+        }
+    }
+
+    pub fn name(self) -> &'static str {
+        match self {
+            ViewDistance::Near => "近",
+            ViewDistance::Normal => "中",
+            ViewDistance::Far => "遠",
+        }
+    }
+
+    /// 遠 → 近 → 中 → 遠 の順に切り替える。
+    pub fn next(self) -> Self {
+        match self {
+            ViewDistance::Near => ViewDistance::Normal,
+            ViewDistance::Normal => ViewDistance::Far,
+            ViewDistance::Far => ViewDistance::Near,
+        }
+    }
+}
+
 #[derive(Clone, Copy, Debug, PartialEq)]
 pub enum FloorTheme {
     /// 村の地上マップ（floor_num = 0）
@@ -842,8 +1308,12 @@ pub enum EventAction {
     ReturnToTown,
     Continue,
     // ── Overworld actions ──
-    /// 村のダンジョン入口から B1F へ降りる
+    /// 村のダンジョン入口から選択中のダンジョン (`RpgState::site`) の B1F へ降りる
     EnterDungeon,
+    /// 魔王撃破後、村のダンジョン入口から深淵 (エンドレス) へ降りる
+    EnterAbyss,
+    /// ダンジョン入口 — 行き先選択 (DungeonSelect overlay) を開く
+    OpenDungeonSelect,
     /// 武器屋 — Shop overlay を開く
     OpenShop,
     /// 掲示板 — QuestBoard overlay を開く
@@ -852,12 +1322,19 @@ pub enum EventAction {
     RestAtInn,
     /// 祭壇 — PrayMenu overlay を開く
     OpenShrineOverlay,
+    /// 武具屋の工房 — Crafting overlay を開く
+    OpenCrafting,
+    /// 受付 — 前回の冒険記録 (RunLog overlay) を開く
+    OpenRunLog,
     /// 受付嬢に話しかける (初回は薬草+パン+50G)
     TalkReception,
     /// 武具屋の親父に話しかける (初回は木の剣+旅人の服)
     TalkBlacksmith,
     /// 広場の村人に話しかける (フレーバーのみ)
     TalkVillager,
+    /// 2 周目以降の起動時: 受付嬢・武具屋の初回配布をまとめて受け取り、
+    /// そのままダンジョン入口の確認へ進む。
+    FastStart,
     // ── Issue #90: new event actions ──
     /// Help up the fallen adventurer — chance the body was a mimic.
     ReviveAdventurer,
@@ -883,10 +1360,18 @@ pub enum EventAction {
     PeddlerBuyMagicWater,
     /// Buy bread from the peddler.
     PeddlerBuyBread,
+    /// 受付嬢の初回会話で難易度を選ぶ (初期物資の配布も兼ねる)。
+    ChooseDifficulty(Difficulty),
     /// Take the monster egg — chance of new pet, else hatches hostile.
     TakeEgg,
     /// Crush the egg — yolk feeds you a little.
     BreakEgg,
+    /// 野営地で休む — スタミナ全快 + HP 回復。満腹度を使い、襲われることがある。
+    RestAtCamp,
+    /// 野営地を漁る — 物資とスタミナ少し。罠や先客に当たることがある。
+    ScavengeCamp,
+    /// 旅の商人の品ぞろえを見る (Shop overlay を開く)。
+    BrowseMerchant,
 }
 
 // ── Quests (Elona-style request board) ────────────────────────
@@ -980,15 +1465,25 @@ pub enum Overlay {
     /// Forced level-up skill pick: choose one of two to learn.
     /// Cannot be dismissed without picking — defines the run's build.
     SkillChoice,
+    /// Endless abyss: pick one of three curses/boons. Forced like SkillChoice.
+    AbyssModifier,
+    /// ダンジョン入口から開く行き先選択 (森 / 遺跡 / 深淵)。
+    DungeonSelect,
+    /// 工房 (武具屋の奥): 素材を消費してレシピを作る。
+    Crafting,
+    /// 前回の探索の冒険記録ビューア (受付で開く)。
+    RunLog,
+    /// モンスター図鑑 (メニューの 4 つ目のタブ)。
+    Bestiary,
 }
 
 impl Overlay {
     /// Whether this overlay belongs to the unified menu tab set
-    /// (Inventory / SkillMenu / Status), tabbed via the dungeon B button.
+    /// (Inventory / SkillMenu / Status / Bestiary), tabbed via the dungeon B button.
     pub fn is_menu_tab(self) -> bool {
         matches!(
             self,
-            Overlay::Inventory | Overlay::SkillMenu | Overlay::Status
+            Overlay::Inventory | Overlay::SkillMenu | Overlay::Status | Overlay::Bestiary
         )
     }
 }
@@ -1060,16 +1555,27 @@ pub struct RpgState {
     /// ダメージを与えると登録され、村に戻っても・新しい run でも
     /// 保持される（`max_floor_reached` と同じ寿命）。
     pub known_weaknesses: Vec<EnemyKind>,
+    /// モンスター図鑑 (遭遇順)。起動時に save.rs から読み、変化したら書き戻す。
+    pub bestiary: Vec<BestiaryEntry>,
 
     // Scene system
     pub scene: Scene,
     pub overlay: Option<Overlay>,
+    /// 長いオーバーレイ (持ち物 / ステータス) のスクロール位置。
+    /// 上限は render の `ScrollableTab` がクランプする。開閉で 0 に戻す。
+    pub overlay_scroll: Cell<u16>,
     pub scene_text: Vec<String>,
 
     pub active_event: Option<DungeonEvent>,
 
     // Log (shown at bottom)
     pub log: Vec<String>,
+    /// 進行中の探索の冒険記録。B1F (か深淵) から入り直すと空に戻る。
+    pub run_log: Vec<RunLogEntry>,
+    /// 直前に終わった探索の冒険記録。受付の RunLog overlay で見返す。
+    pub last_run_log: Vec<RunLogEntry>,
+    /// RunLog overlay でのコピー結果 (`None` はまだ押していない)。
+    pub run_log_copied: Option<bool>,
 
     // Game state
     pub game_cleared: bool,
@@ -1090,6 +1596,18 @@ pub struct RpgState {
     pub satiety: u32,
     pub satiety_max: u32,
 
+    /// 明かり: 0..=LIGHT_MAX。ダンジョンで 1 歩ごとに減り、run 開始時に満タン。
+    /// 0 (暗闇) だとマップが見えず、闇からの奇襲が起きる。
+    pub light: u32,
+
+    /// スタミナ: 0..=STAMINA_MAX。ダンジョンで歩く・殴るたびに減り、
+    /// run 開始時に満タン。野営地で回復する。0 だと通常攻撃が半減する。
+    pub stamina: u32,
+
+    /// 開いている旅の商人の品ぞろえ。`Some` の間、Shop overlay は村の店ではなく
+    /// こちらを並べる。overlay を開け閉めすると消える (出会い 1 回限り)。
+    pub merchant: Option<Vec<ShopOffer>>,
+
     /// Faith (信仰度): grows on prayer / floor clears. Affects pray outcomes.
     pub faith: u32,
     /// Whether the player has prayed in the current dungeon run.
@@ -1108,6 +1626,10 @@ pub struct RpgState {
     /// Counter that increments on each player action (turn-based).
     pub turn_count: u64,
 
+    /// 無傷連勝: 被弾せずに倒した敵の連続数。敵の攻撃が当たると 0 に戻る。
+    /// 溜め攻撃を空振りさせると +1 (見切りボーナス)。ダンジョン run ごとにリセット。
+    pub no_damage_streak: u32,
+
     /// Cursor index for the currently visible menu/overlay/event popup.
     /// Arrow keys move it; A button confirms the cursor's choice.
     /// Reset to 0 on every scene/overlay/event transition so the cursor
@@ -1126,16 +1648,56 @@ pub struct RpgState {
     /// When set, the player has hit a level-up that gates a skill choice.
     /// The dungeon turn engine refuses to advance until they pick.
     pub pending_skill_choice: Option<(SkillKind, SkillKind)>,
+
+    /// 2D マップをドラッグで見回しているずらし量 (端末セル単位、横は 1 タイル = 2 セル)。
+    /// UI 専用で保存しない。タップやキー入力でプレイヤー中心に戻る。
+    pub map_pan: (i32, i32),
+    /// 2D マップの描画距離 ([v] / ステータスで切り替え)。
+    pub view_distance: ViewDistance,
+
+    /// 深淵 (魔王撃破後のエンドレス) に挑戦中なら Some。村に戻ると記録して None。
+    pub abyss: Option<AbyssRun>,
+    /// 深淵の到達層ランキング (降順、最大 `ABYSS_LEADERBOARD_LEN` 件)。
+    /// 周回をまたいで残す (save.rs)。
+    pub abyss_records: Vec<u32>,
+
+    /// 難易度。起動時に save.rs から読み、受付嬢の初回会話で選び直せる。
+    pub difficulty: Difficulty,
+    /// アイアンマンで力尽きた直後だけ true。RpgGame がメタセーブを
+    /// 消してから false に戻す。
+    pub save_wiped: bool,
+
+    /// 入口の「降りる」で向かうダンジョン (森か遺跡)。深淵は `abyss` で表す。
+    pub site: DungeonSite,
+    /// 主を倒したダンジョン。遺跡は `game_cleared` で表すのでここには入れない。
+    pub cleared_sites: Vec<DungeonSite>,
 }
 
 pub const SATIETY_MAX_DEFAULT: u32 = 1000;
 
+/// 明かりの上限 (run 開始時の値)。
+pub const LIGHT_MAX: u32 = 300;
+/// これを下回ると「火が弱い」扱い (警告・奇襲率が少し上がる)。
+pub const LIGHT_DIM: u32 = 60;
+
+/// スタミナの上限 (run 開始時の値)。
+pub const STAMINA_MAX: u32 = 200;
+/// これを下回ると「疲れてきた」扱い (HUD が黄色になる)。
+pub const STAMINA_LOW: u32 = 40;
+
+/// 無傷連勝 1 段あたりの Gold/EXP ボーナス (%)。
+pub const STREAK_BONUS_STEP_PCT: u32 = 10;
+/// 無傷連勝ボーナスの上限 (%)。10 連勝で ×2 に達する。
+pub const STREAK_BONUS_MAX_PCT: u32 = 100;
+
 impl RpgState {
     /// Open an overlay and reset the cursor so it points at the first item
     /// of the new menu (not a stale index from the previous one).
     pub fn open_overlay(&mut self, overlay: Overlay) {
         self.overlay = Some(overlay);
         self.cursor = 0;
+        self.overlay_scroll.set(0);
+        self.merchant = None;
     }
 
     /// Close the active overlay and reset the cursor for whatever scene
@@ -1143,6 +1705,19 @@ impl RpgState {
     pub fn close_overlay(&mut self) {
         self.overlay = None;
         self.cursor = 0;
+        self.overlay_scroll.set(0);
+        self.merchant = None;
+    }
+
+    /// Shop overlay に並べる品。旅の商人に会っていればその品、なければ村の店。
+    pub fn shop_offers(&self) -> Vec<ShopOffer> {
+        match &self.merchant {
+            Some(stock) => stock.clone(),
+            None => shop_items(self.max_floor_reached)
+                .into_iter()
+                .map(|(kind, _)| ShopOffer { kind, price: item_info(kind).buy_price, left: None })
+                .collect(),
+        }
     }
 
     pub fn new() -> Self {
@@ -1165,11 +1740,16 @@ impl RpgState {
             max_floor_reached: 0,
             total_clears: 0,
             known_weaknesses: Vec::new(),
+            bestiary: Vec::new(),
             scene: Scene::Overworld,
             overlay: None,
+            overlay_scroll: Cell::new(0),
             scene_text: Vec::new(),
             active_event: None,
             log: Vec::new(),
+            run_log: Vec::new(),
+            last_run_log: Vec::new(),
+            run_log_copied: None,
             game_cleared: false,
             rng_seed: 42,
             run_gold_earned: 0,
@@ -1179,6 +1759,9 @@ impl RpgState {
             lore_found: Vec::new(),
             satiety: SATIETY_MAX_DEFAULT,
             satiety_max: SATIETY_MAX_DEFAULT,
+            light: LIGHT_MAX,
+            stamina: STAMINA_MAX,
+            merchant: None,
             faith: 0,
             prayed_this_run: false,
             active_quest: None,
@@ -1186,24 +1769,49 @@ impl RpgState {
             pet: None,
             buffs: PlayerBuffs::default(),
             turn_count: 0,
+            no_damage_streak: 0,
             cursor: 0,
             met_reception: false,
             met_blacksmith: false,
             learned_skills: vec![SkillKind::Fire],
             pending_skill_choice: None,
+            map_pan: (0, 0),
+            view_distance: ViewDistance::default(),
+            abyss: None,
+            abyss_records: Vec::new(),
+            difficulty: Difficulty::Normal,
+            save_wiped: false,
+            site: DungeonSite::Forest,
+            cleared_sites: Vec::new(),
         };
         // Load the village map so the player spawns on the overworld.
         s.dungeon = Some(super::overworld_map::generate_overworld());
         s
     }
 
+    /// ターン制で流量が少ないので、すべて読み上げ ([`crate::a11y`]) にも流す。
     pub fn add_log(&mut self, text: &str) {
+        crate::a11y::announce(text);
         self.log.push(text.to_string());
         if self.log.len() > 30 {
             self.log.remove(0);
         }
     }
 
+    /// 冒険記録に 1 行足す。階は今いるマップから取る。
+    pub fn record(&mut self, kind: RunLogKind, text: String) {
+        let floor = self
+            .dungeon
+            .as_ref()
+            .filter(|d| !d.is_overworld)
+            .map(|d| d.floor_num)
+            .unwrap_or(0);
+        self.run_log.push(RunLogEntry { turn: self.turn_count, floor, kind, text });
+        if self.run_log.len() > RUN_LOG_MAX {
+            self.run_log.remove(0);
+        }
+    }
+
     // ── 弱点図鑑 ──────────────────────────────────────────────
 
     /// この敵種の弱点情報が判明済みか。弱点を持たない敵も撃破すれば
@@ -1231,6 +1839,47 @@ impl RpgState {
         }
     }
 
+    // ── モンスター図鑑 ────────────────────────────────────────
+
+    pub fn bestiary_entry(&self, kind: EnemyKind) -> Option<&BestiaryEntry> {
+        self.bestiary.iter().find(|e| e.kind == kind)
+    }
+
+    fn bestiary_entry_mut(&mut self, kind: EnemyKind) -> &mut BestiaryEntry {
+        let i = match self.bestiary.iter().position(|e| e.kind == kind) {
+            Some(i) => i,
+            None => {
+                self.bestiary.push(BestiaryEntry::new(kind));
+                self.bestiary.len() - 1
+            }
+        };
+        &mut self.bestiary[i]
+    }
+
+    /// 敵が気づいた (戦闘が始まった) ことを数える。初遭遇なら true。
+    pub fn note_encounter(&mut self, kind: EnemyKind) -> bool {
+        let entry = self.bestiary_entry_mut(kind);
+        entry.encountered += 1;
+        entry.encountered == 1
+    }
+
+    /// 撃破を数える。初撃破なら true。
+    pub fn note_kill(&mut self, kind: EnemyKind) -> bool {
+        let entry = self.bestiary_entry_mut(kind);
+        entry.killed += 1;
+        entry.killed == 1
+    }
+
+    /// 敵種固有のドロップ (`material` なら素材枠) を拾ったことを記録する。
+    pub fn note_drop(&mut self, kind: EnemyKind, material: bool) {
+        let entry = self.bestiary_entry_mut(kind);
+        if material {
+            entry.material_seen = true;
+        } else {
+            entry.drop_seen = true;
+        }
+    }
+
     /// Equipped weapon entry.
     pub fn weapon(&self) -> Option<&InventoryItem> {
         self.weapon_idx.and_then(|i| self.inventory.get(i))
@@ -1265,6 +1914,47 @@ impl RpgState {
         def.saturating_add(bonus).saturating_sub(pen)
     }
 
+    /// Gold/EXP multiplier (percent) from the no-damage streak:
+    /// +10% per streak step, capped at ×2.
+    pub fn streak_bonus_pct(&self) -> u32 {
+        100 + (self.no_damage_streak * STREAK_BONUS_STEP_PCT).min(STREAK_BONUS_MAX_PCT)
+    }
+
+    /// 深淵の現在の層。深淵の外では 0。
+    pub fn abyss_depth(&self) -> u32 {
+        self.abyss.as_ref().map(|a| a.depth).unwrap_or(0)
+    }
+
+    /// 深淵の挑戦中にこの呪い/恩恵を選んでいるか。
+    pub fn has_abyss_modifier(&self, m: AbyssModifier) -> bool {
+        self.abyss.as_ref().is_some_and(|a| a.modifiers.contains(&m))
+    }
+
+    /// 敵 ATK の倍率 (%)。深淵の外では 100。
+    pub fn abyss_enemy_atk_pct(&self) -> u32 {
+        let depth = self.abyss_depth();
+        if depth == 0 {
+            return 100;
+        }
+        let frenzy = if self.has_abyss_modifier(AbyssModifier::Frenzy) { 25 } else { 0 };
+        abyss_scale_pct(depth) + frenzy
+    }
+
+    /// 新しく生成する層の敵 HP 倍率 (%)。深淵の外では 100。
+    pub fn abyss_enemy_hp_pct(&self) -> u32 {
+        let depth = self.abyss_depth();
+        if depth == 0 {
+            return 100;
+        }
+        let horde = if self.has_abyss_modifier(AbyssModifier::Horde) { 30 } else { 0 };
+        abyss_scale_pct(depth) + horde
+    }
+
+    /// 深淵の最高到達層 (記録なしなら 0)。
+    pub fn best_abyss_depth(&self) -> u32 {
+        self.abyss_records.first().copied().unwrap_or(0)
+    }
+
     /// Effective magic (includes affixes on equipped weapon/armor).
     pub fn total_mag(&self) -> u32 {
         let mut m = self.mag as i32;
@@ -1312,7 +2002,38 @@ impl RpgState {
             .unwrap_or(0)
     }
 
-    #[cfg(test)]
+    /// 今いる (村なら最後に選んだ) ダンジョン。深淵の挑戦中は `Abyss`。
+    pub fn current_site(&self) -> DungeonSite {
+        if self.abyss.is_some() { DungeonSite::Abyss } else { self.site }
+    }
+
+    pub fn site_cleared(&self, site: DungeonSite) -> bool {
+        match site {
+            DungeonSite::Ruins => self.game_cleared,
+            _ => self.cleared_sites.contains(&site),
+        }
+    }
+
+    /// 森 → 遺跡 → 深淵 の順に、一つ前を踏破すると解禁される。
+    pub fn site_unlocked(&self, site: DungeonSite) -> bool {
+        match site {
+            DungeonSite::Forest => true,
+            DungeonSite::Ruins => self.site_cleared(DungeonSite::Forest),
+            DungeonSite::Abyss => self.site_cleared(DungeonSite::Ruins),
+        }
+    }
+
+    /// ダンジョンで明かりが尽きているか。村では常に false。
+    pub fn is_dark(&self) -> bool {
+        self.light == 0 && self.dungeon.as_ref().is_some_and(|m| !m.is_overworld)
+    }
+
+    /// ダンジョンでスタミナが尽きているか。村では常に false。
+    pub fn is_exhausted(&self) -> bool {
+        self.stamina == 0 && self.dungeon.as_ref().is_some_and(|m| !m.is_overworld)
+    }
+
+    /// 付与なしの `kind` の所持数 (素材・消耗品の数え上げ用)。
     pub fn item_count(&self, kind: ItemKind) -> u32 {
         self.inventory
             .iter()
@@ -1409,6 +2130,32 @@ mod tests {
         assert!(q.is_complete());
     }
 
+    #[test]
+    fn dungeon_sites_unlock_in_order() {
+        let mut s = RpgState::new();
+        assert!(s.site_unlocked(DungeonSite::Forest));
+        assert!(!s.site_unlocked(DungeonSite::Ruins));
+        s.cleared_sites.push(DungeonSite::Forest);
+        assert!(s.site_unlocked(DungeonSite::Ruins));
+        assert!(!s.site_unlocked(DungeonSite::Abyss));
+        s.game_cleared = true;
+        assert!(s.site_unlocked(DungeonSite::Abyss));
+        for site in ALL_DUNGEON_SITES {
+            for floor in 1..=site_info(site).floors.unwrap_or(1) {
+                assert!(!site_enemies(site, floor).is_empty());
+            }
+        }
+    }
+
+    #[test]
+    fn recipes_unlock_with_depth() {
+        assert_eq!(known_recipes(0), vec![Recipe::HerbBrew]);
+        assert_eq!(known_recipes(3).len(), 4);
+        assert_eq!(known_recipes(MAX_FLOOR).len(), ALL_RECIPES.len());
+        assert_eq!(equipment_upgrade(ItemKind::SteelSword), Some(ItemKind::HolySword));
+        assert_eq!(equipment_upgrade(ItemKind::KnightArmor), None);
+    }
+
     #[test]
     fn item_count_excludes_affixed() {
         let mut s = RpgState::new();
@@ -8,13 +8,22 @@
 //! のみ。残りのモジュールも同居させているのは、追加の worker 化や統合
 //! テストから再利用できる余地を残すため。
 
+pub mod arcade;
+#[cfg(any(feature = "debug-console", debug_assertions, test))]
+pub mod debug_console;
 pub mod games;
-pub mod input;
-pub mod sound;
-pub mod time;
-pub mod widgets;
+pub mod playstats;
+pub mod routes;
+pub mod screens;
+pub mod session;
+
+// ゲームに依存しない共通モジュールは `engine` crate に移した
+// (docs/adr/0002-workspace-split.md の段 1)。`crate::input` などの既存パスを
+// 壊さないよう、ここで再エクスポートしておく。`Game` / `GameChoice` は
+// `games` が再エクスポートする。
+pub use engine::action::{BACK_TO_MENU, SHOW_SCORE};
+pub use engine::{
+    a11y, action, input, keybind, numfmt, profile, scores, sound, storage, theme, time, toast,
+    widgets, zoom,
+};
 
-/// 「メニューに戻る」共通アクション ID。
-/// 各ゲームの `Clickable::new(back, BACK_TO_MENU)` から参照されるため、
-/// lib のルートに置いてクレート全域から `crate::BACK_TO_MENU` で引けるようにする。
-pub const BACK_TO_MENU: u16 = 65535;
@@ -1,61 +1,32 @@
 use std::{cell::RefCell, io, rc::Rc};
 
-use cli_sim_game_escape::games::{self, create_game, AppState, GameChoice};
+#[cfg(any(feature = "debug-console", debug_assertions))]
+use cli_sim_game_escape::debug_console::DebugConsole;
+use cli_sim_game_escape::a11y;
+use cli_sim_game_escape::arcade;
 use cli_sim_game_escape::input::{
-    is_narrow_layout, pixel_x_to_col, pixel_y_to_row, ClickScope, ClickState, InputEvent,
+    key_from_dom, pixel_x_to_col, pixel_y_to_row, ClickScope, ClickState, InputEvent,
+    KeyRepeater, PAGE_DOWN_KEY, PAGE_UP_KEY,
 };
-use cli_sim_game_escape::sound;
-use cli_sim_game_escape::widgets::{Clickable, ClickableList};
-use cli_sim_game_escape::time::GameTime;
-use cli_sim_game_escape::BACK_TO_MENU;
+use cli_sim_game_escape::keybind;
+use cli_sim_game_escape::numfmt;
+use cli_sim_game_escape::profile;
+use cli_sim_game_escape::routes::{self, Route};
+use cli_sim_game_escape::screens::{AppState, MenuScreen, ProfilesScreen, Router, KEYBIND_CANCEL};
+use cli_sim_game_escape::storage;
+use cli_sim_game_escape::theme;
+use cli_sim_game_escape::toast::{self, Severity};
+use cli_sim_game_escape::time::{GameTime, TICKS_PER_SEC};
+use cli_sim_game_escape::zoom;
 
 use ratzilla::event::{KeyCode, MouseButton, MouseEventKind};
-use ratzilla::ratatui::layout::{Alignment, Constraint, Direction, Layout, Rect};
+use ratzilla::ratatui::layout::Rect;
 use ratzilla::ratatui::style::{Color, Modifier, Style};
-use ratzilla::ratatui::text::{Line, Span};
-use ratzilla::ratatui::widgets::{Block, Borders, Paragraph};
+use ratzilla::ratatui::text::Span;
+use ratzilla::ratatui::widgets::{Clear, Paragraph};
 use ratzilla::ratatui::Terminal;
 use ratzilla::{DomBackend, WebRenderer};
 
-// ── Menu action IDs ─────────────────────────────────────────────
-pub const MENU_SELECT_COOKIE: u16 = 1;
-pub const MENU_SELECT_FACTORY: u16 = 2;
-pub const MENU_SELECT_RPG: u16 = 3;
-pub const MENU_SELECT_ABYSS: u16 = 4;
-pub const MENU_SELECT_GODFIELD: u16 = 5;
-pub const MENU_SELECT_METROPOLIS: u16 = 6;
-pub const MENU_SELECT_SETTINGS: u16 = 7;
-pub const MENU_SCROLL_UP: u16 = 8;
-pub const MENU_SCROLL_DOWN: u16 = 9;
-
-/// Last valid index of the main menu cards (6 games + settings → 0..=6).
-const MENU_LAST_INDEX: u8 = 6;
-
-/// Cursor → menu action, used for the A button on the main menu.
-enum MenuPick {
-    Game(GameChoice),
-    Settings,
-}
-
-fn menu_pick_for(idx: u8) -> MenuPick {
-    match idx {
-        0 => MenuPick::Game(GameChoice::Cookie),
-        1 => MenuPick::Game(GameChoice::Factory),
-        2 => MenuPick::Game(GameChoice::Rpg),
-        3 => MenuPick::Game(GameChoice::Abyss),
-        4 => MenuPick::Game(GameChoice::Godfield),
-        5 => MenuPick::Game(GameChoice::Metropolis),
-        _ => MenuPick::Settings,
-    }
-}
-
-// ── Settings action IDs ─────────────────────────────────────────
-const SETTINGS_RESET_COOKIE: u16 = 10;
-const SETTINGS_RESET_ABYSS: u16 = 11;
-const SETTINGS_RESET_METROPOLIS: u16 = 12;
-const SETTINGS_CONFIRM_YES: u16 = 13;
-const SETTINGS_CONFIRM_NO: u16 = 14;
-
 /// Use `elementFromPoint` to find which grid cell was clicked.
 ///
 /// Ratzilla renders each terminal row as a `<pre>` child of `div#grid`.
@@ -156,21 +127,18 @@ fn now_ms() -> Option<f64> {
         .map(|p| p.now())
 }
 
-/// Process a tap/click at the given client coordinates.
-///
-/// `ClickState::try_consume_tap` drops compatibility mouse events that the
-/// browser fires for the same touch (timestamp-based dedup), so a single
-/// physical tap is dispatched once even if the render loop stutters between
-/// the two synthesized events.
-fn handle_tap(
-    client_x: f64,
-    client_y: f64,
-    app_state: &Rc<RefCell<AppState>>,
-    click_state: &Rc<RefCell<ClickState>>,
-) {
-    let mut cs = click_state.borrow_mut();
-    let (row, col) = match dom_element_to_cell(client_x, client_y, cs.terminal_cols) {
-        Some(r) => r,
+/// `location.search` (e.g. `"?debug"`), or `""` outside a browser.
+#[cfg(any(feature = "debug-console", debug_assertions))]
+fn location_search() -> String {
+    web_sys::window()
+        .and_then(|w| w.location().search().ok())
+        .unwrap_or_default()
+}
+
+/// Resolve client coordinates to a terminal cell `(row, col)`.
+fn client_to_cell(client_x: f64, client_y: f64, cs: &ClickState) -> Option<(u16, u16)> {
+    match dom_element_to_cell(client_x, client_y, cs.terminal_cols) {
+        Some(r) => Some(r),
         None => {
             // elementFromPoint missed the <pre> row.  Try the pixel-based
             // fallback so an overlay or zoom edge case doesn't leave the
@@ -179,213 +147,304 @@ fn handle_tap(
             web_sys::console::warn_1(
                 &"click missed <pre>; trying pixel fallback".into(),
             );
-            match pixel_fallback_to_cell(
-                client_x,
-                client_y,
-                cs.terminal_cols,
-                cs.terminal_rows,
-            ) {
-                Some(r) => r,
-                None => return,
-            }
+            pixel_fallback_to_cell(client_x, client_y, cs.terminal_cols, cs.terminal_rows)
         }
-    };
+    }
+}
 
-    // Skip dedup entirely when the high-resolution clock is unavailable;
-    // JS-side `e.preventDefault()` already suppresses the compatibility
-    // event, so the only loss is the second-line-of-defence guarantee.
-    // (Dropping every tap on the same cell because we'd otherwise compare
-    // `0.0 - 0.0 < 30ms` would be a far worse failure mode.)
-    if let Some(t) = now_ms() {
-        if !cs.try_consume_tap(col, row, t) {
-            return;
+/// Process a press (mousedown) at the given client coordinates.
+///
+/// The press is only recorded here; [`handle_release`] turns it into a
+/// `Click` / `DoubleTap`, and the draw loop turns a held press into a
+/// `LongPress`.  Without a high-resolution clock there is no way to time
+/// gestures, so the press is dispatched as a `Click` immediately instead.
+fn handle_press(
+    client_x: f64,
+    client_y: f64,
+    app_state: &Rc<RefCell<Router>>,
+    click_state: &Rc<RefCell<ClickState>>,
+) {
+    let mut cs = click_state.borrow_mut();
+    let Some((row, col)) = client_to_cell(client_x, client_y, &cs) else {
+        return;
+    };
+    match now_ms() {
+        Some(t) => cs.press(col, row, t),
+        None => {
+            if let Some(action_id) = cs.hit_test(col, row) {
+                let scope = cs.current_scope().cloned().unwrap_or(ClickScope::Menu);
+                drop(cs);
+                dispatch_event(&InputEvent::Click(scope, action_id), app_state);
+            }
         }
     }
+}
 
-    if let Some(action_id) = cs.hit_test(col, row) {
-        // Pair the action ID with the scope that registered the target so
-        // the dispatcher can verify the click is bound for the screen the
-        // user actually saw — protecting against late-arriving compatibility
-        // events crossing a screen transition.
-        let scope = cs
-            .current_scope()
-            .cloned()
-            .unwrap_or(ClickScope::Menu);
+/// Process a release (mouseup) at the given client coordinates.
+///
+/// `ClickState::try_consume_tap` drops compatibility mouse events that the
+/// browser fires for the same touch (timestamp-based dedup), so a single
+/// physical tap is dispatched once even if the render loop stutters between
+/// the two synthesized events.
+fn handle_release(
+    client_x: f64,
+    client_y: f64,
+    app_state: &Rc<RefCell<Router>>,
+    click_state: &Rc<RefCell<ClickState>>,
+) {
+    let mut cs = click_state.borrow_mut();
+    let Some(t) = now_ms() else {
+        // Already dispatched on press (see handle_press).
+        return;
+    };
+    let Some((row, col)) = client_to_cell(client_x, client_y, &cs) else {
+        cs.pointer_moved(None);
+        return;
+    };
+    if !cs.try_consume_tap(col, row, t) {
+        return;
+    }
+    // The event carries the scope that registered the target when the press
+    // went down, so the dispatcher can verify it is bound for the screen the
+    // user actually saw — protecting against late-arriving compatibility
+    // events crossing a screen transition.
+    if let Some(event) = cs.release(col, row, t) {
         drop(cs);
-        dispatch_event(&InputEvent::Click(scope, action_id), app_state);
+        dispatch_event(&event, app_state);
     }
 }
 
-/// Returns `true` if the click's scope matches the currently active screen.
-/// Stale clicks from a previous screen (rare but possible at screen
-/// transitions) are caught here in debug builds and silently dropped in
-/// release.
-fn click_scope_matches_state(scope: &ClickScope, state: &AppState) -> bool {
-    match (scope, state) {
-        (ClickScope::Menu, AppState::Menu { .. }) => true,
-        (ClickScope::Settings, AppState::Settings { .. }) => true,
-        (ClickScope::Game(c), AppState::Playing { game }) => *c == game.choice(),
-        _ => false,
+/// Pointer movement while a press is held: leaving the cell cancels it, or
+/// continues a drag stroke for presses that started in a drag area.
+fn handle_move(
+    client_x: f64,
+    client_y: f64,
+    app_state: &Rc<RefCell<Router>>,
+    click_state: &Rc<RefCell<ClickState>>,
+) {
+    let mut cs = click_state.borrow_mut();
+    if !cs.has_pending_press() {
+        return;
+    }
+    let cell = dom_element_to_cell(client_x, client_y, cs.terminal_cols).map(|(row, col)| (col, row));
+    if let Some(event) = cs.pointer_moved(cell) {
+        drop(cs);
+        dispatch_event(&event, app_state);
     }
 }
 
-/// Dispatch an input event to the current app state.
-fn dispatch_event(event: &InputEvent, app_state: &Rc<RefCell<AppState>>) {
-    let mut state = app_state.borrow_mut();
+/// Publish this frame's drag areas (cell coordinates) as `window.dragAreas`
+/// so the touch handler in index.html can forward finger movement that
+/// starts inside one as a drag instead of turning it into scroll keys.
+/// `updatedAt` lets the JS side ignore a stale value, the same way as
+/// `window.metropolisPanelRect`.
+fn export_drag_areas_to_js(areas: &[Rect]) {
+    use js_sys::{Array, Object, Reflect};
+    use web_sys::wasm_bindgen::JsValue;
+    let Some(win) = web_sys::window() else { return };
+    let rects = Array::new();
+    for r in areas {
+        let obj = Object::new();
+        let _ = Reflect::set(&obj, &"x".into(), &JsValue::from(r.x));
+        let _ = Reflect::set(&obj, &"y".into(), &JsValue::from(r.y));
+        let _ = Reflect::set(&obj, &"w".into(), &JsValue::from(r.width));
+        let _ = Reflect::set(&obj, &"h".into(), &JsValue::from(r.height));
+        rects.push(&obj);
+    }
+    let value = Object::new();
+    let _ = Reflect::set(&value, &"rects".into(), &rects);
+    let _ = Reflect::set(&value, &"updatedAt".into(), &JsValue::from(now_ms().unwrap_or(0.0)));
+    let _ = Reflect::set(&win, &"dragAreas".into(), &value);
+}
 
-    if let InputEvent::Click(scope, _) = event {
-        if !click_scope_matches_state(scope, &state) {
-            debug_assert!(
-                false,
-                "click scope {:?} doesn't match active state",
-                scope,
-            );
-            // In release: drop the stale click rather than misroute it.
-            return;
+/// Browser back/forward (or an edited URL) changes `location.hash`;
+/// follow it.  Our own writes from [`routes::sync`] land on the route
+/// we're already on and are ignored.
+fn install_hash_change_handler(app_state: &Rc<RefCell<Router>>) {
+    use web_sys::wasm_bindgen::{closure::Closure, JsCast, JsValue};
+    let Some(win) = web_sys::window() else { return };
+    let on_hash_change = Closure::<dyn FnMut(JsValue)>::new({
+        let app_state = app_state.clone();
+        move |_: JsValue| {
+            let route = routes::current().unwrap_or(Route::Menu);
+            let mut router = app_state.borrow_mut();
+            if Route::of(router.state()) != route {
+                router.go(route.into_state());
+            }
         }
-    }
+    });
+    win.set_onhashchange(Some(on_hash_change.as_ref().unchecked_ref()));
+    on_hash_change.forget();
+}
 
-    match &mut *state {
-        AppState::Menu { scroll, selected } => {
-            let direct = match event {
-                InputEvent::Key('1') | InputEvent::Click(_, MENU_SELECT_COOKIE) => {
-                    Some(MenuPick::Game(GameChoice::Cookie))
-                }
-                InputEvent::Key('2') | InputEvent::Click(_, MENU_SELECT_FACTORY) => {
-                    Some(MenuPick::Game(GameChoice::Factory))
-                }
-                InputEvent::Key('3') | InputEvent::Click(_, MENU_SELECT_RPG) => {
-                    Some(MenuPick::Game(GameChoice::Rpg))
-                }
-                InputEvent::Key('4') | InputEvent::Click(_, MENU_SELECT_ABYSS) => {
-                    Some(MenuPick::Game(GameChoice::Abyss))
-                }
-                InputEvent::Key('5') | InputEvent::Click(_, MENU_SELECT_GODFIELD) => {
-                    Some(MenuPick::Game(GameChoice::Godfield))
-                }
-                InputEvent::Key('6') | InputEvent::Click(_, MENU_SELECT_METROPOLIS) => {
-                    Some(MenuPick::Game(GameChoice::Metropolis))
-                }
-                InputEvent::Key('0') | InputEvent::Click(_, MENU_SELECT_SETTINGS) => {
-                    Some(MenuPick::Settings)
-                }
-                // A button (' ' / Enter via main.rs key map) confirms the
-                // currently highlighted card, so keyboard-only and tap users
-                // share the same selection model.
-                InputEvent::Key(' ') => Some(menu_pick_for(*selected)),
-                _ => None,
+/// ratzilla only forwards keydown, so keyup (and focus loss, after which the
+/// keyup never arrives) is hooked here to end a held-key repeat.
+fn install_key_release_handlers(key_repeater: &Rc<RefCell<KeyRepeater>>) {
+    use js_sys::Reflect;
+    use web_sys::wasm_bindgen::{closure::Closure, JsCast, JsValue};
+    let Some(win) = web_sys::window() else { return };
+
+    let on_key_up = Closure::<dyn FnMut(JsValue)>::new({
+        let key_repeater = key_repeater.clone();
+        move |e: JsValue| {
+            let key = Reflect::get(&e, &"key".into()).ok().and_then(|k| k.as_string());
+            // Same mapping as keydown: arrows are canonical, chars go
+            // through the key registry.
+            let c = match key.as_deref() {
+                Some(k) if k.starts_with("Arrow") => key_from_dom(k),
+                Some(k) => key_from_dom(k).and_then(keybind::resolve),
+                None => None,
             };
-            if let Some(pick) = direct {
-                sound::play(sound::SELECT);
-                match pick {
-                    MenuPick::Game(choice) => {
-                        let game = create_game(&choice);
-                        *state = AppState::Playing { game };
-                    }
-                    MenuPick::Settings => {
-                        *state = AppState::Settings { confirm_reset: None };
-                    }
-                }
-            } else {
-                match event {
-                    // Arrow up/k: move highlight up. Auto-scroll so the
-                    // selection always stays visible (keeps the UX usable
-                    // when the menu list is taller than the viewport).
-                    InputEvent::Key('k') | InputEvent::Click(_, MENU_SCROLL_UP) => {
-                        let before = *selected;
-                        *selected = selected.saturating_sub(1);
-                        // 3 lines per game card → keep ~one card above
-                        let target = (*selected as u16) * 3;
-                        if *scroll > target {
-                            *scroll = target;
-                        }
-                        if *selected != before {
-                            sound::play(sound::CLICK);
-                        }
-                    }
-                    InputEvent::Key('j') | InputEvent::Click(_, MENU_SCROLL_DOWN) => {
-                        let before = *selected;
-                        *selected = (*selected + 1).min(MENU_LAST_INDEX);
-                        // No upper-bound auto-scroll here — render_menu
-                        // re-clamps `scroll` against the actual viewport.
-                        *scroll = scroll.saturating_add(0);
-                        if *selected != before {
-                            sound::play(sound::CLICK);
-                        }
-                    }
-                    _ => {}
-                }
+            if let Some(c) = c {
+                key_repeater.borrow_mut().key_up(c);
             }
         }
-        AppState::Settings { confirm_reset } => {
-            if confirm_reset.is_some() {
-                // Confirmation dialog is active
-                match event {
-                    InputEvent::Key('y') | InputEvent::Click(_, SETTINGS_CONFIRM_YES) => {
-                        let game = confirm_reset.take().unwrap();
-                        perform_reset(&game);
-                        *state = AppState::Settings {
-                            confirm_reset: None,
-                        };
-                    }
-                    InputEvent::Key('n')
-                    | InputEvent::Key('q')
-                    | InputEvent::Click(_, SETTINGS_CONFIRM_NO) => {
-                        *confirm_reset = None;
-                    }
-                    _ => {}
-                }
-            } else {
-                match event {
-                    InputEvent::Key('1') | InputEvent::Click(_, SETTINGS_RESET_COOKIE) => {
-                        *confirm_reset = Some(GameChoice::Cookie);
-                    }
-                    InputEvent::Key('2') | InputEvent::Click(_, SETTINGS_RESET_ABYSS) => {
-                        *confirm_reset = Some(GameChoice::Abyss);
-                    }
-                    InputEvent::Key('3') | InputEvent::Click(_, SETTINGS_RESET_METROPOLIS) => {
-                        *confirm_reset = Some(GameChoice::Metropolis);
-                    }
-                    InputEvent::Key('q') | InputEvent::Click(_, BACK_TO_MENU) => {
-                        *state = AppState::Menu { scroll: 0, selected: 0 };
-                    }
-                    _ => {}
-                }
+    });
+    win.set_onkeyup(Some(on_key_up.as_ref().unchecked_ref()));
+
+    let on_blur = Closure::<dyn FnMut(JsValue)>::new({
+        let key_repeater = key_repeater.clone();
+        move |_: JsValue| key_repeater.borrow_mut().release()
+    });
+    win.set_onblur(Some(on_blur.as_ref().unchecked_ref()));
+
+    // Both live as long as the page, like ratzilla's own listeners.
+    on_key_up.forget();
+    on_blur.forget();
+}
+
+/// Pinch gestures are detected by the touch handler in index.html (touch
+/// events stay on the JS side, see the note there); it calls
+/// `window.__pinchZoom(steps)` with +1 for spreading fingers and -1 for
+/// pinching, and the zoom level is stepped and saved here.
+fn install_pinch_handler() {
+    use js_sys::Reflect;
+    use web_sys::wasm_bindgen::{closure::Closure, JsValue};
+    let Some(win) = web_sys::window() else { return };
+    let on_pinch = Closure::<dyn FnMut(JsValue)>::new(move |steps: JsValue| {
+        let Some(steps) = steps.as_f64() else { return };
+        if zoom::zoom_by(steps as i32) {
+            toast::push(Severity::Info, format!("文字の大きさ {}%", zoom::active()));
+        }
+    });
+    let _ = Reflect::set(&win, &"__pinchZoom".into(), on_pinch.as_ref());
+    // Lives as long as the page, like ratzilla's own listeners.
+    on_pinch.forget();
+}
+
+/// Autosave runs on an interval, so closing the tab (or the OS killing a
+/// backgrounded one) could lose up to a full interval of progress.  Save
+/// right away when the page unloads or the tab is hidden.
+fn install_suspend_handlers(app_state: &Rc<RefCell<Router>>) {
+    use web_sys::wasm_bindgen::{closure::Closure, JsCast, JsValue};
+    let Some(win) = web_sys::window() else { return };
+
+    let on_before_unload = Closure::<dyn FnMut(JsValue)>::new({
+        let app_state = app_state.clone();
+        move |_: JsValue| {
+            // Skip rather than panic if the router is somehow mid-borrow.
+            if let Ok(mut router) = app_state.try_borrow_mut() {
+                router.suspend();
             }
         }
-        AppState::Playing { game } => {
-            if matches!(event, InputEvent::Key('q') | InputEvent::Click(_, BACK_TO_MENU)) {
-                // Let the game handle back first (e.g., sub-screen → main screen).
-                // Only go to menu if the game didn't consume it.
-                if !game.handle_input(event) {
-                    *state = AppState::Menu { scroll: 0, selected: 0 };
+    });
+    win.set_onbeforeunload(Some(on_before_unload.as_ref().unchecked_ref()));
+
+    if let Some(doc) = win.document() {
+        let on_visibility_change = Closure::<dyn FnMut(JsValue)>::new({
+            let app_state = app_state.clone();
+            let doc = doc.clone();
+            move |_: JsValue| {
+                if !doc.hidden() {
+                    return;
+                }
+                if let Ok(mut router) = app_state.try_borrow_mut() {
+                    router.suspend();
                 }
-            } else {
-                game.handle_input(event);
             }
-        }
+        });
+        doc.set_onvisibilitychange(Some(on_visibility_change.as_ref().unchecked_ref()));
+        on_visibility_change.forget();
     }
+
+    // Live as long as the page, like ratzilla's own listeners.
+    on_before_unload.forget();
+}
+
+/// Dispatch an input event to the current screen.
+fn dispatch_event(event: &InputEvent, app_state: &Rc<RefCell<Router>>) {
+    let mut router = app_state.borrow_mut();
+    router.handle(event);
+    routes::sync(router.state());
 }
 
-/// Delete localStorage save data for the specified game.
-fn perform_reset(game: &GameChoice) {
-    #[cfg(target_arch = "wasm32")]
-    match game {
-        GameChoice::Cookie => games::cookie::save::delete_save(),
-        GameChoice::Abyss => games::abyss::save::delete_save(),
-        GameChoice::Metropolis => games::metropolis::save::delete_save(),
+/// デバッグコンソールへのキー入力。コンソールが受け取ったら `true` を返し、
+/// ゲームには流さない。プレイ中以外では常に閉じておく。
+#[cfg(any(feature = "debug-console", debug_assertions))]
+fn debug_console_key(
+    code: &KeyCode,
+    console: &RefCell<DebugConsole>,
+    app_state: &Rc<RefCell<Router>>,
+) -> bool {
+    let mut console = console.borrow_mut();
+    let mut router = app_state.borrow_mut();
+    let AppState::Playing(playing) = router.state_mut() else {
+        console.close();
+        return false;
+    };
+    if !console.open {
+        // 開くシーケンスの最後の 1 打はゲームに渡さない
+        return matches!(code, KeyCode::Char(c) if console.note_key(*c));
+    }
+    match code {
+        KeyCode::Esc => console.close(),
+        KeyCode::Enter => console.submit(playing.game.as_mut()),
+        KeyCode::Backspace => {
+            console.input.pop();
+        }
+        KeyCode::Char(c) => console.input.push(*c),
         _ => {}
     }
-    #[cfg(not(target_arch = "wasm32"))]
-    let _ = game;
+    true
 }
 
 fn main() -> io::Result<()> {
     console_error_panic_hook::set_once();
-
-    let app_state = Rc::new(RefCell::new(AppState::Menu { scroll: 0, selected: 0 }));
+    // Must run before anything reads a save (profiles first of all).
+    storage::init();
+
+    // Shared devices boot into the profile picker so each player lands
+    // on their own saves; single-profile setups go straight to the menu.
+    let profiles = profile::load();
+    keybind::load();
+    theme::load();
+    a11y::load();
+    numfmt::load();
+    arcade::load();
+    // Before the backend reads the window size: the zoom level decides
+    // how many columns the grid gets.
+    zoom::load();
+    install_pinch_handler();
+    // A deep link (`#cookie`) wins over both; it's what the player bookmarked.
+    let initial_state = if let Some(route) = routes::current() {
+        route.into_state()
+    } else if profiles.list.len() > 1 {
+        AppState::Profiles(ProfilesScreen::new(profiles))
+    } else {
+        AppState::Menu(MenuScreen::resuming())
+    };
+    let app_state = Rc::new(RefCell::new(Router::new(initial_state)));
+    install_hash_change_handler(&app_state);
+    install_suspend_handlers(&app_state);
     let click_state = Rc::new(RefCell::new(ClickState::new()));
-    let game_time = Rc::new(RefCell::new(GameTime::new(10)));
+    // Game logic runs at the active game's rate; the UI clock (toasts)
+    // stays at the default so its timers don't speed up with the game.
+    let game_time = Rc::new(RefCell::new(GameTime::new(TICKS_PER_SEC)));
+    let ui_time = Rc::new(RefCell::new(GameTime::new(TICKS_PER_SEC)));
+    let key_repeater = Rc::new(RefCell::new(KeyRepeater::new()));
+    install_key_release_handlers(&key_repeater);
+    #[cfg(any(feature = "debug-console", debug_assertions))]
+    let debug_console = Rc::new(RefCell::new(DebugConsole::from_query(&location_search())));
     let backend = DomBackend::new()?;
     let terminal = Terminal::new(backend)?;
 
@@ -394,31 +453,50 @@ fn main() -> io::Result<()> {
         let app_state = app_state.clone();
         let click_state = click_state.clone();
         move |mouse_event| {
-            if mouse_event.event != MouseEventKind::Pressed
-                || mouse_event.button != MouseButton::Left
-            {
-                return;
+            let (x, y) = (mouse_event.x as f64, mouse_event.y as f64);
+            match mouse_event.event {
+                MouseEventKind::Moved => handle_move(x, y, &app_state, &click_state),
+                MouseEventKind::Pressed if mouse_event.button == MouseButton::Left => {
+                    handle_press(x, y, &app_state, &click_state)
+                }
+                MouseEventKind::Released if mouse_event.button == MouseButton::Left => {
+                    handle_release(x, y, &app_state, &click_state)
+                }
+                _ => {}
             }
-            handle_tap(
-                mouse_event.x as f64,
-                mouse_event.y as f64,
-                &app_state,
-                &click_state,
-            );
         }
     });
 
     // Keyboard handler
     terminal.on_key_event({
         let app_state = app_state.clone();
+        let key_repeater = key_repeater.clone();
+        #[cfg(any(feature = "debug-console", debug_assertions))]
+        let debug_console = debug_console.clone();
         move |key_event| {
+            #[cfg(any(feature = "debug-console", debug_assertions))]
+            if debug_console_key(&key_event.code, &debug_console, &app_state) {
+                return;
+            }
+            // キー設定画面で割り当て待ちの間は、押されたキーをそのまま渡す
+            let listening = app_state.borrow().screen().captures_keys();
             let event = match key_event.code {
-                KeyCode::Char(c) => InputEvent::Key(c),
+                KeyCode::Char(c) if listening => InputEvent::Key(c),
+                KeyCode::Esc if listening => InputEvent::Click(ClickScope::Settings, KEYBIND_CANCEL),
+                _ if listening => return,
+                // Remappable keys go through the registry; arrows / Enter /
+                // Esc below always send the canonical keys.
+                KeyCode::Char(c) => match keybind::resolve(c) {
+                    Some(c) => InputEvent::Key(c),
+                    None => return,
+                },
                 KeyCode::Esc => InputEvent::Key('q'),
                 KeyCode::Left => InputEvent::Key('h'),
                 KeyCode::Right => InputEvent::Key('l'),
                 KeyCode::Up => InputEvent::Key('k'),
                 KeyCode::Down => InputEvent::Key('j'),
+                KeyCode::PageUp => InputEvent::Key(PAGE_UP_KEY),
+                KeyCode::PageDown => InputEvent::Key(PAGE_DOWN_KEY),
                 // Enter is a synonym for the A button — confirms whatever
                 // the cursor is currently highlighting (RPG menus, main
                 // menu game selection, etc.). ' ' is the canonical char
@@ -427,6 +505,14 @@ fn main() -> io::Result<()> {
                 KeyCode::Enter => InputEvent::Key(' '),
                 _ => return,
             };
+            // The browser's own auto-repeat of a held movement key is dropped;
+            // the draw loop repeats it at the game's pace instead.
+            if let (InputEvent::Key(c), Some(t)) = (&event, now_ms()) {
+                let repeat = app_state.borrow().screen().key_repeat();
+                if !key_repeater.borrow_mut().key_down(*c, t, repeat) {
+                    return;
+                }
+            }
             dispatch_event(&event, &app_state);
         }
     });
@@ -435,6 +521,9 @@ fn main() -> io::Result<()> {
     terminal.draw_web({
         let click_state = click_state.clone();
         let game_time = game_time.clone();
+        let ui_time = ui_time.clone();
+        #[cfg(any(feature = "debug-console", debug_assertions))]
+        let debug_console = debug_console.clone();
         move |f| {
             let size = f.area();
 
@@ -449,421 +538,93 @@ fn main() -> io::Result<()> {
             // Get current timestamp for game time.  Without a high-res clock
             // the game effectively pauses (delta_ticks stays 0), which is
             // acceptable for the rare headless / no-Performance-API case.
-            let delta_ticks = game_time.borrow_mut().update(now_ms().unwrap_or(0.0));
+            let now = now_ms();
+            let delta_ticks = {
+                let mut gt = game_time.borrow_mut();
+                gt.set_rate(app_state.borrow().screen().tick_rate());
+                gt.update(now.unwrap_or(0.0))
+            };
+            toast::tick(ui_time.borrow_mut().update(now.unwrap_or(0.0)));
+
+            // A held movement key repeats.  Leaving the game (or a game
+            // without repeat) drops the hold, since its keyup may never come.
+            if let Some(t) = now {
+                let repeat = {
+                    let mut kr = key_repeater.borrow_mut();
+                    if app_state.borrow().screen().key_repeat().is_none() {
+                        kr.release();
+                    }
+                    kr.poll(t)
+                };
+                if let Some(event) = repeat {
+                    dispatch_event(&event, &app_state);
+                }
+            }
+
+            // A press held long enough becomes a long-press while still down.
+            if let Some(t) = now {
+                let long_press = click_state.borrow_mut().poll_long_press(t);
+                if let Some(event) = long_press {
+                    dispatch_event(&event, &app_state);
+                }
+            }
+
+            let mut router = app_state.borrow_mut();
+
+            // Logic step: advance the game by the ticks accumulated since
+            // the last frame, independent of how often frames are drawn.
+            // The debug console pauses the game while it is open.
+            #[cfg(any(feature = "debug-console", debug_assertions))]
+            let paused = debug_console.borrow().open;
+            #[cfg(not(any(feature = "debug-console", debug_assertions)))]
+            let paused = false;
+            if delta_ticks > 0 && !paused {
+                router.tick(delta_ticks);
+            }
 
-            let mut state = app_state.borrow_mut();
             // Stamp the frame with the scope of click targets it'll register,
-            // so handle_tap can pair it with the action ID for dispatch-time
+            // so handle_press can pair it with the action ID for dispatch-time
             // validation.
-            click_state.borrow_mut().set_scope(match &*state {
-                AppState::Menu { .. } => ClickScope::Menu,
-                AppState::Settings { .. } => ClickScope::Settings,
-                AppState::Playing { game } => ClickScope::Game(game.choice()),
-            });
-            match &mut *state {
-                AppState::Menu { scroll, selected } => {
-                    render_menu(f, size, &click_state, scroll, *selected);
-                }
-                AppState::Settings { confirm_reset } => {
-                    render_settings(f, size, &click_state, confirm_reset.as_ref());
-                }
-                AppState::Playing { game } => {
-                    // Tick game logic
-                    if delta_ticks > 0 {
-                        game.tick(delta_ticks);
-                    }
+            click_state.borrow_mut().set_scope(router.screen().scope());
+            router.render(f, size, &click_state);
 
-                    game.render(f, size, &click_state);
-
-                    // Overlay back button in top-left corner.  Registered
-                    // last so it wins over any game-area target on overlap.
-                    let back_area = Rect::new(size.x, size.y, 6, 1);
-                    let back = Paragraph::new(Span::styled(
-                        " ◀戻る",
-                        Style::default().fg(Color::DarkGray),
-                    ));
-                    Clickable::new(back, BACK_TO_MENU).render(
-                        f,
-                        back_area,
-                        &mut click_state.borrow_mut(),
-                    );
-                }
+            #[cfg(any(feature = "debug-console", debug_assertions))]
+            if matches!(router.state(), AppState::Playing(_)) && debug_console.borrow().open {
+                debug_console.borrow().render(f, size);
+            }
+            if !storage::is_persistent() {
+                render_storage_warning(f, size);
             }
+            toast::render(f, size);
+            theme::apply(f.buffer_mut());
+            export_drag_areas_to_js(click_state.borrow().drag_areas());
         }
     });
 
     Ok(())
 }
 
-fn render_menu(
-    f: &mut ratzilla::ratatui::Frame,
-    area: Rect,
-    click_state: &Rc<RefCell<ClickState>>,
-    scroll: &mut u16,
-    selected: u8,
-) {
-    let is_narrow = is_narrow_layout(area.width);
-
-    let chunks = Layout::default()
-        .direction(Direction::Vertical)
-        .constraints([
-            Constraint::Length(3), // Title
-            Constraint::Min(8),   // Menu items
-            Constraint::Length(3), // Footer
-        ])
-        .split(area);
-
-    // Title
-    let title = if is_narrow {
-        "Game Select"
-    } else {
-        "Game Select - ゲームを選んでください"
-    };
-    let borders = if is_narrow {
-        Borders::TOP | Borders::BOTTOM
-    } else {
-        Borders::ALL
-    };
-    let title_widget = Paragraph::new(Line::from(Span::styled(
-        title,
+/// Persistent chip in the bottom-right corner while saves only live in
+/// memory ([`storage`]), so players know closing the tab loses progress.
+/// Drawn over every screen; it registers no click target.
+fn render_storage_warning(f: &mut ratzilla::ratatui::Frame, size: Rect) {
+    let chip = Span::styled(
+        " ⚠ 保存されません ",
         Style::default()
-            .fg(Color::Cyan)
+            .fg(Color::Black)
+            .bg(Color::Yellow)
             .add_modifier(Modifier::BOLD),
-    )))
-    .block(
-        Block::default()
-            .borders(borders)
-            .border_style(Style::default().fg(Color::Cyan)),
-    )
-    .alignment(Alignment::Center);
-    f.render_widget(title_widget, chunks[0]);
-
-    // Menu items — driven by a single source of truth (MENU_ENTRIES) so
-    // adding a new game is one entry edit. Each card occupies 3 visual
-    // rows: blank / title / description; sharing the action ID across
-    // title + desc lets the player tap either row.
-    // accent はゲームの「顔」となる固有色。タイトル文字に常時乗せることで、
-    // 一覧をスクロールした時にどのゲームか色で識別できる。
-    type Entry = (&'static str, &'static str, u16, char, Color);
-    const MENU_ENTRIES: &[Entry] = &[
-        ("Cookie Factory", "クッキーをクリックして増やす放置ゲーム", MENU_SELECT_COOKIE, '▶', Color::LightYellow),
-        ("Tiny Factory", "工場を作って生産ラインを最適化する放置ゲーム", MENU_SELECT_FACTORY, '▶', Color::Cyan),
-        ("Dungeon Dive", "ダンジョンを探索して帰還するローグライト風RPG", MENU_SELECT_RPG, '▶', Color::LightRed),
-        ("深淵潜行 (Abyss Idle)", "自動戦闘で深層を目指す放置型ローグダンジョン", MENU_SELECT_ABYSS, '▶', Color::LightBlue),
-        ("神の戦場 (God Field)", "4人で戦うターン制カードバトルロイヤル", MENU_SELECT_GODFIELD, '▶', Color::Red),
-        ("Idle Metropolis", "AIが街を建てるのを眺める放置シティビルダー", MENU_SELECT_METROPOLIS, '▶', Color::LightCyan),
-        ("設定", "セーブデータの管理", MENU_SELECT_SETTINGS, '⚙', Color::Gray),
-    ];
-
-    let mut cl = ClickableList::new();
-    for (i, (name, desc, action_id, default_marker, accent)) in MENU_ENTRIES.iter().enumerate() {
-        let is_selected = i as u8 == selected;
-        // Highlighted card: solid yellow ▶ marker + bold yellow title.
-        // Unselected: same shape but muted accent color, so the layout
-        // doesn't shift when the cursor moves and each game keeps its hue.
-        let marker = if is_selected { '▶' } else { *default_marker };
-        let marker_style = if is_selected {
-            Style::default().fg(Color::Yellow).add_modifier(Modifier::BOLD)
-        } else if *default_marker == '⚙' {
-            Style::default().fg(Color::Gray).add_modifier(Modifier::BOLD)
-        } else {
-            Style::default().fg(Color::DarkGray)
-        };
-        let title_style = if is_selected {
-            Style::default().fg(Color::Yellow).add_modifier(Modifier::BOLD)
-        } else if *default_marker == '⚙' {
-            Style::default().fg(Color::Gray)
-        } else {
-            Style::default().fg(*accent)
-        };
-        cl.push(Line::from(""));
-        cl.push_clickable(
-            Line::from(vec![
-                Span::styled(format!(" {} ", marker), marker_style),
-                Span::styled(*name, title_style),
-            ]),
-            *action_id,
-        );
-        cl.push_clickable(
-            Line::from(Span::styled(
-                format!("    {}", desc),
-                Style::default().fg(Color::DarkGray),
-            )),
-            *action_id,
-        );
-    }
-
-    let menu_block = Block::default()
-        .borders(borders)
-        .border_style(Style::default().fg(Color::Green))
-        .title(" Games ");
-
-    // Clamp scroll to content height. With wrap=false each logical line is
-    // exactly one visual row, so visible_rows is the inner height.
-    let inner = menu_block.inner(chunks[1]);
-    let total_lines = cl.len() as u16;
-    let visible_rows = inner.height;
-    let max_scroll = total_lines.saturating_sub(visible_rows);
-    if *scroll > max_scroll {
-        *scroll = max_scroll;
-    }
-
-    // Auto-scroll so the highlighted card stays visible. Each card spans
-    // 3 rows (blank / title / desc), with the title at row 3*selected + 1.
-    // We aim to keep the title row inside [scroll, scroll + visible_rows).
-    let card_top = (selected as u16) * 3;
-    let card_bottom = card_top + 3;
-    if card_top < *scroll {
-        *scroll = card_top;
-    } else if visible_rows > 0 && card_bottom > *scroll + visible_rows {
-        *scroll = card_bottom.saturating_sub(visible_rows);
-    }
-    if *scroll > max_scroll {
-        *scroll = max_scroll;
-    }
-    let can_scroll_up = *scroll > 0;
-    let can_scroll_down = *scroll < max_scroll;
-    let scroll_value = *scroll;
-
-    {
-        let mut cs = click_state.borrow_mut();
-        cl.render(f, chunks[1], menu_block, &mut cs, false, scroll_value);
-    }
-
-    // Scroll indicator overlays — registered last so they win over rows below.
-    if can_scroll_up && inner.height > 0 && inner.width > 0 {
-        let arrow_area = Rect::new(inner.x + inner.width - 3, inner.y, 3, 1);
-        let arrow = Paragraph::new(Span::styled(
-            " ▲ ",
-            Style::default().fg(Color::Yellow).add_modifier(Modifier::BOLD),
-        ));
-        Clickable::new(arrow, MENU_SCROLL_UP).render(
-            f,
-            arrow_area,
-            &mut click_state.borrow_mut(),
-        );
-    }
-    if can_scroll_down && inner.height > 0 && inner.width > 0 {
-        let arrow_area = Rect::new(
-            inner.x + inner.width - 3,
-            inner.y + inner.height - 1,
-            3,
-            1,
-        );
-        let arrow = Paragraph::new(Span::styled(
-            " ▼ ",
-            Style::default().fg(Color::Yellow).add_modifier(Modifier::BOLD),
-        ));
-        Clickable::new(arrow, MENU_SCROLL_DOWN).render(
-            f,
-            arrow_area,
-            &mut click_state.borrow_mut(),
-        );
-    }
-
-    // Footer
-    let footer_widget = Paragraph::new(Line::from(Span::styled(
-        "タップでゲームを選択",
-        Style::default().fg(Color::DarkGray),
-    )))
-    .block(
-        Block::default()
-            .borders(borders)
-            .border_style(Style::default().fg(Color::DarkGray)),
-    )
-    .alignment(Alignment::Center);
-    f.render_widget(footer_widget, chunks[2]);
-}
-
-fn render_settings(
-    f: &mut ratzilla::ratatui::Frame,
-    area: Rect,
-    click_state: &Rc<RefCell<ClickState>>,
-    confirm_reset: Option<&GameChoice>,
-) {
-    let is_narrow = is_narrow_layout(area.width);
-    let borders = if is_narrow {
-        Borders::TOP | Borders::BOTTOM
-    } else {
-        Borders::ALL
-    };
-
-    let chunks = Layout::default()
-        .direction(Direction::Vertical)
-        .constraints([
-            Constraint::Length(3), // Title
-            Constraint::Min(8),   // Content
-            Constraint::Length(3), // Footer
-        ])
-        .split(area);
-
-    // Title
-    let title_widget = Paragraph::new(Line::from(Span::styled(
-        "設定",
-        Style::default()
-            .fg(Color::Cyan)
-            .add_modifier(Modifier::BOLD),
-    )))
-    .block(
-        Block::default()
-            .borders(borders)
-            .border_style(Style::default().fg(Color::Cyan)),
-    )
-    .alignment(Alignment::Center);
-    f.render_widget(title_widget, chunks[0]);
-
-    if let Some(game) = confirm_reset {
-        render_confirm_dialog(f, chunks[1], click_state, borders, game);
-    } else {
-        render_settings_main(f, chunks[1], click_state, borders);
-    }
-
-    // Footer — back to menu
-    let mut cl = ClickableList::new();
-    cl.push_clickable(
-        Line::from(Span::styled(
-            "◀ メニューに戻る",
-            Style::default().fg(Color::DarkGray),
-        )),
-        BACK_TO_MENU,
-    );
-    let footer_block = Block::default()
-        .borders(borders)
-        .border_style(Style::default().fg(Color::DarkGray));
-    {
-        let mut cs = click_state.borrow_mut();
-        cl.render(f, chunks[2], footer_block, &mut cs, false, 0);
-    }
-}
-
-fn render_settings_main(
-    f: &mut ratzilla::ratatui::Frame,
-    area: Rect,
-    click_state: &Rc<RefCell<ClickState>>,
-    borders: Borders,
-) {
-    let mut cl = ClickableList::new();
-
-    cl.push(Line::from(""));
-    cl.push(Line::from(Span::styled(
-        " セーブデータ管理",
-        Style::default()
-            .fg(Color::Yellow)
-            .add_modifier(Modifier::BOLD),
-    )));
-    cl.push(Line::from(""));
-
-    // Cookie Factory
-    cl.push_clickable(
-        Line::from(vec![
-            Span::styled(" ✕ ", Style::default().fg(Color::Red)),
-            Span::styled("Cookie Factory", Style::default().fg(Color::White)),
-            Span::styled(" — データをリセット", Style::default().fg(Color::DarkGray)),
-        ]),
-        SETTINGS_RESET_COOKIE,
-    );
-
-    cl.push(Line::from(""));
-
-    // 深淵潜行 (Abyss Idle)
-    cl.push_clickable(
-        Line::from(vec![
-            Span::styled(" ✕ ", Style::default().fg(Color::Red)),
-            Span::styled("深淵潜行", Style::default().fg(Color::White)),
-            Span::styled(" — データをリセット", Style::default().fg(Color::DarkGray)),
-        ]),
-        SETTINGS_RESET_ABYSS,
-    );
-
-    cl.push(Line::from(""));
-
-    // Idle Metropolis
-    cl.push_clickable(
-        Line::from(vec![
-            Span::styled(" ✕ ", Style::default().fg(Color::Red)),
-            Span::styled("Idle Metropolis", Style::default().fg(Color::White)),
-            Span::styled(" — データをリセット", Style::default().fg(Color::DarkGray)),
-        ]),
-        SETTINGS_RESET_METROPOLIS,
     );
-
-    cl.push(Line::from(""));
-    cl.push(Line::from(""));
-    cl.push(Line::from(Span::styled(
-        " ※ Tiny Factory / Dungeon Dive / God Field は",
-        Style::default().fg(Color::DarkGray),
-    )));
-    cl.push(Line::from(Span::styled(
-        "   セーブデータがありません",
-        Style::default().fg(Color::DarkGray),
-    )));
-
-    let block = Block::default()
-        .borders(borders)
-        .border_style(Style::default().fg(Color::Green))
-        .title(" Data Reset ");
-    {
-        let mut cs = click_state.borrow_mut();
-        cl.render(f, area, block, &mut cs, false, 0);
+    let width = (chip.width() as u16).min(size.width);
+    if size.height == 0 || width == 0 {
+        return;
     }
-}
-
-fn render_confirm_dialog(
-    f: &mut ratzilla::ratatui::Frame,
-    area: Rect,
-    click_state: &Rc<RefCell<ClickState>>,
-    borders: Borders,
-    game: &GameChoice,
-) {
-    let game_name = match game {
-        GameChoice::Cookie => "Cookie Factory",
-        GameChoice::Abyss => "深淵潜行",
-        GameChoice::Metropolis => "Idle Metropolis",
-        _ => "Unknown",
-    };
-
-    let mut cl = ClickableList::new();
-
-    cl.push(Line::from(""));
-    cl.push(Line::from(Span::styled(
-        format!(" {game_name} のセーブデータを"),
-        Style::default().fg(Color::White),
-    )));
-    cl.push(Line::from(Span::styled(
-        " 本当にリセットしますか？",
-        Style::default()
-            .fg(Color::Yellow)
-            .add_modifier(Modifier::BOLD),
-    )));
-    cl.push(Line::from(""));
-    cl.push(Line::from(Span::styled(
-        " ※ この操作は取り消せません",
-        Style::default().fg(Color::Red),
-    )));
-    cl.push(Line::from(""));
-
-    cl.push_clickable(
-        Line::from(Span::styled(
-            " ▶ はい、リセットする",
-            Style::default().fg(Color::Red).add_modifier(Modifier::BOLD),
-        )),
-        SETTINGS_CONFIRM_YES,
-    );
-    cl.push(Line::from(""));
-    cl.push_clickable(
-        Line::from(Span::styled(
-            " ▶ キャンセル",
-            Style::default().fg(Color::Green),
-        )),
-        SETTINGS_CONFIRM_NO,
+    let area = Rect::new(
+        size.x + size.width - width,
+        size.y + size.height - 1,
+        width,
+        1,
     );
-
-    let block = Block::default()
-        .borders(borders)
-        .border_style(Style::default().fg(Color::Red))
-        .title(" 確認 ");
-    {
-        let mut cs = click_state.borrow_mut();
-        cl.render(f, area, block, &mut cs, false, 0);
-    }
+    f.render_widget(Clear, area);
+    f.render_widget(Paragraph::new(chip), area);
 }
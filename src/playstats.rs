@@ -0,0 +1,238 @@
+//! ゲームごとのプレイ統計 (累計プレイ時間・起動回数・最後に遊んだ日時)。
+//!
+//! 記録は [`crate::screens::PlayingScreen`] から行う。ゲームを開いたときに
+//! [`record_session`]、遊んだ時間はプレイ中の logic tick を貯めておき、
+//! 画面を離れるときと一定間隔ごとに [`record_play_time`] で書き足す。
+//! スコア画面などで一時停止している間は tick が来ないので数えない。
+//!
+//! 表示は設定の「プレイ統計」サブ画面。セーブデータとは別物なので、
+//! リセットも別 ([`reset`]) にしてある。
+//!
+//! キーは [`crate::profile::scoped_key`] でプロファイルごとに分ける。
+//! localStorage は WASM ビルドでのみ触る。native (cargo test) では
+//! 読み込みは常に空、書き込みは no-op。
+
+use std::collections::BTreeMap;
+
+use serde::{Deserialize, Serialize};
+
+use crate::games::GameChoice;
+#[cfg(target_arch = "wasm32")]
+use crate::profile;
+
+/// localStorage のキー。値は [`PlayStats`] の JSON。
+#[cfg(target_arch = "wasm32")]
+const PLAY_STATS_KEY: &str = "app_play_stats";
+
+/// 1 ゲーム分の統計。
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct GameStats {
+    /// 累計プレイ時間 (ms)。ゲームの tick 速さによらず実時間換算。
+    pub play_ms: u64,
+    /// ゲームを開いた回数。
+    pub sessions: u32,
+    /// 最後に開いた時刻 (`Date.now()`, ms since epoch)。0 は未記録。
+    pub last_played_ms: u64,
+}
+
+/// 全ゲームの統計。キーは [`GameChoice::id`]。
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct PlayStats {
+    pub games: BTreeMap<String, GameStats>,
+}
+
+impl PlayStats {
+    /// `choice` の統計。遊んだことがなければ 0 のもの。
+    pub fn get(&self, choice: &GameChoice) -> GameStats {
+        self.games.get(choice.id()).cloned().unwrap_or_default()
+    }
+
+    fn entry(&mut self, choice: &GameChoice) -> &mut GameStats {
+        self.games.entry(choice.id().to_string()).or_default()
+    }
+
+    /// ゲームを開いたことを記録する。
+    pub fn start_session(&mut self, choice: &GameChoice, now_ms: u64) {
+        let stats = self.entry(choice);
+        stats.sessions = stats.sessions.saturating_add(1);
+        if now_ms > 0 {
+            stats.last_played_ms = now_ms;
+        }
+    }
+
+    pub fn add_play_time(&mut self, choice: &GameChoice, ms: u64) {
+        let stats = self.entry(choice);
+        stats.play_ms = stats.play_ms.saturating_add(ms);
+    }
+
+    /// 全ゲーム合計のプレイ時間 (ms)。
+    pub fn total_play_ms(&self) -> u64 {
+        self.games.values().map(|s| s.play_ms).sum()
+    }
+}
+
+/// 現在時刻 (ms since epoch)。native では 0 (= 未記録扱い)。
+pub fn now_ms() -> u64 {
+    #[cfg(target_arch = "wasm32")]
+    {
+        let now = js_sys::Date::now();
+        if now.is_finite() && now >= 0.0 {
+            now as u64
+        } else {
+            0
+        }
+    }
+    #[cfg(not(target_arch = "wasm32"))]
+    0
+}
+
+/// logic tick 数を実時間 (ms) に直す。`ticks_per_sec` はそのゲームの速さ。
+pub fn ticks_to_ms(ticks: u64, ticks_per_sec: u32) -> u64 {
+    ticks * 1000 / ticks_per_sec.max(1) as u64
+}
+
+/// プレイ時間の表示 (「3時間12分」「45分」「30秒」)。
+pub fn format_duration(ms: u64) -> String {
+    let secs = ms / 1000;
+    let (h, m) = (secs / 3600, secs / 60 % 60);
+    if h > 0 {
+        format!("{h}時間{m}分")
+    } else if m > 0 {
+        format!("{m}分")
+    } else {
+        format!("{secs}秒")
+    }
+}
+
+/// 最後に遊んだ日時の表示 (「たった今」「5分前」「3時間前」「2日前」)。
+/// 未記録や時計が戻っている場合は「—」。
+pub fn format_ago(then_ms: u64, now_ms: u64) -> String {
+    if then_ms == 0 || now_ms < then_ms {
+        return "—".into();
+    }
+    let mins = (now_ms - then_ms) / 60_000;
+    match mins {
+        0 => "たった今".into(),
+        1..=59 => format!("{mins}分前"),
+        60..=1439 => format!("{}時間前", mins / 60),
+        _ => format!("{}日前", mins / 1440),
+    }
+}
+
+#[cfg(target_arch = "wasm32")]
+fn get_storage() -> Option<crate::storage::Store> {
+    crate::storage::get()
+}
+
+/// アクティブなプロファイルの統計を読み込む。未保存・破損時は空。
+pub fn load() -> PlayStats {
+    #[cfg(target_arch = "wasm32")]
+    {
+        get_storage()
+            .and_then(|s| s.get_item(&profile::scoped_key(PLAY_STATS_KEY)).ok().flatten())
+            .and_then(|json| serde_json::from_str(&json).ok())
+            .unwrap_or_default()
+    }
+    #[cfg(not(target_arch = "wasm32"))]
+    PlayStats::default()
+}
+
+/// 統計を保存する。失敗しても遊べなくなるわけではないので warn だけ出す。
+pub fn store(stats: &PlayStats) {
+    #[cfg(target_arch = "wasm32")]
+    if let (Some(storage), Ok(json)) = (get_storage(), serde_json::to_string(stats)) {
+        if let Err(e) = storage.set_item(&profile::scoped_key(PLAY_STATS_KEY), &json) {
+            web_sys::console::warn_1(&format!("playstats: localStorage への保存に失敗: {e:?}").into());
+        }
+    }
+    #[cfg(not(target_arch = "wasm32"))]
+    let _ = stats;
+}
+
+/// `choice` を開いたことを記録して保存する。
+pub fn record_session(choice: &GameChoice) {
+    let mut stats = load();
+    stats.start_session(choice, now_ms());
+    store(&stats);
+}
+
+/// `choice` のプレイ時間を `ms` 足して保存する。
+pub fn record_play_time(choice: &GameChoice, ms: u64) {
+    if ms == 0 {
+        return;
+    }
+    let mut stats = load();
+    stats.add_play_time(choice, ms);
+    store(&stats);
+}
+
+/// 統計を消す (セーブデータには触らない)。
+pub fn reset() {
+    #[cfg(target_arch = "wasm32")]
+    if let Some(storage) = get_storage() {
+        let _ = storage.remove_item(&profile::scoped_key(PLAY_STATS_KEY));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn sessions_and_time_accumulate_per_game() {
+        let mut stats = PlayStats::default();
+        stats.start_session(&GameChoice::Cookie, 1_000);
+        stats.start_session(&GameChoice::Cookie, 5_000);
+        stats.add_play_time(&GameChoice::Cookie, 90_000);
+        stats.add_play_time(&GameChoice::Rpg, 30_000);
+
+        let cookie = stats.get(&GameChoice::Cookie);
+        assert_eq!(cookie.sessions, 2);
+        assert_eq!(cookie.last_played_ms, 5_000);
+        assert_eq!(cookie.play_ms, 90_000);
+        assert_eq!(stats.get(&GameChoice::Rpg).sessions, 0);
+        assert_eq!(stats.get(&GameChoice::Port), GameStats::default());
+        assert_eq!(stats.total_play_ms(), 120_000);
+
+        // 時計が取れないときは最終プレイを上書きしない
+        stats.start_session(&GameChoice::Cookie, 0);
+        assert_eq!(stats.get(&GameChoice::Cookie).last_played_ms, 5_000);
+    }
+
+    #[test]
+    fn stats_roundtrip_through_json() {
+        let mut stats = PlayStats::default();
+        stats.start_session(&GameChoice::Factory, 42);
+        stats.add_play_time(&GameChoice::Factory, 7);
+        let json = serde_json::to_string(&stats).unwrap();
+        assert_eq!(serde_json::from_str::<PlayStats>(&json).unwrap(), stats);
+        // 欠けた項目は既定値で読める
+        let old: PlayStats = serde_json::from_str(r#"{"games":{"rpg":{"sessions":3}}}"#).unwrap();
+        assert_eq!(old.get(&GameChoice::Rpg).sessions, 3);
+    }
+
+    #[test]
+    fn ticks_convert_at_the_game_rate() {
+        assert_eq!(ticks_to_ms(10, 10), 1000);
+        assert_eq!(ticks_to_ms(30, 30), 1000);
+        assert_eq!(ticks_to_ms(5, 0), 5000);
+    }
+
+    #[test]
+    fn durations_and_ages_read_naturally() {
+        assert_eq!(format_duration(0), "0秒");
+        assert_eq!(format_duration(45_000), "45秒");
+        assert_eq!(format_duration(125_000), "2分");
+        assert_eq!(format_duration(3 * 3_600_000 + 12 * 60_000), "3時間12分");
+
+        let now = 10 * 86_400_000;
+        assert_eq!(format_ago(0, now), "—");
+        assert_eq!(format_ago(now + 1, now), "—");
+        assert_eq!(format_ago(now - 10_000, now), "たった今");
+        assert_eq!(format_ago(now - 5 * 60_000, now), "5分前");
+        assert_eq!(format_ago(now - 3 * 3_600_000, now), "3時間前");
+        assert_eq!(format_ago(now - 2 * 86_400_000, now), "2日前");
+    }
+}
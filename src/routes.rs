@@ -0,0 +1,176 @@
+//! URL ハッシュ (`location.hash`) と画面の対応。
+//!
+//! `#cookie` のようなリンクで特定のゲームを直接開き、画面を移るたびに
+//! ハッシュを書き換えてブックマーク・共有できるようにする。
+//!
+//! | ハッシュ | 画面 |
+//! |---|---|
+//! | (なし) | メニュー |
+//! | `#settings` / `#settings/keys` / `#settings/stats` | 設定 / キー設定 / プレイ統計 |
+//! | `#settings/arcade` | アーケード交換所 |
+//! | `#profiles` | プロファイル一覧 |
+//! | `#<game id>` | そのゲーム ([`GameChoice::id`]) |
+//! | `#<game id>/score` | そのゲームのスコアトークン |
+//!
+//! ゲーム内のタブはゲームの状態なので URL には載せない。
+//!
+//! location は WASM ビルドでのみ触る ([`crate::session`] と同じ)。
+//! native (cargo test) では読み込みは常に空、書き込みは no-op。
+
+use crate::games::{self, GameChoice};
+use crate::profile;
+use crate::screens::{
+    AppState, ArcadeScreen, KeyBindingsScreen, MenuScreen, PlayStatsScreen, PlayingScreen,
+    ProfilesScreen, ScoreScreen, SettingsScreen,
+};
+
+/// ハッシュで表せる画面。
+#[derive(Clone, Debug, PartialEq)]
+pub enum Route {
+    Menu,
+    Settings,
+    KeyBindings,
+    PlayStats,
+    Arcade,
+    Profiles,
+    Game(GameChoice),
+    Score(GameChoice),
+}
+
+impl Route {
+    /// `#` の有無は問わない。空はメニュー、解釈できないものは `None`。
+    pub fn parse(hash: &str) -> Option<Route> {
+        let path = hash.trim_start_matches('#').trim_matches('/');
+        let route = match path.split('/').collect::<Vec<_>>().as_slice() {
+            [""] => Route::Menu,
+            ["settings"] => Route::Settings,
+            ["settings", "keys"] => Route::KeyBindings,
+            ["settings", "stats"] => Route::PlayStats,
+            ["settings", "arcade"] => Route::Arcade,
+            ["profiles"] => Route::Profiles,
+            [id] => Route::Game(GameChoice::from_id(id)?),
+            [id, "score"] => Route::Score(GameChoice::from_id(id)?),
+            _ => return None,
+        };
+        Some(route)
+    }
+
+    /// `#` を除いたハッシュ。メニューは空文字列。
+    pub fn to_hash(&self) -> String {
+        match self {
+            Route::Menu => String::new(),
+            Route::Settings => "settings".into(),
+            Route::KeyBindings => "settings/keys".into(),
+            Route::PlayStats => "settings/stats".into(),
+            Route::Arcade => "settings/arcade".into(),
+            Route::Profiles => "profiles".into(),
+            Route::Game(choice) => choice.id().into(),
+            Route::Score(choice) => format!("{}/score", choice.id()),
+        }
+    }
+
+    /// 表示中の画面に対応するルート。
+    pub fn of(state: &AppState) -> Route {
+        match state {
+            AppState::Menu(_) => Route::Menu,
+            AppState::Settings(_) => Route::Settings,
+            AppState::KeyBindings(_) => Route::KeyBindings,
+            AppState::PlayStats(_) => Route::PlayStats,
+            AppState::Arcade(_) => Route::Arcade,
+            AppState::Profiles(_) => Route::Profiles,
+            AppState::Playing(p) => Route::Game(p.game.choice()),
+            AppState::ScoreToken(s) => Route::Score(s.game.choice()),
+        }
+    }
+
+    /// このルートの画面を新しく作る。ゲームはセーブから読み込み直す。
+    pub fn into_state(self) -> AppState {
+        match self {
+            Route::Menu => AppState::Menu(MenuScreen::new(None)),
+            Route::Settings => AppState::Settings(SettingsScreen::default()),
+            Route::KeyBindings => AppState::KeyBindings(KeyBindingsScreen::default()),
+            Route::PlayStats => AppState::PlayStats(PlayStatsScreen::default()),
+            Route::Arcade => AppState::Arcade(ArcadeScreen::default()),
+            Route::Profiles => AppState::Profiles(ProfilesScreen::new(profile::load())),
+            Route::Game(choice) => {
+                AppState::Playing(PlayingScreen::new(games::create_game(&choice)))
+            }
+            Route::Score(choice) => {
+                AppState::ScoreToken(ScoreScreen::new(games::create_game(&choice)))
+            }
+        }
+    }
+}
+
+/// 現在の `location.hash` が指すルート。ハッシュなし・不明なものは `None`。
+pub fn current() -> Option<Route> {
+    #[cfg(target_arch = "wasm32")]
+    {
+        let hash = web_sys::window()?.location().hash().ok()?;
+        if hash.trim_start_matches('#').is_empty() {
+            return None;
+        }
+        Route::parse(&hash)
+    }
+    #[cfg(not(target_arch = "wasm32"))]
+    None
+}
+
+/// `state` に合わせて `location.hash` を書き換える。同じなら触らない
+/// (書くたびに履歴が 1 つ積まれるため)。
+pub fn sync(state: &AppState) {
+    #[cfg(target_arch = "wasm32")]
+    {
+        let Some(location) = web_sys::window().map(|w| w.location()) else {
+            return;
+        };
+        let hash = Route::of(state).to_hash();
+        let current = location.hash().unwrap_or_default();
+        if current.trim_start_matches('#') != hash {
+            let _ = location.set_hash(&hash);
+        }
+    }
+    #[cfg(not(target_arch = "wasm32"))]
+    let _ = state;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn every_route_round_trips() {
+        let mut routes = vec![
+            Route::Menu,
+            Route::Settings,
+            Route::KeyBindings,
+            Route::PlayStats,
+            Route::Arcade,
+            Route::Profiles,
+        ];
+        for c in GameChoice::all() {
+            routes.push(Route::Game(c.clone()));
+            routes.push(Route::Score(c));
+        }
+        for r in routes {
+            assert_eq!(Route::parse(&format!("#{}", r.to_hash())), Some(r.clone()));
+            assert_eq!(Route::parse(&r.to_hash()), Some(r));
+        }
+    }
+
+    #[test]
+    fn parse_is_lenient_about_slashes_and_strict_about_names() {
+        assert_eq!(Route::parse("#/cookie/"), Some(Route::Game(GameChoice::Cookie)));
+        assert_eq!(Route::parse(""), Some(Route::Menu));
+        assert_eq!(Route::parse("#career/invest"), None);
+        assert_eq!(Route::parse("#rpg/inventory"), None);
+        assert_eq!(Route::parse("#settings/keys/extra"), None);
+    }
+
+    #[test]
+    fn state_maps_back_to_its_route() {
+        for r in [Route::Game(GameChoice::Rpg), Route::Score(GameChoice::Cookie), Route::Settings] {
+            assert_eq!(Route::of(&r.clone().into_state()), r);
+        }
+    }
+}
@@ -0,0 +1,196 @@
+//! アーケード交換所 (設定から開く)。ゲームをまたいで貯めたトークン
+//! ([`crate::arcade`]) を見た目の変化や開始ボーナスと交換する。
+
+use std::cell::RefCell;
+use std::rc::Rc;
+
+use ratzilla::ratatui::layout::{Alignment, Constraint, Direction, Layout, Rect};
+use ratzilla::ratatui::style::{Color, Modifier, Style};
+use ratzilla::ratatui::text::{Line, Span};
+use ratzilla::ratatui::widgets::{Block, Borders, Paragraph};
+use ratzilla::ratatui::Frame;
+
+use super::{AppState, Screen, SettingsScreen, Transition};
+use crate::action::{assert_disjoint, ActionRange};
+use crate::arcade::{self, Reward, Wallet};
+use crate::input::{is_narrow_layout, ClickScope, ClickState, InputEvent};
+use crate::widgets::ClickableList;
+use crate::{sound, theme, BACK_TO_MENU};
+
+// ── Arcade action IDs (Settings scope) ──────────────────────────
+/// One row per [`Reward::ALL`] entry.
+const ARCADE_REDEEM: ActionRange = ActionRange::new(60, Reward::ALL.len() as u16);
+
+const _: () = assert_disjoint(&[ARCADE_REDEEM]);
+
+/// Token redemption screen.
+#[derive(Default)]
+pub struct ArcadeScreen {
+    pub wallet: Wallet,
+}
+
+impl ArcadeScreen {
+    fn redeem(&mut self, reward: Reward) {
+        if arcade::redeem(reward) {
+            sound::play(sound::SELECT);
+        } else {
+            sound::play(sound::ERROR);
+        }
+        self.wallet = arcade::wallet();
+    }
+}
+
+impl Screen for ArcadeScreen {
+    fn scope(&self) -> ClickScope {
+        ClickScope::Settings
+    }
+
+    fn enter(&mut self) {
+        self.wallet = arcade::wallet();
+    }
+
+    fn handle(&mut self, event: &InputEvent) -> Transition {
+        match event {
+            InputEvent::Key(c @ '1'..='9') => {
+                if let Some(&reward) = Reward::ALL.get((*c as u8 - b'1') as usize) {
+                    self.redeem(reward);
+                }
+            }
+            InputEvent::Click(_, id) if ARCADE_REDEEM.contains(*id) => {
+                if let Some(i) = ARCADE_REDEEM.index(*id) {
+                    self.redeem(Reward::ALL[i]);
+                }
+            }
+            InputEvent::Key('q') | InputEvent::Click(_, BACK_TO_MENU) => {
+                return Transition::To(AppState::Settings(SettingsScreen::default()));
+            }
+            _ => {}
+        }
+        Transition::Stay
+    }
+
+    fn render(&mut self, f: &mut Frame, area: Rect, click_state: &Rc<RefCell<ClickState>>) {
+        render_arcade(f, area, click_state, &self.wallet);
+    }
+}
+
+fn render_arcade(f: &mut Frame, area: Rect, click_state: &Rc<RefCell<ClickState>>, wallet: &Wallet) {
+    let theme = theme::active();
+    let is_narrow = is_narrow_layout(area.width);
+    let borders = if is_narrow {
+        Borders::TOP | Borders::BOTTOM
+    } else {
+        Borders::ALL
+    };
+
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Length(3), // Title
+            Constraint::Min(8),   // Content
+            Constraint::Length(3), // Footer
+        ])
+        .split(area);
+
+    let title_widget = Paragraph::new(Line::from(Span::styled(
+        "アーケード交換所",
+        Style::default()
+            .fg(theme.primary)
+            .add_modifier(Modifier::BOLD),
+    )))
+    .block(
+        Block::default()
+            .borders(borders)
+            .border_style(Style::default().fg(theme.primary)),
+    )
+    .alignment(Alignment::Center);
+    f.render_widget(title_widget, chunks[0]);
+
+    let mut cl = ClickableList::new();
+    cl.push(Line::from(""));
+    cl.push(Line::from(vec![
+        Span::styled(" 🪙 ", Style::default().fg(Color::Yellow)),
+        Span::styled(
+            format!("{} 枚", wallet.tokens),
+            Style::default().fg(Color::Yellow).add_modifier(Modifier::BOLD),
+        ),
+        Span::styled(
+            format!("  (これまでに {} 枚)", wallet.earned_total),
+            Style::default().fg(theme.dim),
+        ),
+    ]));
+    cl.push(Line::from(""));
+    for (i, reward) in Reward::ALL.into_iter().enumerate() {
+        let owned = wallet.owns(reward);
+        let affordable = wallet.tokens >= reward.cost();
+        let (mark, mark_style) = if owned {
+            (" ✓ ", Style::default().fg(theme.good))
+        } else if affordable {
+            (" ▶ ", Style::default().fg(theme.accent).add_modifier(Modifier::BOLD))
+        } else {
+            (" ・", Style::default().fg(theme.dim))
+        };
+        let price = if owned {
+            Span::styled(" 交換済み", Style::default().fg(theme.good))
+        } else {
+            Span::styled(
+                format!(" 🪙{}", reward.cost()),
+                Style::default().fg(if affordable { Color::Yellow } else { theme.dim }),
+            )
+        };
+        cl.push_clickable(
+            Line::from(vec![
+                Span::styled(mark, mark_style),
+                Span::styled(format!("{}. ", i + 1), Style::default().fg(theme.dim)),
+                Span::styled(reward.name(), Style::default().fg(Color::White)),
+                price,
+            ]),
+            ARCADE_REDEEM.id(i),
+        );
+        cl.push_clickable(
+            Line::from(Span::styled(
+                format!("      {}", reward.description()),
+                Style::default().fg(theme.dim),
+            )),
+            ARCADE_REDEEM.id(i),
+        );
+    }
+    cl.push(Line::from(""));
+    cl.push(Line::from(Span::styled(" 貯め方", Style::default().fg(theme.accent))));
+    for line in [
+        format!("   Cookie Factory で転生 +{}", arcade::PRESTIGE_TOKENS),
+        format!(
+            "   Dungeon Dive で主を撃破 +{} / 魔王 +{}",
+            arcade::BOSS_TOKENS,
+            arcade::FINAL_BOSS_TOKENS
+        ),
+        format!("   Tiny Factory の目標を達成 +{}", arcade::MILESTONE_TOKENS),
+    ] {
+        cl.push(Line::from(Span::styled(line, Style::default().fg(theme.dim))));
+    }
+
+    let block = Block::default()
+        .borders(borders)
+        .border_style(Style::default().fg(Color::Yellow))
+        .title(" Arcade Tokens ");
+    {
+        let mut cs = click_state.borrow_mut();
+        cl.render(f, chunks[1], block, &mut cs, false, 0);
+    }
+
+    let mut footer = ClickableList::new();
+    footer.push_clickable(
+        Line::from(Span::styled(
+            "◀ 設定に戻る",
+            Style::default().fg(theme.dim),
+        )),
+        BACK_TO_MENU,
+    );
+    let footer_block = Block::default()
+        .borders(borders)
+        .border_style(Style::default().fg(theme.dim));
+    {
+        let mut cs = click_state.borrow_mut();
+        footer.render(f, chunks[2], footer_block, &mut cs, false, 0);
+    }
+}
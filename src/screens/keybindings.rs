@@ -0,0 +1,205 @@
+//! キー設定画面 (設定から開く)。
+
+use std::cell::RefCell;
+use std::rc::Rc;
+
+use ratzilla::ratatui::layout::{Alignment, Constraint, Direction, Layout, Rect};
+use ratzilla::ratatui::style::{Color, Modifier, Style};
+use ratzilla::ratatui::text::{Line, Span};
+use ratzilla::ratatui::widgets::{Block, Borders, Paragraph};
+use ratzilla::ratatui::Frame;
+
+use super::{AppState, Screen, SettingsScreen, Transition};
+use crate::action::{assert_disjoint, ActionRange};
+use crate::input::{is_narrow_layout, ClickScope, ClickState, InputEvent};
+use crate::keybind::{self, KeyAction, KeyBindings};
+use crate::widgets::ClickableList;
+use crate::{sound, theme, BACK_TO_MENU};
+
+// ── Key remap action IDs (Settings scope) ───────────────────────
+/// One row per [`KeyAction::ALL`] entry.
+const KEYBIND_ACTION: ActionRange = ActionRange::new(30, KeyAction::ALL.len() as u16);
+const KEYBIND_RESET: u16 = 40;
+/// Esc while waiting for a key (the raw keypress path has no 'q' for it).
+pub const KEYBIND_CANCEL: u16 = 41;
+
+const _: () = assert_disjoint(&[
+    KEYBIND_ACTION,
+    ActionRange::single(KEYBIND_RESET),
+    ActionRange::single(KEYBIND_CANCEL),
+]);
+
+/// Key remap screen.  `listening` is the action waiting for its new key
+/// (the next keypress is taken raw, see [`Screen::captures_keys`]).
+#[derive(Default)]
+pub struct KeyBindingsScreen {
+    pub bindings: KeyBindings,
+    pub listening: Option<KeyAction>,
+}
+
+impl Screen for KeyBindingsScreen {
+    fn scope(&self) -> ClickScope {
+        ClickScope::Settings
+    }
+
+    fn enter(&mut self) {
+        self.bindings = keybind::active();
+    }
+
+    fn exit(&mut self) {
+        self.listening = None;
+    }
+
+    fn captures_keys(&self) -> bool {
+        self.listening.is_some()
+    }
+
+    fn handle(&mut self, event: &InputEvent) -> Transition {
+        if let Some(action) = self.listening {
+            match event {
+                InputEvent::Key(c) => {
+                    sound::play(sound::SELECT);
+                    self.bindings.set(action, *c);
+                    keybind::store(&self.bindings);
+                    self.listening = None;
+                }
+                InputEvent::Click(_, KEYBIND_CANCEL) => self.listening = None,
+                _ => {}
+            }
+            return Transition::Stay;
+        }
+        match event {
+            InputEvent::Key(c @ '1'..='6') => {
+                self.listening = KeyAction::ALL.get((*c as u8 - b'1') as usize).copied();
+            }
+            InputEvent::Click(_, id) if KEYBIND_ACTION.contains(*id) => {
+                self.listening = KEYBIND_ACTION.index(*id).map(|i| KeyAction::ALL[i]);
+            }
+            InputEvent::Key('r') | InputEvent::Click(_, KEYBIND_RESET) => {
+                self.bindings = KeyBindings::default();
+                keybind::store(&self.bindings);
+            }
+            InputEvent::Key('q') | InputEvent::Click(_, BACK_TO_MENU) => {
+                return Transition::To(AppState::Settings(SettingsScreen::default()));
+            }
+            _ => {}
+        }
+        Transition::Stay
+    }
+
+    fn render(&mut self, f: &mut Frame, area: Rect, click_state: &Rc<RefCell<ClickState>>) {
+        render_keybindings(f, area, click_state, &self.bindings, self.listening);
+    }
+}
+
+fn render_keybindings(
+    f: &mut Frame,
+    area: Rect,
+    click_state: &Rc<RefCell<ClickState>>,
+    bindings: &KeyBindings,
+    listening: Option<KeyAction>,
+) {
+    let theme = theme::active();
+    let is_narrow = is_narrow_layout(area.width);
+    let borders = if is_narrow {
+        Borders::TOP | Borders::BOTTOM
+    } else {
+        Borders::ALL
+    };
+
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Length(3), // Title
+            Constraint::Min(8),   // Content
+            Constraint::Length(3), // Footer
+        ])
+        .split(area);
+
+    let title_widget = Paragraph::new(Line::from(Span::styled(
+        "キー設定",
+        Style::default()
+            .fg(theme.primary)
+            .add_modifier(Modifier::BOLD),
+    )))
+    .block(
+        Block::default()
+            .borders(borders)
+            .border_style(Style::default().fg(theme.primary)),
+    )
+    .alignment(Alignment::Center);
+    f.render_widget(title_widget, chunks[0]);
+
+    let mut cl = ClickableList::new();
+    cl.push(Line::from(""));
+    for (i, action) in KeyAction::ALL.into_iter().enumerate() {
+        let waiting = listening == Some(action);
+        let key_text = if waiting {
+            "▶ キーを押してください".to_string()
+        } else {
+            format!("[{}]", keybind::key_label(bindings.key(action)))
+        };
+        let key_style = if waiting {
+            Style::default().fg(theme.accent).add_modifier(Modifier::BOLD)
+        } else {
+            Style::default().fg(theme.primary)
+        };
+        cl.push_clickable(
+            Line::from(vec![
+                Span::styled(format!(" {}. ", i + 1), Style::default().fg(theme.dim)),
+                Span::styled(format!("{:<10}", action.label()), Style::default().fg(Color::White)),
+                Span::styled(key_text, key_style),
+            ]),
+            KEYBIND_ACTION.id(i),
+        );
+    }
+    cl.push(Line::from(""));
+    if listening.is_some() {
+        cl.push_clickable(
+            Line::from(Span::styled(" ▶ キャンセル (Esc)", Style::default().fg(Color::Green))),
+            KEYBIND_CANCEL,
+        );
+    } else {
+        cl.push_clickable(
+            Line::from(Span::styled(
+                " ↺ 初期設定に戻す [r]",
+                Style::default().fg(theme.accent),
+            )),
+            KEYBIND_RESET,
+        );
+    }
+    cl.push(Line::from(""));
+    cl.push(Line::from(Span::styled(
+        " ※ 矢印キー / Enter / Esc は常に使えます",
+        Style::default().fg(theme.dim),
+    )));
+    cl.push(Line::from(Span::styled(
+        " ※ 使用中のキーを選ぶと入れ替わります",
+        Style::default().fg(theme.dim),
+    )));
+
+    let block = Block::default()
+        .borders(borders)
+        .border_style(Style::default().fg(Color::Green))
+        .title(" Key Bindings ");
+    {
+        let mut cs = click_state.borrow_mut();
+        cl.render(f, chunks[1], block, &mut cs, false, 0);
+    }
+
+    let mut footer = ClickableList::new();
+    footer.push_clickable(
+        Line::from(Span::styled(
+            "◀ 設定に戻る",
+            Style::default().fg(theme.dim),
+        )),
+        BACK_TO_MENU,
+    );
+    let footer_block = Block::default()
+        .borders(borders)
+        .border_style(Style::default().fg(theme.dim));
+    {
+        let mut cs = click_state.borrow_mut();
+        footer.render(f, chunks[2], footer_block, &mut cs, false, 0);
+    }
+}
@@ -0,0 +1,570 @@
+//! ゲーム選択メニュー。
+//!
+//! ゲームはお気に入り → 最後に遊んだ順に並ぶ ([`session::menu_order`])。
+//! 数字キーと各カードの action ID は並びによらずゲームに固定。
+
+use std::cell::RefCell;
+use std::rc::Rc;
+
+use ratzilla::ratatui::layout::{Alignment, Constraint, Direction, Layout, Rect};
+use ratzilla::ratatui::style::{Color, Modifier, Style};
+use ratzilla::ratatui::text::{Line, Span};
+use ratzilla::ratatui::widgets::{Block, Borders, Paragraph};
+use ratzilla::ratatui::Frame;
+
+use super::{AppState, PlayingScreen, Screen, SettingsScreen, Transition};
+use crate::action::{assert_disjoint, ActionRange};
+use crate::games::{self, create_game, GameChoice, SaveStatus};
+use crate::input::{is_narrow_layout, ClickScope, ClickState, InputEvent};
+use crate::widgets::{Clickable, ClickableList};
+use crate::arcade::{self, Reward};
+use crate::{playstats, session, sound, theme};
+
+// ── Menu action IDs ─────────────────────────────────────────────
+const MENU_SELECT_COOKIE: u16 = 1;
+const MENU_SELECT_FACTORY: u16 = 2;
+const MENU_SELECT_RPG: u16 = 3;
+const MENU_SELECT_ABYSS: u16 = 4;
+const MENU_SELECT_GODFIELD: u16 = 5;
+const MENU_SELECT_METROPOLIS: u16 = 6;
+const MENU_SELECT_SETTINGS: u16 = 7;
+const MENU_SELECT_DRILL: u16 = 10;
+const MENU_SELECT_PORT: u16 = 11;
+const MENU_SELECT_DEFENSE: u16 = 12;
+const MENU_SCROLL_UP: u16 = 8;
+const MENU_SCROLL_DOWN: u16 = 9;
+const MENU_RESUME_YES: u16 = 15;
+const MENU_RESUME_NO: u16 = 16;
+/// ☆ (お気に入り切替)。[`GameChoice::all`] の順に 1 つずつ。
+const MENU_FAVORITE: ActionRange = ActionRange::new(20, 9);
+
+const _: () = assert_disjoint(&[
+    ActionRange::new(MENU_SELECT_COOKIE, 7),
+    ActionRange::new(MENU_SCROLL_UP, 2),
+    ActionRange::new(MENU_SELECT_DRILL, 3),
+    ActionRange::single(MENU_RESUME_YES),
+    ActionRange::single(MENU_RESUME_NO),
+    MENU_FAVORITE,
+]);
+
+/// Last valid index of the main menu cards (9 games + settings → 0..=9).
+const MENU_LAST_INDEX: u8 = 9;
+
+/// Cursor → menu action, used for the A button on the main menu.
+enum MenuPick {
+    Game(GameChoice),
+    Settings,
+}
+
+/// カーソル位置のカード。`order` は表示中のゲームの並びで、その後ろが設定。
+fn menu_pick_for(order: &[GameChoice], idx: u8) -> MenuPick {
+    match order.get(idx as usize) {
+        Some(choice) => MenuPick::Game(choice.clone()),
+        None => MenuPick::Settings,
+    }
+}
+
+/// [`GameChoice::all`] 内の位置 (カード表・セーブ状況・☆の action ID の添字)。
+fn game_index(choice: &GameChoice) -> usize {
+    GameChoice::all().iter().position(|c| c == choice).unwrap_or(0)
+}
+
+/// Showing game selection menu.
+pub struct MenuScreen {
+    /// Vertical scroll offset (in visual rows) for the game list,
+    /// so it stays usable when more games exist than fit on screen.
+    pub scroll: u16,
+    /// 0-based index of the currently highlighted game card.
+    /// Arrow keys move it; Enter/Space (= A button) launches.
+    pub selected: u8,
+    /// Game that was still running when the previous session ended.
+    /// `Some` shows the 「続きから再開しますか？」 prompt above the list.
+    pub resume: Option<GameChoice>,
+    /// Per-game save status in [`GameChoice::all`] order, loaded on
+    /// entering the menu ([`games::load_all_save_status`]).
+    pub progress: Vec<Option<SaveStatus>>,
+    /// Pinned games ([`session::load_favorites`]).
+    pub favorites: Vec<GameChoice>,
+    /// Display order of the game cards ([`session::menu_order`]).
+    pub order: Vec<GameChoice>,
+    /// Ticks since the menu opened; drives [`Reward::RainbowTitle`].
+    pub anim_ticks: u32,
+}
+
+impl MenuScreen {
+    pub fn new(resume: Option<GameChoice>) -> Self {
+        Self {
+            scroll: 0,
+            selected: 0,
+            resume,
+            progress: Vec::new(),
+            favorites: Vec::new(),
+            order: GameChoice::all().to_vec(),
+            anim_ticks: 0,
+        }
+    }
+
+    /// お気に入りを付け外しして並べ直す。カーソルはそのゲームについていく。
+    fn toggle_favorite(&mut self, choice: GameChoice) {
+        if let Some(i) = self.favorites.iter().position(|c| *c == choice) {
+            self.favorites.remove(i);
+        } else {
+            self.favorites.push(choice.clone());
+        }
+        session::store_favorites(&self.favorites);
+        self.order = session::menu_order(&self.favorites, &playstats::load());
+        if let Some(i) = self.order.iter().position(|c| *c == choice) {
+            self.selected = i as u8;
+        }
+    }
+
+    /// Menu for the active profile, with its resume prompt loaded.
+    pub fn resuming() -> Self {
+        Self::new(session::load_last_played())
+    }
+}
+
+impl Screen for MenuScreen {
+    fn scope(&self) -> ClickScope {
+        ClickScope::Menu
+    }
+
+    fn enter(&mut self) {
+        self.progress = games::load_all_save_status();
+        self.favorites = session::load_favorites();
+        self.order = session::menu_order(&self.favorites, &playstats::load());
+    }
+
+    fn handle(&mut self, event: &InputEvent) -> Transition {
+        // 「続きから再開」プロンプト表示中は y/n を優先して拾う。
+        // それ以外の入力は通常のメニュー操作に流す (プロンプトは残る)。
+        if let Some(choice) = self.resume.as_ref() {
+            match event {
+                InputEvent::Key('y') | InputEvent::Click(_, MENU_RESUME_YES) => {
+                    sound::play(sound::SELECT);
+                    let game = create_game(choice);
+                    return Transition::To(AppState::Playing(PlayingScreen::new(game)));
+                }
+                InputEvent::Key('n') | InputEvent::Click(_, MENU_RESUME_NO) => {
+                    self.resume = None;
+                    session::store_last_played(None);
+                    return Transition::Stay;
+                }
+                _ => {}
+            }
+        }
+        let direct = match event {
+            InputEvent::Key('1') | InputEvent::Click(_, MENU_SELECT_COOKIE) => {
+                Some(MenuPick::Game(GameChoice::Cookie))
+            }
+            InputEvent::Key('2') | InputEvent::Click(_, MENU_SELECT_FACTORY) => {
+                Some(MenuPick::Game(GameChoice::Factory))
+            }
+            InputEvent::Key('3') | InputEvent::Click(_, MENU_SELECT_RPG) => {
+                Some(MenuPick::Game(GameChoice::Rpg))
+            }
+            InputEvent::Key('4') | InputEvent::Click(_, MENU_SELECT_ABYSS) => {
+                Some(MenuPick::Game(GameChoice::Abyss))
+            }
+            InputEvent::Key('5') | InputEvent::Click(_, MENU_SELECT_GODFIELD) => {
+                Some(MenuPick::Game(GameChoice::Godfield))
+            }
+            InputEvent::Key('6') | InputEvent::Click(_, MENU_SELECT_METROPOLIS) => {
+                Some(MenuPick::Game(GameChoice::Metropolis))
+            }
+            InputEvent::Key('7') | InputEvent::Click(_, MENU_SELECT_DRILL) => {
+                Some(MenuPick::Game(GameChoice::Drill))
+            }
+            InputEvent::Key('8') | InputEvent::Click(_, MENU_SELECT_PORT) => {
+                Some(MenuPick::Game(GameChoice::Port))
+            }
+            InputEvent::Key('9') | InputEvent::Click(_, MENU_SELECT_DEFENSE) => {
+                Some(MenuPick::Game(GameChoice::Defense))
+            }
+            InputEvent::Key('0') | InputEvent::Click(_, MENU_SELECT_SETTINGS) => {
+                Some(MenuPick::Settings)
+            }
+            // A button (' ' / Enter via main.rs key map) confirms the
+            // currently highlighted card, so keyboard-only and tap users
+            // share the same selection model.
+            InputEvent::Key(' ') => Some(menu_pick_for(&self.order, self.selected)),
+            _ => None,
+        };
+        if let Some(pick) = direct {
+            sound::play(sound::SELECT);
+            return match pick {
+                MenuPick::Game(choice) => {
+                    Transition::To(AppState::Playing(PlayingScreen::new(create_game(&choice))))
+                }
+                MenuPick::Settings => Transition::To(AppState::Settings(SettingsScreen::default())),
+            };
+        }
+        match event {
+            InputEvent::Key('f') => {
+                if let MenuPick::Game(choice) = menu_pick_for(&self.order, self.selected) {
+                    sound::play(sound::SELECT);
+                    self.toggle_favorite(choice);
+                }
+            }
+            InputEvent::Click(_, id) if MENU_FAVORITE.contains(*id) => {
+                if let Some(choice) = MENU_FAVORITE.index(*id).map(|i| GameChoice::all()[i].clone()) {
+                    sound::play(sound::SELECT);
+                    self.toggle_favorite(choice);
+                }
+            }
+            // Arrow up/k: move highlight up. Auto-scroll so the
+            // selection always stays visible (keeps the UX usable
+            // when the menu list is taller than the viewport).
+            InputEvent::Key('k') | InputEvent::Click(_, MENU_SCROLL_UP) => {
+                let before = self.selected;
+                self.selected = self.selected.saturating_sub(1);
+                // 3 lines per game card → keep ~one card above
+                let target = (self.selected as u16) * 3;
+                if self.scroll > target {
+                    self.scroll = target;
+                }
+                if self.selected != before {
+                    sound::play(sound::CLICK);
+                }
+            }
+            InputEvent::Key('j') | InputEvent::Click(_, MENU_SCROLL_DOWN) => {
+                let before = self.selected;
+                self.selected = (self.selected + 1).min(MENU_LAST_INDEX);
+                // No upper-bound auto-scroll here — render re-clamps
+                // `scroll` against the actual viewport.
+                if self.selected != before {
+                    sound::play(sound::CLICK);
+                }
+            }
+            _ => {}
+        }
+        Transition::Stay
+    }
+
+    fn tick(&mut self, delta_ticks: u32) {
+        self.anim_ticks = self.anim_ticks.wrapping_add(delta_ticks);
+    }
+
+    fn render(&mut self, f: &mut Frame, area: Rect, click_state: &Rc<RefCell<ClickState>>) {
+        render_menu(f, area, click_state, self);
+    }
+}
+
+/// Only `menu.scroll` is written back (clamped to the viewport).
+fn render_menu(
+    f: &mut Frame,
+    area: Rect,
+    click_state: &Rc<RefCell<ClickState>>,
+    menu: &mut MenuScreen,
+) {
+    let scroll = &mut menu.scroll;
+    let selected = menu.selected;
+    let resume = menu.resume.as_ref();
+    let progress = menu.progress.as_slice();
+    let order = menu.order.as_slice();
+    let favorites = menu.favorites.as_slice();
+    let anim_ticks = menu.anim_ticks;
+    let theme = theme::active();
+    let is_narrow = is_narrow_layout(area.width);
+
+    // Resume prompt gets its own band between title and list so the
+    // card list below keeps the same geometry as without it.
+    let resume_height = if resume.is_some() { 5 } else { 0 };
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Length(3),             // Title
+            Constraint::Length(resume_height), // Resume prompt
+            Constraint::Min(8),                // Menu items
+            Constraint::Length(3),             // Footer
+        ])
+        .split(area);
+
+    // Title
+    let title = if is_narrow {
+        "Game Select"
+    } else {
+        "Game Select - ゲームを選んでください"
+    };
+    let borders = if is_narrow {
+        Borders::TOP | Borders::BOTTOM
+    } else {
+        Borders::ALL
+    };
+    let title_line = if arcade::owns(Reward::RainbowTitle) {
+        rainbow_line(title, anim_ticks)
+    } else {
+        Line::from(Span::styled(
+            title,
+            Style::default()
+                .fg(theme.primary)
+                .add_modifier(Modifier::BOLD),
+        ))
+    };
+    let title_widget = Paragraph::new(title_line)
+    .block(
+        Block::default()
+            .borders(borders)
+            .border_style(Style::default().fg(theme.primary)),
+    )
+    .alignment(Alignment::Center);
+    f.render_widget(title_widget, chunks[0]);
+
+    if let Some(choice) = resume {
+        render_resume_prompt(f, chunks[1], click_state, borders, choice);
+    }
+
+    // Menu items — driven by a single source of truth (MENU_ENTRIES) so
+    // adding a new game is one entry edit. Each card occupies 3 visual
+    // rows: blank / title / description; sharing the action ID across
+    // title + desc lets the player tap either row.
+    // accent はゲームの「顔」となる固有色。タイトル文字に常時乗せることで、
+    // 一覧をスクロールした時にどのゲームか色で識別できる。
+    type Entry = (&'static str, &'static str, u16, char, Color);
+    const MENU_ENTRIES: &[Entry] = &[
+        ("Cookie Factory", "クッキーをクリックして増やす放置ゲーム", MENU_SELECT_COOKIE, '▶', Color::LightYellow),
+        ("Tiny Factory", "工場を作って生産ラインを最適化する放置ゲーム", MENU_SELECT_FACTORY, '▶', Color::Cyan),
+        ("Dungeon Dive", "ダンジョンを探索して帰還するローグライト風RPG", MENU_SELECT_RPG, '▶', Color::LightRed),
+        ("深淵潜行 (Abyss Idle)", "自動戦闘で深層を目指す放置型ローグダンジョン", MENU_SELECT_ABYSS, '▶', Color::LightBlue),
+        ("神の戦場 (God Field)", "4人で戦うターン制カードバトルロイヤル", MENU_SELECT_GODFIELD, '▶', Color::Red),
+        ("Idle Metropolis", "AIが街を建てるのを眺める放置シティビルダー", MENU_SELECT_METROPOLIS, '▶', Color::LightCyan),
+        ("Deep Drill", "地中深く掘り進めて鉱石を集める放置採掘ゲーム", MENU_SELECT_DRILL, '▶', Color::LightGreen),
+        ("Port Trader", "港から港へ船を走らせて相場の差で稼ぐ交易ゲーム", MENU_SELECT_PORT, '▶', Color::LightBlue),
+        ("Grid Defense", "タワーを並べて押し寄せる敵の波を食い止める防衛ゲーム", MENU_SELECT_DEFENSE, '▶', Color::LightMagenta),
+        ("設定", "セーブデータの管理", MENU_SELECT_SETTINGS, '⚙', Color::Gray),
+    ];
+
+    // ゲームは表示順、設定は常に末尾。`Some(game)` はそのゲームのカード。
+    let cards: Vec<(usize, Option<&GameChoice>)> = order
+        .iter()
+        .map(|c| (game_index(c), Some(c)))
+        .chain(std::iter::once((MENU_ENTRIES.len() - 1, None)))
+        .collect();
+
+    let mut cl = ClickableList::new();
+    for (i, &(entry, game)) in cards.iter().enumerate() {
+        let (name, desc, action_id, default_marker, accent) = &MENU_ENTRIES[entry];
+        let is_selected = i as u8 == selected;
+        // Highlighted card: solid yellow ▶ marker + bold yellow title.
+        // Unselected: same shape but muted accent color, so the layout
+        // doesn't shift when the cursor moves and each game keeps its hue.
+        let marker = if is_selected { '▶' } else { *default_marker };
+        let marker_style = if is_selected {
+            Style::default().fg(theme.accent).add_modifier(Modifier::BOLD)
+        } else if *default_marker == '⚙' {
+            Style::default().fg(Color::Gray).add_modifier(Modifier::BOLD)
+        } else {
+            Style::default().fg(theme.dim)
+        };
+        let title_style = if is_selected {
+            Style::default().fg(theme.accent).add_modifier(Modifier::BOLD)
+        } else if *default_marker == '⚙' {
+            Style::default().fg(Color::Gray)
+        } else {
+            Style::default().fg(*accent)
+        };
+        // Entries line up with GameChoice::all(); settings has no status.
+        let status = game.and_then(|_| progress.get(entry)).and_then(|p| p.as_ref());
+        let star = match game {
+            Some(c) => star_span(favorites.contains(c)),
+            None => Span::raw("  "),
+        };
+        let mut title = vec![
+            star,
+            Span::styled(format!(" {} ", marker), marker_style),
+            Span::styled(*name, title_style),
+        ];
+        match status {
+            Some(SaveStatus::New) => title.push(Span::styled(
+                " NEW",
+                Style::default().fg(Color::LightYellow).add_modifier(Modifier::BOLD),
+            )),
+            Some(SaveStatus::Continue(_)) => title.push(Span::styled(
+                " 続きから",
+                Style::default().fg(Color::Green).add_modifier(Modifier::BOLD),
+            )),
+            None => {}
+        }
+        // Returning players see where they left off instead of the blurb.
+        let detail = match status {
+            Some(SaveStatus::Continue(summary)) => {
+                Span::styled(format!("      {}", summary), Style::default().fg(Color::Gray))
+            }
+            _ => Span::styled(format!("      {}", desc), Style::default().fg(theme.dim)),
+        };
+        cl.push(Line::from(""));
+        cl.push_clickable(Line::from(title), *action_id);
+        cl.push_clickable(Line::from(detail), *action_id);
+    }
+
+    let frame_color = if arcade::owns(Reward::GoldFrame) {
+        Color::Yellow
+    } else {
+        Color::Green
+    };
+    let menu_block = Block::default()
+        .borders(borders)
+        .border_style(Style::default().fg(frame_color))
+        .title(" Games ");
+
+    // Clamp scroll to content height. With wrap=false each logical line is
+    // exactly one visual row, so visible_rows is the inner height.
+    let inner = menu_block.inner(chunks[2]);
+    let total_lines = cl.len() as u16;
+    let visible_rows = inner.height;
+    let max_scroll = total_lines.saturating_sub(visible_rows);
+    if *scroll > max_scroll {
+        *scroll = max_scroll;
+    }
+
+    // Auto-scroll so the highlighted card stays visible. Each card spans
+    // 3 rows (blank / title / desc), with the title at row 3*selected + 1.
+    // We aim to keep the title row inside [scroll, scroll + visible_rows).
+    let card_top = (selected as u16) * 3;
+    let card_bottom = card_top + 3;
+    if card_top < *scroll {
+        *scroll = card_top;
+    } else if visible_rows > 0 && card_bottom > *scroll + visible_rows {
+        *scroll = card_bottom.saturating_sub(visible_rows);
+    }
+    if *scroll > max_scroll {
+        *scroll = max_scroll;
+    }
+    let can_scroll_up = *scroll > 0;
+    let can_scroll_down = *scroll < max_scroll;
+    let scroll_value = *scroll;
+
+    {
+        let mut cs = click_state.borrow_mut();
+        cl.render(f, chunks[2], menu_block, &mut cs, false, scroll_value);
+    }
+
+    // ☆ toggles — the star cell of each visible title row, over the card target.
+    for (i, &(entry, game)) in cards.iter().enumerate() {
+        let Some(game) = game else { continue };
+        let row = i as u16 * 3 + 1;
+        if row < scroll_value || row - scroll_value >= inner.height || inner.width < 2 {
+            continue;
+        }
+        let star_area = Rect::new(inner.x, inner.y + row - scroll_value, 2, 1);
+        let star = Paragraph::new(star_span(favorites.contains(game)));
+        Clickable::new(star, MENU_FAVORITE.id(entry)).render(
+            f,
+            star_area,
+            &mut click_state.borrow_mut(),
+        );
+    }
+
+    // Scroll indicator overlays — registered last so they win over rows below.
+    if can_scroll_up && inner.height > 0 && inner.width > 0 {
+        let arrow_area = Rect::new(inner.x + inner.width - 3, inner.y, 3, 1);
+        let arrow = Paragraph::new(Span::styled(
+            " ▲ ",
+            Style::default().fg(theme.accent).add_modifier(Modifier::BOLD),
+        ));
+        Clickable::new(arrow, MENU_SCROLL_UP).render(
+            f,
+            arrow_area,
+            &mut click_state.borrow_mut(),
+        );
+    }
+    if can_scroll_down && inner.height > 0 && inner.width > 0 {
+        let arrow_area = Rect::new(
+            inner.x + inner.width - 3,
+            inner.y + inner.height - 1,
+            3,
+            1,
+        );
+        let arrow = Paragraph::new(Span::styled(
+            " ▼ ",
+            Style::default().fg(theme.accent).add_modifier(Modifier::BOLD),
+        ));
+        Clickable::new(arrow, MENU_SCROLL_DOWN).render(
+            f,
+            arrow_area,
+            &mut click_state.borrow_mut(),
+        );
+    }
+
+    // Footer
+    let footer_widget = Paragraph::new(Line::from(Span::styled(
+        "タップでゲームを選択 / ☆タップ・[f]でお気に入り",
+        Style::default().fg(theme.dim),
+    )))
+    .block(
+        Block::default()
+            .borders(borders)
+            .border_style(Style::default().fg(theme.dim)),
+    )
+    .alignment(Alignment::Center);
+    f.render_widget(footer_widget, chunks[3]);
+}
+
+/// 1 文字ずつ色を変え、時間とともに流れる見出し ([`Reward::RainbowTitle`])。
+fn rainbow_line(text: &str, anim_ticks: u32) -> Line<'static> {
+    const COLORS: [Color; 6] = [
+        Color::LightRed,
+        Color::LightYellow,
+        Color::LightGreen,
+        Color::LightCyan,
+        Color::LightBlue,
+        Color::LightMagenta,
+    ];
+    let shift = (anim_ticks / 2) as usize;
+    Line::from(
+        text.chars()
+            .enumerate()
+            .map(|(i, c)| {
+                let color = COLORS[(i + COLORS.len() - shift % COLORS.len()) % COLORS.len()];
+                Span::styled(c.to_string(), Style::default().fg(color).add_modifier(Modifier::BOLD))
+            })
+            .collect::<Vec<_>>(),
+    )
+}
+
+/// カード先頭の ★ (お気に入り) / ☆。
+fn star_span(favorite: bool) -> Span<'static> {
+    if favorite {
+        Span::styled(" ★", Style::default().fg(Color::Yellow).add_modifier(Modifier::BOLD))
+    } else {
+        Span::styled(" ☆", Style::default().fg(theme::active().dim))
+    }
+}
+
+fn render_resume_prompt(
+    f: &mut Frame,
+    area: Rect,
+    click_state: &Rc<RefCell<ClickState>>,
+    borders: Borders,
+    choice: &GameChoice,
+) {
+    let theme = theme::active();
+    let mut cl = ClickableList::new();
+    cl.push_clickable(
+        Line::from(vec![
+            Span::styled(
+                " ▶ 再開する ",
+                Style::default().fg(theme.accent).add_modifier(Modifier::BOLD),
+            ),
+            Span::styled(choice.title(), Style::default().fg(Color::White)),
+            Span::styled(" [y]", Style::default().fg(theme.dim)),
+        ]),
+        MENU_RESUME_YES,
+    );
+    cl.push(Line::from(""));
+    cl.push_clickable(
+        Line::from(Span::styled(
+            " ✕ メニューから選ぶ [n]",
+            Style::default().fg(theme.dim),
+        )),
+        MENU_RESUME_NO,
+    );
+    let block = Block::default()
+        .borders(borders)
+        .border_style(Style::default().fg(theme.accent))
+        .title(" 続きから再開しますか？ ");
+    {
+        let mut cs = click_state.borrow_mut();
+        cl.render(f, area, block, &mut cs, false, 0);
+    }
+}
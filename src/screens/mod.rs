@@ -0,0 +1,375 @@
+//! トップレベル画面 (メニュー・設定・プレイ中など) とその切り替え。
+//!
+//! 各画面は [`Screen`] を実装し、入力処理・描画・入退場フックを自分の
+//! モジュールに持つ。main.rs は [`Router`] にイベントとフレームを渡す
+//! だけで、どの画面が表示中かを知らなくてよい。
+//!
+//! 画面を増やすときは:
+//! 1. このディレクトリに `Screen` を実装した構造体を置く
+//! 2. [`AppState`] に variant を足し、[`AppState::screen`] /
+//!    [`AppState::screen_mut`] に 1 行ずつ追加する
+//! 3. 遷移元の `handle` から [`Transition::To`] で返す
+
+mod arcade;
+mod keybindings;
+mod menu;
+mod playing;
+mod profiles;
+mod settings;
+mod stats;
+
+pub use arcade::ArcadeScreen;
+pub use keybindings::{KeyBindingsScreen, KEYBIND_CANCEL};
+pub use menu::MenuScreen;
+pub use playing::{PlayingScreen, ScoreScreen};
+pub use profiles::ProfilesScreen;
+pub use settings::SettingsScreen;
+pub use stats::PlayStatsScreen;
+
+use std::cell::RefCell;
+use std::rc::Rc;
+
+use ratzilla::ratatui::layout::Rect;
+use ratzilla::ratatui::Frame;
+
+use crate::input::{ClickScope, ClickState, InputEvent, KeyRepeat};
+use crate::time::TICKS_PER_SEC;
+
+/// トップレベル画面の共通インターフェース。
+pub trait Screen {
+    /// この画面が登録するクリックターゲットのスコープ。
+    /// [`Router::handle`] は一致しないクリックを捨てる。
+    fn scope(&self) -> ClickScope;
+
+    /// 画面に入った直後に呼ばれる。localStorage の読み込みなど、
+    /// 表示のたびに最新にしたいものはここで取る。
+    fn enter(&mut self) {}
+
+    /// 別の画面に切り替わる直前に呼ばれる。
+    fn exit(&mut self) {}
+
+    /// ページを閉じる・タブが隠れる直前に呼ばれる。戻ってこないかもしれない
+    /// ので、溜めている書き込みをここで済ませる。
+    fn suspend(&mut self) {}
+
+    /// 入力を処理し、画面遷移があれば返す。
+    fn handle(&mut self, event: &InputEvent) -> Transition;
+
+    /// 長押し / ダブルタップ。処理したら `true`。`false` なら
+    /// [`Router`] が通常のクリックに落として [`Screen::handle`] に渡す。
+    fn handle_gesture(&mut self, _event: &InputEvent) -> bool {
+        false
+    }
+
+    /// logic tick。ゲーム以外の画面は時間で進まない。
+    fn tick(&mut self, _delta_ticks: u32) {}
+
+    /// 1 秒あたりの logic tick 数 ([`crate::games::Game::tick_rate`])。
+    fn tick_rate(&self) -> u32 {
+        TICKS_PER_SEC
+    }
+
+    /// 押しっぱなしキーのリピート設定。`None` の画面ではリピートしない。
+    fn key_repeat(&self) -> Option<KeyRepeat> {
+        None
+    }
+
+    /// 次のキー入力を割り当て表を通さずそのまま受け取りたいとき `true`
+    /// (キー設定画面で割り当て待ちの間)。
+    fn captures_keys(&self) -> bool {
+        false
+    }
+
+    /// 画面全体を描画する。
+    fn render(&mut self, f: &mut Frame, area: Rect, click_state: &Rc<RefCell<ClickState>>);
+}
+
+/// [`Screen::handle`] の結果。
+pub enum Transition {
+    /// 今の画面に留まる。
+    Stay,
+    /// 新しい画面に切り替える。
+    To(AppState),
+    /// 今の画面を値として受け取って次の画面を作る。プレイ中のゲームを
+    /// スコア画面に持ち替えるときのように、所有しているものを次へ渡す遷移用。
+    Map(fn(AppState) -> AppState),
+}
+
+/// Top-level application state.
+pub enum AppState {
+    /// Showing game selection menu.
+    Menu(MenuScreen),
+    /// Showing settings screen (data reset).
+    Settings(SettingsScreen),
+    /// Key remap screen, opened from Settings.
+    KeyBindings(KeyBindingsScreen),
+    /// Per-game play time / session counts, opened from Settings.
+    PlayStats(PlayStatsScreen),
+    /// Arcade token redemption ([`crate::arcade`]), opened from Settings.
+    Arcade(ArcadeScreen),
+    /// Profile list (create / rename / delete / switch).
+    /// Shown at boot when more than one profile exists, or from Settings.
+    Profiles(ProfilesScreen),
+    /// Playing a game.
+    Playing(PlayingScreen),
+    /// Score token screen for the running game ([`crate::scores`]).
+    ScoreToken(ScoreScreen),
+}
+
+impl AppState {
+    pub fn screen(&self) -> &dyn Screen {
+        match self {
+            AppState::Menu(s) => s,
+            AppState::Settings(s) => s,
+            AppState::KeyBindings(s) => s,
+            AppState::PlayStats(s) => s,
+            AppState::Arcade(s) => s,
+            AppState::Profiles(s) => s,
+            AppState::Playing(s) => s,
+            AppState::ScoreToken(s) => s,
+        }
+    }
+
+    pub fn screen_mut(&mut self) -> &mut dyn Screen {
+        match self {
+            AppState::Menu(s) => s,
+            AppState::Settings(s) => s,
+            AppState::KeyBindings(s) => s,
+            AppState::PlayStats(s) => s,
+            AppState::Arcade(s) => s,
+            AppState::Profiles(s) => s,
+            AppState::Playing(s) => s,
+            AppState::ScoreToken(s) => s,
+        }
+    }
+}
+
+/// 表示中の画面を持ち、入力・tick・描画を振り分けて遷移を適用する。
+pub struct Router {
+    state: AppState,
+}
+
+impl Router {
+    /// `initial` の [`Screen::enter`] を呼んで開始する。
+    pub fn new(initial: AppState) -> Self {
+        let mut router = Self { state: initial };
+        router.state.screen_mut().enter();
+        router
+    }
+
+    pub fn state(&self) -> &AppState {
+        &self.state
+    }
+
+    pub fn state_mut(&mut self) -> &mut AppState {
+        &mut self.state
+    }
+
+    /// 表示中の画面。
+    pub fn screen(&self) -> &dyn Screen {
+        self.state.screen()
+    }
+
+    /// Dispatch an input event to the current screen.
+    pub fn handle(&mut self, event: &InputEvent) {
+        if let Some(scope) = event.scope() {
+            if *scope != self.screen().scope() {
+                debug_assert!(
+                    false,
+                    "click scope {:?} doesn't match active state",
+                    scope,
+                );
+                // In release: drop the stale click rather than misroute it.
+                return;
+            }
+        }
+
+        // Long-press / double-tap go to the screen first.  Anything that
+        // doesn't handle them gets the plain click instead, so gestures
+        // never swallow a tap.
+        if let Some(click) = event.to_click() {
+            if !self.state.screen_mut().handle_gesture(event) {
+                self.handle(&click);
+            }
+            return;
+        }
+
+        let transition = self.state.screen_mut().handle(event);
+        self.apply(transition);
+    }
+
+    /// 入力を介さずに `next` へ切り替える (URL ハッシュの変更など)。
+    pub fn go(&mut self, next: AppState) {
+        self.apply(Transition::To(next));
+    }
+
+    /// ページを閉じる・タブが隠れるときに表示中の画面へ保存を促す。
+    pub fn suspend(&mut self) {
+        self.state.screen_mut().suspend();
+    }
+
+    /// Advance the current screen by `delta_ticks` logic ticks.
+    pub fn tick(&mut self, delta_ticks: u32) {
+        self.state.screen_mut().tick(delta_ticks);
+    }
+
+    pub fn render(&mut self, f: &mut Frame, area: Rect, click_state: &Rc<RefCell<ClickState>>) {
+        self.state.screen_mut().render(f, area, click_state);
+    }
+
+    fn apply(&mut self, transition: Transition) {
+        let next = match transition {
+            Transition::Stay => return,
+            Transition::To(next) => {
+                self.state.screen_mut().exit();
+                next
+            }
+            Transition::Map(to) => {
+                self.state.screen_mut().exit();
+                // 一時的に置く値。localStorage を読まない画面にする。
+                let placeholder = AppState::Settings(SettingsScreen::default());
+                to(std::mem::replace(&mut self.state, placeholder))
+            }
+        };
+        self.state = next;
+        self.state.screen_mut().enter();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::games::GameChoice;
+    use crate::{BACK_TO_MENU, SHOW_SCORE};
+
+    fn playing(choice: GameChoice) -> Router {
+        Router::new(AppState::Playing(PlayingScreen::new(
+            crate::games::create_game(&choice),
+        )))
+    }
+
+    #[test]
+    fn menu_number_key_starts_game() {
+        let mut router = Router::new(AppState::Menu(MenuScreen::new(None)));
+        router.handle(&InputEvent::Key('1'));
+        assert!(matches!(
+            router.state(),
+            AppState::Playing(p) if p.game.choice() == GameChoice::Cookie
+        ));
+    }
+
+    #[test]
+    fn menu_enter_loads_progress() {
+        let router = Router::new(AppState::Menu(MenuScreen::new(None)));
+        let AppState::Menu(menu) = router.state() else {
+            panic!("expected menu");
+        };
+        assert_eq!(menu.progress.len(), GameChoice::all().len());
+    }
+
+    #[test]
+    fn pinning_moves_the_highlighted_game_to_the_top() {
+        let mut router = Router::new(AppState::Menu(MenuScreen::new(None)));
+        for _ in 0..3 {
+            router.handle(&InputEvent::Key('j'));
+        }
+        router.handle(&InputEvent::Key('f'));
+        let AppState::Menu(menu) = router.state() else {
+            panic!("expected menu");
+        };
+        assert_eq!(menu.order[0], GameChoice::Abyss);
+        assert_eq!(menu.selected, 0);
+        // 数字キーは並びによらずゲームに固定
+        router.handle(&InputEvent::Key('1'));
+        assert!(matches!(
+            router.state(),
+            AppState::Playing(p) if p.game.choice() == GameChoice::Cookie
+        ));
+    }
+
+    #[test]
+    fn back_from_game_returns_to_menu() {
+        let mut router = playing(GameChoice::Factory);
+        router.handle(&InputEvent::Click(ClickScope::Game(GameChoice::Factory), BACK_TO_MENU));
+        assert!(matches!(router.state(), AppState::Menu(_)));
+    }
+
+    #[test]
+    fn score_screen_keeps_the_same_game() {
+        let mut router = playing(GameChoice::Cookie);
+        router.handle(&InputEvent::Click(ClickScope::Game(GameChoice::Cookie), SHOW_SCORE));
+        assert!(matches!(router.state(), AppState::ScoreToken(_)));
+        router.handle(&InputEvent::Key('q'));
+        assert!(matches!(
+            router.state(),
+            AppState::Playing(p) if p.game.choice() == GameChoice::Cookie
+        ));
+    }
+
+    #[test]
+    fn suspend_saves_in_place_without_leaving_the_game() {
+        let store = crate::storage::get().unwrap();
+        let key = crate::games::drill::save::storage_key();
+        store.remove_item(&key).unwrap();
+
+        let mut router = playing(GameChoice::Drill);
+        router.tick(10);
+        router.suspend();
+        assert!(matches!(
+            router.state(),
+            AppState::Playing(p) if p.game.choice() == GameChoice::Drill
+        ));
+        let saved = store.get_item(&key).unwrap().expect("suspend must write the save");
+        assert!(saved.contains("\"total_ticks\":10"), "{saved}");
+        store.remove_item(&key).unwrap();
+    }
+
+    #[test]
+    fn settings_keybindings_round_trip() {
+        let mut router = Router::new(AppState::Settings(SettingsScreen::default()));
+        router.handle(&InputEvent::Key('7'));
+        assert!(matches!(router.state(), AppState::KeyBindings(_)));
+        router.handle(&InputEvent::Key('1'));
+        assert!(router.screen().captures_keys());
+        router.handle(&InputEvent::Click(ClickScope::Settings, KEYBIND_CANCEL));
+        assert!(!router.screen().captures_keys());
+        router.handle(&InputEvent::Key('q'));
+        assert!(matches!(router.state(), AppState::Settings(_)));
+    }
+
+    #[test]
+    fn settings_play_stats_round_trip() {
+        let mut router = Router::new(AppState::Settings(SettingsScreen::default()));
+        router.handle(&InputEvent::Key('s'));
+        assert!(matches!(router.state(), AppState::PlayStats(_)));
+        // リセット確認中の q はダイアログを閉じるだけ
+        router.handle(&InputEvent::Key('r'));
+        router.handle(&InputEvent::Key('q'));
+        assert!(matches!(router.state(), AppState::PlayStats(s) if !s.confirm_reset));
+        router.handle(&InputEvent::Key('q'));
+        assert!(matches!(router.state(), AppState::Settings(_)));
+    }
+
+    #[test]
+    fn arcade_redeems_with_number_keys() {
+        crate::arcade::load();
+        crate::arcade::earn("テスト", crate::arcade::Reward::GoldFrame.cost());
+        let mut router = Router::new(AppState::Settings(SettingsScreen::default()));
+        router.handle(&InputEvent::Key('a'));
+        router.handle(&InputEvent::Key('1'));
+        assert!(matches!(
+            router.state(),
+            AppState::Arcade(s) if s.wallet.owns(crate::arcade::Reward::GoldFrame) && s.wallet.tokens == 0
+        ));
+        router.handle(&InputEvent::Key('q'));
+        assert!(matches!(router.state(), AppState::Settings(_)));
+        crate::toast::clear();
+    }
+
+    #[test]
+    fn unhandled_gesture_falls_back_to_click() {
+        let mut router = Router::new(AppState::Settings(SettingsScreen::default()));
+        router.handle(&InputEvent::LongPress(ClickScope::Settings, BACK_TO_MENU));
+        assert!(matches!(router.state(), AppState::Menu(_)));
+    }
+}
@@ -0,0 +1,279 @@
+//! プレイ中のゲームと、その上に開くスコアトークン画面。
+
+use std::cell::RefCell;
+use std::rc::Rc;
+
+use ratzilla::ratatui::layout::Rect;
+use ratzilla::ratatui::style::{Color, Modifier, Style};
+use ratzilla::ratatui::text::{Line, Span};
+use ratzilla::ratatui::widgets::{Block, Borders, Paragraph};
+use ratzilla::ratatui::Frame;
+
+use super::{AppState, MenuScreen, Screen, Transition};
+use crate::games::Game;
+use crate::input::{is_narrow_layout, ClickScope, ClickState, InputEvent, KeyRepeat};
+use crate::widgets::{Clickable, ClickableList};
+use crate::{playstats, scores, session, sound, theme, BACK_TO_MENU, SHOW_SCORE};
+
+// ── Score token action IDs ──────────────────────────────────────
+const SCORE_COPY: u16 = 60;
+const SCORE_CLOSE: u16 = 61;
+
+/// プレイ時間をこの秒数ごとに書き出す。タブを閉じられても失うのは高々これだけ。
+const PLAY_TIME_FLUSH_SECS: u32 = 30;
+
+/// Playing a game.
+pub struct PlayingScreen {
+    pub game: Box<dyn Game>,
+    /// `enter` で起動回数を数えるか (スコア画面から戻ったときは数えない)。
+    new_session: bool,
+    /// まだ [`playstats`] に書き出していない logic tick 数。
+    unsaved_ticks: u32,
+}
+
+impl PlayingScreen {
+    pub fn new(game: Box<dyn Game>) -> Self {
+        Self { game, new_session: true, unsaved_ticks: 0 }
+    }
+
+    /// 一時停止 (スコア画面) から同じセッションを再開する。
+    pub fn resume(game: Box<dyn Game>) -> Self {
+        Self { new_session: false, ..Self::new(game) }
+    }
+
+    fn flush_play_time(&mut self) {
+        let ms = playstats::ticks_to_ms(self.unsaved_ticks as u64, self.game.tick_rate());
+        playstats::record_play_time(&self.game.choice(), ms);
+        self.unsaved_ticks = 0;
+    }
+}
+
+impl Screen for PlayingScreen {
+    fn scope(&self) -> ClickScope {
+        ClickScope::Game(self.game.choice())
+    }
+
+    fn enter(&mut self) {
+        // A session that ends mid-game offers to resume next time.
+        session::store_last_played(Some(&self.game.choice()));
+        if self.new_session {
+            playstats::record_session(&self.game.choice());
+            self.new_session = false;
+        }
+    }
+
+    fn exit(&mut self) {
+        self.flush_play_time();
+    }
+
+    fn suspend(&mut self) {
+        self.game.save();
+        self.flush_play_time();
+    }
+
+    fn handle(&mut self, event: &InputEvent) -> Transition {
+        // ★スコア (プレイ中オーバーレイ): ゲームには渡さず、一時停止して
+        // トークン画面へ。スコアを持たないゲームではボタン自体が出ない。
+        if matches!(event, InputEvent::Click(_, SHOW_SCORE)) {
+            if self.game.score().is_some() {
+                sound::play(sound::SELECT);
+                return Transition::Map(|state| match state {
+                    AppState::Playing(p) => AppState::ScoreToken(ScoreScreen::new(p.game)),
+                    other => other,
+                });
+            }
+            return Transition::Stay;
+        }
+        if matches!(event, InputEvent::Key('q') | InputEvent::Click(_, BACK_TO_MENU)) {
+            // Let the game handle back first (e.g., sub-screen → main screen).
+            // Only go to menu if the game didn't consume it.
+            if !self.game.handle_input(event) {
+                // Leaving via the menu is a clean exit — only a session
+                // that ends mid-game should offer to resume next time.
+                session::store_last_played(None);
+                return Transition::To(AppState::Menu(MenuScreen::new(None)));
+            }
+        } else {
+            self.game.handle_input(event);
+        }
+        Transition::Stay
+    }
+
+    fn handle_gesture(&mut self, event: &InputEvent) -> bool {
+        self.game.handle_input(event)
+    }
+
+    fn tick(&mut self, delta_ticks: u32) {
+        self.game.tick(delta_ticks);
+        self.unsaved_ticks = self.unsaved_ticks.saturating_add(delta_ticks);
+        if self.unsaved_ticks >= PLAY_TIME_FLUSH_SECS * self.game.tick_rate() {
+            self.flush_play_time();
+        }
+    }
+
+    fn tick_rate(&self) -> u32 {
+        self.game.tick_rate()
+    }
+
+    fn key_repeat(&self) -> Option<KeyRepeat> {
+        self.game.key_repeat()
+    }
+
+    fn render(&mut self, f: &mut Frame, area: Rect, click_state: &Rc<RefCell<ClickState>>) {
+        self.game.render(f, area, click_state);
+
+        // Overlay back button in top-left corner.  Registered
+        // last so it wins over any game-area target on overlap.
+        let back_area = Rect::new(area.x, area.y, 6, 1);
+        let back = Paragraph::new(Span::styled(
+            " ◀戻る",
+            Style::default().fg(Color::DarkGray),
+        ));
+        Clickable::new(back, BACK_TO_MENU).render(f, back_area, &mut click_state.borrow_mut());
+
+        // Score token button in the top-right corner, same rules.
+        if self.game.score().is_some() && area.width >= 16 {
+            let score_area = Rect::new(area.x + area.width - 8, area.y, 8, 1);
+            let star = Paragraph::new(Span::styled(
+                " ★スコア",
+                Style::default().fg(Color::DarkGray),
+            ));
+            Clickable::new(star, SHOW_SCORE).render(f, score_area, &mut click_state.borrow_mut());
+        }
+    }
+}
+
+/// Score token screen for the running game ([`crate::scores`]).
+/// The game is paused (not ticked) while this is open and resumes
+/// as `Playing` with the same instance when closed.
+pub struct ScoreScreen {
+    pub game: Box<dyn Game>,
+    /// Result of the last copy attempt (`None` until the button is used).
+    pub copied: Option<bool>,
+}
+
+impl ScoreScreen {
+    pub fn new(game: Box<dyn Game>) -> Self {
+        Self { game, copied: None }
+    }
+}
+
+impl Screen for ScoreScreen {
+    fn scope(&self) -> ClickScope {
+        ClickScope::Scores
+    }
+
+    fn suspend(&mut self) {
+        self.game.save();
+    }
+
+    fn handle(&mut self, event: &InputEvent) -> Transition {
+        match event {
+            InputEvent::Key('c') | InputEvent::Click(_, SCORE_COPY) => {
+                if let Some(score) = self.game.score() {
+                    let ok = scores::copy_to_clipboard(&scores::encode_token(
+                        &self.game.choice(),
+                        score.value,
+                    ));
+                    sound::play(if ok { sound::SELECT } else { sound::ERROR });
+                    self.copied = Some(ok);
+                }
+                Transition::Stay
+            }
+            InputEvent::Key('q') | InputEvent::Click(_, SCORE_CLOSE) => {
+                sound::play(sound::CLICK);
+                Transition::Map(|state| match state {
+                    AppState::ScoreToken(s) => AppState::Playing(PlayingScreen::resume(s.game)),
+                    other => other,
+                })
+            }
+            _ => Transition::Stay,
+        }
+    }
+
+    fn render(&mut self, f: &mut Frame, area: Rect, click_state: &Rc<RefCell<ClickState>>) {
+        render_score_token(f, area, click_state, self.game.as_ref(), self.copied);
+    }
+}
+
+fn render_score_token(
+    f: &mut Frame,
+    area: Rect,
+    click_state: &Rc<RefCell<ClickState>>,
+    game: &dyn Game,
+    copied: Option<bool>,
+) {
+    let theme = theme::active();
+    let choice = game.choice();
+    let borders = if is_narrow_layout(area.width) {
+        Borders::TOP | Borders::BOTTOM
+    } else {
+        Borders::ALL
+    };
+
+    let mut cl = ClickableList::new();
+    cl.push(Line::from(""));
+    cl.push(Line::from(Span::styled(
+        format!(" {}", choice.title()),
+        Style::default().fg(Color::White).add_modifier(Modifier::BOLD),
+    )));
+    if let Some(score) = game.score() {
+        cl.push(Line::from(vec![
+            Span::styled(format!(" {}: ", score.label), Style::default().fg(Color::Gray)),
+            Span::styled(
+                format!("{}{}", score.value, score.unit),
+                Style::default().fg(theme.primary).add_modifier(Modifier::BOLD),
+            ),
+        ]));
+        cl.push(Line::from(""));
+        cl.push(Line::from(Span::styled(
+            " スコアトークン:",
+            Style::default().fg(theme.dim),
+        )));
+        cl.push(Line::from(Span::styled(
+            format!(" {}", scores::encode_token(&choice, score.value)),
+            Style::default().fg(theme.accent).add_modifier(Modifier::BOLD),
+        )));
+    }
+    cl.push(Line::from(""));
+    cl.push_clickable(
+        Line::from(Span::styled(
+            " ▶ コピーする [c]",
+            Style::default().fg(Color::Green).add_modifier(Modifier::BOLD),
+        )),
+        SCORE_COPY,
+    );
+    cl.push(Line::from(match copied {
+        Some(true) => Span::styled(
+            " ✓ クリップボードにコピーしました",
+            Style::default().fg(Color::Green),
+        ),
+        Some(false) => Span::styled(
+            " ✕ コピーできません — 上の文字列を選択してコピーしてください",
+            Style::default().fg(Color::Red),
+        ),
+        None => Span::raw(""),
+    }));
+    cl.push(Line::from(""));
+    cl.push_clickable(
+        Line::from(Span::styled(
+            " ◀ ゲームに戻る [q]",
+            Style::default().fg(theme.dim),
+        )),
+        SCORE_CLOSE,
+    );
+    cl.push(Line::from(""));
+    cl.push(Line::from(Span::styled(
+        " ※ 表示中はゲームが一時停止します",
+        Style::default().fg(theme.dim),
+    )));
+
+    let block = Block::default()
+        .borders(borders)
+        .border_style(Style::default().fg(theme.accent))
+        .title(" スコア共有 ");
+    {
+        let mut cs = click_state.borrow_mut();
+        cl.render(f, area, block, &mut cs, false, 0);
+    }
+}
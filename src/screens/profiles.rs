@@ -0,0 +1,348 @@
+//! プロファイル一覧 (作成 / 名前変更 / 削除 / 切り替え)。
+
+use std::cell::RefCell;
+use std::rc::Rc;
+
+use ratzilla::ratatui::layout::{Alignment, Constraint, Direction, Layout, Rect};
+use ratzilla::ratatui::style::{Color, Modifier, Style};
+use ratzilla::ratatui::text::{Line, Span};
+use ratzilla::ratatui::widgets::{Block, Borders, Paragraph};
+use ratzilla::ratatui::Frame;
+
+use super::{AppState, MenuScreen, Screen, Transition};
+use crate::action::{assert_disjoint, ActionRange};
+use crate::input::{is_narrow_layout, ClickScope, ClickState, InputEvent};
+use crate::profile::{self, Profiles, MAX_PROFILES};
+use crate::widgets::ClickableList;
+use crate::{a11y, arcade, keybind, numfmt, sound, theme, toast, BACK_TO_MENU};
+
+// ── Profile action IDs (per-profile rows: profile index) ────────
+const PROFILE_SELECT: ActionRange = ActionRange::new(20, MAX_PROFILES as u16);
+const PROFILE_RENAME: ActionRange = ActionRange::new(30, MAX_PROFILES as u16);
+const PROFILE_DELETE: ActionRange = ActionRange::new(40, MAX_PROFILES as u16);
+const PROFILE_CREATE: u16 = 50;
+const PROFILE_CONFIRM_YES: u16 = 51;
+const PROFILE_CONFIRM_NO: u16 = 52;
+
+const _: () = assert_disjoint(&[
+    PROFILE_SELECT,
+    PROFILE_RENAME,
+    PROFILE_DELETE,
+    ActionRange::single(PROFILE_CREATE),
+    ActionRange::single(PROFILE_CONFIRM_YES),
+    ActionRange::single(PROFILE_CONFIRM_NO),
+]);
+
+/// Profile list.  `confirm_delete` is `Some(index)` while the delete
+/// dialog is open.
+pub struct ProfilesScreen {
+    pub profiles: Profiles,
+    pub confirm_delete: Option<usize>,
+}
+
+impl ProfilesScreen {
+    pub fn new(profiles: Profiles) -> Self {
+        Self {
+            profiles,
+            confirm_delete: None,
+        }
+    }
+
+    /// Switch to the profile at `index` and reload everything stored per
+    /// profile.  Returns the menu to show, or `None` for a bad index.
+    fn select(&mut self, index: usize) -> Option<Transition> {
+        if !self.profiles.select(index) {
+            return None;
+        }
+        sound::play(sound::SELECT);
+        profile::store(&self.profiles);
+        reload_profile_settings();
+        Some(Transition::To(AppState::Menu(MenuScreen::resuming())))
+    }
+}
+
+/// Reload everything kept in memory per profile after the active profile
+/// changed, so the next save doesn't write the old profile's settings
+/// under the new profile's keys.
+fn reload_profile_settings() {
+    keybind::load();
+    theme::load();
+    a11y::load();
+    numfmt::load();
+    arcade::load();
+    toast::clear();
+}
+
+impl Screen for ProfilesScreen {
+    fn scope(&self) -> ClickScope {
+        ClickScope::Profiles
+    }
+
+    fn handle(&mut self, event: &InputEvent) -> Transition {
+        let profiles = &mut self.profiles;
+        if let Some(index) = self.confirm_delete {
+            match event {
+                InputEvent::Key('y') | InputEvent::Click(_, PROFILE_CONFIRM_YES) => {
+                    let was_active = profile::active_id();
+                    if let Some(id) = profiles.delete(index) {
+                        profile::delete_data(id);
+                        profile::store(profiles);
+                        if id == was_active {
+                            reload_profile_settings();
+                        }
+                    }
+                    self.confirm_delete = None;
+                }
+                InputEvent::Key('n')
+                | InputEvent::Key('q')
+                | InputEvent::Click(_, PROFILE_CONFIRM_NO) => {
+                    self.confirm_delete = None;
+                }
+                _ => {}
+            }
+            return Transition::Stay;
+        }
+        match event {
+            InputEvent::Key(c @ '1'..='9') => {
+                if let Some(t) = self.select((*c as u8 - b'1') as usize) {
+                    return t;
+                }
+            }
+            InputEvent::Click(_, id) if PROFILE_SELECT.contains(*id) => {
+                let index = PROFILE_SELECT.index(*id).unwrap_or(0);
+                if let Some(t) = self.select(index) {
+                    return t;
+                }
+            }
+            InputEvent::Click(_, id) if PROFILE_RENAME.contains(*id) => {
+                let index = PROFILE_RENAME.index(*id).unwrap_or(0);
+                let current = profiles.list.get(index).map(|p| p.name.clone());
+                if let Some(current) = current {
+                    if let Some(name) = profile::prompt_name("新しい名前", &current) {
+                        if profiles.rename(index, &name) {
+                            profile::store(profiles);
+                        }
+                    }
+                }
+            }
+            InputEvent::Click(_, id) if PROFILE_DELETE.contains(*id) => {
+                let index = PROFILE_DELETE.index(*id).unwrap_or(0);
+                if profiles.list.len() > 1 && index < profiles.list.len() {
+                    self.confirm_delete = Some(index);
+                }
+            }
+            InputEvent::Key('a') | InputEvent::Click(_, PROFILE_CREATE) => {
+                if profiles.list.len() < MAX_PROFILES {
+                    let name = profile::prompt_name("プロファイル名", "");
+                    if profiles.create(name.as_deref()) {
+                        sound::play(sound::SELECT);
+                        profile::store(profiles);
+                    }
+                } else {
+                    sound::play(sound::ERROR);
+                }
+            }
+            InputEvent::Key('q') | InputEvent::Click(_, BACK_TO_MENU) => {
+                return Transition::To(AppState::Menu(MenuScreen::resuming()));
+            }
+            _ => {}
+        }
+        Transition::Stay
+    }
+
+    fn render(&mut self, f: &mut Frame, area: Rect, click_state: &Rc<RefCell<ClickState>>) {
+        render_profiles(f, area, click_state, &self.profiles, self.confirm_delete);
+    }
+}
+
+fn render_profiles(
+    f: &mut Frame,
+    area: Rect,
+    click_state: &Rc<RefCell<ClickState>>,
+    profiles: &Profiles,
+    confirm_delete: Option<usize>,
+) {
+    let theme = theme::active();
+    let is_narrow = is_narrow_layout(area.width);
+    let borders = if is_narrow {
+        Borders::TOP | Borders::BOTTOM
+    } else {
+        Borders::ALL
+    };
+
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Length(3), // Title
+            Constraint::Min(8),   // Content
+            Constraint::Length(3), // Footer
+        ])
+        .split(area);
+
+    let title_widget = Paragraph::new(Line::from(Span::styled(
+        "プロファイル選択",
+        Style::default()
+            .fg(theme.primary)
+            .add_modifier(Modifier::BOLD),
+    )))
+    .block(
+        Block::default()
+            .borders(borders)
+            .border_style(Style::default().fg(theme.primary)),
+    )
+    .alignment(Alignment::Center);
+    f.render_widget(title_widget, chunks[0]);
+
+    let mut cl = ClickableList::new();
+    if let Some(index) = confirm_delete {
+        let name = profiles.list.get(index).map(|p| p.name.as_str()).unwrap_or("");
+        cl.push(Line::from(""));
+        cl.push(Line::from(Span::styled(
+            format!(" 「{name}」を削除しますか？"),
+            Style::default()
+                .fg(theme.accent)
+                .add_modifier(Modifier::BOLD),
+        )));
+        cl.push(Line::from(Span::styled(
+            " ※ このプロファイルのセーブデータも消えます",
+            Style::default().fg(Color::Red),
+        )));
+        cl.push(Line::from(""));
+        cl.push_clickable(
+            Line::from(Span::styled(
+                " ▶ はい、削除する",
+                Style::default().fg(Color::Red).add_modifier(Modifier::BOLD),
+            )),
+            PROFILE_CONFIRM_YES,
+        );
+        cl.push(Line::from(""));
+        cl.push_clickable(
+            Line::from(Span::styled(" ▶ キャンセル", Style::default().fg(Color::Green))),
+            PROFILE_CONFIRM_NO,
+        );
+    } else {
+        let can_delete = profiles.list.len() > 1;
+        for (i, p) in profiles.list.iter().enumerate() {
+            let is_active = i == profiles.active;
+            let name_style = if is_active {
+                Style::default().fg(theme.accent).add_modifier(Modifier::BOLD)
+            } else {
+                Style::default().fg(Color::White)
+            };
+            cl.push(Line::from(""));
+            cl.push_clickable(
+                Line::from(vec![
+                    Span::styled(format!(" [{}] ", i + 1), Style::default().fg(theme.dim)),
+                    Span::styled(p.name.clone(), name_style),
+                    Span::styled(
+                        if is_active { " (使用中)" } else { "" },
+                        Style::default().fg(theme.dim),
+                    ),
+                ]),
+                PROFILE_SELECT.id(i),
+            );
+            cl.push_clickable(
+                Line::from(Span::styled(
+                    "     ✎ 名前を変更",
+                    Style::default().fg(theme.dim),
+                )),
+                PROFILE_RENAME.id(i),
+            );
+            if can_delete {
+                cl.push_clickable(
+                    Line::from(Span::styled("     ✕ 削除", Style::default().fg(Color::Red))),
+                    PROFILE_DELETE.id(i),
+                );
+            }
+        }
+        cl.push(Line::from(""));
+        if profiles.list.len() < MAX_PROFILES {
+            cl.push_clickable(
+                Line::from(Span::styled(
+                    " ＋ 新しいプロファイル [a]",
+                    Style::default().fg(Color::Green),
+                )),
+                PROFILE_CREATE,
+            );
+        } else {
+            cl.push(Line::from(Span::styled(
+                format!(" ※ プロファイルは{}件まで", MAX_PROFILES),
+                Style::default().fg(theme.dim),
+            )));
+        }
+    }
+
+    let block = Block::default()
+        .borders(borders)
+        .border_style(Style::default().fg(Color::Green))
+        .title(" Profiles ");
+    {
+        let mut cs = click_state.borrow_mut();
+        cl.render(f, chunks[1], block, &mut cs, false, 0);
+    }
+
+    let mut footer = ClickableList::new();
+    footer.push_clickable(
+        Line::from(Span::styled(
+            "◀ メニューへ",
+            Style::default().fg(theme.dim),
+        )),
+        BACK_TO_MENU,
+    );
+    let footer_block = Block::default()
+        .borders(borders)
+        .border_style(Style::default().fg(theme.dim));
+    {
+        let mut cs = click_state.borrow_mut();
+        footer.render(f, chunks[2], footer_block, &mut cs, false, 0);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn two_profiles_with_second_active() -> ProfilesScreen {
+        let mut profiles = Profiles::new();
+        assert!(profiles.create(Some("B")));
+        assert!(profiles.select(1));
+        profile::store(&profiles);
+        ProfilesScreen::new(profiles)
+    }
+
+    #[test]
+    fn deleting_the_active_profile_drops_its_arcade_wallet() {
+        let mut screen = two_profiles_with_second_active();
+        arcade::earn("test", 5);
+        assert_eq!(arcade::wallet().tokens, 5);
+
+        screen.confirm_delete = Some(1);
+        screen.handle(&InputEvent::Key('y'));
+        assert_eq!(profile::active_id(), screen.profiles.list[0].id);
+        assert_eq!(arcade::wallet().tokens, 0);
+        toast::clear();
+    }
+
+    #[test]
+    fn deleting_the_active_profile_reloads_its_settings() {
+        let mut screen = two_profiles_with_second_active();
+        theme::store(theme::Palette::HighContrast);
+        numfmt::store(numfmt::Notation::Scientific);
+
+        screen.confirm_delete = Some(1);
+        screen.handle(&InputEvent::Key('y'));
+        assert_eq!(theme::active().palette, theme::Palette::default());
+        assert_eq!(numfmt::active(), numfmt::Notation::default());
+    }
+
+    #[test]
+    fn deleting_another_profile_keeps_the_active_settings() {
+        let mut screen = two_profiles_with_second_active();
+        theme::store(theme::Palette::HighContrast);
+
+        screen.confirm_delete = Some(0);
+        screen.handle(&InputEvent::Key('y'));
+        assert_eq!(theme::active().palette, theme::Palette::HighContrast);
+        theme::store(theme::Palette::default());
+    }
+}
@@ -0,0 +1,584 @@
+//! 設定画面 (セーブデータのリセット・配色・読み上げ・各サブ画面への入口)。
+
+use std::cell::RefCell;
+use std::rc::Rc;
+
+use ratzilla::ratatui::layout::{Alignment, Constraint, Direction, Layout, Rect};
+use ratzilla::ratatui::style::{Color, Modifier, Style};
+use ratzilla::ratatui::text::{Line, Span};
+use ratzilla::ratatui::widgets::{Block, Borders, Paragraph};
+use ratzilla::ratatui::Frame;
+
+use super::{
+    AppState, ArcadeScreen, KeyBindingsScreen, MenuScreen, PlayStatsScreen, ProfilesScreen, Screen,
+    Transition,
+};
+use crate::games::GameChoice;
+use crate::input::{is_narrow_layout, ClickScope, ClickState, InputEvent};
+use crate::storage::TrashError;
+use crate::toast::{self, Severity};
+use crate::widgets::{ClickableList, Modal};
+use crate::{a11y, arcade, numfmt, playstats, profile, storage, theme, zoom, BACK_TO_MENU};
+
+// ── Settings action IDs ─────────────────────────────────────────
+const SETTINGS_RESET_COOKIE: u16 = 10;
+const SETTINGS_RESET_ABYSS: u16 = 11;
+const SETTINGS_RESET_METROPOLIS: u16 = 12;
+const SETTINGS_CONFIRM_YES: u16 = 13;
+const SETTINGS_CONFIRM_NO: u16 = 14;
+const SETTINGS_PROFILES: u16 = 17;
+const SETTINGS_RESET_DRILL: u16 = 18;
+const SETTINGS_RESET_PORT: u16 = 19;
+const SETTINGS_KEYBINDS: u16 = 15;
+const SETTINGS_PALETTE: u16 = 16;
+const SETTINGS_SCREEN_READER: u16 = 20;
+const SETTINGS_NOTATION: u16 = 21;
+const SETTINGS_PLAY_STATS: u16 = 22;
+const SETTINGS_ARCADE: u16 = 23;
+const SETTINGS_ZOOM_IN: u16 = 24;
+const SETTINGS_ZOOM_OUT: u16 = 25;
+const SETTINGS_UNDO_RESET: u16 = 26;
+
+/// セーブデータを持ち、設定画面からリセットできるゲーム。
+const RESETTABLE_GAMES: [GameChoice; 5] = [
+    GameChoice::Cookie,
+    GameChoice::Abyss,
+    GameChoice::Metropolis,
+    GameChoice::Drill,
+    GameChoice::Port,
+];
+
+/// Showing settings screen (data reset).
+#[derive(Default)]
+pub struct SettingsScreen {
+    /// `Some(game)` when a confirmation dialog is shown.
+    pub confirm_reset: Option<GameChoice>,
+}
+
+impl Screen for SettingsScreen {
+    fn scope(&self) -> ClickScope {
+        ClickScope::Settings
+    }
+
+    fn handle(&mut self, event: &InputEvent) -> Transition {
+        if let Some(game) = &self.confirm_reset {
+            // Confirmation dialog is active
+            match event {
+                InputEvent::Key('y') | InputEvent::Click(_, SETTINGS_CONFIRM_YES) => {
+                    perform_reset(game);
+                    self.confirm_reset = None;
+                }
+                InputEvent::Key('n')
+                | InputEvent::Key('q')
+                | InputEvent::Click(_, SETTINGS_CONFIRM_NO) => {
+                    self.confirm_reset = None;
+                }
+                _ => {}
+            }
+            return Transition::Stay;
+        }
+        match event {
+            InputEvent::Key('1') | InputEvent::Click(_, SETTINGS_RESET_COOKIE) => {
+                self.confirm_reset = Some(GameChoice::Cookie);
+            }
+            InputEvent::Key('2') | InputEvent::Click(_, SETTINGS_RESET_ABYSS) => {
+                self.confirm_reset = Some(GameChoice::Abyss);
+            }
+            InputEvent::Key('3') | InputEvent::Click(_, SETTINGS_RESET_METROPOLIS) => {
+                self.confirm_reset = Some(GameChoice::Metropolis);
+            }
+            InputEvent::Key('4') | InputEvent::Click(_, SETTINGS_PROFILES) => {
+                return Transition::To(AppState::Profiles(ProfilesScreen::new(profile::load())));
+            }
+            InputEvent::Key('5') | InputEvent::Click(_, SETTINGS_RESET_DRILL) => {
+                self.confirm_reset = Some(GameChoice::Drill);
+            }
+            InputEvent::Key('6') | InputEvent::Click(_, SETTINGS_RESET_PORT) => {
+                self.confirm_reset = Some(GameChoice::Port);
+            }
+            InputEvent::Key('8') | InputEvent::Click(_, SETTINGS_PALETTE) => {
+                theme::store(theme::active().palette.next());
+            }
+            InputEvent::Key('9') | InputEvent::Click(_, SETTINGS_SCREEN_READER) => {
+                a11y::store(!a11y::enabled());
+            }
+            InputEvent::Key('0') | InputEvent::Click(_, SETTINGS_NOTATION) => {
+                numfmt::store(numfmt::active().next());
+            }
+            InputEvent::Key('+') | InputEvent::Click(_, SETTINGS_ZOOM_IN) => {
+                zoom::zoom_by(1);
+            }
+            InputEvent::Key('-') | InputEvent::Click(_, SETTINGS_ZOOM_OUT) => {
+                zoom::zoom_by(-1);
+            }
+            InputEvent::Key('u') | InputEvent::Click(_, SETTINGS_UNDO_RESET) => {
+                undo_last_reset(playstats::now_ms());
+            }
+            InputEvent::Key('7') | InputEvent::Click(_, SETTINGS_KEYBINDS) => {
+                return Transition::To(AppState::KeyBindings(KeyBindingsScreen::default()));
+            }
+            InputEvent::Key('s') | InputEvent::Click(_, SETTINGS_PLAY_STATS) => {
+                return Transition::To(AppState::PlayStats(PlayStatsScreen::default()));
+            }
+            InputEvent::Key('a') | InputEvent::Click(_, SETTINGS_ARCADE) => {
+                return Transition::To(AppState::Arcade(ArcadeScreen::default()));
+            }
+            InputEvent::Key('q') | InputEvent::Click(_, BACK_TO_MENU) => {
+                return Transition::To(AppState::Menu(MenuScreen::new(None)));
+            }
+            _ => {}
+        }
+        Transition::Stay
+    }
+
+    fn render(&mut self, f: &mut Frame, area: Rect, click_state: &Rc<RefCell<ClickState>>) {
+        render_settings(f, area, click_state, self.confirm_reset.as_ref());
+    }
+}
+
+fn render_settings(
+    f: &mut Frame,
+    area: Rect,
+    click_state: &Rc<RefCell<ClickState>>,
+    confirm_reset: Option<&GameChoice>,
+) {
+    let theme = theme::active();
+    let is_narrow = is_narrow_layout(area.width);
+    let borders = if is_narrow {
+        Borders::TOP | Borders::BOTTOM
+    } else {
+        Borders::ALL
+    };
+
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Length(3), // Title
+            Constraint::Min(8),   // Content
+            Constraint::Length(3), // Footer
+        ])
+        .split(area);
+
+    // Title
+    let title_widget = Paragraph::new(Line::from(Span::styled(
+        "設定",
+        Style::default()
+            .fg(theme.primary)
+            .add_modifier(Modifier::BOLD),
+    )))
+    .block(
+        Block::default()
+            .borders(borders)
+            .border_style(Style::default().fg(theme.primary)),
+    )
+    .alignment(Alignment::Center);
+    f.render_widget(title_widget, chunks[0]);
+
+    render_settings_main(f, chunks[1], click_state, borders);
+
+    // Footer — back to menu
+    let mut cl = ClickableList::new();
+    cl.push_clickable(
+        Line::from(Span::styled(
+            "◀ メニューに戻る",
+            Style::default().fg(theme.dim),
+        )),
+        BACK_TO_MENU,
+    );
+    let footer_block = Block::default()
+        .borders(borders)
+        .border_style(Style::default().fg(theme.dim));
+    {
+        let mut cs = click_state.borrow_mut();
+        cl.render(f, chunks[2], footer_block, &mut cs, false, 0);
+    }
+
+    // Drawn last so it dims the whole screen and takes over its taps.
+    if let Some(game) = confirm_reset {
+        render_confirm_dialog(f, area, click_state, game);
+    }
+}
+
+fn render_settings_main(
+    f: &mut Frame,
+    area: Rect,
+    click_state: &Rc<RefCell<ClickState>>,
+    borders: Borders,
+) {
+    let theme = theme::active();
+    let mut cl = ClickableList::new();
+
+    cl.push(Line::from(""));
+    cl.push(Line::from(Span::styled(
+        " セーブデータ管理",
+        Style::default()
+            .fg(theme.accent)
+            .add_modifier(Modifier::BOLD),
+    )));
+    cl.push(Line::from(""));
+
+    // Cookie Factory
+    cl.push_clickable(
+        Line::from(vec![
+            Span::styled(" ✕ ", Style::default().fg(theme.danger)),
+            Span::styled("Cookie Factory", Style::default().fg(Color::White)),
+            Span::styled(" — データをリセット", Style::default().fg(theme.dim)),
+        ]),
+        SETTINGS_RESET_COOKIE,
+    );
+
+    cl.push(Line::from(""));
+
+    // 深淵潜行 (Abyss Idle)
+    cl.push_clickable(
+        Line::from(vec![
+            Span::styled(" ✕ ", Style::default().fg(theme.danger)),
+            Span::styled("深淵潜行", Style::default().fg(Color::White)),
+            Span::styled(" — データをリセット", Style::default().fg(theme.dim)),
+        ]),
+        SETTINGS_RESET_ABYSS,
+    );
+
+    cl.push(Line::from(""));
+
+    // Idle Metropolis
+    cl.push_clickable(
+        Line::from(vec![
+            Span::styled(" ✕ ", Style::default().fg(theme.danger)),
+            Span::styled("Idle Metropolis", Style::default().fg(Color::White)),
+            Span::styled(" — データをリセット", Style::default().fg(theme.dim)),
+        ]),
+        SETTINGS_RESET_METROPOLIS,
+    );
+
+    cl.push(Line::from(""));
+
+    // Deep Drill
+    cl.push_clickable(
+        Line::from(vec![
+            Span::styled(" ✕ ", Style::default().fg(theme.danger)),
+            Span::styled("Deep Drill", Style::default().fg(Color::White)),
+            Span::styled(" — データをリセット", Style::default().fg(theme.dim)),
+        ]),
+        SETTINGS_RESET_DRILL,
+    );
+
+    cl.push(Line::from(""));
+
+    // Port Trader
+    cl.push_clickable(
+        Line::from(vec![
+            Span::styled(" ✕ ", Style::default().fg(theme.danger)),
+            Span::styled("Port Trader", Style::default().fg(Color::White)),
+            Span::styled(" — データをリセット", Style::default().fg(theme.dim)),
+        ]),
+        SETTINGS_RESET_PORT,
+    );
+
+    let now = playstats::now_ms();
+    if let Some((game, at)) = last_reset(now) {
+        let elapsed = now.saturating_sub(at);
+        let hours_left = storage::TRASH_TTL_MS.saturating_sub(elapsed).div_ceil(60 * 60 * 1000);
+        cl.push(Line::from(""));
+        cl.push_clickable(
+            Line::from(vec![
+                Span::styled(" ↩ ", Style::default().fg(theme.good)),
+                Span::styled(
+                    format!("{} のリセットを取り消す", game_name(&game)),
+                    Style::default().fg(Color::White),
+                ),
+                Span::styled(
+                    format!(" [u] (残り {hours_left}時間)"),
+                    Style::default().fg(theme.dim),
+                ),
+            ]),
+            SETTINGS_UNDO_RESET,
+        );
+    }
+
+    cl.push(Line::from(""));
+    cl.push(Line::from(""));
+    cl.push_clickable(
+        Line::from(vec![
+            Span::styled(" ⌨ ", Style::default().fg(theme.primary)),
+            Span::styled("キー設定", Style::default().fg(Color::White)),
+            Span::styled(" — 移動/決定/戻るの割り当て", Style::default().fg(theme.dim)),
+        ]),
+        SETTINGS_KEYBINDS,
+    );
+    cl.push(Line::from(""));
+    cl.push_clickable(
+        Line::from(vec![
+            Span::styled(" 🎨 ", Style::default().fg(theme.primary)),
+            Span::styled("配色", Style::default().fg(Color::White)),
+            Span::styled(
+                format!(": {}", theme.palette.label()),
+                Style::default().fg(theme.accent),
+            ),
+            Span::styled(" — タップで切替 (標準/色覚サポート/高コントラスト/ダークブルー/モノクロ)", Style::default().fg(theme.dim)),
+        ]),
+        SETTINGS_PALETTE,
+    );
+    cl.push(Line::from(""));
+    cl.push_clickable(
+        Line::from(vec![
+            Span::styled(" 🔈 ", Style::default().fg(theme.primary)),
+            Span::styled("読み上げ", Style::default().fg(Color::White)),
+            Span::styled(
+                if a11y::enabled() { ": ON" } else { ": OFF" },
+                Style::default().fg(theme.accent),
+            ),
+            Span::styled(" — 通知やログをスクリーンリーダーへ", Style::default().fg(theme.dim)),
+        ]),
+        SETTINGS_SCREEN_READER,
+    );
+    cl.push(Line::from(""));
+    cl.push_clickable(
+        Line::from(vec![
+            Span::styled(" 🔢 ", Style::default().fg(theme.primary)),
+            Span::styled("数の表記", Style::default().fg(Color::White)),
+            Span::styled(
+                format!(": {}", numfmt::active().label()),
+                Style::default().fg(theme.accent),
+            ),
+            Span::styled(
+                format!(" — 例 {} (桁区切り/万・億・兆/K・M・B/指数)", numfmt::format(123_456_789.0)),
+                Style::default().fg(theme.dim),
+            ),
+        ]),
+        SETTINGS_NOTATION,
+    );
+    cl.push(Line::from(""));
+    cl.push(Line::from(vec![
+        Span::styled(" 🔍 ", Style::default().fg(theme.primary)),
+        Span::styled("文字の大きさ", Style::default().fg(Color::White)),
+        Span::styled(format!(": {}%", zoom::active()), Style::default().fg(theme.accent)),
+        Span::styled(" — 2 本指のピンチでも変えられます", Style::default().fg(theme.dim)),
+    ]));
+    let zoom_button = |label: &'static str, key: char, enabled: bool| {
+        let color = if enabled { Color::White } else { theme.dim };
+        Line::from(vec![
+            Span::styled(format!("    {label}"), Style::default().fg(color)),
+            Span::styled(format!(" [{key}]"), Style::default().fg(theme.dim)),
+        ])
+    };
+    let current = zoom::active();
+    cl.push_clickable(
+        zoom_button("＋ 大きく", '+', zoom::step(current, 1) != current),
+        SETTINGS_ZOOM_IN,
+    );
+    cl.push_clickable(
+        zoom_button("− 小さく", '-', zoom::step(current, -1) != current),
+        SETTINGS_ZOOM_OUT,
+    );
+    cl.push(Line::from(""));
+    cl.push_clickable(
+        Line::from(vec![
+            Span::styled(" 👤 ", Style::default().fg(theme.primary)),
+            Span::styled("プロファイル管理", Style::default().fg(Color::White)),
+            Span::styled(" — 作成/名前変更/削除", Style::default().fg(theme.dim)),
+        ]),
+        SETTINGS_PROFILES,
+    );
+    cl.push(Line::from(""));
+    cl.push_clickable(
+        Line::from(vec![
+            Span::styled(" 📊 ", Style::default().fg(theme.primary)),
+            Span::styled("プレイ統計", Style::default().fg(Color::White)),
+            Span::styled(" — ゲームごとのプレイ時間/回数", Style::default().fg(theme.dim)),
+        ]),
+        SETTINGS_PLAY_STATS,
+    );
+    cl.push(Line::from(""));
+    cl.push_clickable(
+        Line::from(vec![
+            Span::styled(" 🪙 ", Style::default().fg(theme.primary)),
+            Span::styled("アーケード交換所", Style::default().fg(Color::White)),
+            Span::styled(
+                format!(": {} 枚", arcade::wallet().tokens),
+                Style::default().fg(theme.accent),
+            ),
+            Span::styled(" — 全ゲーム共通のトークンを交換", Style::default().fg(theme.dim)),
+        ]),
+        SETTINGS_ARCADE,
+    );
+
+    cl.push(Line::from(""));
+    cl.push(Line::from(Span::styled(
+        " ※ Tiny Factory / Dungeon Dive / God Field / Grid Defense は",
+        Style::default().fg(theme.dim),
+    )));
+    cl.push(Line::from(Span::styled(
+        "   セーブデータがありません",
+        Style::default().fg(theme.dim),
+    )));
+
+    let block = Block::default()
+        .borders(borders)
+        .border_style(Style::default().fg(Color::Green))
+        .title(" Data Reset ");
+    {
+        let mut cs = click_state.borrow_mut();
+        cl.render(f, area, block, &mut cs, false, 0);
+    }
+}
+
+fn render_confirm_dialog(
+    f: &mut Frame,
+    area: Rect,
+    click_state: &Rc<RefCell<ClickState>>,
+    game: &GameChoice,
+) {
+    let game_name = game_name(game);
+
+    let body = vec![
+        Line::from(Span::styled(
+            format!(" {game_name} のセーブデータを"),
+            Style::default().fg(Color::White),
+        )),
+        Line::from(Span::styled(
+            " 本当にリセットしますか？",
+            Style::default()
+                .fg(Color::Yellow)
+                .add_modifier(Modifier::BOLD),
+        )),
+        Line::from(""),
+        Line::from(Span::styled(
+            " ※ 24時間以内ならこの画面で取り消せます",
+            Style::default().fg(Color::Red),
+        )),
+    ];
+
+    let mut cs = click_state.borrow_mut();
+    Modal::new(" 確認 ")
+        .border_color(Color::Red)
+        .body(body)
+        .button(
+            Span::styled(
+                "はい、リセットする",
+                Style::default().fg(Color::Red).add_modifier(Modifier::BOLD),
+            ),
+            SETTINGS_CONFIRM_YES,
+        )
+        .button(
+            Span::styled("キャンセル", Style::default().fg(Color::Green)),
+            SETTINGS_CONFIRM_NO,
+        )
+        .backdrop(SETTINGS_CONFIRM_NO)
+        .render(f, area, &mut cs);
+}
+
+fn game_name(game: &GameChoice) -> &'static str {
+    match game {
+        GameChoice::Cookie => "Cookie Factory",
+        GameChoice::Abyss => "深淵潜行",
+        GameChoice::Metropolis => "Idle Metropolis",
+        GameChoice::Drill => "Deep Drill",
+        GameChoice::Port => "Port Trader",
+        _ => "Unknown",
+    }
+}
+
+/// ゲームのセーブの置き場のキー (現在のプロファイル)。セーブが無いゲームは `None`。
+fn save_key(game: &GameChoice) -> Option<String> {
+    match game {
+        GameChoice::Cookie => Some(crate::games::cookie::save::storage_key()),
+        GameChoice::Abyss => Some(crate::games::abyss::save::storage_key()),
+        GameChoice::Metropolis => Some(crate::games::metropolis::save::storage_key()),
+        GameChoice::Drill => Some(crate::games::drill::save::storage_key()),
+        GameChoice::Port => Some(crate::games::port::save::storage_key()),
+        _ => None,
+    }
+}
+
+/// Move the game's save into the trash so the reset can be undone.
+/// If the trash can't be written the reset is called off and the save kept,
+/// so nothing is ever deleted without a way back.
+fn perform_reset(game: &GameChoice) {
+    let Some(key) = save_key(game) else {
+        return;
+    };
+    let name = game_name(game);
+    match storage::move_to_trash(&key, playstats::now_ms()) {
+        Ok(()) => toast::push(
+            Severity::Success,
+            format!("{name} をリセットしました (24時間以内なら取り消せます)"),
+        ),
+        Err(TrashError::Empty) => {
+            toast::push(Severity::Info, format!("{name} のセーブデータはありません"))
+        }
+        Err(TrashError::WriteFailed) => toast::push(
+            Severity::Danger,
+            format!("{name} のセーブを退避できないため、リセットを中止しました"),
+        ),
+    }
+}
+
+/// 取り消せるリセットのうち一番新しいもの (ゲームと退避時刻)。
+fn last_reset(now_ms: u64) -> Option<(GameChoice, u64)> {
+    RESETTABLE_GAMES
+        .into_iter()
+        .filter_map(|g| {
+            let at = storage::trashed_at(&save_key(&g)?, now_ms)?;
+            Some((g, at))
+        })
+        .max_by_key(|&(_, at)| at)
+}
+
+/// 一番新しいリセットを取り消す。今そのゲームにあるセーブは上書きされる。
+fn undo_last_reset(now_ms: u64) {
+    let Some((game, _)) = last_reset(now_ms) else {
+        return;
+    };
+    let restored = save_key(&game).is_some_and(|key| storage::restore_from_trash(&key, now_ms));
+    if restored {
+        toast::push(
+            Severity::Success,
+            format!("{} のセーブを元に戻しました", game_name(&game)),
+        );
+    } else {
+        toast::push(Severity::Danger, "セーブを元に戻せませんでした");
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn reset_can_be_undone_from_the_settings_screen() {
+        let store = storage::get().unwrap();
+        let key = save_key(&GameChoice::Drill).unwrap();
+        store.set_item(&key, "drill-save").unwrap();
+
+        let mut screen = SettingsScreen::default();
+        screen.handle(&InputEvent::Key('5'));
+        screen.handle(&InputEvent::Key('y'));
+        assert_eq!(store.get_item(&key).unwrap(), None);
+        assert!(matches!(last_reset(0), Some((GameChoice::Drill, 0))));
+
+        screen.handle(&InputEvent::Click(ClickScope::Settings, SETTINGS_UNDO_RESET));
+        assert_eq!(store.get_item(&key).unwrap().as_deref(), Some("drill-save"));
+        assert!(last_reset(0).is_none());
+        store.remove_item(&key).unwrap();
+        toast::clear();
+    }
+
+    #[test]
+    fn reset_without_a_save_offers_no_undo() {
+        let store = storage::get().unwrap();
+        let key = save_key(&GameChoice::Port).unwrap();
+        store.remove_item(&key).unwrap();
+        toast::clear();
+
+        let mut screen = SettingsScreen::default();
+        screen.handle(&InputEvent::Key('6'));
+        screen.handle(&InputEvent::Key('y'));
+        assert!(screen.confirm_reset.is_none());
+        assert!(last_reset(0).is_none());
+        assert_eq!(store.get_item(&storage::trash_key(&key)).unwrap(), None);
+        let shown = toast::snapshot();
+        assert_eq!(shown.last().map(|t| t.severity), Some(Severity::Info));
+        toast::clear();
+    }
+}
@@ -0,0 +1,227 @@
+//! プレイ統計画面 (設定から開く)。ゲームごとのプレイ時間・起動回数・
+//! 最後に遊んだ日時を一覧し、統計だけをリセットできる。
+
+use std::cell::RefCell;
+use std::rc::Rc;
+
+use ratzilla::ratatui::layout::{Alignment, Constraint, Direction, Layout, Rect};
+use ratzilla::ratatui::style::{Color, Modifier, Style};
+use ratzilla::ratatui::text::{Line, Span};
+use ratzilla::ratatui::widgets::{Block, Borders, Paragraph};
+use ratzilla::ratatui::Frame;
+
+use super::{AppState, Screen, SettingsScreen, Transition};
+use crate::action::{assert_disjoint, ActionRange};
+use crate::games::GameChoice;
+use crate::input::{is_narrow_layout, ClickScope, ClickState, InputEvent};
+use crate::playstats::{self, PlayStats};
+use crate::widgets::{ClickableList, Modal};
+use crate::{sound, theme, BACK_TO_MENU};
+
+// ── Play stats action IDs (Settings scope) ──────────────────────
+const STATS_RESET: u16 = 50;
+const STATS_CONFIRM_YES: u16 = 51;
+const STATS_CONFIRM_NO: u16 = 52;
+
+const _: () = assert_disjoint(&[
+    ActionRange::single(STATS_RESET),
+    ActionRange::single(STATS_CONFIRM_YES),
+    ActionRange::single(STATS_CONFIRM_NO),
+]);
+
+/// Play stats screen.
+#[derive(Default)]
+pub struct PlayStatsScreen {
+    pub stats: PlayStats,
+    /// リセット確認ダイアログを表示中か。
+    pub confirm_reset: bool,
+}
+
+impl Screen for PlayStatsScreen {
+    fn scope(&self) -> ClickScope {
+        ClickScope::Settings
+    }
+
+    fn enter(&mut self) {
+        self.stats = playstats::load();
+    }
+
+    fn handle(&mut self, event: &InputEvent) -> Transition {
+        if self.confirm_reset {
+            match event {
+                InputEvent::Key('y') | InputEvent::Click(_, STATS_CONFIRM_YES) => {
+                    sound::play(sound::SELECT);
+                    playstats::reset();
+                    self.stats = PlayStats::default();
+                    self.confirm_reset = false;
+                }
+                InputEvent::Key('n')
+                | InputEvent::Key('q')
+                | InputEvent::Click(_, STATS_CONFIRM_NO) => {
+                    self.confirm_reset = false;
+                }
+                _ => {}
+            }
+            return Transition::Stay;
+        }
+        match event {
+            InputEvent::Key('r') | InputEvent::Click(_, STATS_RESET) => {
+                self.confirm_reset = true;
+            }
+            InputEvent::Key('q') | InputEvent::Click(_, BACK_TO_MENU) => {
+                return Transition::To(AppState::Settings(SettingsScreen::default()));
+            }
+            _ => {}
+        }
+        Transition::Stay
+    }
+
+    fn render(&mut self, f: &mut Frame, area: Rect, click_state: &Rc<RefCell<ClickState>>) {
+        render_play_stats(f, area, click_state, &self.stats, self.confirm_reset);
+    }
+}
+
+fn render_play_stats(
+    f: &mut Frame,
+    area: Rect,
+    click_state: &Rc<RefCell<ClickState>>,
+    stats: &PlayStats,
+    confirm_reset: bool,
+) {
+    let theme = theme::active();
+    let is_narrow = is_narrow_layout(area.width);
+    let borders = if is_narrow {
+        Borders::TOP | Borders::BOTTOM
+    } else {
+        Borders::ALL
+    };
+
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Length(3), // Title
+            Constraint::Min(8),   // Content
+            Constraint::Length(3), // Footer
+        ])
+        .split(area);
+
+    let title_widget = Paragraph::new(Line::from(Span::styled(
+        "プレイ統計",
+        Style::default()
+            .fg(theme.primary)
+            .add_modifier(Modifier::BOLD),
+    )))
+    .block(
+        Block::default()
+            .borders(borders)
+            .border_style(Style::default().fg(theme.primary)),
+    )
+    .alignment(Alignment::Center);
+    f.render_widget(title_widget, chunks[0]);
+
+    let now = playstats::now_ms();
+    let mut cl = ClickableList::new();
+    cl.push(Line::from(""));
+    cl.push(Line::from(vec![
+        Span::styled(" 合計プレイ時間 ", Style::default().fg(theme.dim)),
+        Span::styled(
+            playstats::format_duration(stats.total_play_ms()),
+            Style::default().fg(theme.accent).add_modifier(Modifier::BOLD),
+        ),
+    ]));
+    cl.push(Line::from(""));
+    for choice in GameChoice::all() {
+        let s = stats.get(&choice);
+        let played = s.sessions > 0;
+        let name_style = if played {
+            Style::default().fg(Color::White).add_modifier(Modifier::BOLD)
+        } else {
+            Style::default().fg(theme.dim)
+        };
+        cl.push(Line::from(Span::styled(format!(" {}", choice.title()), name_style)));
+        if played {
+            cl.push(Line::from(vec![
+                Span::styled("   ⏱ ", Style::default().fg(theme.dim)),
+                Span::styled(playstats::format_duration(s.play_ms), Style::default().fg(theme.primary)),
+                Span::styled(format!("  ▶ {}回", s.sessions), Style::default().fg(Color::White)),
+                Span::styled(
+                    format!("  最終 {}", playstats::format_ago(s.last_played_ms, now)),
+                    Style::default().fg(theme.dim),
+                ),
+            ]));
+        } else {
+            cl.push(Line::from(Span::styled("   まだ遊んでいません", Style::default().fg(theme.dim))));
+        }
+    }
+    cl.push(Line::from(""));
+    cl.push_clickable(
+        Line::from(vec![
+            Span::styled(" ✕ ", Style::default().fg(theme.danger)),
+            Span::styled("統計をリセット [r]", Style::default().fg(Color::White)),
+            Span::styled(" — セーブデータは消えません", Style::default().fg(theme.dim)),
+        ]),
+        STATS_RESET,
+    );
+
+    let block = Block::default()
+        .borders(borders)
+        .border_style(Style::default().fg(Color::Green))
+        .title(" Play Stats ");
+    {
+        let mut cs = click_state.borrow_mut();
+        cl.render(f, chunks[1], block, &mut cs, false, 0);
+    }
+
+    let mut footer = ClickableList::new();
+    footer.push_clickable(
+        Line::from(Span::styled(
+            "◀ 設定に戻る",
+            Style::default().fg(theme.dim),
+        )),
+        BACK_TO_MENU,
+    );
+    let footer_block = Block::default()
+        .borders(borders)
+        .border_style(Style::default().fg(theme.dim));
+    {
+        let mut cs = click_state.borrow_mut();
+        footer.render(f, chunks[2], footer_block, &mut cs, false, 0);
+    }
+
+    if confirm_reset {
+        let body = vec![
+            Line::from(Span::styled(
+                " 全ゲームのプレイ統計を",
+                Style::default().fg(Color::White),
+            )),
+            Line::from(Span::styled(
+                " 本当にリセットしますか？",
+                Style::default()
+                    .fg(Color::Yellow)
+                    .add_modifier(Modifier::BOLD),
+            )),
+            Line::from(""),
+            Line::from(Span::styled(
+                " ※ セーブデータはそのまま残ります",
+                Style::default().fg(theme.dim),
+            )),
+        ];
+        let mut cs = click_state.borrow_mut();
+        Modal::new(" 確認 ")
+            .border_color(Color::Red)
+            .body(body)
+            .button(
+                Span::styled(
+                    "はい、リセットする",
+                    Style::default().fg(Color::Red).add_modifier(Modifier::BOLD),
+                ),
+                STATS_CONFIRM_YES,
+            )
+            .button(
+                Span::styled("キャンセル", Style::default().fg(Color::Green)),
+                STATS_CONFIRM_NO,
+            )
+            .backdrop(STATS_CONFIRM_NO)
+            .render(f, area, &mut cs);
+    }
+}
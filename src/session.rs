@@ -0,0 +1,146 @@
+//! アプリシェルのセッション情報 (ゲームをまたぐ永続状態)。
+//!
+//! - 最後にプレイしていたゲーム: ゲーム開始時に記録し、メニューへ戻った
+//!   時点で消す。つまりタブを閉じた瞬間にプレイ中だった場合だけフラグが
+//!   残り、次回起動時のメニューで「続きから再開しますか？」プロンプトを
+//!   出す根拠になる。
+//! - お気に入り: メニューの先頭に固定するゲーム。残りは最後に遊んだ順
+//!   ([`crate::playstats`]) に並べる ([`menu_order`])。
+//!
+//! キーは [`crate::profile::scoped_key`] でプロファイルごとに分ける。
+//!
+//! localStorage は WASM ビルドでのみ触る。native (cargo test) では
+//! 読み込みは常に `None`、書き込みは no-op。
+
+use crate::games::GameChoice;
+use crate::playstats::PlayStats;
+#[cfg(target_arch = "wasm32")]
+use crate::profile;
+
+/// localStorage のキー。値は [`GameChoice::id`] の文字列。
+#[cfg(target_arch = "wasm32")]
+const LAST_PLAYED_KEY: &str = "app_last_played";
+
+/// localStorage のキー。値は [`format_favorites`] の文字列。
+#[cfg(target_arch = "wasm32")]
+const FAVORITES_KEY: &str = "app_favorites";
+
+#[cfg(target_arch = "wasm32")]
+fn get_storage() -> Option<crate::storage::Store> {
+    crate::storage::get()
+}
+
+/// 前回セッション終了時にプレイ中だったゲームを返す。
+/// 未記録・不明な ID・storage 不可の場合は `None`。
+pub fn load_last_played() -> Option<GameChoice> {
+    #[cfg(target_arch = "wasm32")]
+    {
+        let id = get_storage()?.get_item(&profile::scoped_key(LAST_PLAYED_KEY)).ok()??;
+        GameChoice::from_id(&id)
+    }
+    #[cfg(not(target_arch = "wasm32"))]
+    None
+}
+
+/// プレイ中のゲームを記録する。`None` で記録を消す (メニューに戻った時)。
+/// 失敗してもプロンプトが出ないだけなので握りつぶす。
+pub fn store_last_played(choice: Option<&GameChoice>) {
+    #[cfg(target_arch = "wasm32")]
+    if let Some(storage) = get_storage() {
+        let _ = match choice {
+            Some(c) => storage.set_item(&profile::scoped_key(LAST_PLAYED_KEY), c.id()),
+            None => storage.remove_item(&profile::scoped_key(LAST_PLAYED_KEY)),
+        };
+    }
+    #[cfg(not(target_arch = "wasm32"))]
+    let _ = choice;
+}
+
+/// お気に入りを保存用の文字列にする (`"rpg,cookie"`)。
+pub fn format_favorites(favorites: &[GameChoice]) -> String {
+    favorites.iter().map(GameChoice::id).collect::<Vec<_>>().join(",")
+}
+
+/// [`format_favorites`] の逆。不明な ID と重複は読み飛ばす。
+pub fn parse_favorites(s: &str) -> Vec<GameChoice> {
+    let mut favorites = Vec::new();
+    for choice in s.split(',').filter_map(|id| GameChoice::from_id(id.trim())) {
+        if !favorites.contains(&choice) {
+            favorites.push(choice);
+        }
+    }
+    favorites
+}
+
+/// お気に入りに固定したゲーム。未保存・storage 不可なら空。
+pub fn load_favorites() -> Vec<GameChoice> {
+    #[cfg(target_arch = "wasm32")]
+    {
+        get_storage()
+            .and_then(|s| s.get_item(&profile::scoped_key(FAVORITES_KEY)).ok().flatten())
+            .map(|s| parse_favorites(&s))
+            .unwrap_or_default()
+    }
+    #[cfg(not(target_arch = "wasm32"))]
+    Vec::new()
+}
+
+/// お気に入りを保存する。失敗しても並びが戻るだけなので握りつぶす。
+pub fn store_favorites(favorites: &[GameChoice]) {
+    #[cfg(target_arch = "wasm32")]
+    if let Some(storage) = get_storage() {
+        let _ = storage.set_item(&profile::scoped_key(FAVORITES_KEY), &format_favorites(favorites));
+    }
+    #[cfg(not(target_arch = "wasm32"))]
+    let _ = favorites;
+}
+
+/// メニューに並べる順。お気に入りが先、その中と残りはそれぞれ最後に
+/// 遊んだのが新しい順。遊んだことのないゲームは [`GameChoice::all`] の順で末尾。
+pub fn menu_order(favorites: &[GameChoice], stats: &PlayStats) -> Vec<GameChoice> {
+    let mut order = GameChoice::all().to_vec();
+    // 安定ソートなので同着は元の順のまま
+    order.sort_by_key(|c| {
+        (
+            !favorites.contains(c),
+            std::cmp::Reverse(stats.get(c).last_played_ms),
+        )
+    });
+    order
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn favorites_roundtrip_and_skip_junk() {
+        let favorites = vec![GameChoice::Rpg, GameChoice::Cookie];
+        assert_eq!(format_favorites(&favorites), "rpg,cookie");
+        assert_eq!(parse_favorites("rpg,cookie"), favorites);
+        assert_eq!(parse_favorites("rpg,career,,rpg, cookie"), favorites);
+        assert!(parse_favorites("").is_empty());
+    }
+
+    #[test]
+    fn menu_puts_favorites_first_then_recent() {
+        assert_eq!(menu_order(&[], &PlayStats::default()), GameChoice::all().to_vec());
+
+        let mut stats = PlayStats::default();
+        stats.start_session(&GameChoice::Port, 100);
+        stats.start_session(&GameChoice::Drill, 300);
+        stats.start_session(&GameChoice::Defense, 200);
+        let order = menu_order(&[GameChoice::Abyss, GameChoice::Defense], &stats);
+        assert_eq!(
+            order[..5],
+            [
+                GameChoice::Defense,
+                GameChoice::Abyss,
+                GameChoice::Drill,
+                GameChoice::Port,
+                GameChoice::Cookie,
+            ]
+        );
+        assert_eq!(order.len(), GameChoice::all().len());
+    }
+}
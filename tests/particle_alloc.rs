@@ -0,0 +1,107 @@
+//! Cookie Factory のパーティクルが定常状態でヒープ確保しないことを検証する。
+//!
+//! 連打で毎フレーム `Particle` を作り捨てると、そのたびに `String` の確保・
+//! 解放が走る。`ParticlePool` は上限付きのリングで、捨てたパーティクルの
+//! 文字列バッファを `text_buf` で使い回す。ここでは確保回数を数える
+//! アロケータを差し込み、暖機後の「生成 → 寿命切れ」の繰り返しで確保が
+//! 0 回であることを確かめる (このファイル専用のテストバイナリなので、
+//! 他のテストには影響しない)。
+
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::borrow::Cow;
+use std::cell::Cell;
+
+use cli_sim_game_escape::games::cookie::logic::spawn_celebration;
+use cli_sim_game_escape::games::cookie::state::{
+    CookieState, Particle, ParticlePanel, ParticlePool, ParticleStyle, MAX_PARTICLES,
+};
+
+struct CountingAlloc;
+
+thread_local! {
+    // テストは並列に走るので、数えるのは自スレッドの確保だけ。
+    static ALLOCS: Cell<usize> = const { Cell::new(0) };
+}
+
+unsafe impl GlobalAlloc for CountingAlloc {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        let _ = ALLOCS.try_with(|c| c.set(c.get() + 1));
+        System.alloc(layout)
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        System.dealloc(ptr, layout)
+    }
+
+    unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+        let _ = ALLOCS.try_with(|c| c.set(c.get() + 1));
+        System.realloc(ptr, layout, new_size)
+    }
+}
+
+#[global_allocator]
+static GLOBAL: CountingAlloc = CountingAlloc;
+
+/// `f` の実行中に自スレッドで起きた確保の回数。
+fn allocations_during(f: impl FnOnce()) -> usize {
+    let before = ALLOCS.with(|c| c.get());
+    f();
+    ALLOCS.with(|c| c.get()) - before
+}
+
+/// クリックの "+N" 相当: バッファを借りて数字を書き込む。
+fn spawn_number(pool: &mut ParticlePool, n: u32) {
+    use std::fmt::Write;
+    let mut text = pool.text_buf();
+    let _ = write!(text, "+{n}");
+    pool.spawn(Particle {
+        text: Cow::Owned(text),
+        col_offset: 0,
+        life: 8,
+        max_life: 8,
+        style: ParticleStyle::Click,
+        row_offset: 0,
+        panel: ParticlePanel::Cookie,
+        amount: n as f64,
+    });
+}
+
+#[test]
+fn pool_steady_state_is_allocation_free() {
+    let mut pool = ParticlePool::new(MAX_PARTICLES);
+    // 1 tick に 5 個 (寿命 8) なので、上限超えの追い出しと寿命切れの
+    // 両方が起きる。
+    let burst = |pool: &mut ParticlePool, i: u32| {
+        for _ in 0..5 {
+            spawn_number(pool, 100_000 + i);
+        }
+        pool.tick(1);
+    };
+    // 暖機: 同じ負荷でバッファの本数が行き渡るまで回す。
+    for i in 0..100 {
+        burst(&mut pool, i);
+    }
+
+    let allocs = allocations_during(|| {
+        for i in 0..1_000 {
+            burst(&mut pool, i);
+        }
+    });
+    assert_eq!(allocs, 0);
+    assert!(pool.len() <= MAX_PARTICLES);
+}
+
+#[test]
+fn celebration_bursts_do_not_allocate() {
+    let mut state = CookieState::new();
+    spawn_celebration(&mut state, MAX_PARTICLES as u32);
+
+    let allocs = allocations_during(|| {
+        for _ in 0..500 {
+            spawn_celebration(&mut state, 5);
+            state.particles.tick(3);
+        }
+    });
+    assert_eq!(allocs, 0);
+    assert!(state.particles.len() <= MAX_PARTICLES);
+}